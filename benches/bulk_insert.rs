@@ -0,0 +1,76 @@
+//! Compares `insert_records` against a loop of `insert_record` on an
+//! initial load.
+//!
+//! Run with `cargo bench --bench bulk_insert`.
+
+use std::time::{Duration, Instant};
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::{CompilerInput, ContextMeta, MemoryRecord, SemanticTuple};
+
+const CONTEXTS: usize = 1_000;
+const TUPLES_PER_CONTEXT: usize = 100;
+const ROUNDS: u32 = 5;
+
+fn build_records() -> Vec<MemoryRecord> {
+    (0..CONTEXTS)
+        .map(|c| {
+            let tuples = (0..TUPLES_PER_CONTEXT)
+                .map(|t| SemanticTuple {
+                    subject: format!("concept_{}", (c * 7 + t) % 20_000),
+                    predicate: format!("pred_{}", t % 20),
+                    object: format!("concept_{}", (c * 13 + t * 3) % 20_000),
+                    confidence: 0.5 + (t % 50) as f64 / 100.0,
+                    ..Default::default()
+                })
+                .collect();
+            let input = CompilerInput {
+                utterance: None,
+                tuples,
+                context: ContextMeta {
+                    event_time: format!("2026-02-18T{:02}:{:02}:00Z", c / 60 % 24, c % 60)
+                        .parse()
+                        .unwrap(),
+                    source: "bench".to_string(),
+                    scope: format!("scope_{}", c),
+                    agent_id: None,
+                    session_id: None,
+                    metadata: None,
+                },
+            };
+            compile(&input).record
+        })
+        .collect()
+}
+
+fn time(label: &str, records: &[MemoryRecord], load: fn(Vec<MemoryRecord>) -> MemoryStore) {
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        let batch = records.to_vec();
+        let start = Instant::now();
+        let store = load(batch);
+        total += start.elapsed();
+        assert!(store.relation_count() > 0);
+    }
+    println!("{:<6} {:?} per load", label, total / ROUNDS);
+}
+
+fn main() {
+    let records = build_records();
+    let relations: usize = records.iter().map(|r| r.relations.len()).sum();
+    println!("{} records, {} relations", records.len(), relations);
+
+    time("loop", &records, |batch| {
+        let mut store = MemoryStore::new();
+        for record in batch {
+            store.insert_record(record);
+        }
+        store
+    });
+    time("bulk", &records, |batch| {
+        let mut store = MemoryStore::new();
+        store.insert_records(batch);
+        store
+    });
+}
@@ -0,0 +1,76 @@
+//! Compares JSON and binary snapshot size and load time.
+//!
+//! Run with `cargo bench --bench snapshot`.
+
+use std::time::Instant;
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::{CompilerInput, ContextMeta, SemanticTuple};
+
+const CONTEXTS: usize = 200;
+const TUPLES_PER_CONTEXT: usize = 100;
+const ROUNDS: u32 = 5;
+
+fn build_store() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    for c in 0..CONTEXTS {
+        let tuples = (0..TUPLES_PER_CONTEXT)
+            .map(|t| SemanticTuple {
+                subject: format!("concept_{}", (c * 7 + t) % 1000),
+                predicate: format!("pred_{}", t % 20),
+                object: format!("concept_{}", (c * 13 + t * 3) % 1000),
+                confidence: 0.5 + (t % 50) as f64 / 100.0,
+                ..Default::default()
+            })
+            .collect();
+        let input = CompilerInput {
+            utterance: None,
+            tuples,
+            context: ContextMeta {
+                event_time: format!("2026-02-18T{:02}:{:02}:00Z", c / 60, c % 60)
+                    .parse()
+                    .unwrap(),
+                source: "bench".to_string(),
+                scope: format!("scope_{}", c),
+                agent_id: None,
+                session_id: None,
+                metadata: None,
+            },
+        };
+        store.insert_record(compile(&input).record);
+    }
+    store
+}
+
+fn time_load(label: &str, bytes: &[u8], load: fn(&[u8]) -> MemoryStore) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let store = load(bytes);
+        assert!(store.relation_count() > 0);
+    }
+    let per_load = start.elapsed() / ROUNDS;
+    println!(
+        "{:<6} {:>10} bytes   load {:?}",
+        label,
+        bytes.len(),
+        per_load
+    );
+}
+
+fn main() {
+    let store = build_store();
+    println!(
+        "store: {} concepts, {} episodes",
+        store.concept_count(),
+        store.relation_count()
+    );
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+
+    time_load("json", &json, |b| MemoryStore::load_json(b).unwrap());
+    time_load("binary", &binary, |b| MemoryStore::load_binary(b).unwrap());
+}
@@ -0,0 +1,118 @@
+//! Reports the heap held by a synthetic store of one million episodes.
+//!
+//! Run with `cargo bench --bench store_memory`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zero_memory::compiler::hasher;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+/// Tracks bytes currently allocated through the global allocator.
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const CONTEXTS: usize = 10_000;
+const EPISODES_PER_CONTEXT: usize = 100;
+const CONCEPTS: usize = 50_000;
+const PREDICATES: usize = 40;
+
+fn record(c: usize) -> MemoryRecord {
+    let meta = ContextMeta {
+        event_time: format!("2026-02-18T{:02}:{:02}:00Z", c / 60 % 24, c % 60)
+            .parse()
+            .unwrap(),
+        source: "bench".to_string(),
+        scope: format!("scope_{}", c),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    let context = ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    };
+    let label = |i: usize| format!("concept_{}", i % CONCEPTS);
+    let mut concepts = Vec::new();
+    let relations = (0..EPISODES_PER_CONTEXT)
+        .map(|t| {
+            let (s, o) = (label(c * 7 + t), label(c * 13 + t * 3 + 1));
+            let predicate = format!("predicate_number_{}", t % PREDICATES);
+            let fact = hasher::fact_hash(&s, &predicate, &o);
+            for l in [&s, &o] {
+                concepts.push(ConceptNode {
+                    hash: hasher::concept_hash(l),
+                    label: l.clone(),
+                    aliases: vec![],
+                    confidence: 0.9,
+                    created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+                    updated_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+                    display_label: None,
+                });
+            }
+            RelationNode {
+                episode_hash: hasher::episode_hash(&fact, &context.hash),
+                fact_hash: fact,
+                subject_hash: hasher::concept_hash(&s),
+                predicate: predicate.into(),
+                object_hash: hasher::concept_hash(&o),
+                confidence: 0.9,
+                context_hash: context.hash.clone(),
+                created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+                polarity: Polarity::Positive,
+                valid_from: None,
+                valid_to: None,
+                subject_span: None,
+                object_span: None,
+                evidence_span: None,
+            }
+        })
+        .collect();
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts,
+        relations,
+        context,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+fn main() {
+    let before = LIVE.load(Ordering::Relaxed);
+    let mut store = MemoryStore::new();
+    for c in 0..CONTEXTS {
+        store.insert_record(record(c));
+    }
+    let held = LIVE.load(Ordering::Relaxed) - before;
+
+    println!(
+        "store: {} concepts, {} facts, {} episodes",
+        store.concept_count(),
+        store.fact_count(),
+        store.relation_count()
+    );
+    println!(
+        "heap held: {:.1} MiB ({} bytes/episode)",
+        held as f64 / (1024.0 * 1024.0),
+        held / store.relation_count()
+    );
+}
@@ -0,0 +1,333 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::types::*;
+
+/// Why [`value_to_record`] or [`value_to_input`] rejected a value: what
+/// was wrong, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertError {
+    /// Field path to the offending value, e.g. `relations[3].fact_hash`;
+    /// empty for the value itself.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// `record` as a runtime value: structs become maps keyed by field name,
+/// hashes 64-char hex strings, vectors lists, and absent optional fields
+/// are left out. The same layout as the record's JSON serialization, so
+/// the value can also be handed to anything that reads that.
+pub fn record_to_value(record: &MemoryRecord) -> Value {
+    serde_json::to_value(record).expect("records serialize to JSON")
+}
+
+/// Read a record back from a value laid out as [`record_to_value`] writes
+/// it. Optional fields may be missing or null; a record with no
+/// `schema_version` is taken as version 1, like its JSON would be.
+pub fn value_to_record(value: &Value) -> Result<MemoryRecord, ConvertError> {
+    let fields = Fields::of(value, String::new())?;
+    Ok(MemoryRecord {
+        schema_version: fields.optional("schema_version")?.unwrap_or(1),
+        concepts: fields.list("concepts", concept)?,
+        relations: fields.list("relations", relation)?,
+        context: context(fields.required("context")?, fields.path("context"))?,
+        proof: fields.optional("proof")?,
+        prev: fields.optional_hash("prev")?.map(RecordHash),
+        namespace: fields.optional("namespace")?,
+    })
+}
+
+/// `input` as a runtime value, laid out like its JSON serialization; see
+/// [`record_to_value`].
+pub fn input_to_value(input: &CompilerInput) -> Value {
+    serde_json::to_value(input).expect("compiler inputs serialize to JSON")
+}
+
+/// Read a compiler input back from a value laid out as
+/// [`input_to_value`] writes it.
+pub fn value_to_input(value: &Value) -> Result<CompilerInput, ConvertError> {
+    let fields = Fields::of(value, String::new())?;
+    Ok(CompilerInput {
+        utterance: fields.optional("utterance")?,
+        tuples: fields.list("tuples", tuple)?,
+        context: meta(fields.required("context")?, fields.path("context"))?,
+    })
+}
+
+fn concept(value: &Value, path: String) -> Result<ConceptNode, ConvertError> {
+    let fields = Fields::of(value, path)?;
+    Ok(ConceptNode {
+        hash: ConceptHash(fields.hash("hash")?),
+        label: fields.leaf("label")?,
+        aliases: fields.leaf("aliases")?,
+        confidence: fields.leaf("confidence")?,
+        created_at: fields.leaf("created_at")?,
+        updated_at: fields.leaf("updated_at")?,
+        display_label: fields.optional("display_label")?,
+    })
+}
+
+fn relation(value: &Value, path: String) -> Result<RelationNode, ConvertError> {
+    let fields = Fields::of(value, path)?;
+    Ok(RelationNode {
+        fact_hash: FactHash(fields.hash("fact_hash")?),
+        episode_hash: EpisodeHash(fields.hash("episode_hash")?),
+        subject_hash: ConceptHash(fields.hash("subject_hash")?),
+        predicate: fields.leaf::<String>("predicate")?.into(),
+        object_hash: ConceptHash(fields.hash("object_hash")?),
+        confidence: fields.leaf("confidence")?,
+        context_hash: ContextHash(fields.hash("context_hash")?),
+        created_at: fields.leaf("created_at")?,
+        polarity: fields.optional("polarity")?.unwrap_or_default(),
+        valid_from: fields.optional("valid_from")?,
+        valid_to: fields.optional("valid_to")?,
+        subject_span: fields.optional("subject_span")?,
+        object_span: fields.optional("object_span")?,
+        evidence_span: fields.optional("evidence_span")?,
+    })
+}
+
+fn context(value: &Value, path: String) -> Result<ContextNode, ConvertError> {
+    let fields = Fields::of(value, path)?;
+    Ok(ContextNode {
+        hash: ContextHash(fields.hash("hash")?),
+        meta: meta(fields.required("meta")?, fields.path("meta"))?,
+    })
+}
+
+fn meta(value: &Value, path: String) -> Result<ContextMeta, ConvertError> {
+    let fields = Fields::of(value, path)?;
+    Ok(ContextMeta {
+        event_time: fields.leaf("event_time")?,
+        source: fields.leaf("source")?,
+        scope: fields.leaf("scope")?,
+        agent_id: fields.optional("agent_id")?,
+        session_id: fields.optional("session_id")?,
+        metadata: fields.optional("metadata")?,
+    })
+}
+
+fn tuple(value: &Value, path: String) -> Result<SemanticTuple, ConvertError> {
+    let fields = Fields::of(value, path)?;
+    Ok(SemanticTuple {
+        subject: fields.leaf("subject")?,
+        predicate: fields.leaf("predicate")?,
+        object: fields.leaf("object")?,
+        confidence: fields.leaf("confidence")?,
+        polarity: fields.optional("polarity")?.unwrap_or_default(),
+        valid_from: fields.optional("valid_from")?,
+        valid_to: fields.optional("valid_to")?,
+        qualifiers: fields.optional("qualifiers")?.unwrap_or_default(),
+        subject_span: fields.optional("subject_span")?,
+        object_span: fields.optional("object_span")?,
+        evidence_span: fields.optional("evidence_span")?,
+    })
+}
+
+/// The fields of a map value, and the path to it for errors.
+struct Fields<'a> {
+    map: &'a Map<String, Value>,
+    path: String,
+}
+
+impl<'a> Fields<'a> {
+    fn of(value: &'a Value, path: String) -> Result<Self, ConvertError> {
+        match value {
+            Value::Object(map) => Ok(Self { map, path }),
+            _ => Err(error(path, "expected a map")),
+        }
+    }
+
+    fn path(&self, name: &str) -> String {
+        if self.path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.path, name)
+        }
+    }
+
+    fn required(&self, name: &str) -> Result<&'a Value, ConvertError> {
+        match self.map.get(name) {
+            Some(Value::Null) | None => Err(error(self.path(name), "missing")),
+            Some(value) => Ok(value),
+        }
+    }
+
+    /// A required field holding anything serde can read as `T`.
+    fn leaf<T: DeserializeOwned>(&self, name: &str) -> Result<T, ConvertError> {
+        let value = self.required(name)?;
+        T::deserialize(value).map_err(|e| error(self.path(name), e))
+    }
+
+    /// Like [`leaf`](Self::leaf), with missing and null both `None`.
+    fn optional<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, ConvertError> {
+        match self.map.get(name) {
+            Some(Value::Null) | None => Ok(None),
+            Some(_) => self.leaf(name).map(Some),
+        }
+    }
+
+    fn hash(&self, name: &str) -> Result<[u8; 32], ConvertError> {
+        let path = self.path(name);
+        match self.required(name)? {
+            Value::String(text) if text.len() == 64 => {
+                let mut hash = [0u8; 32];
+                hex::decode_to_slice(text, &mut hash)
+                    .map_err(|_| error(path, "expected 64 hex chars"))?;
+                Ok(hash)
+            }
+            _ => Err(error(path, "expected 64 hex chars")),
+        }
+    }
+
+    fn optional_hash(&self, name: &str) -> Result<Option<[u8; 32]>, ConvertError> {
+        match self.map.get(name) {
+            Some(Value::Null) | None => Ok(None),
+            Some(_) => self.hash(name).map(Some),
+        }
+    }
+
+    fn list<T>(
+        &self,
+        name: &str,
+        item: impl Fn(&Value, String) -> Result<T, ConvertError>,
+    ) -> Result<Vec<T>, ConvertError> {
+        let path = self.path(name);
+        match self.required(name)? {
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, value)| item(value, format!("{}[{}]", path, i)))
+                .collect(),
+            _ => Err(error(path, "expected a list")),
+        }
+    }
+}
+
+fn error(path: String, message: impl fmt::Display) -> ConvertError {
+    ConvertError {
+        path,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::compiler::emitter::compile;
+
+    fn input() -> CompilerInput {
+        CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .utterance("Alice likes tea")
+            .tuple("Alice", "likes", "Tea", 0.9)
+            .tuple("Tea", "is_a", "Drink", 0.7)
+            .build()
+            .unwrap()
+    }
+
+    fn record_value() -> Value {
+        record_to_value(&compile(&input()).record)
+    }
+
+    fn rejection(value: &Value) -> String {
+        value_to_record(value).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let record = compile(&input()).record;
+        let value = record_to_value(&record);
+        assert_eq!(
+            value["relations"][0]["fact_hash"],
+            json!(record.relations[0].fact_hash.to_string())
+        );
+        let back = value_to_record(&value).unwrap();
+        assert_eq!(back.hash(), record.hash());
+        assert_eq!(record_to_value(&back), value);
+    }
+
+    #[test]
+    fn inputs_round_trip() {
+        let value = input_to_value(&input());
+        assert_eq!(input_to_value(&value_to_input(&value).unwrap()), value);
+    }
+
+    #[test]
+    fn bad_hashes_name_their_field() {
+        let mut value = record_value();
+        value["relations"][1]["fact_hash"] = json!("abc");
+        assert_eq!(
+            rejection(&value),
+            "relations[1].fact_hash: expected 64 hex chars"
+        );
+
+        let mut value = record_value();
+        value["context"]["hash"] = json!("z".repeat(64));
+        assert_eq!(rejection(&value), "context.hash: expected 64 hex chars");
+
+        let mut value = record_value();
+        value["prev"] = json!(7);
+        assert_eq!(rejection(&value), "prev: expected 64 hex chars");
+    }
+
+    #[test]
+    fn missing_and_mistyped_fields_name_their_field() {
+        let mut value = record_value();
+        value["concepts"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("label");
+        assert_eq!(rejection(&value), "concepts[0].label: missing");
+
+        let mut value = record_value();
+        value["context"]["meta"]["scope"] = Value::Null;
+        assert_eq!(rejection(&value), "context.meta.scope: missing");
+
+        let mut value = record_value();
+        value["relations"] = json!({});
+        assert_eq!(rejection(&value), "relations: expected a list");
+
+        let mut value = record_value();
+        value["relations"][0]["confidence"] = json!("high");
+        assert!(rejection(&value).starts_with("relations[0].confidence: invalid type"));
+
+        assert_eq!(rejection(&json!([])), "expected a map");
+
+        let mut value = input_to_value(&input());
+        value["tuples"][1]["polarity"] = json!("maybe");
+        assert!(value_to_input(&value)
+            .unwrap_err()
+            .to_string()
+            .starts_with("tuples[1].polarity: unknown variant"));
+    }
+
+    #[test]
+    fn optional_fields_may_be_missing_or_null() {
+        let mut value = record_value();
+        value.as_object_mut().unwrap().remove("schema_version");
+        value["relations"][0]["valid_from"] = Value::Null;
+        value["relations"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("polarity");
+        let record = value_to_record(&value).unwrap();
+        assert_eq!(record.schema_version, 1);
+        assert_eq!(record.relations[0].polarity, Polarity::Positive);
+    }
+}
@@ -1,2 +1,9 @@
+pub mod convert;
+
 #[cfg(feature = "openclaw")]
 pub mod openclaw;
+
+#[cfg(feature = "remote")]
+pub mod remote;
+
+pub mod verify;
@@ -1,31 +1,64 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::ops::Bound;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+use async_trait::async_trait;
 use sha2::{Digest, Sha256};
 use zero_openclaw::error::GatewayError;
 use zero_openclaw::runtime::{parse_graph, GraphInterpreter};
 use zero_openclaw::Value;
 
-use crate::runtime_trait::MemoryRuntime;
+use crate::runtime_trait::{block_on, AsyncMemoryRuntime, InspectValue, MemoryRuntime, TextValue};
+
+/// Error returned by [`OpenclawAdapter`].
+#[derive(Debug)]
+pub enum OpenclawError {
+    /// 0-openclaw failed to parse or execute a graph.
+    Gateway(GatewayError),
+    /// A graph ran longer than the adapter's
+    /// [execution timeout](OpenclawAdapterBuilder::execution_timeout).
+    Timeout { limit: Duration },
+}
+
+impl fmt::Display for OpenclawError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenclawError::Gateway(e) => e.fmt(f),
+            OpenclawError::Timeout { limit } => {
+                write!(f, "graph execution exceeded its {:?} timeout", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OpenclawError {}
+
+impl From<GatewayError> for OpenclawError {
+    fn from(e: GatewayError) -> Self {
+        OpenclawError::Gateway(e)
+    }
+}
 
 /// Adapter that delegates 0-memory runtime operations to 0-openclaw's
 /// `GraphInterpreter`.
 ///
-/// # Sync bridge
+/// # Async and sync use
 ///
 /// The interpreter's methods are `async` (it uses `tokio::sync::RwLock`
-/// internally), but [`MemoryRuntime`] is a sync trait.  The adapter bridges
-/// the gap via [`block_on`](Self::block_on):
+/// internally). The adapter implements [`AsyncMemoryRuntime`] by awaiting
+/// them directly, which is what async callers should use.
 ///
-/// * If a tokio runtime is already active (e.g. the caller is an async
-///   test or an async application), it uses `block_in_place` + the
-///   existing runtime handle — avoiding the "cannot start a runtime
-///   from within a runtime" panic.  **The runtime must be multi-threaded**
-///   (`tokio::runtime::Builder::new_multi_thread`); calling from a
-///   current-thread runtime will panic.
-/// * Otherwise it spins up a lightweight current-thread runtime per call.
+/// It also implements the sync [`MemoryRuntime`] by blocking on those
+/// same futures:
 ///
-/// For fully-async callers, consider writing an `AsyncMemoryRuntime` trait
-/// or calling the interpreter directly.
+/// * If a multi-threaded tokio runtime is active, it uses
+///   `block_in_place` + the existing runtime handle, avoiding the "cannot
+///   start a runtime from within a runtime" panic.
+/// * If a current-thread runtime is active, which cannot block in place,
+///   it runs the call on a lightweight runtime in a scoped thread.
+/// * Otherwise it spins up a lightweight current-thread runtime per call.
 ///
 /// # Thread safety
 ///
@@ -34,15 +67,50 @@ use crate::runtime_trait::MemoryRuntime;
 /// and safe to share across threads.  The internal state store is
 /// session-scoped (keyed by a string ID), so concurrent calls with
 /// different keys are non-contending.
+///
+/// # Configuration
+///
+/// [`new`](Self::new) uses a default interpreter and no time limit.
+/// [`with_interpreter`](Self::with_interpreter) takes one configured by
+/// the caller, and [`builder`](Self::builder) also sets an execution
+/// timeout.
+///
+/// # Listing state
+///
+/// The interpreter cannot enumerate its state, so the adapter remembers
+/// the keys saved through it; `list_state_keys` lists those still holding
+/// a value. State written by graphs themselves is not listed.
 pub struct OpenclawAdapter {
     interpreter: GraphInterpreter,
+    execution_timeout: Option<Duration>,
+    saved_keys: Mutex<BTreeSet<String>>,
 }
 
 impl OpenclawAdapter {
+    /// An adapter over a default interpreter, with no execution timeout.
     pub fn new() -> Self {
-        Self {
-            interpreter: GraphInterpreter::default(),
-        }
+        Self::builder().build()
+    }
+
+    /// An adapter over an interpreter configured by the caller.
+    pub fn with_interpreter(interpreter: GraphInterpreter) -> Self {
+        Self::builder().interpreter(interpreter).build()
+    }
+
+    pub fn builder() -> OpenclawAdapterBuilder {
+        OpenclawAdapterBuilder::default()
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let saved_keys = self
+            .saved_keys
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        saved_keys
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|key| key.starts_with(prefix))
+            .cloned()
+            .collect()
     }
 }
 
@@ -52,30 +120,77 @@ impl Default for OpenclawAdapter {
     }
 }
 
-impl OpenclawAdapter {
-    /// Drive a future to completion, handling both sync and async contexts.
+/// Builds an [`OpenclawAdapter`]; see [`OpenclawAdapter::builder`].
+#[derive(Default)]
+pub struct OpenclawAdapterBuilder {
+    interpreter: Option<GraphInterpreter>,
+    execution_timeout: Option<Duration>,
+}
+
+impl OpenclawAdapterBuilder {
+    /// Run graphs and keep state on `interpreter` instead of a default
+    /// one.
+    pub fn interpreter(mut self, interpreter: GraphInterpreter) -> Self {
+        self.interpreter = Some(interpreter);
+        self
+    }
+
+    /// Fail any `execute_graph` call, parsing included, that takes longer
+    /// than `limit` with [`OpenclawError::Timeout`].
     ///
-    /// If called from within an active tokio runtime, uses `block_in_place`
-    /// (requires the `rt-multi-thread` tokio feature).  Otherwise creates a
-    /// lightweight current-thread runtime for the call.
-    fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
-        match tokio::runtime::Handle::try_current() {
-            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
-            Err(_) => {
-                let rt = tokio::runtime::Builder::new_current_thread()
-                    .enable_all()
-                    .build()
-                    .expect("failed to create tokio runtime for OpenclawAdapter");
-                rt.block_on(future)
-            }
+    /// The execution is cancelled if it is still waiting when the limit
+    /// passes. The interpreter can only be cancelled where it awaits, so a
+    /// graph that runs to the end without yielding fails once it finishes
+    /// late, its outputs discarded. The timer needs a tokio runtime with
+    /// time enabled, which the adapter's own sync bridge provides.
+    pub fn execution_timeout(mut self, limit: Duration) -> Self {
+        self.execution_timeout = Some(limit);
+        self
+    }
+
+    pub fn build(self) -> OpenclawAdapter {
+        OpenclawAdapter {
+            interpreter: self.interpreter.unwrap_or_default(),
+            execution_timeout: self.execution_timeout,
+            saved_keys: Mutex::new(BTreeSet::new()),
         }
     }
 }
 
-impl MemoryRuntime for OpenclawAdapter {
+impl TextValue for Value {
+    fn from_text(text: String) -> Self {
+        Value::String(text)
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+/// Maps, strings, and hashes, which is what compiled graphs produce;
+/// other values read as `null`.
+impl InspectValue for Value {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::String(text) => serde_json::Value::String(text.clone()),
+            Value::Hash(hash) => serde_json::Value::String(hex::encode(hash)),
+            Value::Map(map) => map
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_json()))
+                .collect(),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncMemoryRuntime for OpenclawAdapter {
     type Value = Value;
     type Hash = [u8; 32];
-    type Error = GatewayError;
+    type Error = OpenclawError;
 
     fn hash(&self, input: &[u8]) -> [u8; 32] {
         let result = Sha256::digest(input);
@@ -84,23 +199,32 @@ impl MemoryRuntime for OpenclawAdapter {
         hash
     }
 
-    fn execute_graph(
+    async fn execute_graph(
         &self,
         graph_source: &str,
         inputs: HashMap<String, Self::Value>,
     ) -> Result<HashMap<String, Self::Value>, Self::Error> {
-        let graph = parse_graph(graph_source)?;
-        let result = self.block_on(self.interpreter.execute(&graph, inputs))?;
-        Ok(result.outputs)
+        let started = Instant::now();
+        let execution = async {
+            let graph = parse_graph(graph_source)?;
+            let result = self.interpreter.execute(&graph, inputs).await?;
+            Ok(result.outputs)
+        };
+        let Some(limit) = self.execution_timeout else {
+            return execution.await;
+        };
+        match tokio::time::timeout(limit, execution).await {
+            Ok(outputs) if started.elapsed() <= limit => outputs,
+            _ => Err(OpenclawError::Timeout { limit }),
+        }
     }
 
     /// Load persisted state by key.
     ///
     /// The underlying `GraphInterpreter::load_state` is infallible and
     /// returns `Value::Null` for missing keys, which we map to `None`.
-    fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
-        let value = self.block_on(self.interpreter.load_state(key));
-        match value {
+    async fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        match self.interpreter.load_state(key).await {
             Value::Null => Ok(None),
             other => Ok(Some(other)),
         }
@@ -110,15 +234,80 @@ impl MemoryRuntime for OpenclawAdapter {
     ///
     /// The underlying `GraphInterpreter::save_state` takes ownership of the
     /// value and is infallible, so we clone from the `&Value` reference.
-    fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
-        self.block_on(self.interpreter.save_state(key, value.clone()));
+    /// Saving `Value::Null` reads back as missing, so it drops the key from
+    /// [`list_state_keys`](AsyncMemoryRuntime::list_state_keys).
+    async fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        self.interpreter.save_state(key, value.clone()).await;
+        let mut saved_keys = self
+            .saved_keys
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if matches!(value, Value::Null) {
+            saved_keys.remove(key);
+        } else {
+            saved_keys.insert(key.to_string());
+        }
         Ok(())
     }
+
+    async fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        Ok(Some(self.keys_with_prefix(prefix)))
+    }
+}
+
+impl MemoryRuntime for OpenclawAdapter {
+    type Value = Value;
+    type Hash = [u8; 32];
+    type Error = OpenclawError;
+
+    fn hash(&self, input: &[u8]) -> [u8; 32] {
+        AsyncMemoryRuntime::hash(self, input)
+    }
+
+    fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        block_on(AsyncMemoryRuntime::execute_graph(
+            self,
+            graph_source,
+            inputs,
+        ))
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        block_on(AsyncMemoryRuntime::load_state(self, key))
+    }
+
+    fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        block_on(AsyncMemoryRuntime::save_state(self, key, value))
+    }
+
+    /// Crosses the sync bridge once for the whole batch.
+    fn load_state_many(&self, keys: &[String]) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        block_on(AsyncMemoryRuntime::load_state_many(self, keys))
+    }
+
+    /// Crosses the sync bridge once for the whole batch.
+    fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        block_on(AsyncMemoryRuntime::save_state_many(self, entries))
+    }
+
+    fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        Ok(Some(self.keys_with_prefix(prefix)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::{GraphInterpreter, MemoryRuntime, OpenclawAdapter, OpenclawError, Value};
+    use crate::compiler::emitter::compile;
+    use crate::runtime_trait::StateNamespace;
+    use crate::types::{CompilerInput, ContextMeta, SemanticTuple};
 
     #[test]
     fn hash_determinism() {
@@ -149,6 +338,72 @@ mod tests {
         assert_eq!(loaded, Some(Value::String("stored".into())));
     }
 
+    #[test]
+    fn namespaces_keep_agents_apart() {
+        let adapter = OpenclawAdapter::new();
+        let a = adapter.namespaced(StateNamespace::new().agent_id("a"));
+        let b = adapter.namespaced(StateNamespace::new().agent_id("b"));
+        a.save_state("working_set", &Value::String("from a".into()))
+            .unwrap();
+        b.save_state("working_set", &Value::String("from b".into()))
+            .unwrap();
+        b.save_state("scratch", &Value::Null).unwrap();
+
+        assert_eq!(
+            a.load_state("working_set").unwrap(),
+            Some(Value::String("from a".into()))
+        );
+        assert_eq!(
+            b.load_state("working_set").unwrap(),
+            Some(Value::String("from b".into()))
+        );
+        assert_eq!(
+            a.list_state_keys("").unwrap(),
+            Some(vec!["working_set".to_string()])
+        );
+        assert_eq!(
+            adapter
+                .list_state_keys("agent/")
+                .unwrap()
+                .map(|keys| keys.len()),
+            Some(2)
+        );
+    }
+
+    fn batch_round_trip() {
+        let adapter = OpenclawAdapter::new();
+        let entries: Vec<(String, Value)> = (0..500)
+            .map(|i| (format!("key_{}", i), Value::String(format!("value_{}", i))))
+            .collect();
+        adapter.save_state_many(&entries).unwrap();
+
+        let keys: Vec<String> = [0, 1, 250, 499, 500]
+            .iter()
+            .map(|i| format!("key_{}", i))
+            .collect();
+        let loaded = adapter.load_state_many(&keys).unwrap();
+        assert_eq!(loaded[0], Some(Value::String("value_0".into())));
+        assert_eq!(loaded[2], Some(Value::String("value_250".into())));
+        assert_eq!(loaded[3], Some(Value::String("value_499".into())));
+        assert_eq!(loaded[4], None);
+        assert_eq!(adapter.load_state("key_1").unwrap(), loaded[1]);
+    }
+
+    #[test]
+    fn batch_round_trip_outside_tokio() {
+        batch_round_trip();
+    }
+
+    #[tokio::test]
+    async fn batch_round_trip_inside_current_thread_runtime() {
+        batch_round_trip();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn batch_round_trip_inside_multi_thread_runtime() {
+        batch_round_trip();
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn adapter_works_inside_tokio_runtime() {
         let adapter = OpenclawAdapter::new();
@@ -158,4 +413,130 @@ mod tests {
         let loaded = adapter.load_state("async_key").unwrap();
         assert_eq!(loaded, Some(Value::String("from_async".into())));
     }
+
+    // `#[tokio::test]` defaults to a current-thread runtime, where
+    // `block_in_place` would panic.
+    #[tokio::test]
+    async fn state_round_trip_inside_current_thread_runtime() {
+        let adapter = OpenclawAdapter::new();
+        assert_eq!(adapter.load_state("current_key").unwrap(), None);
+        adapter
+            .save_state("current_key", &Value::String("from_current".into()))
+            .unwrap();
+        let loaded = adapter.load_state("current_key").unwrap();
+        assert_eq!(loaded, Some(Value::String("from_current".into())));
+    }
+
+    #[tokio::test]
+    async fn execute_graph_inside_current_thread_runtime() {
+        let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .tuple("Agent", "needs", "Memory", 0.9)
+            .build()
+            .unwrap();
+        let outputs = OpenclawAdapter::new()
+            .execute_graph(&compile(&input).graph_text, HashMap::new())
+            .unwrap();
+        assert!(outputs.contains_key("output"));
+    }
+
+    #[tokio::test]
+    async fn execute_graph_errors_inside_current_thread_runtime() {
+        let adapter = OpenclawAdapter::new();
+        assert!(adapter
+            .execute_graph("not a graph", HashMap::new())
+            .is_err());
+    }
+
+    /// The graph of a record with a few hundred concepts and relations.
+    fn large_graph() -> String {
+        let tuples: Vec<SemanticTuple> = (0..200)
+            .map(|i| {
+                let subject = format!("concept_{}", i);
+                let object = format!("concept_{}", (i * 7 + 1) % 200);
+                SemanticTuple::from((subject.as_str(), "relates_to", object.as_str(), 0.9))
+            })
+            .collect();
+        let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .tuples(tuples)
+            .build()
+            .unwrap();
+        compile(&input).graph_text
+    }
+
+    fn times_out_with_a_tiny_limit() {
+        let adapter = OpenclawAdapter::builder()
+            .execution_timeout(Duration::from_nanos(1))
+            .build();
+        let err = adapter
+            .execute_graph(&large_graph(), HashMap::new())
+            .unwrap_err();
+        assert!(matches!(err, OpenclawError::Timeout { limit } if limit.as_nanos() == 1));
+        assert_eq!(err.to_string(), "graph execution exceeded its 1ns timeout");
+    }
+
+    #[test]
+    fn execution_times_out_outside_tokio() {
+        times_out_with_a_tiny_limit();
+    }
+
+    #[tokio::test]
+    async fn execution_times_out_inside_current_thread_runtime() {
+        times_out_with_a_tiny_limit();
+    }
+
+    #[test]
+    fn generous_timeouts_let_graphs_finish() {
+        let adapter = OpenclawAdapter::builder()
+            .interpreter(GraphInterpreter::default())
+            .execution_timeout(Duration::from_secs(60))
+            .build();
+        let outputs = adapter
+            .execute_graph(&large_graph(), HashMap::new())
+            .unwrap();
+        assert!(outputs.contains_key("output"));
+        assert!(matches!(
+            adapter.execute_graph("not a graph", HashMap::new()),
+            Err(OpenclawError::Gateway(_))
+        ));
+    }
+
+    #[test]
+    fn injected_interpreters_are_used() {
+        let adapter = OpenclawAdapter::with_interpreter(GraphInterpreter::default());
+        adapter
+            .save_state("key", &Value::String("stored".into()))
+            .unwrap();
+        assert_eq!(
+            adapter.load_state("key").unwrap(),
+            Some(Value::String("stored".into()))
+        );
+    }
+
+    async fn async_state_round_trip() {
+        use crate::runtime_trait::AsyncMemoryRuntime;
+
+        let adapter = OpenclawAdapter::new();
+        let loaded = AsyncMemoryRuntime::load_state(&adapter, "nonexistent")
+            .await
+            .unwrap();
+        assert_eq!(loaded, None);
+
+        AsyncMemoryRuntime::save_state(&adapter, "key", &Value::String("stored".into()))
+            .await
+            .unwrap();
+        let loaded = AsyncMemoryRuntime::load_state(&adapter, "key")
+            .await
+            .unwrap();
+        assert_eq!(loaded, Some(Value::String("stored".into())));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn async_state_round_trip_multi_thread() {
+        async_state_round_trip().await;
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn async_state_round_trip_current_thread() {
+        async_state_round_trip().await;
+    }
 }
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::runtime_trait::MemoryRuntime;
+
+/// Error returned by [`RemoteRuntime`].
+#[derive(Debug)]
+pub enum RemoteError {
+    /// The service answered with a non-success status.
+    Http { status: u16, body: String },
+    /// The request never got an answer: bad URL, refused connection,
+    /// TLS failure, timeout.
+    Transport(String),
+    /// A success response whose body was not the expected JSON.
+    Decode(io::Error),
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteError::Http { status, body } if body.is_empty() => {
+                write!(f, "remote runtime returned HTTP {}", status)
+            }
+            RemoteError::Http { status, body } => {
+                write!(f, "remote runtime returned HTTP {}: {}", status, body)
+            }
+            RemoteError::Transport(message) => {
+                write!(f, "remote runtime unreachable: {}", message)
+            }
+            RemoteError::Decode(e) => write!(f, "bad response from remote runtime: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+impl From<ureq::Error> for RemoteError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => RemoteError::Http {
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(transport) => RemoteError::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// A [`MemoryRuntime`] served by a 0-openclaw interpreter running as an
+/// HTTP service.
+///
+/// * `execute_graph` POSTs `{graph_source, inputs}` to `/execute` and
+///   reads the outputs object back.
+/// * `load_state` GETs `/state/{key}`; a `404` means no value.
+/// * `save_state` PUTs the value to `/state/{key}`.
+/// * [`delete_state`](Self::delete_state) DELETEs `/state/{key}`; a `404`
+///   means there was nothing to delete.
+///
+/// Bodies are JSON, keys are percent-encoded into a single path segment,
+/// and an auth token, if given, is sent as `Authorization: Bearer …`.
+/// Hashing stays local (SHA-256, like the other runtimes).
+///
+/// Requests are made with a blocking client on the calling thread; from
+/// async code, call through `tokio::task::spawn_blocking`.
+#[derive(Clone)]
+pub struct RemoteRuntime {
+    agent: ureq::Agent,
+    base_url: String,
+    auth_header: Option<String>,
+}
+
+impl RemoteRuntime {
+    /// A runtime talking to the service at `base_url`, e.g.
+    /// `http://localhost:8080` or `https://host/openclaw/`.
+    pub fn new(base_url: &str, auth_token: Option<&str>) -> Self {
+        Self::with_agent(ureq::Agent::new(), base_url, auth_token)
+    }
+
+    /// Like [`new`](Self::new), sending requests through `agent`, e.g.
+    /// one built with timeouts or a proxy.
+    pub fn with_agent(agent: ureq::Agent, base_url: &str, auth_token: Option<&str>) -> Self {
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth_header: auth_token.map(|token| format!("Bearer {}", token)),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Delete the value under `key`. Deleting a key with no value is not
+    /// an error.
+    pub fn delete_state(&self, key: &str) -> Result<(), RemoteError> {
+        match self.request("DELETE", &state_path(key)).call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let request = self
+            .agent
+            .request(method, &format!("{}{}", self.base_url, path));
+        match &self.auth_header {
+            Some(header) => request.set("Authorization", header),
+            None => request,
+        }
+    }
+}
+
+impl fmt::Debug for RemoteRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Leaves out the token.
+        f.debug_struct("RemoteRuntime")
+            .field("base_url", &self.base_url)
+            .field("authenticated", &self.auth_header.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemoryRuntime for RemoteRuntime {
+    type Value = Value;
+    type Hash = [u8; 32];
+    type Error = RemoteError;
+
+    fn hash(&self, input: &[u8]) -> [u8; 32] {
+        Sha256::digest(input).into()
+    }
+
+    fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Value>,
+    ) -> Result<HashMap<String, Value>, RemoteError> {
+        let body = json!({ "graph_source": graph_source, "inputs": inputs });
+        self.request("POST", "/execute")
+            .send_json(body)?
+            .into_json()
+            .map_err(RemoteError::Decode)
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<Value>, RemoteError> {
+        match self.request("GET", &state_path(key)).call() {
+            Ok(response) => response.into_json().map(Some).map_err(RemoteError::Decode),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save_state(&self, key: &str, value: &Value) -> Result<(), RemoteError> {
+        self.request("PUT", &state_path(key)).send_json(value)?;
+        Ok(())
+    }
+}
+
+/// `/state/{key}`, with every byte of `key` outside the URL's unreserved
+/// set percent-encoded, `/` included, so namespaced keys stay one segment.
+fn state_path(key: &str) -> String {
+    let mut path = String::from("/state/");
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            path.push(byte as char);
+        } else {
+            path.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_keys_become_one_path_segment() {
+        assert_eq!(state_path("working_set"), "/state/working_set");
+        assert_eq!(
+            state_path("agent/a%2Fb/session/s 1/k"),
+            "/state/agent%2Fa%252Fb%2Fsession%2Fs%201%2Fk"
+        );
+        assert_eq!(state_path("é"), "/state/%C3%A9");
+    }
+
+    #[test]
+    fn debug_output_leaves_out_the_token() {
+        let runtime = RemoteRuntime::new("http://localhost:8080/", Some("s3cret"));
+        assert_eq!(runtime.base_url(), "http://localhost:8080");
+        assert!(!format!("{:?}", runtime).contains("s3cret"));
+    }
+}
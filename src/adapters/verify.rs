@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::compiler::hasher;
+use crate::compiler::integrity::{is_statement_label, statement_qualifiers};
+use crate::runtime_trait::MemoryRuntime;
+use crate::types::*;
+
+/// The record node whose stored hash a [`HashMismatch`] is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashedNode {
+    /// The record's context node.
+    Context,
+    /// The concept with this label.
+    Concept { label: String },
+    /// The fact hash of the relation with this episode hash.
+    Fact { episode: EpisodeHash },
+    /// The episode hash of a relation, as stored.
+    Episode { episode: EpisodeHash },
+}
+
+impl fmt::Display for HashedNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashedNode::Context => f.write_str("context"),
+            HashedNode::Concept { label } => write!(f, "concept {:?}", label),
+            HashedNode::Fact { episode } => write!(f, "fact of episode {}", episode),
+            HashedNode::Episode { episode } => write!(f, "episode {}", episode),
+        }
+    }
+}
+
+/// A hash the runtime computed differently from the one the record
+/// stores; see [`verify_hashes_with_runtime`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashMismatch {
+    pub node: HashedNode,
+    /// The record's hash, in hex.
+    pub stored: String,
+    /// The runtime's hash of the same input, in hex.
+    pub computed: String,
+}
+
+impl fmt::Display for HashMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} has hash {}, but the runtime computes {}",
+            self.node, self.stored, self.computed
+        )
+    }
+}
+
+impl std::error::Error for HashMismatch {}
+
+/// Check that `runtime` and the compiler agree on content addressing:
+/// recompute every hash in `record` with `runtime.hash`, over the same
+/// bytes the [`hasher`] hashes, and compare with the stored one.
+///
+/// Covers the context hash, each concept hash (from its label, in the
+/// record's namespace), and each relation's fact hash (from its endpoints'
+/// labels, predicate, and polarity) and episode hash. Episodes are hashed
+/// from the runtime's fact and context hashes, so one bad hash is reported
+/// once rather than again in every hash built on it. Relations whose
+/// endpoints the record lacks have no fact hash to recompute;
+/// [`MemoryRecord::validate`] reports those.
+///
+/// Returns every mismatch, the context first, then concepts and relations
+/// in record order.
+pub fn verify_hashes_with_runtime<R: MemoryRuntime>(
+    record: &MemoryRecord,
+    runtime: &R,
+) -> Result<(), Vec<HashMismatch>> {
+    let namespace = record.namespace.as_deref();
+    let mut mismatches = Vec::new();
+
+    let context = runtime.hash(&hasher::context_preimage(&record.context.meta));
+    compare(
+        &mut mismatches,
+        HashedNode::Context,
+        record.context.hash.as_bytes(),
+        context.as_ref(),
+    );
+
+    let mut labels: HashMap<&ConceptHash, &str> = HashMap::new();
+    for concept in &record.concepts {
+        // Statement concepts are hashed outside the namespace.
+        let namespace = if is_statement_label(&concept.label) {
+            None
+        } else {
+            namespace
+        };
+        let computed = runtime.hash(&hasher::concept_preimage(namespace, &concept.label));
+        compare(
+            &mut mismatches,
+            HashedNode::Concept {
+                label: concept.label.clone(),
+            },
+            concept.hash.as_bytes(),
+            computed.as_ref(),
+        );
+        labels.insert(&concept.hash, &concept.label);
+    }
+
+    let qualifiers = statement_qualifiers(record, &labels);
+    for relation in &record.relations {
+        let episode = &relation.episode_hash;
+        let fact = match (
+            labels.get(&relation.subject_hash),
+            labels.get(&relation.object_hash),
+        ) {
+            (Some(subject), Some(object)) => {
+                let computed = runtime.hash(&hasher::fact_preimage(
+                    namespace,
+                    subject,
+                    &relation.predicate,
+                    object,
+                    relation.polarity,
+                ));
+                compare(
+                    &mut mismatches,
+                    HashedNode::Fact {
+                        episode: episode.clone(),
+                    },
+                    relation.fact_hash.as_bytes(),
+                    computed.as_ref(),
+                );
+                computed.as_ref().to_vec()
+            }
+            _ => relation.fact_hash.as_bytes().to_vec(),
+        };
+        let ctx = if relation.context_hash == record.context.hash {
+            context.as_ref()
+        } else {
+            relation.context_hash.as_bytes()
+        };
+        let statement = hasher::concept_hash(&hasher::statement_label(episode));
+        let computed = runtime.hash(&hasher::episode_preimage(
+            &fact,
+            ctx,
+            qualifiers.get(&statement).map_or(&[], Vec::as_slice),
+        ));
+        compare(
+            &mut mismatches,
+            HashedNode::Episode {
+                episode: episode.clone(),
+            },
+            episode.as_bytes(),
+            computed.as_ref(),
+        );
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+fn compare(mismatches: &mut Vec<HashMismatch>, node: HashedNode, stored: &[u8], computed: &[u8]) {
+    if stored != computed {
+        mismatches.push(HashMismatch {
+            node,
+            stored: hex::encode(stored),
+            computed: hex::encode(computed),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::compiler::emitter::{compile, compile_with};
+    use crate::compiler::CompilerOptions;
+
+    /// Hashes like the compiler, or, if `utf16`, SHA-256 of the input
+    /// read as Latin-1 and re-encoded as UTF-16: the kind of encoding slip
+    /// the check is for.
+    struct HashRuntime {
+        utf16: bool,
+    }
+
+    impl MemoryRuntime for HashRuntime {
+        type Value = String;
+        type Hash = [u8; 32];
+        type Error = String;
+
+        fn hash(&self, input: &[u8]) -> [u8; 32] {
+            if !self.utf16 {
+                return Sha256::digest(input).into();
+            }
+            let wide: Vec<u8> = input.iter().flat_map(|b| [*b, 0]).collect();
+            Sha256::digest(wide).into()
+        }
+
+        fn execute_graph(
+            &self,
+            _graph_source: &str,
+            _inputs: HashMap<String, String>,
+        ) -> Result<HashMap<String, String>, String> {
+            Err("not a graph runtime".to_string())
+        }
+
+        fn load_state(&self, _key: &str) -> Result<Option<String>, String> {
+            Ok(None)
+        }
+
+        fn save_state(&self, _key: &str, _value: &String) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    const AGREES: HashRuntime = HashRuntime { utf16: false };
+
+    fn input() -> CompilerInput {
+        let mut met = SemanticTuple::from(("Alice", "met", "Bob", 0.9));
+        met.qualifiers = HashMap::from([("location".to_string(), "Paris".to_string())]);
+        let mut dislikes = SemanticTuple::from(("Alice", "likes", "Mushrooms", 0.7));
+        dislikes.polarity = Polarity::Negative;
+        CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .tuple("Alice", "needs", "LTM", 0.8)
+            .tuples([met, dislikes])
+            .build()
+            .unwrap()
+    }
+
+    fn record() -> MemoryRecord {
+        compile(&input()).record
+    }
+
+    #[test]
+    fn compiled_records_pass() {
+        verify_hashes_with_runtime(&record(), &AGREES).unwrap();
+
+        let options = CompilerOptions {
+            namespace: Some("tenant-a".to_string()),
+            ..CompilerOptions::default()
+        };
+        let namespaced = compile_with(&input(), &options).record;
+        verify_hashes_with_runtime(&namespaced, &AGREES).unwrap();
+    }
+
+    #[test]
+    fn one_corrupt_byte_is_one_mismatch() {
+        let mut record = record();
+        let alice = record
+            .concepts
+            .iter()
+            .position(|c| c.label == "alice")
+            .unwrap();
+        record.concepts[alice].hash.0[7] ^= 1;
+        let mismatches = verify_hashes_with_runtime(&record, &AGREES).unwrap_err();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].node,
+            HashedNode::Concept {
+                label: "alice".to_string()
+            }
+        );
+        assert_eq!(
+            mismatches[0].stored,
+            record.concepts[alice].hash.to_string()
+        );
+        assert_eq!(
+            mismatches[0].computed,
+            hasher::concept_hash("alice").to_string()
+        );
+
+        let mut record = self::record();
+        record.relations[1].fact_hash.0[0] ^= 0x80;
+        let episode = record.relations[1].episode_hash.clone();
+        assert_eq!(
+            verify_hashes_with_runtime(&record, &AGREES)
+                .unwrap_err()
+                .into_iter()
+                .map(|m| m.node)
+                .collect::<Vec<_>>(),
+            [HashedNode::Fact { episode }]
+        );
+
+        let mut record = self::record();
+        record.context.hash.0[31] ^= 1;
+        let mismatches = verify_hashes_with_runtime(&record, &AGREES).unwrap_err();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].node, HashedNode::Context);
+        assert!(mismatches[0].to_string().starts_with("context has hash "));
+
+        let mut record = self::record();
+        record.relations[0].episode_hash.0[3] ^= 1;
+        let mismatches = verify_hashes_with_runtime(&record, &AGREES).unwrap_err();
+        assert_eq!(
+            mismatches.iter().map(|m| &m.node).collect::<Vec<_>>(),
+            [&HashedNode::Episode {
+                episode: record.relations[0].episode_hash.clone()
+            }]
+        );
+    }
+
+    #[test]
+    fn runtimes_hashing_another_encoding_disagree_everywhere() {
+        let record = record();
+        let mismatches =
+            verify_hashes_with_runtime(&record, &HashRuntime { utf16: true }).unwrap_err();
+        assert_eq!(
+            mismatches.len(),
+            1 + record.concepts.len() + 2 * record.relations.len()
+        );
+    }
+}
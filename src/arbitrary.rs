@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::compiler::emitter::compile_with;
+use crate::compiler::CompilerOptions;
+use crate::types::*;
+
+/// Labels common enough that independently generated tuples and records
+/// share concepts, as real observations do.
+const COMMON_LABELS: [&str; 8] = [
+    "Alice", "Bob", "agent", "memory", "LTM", "Paris", "Rust", "coffee",
+];
+
+const SOURCES: [&str; 3] = ["user_prompt", "observation", "tool_output"];
+
+/// A concept label that normalizes to something: one of a few common
+/// labels, or one to three words of ASCII letters.
+pub fn arb_label() -> impl Strategy<Value = String> {
+    prop_oneof![
+        select(&COMMON_LABELS[..]).prop_map(str::to_string),
+        "[A-Z]?[a-z]{1,8}( [a-z]{1,8}){0,2}",
+    ]
+}
+
+/// A predicate of one or two lowercase words joined by `_`.
+pub fn arb_predicate() -> impl Strategy<Value = String> {
+    "[a-z]{2,8}(_[a-z]{2,6})?"
+}
+
+/// A time between 2000 and 2100, sometimes with milliseconds.
+pub fn arb_event_time() -> impl Strategy<Value = EventTime> {
+    (
+        946_684_800i64..4_102_444_800,
+        prop_oneof![Just(0u32), 0u32..1000],
+    )
+        .prop_map(|(secs, millis)| {
+            DateTime::<Utc>::from_timestamp(secs, millis * 1_000_000)
+                .expect("in range")
+                .into()
+        })
+}
+
+/// A tuple the compiler accepts: non-blank components, a confidence in
+/// `0.0..=1.0`, either polarity, and up to two qualifiers.
+pub fn arb_semantic_tuple() -> impl Strategy<Value = SemanticTuple> {
+    (
+        arb_label(),
+        arb_predicate(),
+        arb_label(),
+        0.0..=1.0f64,
+        any::<bool>(),
+        prop::collection::hash_map("[a-z]{2,8}", arb_label(), 0..=2),
+    )
+        .prop_map(
+            |(subject, predicate, object, confidence, negative, qualifiers)| {
+                let mut tuple = SemanticTuple::from((
+                    subject.as_str(),
+                    predicate.as_str(),
+                    object.as_str(),
+                    confidence,
+                ));
+                if negative {
+                    tuple.polarity = Polarity::Negative;
+                }
+                tuple.qualifiers = qualifiers;
+                tuple
+            },
+        )
+}
+
+/// A context with a source, a scope, and sometimes an agent and session.
+pub fn arb_context_meta() -> impl Strategy<Value = ContextMeta> {
+    (
+        arb_event_time(),
+        select(&SOURCES[..]),
+        "[a-z]{1,8}_[0-9]{1,4}",
+        proptest::option::of("agent-[0-9]{1,3}"),
+        proptest::option::of("session-[0-9]{1,3}"),
+    )
+        .prop_map(
+            |(event_time, source, scope, agent_id, session_id)| ContextMeta {
+                event_time,
+                source: source.to_string(),
+                scope,
+                agent_id,
+                session_id,
+                metadata: None,
+            },
+        )
+}
+
+/// An input of one to eight [tuples](arb_semantic_tuple) in one context.
+pub fn arb_compiler_input() -> impl Strategy<Value = CompilerInput> {
+    (
+        arb_context_meta(),
+        prop::collection::vec(arb_semantic_tuple(), 1..=8),
+    )
+        .prop_map(|(context, tuples)| CompilerInput::from((context, tuples)))
+}
+
+/// A record whose hashes are what they claim to be: concept hashes derive
+/// from their labels, fact hashes from endpoints, predicate, and polarity,
+/// and episode hashes from fact, context, and qualifiers, sometimes in a
+/// namespace. Each is [compiled](compile_with) from an
+/// [arbitrary input](arb_compiler_input), so it always
+/// [validates](MemoryRecord::validate).
+pub fn arb_consistent_memory_record() -> impl Strategy<Value = MemoryRecord> {
+    (
+        arb_compiler_input(),
+        proptest::option::weighted(0.2, "[a-z]{1,8}"),
+    )
+        .prop_map(|(input, namespace)| {
+            let options = CompilerOptions {
+                namespace,
+                ..CompilerOptions::default()
+            };
+            compile_with(&input, &options).record
+        })
+}
+
+impl Arbitrary for SemanticTuple {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_semantic_tuple().boxed()
+    }
+}
+
+impl Arbitrary for ContextMeta {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_context_meta().boxed()
+    }
+}
+
+impl Arbitrary for CompilerInput {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_compiler_input().boxed()
+    }
+}
+
+/// See [`arb_consistent_memory_record`].
+impl Arbitrary for MemoryRecord {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_consistent_memory_record().boxed()
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::Args;
+use zero_memory::compiler::emitter::try_compile_with;
+use zero_memory::compiler::{CompilerOptions, SanitizePolicy};
+use zero_memory::types::CompilerInput;
+
+use crate::{read_json, write_output, CliError};
+
+#[derive(Args)]
+pub struct CompileArgs {
+    /// A CompilerInput, as JSON.
+    input: PathBuf,
+    /// Where to write the `.0` graph text [default: stdout].
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Also write the MemoryRecord here, as JSON.
+    #[arg(long)]
+    record: Option<PathBuf>,
+    /// A JSON object mapping aliases to canonical labels, added to the
+    /// default aliases.
+    #[arg(long)]
+    aliases: Option<PathBuf>,
+    /// Write string values verbatim instead of escaping colons.
+    #[arg(long)]
+    no_sanitize: bool,
+}
+
+pub fn run(args: CompileArgs) -> Result<(), CliError> {
+    let input: CompilerInput = read_json(&args.input)?;
+    let mut options = CompilerOptions::default();
+    if let Some(path) = &args.aliases {
+        let aliases: BTreeMap<String, String> = read_json(path)?;
+        for (alias, canonical) in &aliases {
+            options.aliases.insert(alias, canonical);
+        }
+    }
+    if args.no_sanitize {
+        options.sanitize = SanitizePolicy::None;
+    }
+
+    let output = try_compile_with(&input, &options)
+        .map_err(|e| CliError::Invalid(format!("{}: {}", args.input.display(), e)))?;
+    for warning in &output.warnings {
+        eprintln!("zmem: warning: {:?}", warning);
+    }
+    write_output(args.output.as_deref(), &output.graph_text)?;
+    if let Some(path) = &args.record {
+        let json = serde_json::to_string_pretty(&output.record)
+            .expect("a MemoryRecord serializes to JSON");
+        write_output(Some(path), &json)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde::Serialize;
+use zero_memory::compiler::{decompile, proof_node, record_from_json};
+use zero_memory::types::{ContextNode, MemoryRecord, Proof};
+
+use crate::{write_output, CliError};
+
+#[derive(Args)]
+pub struct InspectArgs {
+    /// A `.0` graph emitted by the compiler, or a MemoryRecord as JSON.
+    file: PathBuf,
+    /// Print the summary as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+/// What `zmem inspect` prints about a file.
+#[derive(Serialize)]
+struct Summary {
+    /// `"graph"` or `"record"`.
+    kind: &'static str,
+    record_hash: String,
+    concepts: usize,
+    relations: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+    context: ContextNode,
+    proof: Option<Proof>,
+    /// Relations per predicate; records only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    predicates: Option<BTreeMap<String, usize>>,
+}
+
+impl Summary {
+    fn new(kind: &'static str, record: MemoryRecord, proof: Option<Proof>) -> Self {
+        Summary {
+            kind,
+            record_hash: record.hash().to_string(),
+            concepts: record.concepts.len(),
+            relations: record.relations.len(),
+            namespace: record.namespace,
+            context: record.context,
+            proof,
+            predicates: None,
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let meta = &self.context.meta;
+        let mut lines = vec![
+            format!("kind: {}", self.kind),
+            format!("record hash: {}", self.record_hash),
+            format!("concepts: {}", self.concepts),
+            format!("relations: {}", self.relations),
+        ];
+        if let Some(namespace) = &self.namespace {
+            lines.push(format!("namespace: {}", namespace));
+        }
+        lines.push("context:".to_string());
+        lines.push(format!("  hash: {}", self.context.hash));
+        lines.push(format!("  event_time: {}", meta.event_time));
+        lines.push(format!("  source: {}", meta.source));
+        lines.push(format!("  scope: {}", meta.scope));
+        if let Some(agent_id) = &meta.agent_id {
+            lines.push(format!("  agent_id: {}", agent_id));
+        }
+        if let Some(session_id) = &meta.session_id {
+            lines.push(format!("  session_id: {}", session_id));
+        }
+        match &self.proof {
+            Some(proof) => {
+                lines.push("proof:".to_string());
+                lines.push(format!("  trace_hash: {}", proof.trace_hash));
+                lines.push(format!("  signer: {}", proof.signer));
+                lines.push(format!("  signature: {}", proof.signature));
+            }
+            None => lines.push("proof: none".to_string()),
+        }
+        if let Some(predicates) = &self.predicates {
+            lines.push("predicates:".to_string());
+            for (predicate, count) in predicates {
+                lines.push(format!("  {}: {}", predicate, count));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+pub fn run(args: InspectArgs) -> Result<(), CliError> {
+    let summary = summarize(&args.file)?;
+    let text = if args.json {
+        serde_json::to_string_pretty(&summary).expect("a summary serializes to JSON") + "\n"
+    } else {
+        summary.to_text()
+    };
+    write_output(None, &text)
+}
+
+/// Tells graphs from records by their first character: graph text starts
+/// with `Graph {`, record JSON with `{`.
+fn summarize(path: &Path) -> Result<Summary, CliError> {
+    let bytes = fs::read(path).map_err(|error| CliError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let invalid = |message: String| CliError::Invalid(format!("{}: {}", path.display(), message));
+    let text = String::from_utf8(bytes).map_err(|_| invalid("not UTF-8 text".to_string()))?;
+
+    let trimmed = text.trim_start();
+    if trimmed.starts_with("Graph") {
+        let record = decompile(&text).map_err(|e| invalid(e.to_string()))?;
+        let proof = proof_node(&text);
+        Ok(Summary::new("graph", record, proof))
+    } else if trimmed.starts_with('{') {
+        let record = record_from_json(&text).map_err(|e| invalid(e.to_string()))?;
+        let mut predicates = BTreeMap::new();
+        for relation in &record.relations {
+            *predicates
+                .entry(relation.predicate.to_string())
+                .or_insert(0) += 1;
+        }
+        let proof = record.proof.clone();
+        let mut summary = Summary::new("record", record, proof);
+        summary.predicates = Some(predicates);
+        Ok(summary)
+    } else {
+        Err(invalid(
+            "neither a .0 graph nor a MemoryRecord JSON file".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,107 @@
+//! `zmem`: the 0-memory compiler and store on the command line.
+//!
+//! Uses only the library's public API. Exits with 0 on success, 1 when an
+//! input is malformed or invalid (e.g. rejected by the compiler), and 2
+//! when a file cannot be read or written.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde::de::DeserializeOwned;
+
+mod compile;
+mod inspect;
+mod store;
+
+#[derive(Parser)]
+#[command(
+    name = "zmem",
+    version,
+    about = "Compile, inspect, and store 0-memory records"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compile a CompilerInput JSON file to `.0` graph text.
+    Compile(compile::CompileArgs),
+    /// Summarize a `.0` graph or a MemoryRecord JSON file.
+    Inspect(inspect::InspectArgs),
+    /// Add records to, query, and count a store kept in a snapshot file.
+    Store(store::StoreArgs),
+}
+
+/// Why a command failed, which decides the exit code.
+#[derive(Debug)]
+enum CliError {
+    /// The input is malformed or invalid.
+    Invalid(String),
+    /// A file could not be read or written.
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl CliError {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            CliError::Invalid(_) => ExitCode::from(1),
+            CliError::Io { .. } => ExitCode::from(2),
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Invalid(message) => f.write_str(message),
+            CliError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+fn read_to_string(path: &Path) -> Result<String, CliError> {
+    fs::read_to_string(path).map_err(|error| CliError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// The JSON file at `path` as a `T`.
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, CliError> {
+    let text = read_to_string(path)?;
+    serde_json::from_str(&text).map_err(|e| CliError::Invalid(format!("{}: {}", path.display(), e)))
+}
+
+/// Write `contents` to `path`, or to stdout if there is no path.
+fn write_output(path: Option<&Path>, contents: &str) -> Result<(), CliError> {
+    let result = match path {
+        Some(path) => fs::write(path, contents),
+        None => io::Write::write_all(&mut io::stdout().lock(), contents.as_bytes()),
+    };
+    result.map_err(|error| CliError::Io {
+        path: path.unwrap_or_else(|| Path::new("<stdout>")).to_path_buf(),
+        error,
+    })
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Compile(args) => compile::run(args),
+        Command::Inspect(args) => inspect::run(args),
+        Command::Store(args) => store::run(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("zmem: {}", e);
+            e.exit_code()
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+use zero_memory::compiler::record_from_json;
+use zero_memory::store::{MemoryStore, SnapshotError};
+use zero_memory::types::{ConceptHash, InsertResult, Polarity, RelationNode};
+
+use crate::{read_to_string, write_output, CliError};
+
+#[derive(Args)]
+pub struct StoreArgs {
+    /// The store: a JSON snapshot if the name ends in `.json`, a binary
+    /// snapshot otherwise. Created by the first `add`.
+    #[arg(long)]
+    path: PathBuf,
+    #[command(subcommand)]
+    command: StoreCommand,
+}
+
+#[derive(Subcommand)]
+enum StoreCommand {
+    /// Check a MemoryRecord JSON file, insert it, and save the store.
+    Add { record: PathBuf },
+    /// Print the relations around a concept, labels resolved.
+    Query {
+        /// Label or alias of the concept to start from.
+        #[arg(long)]
+        label: String,
+        /// How many relations away from the concept to go.
+        #[arg(long, default_value = "1")]
+        depth: usize,
+        /// Only print and follow relations with this predicate.
+        #[arg(long)]
+        predicate: Option<String>,
+    },
+    /// Print what the store holds and has ingested.
+    Stats,
+}
+
+pub fn run(args: StoreArgs) -> Result<(), CliError> {
+    let mut store = load(&args.path)?;
+    let text = match args.command {
+        StoreCommand::Add { record } => {
+            let json = read_to_string(&record)?;
+            let invalid =
+                |message: String| CliError::Invalid(format!("{}: {}", record.display(), message));
+            let record = record_from_json(&json).map_err(|e| invalid(e.to_string()))?;
+            record.validate().map_err(|issues| {
+                let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                invalid(format!("invalid record: {}", issues.join("; ")))
+            })?;
+            let result = store.insert_record(record);
+            save(&store, &args.path)?;
+            insert_summary(&result)
+        }
+        StoreCommand::Query {
+            label,
+            depth,
+            predicate,
+        } => query(&store, &label, depth, predicate.as_deref())?,
+        StoreCommand::Stats => stats(&store),
+    };
+    write_output(None, &text)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |extension| extension == "json")
+}
+
+fn snapshot_error(path: &Path, e: SnapshotError) -> CliError {
+    match e {
+        SnapshotError::Io(error) => CliError::Io {
+            path: path.to_path_buf(),
+            error,
+        },
+        e => CliError::Invalid(format!("{}: {}", path.display(), e)),
+    }
+}
+
+/// The store at `path`, or an empty one if there is none yet.
+fn load(path: &Path) -> Result<MemoryStore, CliError> {
+    let file = match File::open(path) {
+        Ok(file) => io::BufReader::new(file),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(MemoryStore::new()),
+        Err(error) => {
+            return Err(CliError::Io {
+                path: path.to_path_buf(),
+                error,
+            })
+        }
+    };
+    let store = if is_json(path) {
+        MemoryStore::load_json(file)
+    } else {
+        MemoryStore::load_binary(file)
+    };
+    store.map_err(|e| snapshot_error(path, e))
+}
+
+/// Save `store` to `path` through a temporary file next to it, renamed
+/// over `path` once written and synced, so a crash leaves the old store
+/// whole.
+fn save(store: &MemoryStore, path: &Path) -> Result<(), CliError> {
+    let mut bytes = Vec::new();
+    let written = if is_json(path) {
+        store.save_json(&mut bytes)
+    } else {
+        store.save_binary(&mut bytes)
+    };
+    written.map_err(|e| snapshot_error(path, e))?;
+
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(".tmp{}", std::process::id()));
+    let temp = PathBuf::from(temp);
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |error| CliError::Io { path, error }
+    };
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(&bytes)?;
+            file.sync_all()
+        })
+        .map_err(io_error(&temp))
+        .and_then(|()| fs::rename(&temp, path).map_err(io_error(path)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+fn insert_summary(result: &InsertResult) -> String {
+    let mut lines = vec![
+        format!("new concepts: {}", result.new_concepts),
+        format!("new facts: {}", result.new_facts),
+        format!("new episodes: {}", result.new_episodes),
+        format!("duplicates skipped: {}", result.dupes_skipped),
+        format!("concepts updated: {}", result.concepts_updated),
+    ];
+    for (name, count) in [
+        ("reinforced", result.reinforced),
+        ("resurrected", result.resurrected),
+        ("refused concepts", result.refused_concepts),
+        ("refused relations", result.refused_relations),
+        ("alias conflicts", result.alias_conflicts.len()),
+    ] {
+        if count > 0 {
+            lines.push(format!("{}: {}", name, count));
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// The relations within `depth` of the concept labelled `label`, nearest
+/// first, one line each.
+fn query(
+    store: &MemoryStore,
+    label: &str,
+    depth: usize,
+    predicate: Option<&str>,
+) -> Result<String, CliError> {
+    let root = store
+        .get_concept_by_label(label)
+        .ok_or_else(|| CliError::Invalid(format!("no concept labelled {:?}", label)))?;
+    let predicate = predicate.map(|p| store.label_index().normalizer().normalize_predicate(p));
+
+    let mut seen_concepts = HashSet::from([root.hash.clone()]);
+    let mut seen_episodes = HashSet::new();
+    let mut frontier = vec![root.hash.clone()];
+    let mut text = String::new();
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for hash in &frontier {
+            for relation in store.get_relations(hash) {
+                if predicate
+                    .as_deref()
+                    .map_or(false, |p| &*relation.predicate != p)
+                {
+                    continue;
+                }
+                if !seen_episodes.insert(relation.episode_hash.clone()) {
+                    continue;
+                }
+                text += &relation_line(store, relation);
+                for end in [&relation.subject_hash, &relation.object_hash] {
+                    if seen_concepts.insert(end.clone()) {
+                        next.push(end.clone());
+                    }
+                }
+            }
+        }
+        frontier = next;
+    }
+    Ok(text)
+}
+
+fn relation_line(store: &MemoryStore, relation: &RelationNode) -> String {
+    let label = |hash: &ConceptHash| match store.label_of(hash) {
+        Some(label) => label.to_string(),
+        None => hash.to_string(),
+    };
+    let negation = match relation.polarity {
+        Polarity::Positive => "",
+        Polarity::Negative => "not ",
+    };
+    format!(
+        "{} {}{} {}\tconfidence {}\t{}\n",
+        label(&relation.subject_hash),
+        negation,
+        relation.predicate,
+        label(&relation.object_hash),
+        relation.confidence,
+        relation.created_at
+    )
+}
+
+fn stats(store: &MemoryStore) -> String {
+    let ingest = store.ingest_stats();
+    let mut lines = vec![
+        format!("concepts: {}", store.concept_count()),
+        format!("facts: {}", store.fact_count()),
+        format!("relations: {}", store.relation_count()),
+        format!("contexts: {}", store.context_count()),
+        format!("records ingested: {}", ingest.records),
+        format!("episodes ingested: {}", ingest.episodes),
+    ];
+    for (name, counts) in [
+        ("by source", &ingest.by_source),
+        ("by scope", &ingest.by_scope),
+        ("by agent", &ingest.by_agent),
+    ] {
+        if counts.is_empty() {
+            continue;
+        }
+        lines.push(format!("{}:", name));
+        let sorted: BTreeMap<&String, &u64> = counts.iter().collect();
+        for (key, count) in sorted {
+            lines.push(format!("  {}: {}", key, count));
+        }
+    }
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use crate::types::{MemoryRecord, RecordHash};
+
+/// Links records into a hash chain as they pass through: each record's
+/// `prev` is set to the [hash](MemoryRecord::hash) of the record before
+/// it, so that changing, dropping, or reordering a record breaks the link
+/// after it (see [`verify_chain`]).
+///
+/// A record's hash covers its `prev`, so each link also vouches for every
+/// record before it. Sign records after linking them: linking changes
+/// what a signature signs.
+///
+/// ```ignore
+/// let chained: Vec<MemoryRecord> = RecordChain::new(records).collect();
+/// ```
+pub struct RecordChain<I> {
+    records: I,
+    prev: Option<RecordHash>,
+}
+
+impl<I: Iterator<Item = MemoryRecord>> RecordChain<I> {
+    /// Start a new chain: the first record gets no `prev`.
+    pub fn new(records: impl IntoIterator<IntoIter = I>) -> Self {
+        RecordChain {
+            records: records.into_iter(),
+            prev: None,
+        }
+    }
+
+    /// Continue a chain whose last record hashes to `prev`.
+    pub fn after(prev: RecordHash, records: impl IntoIterator<IntoIter = I>) -> Self {
+        RecordChain {
+            records: records.into_iter(),
+            prev: Some(prev),
+        }
+    }
+
+    /// Hash of the last record linked so far, which the next record will
+    /// point to.
+    pub fn head(&self) -> Option<&RecordHash> {
+        self.prev.as_ref()
+    }
+}
+
+impl<I: Iterator<Item = MemoryRecord>> Iterator for RecordChain<I> {
+    type Item = MemoryRecord;
+
+    fn next(&mut self) -> Option<MemoryRecord> {
+        let mut record = self.records.next()?;
+        record.prev = self.prev.take();
+        self.prev = Some(record.hash());
+        Some(record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.records.size_hint()
+    }
+}
+
+/// The first broken link [`verify_chain`] found: the record at `index`
+/// does not point to the hash of the record before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainError {
+    /// Position of the record whose `prev` is wrong.
+    pub index: usize,
+    /// Hash of the record at `index - 1`, as it is now.
+    pub expected: RecordHash,
+    /// The `prev` the record at `index` carries.
+    pub found: Option<RecordHash>,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.found {
+            Some(found) => write!(
+                f,
+                "chain broken at record {}: prev is {}, but the record before hashes to {}",
+                self.index, found, self.expected
+            ),
+            None => write!(
+                f,
+                "chain broken at record {}: no prev, but the record before hashes to {}",
+                self.index, self.expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Check that every record in `records` after the first points to the
+/// hash of the record before it, recomputing each hash from the record as
+/// it is now.
+///
+/// The first record's `prev` is not checked, so a chain can be verified
+/// a segment at a time.
+pub fn verify_chain(records: &[MemoryRecord]) -> Result<(), ChainError> {
+    for (index, pair) in records.windows(2).enumerate() {
+        let expected = pair[0].hash();
+        if pair[1].prev.as_ref() != Some(&expected) {
+            return Err(ChainError {
+                index: index + 1,
+                expected,
+                found: pair[1].prev.clone(),
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,178 @@
+/// Characters whose full case folding (`C` and `F` entries of Unicode's
+/// `CaseFolding.txt`) differs from [`char::to_lowercase`], sorted by code
+/// point. Cherokee and the Greek letters with ypogegrammeni in
+/// U+1F80..U+1FAF follow a pattern and are folded in [`fold_char`].
+const FOLDS: &[(char, &str)] = &[
+    ('\u{b5}', "\u{3bc}"),
+    ('\u{df}', "ss"),
+    ('\u{149}', "\u{2bc}n"),
+    ('\u{17f}', "s"),
+    ('\u{1f0}', "j\u{30c}"),
+    ('\u{345}', "\u{3b9}"),
+    ('\u{390}', "\u{3b9}\u{308}\u{301}"),
+    ('\u{3b0}', "\u{3c5}\u{308}\u{301}"),
+    ('\u{3c2}', "\u{3c3}"),
+    ('\u{3d0}', "\u{3b2}"),
+    ('\u{3d1}', "\u{3b8}"),
+    ('\u{3d5}', "\u{3c6}"),
+    ('\u{3d6}', "\u{3c0}"),
+    ('\u{3f0}', "\u{3ba}"),
+    ('\u{3f1}', "\u{3c1}"),
+    ('\u{3f5}', "\u{3b5}"),
+    ('\u{587}', "\u{565}\u{582}"),
+    ('\u{1c80}', "\u{432}"),
+    ('\u{1c81}', "\u{434}"),
+    ('\u{1c82}', "\u{43e}"),
+    ('\u{1c83}', "\u{441}"),
+    ('\u{1c84}', "\u{442}"),
+    ('\u{1c85}', "\u{442}"),
+    ('\u{1c86}', "\u{44a}"),
+    ('\u{1c87}', "\u{463}"),
+    ('\u{1c88}', "\u{a64b}"),
+    ('\u{1e96}', "h\u{331}"),
+    ('\u{1e97}', "t\u{308}"),
+    ('\u{1e98}', "w\u{30a}"),
+    ('\u{1e99}', "y\u{30a}"),
+    ('\u{1e9a}', "a\u{2be}"),
+    ('\u{1e9b}', "\u{1e61}"),
+    ('\u{1e9e}', "ss"),
+    ('\u{1f50}', "\u{3c5}\u{313}"),
+    ('\u{1f52}', "\u{3c5}\u{313}\u{300}"),
+    ('\u{1f54}', "\u{3c5}\u{313}\u{301}"),
+    ('\u{1f56}', "\u{3c5}\u{313}\u{342}"),
+    ('\u{1fb2}', "\u{1f70}\u{3b9}"),
+    ('\u{1fb3}', "\u{3b1}\u{3b9}"),
+    ('\u{1fb4}', "\u{3ac}\u{3b9}"),
+    ('\u{1fb6}', "\u{3b1}\u{342}"),
+    ('\u{1fb7}', "\u{3b1}\u{342}\u{3b9}"),
+    ('\u{1fbc}', "\u{3b1}\u{3b9}"),
+    ('\u{1fbe}', "\u{3b9}"),
+    ('\u{1fc2}', "\u{1f74}\u{3b9}"),
+    ('\u{1fc3}', "\u{3b7}\u{3b9}"),
+    ('\u{1fc4}', "\u{3ae}\u{3b9}"),
+    ('\u{1fc6}', "\u{3b7}\u{342}"),
+    ('\u{1fc7}', "\u{3b7}\u{342}\u{3b9}"),
+    ('\u{1fcc}', "\u{3b7}\u{3b9}"),
+    ('\u{1fd2}', "\u{3b9}\u{308}\u{300}"),
+    ('\u{1fd3}', "\u{3b9}\u{308}\u{301}"),
+    ('\u{1fd6}', "\u{3b9}\u{342}"),
+    ('\u{1fd7}', "\u{3b9}\u{308}\u{342}"),
+    ('\u{1fe2}', "\u{3c5}\u{308}\u{300}"),
+    ('\u{1fe3}', "\u{3c5}\u{308}\u{301}"),
+    ('\u{1fe4}', "\u{3c1}\u{313}"),
+    ('\u{1fe6}', "\u{3c5}\u{342}"),
+    ('\u{1fe7}', "\u{3c5}\u{308}\u{342}"),
+    ('\u{1ff2}', "\u{1f7c}\u{3b9}"),
+    ('\u{1ff3}', "\u{3c9}\u{3b9}"),
+    ('\u{1ff4}', "\u{3ce}\u{3b9}"),
+    ('\u{1ff6}', "\u{3c9}\u{342}"),
+    ('\u{1ff7}', "\u{3c9}\u{342}\u{3b9}"),
+    ('\u{1ffc}', "\u{3c9}\u{3b9}"),
+    ('\u{fb00}', "ff"),
+    ('\u{fb01}', "fi"),
+    ('\u{fb02}', "fl"),
+    ('\u{fb03}', "ffi"),
+    ('\u{fb04}', "ffl"),
+    ('\u{fb05}', "st"),
+    ('\u{fb06}', "st"),
+    ('\u{fb13}', "\u{574}\u{576}"),
+    ('\u{fb14}', "\u{574}\u{565}"),
+    ('\u{fb15}', "\u{574}\u{56b}"),
+    ('\u{fb16}', "\u{57e}\u{576}"),
+    ('\u{fb17}', "\u{574}\u{56d}"),
+];
+
+fn fold_char(c: char, folded: &mut String) {
+    if c.is_ascii() {
+        folded.push(c.to_ascii_lowercase());
+        return;
+    }
+    if let Ok(i) = FOLDS.binary_search_by_key(&c, |&(from, _)| from) {
+        folded.push_str(FOLDS[i].1);
+        return;
+    }
+    let code = c as u32;
+    match code {
+        // Cherokee folds to its uppercase letters, which came first.
+        0x13a0..=0x13f5 => folded.push(c),
+        0x13f8..=0x13fd | 0xab70..=0xabbf => {
+            let upper = if code < 0xab70 {
+                code - 8
+            } else {
+                code - 0xab70 + 0x13a0
+            };
+            folded.extend(char::from_u32(upper));
+        }
+        // Greek vowels with ypogegrammeni or prosgegrammeni: the vowel
+        // with its breathing and accent, then iota.
+        0x1f80..=0x1faf => {
+            let base = [0x1f00, 0x1f20, 0x1f60][(code as usize - 0x1f80) / 16] + (code & 7);
+            folded.extend(char::from_u32(base));
+            folded.push('\u{3b9}');
+        }
+        _ => folded.extend(c.to_lowercase()),
+    }
+}
+
+/// Full Unicode case folding of `s`, the locale-independent mapping
+/// Unicode defines for caseless matching: `Straße` and `STRASSE` both fold
+/// to `strasse`, and `Σ`, `σ`, and final `ς` all fold to `σ`.
+///
+/// Turkish-specific folds (status `T`) are not applied: `I` folds to `i`
+/// and `İ` to `i` plus a combining dot, whatever the locale, and dotless
+/// `ı` folds to itself. The output is not necessarily NFC.
+pub fn case_fold(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        fold_char(c, &mut folded);
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_are_sorted() {
+        assert!(FOLDS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn simple_folds_match_lowercase() {
+        assert_eq!(case_fold("Agent MEMORY"), "agent memory");
+        assert_eq!(case_fold("\u{c9}T\u{c9}"), "\u{e9}t\u{e9}");
+        assert_eq!(case_fold("\u{416}\u{423}\u{41a}"), "\u{436}\u{443}\u{43a}");
+        assert_eq!(case_fold("\u{212a}"), "k");
+    }
+
+    #[test]
+    fn full_folds_expand() {
+        assert_eq!(case_fold("Stra\u{df}e"), "strasse");
+        assert_eq!(case_fold("STRA\u{1e9e}E"), "strasse");
+        assert_eq!(case_fold("\u{fb01}le"), "file");
+        assert_eq!(case_fold("\u{1f88}"), "\u{1f00}\u{3b9}");
+        assert_eq!(case_fold("\u{1fa9}"), "\u{1f61}\u{3b9}");
+    }
+
+    #[test]
+    fn greek_sigmas_fold_alike() {
+        assert_eq!(case_fold("\u{3a3}"), "\u{3c3}");
+        assert_eq!(case_fold("\u{3c3}"), "\u{3c3}");
+        assert_eq!(case_fold("\u{3c2}"), "\u{3c3}");
+    }
+
+    #[test]
+    fn cherokee_folds_to_uppercase() {
+        assert_eq!(case_fold("\u{13a0}"), "\u{13a0}");
+        assert_eq!(case_fold("\u{ab70}"), "\u{13a0}");
+        assert_eq!(case_fold("\u{13f8}"), "\u{13f0}");
+    }
+
+    #[test]
+    fn turkish_i_folds_without_locale() {
+        assert_eq!(case_fold("I"), "i");
+        assert_eq!(case_fold("\u{130}"), "i\u{307}");
+        assert_eq!(case_fold("\u{131}"), "\u{131}");
+    }
+}
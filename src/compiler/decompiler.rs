@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::compiler::emitter::desanitize;
+use crate::compiler::hasher;
+use crate::types::*;
+
+/// Why [`decompile`] could not read a graph back.
+#[derive(Debug)]
+pub enum DecompileError {
+    /// The text is not a `Graph { ... }` block holding a JSON object.
+    Syntax(serde_json::Error),
+    /// The graph parses but was not laid out by
+    /// [`emit_graph_text`](super::emit_graph_text).
+    UnrecognizedLayout(String),
+    /// A node of the expected layout holds a malformed value. `field` is
+    /// the part of the node's value that could not be read.
+    InvalidField { node: String, field: &'static str },
+}
+
+impl fmt::Display for DecompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecompileError::Syntax(e) => write!(f, "graph text is not valid JSON: {}", e),
+            DecompileError::UnrecognizedLayout(reason) => {
+                write!(f, "unrecognized layout: {}", reason)
+            }
+            DecompileError::InvalidField { node, field } => {
+                write!(f, "node {}: invalid {}", node, field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecompileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecompileError::Syntax(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The value of the `context` Constant.
+#[derive(Deserialize)]
+struct ContextConstant {
+    event_time: EventTime,
+    source: String,
+    scope: String,
+    hash: ContextHash,
+}
+
+/// The value of a `rel_*` Constant.
+#[derive(Deserialize)]
+struct RelationConstant {
+    subject_hash: ConceptHash,
+    predicate: String,
+    object_hash: ConceptHash,
+    confidence: f64,
+    fact_hash: FactHash,
+    episode_hash: EpisodeHash,
+    #[serde(default)]
+    polarity: Polarity,
+    #[serde(default)]
+    valid_from: Option<String>,
+    #[serde(default)]
+    valid_to: Option<String>,
+}
+
+fn unrecognized(reason: &str) -> DecompileError {
+    DecompileError::UnrecognizedLayout(reason.to_string())
+}
+
+/// The JSON object inside `Graph { ... }`, colons desanitized.
+pub(crate) fn parse_graph_json(graph_text: &str) -> Result<Value, DecompileError> {
+    let body = graph_text
+        .trim_start()
+        .strip_prefix("Graph")
+        .ok_or_else(|| unrecognized("text does not start with `Graph`"))?;
+    serde_json::from_str(&desanitize(body)).map_err(DecompileError::Syntax)
+}
+
+/// The value of the `concept_<field>_<short_id>` Constant, if the graph
+/// has one.
+fn concept_field<T: DeserializeOwned>(
+    constants: &HashMap<&str, &Value>,
+    field: &'static str,
+    short_id: &str,
+) -> Result<Option<T>, DecompileError> {
+    let node = format!("concept_{}_{}", field, short_id);
+    match constants.get(node.as_str()) {
+        Some(value) => T::deserialize(*value)
+            .map(Some)
+            .map_err(|_| DecompileError::InvalidField { node, field }),
+        None => Ok(None),
+    }
+}
+
+/// Parse graph text produced by [`emit_graph_text`](super::emit_graph_text)
+/// back into a [`MemoryRecord`].
+///
+/// Fact, episode, and context hashes are read from the hex strings the
+/// graph carries; concept hashes are re-derived from the concept labels,
+/// which the graph only feeds to a runtime `Hash` operation, in the
+/// namespace named by the graph's metadata.
+///
+/// The graph holds less than the record, so some fields come back
+/// approximated:
+/// - the context's agent, session, and metadata are empty;
+/// - a relation's `created_at` is the context's event time, and relations
+///   carry no spans.
+///
+/// A signed record comes back with its [`Proof`]; an unsigned one, with
+/// none.
+///
+/// Graphs emitted before concepts carried their aliases, confidence, and
+/// timestamps are still read: their concepts get no aliases, the highest
+/// confidence of the relations touching them (or 1.0), and the context's
+/// event time.
+///
+/// Graphs not laid out by this crate's emitter are rejected with
+/// [`DecompileError::UnrecognizedLayout`].
+pub fn decompile(graph_text: &str) -> Result<MemoryRecord, DecompileError> {
+    let graph = parse_graph_json(graph_text)?;
+
+    if graph.get("name").and_then(Value::as_str).is_none() {
+        return Err(unrecognized("graph has no name"));
+    }
+    let nodes = graph
+        .get("nodes")
+        .and_then(Value::as_array)
+        .ok_or_else(|| unrecognized("graph has no node list"))?;
+
+    let mut constants = Vec::new();
+    let mut has_empty_map = false;
+    let mut has_output = false;
+    for node in nodes {
+        let id = node
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| unrecognized("node without an id"))?;
+        match (
+            node.get("type").and_then(Value::as_str),
+            node.get("op").and_then(Value::as_str),
+        ) {
+            (Some("Constant"), _) => {
+                let value = node
+                    .get("value")
+                    .ok_or_else(|| unrecognized("Constant node without a value"))?;
+                constants.push((id, value));
+            }
+            (Some("Operation"), Some("CreateMap")) if id == "empty_map" => has_empty_map = true,
+            (Some("Operation"), Some("MergeMap")) if id == "output" => has_output = true,
+            (Some("Operation"), Some(_)) => {}
+            _ => return Err(unrecognized("node is neither a Constant nor an Operation")),
+        }
+    }
+    if !has_empty_map || !has_output {
+        return Err(unrecognized(
+            "graph lacks the empty_map and output nodes of the emitter",
+        ));
+    }
+
+    let mut context = None;
+    let mut labels = Vec::new();
+    let mut relations = Vec::new();
+    let mut concept_fields: HashMap<&str, &Value> = HashMap::new();
+    let mut proof = None;
+    let mut prev = None;
+    for (id, value) in constants {
+        if id == "context" {
+            let ctx: ContextConstant = serde_json::from_value(value.clone()).map_err(|_| {
+                DecompileError::InvalidField {
+                    node: id.to_string(),
+                    field: "context",
+                }
+            })?;
+            context = Some(ctx);
+        } else if let Some(short_id) = id.strip_prefix("concept_label_") {
+            let label = value.as_str().ok_or_else(|| DecompileError::InvalidField {
+                node: id.to_string(),
+                field: "label",
+            })?;
+            labels.push((short_id, label.to_string()));
+        } else if id.starts_with("concept_") {
+            concept_fields.insert(id, value);
+        } else if id.starts_with("rel_") {
+            let rel: RelationConstant = serde_json::from_value(value.clone()).map_err(|_| {
+                DecompileError::InvalidField {
+                    node: id.to_string(),
+                    field: "relation",
+                }
+            })?;
+            relations.push(rel);
+        } else if id == "proof" {
+            // Unsigned records carry a pending signature and no proof.
+            if value.get("signature").and_then(Value::as_str) != Some("pending") {
+                let signed: Proof = serde_json::from_value(value.clone()).map_err(|_| {
+                    DecompileError::InvalidField {
+                        node: id.to_string(),
+                        field: "proof",
+                    }
+                })?;
+                proof = Some(signed);
+            }
+            if let Some(hash) = value.get("prev") {
+                let hash = serde_json::from_value(hash.clone()).map_err(|_| {
+                    DecompileError::InvalidField {
+                        node: id.to_string(),
+                        field: "prev",
+                    }
+                })?;
+                prev = Some(hash);
+            }
+        }
+    }
+    let context = context.ok_or_else(|| unrecognized("graph has no context node"))?;
+    let namespace = match graph.get("metadata").and_then(|m| m.get("namespace")) {
+        Some(namespace) => Some(namespace.as_str().map(str::to_string).ok_or_else(|| {
+            DecompileError::InvalidField {
+                node: "metadata".to_string(),
+                field: "namespace",
+            }
+        })?),
+        None => None,
+    };
+    let now = context.event_time;
+
+    let relations: Vec<RelationNode> = relations
+        .into_iter()
+        .map(|r| RelationNode {
+            fact_hash: r.fact_hash,
+            episode_hash: r.episode_hash,
+            subject_hash: r.subject_hash,
+            predicate: r.predicate.into(),
+            object_hash: r.object_hash,
+            confidence: r.confidence,
+            context_hash: context.hash.clone(),
+            created_at: now,
+            polarity: r.polarity,
+            valid_from: r.valid_from,
+            valid_to: r.valid_to,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        })
+        .collect();
+
+    let mut confidence: HashMap<&ConceptHash, f64> = HashMap::new();
+    for r in &relations {
+        for hash in [&r.subject_hash, &r.object_hash] {
+            let best = confidence.entry(hash).or_insert(r.confidence);
+            *best = best.max(r.confidence);
+        }
+    }
+    let concepts = labels
+        .into_iter()
+        .map(|(short_id, label)| -> Result<ConceptNode, DecompileError> {
+            // Concepts hashed in the namespace have a key node; statement
+            // concepts do not.
+            let key = format!("concept_key_{}", short_id);
+            let hash = match &namespace {
+                Some(namespace) if concept_fields.contains_key(key.as_str()) => {
+                    hasher::concept_hash_ns(namespace, &label)
+                }
+                _ => hasher::concept_hash(&label),
+            };
+            Ok(ConceptNode {
+                aliases: concept_field(&concept_fields, "aliases", short_id)?.unwrap_or_default(),
+                confidence: concept_field(&concept_fields, "confidence", short_id)?
+                    .unwrap_or_else(|| confidence.get(&hash).copied().unwrap_or(1.0)),
+                created_at: concept_field(&concept_fields, "created_at", short_id)?.unwrap_or(now),
+                updated_at: concept_field(&concept_fields, "updated_at", short_id)?.unwrap_or(now),
+                display_label: concept_field(&concept_fields, "display_label", short_id)?,
+                hash,
+                label,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts,
+        relations,
+        context: ContextNode {
+            hash: context.hash,
+            meta: ContextMeta {
+                event_time: context.event_time,
+                source: context.source,
+                scope: context.scope,
+                agent_id: None,
+                session_id: None,
+                metadata: None,
+            },
+        },
+        proof,
+        prev,
+        namespace,
+    })
+}
+
+/// The value of the proof Constant of `graph_text`, pending fields and
+/// all: unlike [`decompile`], which keeps only a signed record's
+/// [`Proof`], this reads the trace hash of unsigned graphs too. `None` if
+/// the text is not a graph or has no readable proof node.
+pub fn proof_node(graph_text: &str) -> Option<Proof> {
+    let graph = parse_graph_json(graph_text).ok()?;
+    let proof = graph
+        .get("nodes")?
+        .as_array()?
+        .iter()
+        .find(|node| node.get("id").and_then(Value::as_str) == Some("proof"))?;
+    serde_json::from_value(proof.get("value")?.clone()).ok()
+}
+
+/// Whether the proof node of `graph_text` carries the trace hash of
+/// `record` (see [`hasher::trace_hash`]).
+///
+/// False if the graph has no proof node, or one whose trace hash is still
+/// `"pending"`, as in graphs emitted before trace hashes were computed.
+pub fn verify_trace_hash(record: &MemoryRecord, graph_text: &str) -> bool {
+    match proof_node(graph_text) {
+        Some(proof) => proof.trace_hash == hex::encode(hasher::trace_hash(record)),
+        None => false,
+    }
+}
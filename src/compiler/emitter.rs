@@ -1,212 +1,1145 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
 
+use crate::compiler::error::CompilerError;
 use crate::compiler::hasher;
-use crate::compiler::normalizer::{normalize_predicate, AliasTable};
+use crate::compiler::normalizer::Resolution;
+use crate::compiler::options::{CompilerOptions, GraphMeta, PredicateCheck, SanitizePolicy};
 use crate::types::*;
 
 pub use crate::types::CompilerOutput;
 
-/// Strip colons from a string value before embedding in `.0` graph text.
+/// How a colon inside a string value is written in `.0` graph text.
+const COLON_ESCAPE: &str = "\\u003a";
+
+/// Escape every colon inside the string literals of JSON text as `\u003a`,
+/// leaving the colons between keys and values alone.
 ///
 /// The 0-openclaw parser applies a `word:` → `"word":` regex across the entire
 /// source, including the interior of quoted strings.  Any colon inside a value
 /// (e.g., ISO 8601 `T00:00:00Z`) triggers false-positive key quoting and
-/// produces unparseable JSON.  Stripping colons is the simplest safe workaround
-/// (see `compatibility.md` Gotcha #5).
-fn sanitize_for_graph(s: &str) -> String {
-    s.replace(':', "")
+/// produces unparseable JSON (see `compatibility.md` Gotcha #5).  The JSON
+/// escape hides the colon from the regex, and the runtime's JSON parser
+/// turns it back into a colon, so values reach the graph unchanged.
+pub fn sanitize_for_graph(json: &str) -> String {
+    replace_colons_in_strings(json, COLON_ESCAPE)
+}
+
+/// Replace every colon inside the string literals of JSON text.
+fn replace_colons_in_strings(json: &str, replacement: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut chars = json.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ':' if in_string => out.push_str(replacement),
+            '\\' if in_string => {
+                out.push(c);
+                out.extend(chars.next());
+            }
+            '"' => {
+                in_string = !in_string;
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Undo [`sanitize_for_graph`]: turn every `\u003a` escape inside a string
+/// literal back into a colon.
+///
+/// For JSON without `\u003a` escapes of its own, such as any output of
+/// [`serde_json::to_string`], `desanitize(&sanitize_for_graph(json))` is
+/// `json` byte for byte.
+pub fn desanitize(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut chars = json.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                if chars.as_str().starts_with(&COLON_ESCAPE[1..]) {
+                    out.push(':');
+                    chars.nth(COLON_ESCAPE.len() - 2);
+                } else {
+                    out.push(c);
+                    out.extend(chars.next());
+                }
+            }
+            '"' => {
+                in_string = !in_string;
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `value` as JSON text, with its string values written under `policy`.
+/// Values are written verbatim under [`SanitizePolicy::Strict`], which is
+/// checked before anything is written.
+fn graph_json<T: serde::Serialize + ?Sized>(value: &T, policy: SanitizePolicy) -> String {
+    let json = serde_json::to_string(value).unwrap();
+    match policy {
+        SanitizePolicy::StripColons => replace_colons_in_strings(&json, ""),
+        SanitizePolicy::EscapeColons => sanitize_for_graph(&json),
+        SanitizePolicy::None | SanitizePolicy::Strict => json,
+    }
+}
+
+/// How `time` is written in graph text. Timestamps always hold colons, so
+/// under [`SanitizePolicy::Strict`] they are written without them, as under
+/// `StripColons`; the colon-free form reads back as the same instant.
+fn graph_time(time: &EventTime, policy: SanitizePolicy) -> String {
+    let text = time.to_string();
+    match policy {
+        SanitizePolicy::Strict => text.replace(':', ""),
+        _ => text,
+    }
+}
+
+/// The forms a label was written in: as given, as normalized, and, when
+/// it was an alias, as the alias table matched it.
+fn surface_forms<'a>(
+    raw: &'a str,
+    normalized: &'a str,
+    resolution: &'a Resolution,
+) -> Vec<&'a str> {
+    let mut forms = vec![raw, normalized];
+    if resolution.was_alias {
+        forms.push(&resolution.original_normalized);
+    }
+    forms
+}
+
+/// Record the ways a concept was written in the input as aliases, skipping
+/// the canonical label and forms already recorded.
+fn add_surface_forms(concept: &mut ConceptNode, forms: &[&str]) {
+    for form in forms {
+        if *form != concept.label && !concept.aliases.iter().any(|a| a == *form) {
+            concept.aliases.push(form.to_string());
+        }
+    }
+}
+
+/// Make `display`, the display form of a label that normalized to
+/// `normalized`, the display label of `concept` when it is the label
+/// written differently (`OpenAI` for `openai`). Input that only resolves
+/// to the label, through an alias, a plural, or a dropped stop word, names
+/// the concept but is not a way to show it.
+fn set_display_label(concept: &mut ConceptNode, display: &str, normalized: &str) {
+    if normalized == concept.label && display != concept.label {
+        concept.display_label = Some(display.to_string());
+    }
+}
+
+/// One qualifier of a tuple, as written and as hashed.
+struct Qualifier<'a> {
+    /// The normalized key: the qualifier relation's predicate.
+    key: String,
+    /// The alias-resolved value label: the value concept's label.
+    value_label: String,
+    raw_key: &'a String,
+    raw_value: &'a String,
+    normalized: String,
+    resolution: Resolution,
+}
+
+/// Concept hashes of one namespace, memoized by label. [`compile_batch`]
+/// shares one across its inputs, so a label repeated between inputs is
+/// hashed once.
+#[derive(Default)]
+struct ConceptHashes {
+    namespace: Option<String>,
+    hashes: HashMap<String, ConceptHash>,
+}
+
+impl ConceptHashes {
+    fn new(namespace: Option<&str>) -> Self {
+        ConceptHashes {
+            namespace: namespace.map(str::to_string),
+            hashes: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, label: &str) -> ConceptHash {
+        if let Some(hash) = self.hashes.get(label) {
+            return hash.clone();
+        }
+        let hash = hasher::concept_hash_in(self.namespace.as_deref(), label);
+        self.hashes.insert(label.to_string(), hash.clone());
+        hash
+    }
+}
+
+/// The concept labelled `label`, created on first use with the given
+/// confidence and timestamp.
+fn concept_entry<'a>(
+    concepts: &'a mut HashMap<String, ConceptNode>,
+    hashes: &mut ConceptHashes,
+    label: &str,
+    confidence: f64,
+    now: EventTime,
+) -> &'a mut ConceptNode {
+    concepts
+        .entry(label.to_string())
+        .or_insert_with(|| ConceptNode {
+            hash: hashes.get(label),
+            label: label.to_string(),
+            aliases: vec![],
+            confidence,
+            created_at: now,
+            updated_at: now,
+            display_label: None,
+        })
 }
 
 /// Compile raw semantic tuples + context into a `.0` graph and structured record.
 ///
+/// Lenient: tuples whose subject, object, or predicate is blank after
+/// normalization are skipped with a warning (see
+/// [`CompilerOutput::tuples_skipped`]), and out-of-range confidences and
+/// blank context fields are compiled as-is, yielding degenerate hashes.
+/// Prefer [`try_compile`], which rejects all of them; this function is
+/// kept for compatibility.
+///
 /// Pipeline:
-/// 1. Resolve aliases and normalize all concept labels and predicates
+/// 1. Resolve aliases and normalize all concept labels and predicates,
+///    keeping labels written differently as the concept's aliases
 /// 2. Compute ConceptHash, FactHash, ContextHash, EpisodeHash
-/// 3. Deduplicate concepts by label
-/// 4. Build MemoryRecord
-/// 5. Emit `.0` graph text using only Constant, Operation, SetField nodes
+/// 3. Deduplicate concepts by label and relations by episode
+/// 4. Reify qualified relations: mint a statement concept per qualified
+///    episode, with one relation per qualifier hanging off it
+/// 5. Build MemoryRecord
+/// 6. Emit `.0` graph text using only Constant, Operation, SetField nodes
+///
+/// Uses the default [`CompilerOptions`]; see [`compile_with`].
 pub fn compile(input: &CompilerInput) -> CompilerOutput {
-    let alias_table = AliasTable::with_defaults();
+    compile_with(input, &CompilerOptions::default())
+}
+
+/// Like [`compile`], but with caller-supplied options, e.g. a domain
+/// [`AliasTable`](crate::compiler::AliasTable) applied to every subject and
+/// object label.
+pub fn compile_with(input: &CompilerInput, options: &CompilerOptions) -> CompilerOutput {
+    let mut hashes = ConceptHashes::new(options.namespace.as_deref());
+    compile_one(input, options, &mut hashes, true).expect("lenient compiles do not fail")
+}
+
+/// Compile many inputs with the same options, as [`compile_with`] would
+/// compile each of them. Outputs are per input, in input order, and
+/// identical to those of separate calls; the batch only shares work, such
+/// as hashing labels that repeat across inputs.
+pub fn compile_batch(inputs: &[CompilerInput], options: &CompilerOptions) -> Vec<CompilerOutput> {
+    let mut hashes = ConceptHashes::new(options.namespace.as_deref());
+    inputs
+        .iter()
+        .map(|input| {
+            compile_one(input, options, &mut hashes, true).expect("lenient compiles do not fail")
+        })
+        .collect()
+}
+
+/// Compile one input. Only fails under [`SanitizePolicy::Strict`], and
+/// not even then if `lenient`, which escapes colons instead.
+fn compile_one(
+    input: &CompilerInput,
+    options: &CompilerOptions,
+    hashes: &mut ConceptHashes,
+    lenient: bool,
+) -> Result<CompilerOutput, CompilerError> {
     let ctx_hash = hasher::context_hash(&input.context);
-    let mut concept_map: HashMap<String, ConceptNode> = HashMap::new();
-    let mut relations = Vec::new();
-    let now = input.context.event_time.clone();
-
-    for tuple in &input.tuples {
-        let subj_label = alias_table.resolve(&tuple.subject);
-        let obj_label = alias_table.resolve(&tuple.object);
-        let pred = normalize_predicate(&tuple.predicate);
-
-        let subj_hash = hasher::concept_hash(&subj_label);
-        let obj_hash = hasher::concept_hash(&obj_label);
-
-        concept_map
-            .entry(subj_label.clone())
-            .or_insert_with(|| ConceptNode {
-                hash: subj_hash.clone(),
-                label: subj_label.clone(),
-                aliases: vec![],
-                confidence: tuple.confidence,
-                created_at: now.clone(),
-                updated_at: now.clone(),
-            });
+    let mut builder = RecordBuilder::new(ctx_hash.clone(), input.context.event_time);
+    builder.add_tuples(&input.tuples, options, hashes);
+    let (concepts, relations) = builder.finish();
 
-        concept_map
-            .entry(obj_label.clone())
-            .or_insert_with(|| ConceptNode {
-                hash: obj_hash.clone(),
-                label: obj_label.clone(),
-                aliases: vec![],
-                confidence: tuple.confidence,
-                created_at: now.clone(),
-                updated_at: now.clone(),
+    let record = MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts,
+        relations,
+        context: ContextNode {
+            hash: ctx_hash,
+            meta: input.context.clone(),
+        },
+        proof: None,
+        prev: None,
+        namespace: options.namespace.clone(),
+    };
+    Ok(CompilerOutput {
+        graph_text: emit_compiled(&record, input, options, lenient)?,
+        record,
+        duplicates_collapsed: builder.duplicates_collapsed,
+        tuples_skipped: builder.tuples_skipped,
+        warnings: builder.warnings,
+    })
+}
+
+/// Graph text of `record`, compiled from `input` with `options`. Only
+/// fails as [`compile_one`] does.
+pub(crate) fn emit_compiled(
+    record: &MemoryRecord,
+    input: &CompilerInput,
+    options: &CompilerOptions,
+    lenient: bool,
+) -> Result<String, CompilerError> {
+    let emit = |policy| {
+        emit_graph_text_with(
+            record,
+            &input.context,
+            input.utterance.as_deref(),
+            policy,
+            &options.graph_meta,
+        )
+    };
+    match emit(options.sanitize) {
+        Err(_) if lenient => emit(SanitizePolicy::EscapeColons),
+        result => result,
+    }
+}
+
+/// Compile `tuples` observed in `context` into an existing `record` of the
+/// same context, for tuples that arrive a few at a time. New concepts and
+/// relations are appended and deduplicated against the record exactly as
+/// if all of its tuples had been compiled in one [`compile_with`] call.
+///
+/// Fails with [`CompilerError::ContextMismatch`], leaving the record
+/// untouched, if `context` does not hash to the record's `ContextHash`,
+/// and with [`CompilerError::NamespaceMismatch`] if `options` hash in
+/// another namespace than the record was compiled in. The record's graph
+/// text is not kept up to date; emit it once at the end with
+/// [`emit_graph_text`].
+pub fn compile_into(
+    record: &mut MemoryRecord,
+    context: &ContextMeta,
+    tuples: &[SemanticTuple],
+    options: &CompilerOptions,
+) -> Result<IncrementalReport, CompilerError> {
+    let found = hasher::context_hash(context);
+    if found != record.context.hash {
+        return Err(CompilerError::ContextMismatch {
+            expected: record.context.hash.clone(),
+            found,
+        });
+    }
+    if options.namespace != record.namespace {
+        return Err(CompilerError::NamespaceMismatch {
+            expected: record.namespace.clone(),
+            found: options.namespace.clone(),
+        });
+    }
+
+    let known: HashSet<ConceptHash> = record.concepts.iter().map(|c| c.hash.clone()).collect();
+    let known_relations = record.relations.len();
+    let mut builder = RecordBuilder::new(found, record.context.meta.event_time);
+    builder.extend(
+        std::mem::take(&mut record.concepts),
+        std::mem::take(&mut record.relations),
+    );
+    let mut hashes = ConceptHashes::new(options.namespace.as_deref());
+    builder.add_tuples(tuples, options, &mut hashes);
+    let (concepts, relations) = builder.finish();
+    record.concepts = concepts;
+    record.relations = relations;
+
+    Ok(IncrementalReport {
+        concepts_added: record
+            .concepts
+            .iter()
+            .filter(|c| !known.contains(&c.hash))
+            .map(|c| c.hash.clone())
+            .collect(),
+        relations_added: record.relations[known_relations..]
+            .iter()
+            .map(|r| r.episode_hash.clone())
+            .collect(),
+        duplicates_collapsed: builder.duplicates_collapsed,
+        tuples_skipped: builder.tuples_skipped,
+        warnings: builder.warnings,
+    })
+}
+
+/// Concepts and relations of one context, deduplicated as tuples are added.
+struct RecordBuilder {
+    ctx_hash: ContextHash,
+    now: EventTime,
+    concepts: HashMap<String, ConceptNode>,
+    relations: Vec<RelationNode>,
+    /// Position in `relations` of each episode seen so far.
+    relation_index: HashMap<EpisodeHash, usize>,
+    duplicates_collapsed: usize,
+    tuples_skipped: usize,
+    warnings: Vec<CompilerWarning>,
+}
+
+impl RecordBuilder {
+    fn new(ctx_hash: ContextHash, now: EventTime) -> Self {
+        RecordBuilder {
+            ctx_hash,
+            now,
+            concepts: HashMap::new(),
+            relations: Vec::new(),
+            relation_index: HashMap::new(),
+            duplicates_collapsed: 0,
+            tuples_skipped: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Seed the builder with already compiled concepts and relations.
+    fn extend(&mut self, concepts: Vec<ConceptNode>, relations: Vec<RelationNode>) {
+        for concept in concepts {
+            self.concepts.insert(concept.label.clone(), concept);
+        }
+        for relation in relations {
+            self.relation_index
+                .insert(relation.episode_hash.clone(), self.relations.len());
+            self.relations.push(relation);
+        }
+    }
+
+    fn add_tuples(
+        &mut self,
+        tuples: &[SemanticTuple],
+        options: &CompilerOptions,
+        hashes: &mut ConceptHashes,
+    ) {
+        for (tuple_index, tuple) in tuples.iter().enumerate() {
+            self.add_tuple(tuple_index, tuple, options, hashes);
+        }
+    }
+
+    fn add_tuple(
+        &mut self,
+        tuple_index: usize,
+        tuple: &SemanticTuple,
+        options: &CompilerOptions,
+        hashes: &mut ConceptHashes,
+    ) {
+        let now = self.now;
+        let ctx_hash = &self.ctx_hash;
+        let subj_normalized = options.normalizer.normalize_label(&tuple.subject);
+        let obj_normalized = options.normalizer.normalize_label(&tuple.object);
+        let pred = options.canonical_predicate(&tuple.predicate);
+        // The checks of `validate`, which a lenient compile turns into
+        // warnings: a blank label would hash to a concept nothing can find.
+        let blank = if subj_normalized.is_empty() {
+            Some(CompilerWarning::EmptyLabel {
+                tuple_index,
+                field: "subject".to_string(),
+            })
+        } else if obj_normalized.is_empty() {
+            Some(CompilerWarning::EmptyLabel {
+                tuple_index,
+                field: "object".to_string(),
+            })
+        } else if pred.is_empty() {
+            Some(CompilerWarning::EmptyPredicate { tuple_index })
+        } else {
+            None
+        };
+        if let Some(warning) = blank {
+            self.warnings.push(warning);
+            self.tuples_skipped += 1;
+            return;
+        }
+        let subj = options.canonical_label(&subj_normalized);
+        let obj = options.canonical_label(&obj_normalized);
+        let subj_label = subj.canonical.as_str();
+        let obj_label = obj.canonical.as_str();
+        if !options.allows_predicate(&pred) {
+            self.warnings.push(CompilerWarning::UnknownPredicate {
+                tuple_index,
+                predicate: pred.clone(),
             });
+        }
+
+        let subject = concept_entry(
+            &mut self.concepts,
+            hashes,
+            subj_label,
+            tuple.confidence,
+            now,
+        );
+        add_surface_forms(
+            subject,
+            &surface_forms(&tuple.subject, &subj_normalized, &subj),
+        );
+        set_display_label(
+            subject,
+            &options.normalizer.display_label(&tuple.subject),
+            &subj_normalized,
+        );
+        let subj_hash = subject.hash.clone();
 
-        let fh = hasher::fact_hash(&subj_label, &pred, &obj_label);
-        let eh = hasher::episode_hash(&fh, &ctx_hash);
+        let object = concept_entry(&mut self.concepts, hashes, obj_label, tuple.confidence, now);
+        add_surface_forms(object, &surface_forms(&tuple.object, &obj_normalized, &obj));
+        set_display_label(
+            object,
+            &options.normalizer.display_label(&tuple.object),
+            &obj_normalized,
+        );
+        let obj_hash = object.hash.clone();
 
-        relations.push(RelationNode {
+        // Raw qualifiers with their normalized and resolved values, their
+        // normalized keys, and alias-resolved value labels, sorted by
+        // (key, value label). Raw spellings of one normalized pair are one
+        // qualifier: `qualifiers` holds each pair once.
+        let mut entries: Vec<Qualifier> = tuple
+            .qualifiers
+            .iter()
+            .map(|(raw_key, raw_value)| {
+                let normalized = options.normalizer.normalize_label(raw_value);
+                let resolution = options.canonical_label(&normalized);
+                Qualifier {
+                    key: options.normalizer.normalize_predicate(raw_key),
+                    value_label: resolution.canonical.clone(),
+                    raw_key,
+                    raw_value,
+                    normalized,
+                    resolution,
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (&a.key, &a.value_label, a.raw_key, a.raw_value).cmp(&(
+                &b.key,
+                &b.value_label,
+                b.raw_key,
+                b.raw_value,
+            ))
+        });
+        let mut qualifiers: Vec<(String, String)> = entries
+            .iter()
+            .map(|q| (q.key.clone(), q.value_label.clone()))
+            .collect();
+        qualifiers.dedup();
+
+        let namespace = options.namespace.as_deref();
+        let fh = hasher::fact_hash_in(namespace, subj_label, &pred, obj_label, tuple.polarity);
+        let eh = hasher::qualified_episode_hash(&fh, ctx_hash, &qualifiers);
+        if let Some(&i) = self.relation_index.get(&eh) {
+            let existing = &mut self.relations[i];
+            existing.confidence = existing.confidence.max(tuple.confidence);
+            self.duplicates_collapsed += 1;
+            return;
+        }
+        self.relation_index.insert(eh.clone(), self.relations.len());
+
+        self.relations.push(RelationNode {
             fact_hash: fh,
-            episode_hash: eh,
+            episode_hash: eh.clone(),
             subject_hash: subj_hash,
-            predicate: pred,
+            predicate: pred.into(),
             object_hash: obj_hash,
             confidence: tuple.confidence,
             context_hash: ctx_hash.clone(),
-            created_at: now.clone(),
+            created_at: now,
+            polarity: tuple.polarity,
+            valid_from: tuple.valid_from.clone(),
+            valid_to: tuple.valid_to.clone(),
+            subject_span: tuple.subject_span,
+            object_span: tuple.object_span,
+            evidence_span: tuple.evidence_span,
         });
+
+        if qualifiers.is_empty() {
+            return;
+        }
+        // The statement concept is hashed outside the namespace: its label
+        // already holds the namespaced episode hash, and stores find it by
+        // the plain hash of that label.
+        let statement_label = hasher::statement_label(&eh);
+        let statement = concept_entry(
+            &mut self.concepts,
+            &mut ConceptHashes::default(),
+            &statement_label,
+            tuple.confidence,
+            now,
+        );
+        let statement_hash = statement.hash.clone();
+        for (i, q) in entries.iter().enumerate() {
+            let value = concept_entry(
+                &mut self.concepts,
+                hashes,
+                &q.value_label,
+                tuple.confidence,
+                now,
+            );
+            add_surface_forms(
+                value,
+                &surface_forms(q.raw_value, &q.normalized, &q.resolution),
+            );
+            set_display_label(
+                value,
+                &options.normalizer.display_label(q.raw_value),
+                &q.normalized,
+            );
+            let value_hash = value.hash.clone();
+            // Later spellings of a pair only add their surface forms.
+            if i > 0
+                && (&entries[i - 1].key, &entries[i - 1].value_label) == (&q.key, &q.value_label)
+            {
+                continue;
+            }
+            let qualifier_fact = hasher::fact_hash_in(
+                namespace,
+                &statement_label,
+                &q.key,
+                &q.value_label,
+                Polarity::Positive,
+            );
+            self.relations.push(RelationNode {
+                episode_hash: hasher::episode_hash(&qualifier_fact, ctx_hash),
+                fact_hash: qualifier_fact,
+                subject_hash: statement_hash.clone(),
+                predicate: q.key.as_str().into(),
+                object_hash: value_hash,
+                confidence: tuple.confidence,
+                context_hash: ctx_hash.clone(),
+                created_at: now,
+                polarity: Polarity::Positive,
+                valid_from: None,
+                valid_to: None,
+                subject_span: None,
+                object_span: None,
+                evidence_span: tuple.evidence_span,
+            });
+        }
     }
 
-    let mut concepts: Vec<ConceptNode> = concept_map.into_values().collect();
-    concepts.sort_by(|a, b| a.label.cmp(&b.label));
+    /// Concepts sorted by label, and relations in the order they were added.
+    fn finish(&mut self) -> (Vec<ConceptNode>, Vec<RelationNode>) {
+        let mut concepts: Vec<ConceptNode> = self.concepts.drain().map(|(_, c)| c).collect();
+        concepts.sort_by(|a, b| a.label.cmp(&b.label));
+        (concepts, std::mem::take(&mut self.relations))
+    }
+}
 
-    let context_node = ContextNode {
-        hash: ctx_hash,
-        meta: input.context.clone(),
-    };
+/// Validate `input`, then compile it like [`compile`].
+///
+/// Fails on the first problem found: context fields are checked before the
+/// tuples, and tuples in order.
+pub fn try_compile(input: &CompilerInput) -> Result<CompilerOutput, CompilerError> {
+    try_compile_with(input, &CompilerOptions::default())
+}
 
-    let record = MemoryRecord {
-        concepts,
-        relations,
-        context: context_node,
-    };
+/// Validate `input`, then compile it like [`compile_with`].
+///
+/// With [`CompilerOptions::allowed_predicates`] set, predicates outside the
+/// vocabulary fail the compile under [`PredicateCheck::Reject`] and only
+/// produce warnings under [`PredicateCheck::Warn`]. Under
+/// [`SanitizePolicy::Strict`], values the 0-openclaw parser cannot read
+/// fail the compile.
+pub fn try_compile_with(
+    input: &CompilerInput,
+    options: &CompilerOptions,
+) -> Result<CompilerOutput, CompilerError> {
+    validate(input, options)?;
+    let mut hashes = ConceptHashes::new(options.namespace.as_deref());
+    compile_one(input, options, &mut hashes, false)
+}
+
+fn validate(input: &CompilerInput, options: &CompilerOptions) -> Result<(), CompilerError> {
+    let ctx = &input.context;
+    for (field, value) in [("source", &ctx.source), ("scope", &ctx.scope)] {
+        if value.trim().is_empty() {
+            return Err(CompilerError::EmptyContextField { field });
+        }
+    }
 
-    let graph_text = emit_graph_text(&record, &input.context);
+    for (tuple_index, tuple) in input.tuples.iter().enumerate() {
+        for (field, label) in [("subject", &tuple.subject), ("object", &tuple.object)] {
+            if options.normalizer.normalize_label(label).is_empty() {
+                return Err(CompilerError::EmptyLabel { tuple_index, field });
+            }
+        }
+        if options
+            .normalizer
+            .normalize_predicate(&tuple.predicate)
+            .is_empty()
+        {
+            return Err(CompilerError::EmptyPredicate { tuple_index });
+        }
+        if options.predicate_check == PredicateCheck::Reject {
+            let predicate = options.canonical_predicate(&tuple.predicate);
+            if !options.allows_predicate(&predicate) {
+                return Err(CompilerError::UnknownPredicate {
+                    tuple_index,
+                    predicate,
+                });
+            }
+        }
+        if !(0.0..=1.0).contains(&tuple.confidence) {
+            return Err(CompilerError::InvalidConfidence {
+                tuple_index,
+                value: tuple.confidence,
+            });
+        }
+        for (field, span) in [
+            ("subject", tuple.subject_span),
+            ("object", tuple.object_span),
+            ("evidence", tuple.evidence_span),
+        ] {
+            let Some(span) = span else {
+                continue;
+            };
+            let fits = match &input.utterance {
+                Some(utterance) => span.text(utterance).is_some(),
+                None => false,
+            };
+            if !fits {
+                return Err(CompilerError::InvalidSpan {
+                    tuple_index,
+                    field,
+                    span,
+                });
+            }
+        }
+        let mut qualifiers: Vec<(&String, &String)> = tuple.qualifiers.iter().collect();
+        qualifiers.sort();
+        for (key, value) in qualifiers {
+            if options.normalizer.normalize_predicate(key).is_empty()
+                || options.normalizer.normalize_label(value).is_empty()
+            {
+                return Err(CompilerError::EmptyQualifier {
+                    tuple_index,
+                    key: key.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The `signer` of the proof node: this crate and its version.
+const PROOF_SIGNER: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
-    CompilerOutput { graph_text, record }
+/// Hex characters of a hash used in node IDs when they suffice to tell it
+/// apart from the other hashes of the graph.
+const SHORT_ID_LEN: usize = 12;
+
+/// Short IDs for `hashes`: the first `min_len` hex characters of each, or
+/// as many more as it takes to tell it apart from every other distinct
+/// hash in the list.
+pub(crate) fn short_ids(hashes: &[&[u8; 32]], min_len: usize) -> Vec<String> {
+    let full: Vec<String> = hashes.iter().map(|h| hasher::short_hex(h, 64)).collect();
+    let mut distinct: Vec<&str> = full.iter().map(String::as_str).collect();
+    distinct.sort_unstable();
+    distinct.dedup();
+
+    // In sorted order, a hash shares its longest prefix with a neighbour.
+    let mut lens: HashMap<&str, usize> = distinct.iter().map(|h| (*h, min_len)).collect();
+    for pair in distinct.windows(2) {
+        let common = pair[0]
+            .bytes()
+            .zip(pair[1].bytes())
+            .take_while(|(x, y)| x == y)
+            .count();
+        for h in pair {
+            let len = lens.get_mut(h).expect("every distinct hash has a length");
+            *len = (*len).max(common + 1);
+        }
+    }
+    hashes
+        .iter()
+        .zip(&full)
+        .map(|(h, f)| hasher::short_hex(h, lens[f.as_str()]))
+        .collect()
 }
 
 /// Serialize a MemoryRecord into `.0` graph text format.
 ///
 /// All inputs to `MergeMap` must be `Value::Map`.  The emitter wraps every
 /// value via `SetField` (from a shared `empty_map` created by `CreateMap`)
-/// under a unique key so that merged maps never collide.
+/// under a unique key so that merged maps never collide. Each concept map
+/// carries the concept's `label`, runtime-computed `hash`, `aliases`,
+/// `confidence`, `created_at`, `updated_at`, and `display_label` if it
+/// has one.
+///
+/// Node IDs derive from content, not position: `concept_<id>` from the
+/// concept hash and `rel_<id>` from the episode hash, where `<id>` is a
+/// short hex prefix (see [`hasher::short_hex`]). Concepts and relations are
+/// emitted in ID order, so the same record always yields the same text,
+/// and adding a tuple only adds lines.
 ///
 /// String values are JSON-escaped via [`serde_json::to_string`] and sanitized
-/// for the 0-openclaw parser (colons stripped).
+/// for the 0-openclaw parser (colons escaped; see [`sanitize_for_graph`]).
+/// The proof node carries the record's [`hasher::trace_hash`], which
+/// [`verify_trace_hash`](super::verify_trace_hash) checks.
+///
+/// The graph carries no utterance and the default [`GraphMeta`]; use
+/// [`emit_graph_text_with`] to include an utterance, or for another
+/// [`SanitizePolicy`] or header. For large records, prefer
+/// [`emit_graph_to`], which writes the same text without building it in
+/// memory.
 pub fn emit_graph_text(record: &MemoryRecord, context: &ContextMeta) -> String {
-    let mut nodes = Vec::<String>::new();
+    emit_graph_text_with(
+        record,
+        context,
+        None,
+        SanitizePolicy::EscapeColons,
+        &GraphMeta::default(),
+    )
+    .expect("only the strict policy fails")
+}
+
+/// Like [`emit_graph_text`], but with the `utterance` the record was
+/// compiled from, as the compiler emits it, string values written under
+/// `policy`, and the graph header taken from `meta`. Fails only under
+/// [`SanitizePolicy::Strict`], with [`CompilerError::UnsafeGraphValue`].
+///
+/// The utterance is a Constant, merged into the output together with its
+/// runtime-computed hash, so the graph can name the text that produced it;
+/// without an utterance there are no utterance nodes.
+pub fn emit_graph_text_with(
+    record: &MemoryRecord,
+    context: &ContextMeta,
+    utterance: Option<&str>,
+    policy: SanitizePolicy,
+    meta: &GraphMeta,
+) -> Result<String, CompilerError> {
+    if policy == SanitizePolicy::Strict {
+        check_strict(record, context, utterance, meta)?;
+    }
+    let mut text = Vec::new();
+    write_graph(&mut text, record, context, utterance, policy, meta)
+        .expect("writing to a Vec does not fail");
+    Ok(String::from_utf8(text).expect("graph text is UTF-8"))
+}
+
+/// Write the graph text of [`emit_graph_text`] to `writer`, node by node,
+/// without holding the whole text in memory.
+///
+/// Output is byte-identical to [`emit_graph_text`]. Nodes are written with
+/// many small writes, so wrap unbuffered writers such as a
+/// [`File`](std::fs::File) in a [`BufWriter`](std::io::BufWriter).
+pub fn emit_graph_to<W: Write>(
+    record: &MemoryRecord,
+    context: &ContextMeta,
+    writer: W,
+) -> io::Result<()> {
+    write_graph(
+        writer,
+        record,
+        context,
+        None,
+        SanitizePolicy::EscapeColons,
+        &GraphMeta::default(),
+    )
+}
+
+/// Fail with [`CompilerError::UnsafeGraphValue`] on the first string value
+/// of the graph the 0-openclaw parser cannot read, before anything is
+/// written.
+fn check_strict(
+    record: &MemoryRecord,
+    context: &ContextMeta,
+    utterance: Option<&str>,
+    meta: &GraphMeta,
+) -> Result<(), CompilerError> {
+    let mut concepts: Vec<&ConceptNode> = record.concepts.iter().collect();
+    concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+    let mut relations: Vec<&RelationNode> = record.relations.iter().collect();
+    relations.sort_by(|a, b| a.episode_hash.cmp(&b.episode_hash));
+    let name = meta.name_for(&context.scope);
+
+    let concept_values = concepts.iter().flat_map(|c| {
+        std::iter::once(c.label.as_str()).chain(c.aliases.iter().map(String::as_str))
+    });
+    let context_values = [context.source.as_str(), context.scope.as_str()];
+    let relation_values = relations.iter().flat_map(|r| {
+        std::iter::once(&*r.predicate)
+            .chain(r.valid_from.as_deref())
+            .chain(r.valid_to.as_deref())
+    });
+    let proof_values = record
+        .proof
+        .iter()
+        .flat_map(|p| [p.trace_hash.as_str(), &p.signer, &p.signature]);
+    let header_values = [name.as_str(), &meta.description, &meta.author]
+        .into_iter()
+        .chain(meta.tags.iter().map(String::as_str))
+        .chain(record.namespace.as_deref());
+
+    let mut values = concept_values
+        .chain(context_values)
+        .chain(utterance)
+        .chain(relation_values)
+        .chain(proof_values)
+        .chain(header_values);
+    match values.find(|value| value.contains(':')) {
+        Some(value) => Err(CompilerError::UnsafeGraphValue {
+            value: value.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Writes graph nodes, one per line, separated by commas.
+struct NodeWriter<W> {
+    writer: W,
+    first: bool,
+}
+
+impl<W: Write> NodeWriter<W> {
+    fn node(&mut self, node: fmt::Arguments<'_>) -> io::Result<()> {
+        if !self.first {
+            self.writer.write_all(b",\n")?;
+        }
+        self.first = false;
+        self.writer.write_fmt(node)
+    }
+}
+
+fn write_graph<W: Write>(
+    mut writer: W,
+    record: &MemoryRecord,
+    context: &ContextMeta,
+    utterance: Option<&str>,
+    policy: SanitizePolicy,
+    meta: &GraphMeta,
+) -> io::Result<()> {
+    let concept_ids = short_ids(
+        &record
+            .concepts
+            .iter()
+            .map(|c| c.hash.as_bytes())
+            .collect::<Vec<_>>(),
+        SHORT_ID_LEN,
+    );
+    let mut concepts: Vec<(&ConceptNode, String)> =
+        record.concepts.iter().zip(concept_ids).collect();
+    concepts.sort_by(|(a, _), (b, _)| a.hash.cmp(&b.hash));
+
+    let entry = match concepts.first() {
+        Some((_, id)) => format!("concept_label_{}", id),
+        None => "context".to_string(),
+    };
+
+    writeln!(
+        writer,
+        r#"Graph {{
+    "name": {},
+    "version": 1,
+    "description": {},
+    "nodes": ["#,
+        graph_json(&meta.name_for(&context.scope), policy),
+        graph_json(&meta.description, policy)
+    )?;
+
+    let mut nodes = NodeWriter {
+        writer: &mut writer,
+        first: true,
+    };
     let mut merge_inputs = Vec::<String>::new();
 
     // Shared empty map — every SetField chain starts from this node
-    nodes.push(
-        r#"        { "id": "empty_map", "type": "Operation", "op": "CreateMap", "inputs": [], "params": {} }"#
-            .to_string(),
-    );
+    nodes.node(format_args!(
+        r#"        {{ "id": "empty_map", "type": "Operation", "op": "CreateMap", "inputs": [], "params": {{}} }}"#
+    ))?;
 
     // --- Concept nodes ---
-    for (i, c) in record.concepts.iter().enumerate() {
-        let label_id = format!("concept_label_{}", i);
-        let hash_id = format!("concept_hash_{}", i);
-        let set_label_id = format!("concept_slabel_{}", i);
-        let data_id = format!("concept_data_{}", i);
-        let wrap_id = format!("concept_{}", i);
+    for (c, id) in &concepts {
+        let label_id = format!("concept_label_{}", id);
+        let hash_id = format!("concept_hash_{}", id);
+        let data_id = format!("concept_data_{}", id);
+        let wrap_id = format!("concept_{}", id);
 
-        let escaped_label = serde_json::to_string(&sanitize_for_graph(&c.label)).unwrap();
+        let escaped_label = graph_json(&c.label, policy);
 
-        nodes.push(format!(
+        nodes.node(format_args!(
             r#"        {{ "id": "{label_id}", "type": "Constant", "value": {escaped_label} }}"#
-        ));
+        ))?;
 
-        nodes.push(format!(
-            r#"        {{ "id": "{hash_id}", "type": "Operation", "op": "Hash", "inputs": ["{label_id}"] }}"#
-        ));
+        // A namespaced concept hashes `namespace + NUL + label`, which the
+        // runtime's Hash reads from a key Constant of its own.
+        let mut hash_input = label_id.clone();
+        if let Some(namespace) = &record.namespace {
+            if c.hash == hasher::concept_hash_ns(namespace, &c.label) {
+                hash_input = format!("concept_key_{}", id);
+                let key = graph_json(&format!("{}\0{}", namespace, c.label), policy);
+                nodes.node(format_args!(
+                    r#"        {{ "id": "{hash_input}", "type": "Constant", "value": {key} }}"#
+                ))?;
+            }
+        }
+        nodes.node(format_args!(
+            r#"        {{ "id": "{hash_id}", "type": "Operation", "op": "Hash", "inputs": ["{hash_input}"] }}"#
+        ))?;
 
-        // Build { "label": <label>, "hash": <hash> } via chained SetField
-        nodes.push(format!(
-            r#"        {{ "id": "{set_label_id}", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "{label_id}"], "params": {{ "field": "label" }} }}"#
-        ));
-        nodes.push(format!(
-            r#"        {{ "id": "{data_id}", "type": "Operation", "op": "SetField", "inputs": ["{set_label_id}", "{hash_id}"], "params": {{ "field": "hash" }} }}"#
-        ));
+        let mut fields = vec![("label", label_id), ("hash", hash_id)];
+        let display_label = c
+            .display_label
+            .as_ref()
+            .map(|display| ("display_label", graph_json(display, policy)));
+        for (field, value) in [
+            ("aliases", graph_json(&c.aliases, policy)),
+            ("confidence", graph_json(&c.confidence, policy)),
+            (
+                "created_at",
+                graph_json(&graph_time(&c.created_at, policy), policy),
+            ),
+            (
+                "updated_at",
+                graph_json(&graph_time(&c.updated_at, policy), policy),
+            ),
+        ]
+        .into_iter()
+        .chain(display_label)
+        {
+            let value_id = format!("concept_{}_{}", field, id);
+            nodes.node(format_args!(
+                r#"        {{ "id": "{value_id}", "type": "Constant", "value": {value} }}"#
+            ))?;
+            fields.push((field, value_id));
+        }
+
+        // Build { "label": <label>, "hash": <hash>, "aliases": [...], ... }
+        // via chained SetField, ending in the data node
+        let mut map_id = "empty_map".to_string();
+        let last = fields.len() - 1;
+        for (i, (field, value_id)) in fields.into_iter().enumerate() {
+            let set_id = if i == last {
+                data_id.clone()
+            } else {
+                format!("concept_s{}_{}", field, id)
+            };
+            nodes.node(format_args!(
+                r#"        {{ "id": "{set_id}", "type": "Operation", "op": "SetField", "inputs": ["{map_id}", "{value_id}"], "params": {{ "field": "{field}" }} }}"#
+            ))?;
+            map_id = set_id;
+        }
 
         // Wrap under unique key so MergeMap inputs don't collide
-        nodes.push(format!(
+        nodes.node(format_args!(
             r#"        {{ "id": "{wrap_id}", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "{data_id}"], "params": {{ "field": "{wrap_id}" }} }}"#
-        ));
+        ))?;
 
         merge_inputs.push(wrap_id);
     }
 
     // --- Context node ---
+    // The hash is carried explicitly so that readers of the graph need not
+    // recompute it from the context fields.
     let ctx_value = serde_json::json!({
-        "event_time": sanitize_for_graph(&context.event_time),
-        "source": sanitize_for_graph(&context.source),
-        "scope": sanitize_for_graph(&context.scope),
+        "event_time": graph_time(&context.event_time, policy),
+        "source": context.source,
+        "scope": context.scope,
+        "hash": record.context.hash.to_string(),
     });
-    nodes.push(format!(
+    nodes.node(format_args!(
         r#"        {{ "id": "context", "type": "Constant", "value": {} }}"#,
-        serde_json::to_string(&ctx_value).unwrap()
-    ));
+        graph_json(&ctx_value, policy)
+    ))?;
 
-    nodes.push(
-        r#"        { "id": "context_hash", "type": "Operation", "op": "Hash", "inputs": ["context"] }"#
-            .to_string(),
-    );
+    nodes.node(format_args!(
+        r#"        {{ "id": "context_hash", "type": "Operation", "op": "Hash", "inputs": ["context"] }}"#
+    ))?;
 
-    nodes.push(
-        r#"        { "id": "context_map", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "context"], "params": { "field": "context" } }"#
-            .to_string(),
-    );
-    nodes.push(
-        r#"        { "id": "context_wrapped", "type": "Operation", "op": "SetField", "inputs": ["context_map", "context_hash"], "params": { "field": "context_hash" } }"#
-            .to_string(),
-    );
+    nodes.node(format_args!(
+        r#"        {{ "id": "context_map", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "context"], "params": {{ "field": "context" }} }}"#
+    ))?;
+    nodes.node(format_args!(
+        r#"        {{ "id": "context_wrapped", "type": "Operation", "op": "SetField", "inputs": ["context_map", "context_hash"], "params": {{ "field": "context_hash" }} }}"#
+    ))?;
     merge_inputs.push("context_wrapped".to_string());
 
+    // --- Utterance node ---
+    if let Some(utterance) = utterance {
+        nodes.node(format_args!(
+            r#"        {{ "id": "utterance", "type": "Constant", "value": {} }}"#,
+            graph_json(utterance, policy)
+        ))?;
+        nodes.node(format_args!(
+            r#"        {{ "id": "utterance_hash", "type": "Operation", "op": "Hash", "inputs": ["utterance"] }}"#
+        ))?;
+        nodes.node(format_args!(
+            r#"        {{ "id": "utterance_map", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "utterance"], "params": {{ "field": "utterance" }} }}"#
+        ))?;
+        nodes.node(format_args!(
+            r#"        {{ "id": "utterance_wrapped", "type": "Operation", "op": "SetField", "inputs": ["utterance_map", "utterance_hash"], "params": {{ "field": "utterance_hash" }} }}"#
+        ))?;
+        merge_inputs.push("utterance_wrapped".to_string());
+    }
+
     // --- Relation nodes ---
-    for (i, r) in record.relations.iter().enumerate() {
-        let rel_id = format!("rel_{}", i);
-        let wrap_id = format!("wrap_rel_{}", i);
+    let concept_hashes: HashSet<&ConceptHash> = record.concepts.iter().map(|c| &c.hash).collect();
+    let relation_ids = short_ids(
+        &record
+            .relations
+            .iter()
+            .map(|r| r.episode_hash.as_bytes())
+            .collect::<Vec<_>>(),
+        SHORT_ID_LEN,
+    );
+    let mut relations: Vec<(&RelationNode, String)> =
+        record.relations.iter().zip(relation_ids).collect();
+    relations.sort_by(|(a, _), (b, _)| a.episode_hash.cmp(&b.episode_hash));
+    for (r, id) in &relations {
+        let rel_id = format!("rel_{}", id);
+        let wrap_id = format!("wrap_rel_{}", id);
 
-        let rel_value = serde_json::json!({
+        let mut rel_value = serde_json::json!({
             "subject_hash": r.subject_hash.to_string(),
-            "predicate": sanitize_for_graph(&r.predicate),
+            "predicate": &*r.predicate,
             "object_hash": r.object_hash.to_string(),
             "confidence": r.confidence,
             "fact_hash": r.fact_hash.to_string(),
             "episode_hash": r.episode_hash.to_string(),
+            "polarity": r.polarity,
         });
-        nodes.push(format!(
+        for (field, bound) in [("valid_from", &r.valid_from), ("valid_to", &r.valid_to)] {
+            if let Some(bound) = bound {
+                rel_value[field] = bound.as_str().into();
+            }
+        }
+        // Point a qualified relation at the statement concept holding its
+        // qualifiers.
+        let statement = hasher::concept_hash(&hasher::statement_label(&r.episode_hash));
+        if concept_hashes.contains(&statement) {
+            rel_value["statement_hash"] = statement.to_string().into();
+        }
+        nodes.node(format_args!(
             r#"        {{ "id": "{rel_id}", "type": "Constant", "value": {} }}"#,
-            serde_json::to_string(&rel_value).unwrap()
-        ));
+            graph_json(&rel_value, policy)
+        ))?;
 
-        nodes.push(format!(
+        nodes.node(format_args!(
             r#"        {{ "id": "{wrap_id}", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "{rel_id}"], "params": {{ "field": "{rel_id}" }} }}"#
-        ));
+        ))?;
         merge_inputs.push(wrap_id);
     }
 
-    // --- Proof placeholder ---
-    nodes.push(
-        r#"        { "id": "proof", "type": "Constant", "value": { "trace_hash": "pending", "signer": "0-memory-compiler", "signature": "pending" } }"#
-            .to_string(),
-    );
-    nodes.push(
-        r#"        { "id": "wrap_proof", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "proof"], "params": { "field": "proof" } }"#
-            .to_string(),
-    );
+    // --- Proof node ---
+    // An unsigned record names the crate as its signer, its signature
+    // pending.
+    let (trace_hash, signer, signature) = match &record.proof {
+        Some(proof) => (
+            graph_json(&proof.trace_hash, policy),
+            graph_json(&proof.signer, policy),
+            graph_json(&proof.signature, policy),
+        ),
+        None => (
+            graph_json(&hex::encode(hasher::trace_hash(record)), policy),
+            graph_json(PROOF_SIGNER, policy),
+            graph_json("pending", policy),
+        ),
+    };
+    // A chained record also names the record before it.
+    let prev = match &record.prev {
+        Some(prev) => format!(r#", "prev": "{}""#, prev),
+        None => String::new(),
+    };
+    nodes.node(format_args!(
+        r#"        {{ "id": "proof", "type": "Constant", "value": {{ "trace_hash": {trace_hash}, "signer": {signer}, "signature": {signature}{prev} }} }}"#
+    ))?;
+    nodes.node(format_args!(
+        r#"        {{ "id": "wrap_proof", "type": "Operation", "op": "SetField", "inputs": ["empty_map", "proof"], "params": {{ "field": "proof" }} }}"#
+    ))?;
     merge_inputs.push("wrap_proof".to_string());
 
     // --- MergeMap output ---
@@ -216,32 +1149,25 @@ pub fn emit_graph_text(record: &MemoryRecord, context: &ContextMeta) -> String {
         .collect::<Vec<_>>()
         .join(", ");
 
-    nodes.push(format!(
+    nodes.node(format_args!(
         r#"        {{ "id": "output", "type": "Operation", "op": "MergeMap", "inputs": [{}] }}"#,
         inputs_str
-    ));
+    ))?;
 
-    let nodes_block = nodes.join(",\n");
-
-    let entry = if record.concepts.is_empty() {
-        "context"
-    } else {
-        "concept_label_0"
+    let namespace = match &record.namespace {
+        Some(namespace) => format!(r#", "namespace": {}"#, graph_json(namespace, policy)),
+        None => String::new(),
     };
-
-    format!(
-        r#"Graph {{
-    "name": "zero_memory_compiled",
-    "version": 1,
-    "description": "Compiled memory graph from 0-memory",
-    "nodes": [
-{}
+    write!(
+        writer,
+        r#"
     ],
     "entry_point": "{entry}",
     "outputs": ["output"],
-    "metadata": {{ "author": "0-memory", "tags": ["memory", "compiled"] }}
+    "metadata": {{ "author": {}, "tags": {}{namespace} }}
 }}"#,
-        nodes_block
+        graph_json(&meta.author, policy),
+        graph_json(&meta.tags, policy)
     )
 }
 
@@ -254,27 +1180,12 @@ mod tests {
         CompilerInput {
             utterance: Some("An agent needs long-term memory".into()),
             tuples: vec![
-                SemanticTuple {
-                    subject: "Agent".into(),
-                    predicate: "needs".into(),
-                    object: "Long Term Memory".into(),
-                    confidence: 0.98,
-                },
-                SemanticTuple {
-                    subject: "0-memory".into(),
-                    predicate: "solves".into(),
-                    object: "Long Term Memory".into(),
-                    confidence: 0.97,
-                },
-                SemanticTuple {
-                    subject: "0-memory".into(),
-                    predicate: "uses".into(),
-                    object: "Content Addressing".into(),
-                    confidence: 0.95,
-                },
+                SemanticTuple::from(("Agent", "needs", "Long Term Memory", 0.98)),
+                SemanticTuple::from(("0-memory", "solves", "Long Term Memory", 0.97)),
+                SemanticTuple::from(("0-memory", "uses", "Content Addressing", 0.95)),
             ],
             context: ContextMeta {
-                event_time: "2026-02-18T00:00:00Z".into(),
+                event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
                 source: "user_prompt".into(),
                 scope: "design_session".into(),
                 agent_id: None,
@@ -348,11 +1259,13 @@ mod tests {
     }
 
     #[test]
-    fn graph_text_sanitizes_colons() {
+    fn graph_text_escapes_colons() {
         let output = compile(&sample_input());
         assert!(
-            output.graph_text.contains("2026-02-18T000000Z"),
-            "colons must be stripped from timestamps in graph text"
+            output
+                .graph_text
+                .contains(r"2026-02-18T00\u003a00\u003a00Z"),
+            "colons must be escaped in timestamps in graph text"
         );
         assert!(
             !output.graph_text.contains("00:00"),
@@ -362,6 +1275,56 @@ mod tests {
         assert!(output.graph_text.contains("design_session"));
     }
 
+    #[test]
+    fn graph_text_carries_the_utterance_and_its_hash() {
+        let output = compile(&sample_input());
+        assert!(output.graph_text.contains(
+            r#""id": "utterance", "type": "Constant", "value": "An agent needs long-term memory""#
+        ));
+        assert!(output
+            .graph_text
+            .contains(r#""op": "Hash", "inputs": ["utterance"]"#));
+        assert!(output.graph_text.contains(r#""utterance_wrapped""#));
+    }
+
+    #[test]
+    fn graph_text_omits_a_missing_utterance() {
+        let mut input = sample_input();
+        input.utterance = None;
+        let output = compile(&input);
+        assert!(!output.graph_text.contains("utterance"));
+    }
+
+    #[test]
+    fn desanitize_recovers_values_byte_for_byte() {
+        let values = [
+            "2026-02-18T00:00:00Z",
+            "agent://memory/recall",
+            r"a\u003a literal escape, a \ backslash, and a : colon",
+            r#"key: "quoted: value""#,
+            "",
+        ];
+        for value in values {
+            let json = serde_json::to_string(value).unwrap();
+            let sanitized = sanitize_for_graph(&json);
+            assert!(!sanitized.contains(':'), "{}", sanitized);
+            assert_eq!(desanitize(&sanitized), json);
+            let parsed: String = serde_json::from_str(&sanitized).unwrap();
+            assert_eq!(parsed, value);
+        }
+    }
+
+    #[test]
+    fn sanitize_keeps_colons_between_keys_and_values() {
+        let json = r#"{"time":"00:00","nested":{"url":"a://b"}}"#;
+        let sanitized = sanitize_for_graph(json);
+        assert_eq!(
+            sanitized,
+            r#"{"time":"00\u003a00","nested":{"url":"a\u003a//b"}}"#
+        );
+        assert_eq!(desanitize(&sanitized), json);
+    }
+
     #[test]
     fn graph_text_string_values_are_json_escaped() {
         let mut input = sample_input();
@@ -370,10 +1333,11 @@ mod tests {
             predicate: "tests".into(),
             object: "escaping".into(),
             confidence: 0.5,
+            ..Default::default()
         });
         let output = compile(&input);
         let normalized = normalize_label(r#"tricky "quoted" label"#);
-        let escaped = serde_json::to_string(&sanitize_for_graph(&normalized)).unwrap();
+        let escaped = graph_json(&normalized, SanitizePolicy::EscapeColons);
         assert!(
             output.graph_text.contains(&escaped),
             "label with quotes must be JSON-escaped in graph text"
@@ -384,14 +1348,9 @@ mod tests {
     fn compile_resolves_aliases() {
         let input = CompilerInput {
             utterance: None,
-            tuples: vec![SemanticTuple {
-                subject: "Agent".into(),
-                predicate: "needs".into(),
-                object: "LTM".into(),
-                confidence: 0.9,
-            }],
+            tuples: vec![SemanticTuple::from(("Agent", "needs", "LTM", 0.9))],
             context: ContextMeta {
-                event_time: "20260218T000000Z".into(),
+                event_time: "20260218T000000Z".parse().unwrap(),
                 source: "test".into(),
                 scope: "alias_test".into(),
                 agent_id: None,
@@ -419,7 +1378,7 @@ mod tests {
             utterance: None,
             tuples: vec![],
             context: ContextMeta {
-                event_time: "20260218T000000Z".into(),
+                event_time: "20260218T000000Z".parse().unwrap(),
                 source: "test".into(),
                 scope: "empty".into(),
                 agent_id: None,
@@ -431,4 +1390,63 @@ mod tests {
         assert!(output.graph_text.contains("\"entry_point\": \"context\""));
         assert!(output.record.concepts.is_empty());
     }
+
+    #[test]
+    fn node_ids_derive_from_hashes() {
+        let output = compile(&sample_input());
+        let agent = hasher::concept_hash("agent");
+        let agent_id = format!("\"concept_{}\"", agent.short_hex(12));
+        assert!(output.graph_text.contains(&agent_id));
+        for r in &output.record.relations {
+            let rel_id = format!("\"rel_{}\"", r.episode_hash.fmt_short(12));
+            assert!(output.graph_text.contains(&rel_id));
+        }
+        assert!(!output.graph_text.contains("\"concept_0\""));
+    }
+
+    #[test]
+    fn graph_text_ignores_record_order() {
+        let mut record = compile(&sample_input()).record;
+        let context = record.context.meta.clone();
+        let text = emit_graph_text(&record, &context);
+
+        record.concepts.reverse();
+        record.relations.reverse();
+        assert_eq!(emit_graph_text(&record, &context), text);
+    }
+
+    #[test]
+    fn adding_a_tuple_keeps_existing_ids() {
+        let before = compile(&sample_input());
+        let mut input = sample_input();
+        input
+            .tuples
+            .insert(0, SemanticTuple::from(("agent", "uses", "tools", 0.8)));
+        let after = compile(&input);
+
+        let lines: std::collections::HashSet<&str> = after.graph_text.lines().collect();
+        let removed: Vec<&str> = before
+            .graph_text
+            .lines()
+            .filter(|line| !lines.contains(line))
+            .collect();
+        // Only the MergeMap input list, the confidence of "agent" (now
+        // first seen at 0.8), and, if "tools" sorts first, the entry point
+        // change.
+        assert!(removed.len() <= 3, "changed lines: {:?}", removed);
+    }
+
+    #[test]
+    fn short_ids_lengthen_only_on_collision() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        let c = [0xffu8; 32];
+        a[7] = 0x10;
+        b[7] = 0x11;
+        let ids = short_ids(&[&a, &b, &c, &a], SHORT_ID_LEN);
+        assert_eq!(ids[0], hasher::short_hex(&a, 16));
+        assert_eq!(ids[1], hasher::short_hex(&b, 16));
+        assert_eq!(ids[2], "ffffffffffff");
+        assert_eq!(ids[3], ids[0], "equal hashes share an id");
+    }
 }
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::types::{ContextHash, Span};
+
+/// Why [`try_compile`](super::try_compile) or
+/// [`compile_into`](super::compile_into) rejected an input.
+///
+/// Tuple errors carry the index of the offending tuple in
+/// `CompilerInput::tuples`, so extraction bugs can be traced back to
+/// the tuple that caused them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompilerError {
+    /// The subject or object label is blank after normalization.
+    /// `field` is `"subject"` or `"object"`.
+    EmptyLabel {
+        tuple_index: usize,
+        field: &'static str,
+    },
+    /// The predicate is blank after normalization.
+    EmptyPredicate { tuple_index: usize },
+    /// The predicate, normalized and alias-resolved, is not in
+    /// `CompilerOptions::allowed_predicates`.
+    UnknownPredicate {
+        tuple_index: usize,
+        predicate: String,
+    },
+    /// The confidence is not a finite number in `[0, 1]`.
+    InvalidConfidence { tuple_index: usize, value: f64 },
+    /// A span does not fit the utterance: it is reversed, runs past its
+    /// end, splits a character, or there is no utterance. `field` is
+    /// `"subject"`, `"object"`, or `"evidence"`.
+    InvalidSpan {
+        tuple_index: usize,
+        field: &'static str,
+        span: Span,
+    },
+    /// A qualifier key or value is blank after normalization. `key` is the
+    /// qualifier's key as written.
+    EmptyQualifier { tuple_index: usize, key: String },
+    /// A context field that feeds the `ContextHash` is blank. `field` is
+    /// `"source"` or `"scope"`.
+    EmptyContextField { field: &'static str },
+    /// Under [`SanitizePolicy::Strict`](super::SanitizePolicy::Strict), a
+    /// string value contains a colon, which the 0-openclaw parser cannot
+    /// read.
+    UnsafeGraphValue { value: String },
+    /// [`compile_into`](super::compile_into) was given tuples from a
+    /// context other than the record's.
+    ContextMismatch {
+        expected: ContextHash,
+        found: ContextHash,
+    },
+    /// [`compile_into`](super::compile_into) was given options with a
+    /// namespace other than the record's.
+    NamespaceMismatch {
+        expected: Option<String>,
+        found: Option<String>,
+    },
+}
+
+impl fmt::Display for CompilerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompilerError::EmptyLabel { tuple_index, field } => {
+                write!(f, "tuple {}: {} label is empty", tuple_index, field)
+            }
+            CompilerError::EmptyPredicate { tuple_index } => {
+                write!(f, "tuple {}: predicate is empty", tuple_index)
+            }
+            CompilerError::UnknownPredicate {
+                tuple_index,
+                predicate,
+            } => write!(
+                f,
+                "tuple {}: predicate {:?} is not in the allowed predicates",
+                tuple_index, predicate
+            ),
+            CompilerError::InvalidConfidence { tuple_index, value } => write!(
+                f,
+                "tuple {}: confidence {} is not in [0, 1]",
+                tuple_index, value
+            ),
+            CompilerError::InvalidSpan {
+                tuple_index,
+                field,
+                span,
+            } => write!(
+                f,
+                "tuple {}: {} span {} does not fit the utterance",
+                tuple_index, field, span
+            ),
+            CompilerError::EmptyQualifier { tuple_index, key } => {
+                write!(f, "tuple {}: qualifier {:?} is empty", tuple_index, key)
+            }
+            CompilerError::EmptyContextField { field } => {
+                write!(f, "context {} is empty", field)
+            }
+            CompilerError::UnsafeGraphValue { value } => write!(
+                f,
+                "graph value {:?} contains a colon, which the 0-openclaw parser cannot read",
+                value
+            ),
+            CompilerError::ContextMismatch { expected, found } => write!(
+                f,
+                "context {} does not match the record's context {}",
+                found, expected
+            ),
+            CompilerError::NamespaceMismatch { expected, found } => write!(
+                f,
+                "namespace {:?} does not match the record's namespace {:?}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CompilerError {}
@@ -1,57 +1,383 @@
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 
-use crate::types::{ConceptHash, ContextHash, ContextMeta, EpisodeHash, FactHash};
+use std::collections::BTreeMap;
+
+use crate::types::{
+    ConceptHash, ConceptNode, ContextHash, ContextMeta, EpisodeHash, EventTime, FactHash,
+    MemoryRecord, Polarity, RecordHash, RelationNode, ShortHex,
+};
 
 /// sha256(normalized_label)
 pub fn concept_hash(normalized_label: &str) -> ConceptHash {
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&Sha256::digest(normalized_label.as_bytes()));
-    ConceptHash(hash)
+    concept_hash_in(None, normalized_label)
+}
+
+/// sha256(namespace + 0x00 + normalized_label)
+///
+/// Like [`concept_hash`], for concepts of one domain: the same label in
+/// two namespaces hashes to two concepts. The NUL byte keeps namespace and
+/// label apart, so `("a", "bc")` and `("ab", "c")` hash differently.
+pub fn concept_hash_ns(namespace: &str, normalized_label: &str) -> ConceptHash {
+    concept_hash_in(Some(namespace), normalized_label)
 }
 
-/// sha256(subject_label + "|" + predicate + "|" + object_label)
+/// sha256(subject_label + "|" + predicate + "|" + object_label), each
+/// part [escaped](join_escaped).
 ///
 /// The pipe separator prevents ambiguity when labels contain parts of other
-/// labels (e.g., "a|b" vs "a" "|" "b").
+/// labels (e.g., "ab" "c" vs "a" "bc"); escaping keeps pipes inside labels
+/// from passing for separators, so ("a|b", "c", "d") and ("a", "b|c", "d")
+/// hash differently.
 pub fn fact_hash(subject_label: &str, predicate: &str, object_label: &str) -> FactHash {
-    let input = format!("{}|{}|{}", subject_label, predicate, object_label);
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&Sha256::digest(input.as_bytes()));
-    FactHash(hash)
+    fact_hash_in(
+        None,
+        subject_label,
+        predicate,
+        object_label,
+        Polarity::Positive,
+    )
+}
+
+/// Like [`fact_hash`], but for a fact of the given polarity.
+///
+/// Positive facts hash exactly as [`fact_hash`] does, so existing hashes
+/// stay valid; negative facts append `"|neg"`:
+/// sha256(subject_label + "|" + predicate + "|" + object_label + "|neg"),
+/// each label [escaped](join_escaped).
+pub fn fact_hash_with_polarity(
+    subject_label: &str,
+    predicate: &str,
+    object_label: &str,
+    polarity: Polarity,
+) -> FactHash {
+    fact_hash_in(None, subject_label, predicate, object_label, polarity)
+}
+
+/// sha256(namespace + 0x00 + fact), where `fact` is what
+/// [`fact_hash_with_polarity`] hashes: the fact hash of a namespace, as
+/// [`concept_hash_ns`] is its concept hash.
+pub fn fact_hash_ns(
+    namespace: &str,
+    subject_label: &str,
+    predicate: &str,
+    object_label: &str,
+    polarity: Polarity,
+) -> FactHash {
+    fact_hash_in(
+        Some(namespace),
+        subject_label,
+        predicate,
+        object_label,
+        polarity,
+    )
 }
 
-/// sha256(event_time + "|" + source + "|" + scope)
+/// [`concept_hash_ns`] in `namespace`, or [`concept_hash`] without one.
+pub(crate) fn concept_hash_in(namespace: Option<&str>, normalized_label: &str) -> ConceptHash {
+    ConceptHash::from_bytes(sha256(&concept_preimage(namespace, normalized_label)))
+}
+
+/// [`fact_hash_ns`] in `namespace`, or [`fact_hash_with_polarity`]
+/// without one.
+pub(crate) fn fact_hash_in(
+    namespace: Option<&str>,
+    subject_label: &str,
+    predicate: &str,
+    object_label: &str,
+    polarity: Polarity,
+) -> FactHash {
+    let preimage = fact_preimage(namespace, subject_label, predicate, object_label, polarity);
+    FactHash::from_bytes(sha256(&preimage))
+}
+
+// The bytes each hash is taken over, kept apart from the hashing so that
+// a runtime's `hash` can be checked against the compiler's on the same
+// input (see `adapters::verify`).
+
+pub(crate) fn concept_preimage(namespace: Option<&str>, normalized_label: &str) -> Vec<u8> {
+    namespaced(namespace, normalized_label)
+}
+
+pub(crate) fn fact_preimage(
+    namespace: Option<&str>,
+    subject_label: &str,
+    predicate: &str,
+    object_label: &str,
+    polarity: Polarity,
+) -> Vec<u8> {
+    let input = match polarity {
+        Polarity::Positive => join_escaped(&[subject_label, predicate, object_label]),
+        Polarity::Negative => join_escaped(&[subject_label, predicate, object_label, "neg"]),
+    };
+    namespaced(namespace, &input)
+}
+
+pub(crate) fn context_preimage(meta: &ContextMeta) -> Vec<u8> {
+    let event_time = meta.event_time.to_string();
+    join_escaped(&[&event_time, &meta.source, &meta.scope]).into_bytes()
+}
+
+/// Qualifiers are sorted here; see [`qualified_episode_hash`].
+pub(crate) fn episode_preimage(
+    fact: &[u8],
+    ctx: &[u8],
+    qualifiers: &[(String, String)],
+) -> Vec<u8> {
+    let mut sorted: Vec<&(String, String)> = qualifiers.iter().collect();
+    sorted.sort();
+    let mut preimage = [fact, ctx].concat();
+    for (key, value) in sorted {
+        preimage.push(b'|');
+        preimage.extend_from_slice(join_escaped(&[key, value]).as_bytes());
+    }
+    preimage
+}
+
+/// `input`, prefixed by `namespace` and a NUL byte if there is one.
+fn namespaced(namespace: Option<&str>, input: &str) -> Vec<u8> {
+    match namespace {
+        Some(namespace) => [namespace.as_bytes(), &[0], input.as_bytes()].concat(),
+        None => input.as_bytes().to_vec(),
+    }
+}
+
+fn sha256(input: &[u8]) -> [u8; 32] {
+    Sha256::digest(input).into()
+}
+
+/// `parts` joined with `|`, with every `\` and `|` inside a part escaped
+/// by a backslash, so that the joined string splits back into its parts
+/// one way only.
+///
+/// Parts without either character are joined as they are: hashes of
+/// inputs without pipes or backslashes are the same as before escaping
+/// was introduced. Only inputs containing one hash differently, and those
+/// could collide before.
+pub fn join_escaped(parts: &[&str]) -> String {
+    let mut joined = String::with_capacity(parts.iter().map(|p| p.len() + 1).sum());
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            joined.push('|');
+        }
+        for c in part.chars() {
+            if c == '|' || c == '\\' {
+                joined.push('\\');
+            }
+            joined.push(c);
+        }
+    }
+    joined
+}
+
+/// sha256(event_time + "|" + source + "|" + scope), each part
+/// [escaped](join_escaped), the time in its canonical RFC 3339 rendering
+/// (see [`EventTime`]), so offsets do not change the hash.
 pub fn context_hash(meta: &ContextMeta) -> ContextHash {
-    let input = format!("{}|{}|{}", meta.event_time, meta.source, meta.scope);
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&Sha256::digest(input.as_bytes()));
-    ContextHash(hash)
+    ContextHash::from_bytes(sha256(&context_preimage(meta)))
 }
 
 /// sha256(fact_hash_bytes ++ context_hash_bytes)
 ///
 /// Concatenates the raw 32-byte arrays (64 bytes total) before hashing.
 pub fn episode_hash(fact: &FactHash, ctx: &ContextHash) -> EpisodeHash {
-    let mut combined = [0u8; 64];
-    combined[..32].copy_from_slice(&fact.0);
-    combined[32..].copy_from_slice(&ctx.0);
+    qualified_episode_hash(fact, ctx, &[])
+}
+
+/// Like [`episode_hash`], for an episode carrying qualifiers ("met *in
+/// Paris*"). Qualifiers describe the observation, not the fact: they leave
+/// the `FactHash` alone but separate episodes, so the same fact observed
+/// twice in one context with different qualifiers is stored twice.
+///
+/// Without qualifiers this is exactly [`episode_hash`]; otherwise each
+/// `(key, value)` pair, sorted, is appended to the 64 hashed bytes as
+/// `"|" + key + "|" + value`, key and value [escaped](join_escaped). Keys
+/// and values must already be normalized.
+pub fn qualified_episode_hash(
+    fact: &FactHash,
+    ctx: &ContextHash,
+    qualifiers: &[(String, String)],
+) -> EpisodeHash {
+    let preimage = episode_preimage(fact.as_bytes(), ctx.as_bytes(), qualifiers);
+    EpisodeHash::from_bytes(sha256(&preimage))
+}
+
+/// Label of the statement concept that reifies a qualified episode:
+/// `"statement-"` followed by the episode hash in hex. Qualifier relations
+/// hang off this concept, so its hash (`concept_hash` of the label) is
+/// how a store finds them.
+pub fn statement_label(episode: &EpisodeHash) -> String {
+    format!("statement-{}", episode)
+}
+
+/// A record as serialized by [`canonical_record_bytes`].
+/// Serde writes fields in declaration order, so the JSON is the same
+/// whatever features `serde_json` is built with.
+#[derive(Serialize)]
+struct TraceRecord<'a> {
+    context: &'a ContextHash,
+    concepts: Vec<TraceConcept<'a>>,
+    relations: Vec<TraceRelation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prev: Option<&'a RecordHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<&'a str>,
+}
+
+/// The parts of a concept its graph carries.
+#[derive(Serialize)]
+struct TraceConcept<'a> {
+    hash: &'a ConceptHash,
+    label: &'a str,
+    aliases: &'a [String],
+    confidence: f64,
+    created_at: &'a EventTime,
+    updated_at: &'a EventTime,
+}
+
+/// The parts of a relation its graph carries.
+#[derive(Serialize)]
+struct TraceRelation<'a> {
+    episode_hash: &'a EpisodeHash,
+    fact_hash: &'a FactHash,
+    subject_hash: &'a ConceptHash,
+    predicate: &'a str,
+    object_hash: &'a ConceptHash,
+    confidence: f64,
+    polarity: Polarity,
+    valid_from: Option<&'a str>,
+    valid_to: Option<&'a str>,
+}
+
+/// The canonical serialization of a record: compact JSON of the context
+/// hash, the concepts sorted by hash, the relations sorted by episode
+/// hash, each with its fields in a fixed order, the hash of the previous
+/// record if the record is chained, and the namespace if the record was
+/// compiled in one. This is what
+/// [`trace_hash`] hashes and a record's signature signs.
+///
+/// It covers what the `.0` graph of the record carries, so a record
+/// decompiled from its graph serializes the same. Concept display labels,
+/// relation timestamps and spans, the context's agent, session, and
+/// metadata, and the record's proof are left out.
+pub fn canonical_record_bytes(record: &MemoryRecord) -> Vec<u8> {
+    let mut concepts: Vec<&ConceptNode> = record.concepts.iter().collect();
+    concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+    let mut relations: Vec<&RelationNode> = record.relations.iter().collect();
+    relations.sort_by(|a, b| a.episode_hash.cmp(&b.episode_hash));
+    let canonical = TraceRecord {
+        context: &record.context.hash,
+        concepts: concepts
+            .into_iter()
+            .map(|c| TraceConcept {
+                hash: &c.hash,
+                label: &c.label,
+                aliases: &c.aliases,
+                confidence: c.confidence,
+                created_at: &c.created_at,
+                updated_at: &c.updated_at,
+            })
+            .collect(),
+        relations: relations
+            .into_iter()
+            .map(|r| TraceRelation {
+                episode_hash: &r.episode_hash,
+                fact_hash: &r.fact_hash,
+                subject_hash: &r.subject_hash,
+                predicate: &r.predicate,
+                object_hash: &r.object_hash,
+                confidence: r.confidence,
+                polarity: r.polarity,
+                valid_from: r.valid_from.as_deref(),
+                valid_to: r.valid_to.as_deref(),
+            })
+            .collect(),
+        prev: record.prev.as_ref(),
+        namespace: record.namespace.as_deref(),
+    };
+    serde_json::to_vec(&canonical).expect("trace records serialize")
+}
+
+/// sha256 of the [canonical bytes](canonical_record_bytes) of a record.
+pub fn trace_hash(record: &MemoryRecord) -> [u8; 32] {
     let mut hash = [0u8; 32];
-    hash.copy_from_slice(&Sha256::digest(combined));
-    EpisodeHash(hash)
+    hash.copy_from_slice(&Sha256::digest(canonical_record_bytes(record)));
+    hash
 }
 
-/// Return the first `n` hex characters of a 32-byte hash.
-/// Used for short display references (e.g., first 8 chars in .0c format).
+/// A record as serialized by [`record_hash`].
+#[derive(Serialize)]
+struct CanonicalRecord<'a> {
+    context_hash: &'a ContextHash,
+    event_time: &'a EventTime,
+    source: &'a str,
+    scope: &'a str,
+    agent_id: Option<&'a str>,
+    session_id: Option<&'a str>,
+    metadata: Option<BTreeMap<&'a str, &'a str>>,
+    concepts: Vec<&'a ConceptNode>,
+    relations: Vec<&'a RelationNode>,
+    // Left out when empty, so records without them hash as they did
+    // before they existed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    extensions: Vec<Extension<'a>>,
+}
+
+/// Optional record fields added after record hashes were first computed.
+/// Each is tagged with its variant, so no two sets of them serialize
+/// alike.
+#[derive(Serialize)]
+enum Extension<'a> {
+    Prev(&'a RecordHash),
+    Namespace(&'a str),
+}
+
+/// sha256 of the whole record, serialized with bincode: the context with
+/// its metadata sorted by key, the concepts sorted by hash, and the
+/// relations sorted by episode hash, every field in declaration order and
+/// every float as its IEEE 754 bits.
+///
+/// Unlike [`trace_hash`], this covers every field of the record but its
+/// proof, which attests to the content rather than being part of it: the
+/// same content in any order hashes the same, and any other change
+/// changes the hash.
+pub fn record_hash(record: &MemoryRecord) -> RecordHash {
+    let meta = &record.context.meta;
+    let mut concepts: Vec<&ConceptNode> = record.concepts.iter().collect();
+    concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+    let mut relations: Vec<&RelationNode> = record.relations.iter().collect();
+    relations.sort_by(|a, b| a.episode_hash.cmp(&b.episode_hash));
+    let canonical = CanonicalRecord {
+        context_hash: &record.context.hash,
+        event_time: &meta.event_time,
+        source: &meta.source,
+        scope: &meta.scope,
+        agent_id: meta.agent_id.as_deref(),
+        session_id: meta.session_id.as_deref(),
+        metadata: meta
+            .metadata
+            .as_ref()
+            .map(|m| m.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()),
+        concepts,
+        relations,
+        extensions: record
+            .prev
+            .iter()
+            .map(Extension::Prev)
+            .chain(record.namespace.as_deref().map(Extension::Namespace))
+            .collect(),
+    };
+    let bytes = bincode::serialize(&canonical).expect("records serialize");
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(bytes));
+    RecordHash::from_bytes(hash)
+}
+
+/// Return the first `n` hex characters of a 32-byte hash, or all 64 if
+/// `n` is larger. Used for short display references (e.g., first 8 chars
+/// in .0c format); the hash types have it as a method too.
 pub fn short_hex(hash: &[u8; 32], n: usize) -> String {
-    let n = n.min(64);
-    let bytes_needed = (n + 1) / 2;
-    let mut s = String::with_capacity(n);
-    for &b in &hash[..bytes_needed] {
-        use std::fmt::Write;
-        let _ = write!(s, "{:02x}", b);
-    }
-    s.truncate(n);
-    s
+    ShortHex::new(hash, n).to_string()
 }
 
 #[cfg(test)]
@@ -79,11 +405,52 @@ mod tests {
         assert_eq!(f1, f2, "same (s,p,o) must produce identical FactHash");
     }
 
+    #[test]
+    fn polarity_separates_facts() {
+        let positive = fact_hash_with_polarity("user", "likes", "mushrooms", Polarity::Positive);
+        let negative = fact_hash_with_polarity("user", "likes", "mushrooms", Polarity::Negative);
+        assert_eq!(positive, fact_hash("user", "likes", "mushrooms"));
+        assert_ne!(positive, negative);
+    }
+
+    #[test]
+    fn qualifiers_separate_episodes_not_facts() {
+        let fh = fact_hash("alice", "met", "bob");
+        let ctx = ContextHash::from_bytes([7; 32]);
+        let paris = vec![("location".to_string(), "paris".to_string())];
+        let london = vec![("location".to_string(), "london".to_string())];
+
+        assert_eq!(
+            qualified_episode_hash(&fh, &ctx, &[]),
+            episode_hash(&fh, &ctx)
+        );
+        assert_ne!(
+            qualified_episode_hash(&fh, &ctx, &paris),
+            qualified_episode_hash(&fh, &ctx, &london)
+        );
+        assert_ne!(
+            qualified_episode_hash(&fh, &ctx, &paris),
+            episode_hash(&fh, &ctx)
+        );
+    }
+
+    #[test]
+    fn qualifier_order_does_not_matter() {
+        let fh = fact_hash("alice", "met", "bob");
+        let ctx = ContextHash::from_bytes([7; 32]);
+        let location = ("location".to_string(), "paris".to_string());
+        let day = ("day".to_string(), "tuesday".to_string());
+        assert_eq!(
+            qualified_episode_hash(&fh, &ctx, &[location.clone(), day.clone()]),
+            qualified_episode_hash(&fh, &ctx, &[day, location])
+        );
+    }
+
     #[test]
     fn episode_hash_varies_with_context() {
         let fh = fact_hash("agent", "needs", "long-term-memory");
         let ctx1 = ContextMeta {
-            event_time: "2026-02-18T00:00:00Z".into(),
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
             source: "user_prompt".into(),
             scope: "conversation_1".into(),
             agent_id: None,
@@ -91,7 +458,7 @@ mod tests {
             metadata: None,
         };
         let ctx2 = ContextMeta {
-            event_time: "2026-02-18T01:00:00Z".into(),
+            event_time: "2026-02-18T01:00:00Z".parse().unwrap(),
             source: "user_prompt".into(),
             scope: "conversation_2".into(),
             agent_id: None,
@@ -119,4 +486,17 @@ mod tests {
             "d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
         );
     }
+
+    #[test]
+    fn short_hex_cuts_at_any_length() {
+        let h = concept_hash("agent");
+        assert_eq!(h.short_hex(0), "");
+        assert_eq!(h.short_hex(1), "d");
+        assert_eq!(h.short_hex(7), "d4f0bc5");
+        assert_eq!(h.short_hex(8), "d4f0bc5a");
+        assert_eq!(h.short_hex(64), h.to_string());
+        assert_eq!(h.short_hex(100), h.to_string());
+        assert_eq!(short_hex(h.as_bytes(), 7), h.short_hex(7));
+        assert_eq!(format!("[{}]", h.fmt_short(5)), "[d4f0b]");
+    }
 }
@@ -0,0 +1,236 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::compiler::hasher;
+use crate::types::*;
+
+/// One way a [`MemoryRecord`] disagrees with itself; see
+/// [`check_record`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum RecordIssue {
+    /// The context node's hash is not the hash of its metadata.
+    ContextHashMismatch {
+        hash: ContextHash,
+        expected: ContextHash,
+    },
+    /// A concept's hash is not the hash of its label.
+    ConceptHashMismatch {
+        label: String,
+        hash: ConceptHash,
+        expected: ConceptHash,
+    },
+    /// A relation's subject or object is not among the record's concepts.
+    MissingConcept {
+        episode: EpisodeHash,
+        concept: ConceptHash,
+    },
+    /// A relation points at a context other than the record's.
+    ForeignContext {
+        episode: EpisodeHash,
+        context: ContextHash,
+    },
+    /// A relation's fact hash is not the hash of its subject, predicate,
+    /// object, and polarity.
+    FactHashMismatch {
+        episode: EpisodeHash,
+        hash: FactHash,
+        expected: FactHash,
+    },
+    /// A relation's episode hash is not the hash of its fact, context, and
+    /// qualifiers.
+    EpisodeHashMismatch {
+        hash: EpisodeHash,
+        expected: EpisodeHash,
+    },
+    /// Two relations share an episode hash.
+    DuplicateEpisode(EpisodeHash),
+}
+
+impl fmt::Display for RecordIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordIssue::ContextHashMismatch { hash, expected } => {
+                write!(f, "context {} should hash to {}", hash, expected)
+            }
+            RecordIssue::ConceptHashMismatch {
+                label,
+                hash,
+                expected,
+            } => write!(
+                f,
+                "concept {:?} has hash {}, expected {}",
+                label, hash, expected
+            ),
+            RecordIssue::MissingConcept { episode, concept } => write!(
+                f,
+                "episode {} refers to concept {}, which the record lacks",
+                episode, concept
+            ),
+            RecordIssue::ForeignContext { episode, context } => write!(
+                f,
+                "episode {} belongs to context {}, not the record's",
+                episode, context
+            ),
+            RecordIssue::FactHashMismatch {
+                episode,
+                hash,
+                expected,
+            } => write!(
+                f,
+                "episode {} has fact hash {}, expected {}",
+                episode, hash, expected
+            ),
+            RecordIssue::EpisodeHashMismatch { hash, expected } => {
+                write!(f, "episode {} should hash to {}", hash, expected)
+            }
+            RecordIssue::DuplicateEpisode(episode) => {
+                write!(f, "episode {} appears more than once", episode)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecordIssue {}
+
+/// Whether `label` is the label of a statement concept, which is hashed
+/// outside the record's namespace (see
+/// [`statement_label`](hasher::statement_label)).
+pub(crate) fn is_statement_label(label: &str) -> bool {
+    label
+        .strip_prefix("statement-")
+        .map_or(false, |hex| EpisodeHash::from_hex(hex).is_ok())
+}
+
+/// Qualifiers of each statement concept of `record`, as the compiler
+/// hashed them: the normalized key and the value's label. `labels` maps
+/// the record's concept hashes to their labels.
+pub(crate) fn statement_qualifiers<'a>(
+    record: &'a MemoryRecord,
+    labels: &HashMap<&ConceptHash, &str>,
+) -> HashMap<&'a ConceptHash, Vec<(String, String)>> {
+    let mut qualifiers: HashMap<&ConceptHash, Vec<(String, String)>> = HashMap::new();
+    for relation in &record.relations {
+        let Some(value) = labels.get(&relation.object_hash) else {
+            continue;
+        };
+        if labels
+            .get(&relation.subject_hash)
+            .map_or(false, |label| is_statement_label(label))
+        {
+            qualifiers
+                .entry(&relation.subject_hash)
+                .or_default()
+                .push((relation.predicate.to_string(), value.to_string()));
+        }
+    }
+    qualifiers
+}
+
+/// Check that `record` is internally consistent: every hash recomputes
+/// from what it names, in the record's namespace, and every relation
+/// stays inside the record.
+///
+/// Concept hashes must match their labels and the context hash its
+/// metadata. Each relation must connect concepts the record includes,
+/// belong to the record's context, and carry the fact hash of its
+/// endpoints' labels, predicate, and polarity; its episode hash must
+/// follow from the fact, its context, and the qualifier relations hanging
+/// off its statement concept, if any. No two relations may share an
+/// episode hash.
+///
+/// Returns every issue found, in that order, relations in record order.
+/// Records from the compiler always pass.
+pub fn check_record(record: &MemoryRecord) -> Result<(), Vec<RecordIssue>> {
+    let namespace = record.namespace.as_deref();
+    let mut issues = Vec::new();
+
+    let expected = hasher::context_hash(&record.context.meta);
+    if record.context.hash != expected {
+        issues.push(RecordIssue::ContextHashMismatch {
+            hash: record.context.hash.clone(),
+            expected,
+        });
+    }
+
+    let mut labels: HashMap<&ConceptHash, &str> = HashMap::new();
+    for concept in &record.concepts {
+        let expected = if is_statement_label(&concept.label) {
+            hasher::concept_hash(&concept.label)
+        } else {
+            hasher::concept_hash_in(namespace, &concept.label)
+        };
+        if concept.hash != expected {
+            issues.push(RecordIssue::ConceptHashMismatch {
+                label: concept.label.clone(),
+                hash: concept.hash.clone(),
+                expected,
+            });
+        }
+        labels.insert(&concept.hash, &concept.label);
+    }
+
+    let qualifiers = statement_qualifiers(record, &labels);
+
+    let mut seen: HashSet<&EpisodeHash> = HashSet::new();
+    for relation in &record.relations {
+        let episode = &relation.episode_hash;
+        if !seen.insert(episode) {
+            issues.push(RecordIssue::DuplicateEpisode(episode.clone()));
+        }
+        if relation.context_hash != record.context.hash {
+            issues.push(RecordIssue::ForeignContext {
+                episode: episode.clone(),
+                context: relation.context_hash.clone(),
+            });
+        }
+        let mut endpoints = Vec::with_capacity(2);
+        for concept in [&relation.subject_hash, &relation.object_hash] {
+            match labels.get(concept) {
+                Some(label) => endpoints.push(*label),
+                None => issues.push(RecordIssue::MissingConcept {
+                    episode: episode.clone(),
+                    concept: concept.clone(),
+                }),
+            }
+        }
+        let [subject, object] = endpoints[..] else {
+            continue;
+        };
+
+        let expected = hasher::fact_hash_in(
+            namespace,
+            subject,
+            &relation.predicate,
+            object,
+            relation.polarity,
+        );
+        if relation.fact_hash != expected {
+            issues.push(RecordIssue::FactHashMismatch {
+                episode: episode.clone(),
+                hash: relation.fact_hash.clone(),
+                expected,
+            });
+        }
+
+        let statement = hasher::concept_hash(&hasher::statement_label(episode));
+        let expected = hasher::qualified_episode_hash(
+            &relation.fact_hash,
+            &relation.context_hash,
+            qualifiers.get(&statement).map_or(&[], Vec::as_slice),
+        );
+        if *episode != expected {
+            issues.push(RecordIssue::EpisodeHashMismatch {
+                hash: episode.clone(),
+                expected,
+            });
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
@@ -0,0 +1,641 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{CompilerInput, ContextMeta, SemanticTuple};
+
+/// Why a tuple stream could not be read.
+#[derive(Debug)]
+pub enum IngestError {
+    Io(io::Error),
+    /// Line `line` (1-based) is not JSON, or not a context or tuple line.
+    Json {
+        line: usize,
+        error: serde_json::Error,
+    },
+    /// The tuple on line `line` comes before any context line.
+    MissingContext {
+        line: usize,
+    },
+    /// [`read_ndjson`] found a second context line on line `line`; use
+    /// [`read_ndjson_blocks`] for streams of several contexts.
+    UnexpectedContext {
+        line: usize,
+    },
+    /// The stream holds no context line and no tuples.
+    Empty,
+    /// Row `row` (1-based, counting a header row) of a CSV file could not
+    /// be read.
+    #[cfg(feature = "io")]
+    Csv {
+        row: usize,
+        error: csv::Error,
+    },
+    /// Row `row` (1-based, counting a header row) of a CSV file is not a
+    /// tuple.
+    Row {
+        row: usize,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for IngestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IngestError::Io(e) => write!(f, "tuple stream I/O error: {}", e),
+            IngestError::Json { line, error } => write!(f, "line {}: {}", line, error),
+            IngestError::MissingContext { line } => {
+                write!(f, "line {}: tuple before any context line", line)
+            }
+            IngestError::UnexpectedContext { line } => {
+                write!(
+                    f,
+                    "line {}: a second context in a single-context stream",
+                    line
+                )
+            }
+            IngestError::Empty => write!(f, "tuple stream is empty"),
+            #[cfg(feature = "io")]
+            IngestError::Csv { row, error } => write!(f, "row {}: {}", row, error),
+            IngestError::Row { row, reason } => write!(f, "row {}: {}", row, reason),
+        }
+    }
+}
+
+impl std::error::Error for IngestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IngestError::Io(e) => Some(e),
+            IngestError::Json { error, .. } => Some(error),
+            #[cfg(feature = "io")]
+            IngestError::Csv { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for IngestError {
+    fn from(e: io::Error) -> Self {
+        IngestError::Io(e)
+    }
+}
+
+/// The line that opens a context block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContextLine {
+    context: ContextMeta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    utterance: Option<String>,
+}
+
+/// One non-blank line of a tuple stream.
+enum Line {
+    Context(ContextLine),
+    Tuple(SemanticTuple),
+}
+
+/// Parse line `line_no` of a tuple stream. An object with a `context` key
+/// is a context line, and so is a bare [`ContextMeta`], an object with an
+/// `event_time` key; anything else must be a [`SemanticTuple`].
+fn parse_line(text: &str, line_no: usize) -> Result<Line, IngestError> {
+    let json = |error| IngestError::Json {
+        line: line_no,
+        error,
+    };
+    let value: Value = serde_json::from_str(text).map_err(json)?;
+    let is_key = |key| value.get(key).is_some();
+    if is_key("context") {
+        serde_json::from_value(value)
+            .map(Line::Context)
+            .map_err(json)
+    } else if is_key("event_time") {
+        serde_json::from_value(value)
+            .map(|context| {
+                Line::Context(ContextLine {
+                    context,
+                    utterance: None,
+                })
+            })
+            .map_err(json)
+    } else {
+        serde_json::from_value(value).map(Line::Tuple).map_err(json)
+    }
+}
+
+/// Read a newline-delimited JSON stream of one context into a
+/// [`CompilerInput`]: a context line, then one [`SemanticTuple`] per line.
+///
+/// The context line is `{"context": <ContextMeta>, "utterance": "..."}`,
+/// with the utterance optional, or a bare [`ContextMeta`] object. Blank
+/// lines are skipped, `\r\n` line endings are accepted, and errors carry
+/// the 1-based number of the line they were found on.
+///
+/// ```text
+/// {"context": {"event_time": "2026-02-18T00:00:00Z", "source": "chat", "scope": "s1"}}
+/// {"subject": "Agent", "predicate": "needs", "object": "Memory", "confidence": 0.9}
+/// ```
+pub fn read_ndjson<R: BufRead>(reader: R) -> Result<CompilerInput, IngestError> {
+    let mut header: Option<ContextLine> = None;
+    let mut tuples = Vec::new();
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line_no = index + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+        match parse_line(&text, line_no)? {
+            Line::Context(_) if header.is_some() => {
+                return Err(IngestError::UnexpectedContext { line: line_no })
+            }
+            Line::Context(line) => header = Some(line),
+            Line::Tuple(_) if header.is_none() => {
+                return Err(IngestError::MissingContext { line: line_no })
+            }
+            Line::Tuple(tuple) => tuples.push(tuple),
+        }
+    }
+    let header = header.ok_or(IngestError::Empty)?;
+    Ok(CompilerInput {
+        utterance: header.utterance,
+        tuples,
+        context: header.context,
+    })
+}
+
+/// Read a newline-delimited JSON stream of several context blocks, one
+/// [`CompilerInput`] per block; see [`read_ndjson_blocks`].
+pub struct NdjsonBlocks<R> {
+    lines: io::Lines<R>,
+    line_no: usize,
+    header: Option<ContextLine>,
+    /// A context line read ahead, which opens the next block.
+    pending: Option<ContextLine>,
+    done: bool,
+}
+
+impl<R: BufRead> NdjsonBlocks<R> {
+    fn input(header: &ContextLine, tuples: Vec<SemanticTuple>) -> CompilerInput {
+        CompilerInput {
+            utterance: header.utterance.clone(),
+            tuples,
+            context: header.context.clone(),
+        }
+    }
+
+    fn next_block(&mut self) -> Result<Option<CompilerInput>, IngestError> {
+        let mut opened = false;
+        if let Some(header) = self.pending.take() {
+            self.header = Some(header);
+            opened = true;
+        }
+        let mut tuples = Vec::new();
+        for text in self.lines.by_ref() {
+            let text = text?;
+            self.line_no += 1;
+            if text.trim().is_empty() {
+                if opened || !tuples.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            match parse_line(&text, self.line_no)? {
+                Line::Context(line) if opened || !tuples.is_empty() => {
+                    self.pending = Some(line);
+                    break;
+                }
+                Line::Context(line) => {
+                    self.header = Some(line);
+                    opened = true;
+                }
+                Line::Tuple(tuple) => {
+                    if self.header.is_none() {
+                        return Err(IngestError::MissingContext { line: self.line_no });
+                    }
+                    tuples.push(tuple);
+                }
+            }
+        }
+        match &self.header {
+            Some(header) if opened || !tuples.is_empty() => Ok(Some(Self::input(header, tuples))),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for NdjsonBlocks<R> {
+    type Item = Result<CompilerInput, IngestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let block = self.next_block();
+        if !matches!(block, Ok(Some(_))) {
+            self.done = true;
+        }
+        block.transpose()
+    }
+}
+
+/// Read a newline-delimited JSON stream of context blocks lazily, one
+/// [`CompilerInput`] per block, for streams too long to hold at once.
+///
+/// Lines are as for [`read_ndjson`]. A block ends at a blank line or at
+/// the next context line. A block that starts without a context line of
+/// its own, after a blank line, has the context and utterance of the
+/// block before it. Runs of blank lines yield no empty blocks, but a
+/// context line followed by no tuples yields an input without tuples.
+///
+/// Iteration stops after the first error.
+pub fn read_ndjson_blocks<R: BufRead>(reader: R) -> NdjsonBlocks<R> {
+    NdjsonBlocks {
+        lines: reader.lines(),
+        line_no: 0,
+        header: None,
+        pending: None,
+        done: false,
+    }
+}
+
+/// Write `input` as newline-delimited JSON that [`read_ndjson`] reads
+/// back: its context line, then one line per tuple.
+pub fn write_ndjson<W: Write>(input: &CompilerInput, mut writer: W) -> io::Result<()> {
+    let header = ContextLine {
+        context: input.context.clone(),
+        utterance: input.utterance.clone(),
+    };
+    serde_json::to_writer(&mut writer, &header)?;
+    writer.write_all(b"\n")?;
+    for tuple in &input.tuples {
+        serde_json::to_writer(&mut writer, tuple)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Which columns of a CSV file hold each part of a tuple, by 0-based index.
+#[cfg(feature = "io")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvColumns {
+    pub subject: usize,
+    pub predicate: usize,
+    pub object: usize,
+    /// Confidence column; `None` if the file has none. Rows where it is
+    /// missing or empty get a confidence of 1.0.
+    pub confidence: Option<usize>,
+    /// Whether the first row is a header, skipped rather than read.
+    pub has_header: bool,
+}
+
+#[cfg(feature = "io")]
+impl Default for CsvColumns {
+    /// `subject,predicate,object,confidence`, under a header row.
+    fn default() -> Self {
+        CsvColumns {
+            subject: 0,
+            predicate: 1,
+            object: 2,
+            confidence: Some(3),
+            has_header: true,
+        }
+    }
+}
+
+/// Read a CSV file of `subject,predicate,object,confidence` rows under a
+/// header row into a [`CompilerInput`] observed in `context`.
+///
+/// See [`read_csv_with`].
+#[cfg(feature = "io")]
+pub fn read_csv<R: io::Read>(
+    reader: R,
+    context: ContextMeta,
+) -> Result<CompilerInput, IngestError> {
+    read_csv_with(reader, context, &CsvColumns::default())
+}
+
+/// Read a CSV file into a [`CompilerInput`] observed in `context`, taking
+/// each part of a tuple from the column `columns` gives it.
+///
+/// Fields may be quoted to hold commas, and are trimmed. Empty lines are
+/// skipped, and rows may differ in length as long as each holds the
+/// subject, predicate and object columns. Errors carry the 1-based number
+/// of the row they were found on, the header row being row 1.
+#[cfg(feature = "io")]
+pub fn read_csv_with<R: io::Read>(
+    reader: R,
+    context: ContextMeta,
+    columns: &CsvColumns,
+) -> Result<CompilerInput, IngestError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let mut tuples = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+        let record = record.map_err(|error| IngestError::Csv { row, error })?;
+        if columns.has_header && index == 0 {
+            continue;
+        }
+        let field =
+            |column: usize, reason| record.get(column).ok_or(IngestError::Row { row, reason });
+        let confidence = match columns.confidence.and_then(|c| record.get(c)) {
+            None | Some("") => 1.0,
+            Some(text) => text.parse().map_err(|_| IngestError::Row {
+                row,
+                reason: "confidence is not a number",
+            })?,
+        };
+        tuples.push(SemanticTuple {
+            subject: field(columns.subject, "missing subject column")?.to_string(),
+            predicate: field(columns.predicate, "missing predicate column")?.into(),
+            object: field(columns.object, "missing object column")?.to_string(),
+            confidence,
+            ..Default::default()
+        });
+    }
+    Ok(CompilerInput {
+        utterance: None,
+        tuples,
+        context,
+    })
+}
+
+/// Why N-Triples text could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RdfImportError {
+    /// Line `line` (1-based) does not fit the N-Triples grammar.
+    Malformed { line: usize, reason: &'static str },
+}
+
+impl fmt::Display for RdfImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RdfImportError::Malformed { line, reason } => write!(f, "line {}: {}", line, reason),
+        }
+    }
+}
+
+impl std::error::Error for RdfImportError {}
+
+/// How [`from_ntriples`] turns a subject or object IRI into a label.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IriLabel {
+    /// The whole IRI, e.g. `http://example.org/people#alice`.
+    Full,
+    /// The fragment of the IRI, or else its last path segment, e.g.
+    /// `alice`.
+    #[default]
+    LocalName,
+}
+
+/// Options for [`from_ntriples`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NTriplesOptions {
+    /// How subject and object IRIs become labels. Predicates always take
+    /// the local name of their IRI.
+    pub iri_label: IriLabel,
+    /// Confidence of every tuple; RDF has no notion of one.
+    pub confidence: f64,
+}
+
+impl Default for NTriplesOptions {
+    fn default() -> Self {
+        NTriplesOptions {
+            iri_label: IriLabel::LocalName,
+            confidence: 1.0,
+        }
+    }
+}
+
+/// A subject, predicate, or object of a triple.
+enum Term {
+    Iri(String),
+    /// A blank node, by its label without the `_:` prefix.
+    Blank(String),
+    /// The lexical form of a literal; datatypes and language tags are
+    /// dropped.
+    Literal(String),
+}
+
+/// The fragment of `iri`, or else its last path segment, or else the
+/// whole IRI.
+fn local_name(iri: &str) -> &str {
+    if let Some((_, fragment)) = iri.rsplit_once('#') {
+        if !fragment.is_empty() {
+            return fragment;
+        }
+    }
+    let path = iri.trim_end_matches(['/', '#']);
+    match path.rsplit_once(['/', ':']) {
+        Some((_, name)) if !name.is_empty() => name,
+        _ => iri,
+    }
+}
+
+/// The remainder of an N-Triples line being parsed.
+struct NTriplesLine<'a> {
+    rest: &'a str,
+}
+
+impl<'a> NTriplesLine<'a> {
+    fn skip_space(&mut self) {
+        self.rest = self.rest.trim_start_matches([' ', '\t']);
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        match self.rest.strip_prefix(prefix) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.rest.chars().next()?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Some(c)
+    }
+
+    /// The character of a `\u` or `\U` escape whose letter has been read.
+    fn unicode_escape(&mut self, digits: usize) -> Result<char, &'static str> {
+        let hex = self.rest.get(..digits).ok_or("invalid unicode escape")?;
+        let c = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(char::from_u32)
+            .ok_or("invalid unicode escape")?;
+        self.rest = &self.rest[digits..];
+        Ok(c)
+    }
+
+    /// An IRI whose `<` has been read.
+    fn iri(&mut self) -> Result<String, &'static str> {
+        let mut iri = String::new();
+        loop {
+            match self.next_char().ok_or("unterminated IRI")? {
+                '>' => return Ok(iri),
+                '\\' => match self.next_char() {
+                    Some('u') => iri.push(self.unicode_escape(4)?),
+                    Some('U') => iri.push(self.unicode_escape(8)?),
+                    _ => return Err("invalid escape in IRI"),
+                },
+                c if c <= ' ' || "<\"{}|^`".contains(c) => return Err("invalid character in IRI"),
+                c => iri.push(c),
+            }
+        }
+    }
+
+    /// A blank node label whose `_:` has been read.
+    fn blank(&mut self) -> Result<String, &'static str> {
+        let len = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || "_-.\u{b7}".contains(c)))
+            .unwrap_or(self.rest.len());
+        // A label cannot end in `.`, which ends the triple instead.
+        let label = self.rest[..len].trim_end_matches('.');
+        if label.is_empty() || label.starts_with(['-', '.']) {
+            return Err("invalid blank node label");
+        }
+        self.rest = &self.rest[label.len()..];
+        Ok(label.to_string())
+    }
+
+    /// A literal whose opening `"` has been read, with any datatype or
+    /// language tag after it.
+    fn literal(&mut self) -> Result<String, &'static str> {
+        let mut lexical = String::new();
+        loop {
+            match self.next_char().ok_or("unterminated literal")? {
+                '"' => break,
+                '\\' => {
+                    let c = match self.next_char() {
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('f') => '\u{c}',
+                        Some('"') => '"',
+                        Some('\'') => '\'',
+                        Some('\\') => '\\',
+                        Some('u') => self.unicode_escape(4)?,
+                        Some('U') => self.unicode_escape(8)?,
+                        _ => return Err("invalid escape in literal"),
+                    };
+                    lexical.push(c);
+                }
+                c => lexical.push(c),
+            }
+        }
+        if self.eat("^^") {
+            if !self.eat("<") {
+                return Err("expected a datatype IRI");
+            }
+            self.iri()?;
+        } else if self.eat("@") {
+            let len = self
+                .rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                .unwrap_or(self.rest.len());
+            if len == 0 || !self.rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                return Err("invalid language tag");
+            }
+            self.rest = &self.rest[len..];
+        }
+        Ok(lexical)
+    }
+
+    fn term(&mut self, literals: bool) -> Result<Term, &'static str> {
+        self.skip_space();
+        if self.eat("<") {
+            self.iri().map(Term::Iri)
+        } else if self.eat("_:") {
+            self.blank().map(Term::Blank)
+        } else if literals && self.eat("\"") {
+            self.literal().map(Term::Literal)
+        } else if literals {
+            Err("expected an IRI, blank node, or literal")
+        } else {
+            Err("expected an IRI or blank node")
+        }
+    }
+
+    /// The triple on this line, which must end in `.` and may be followed
+    /// by a comment.
+    fn triple(&mut self) -> Result<(Term, String, Term), &'static str> {
+        let subject = self.term(false)?;
+        self.skip_space();
+        if !self.eat("<") {
+            return Err("expected a predicate IRI");
+        }
+        let predicate = self.iri()?;
+        let object = self.term(true)?;
+        self.skip_space();
+        if !self.eat(".") {
+            return Err("expected `.` at the end of the triple");
+        }
+        self.skip_space();
+        if !self.rest.is_empty() && !self.rest.starts_with('#') {
+            return Err("unexpected text after the triple");
+        }
+        Ok((subject, predicate, object))
+    }
+}
+
+/// Import RDF N-Triples as a [`CompilerInput`] observed in `context`, one
+/// [`SemanticTuple`] per triple.
+///
+/// Subject and object IRIs become labels as `options.iri_label` says, and
+/// predicates take the local name of their IRI, so
+/// `<http://xmlns.com/foaf/0.1/knows>` is `knows`. Blank nodes keep their
+/// `_:` label, which is only unique within one document. Literals become
+/// labels of their lexical form, whatever their datatype or language.
+///
+/// Blank lines and comments are skipped. Lines that do not fit the
+/// grammar are reported by their 1-based number.
+pub fn from_ntriples(
+    text: &str,
+    context: ContextMeta,
+    options: &NTriplesOptions,
+) -> Result<CompilerInput, RdfImportError> {
+    let label = |term: Term| match term {
+        Term::Iri(iri) => match options.iri_label {
+            IriLabel::Full => iri,
+            IriLabel::LocalName => local_name(&iri).to_string(),
+        },
+        Term::Blank(label) => format!("_:{}", label),
+        Term::Literal(lexical) => lexical,
+    };
+    let mut tuples = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let mut parser = NTriplesLine { rest: line };
+        parser.skip_space();
+        if parser.rest.is_empty() || parser.rest.starts_with('#') {
+            continue;
+        }
+        let (subject, predicate, object) =
+            parser
+                .triple()
+                .map_err(|reason| RdfImportError::Malformed {
+                    line: index + 1,
+                    reason,
+                })?;
+        tuples.push(SemanticTuple {
+            subject: label(subject),
+            predicate: local_name(&predicate).into(),
+            object: label(object),
+            confidence: options.confidence,
+            ..Default::default()
+        });
+    }
+    Ok(CompilerInput {
+        utterance: None,
+        tuples,
+        context,
+    })
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::store::graph::merge_concept;
+use crate::types::*;
+
+/// Why records could not be [merged](merge_records).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    /// The records were observed in different contexts.
+    ContextMismatch {
+        expected: ContextHash,
+        found: ContextHash,
+    },
+    /// The records were compiled in different namespaces, so their hashes
+    /// do not mix.
+    NamespaceMismatch {
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    /// [`merge_many`] was given no records.
+    Empty,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::ContextMismatch { expected, found } => write!(
+                f,
+                "context {} does not match the record's context {}",
+                found, expected
+            ),
+            MergeError::NamespaceMismatch { expected, found } => write!(
+                f,
+                "namespace {:?} does not match the record's namespace {:?}",
+                found, expected
+            ),
+            MergeError::Empty => write!(f, "no records to merge"),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Combine two records of the same context into one, e.g. to emit a single
+/// graph for tuples compiled in several batches.
+///
+/// Concepts are deduplicated by hash and merged as a store merges a
+/// re-inserted concept: the higher confidence, the union of the aliases.
+/// Relations are deduplicated by episode hash, keeping the higher
+/// confidence, as the compiler collapses duplicate tuples. Concepts come
+/// out sorted by label, relations in order, those of `base` first.
+///
+/// The result keeps the `prev` of `base` and drops its proof, which no
+/// longer covers it. Fails, returning neither record, if the two differ
+/// in context or namespace.
+pub fn merge_records(base: MemoryRecord, other: MemoryRecord) -> Result<MemoryRecord, MergeError> {
+    merge_many(vec![base, other])
+}
+
+/// [`merge_records`] over any number of records, into the first.
+pub fn merge_many(records: Vec<MemoryRecord>) -> Result<MemoryRecord, MergeError> {
+    let mut records = records.into_iter();
+    let mut base = records.next().ok_or(MergeError::Empty)?;
+    let others: Vec<MemoryRecord> = records.collect();
+    for other in &others {
+        if other.context.hash != base.context.hash {
+            return Err(MergeError::ContextMismatch {
+                expected: base.context.hash.clone(),
+                found: other.context.hash.clone(),
+            });
+        }
+        if other.namespace != base.namespace {
+            return Err(MergeError::NamespaceMismatch {
+                expected: base.namespace.clone(),
+                found: other.namespace.clone(),
+            });
+        }
+    }
+
+    let mut concepts: HashMap<ConceptHash, ConceptNode> = HashMap::new();
+    let mut relations: Vec<RelationNode> = Vec::new();
+    let mut relation_index: HashMap<EpisodeHash, usize> = HashMap::new();
+    let base_parts = (
+        std::mem::take(&mut base.concepts),
+        std::mem::take(&mut base.relations),
+    );
+    let parts =
+        std::iter::once(base_parts).chain(others.into_iter().map(|r| (r.concepts, r.relations)));
+    for (record_concepts, record_relations) in parts {
+        for concept in record_concepts {
+            match concepts.get_mut(&concept.hash) {
+                Some(existing) => {
+                    merge_concept(existing, concept);
+                }
+                None => {
+                    concepts.insert(concept.hash.clone(), concept);
+                }
+            }
+        }
+        for relation in record_relations {
+            match relation_index.get(&relation.episode_hash) {
+                Some(&i) => {
+                    let existing = &mut relations[i];
+                    existing.confidence = existing.confidence.max(relation.confidence);
+                }
+                None => {
+                    relation_index.insert(relation.episode_hash.clone(), relations.len());
+                    relations.push(relation);
+                }
+            }
+        }
+    }
+
+    base.concepts = concepts.into_values().collect();
+    base.concepts.sort_by(|a, b| a.label.cmp(&b.label));
+    base.relations = relations;
+    base.proof = None;
+    Ok(base)
+}
@@ -0,0 +1,116 @@
+use sha2::{Digest, Sha256};
+
+use crate::types::{EpisodeHash, MemoryRecord};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// The sibling path from one leaf of a record's Merkle tree to its root.
+/// See [`merkle_root`] for how the tree is built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Position of the leaf among the record's sorted episode hashes. Its
+    /// bits, lowest first, say on which side of each sibling the path runs.
+    pub index: usize,
+    /// The sibling at each level, from the leaves up.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// Whether this path leads from `leaf` to `root`.
+    pub fn verify(&self, root: &[u8; 32], leaf: &EpisodeHash) -> bool {
+        let mut hash = leaf_hash(leaf);
+        let mut index = self.index;
+        for sibling in &self.siblings {
+            hash = if index % 2 == 0 {
+                node_hash(&hash, sibling)
+            } else {
+                node_hash(sibling, &hash)
+            };
+            index /= 2;
+        }
+        index == 0 && &hash == root
+    }
+}
+
+fn leaf_hash(episode: &EpisodeHash) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(episode.as_bytes());
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+/// The record's distinct episode hashes, sorted.
+fn leaves(record: &MemoryRecord) -> Vec<&EpisodeHash> {
+    let mut episodes: Vec<&EpisodeHash> =
+        record.relations.iter().map(|r| &r.episode_hash).collect();
+    episodes.sort();
+    episodes.dedup();
+    episodes
+}
+
+/// The level above `level`, its odd last node paired with itself.
+fn parent_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| node_hash(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+        .collect()
+}
+
+/// Merkle root over the episode hashes of `record`, for selective
+/// disclosure: a [`MerkleProof`] shows that one episode is in a record
+/// whose root is known, without revealing the others.
+///
+/// The leaves are the record's distinct episode hashes in ascending byte
+/// order. Hashing is domain-separated as in RFC 6962, so a leaf can never
+/// pass for an inner node:
+///
+/// - leaf: `sha256(0x00 | episode_hash)`
+/// - inner node: `sha256(0x01 | left | right)`
+///
+/// A level with an odd number of nodes pairs its last node with itself.
+/// Duplicate episodes are dropped before the tree is built, so the root
+/// commits to the set of episodes and no leaf is ever paired with a copy
+/// of itself that the record really holds. The root of a record without relations is `sha256("")`, and the
+/// root of a single relation is its leaf hash.
+pub fn merkle_root(record: &MemoryRecord) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = leaves(record).into_iter().map(leaf_hash).collect();
+    if level.is_empty() {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&Sha256::digest([]));
+        return hash;
+    }
+    while level.len() > 1 {
+        level = parent_level(&level);
+    }
+    level[0]
+}
+
+/// The path proving that `episode` is one of the relations of `record`,
+/// or `None` if it is not.
+pub fn merkle_proof(record: &MemoryRecord, episode: &EpisodeHash) -> Option<MerkleProof> {
+    let leaves = leaves(record);
+    let index = leaves.iter().position(|e| *e == episode)?;
+    let mut level: Vec<[u8; 32]> = leaves.into_iter().map(leaf_hash).collect();
+    let mut siblings = Vec::new();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = (position ^ 1).min(level.len() - 1);
+        siblings.push(level[sibling]);
+        level = parent_level(&level);
+        position /= 2;
+    }
+    Some(MerkleProof { index, siblings })
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+
+use serde::de::Error as _;
+use serde_json::Value;
+
+use crate::types::*;
+
+/// Why [`record_from_json`] could not read a record.
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The text is not a record of the version it claims, before or after
+    /// upgrading.
+    Json(serde_json::Error),
+    /// The record was written with a layout version this build cannot
+    /// read. `supported` is the newest version this build understands.
+    UnsupportedVersion { found: u64, supported: u32 },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Json(e) => write!(f, "invalid record JSON: {}", e),
+            MigrationError::UnsupportedVersion { found, supported }
+                if *found > u64::from(*supported) =>
+            {
+                write!(
+                    f,
+                    "record schema version {} is newer than this build understands \
+                     (it reads versions 1 to {}); upgrade the crate",
+                    found, supported
+                )
+            }
+            MigrationError::UnsupportedVersion { found, .. } => {
+                write!(f, "unknown record schema version {}", found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MigrationError::Json(e) => Some(e),
+            MigrationError::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for MigrationError {
+    fn from(e: serde_json::Error) -> Self {
+        MigrationError::Json(e)
+    }
+}
+
+/// Read a [`MemoryRecord`] from JSON written by this or an earlier version
+/// of the crate, upgrading it to [`RECORD_SCHEMA_VERSION`].
+///
+/// A record without a `schema_version` is version 1. Its timestamps were
+/// not checked when it was written, so each is read as
+/// [`EventTime::from_legacy`] would, and fields added since take their
+/// defaults. Hashes are kept as written, so a record whose timestamps
+/// were not canonical may no longer [validate](MemoryRecord::validate).
+///
+/// Fails with [`MigrationError::UnsupportedVersion`] for versions newer
+/// than this build, rather than dropping fields it does not know.
+pub fn record_from_json(json: &str) -> Result<MemoryRecord, MigrationError> {
+    let mut value: Value = serde_json::from_str(json)?;
+    let version = match value.get("schema_version") {
+        None => 1,
+        Some(version) => version.as_u64().ok_or_else(|| {
+            serde_json::Error::custom(format!("schema_version {} is not a version", version))
+        })?,
+    };
+    match version {
+        1 => upgrade_v1(&mut value),
+        v if v == u64::from(RECORD_SCHEMA_VERSION) => {}
+        found => {
+            return Err(MigrationError::UnsupportedVersion {
+                found,
+                supported: RECORD_SCHEMA_VERSION,
+            })
+        }
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Version 1 to 2: canonicalize the timestamps and record the version.
+fn upgrade_v1(value: &mut Value) {
+    let Some(record) = value.as_object_mut() else {
+        return;
+    };
+    if let Some(time) = record
+        .get_mut("context")
+        .and_then(|c| c.pointer_mut("/meta/event_time"))
+    {
+        canonicalize_time(time);
+    }
+    for (nodes, fields) in [
+        ("concepts", &["created_at", "updated_at"][..]),
+        ("relations", &["created_at"][..]),
+    ] {
+        let Some(Value::Array(nodes)) = record.get_mut(nodes) else {
+            continue;
+        };
+        for node in nodes {
+            for field in fields {
+                if let Some(time) = node.get_mut(*field) {
+                    canonicalize_time(time);
+                }
+            }
+        }
+    }
+    record.insert("schema_version".to_string(), Value::from(2u32));
+}
+
+fn canonicalize_time(time: &mut Value) {
+    if let Value::String(text) = time {
+        *text = EventTime::from_legacy(text).to_string();
+    }
+}
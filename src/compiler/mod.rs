@@ -1,6 +1,48 @@
+pub mod casefold;
+pub mod decompiler;
 pub mod emitter;
+pub mod error;
 pub mod hasher;
+pub mod integrity;
+pub mod io;
+pub mod merge;
+pub mod merkle;
+pub mod migrate;
 pub mod normalizer;
+pub mod options;
+pub mod roundtrip;
+#[cfg(feature = "sign")]
+pub mod sign;
+pub mod symbols;
+#[cfg(feature = "validate")]
+pub mod validate;
 
-pub use emitter::{compile, emit_graph_text, CompilerOutput};
-pub use normalizer::AliasTable;
+pub use decompiler::{decompile, proof_node, verify_trace_hash, DecompileError};
+pub use emitter::{
+    compile, compile_batch, compile_into, compile_with, emit_graph_text, emit_graph_text_with,
+    emit_graph_to, try_compile, try_compile_with, CompilerOutput,
+};
+pub use error::CompilerError;
+pub use integrity::{check_record, RecordIssue};
+pub use io::{
+    from_ntriples, read_ndjson, read_ndjson_blocks, write_ndjson, IngestError, IriLabel,
+    NTriplesOptions, NdjsonBlocks, RdfImportError,
+};
+#[cfg(feature = "io")]
+pub use io::{read_csv, read_csv_with, CsvColumns};
+pub use merge::{merge_many, merge_records, MergeError};
+pub use merkle::MerkleProof;
+pub use migrate::{record_from_json, MigrationError};
+pub use normalizer::{
+    AliasTable, CaseMode, DefaultNormalizer, Normalizer, PluralFolder, PredicateAliasTable,
+    Punctuation, Resolution, StopWords, SymbolStripper,
+};
+pub use options::{CompilerOptions, GraphMeta, PredicateCheck, SanitizePolicy};
+pub use roundtrip::{verify_roundtrip, RoundtripError, RoundtripIssue, RoundtripReport};
+#[cfg(feature = "sign")]
+pub use sign::{
+    compile_signed, verify_graph_text, verify_record, verify_signature, ProofStatus, Signer,
+    VerifyError,
+};
+#[cfg(feature = "validate")]
+pub use validate::{compile_validated, ValidationError};
@@ -1,14 +1,72 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::compiler::casefold::case_fold;
+use crate::compiler::symbols::{is_emoji_or_symbol, is_punctuation};
+use crate::types::InsertResult;
+
+/// `raw` in Unicode Normalization Form C, so that a precomposed `é` and
+/// `e` followed by a combining accent are the same string. ASCII and other
+/// text already in NFC is returned as is.
+///
+/// Without the `nfc` feature (on by default), `raw` is returned as is.
+pub fn nfc(raw: &str) -> Cow<'_, str> {
+    #[cfg(feature = "nfc")]
+    {
+        use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+        if is_nfc_quick(raw.chars()) != IsNormalized::Yes {
+            return Cow::Owned(raw.nfc().collect());
+        }
+    }
+    Cow::Borrowed(raw)
+}
+
+/// How normalization treats letter case.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaseMode {
+    /// Full Unicode case folding (see
+    /// [`case_fold`](super::casefold::case_fold)), then NFC again:
+    /// locale-independent, and `Straße`/`STRASSE` or `ΟΔΟΣ`/`οδος` are
+    /// one label.
+    #[default]
+    Fold,
+    /// [`str::to_lowercase`], as normalization did before folding: `ß`
+    /// and `ss` stay apart, and a word-final `Σ` becomes `ς`.
+    Lower,
+    /// Keep case as written. Stop words and plural folding then only
+    /// apply to words written in lowercase.
+    Preserve,
+}
+
+impl CaseMode {
+    /// `s` with this case treatment applied.
+    pub fn apply<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self {
+            CaseMode::Fold => Cow::Owned(nfc(&case_fold(s)).into_owned()),
+            CaseMode::Lower => Cow::Owned(s.to_lowercase()),
+            CaseMode::Preserve => Cow::Borrowed(s),
+        }
+    }
+}
+
+/// Normalize a concept label to canonical form, with [`CaseMode::Fold`].
+pub fn normalize_label(raw: &str) -> String {
+    normalize_label_with(raw, CaseMode::Fold)
+}
 
 /// Normalize a concept label to canonical form.
 ///
 /// Rules:
-/// - lowercase
-/// - trim whitespace
-/// - collapse multiple spaces to single space
+/// - Unicode NFC (see [`nfc`])
+/// - apply `case` (see [`CaseMode`])
+/// - trim whitespace: anything [`char::is_whitespace`], such as tabs,
+///   newlines, and non-breaking spaces
+/// - collapse each run of whitespace to a single space
 /// - replace common separators (`_`, `.`) with `-`
-pub fn normalize_label(raw: &str) -> String {
-    let s = raw.trim().to_lowercase();
+pub fn normalize_label_with(raw: &str, case: CaseMode) -> String {
+    let s = case.apply(nfc(raw).trim()).into_owned();
     let s: String = s
         .chars()
         .map(|c| match c {
@@ -19,7 +77,7 @@ pub fn normalize_label(raw: &str) -> String {
     let mut result = String::with_capacity(s.len());
     let mut prev_space = false;
     for c in s.chars() {
-        if c == ' ' {
+        if c.is_whitespace() {
             if !prev_space {
                 result.push(' ');
             }
@@ -33,6 +91,7 @@ pub fn normalize_label(raw: &str) -> String {
 }
 
 /// Bidirectional alias table mapping variant labels to a canonical form.
+#[derive(Debug, Clone)]
 pub struct AliasTable {
     map: HashMap<String, String>,
 }
@@ -72,25 +131,122 @@ impl AliasTable {
     /// Resolve a label through the alias table. Returns the canonical label
     /// if an alias exists, otherwise returns the normalized input.
     pub fn resolve(&self, label: &str) -> String {
+        self.resolve_traced(label).canonical
+    }
+
+    /// [`resolve`](Self::resolve), also reporting whether `label` was an
+    /// alias and the normalized form it had before resolution.
+    pub fn resolve_traced(&self, label: &str) -> Resolution {
         let normalized = normalize_label(label);
-        self.map.get(&normalized).cloned().unwrap_or(normalized)
+        match self.map.get(&normalized) {
+            Some(canonical) => Resolution {
+                canonical: canonical.clone(),
+                was_alias: true,
+                original_normalized: normalized,
+            },
+            None => Resolution {
+                canonical: normalized.clone(),
+                was_alias: false,
+                original_normalized: normalized,
+            },
+        }
+    }
+
+    /// The canonical label `label` is an alias of, if any.
+    pub fn get(&self, label: &str) -> Option<&str> {
+        self.map.get(&normalize_label(label)).map(String::as_str)
+    }
+
+    /// Whether `alias`, normalized as [`insert`](Self::insert) normalizes
+    /// it, is in the table.
+    pub fn contains(&self, alias: &str) -> bool {
+        self.map.contains_key(&normalize_label(alias))
+    }
+
+    /// Remove the entry for `alias`, normalized as [`insert`](Self::insert)
+    /// normalizes it, and return the canonical label it mapped to.
+    pub fn remove(&mut self, alias: &str) -> Option<String> {
+        self.map.remove(&normalize_label(alias))
+    }
+
+    /// Every `(alias, canonical)` pair, both normalized, in no particular
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.map
+            .iter()
+            .map(|(alias, canonical)| (alias.as_str(), canonical.as_str()))
+    }
+
+    /// The distinct canonical labels the table maps to.
+    pub fn canonical_targets(&self) -> HashSet<&str> {
+        self.map.values().map(String::as_str).collect()
     }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Record that `alias` names `canonical`, unless the table already
+    /// knows `alias`. Unlike [`insert`](Self::insert), a learned pair never
+    /// overrides a mapping or remaps a canonical label, and `canonical` is
+    /// resolved first, so learning never builds alias chains. Returns
+    /// whether the table changed.
+    pub fn learn(&mut self, alias: &str, canonical: &str) -> bool {
+        let alias = normalize_label(alias);
+        let canonical = self.resolve(canonical);
+        if alias == canonical
+            || self.map.contains_key(&alias)
+            || self.map.values().any(|c| *c == alias)
+        {
+            return false;
+        }
+        self.map.insert(alias, canonical);
+        true
+    }
+
+    /// [`learn`](Self::learn) the aliases a store merged into concepts it
+    /// already held, listed in [`InsertResult::merged_aliases`], so the
+    /// table resolves what the store resolves. Returns how many pairs
+    /// were new to the table.
+    pub fn learn_merged(&mut self, result: &InsertResult) -> usize {
+        result
+            .merged_aliases
+            .iter()
+            .filter(|merged| self.learn(&merged.alias, &merged.label))
+            .count()
+    }
+}
+
+/// What [`AliasTable::resolve_traced`] made of a label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// The label to hash: the alias target, or the normalized input.
+    pub canonical: String,
+    /// Whether the input was an alias of `canonical`.
+    pub was_alias: bool,
+    /// The input as normalized, before alias resolution.
+    pub original_normalized: String,
 }
 
 /// Normalize a predicate string.
 ///
 /// Rules:
-/// - lowercase
-/// - trim whitespace
-/// - collapse multiple spaces
-/// - convert spaces, hyphens, and dots to underscores (predicate convention: `is_a`, `has_part`)
+/// - Unicode NFC (see [`nfc`])
+/// - case folding (see [`CaseMode::Fold`])
+/// - trim whitespace, as [`normalize_label`] does
+/// - convert each run of whitespace, hyphens, and dots to a single
+///   underscore (predicate convention: `is_a`, `has_part`)
 pub fn normalize_predicate(raw: &str) -> String {
-    let s = raw.trim().to_lowercase();
+    let s = CaseMode::Fold.apply(nfc(raw).trim()).into_owned();
     let mut result = String::with_capacity(s.len());
     let mut prev_sep = false;
     for c in s.chars() {
         match c {
-            ' ' | '-' | '.' => {
+            c if c.is_whitespace() || c == '-' || c == '.' => {
                 if !prev_sep {
                     result.push('_');
                 }
@@ -105,6 +261,425 @@ pub fn normalize_predicate(raw: &str) -> String {
     result
 }
 
+/// Label and predicate normalization as a swappable value: the compiler
+/// takes one in [`CompilerOptions::normalizer`](super::CompilerOptions::normalizer),
+/// and the stores take one for their label index. A store resolves the
+/// labels of compiled concepts only if it normalizes them the same way
+/// the compiler did, so give both the same normalizer.
+pub trait Normalizer: fmt::Debug + Send + Sync {
+    /// Canonical form of a concept label, which its hash is computed from.
+    fn normalize_label(&self, raw: &str) -> String;
+    /// Canonical form of a predicate, which fact hashes are computed from.
+    fn normalize_predicate(&self, raw: &str) -> String;
+    /// The form to show a label written as `raw` in, which the compiler
+    /// keeps as the concept's display label: `raw` trimmed, by default.
+    fn display_label<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(raw.trim())
+    }
+}
+
+/// The built-in normalization: [`normalize_label_with`] under `case`, and
+/// [`normalize_predicate`]. With `symbols`, labels are stripped of emoji,
+/// symbols, and punctuation first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefaultNormalizer {
+    pub case: CaseMode,
+    /// Strips labels before they are normalized, and display labels too.
+    /// `None` (the default) strips nothing.
+    pub symbols: Option<SymbolStripper>,
+}
+
+impl Normalizer for DefaultNormalizer {
+    fn normalize_label(&self, raw: &str) -> String {
+        match &self.symbols {
+            Some(symbols) => normalize_label_with(&symbols.strip(raw), self.case),
+            None => normalize_label_with(raw, self.case),
+        }
+    }
+
+    fn display_label<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+        match &self.symbols {
+            Some(symbols) => symbols.strip(raw.trim()),
+            None => Cow::Borrowed(raw.trim()),
+        }
+    }
+
+    fn normalize_predicate(&self, raw: &str) -> String {
+        normalize_predicate(raw)
+    }
+}
+
+/// A shared [`DefaultNormalizer`] with case folding.
+pub(crate) fn default_normalizer() -> Arc<dyn Normalizer> {
+    Arc::new(DefaultNormalizer::default())
+}
+
+/// Alias table mapping predicate synonyms to a canonical predicate, e.g.
+/// `requires` → `needs`. Empty by default.
+///
+/// Both sides are normalized with [`normalize_predicate`], so `"Must Have"`
+/// and `"must_have"` are the same entry.
+#[derive(Debug, Clone, Default)]
+pub struct PredicateAliasTable {
+    map: HashMap<String, String>,
+}
+
+impl PredicateAliasTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, alias: &str, canonical: &str) {
+        self.map
+            .insert(normalize_predicate(alias), normalize_predicate(canonical));
+    }
+
+    /// Resolve a predicate through the alias table. Returns the canonical
+    /// predicate if an alias exists, otherwise returns the normalized input.
+    pub fn resolve(&self, predicate: &str) -> String {
+        let normalized = normalize_predicate(predicate);
+        self.map.get(&normalized).cloned().unwrap_or(normalized)
+    }
+
+    /// The canonical predicate `predicate` is an alias of, if any.
+    pub fn get(&self, predicate: &str) -> Option<&str> {
+        self.map
+            .get(&normalize_predicate(predicate))
+            .map(String::as_str)
+    }
+}
+
+/// Words stripped from multi-word labels, so that "the database" and
+/// "database" are one concept. Empty by default, which strips nothing.
+///
+/// Words are normalized with [`normalize_label`], so `"The"` and `"the"`
+/// are the same entry.
+#[derive(Debug, Clone, Default)]
+pub struct StopWords {
+    words: HashSet<String>,
+}
+
+impl StopWords {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The English articles: "the", "a", and "an".
+    pub fn with_defaults() -> Self {
+        let mut words = Self::new();
+        for word in ["the", "a", "an"] {
+            words.insert(word);
+        }
+        words
+    }
+
+    pub fn insert(&mut self, word: &str) {
+        self.words.insert(normalize_label(word));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// `label`, already normalized, without its stop words. A label made
+    /// only of stop words is returned whole, so no label strips to nothing.
+    pub fn strip(&self, label: &str) -> String {
+        if self.words.is_empty() {
+            return label.to_string();
+        }
+        let kept: Vec<&str> = label
+            .split(' ')
+            .filter(|word| !self.words.contains(*word))
+            .collect();
+        if kept.is_empty() {
+            label.to_string()
+        } else {
+            kept.join(" ")
+        }
+    }
+}
+
+/// Punctuation a [`SymbolStripper`] strips from the ends of words.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Punctuation {
+    /// Every character in a Unicode punctuation category: `!` and `?`, but
+    /// also `¿`, `…`, `「`, and `。`.
+    #[default]
+    All,
+    /// Only these characters.
+    Only(HashSet<char>),
+}
+
+impl Punctuation {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            Punctuation::All => is_punctuation(c),
+            Punctuation::Only(chars) => chars.contains(&c),
+        }
+    }
+}
+
+/// Strips what chat adds to a label without changing what it names, so
+/// that `coffee ☕!` and `rust?` are the concepts `coffee` and `rust`:
+///
+/// - emoji and other symbols, wherever they are (see
+///   [`is_emoji_or_symbol`](super::symbols::is_emoji_or_symbol)), and a
+///   zero width joiner right after one
+/// - [`punctuation`](Self::punctuation) at the start and end of each word,
+///   so that hyphens and apostrophes inside words (`0-memory`, `rust's`)
+///   are kept
+///
+/// Words left empty are dropped. A label with nothing left, such as one
+/// made only of emoji, is kept whole rather than stripped to nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolStripper {
+    pub punctuation: Punctuation,
+}
+
+impl SymbolStripper {
+    /// Strip emoji, symbols, and every kind of punctuation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Strip emoji, symbols, and only the punctuation in `chars`.
+    pub fn with_punctuation(chars: &str) -> Self {
+        Self {
+            punctuation: Punctuation::Only(chars.chars().collect()),
+        }
+    }
+
+    /// `label` stripped, its remaining words separated by single spaces.
+    /// A label with nothing to strip is returned as is.
+    pub fn strip<'a>(&self, label: &'a str) -> Cow<'a, str> {
+        if !label
+            .chars()
+            .any(|c| is_emoji_or_symbol(c) || self.punctuation.contains(c))
+        {
+            return Cow::Borrowed(label);
+        }
+        let mut stripped = String::with_capacity(label.len());
+        for word in label.split(char::is_whitespace) {
+            let mut kept = String::with_capacity(word.len());
+            let mut after_symbol = false;
+            for c in word.chars() {
+                if is_emoji_or_symbol(c) || (after_symbol && c == '\u{200d}') {
+                    after_symbol = true;
+                } else {
+                    after_symbol = false;
+                    kept.push(c);
+                }
+            }
+            let kept = kept.trim_matches(|c| self.punctuation.contains(c));
+            if kept.is_empty() {
+                continue;
+            }
+            if !stripped.is_empty() {
+                stripped.push(' ');
+            }
+            stripped.push_str(kept);
+        }
+        if stripped.is_empty() {
+            Cow::Borrowed(label)
+        } else {
+            Cow::Owned(stripped)
+        }
+    }
+}
+
+/// Plurals the suffix rules of [`PluralFolder`] get wrong: irregular
+/// plurals, and words that end like plurals but are not.
+const PLURAL_EXCEPTIONS: &[(&str, &str)] = &[
+    // Irregular plurals.
+    ("children", "child"),
+    ("people", "person"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("mice", "mouse"),
+    ("geese", "goose"),
+    ("feet", "foot"),
+    ("teeth", "tooth"),
+    ("oxen", "ox"),
+    ("indices", "index"),
+    ("matrices", "matrix"),
+    ("vertices", "vertex"),
+    ("appendices", "appendix"),
+    ("criteria", "criterion"),
+    ("phenomena", "phenomenon"),
+    ("cacti", "cactus"),
+    ("fungi", "fungus"),
+    ("nuclei", "nucleus"),
+    ("radii", "radius"),
+    ("stimuli", "stimulus"),
+    ("memories", "memory"),
+    // -ves from -f or -fe.
+    ("leaves", "leaf"),
+    ("knives", "knife"),
+    ("lives", "life"),
+    ("wives", "wife"),
+    ("halves", "half"),
+    ("wolves", "wolf"),
+    ("shelves", "shelf"),
+    ("thieves", "thief"),
+    ("calves", "calf"),
+    ("loaves", "loaf"),
+    ("elves", "elf"),
+    ("selves", "self"),
+    ("scarves", "scarf"),
+    // -es after a singular ending in -o, -s, or -z.
+    ("heroes", "hero"),
+    ("potatoes", "potato"),
+    ("tomatoes", "tomato"),
+    ("echoes", "echo"),
+    ("vetoes", "veto"),
+    ("torpedoes", "torpedo"),
+    ("buses", "bus"),
+    ("statuses", "status"),
+    ("viruses", "virus"),
+    ("campuses", "campus"),
+    ("bonuses", "bonus"),
+    ("censuses", "census"),
+    ("focuses", "focus"),
+    ("lenses", "lens"),
+    ("gases", "gas"),
+    ("aliases", "alias"),
+    ("biases", "bias"),
+    ("atlases", "atlas"),
+    ("canvases", "canvas"),
+    ("quizzes", "quiz"),
+    ("axes", "axis"),
+    ("analyses", "analysis"),
+    ("crises", "crisis"),
+    ("theses", "thesis"),
+    ("hypotheses", "hypothesis"),
+    ("diagnoses", "diagnosis"),
+    // -ies and -ches after a singular ending in -ie or -che.
+    ("movies", "movie"),
+    ("cookies", "cookie"),
+    ("zombies", "zombie"),
+    ("calories", "calorie"),
+    ("rookies", "rookie"),
+    ("selfies", "selfie"),
+    ("caches", "cache"),
+    ("niches", "niche"),
+    ("aches", "ache"),
+    ("headaches", "headache"),
+    ("avalanches", "avalanche"),
+    // Not plurals, or the same in both numbers.
+    ("lens", "lens"),
+    ("news", "news"),
+    ("series", "series"),
+    ("species", "species"),
+    ("means", "means"),
+    ("physics", "physics"),
+    ("mathematics", "mathematics"),
+    ("economics", "economics"),
+    ("ethics", "ethics"),
+    ("politics", "politics"),
+    ("always", "always"),
+    ("perhaps", "perhaps"),
+    ("whereas", "whereas"),
+    ("chaos", "chaos"),
+    ("cosmos", "cosmos"),
+    ("ethos", "ethos"),
+    ("kudos", "kudos"),
+    ("atlas", "atlas"),
+    ("canvas", "canvas"),
+    ("alias", "alias"),
+    ("bias", "bias"),
+    ("does", "does"),
+    ("goes", "goes"),
+    ("diabetes", "diabetes"),
+    ("kubernetes", "kubernetes"),
+    ("postgres", "postgres"),
+];
+
+/// Folds English plurals in labels to the singular, so that "agents" and
+/// "agent" are one concept.
+///
+/// Each word of a label (separated by spaces or hyphens) is folded on its
+/// own. A word in the exceptions table maps to its entry; otherwise words
+/// of lowercase ASCII letters longer than three are folded by these rules,
+/// the first that applies winning:
+///
+/// 1. `-ss`, `-us`, `-is`: not a plural (`class`, `status`, `analysis`)
+/// 2. `-ies` with at least two letters before it: `-y` (`stories`)
+/// 3. `-sses`, `-shes`, `-ches`, `-xes`, `-zzes`: drop `-es` (`boxes`)
+/// 4. `-s`: drop it (`agents`, `databases`, `ties`)
+///
+/// The built-in exceptions cover common irregulars (`children`, `mice`),
+/// `-ves` plurals (`knives`), and words the rules would butcher (`lens`,
+/// `news`, `buses`); add more with [`insert`](Self::insert).
+#[derive(Debug, Clone)]
+pub struct PluralFolder {
+    exceptions: HashMap<String, String>,
+}
+
+impl Default for PluralFolder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluralFolder {
+    /// A folder with the built-in exceptions.
+    pub fn new() -> Self {
+        Self {
+            exceptions: PLURAL_EXCEPTIONS
+                .iter()
+                .map(|(plural, singular)| (plural.to_string(), singular.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Fold `plural` to `singular` instead of by the rules. Map a word to
+    /// itself to keep it as is.
+    pub fn insert(&mut self, plural: &str, singular: &str) {
+        self.exceptions
+            .insert(normalize_label(plural), normalize_label(singular));
+    }
+
+    /// The singular of one word of a normalized label.
+    pub fn singular<'a>(&'a self, word: &'a str) -> Cow<'a, str> {
+        if let Some(singular) = self.exceptions.get(word) {
+            return Cow::Borrowed(singular);
+        }
+        if word.len() <= 3 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Cow::Borrowed(word);
+        }
+        if ["ss", "us", "is"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix))
+        {
+            Cow::Borrowed(word)
+        } else if word.len() > 4 && word.ends_with("ies") {
+            Cow::Owned(format!("{}y", &word[..word.len() - 3]))
+        } else if ["sses", "shes", "ches", "xes", "zzes"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix))
+        {
+            Cow::Borrowed(&word[..word.len() - 2])
+        } else {
+            Cow::Borrowed(word.strip_suffix('s').unwrap_or(word))
+        }
+    }
+
+    /// `label`, already normalized, with each word folded to the singular.
+    pub fn fold(&self, label: &str) -> String {
+        let mut folded = String::with_capacity(label.len());
+        let mut rest = label;
+        loop {
+            let end = rest.find([' ', '-']).unwrap_or(rest.len());
+            folded.push_str(&self.singular(&rest[..end]));
+            let Some(separator) = rest[end..].chars().next() else {
+                return folded;
+            };
+            folded.push(separator);
+            rest = &rest[end + 1..];
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +692,289 @@ mod tests {
         assert_eq!(normalize_label("  multiple   spaces  "), "multiple spaces");
     }
 
+    #[cfg(feature = "nfc")]
+    #[test]
+    fn composed_and_decomposed_labels_normalize_alike() {
+        assert_eq!(normalize_label("Caf\u{e9}"), "caf\u{e9}");
+        assert_eq!(normalize_label("Cafe\u{301}"), "caf\u{e9}");
+        // Angstrom sign, A with ring above, and A + combining ring.
+        for raw in [
+            "\u{212b}ngstr\u{f6}m",
+            "\u{c5}ngstr\u{f6}m",
+            "A\u{30a}ngstro\u{308}m",
+        ] {
+            assert_eq!(normalize_label(raw), "\u{e5}ngstr\u{f6}m");
+        }
+        assert_eq!(normalize_label("\u{1100}\u{1161}"), "\u{ac00}");
+    }
+
+    #[cfg(feature = "nfc")]
+    #[test]
+    fn composed_and_decomposed_predicates_normalize_alike() {
+        assert_eq!(
+            normalize_predicate("Cr\u{e9}\u{e9} Par"),
+            normalize_predicate("Cre\u{301}e\u{301} par")
+        );
+    }
+
+    #[test]
+    fn nfc_borrows_normalized_text() {
+        assert!(matches!(nfc("agent"), Cow::Borrowed("agent")));
+        assert!(matches!(nfc("caf\u{e9}"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn normalize_label_collapses_every_kind_of_whitespace() {
+        for raw in [
+            "long\tterm memory",
+            "long\nterm\r\nmemory",
+            "long\u{a0}term\u{a0}\u{a0}memory",
+            "long\u{3000}term memory",
+            "\u{a0}\tlong term memory\n\u{3000}",
+        ] {
+            assert_eq!(normalize_label(raw), "long term memory", "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn normalize_predicate_collapses_every_kind_of_whitespace() {
+        for raw in [
+            "has\tpart",
+            "has\n\npart",
+            "has\u{a0}part",
+            "has\u{3000}-\tpart",
+            " has part\u{a0}",
+        ] {
+            assert_eq!(normalize_predicate(raw), "has_part", "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn case_modes() {
+        assert_eq!(normalize_label("Stra\u{df}e"), "strasse");
+        assert_eq!(
+            normalize_label_with("Stra\u{df}e", CaseMode::Lower),
+            "stra\u{df}e"
+        );
+        assert_eq!(
+            normalize_label_with("Stra\u{df}e", CaseMode::Preserve),
+            "Stra\u{df}e"
+        );
+        assert_eq!(normalize_predicate("GR\u{dc}SST"), "gr\u{fc}sst");
+    }
+
+    #[test]
+    fn folded_labels_normalize_to_themselves() {
+        for raw in [
+            "Stra\u{df}e",
+            "\u{1f0}",
+            "\u{1f50}",
+            "\u{1fb3}",
+            "\u{130}",
+            "\u{3a3}\u{3c2}",
+            "\u{fb03}",
+            "\u{ab70}",
+        ] {
+            let once = normalize_label(raw);
+            assert_eq!(normalize_label(&once), once, "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn symbols_and_edge_punctuation_are_stripped() {
+        let symbols = SymbolStripper::new();
+        for (raw, stripped) in [
+            ("coffee \u{2615}!", "coffee"),
+            ("rust?", "rust"),
+            ("\u{bf}qu\u{e9}?", "qu\u{e9}"),
+            ("\u{1f44d}\u{1f3fd} great", "great"),
+            (
+                "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467} family",
+                "family",
+            ),
+            ("\u{2764}\u{fe0f}love", "love"),
+            ("(draft) plan...", "draft plan"),
+            ("rust - lang", "rust lang"),
+            ("caf\u{e9}\u{2122}", "caf\u{e9}"),
+        ] {
+            assert_eq!(symbols.strip(raw), stripped, "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn stripping_keeps_what_names_the_label() {
+        let symbols = SymbolStripper::new();
+        for raw in [
+            "0-memory",
+            "rust's book",
+            "c++",
+            "$5 plan",
+            "web 2",
+            "\u{915}\u{94d}\u{200d}\u{937}",
+            "  spaced   out ",
+        ] {
+            assert_eq!(symbols.strip(raw), raw);
+        }
+        assert_eq!(symbols.strip("1\u{fe0f}\u{20e3}"), "1");
+    }
+
+    #[test]
+    fn labels_with_nothing_left_are_kept_whole() {
+        let symbols = SymbolStripper::new();
+        for raw in ["\u{2615}", "\u{1f1e9}\u{1f1ea}", "!!!", "\u{2615} ?"] {
+            assert_eq!(symbols.strip(raw), raw);
+        }
+        let normalizer = DefaultNormalizer {
+            symbols: Some(symbols),
+            ..DefaultNormalizer::default()
+        };
+        assert_eq!(normalizer.normalize_label("\u{2615}"), "\u{2615}");
+        assert_eq!(normalizer.normalize_label(" !! "), "!!");
+    }
+
+    #[test]
+    fn only_the_configured_punctuation_is_stripped() {
+        let symbols = SymbolStripper::with_punctuation("!");
+        assert_eq!(symbols.strip("rust!"), "rust");
+        assert_eq!(symbols.strip("rust?"), "rust?");
+        assert_eq!(symbols.strip("rust? \u{1f980}"), "rust?");
+    }
+
+    #[test]
+    fn default_normalizer_strips_labels_and_display_labels() {
+        let normalizer = DefaultNormalizer {
+            symbols: Some(SymbolStripper::new()),
+            ..DefaultNormalizer::default()
+        };
+        assert_eq!(normalizer.normalize_label("  Coffee \u{2615}! "), "coffee");
+        assert_eq!(normalizer.normalize_label("0-Memory."), "0-memory");
+        assert_eq!(normalizer.display_label("  Coffee \u{2615}! "), "Coffee");
+        assert_eq!(normalizer.normalize_predicate("likes!"), "likes!");
+
+        let plain = DefaultNormalizer::default();
+        assert_eq!(plain.normalize_label("rust?"), "rust?");
+        assert_eq!(plain.display_label("  Rust? "), "Rust?");
+    }
+
+    #[test]
+    fn stop_words_are_stripped_from_multi_word_labels() {
+        let words = StopWords::with_defaults();
+        assert_eq!(words.strip("the database"), "database");
+        assert_eq!(words.strip("a cup of the tea"), "cup of tea");
+        assert_eq!(words.strip("theory"), "theory");
+        assert_eq!(StopWords::new().strip("the database"), "the database");
+    }
+
+    #[test]
+    fn labels_of_stop_words_only_are_kept_whole() {
+        let words = StopWords::with_defaults();
+        assert_eq!(words.strip("the"), "the");
+        assert_eq!(words.strip("the a an"), "the a an");
+    }
+
+    #[test]
+    fn custom_stop_words_are_normalized() {
+        let mut words = StopWords::new();
+        words.insert("  Der ");
+        words.insert("DIE");
+        assert_eq!(words.strip("der hund"), "hund");
+        assert_eq!(words.strip("die katze"), "katze");
+        assert_eq!(words.strip("the database"), "the database");
+    }
+
+    /// (plural, singular) pairs the folder must get right, by rule and by
+    /// exception.
+    const PLURALS: &[(&str, &str)] = &[
+        ("agents", "agent"),
+        ("users", "user"),
+        ("databases", "database"),
+        ("responses", "response"),
+        ("cases", "case"),
+        ("sizes", "size"),
+        ("archives", "archive"),
+        ("photos", "photo"),
+        ("ideas", "idea"),
+        ("ties", "tie"),
+        ("stories", "story"),
+        ("policies", "policy"),
+        ("memories", "memory"),
+        ("classes", "class"),
+        ("addresses", "address"),
+        ("processes", "process"),
+        ("wishes", "wish"),
+        ("matches", "match"),
+        ("branches", "branch"),
+        ("boxes", "box"),
+        ("taxes", "tax"),
+        ("buzzes", "buzz"),
+        ("children", "child"),
+        ("people", "person"),
+        ("mice", "mouse"),
+        ("teeth", "tooth"),
+        ("indices", "index"),
+        ("criteria", "criterion"),
+        ("knives", "knife"),
+        ("leaves", "leaf"),
+        ("heroes", "hero"),
+        ("buses", "bus"),
+        ("statuses", "status"),
+        ("lenses", "lens"),
+        ("quizzes", "quiz"),
+        ("analyses", "analysis"),
+        ("movies", "movie"),
+        ("caches", "cache"),
+    ];
+
+    /// Words that are not plurals, or are the same in both numbers.
+    const NOT_PLURALS: &[&str] = &[
+        "bus", "lens", "gas", "news", "series", "species", "status", "virus", "focus", "class",
+        "process", "analysis", "axis", "this", "chaos", "alias", "physics", "does", "its", "yes",
+        "sheep", "data", "memory", "agent",
+    ];
+
+    #[test]
+    fn plurals_fold_to_the_singular() {
+        let plurals = PluralFolder::new();
+        for (plural, singular) in PLURALS {
+            assert_eq!(plurals.singular(plural), *singular, "{}", plural);
+        }
+    }
+
+    #[test]
+    fn words_that_are_not_plurals_are_kept() {
+        let plurals = PluralFolder::new();
+        for word in NOT_PLURALS {
+            assert_eq!(plurals.singular(word), *word);
+        }
+        for (_, singular) in PLURALS {
+            assert_eq!(plurals.singular(singular), *singular);
+        }
+        // Only lowercase ASCII words are folded.
+        assert_eq!(plurals.singular("v2s"), "v2s");
+        assert_eq!(plurals.singular("caf\u{e9}s"), "caf\u{e9}s");
+    }
+
+    #[test]
+    fn each_word_of_a_label_is_folded() {
+        let plurals = PluralFolder::new();
+        assert_eq!(plurals.fold("agents"), "agent");
+        assert_eq!(plurals.fold("memory stores"), "memory store");
+        assert_eq!(plurals.fold("long-term-memories"), "long-term-memory");
+        assert_eq!(plurals.fold("children of buses"), "child of bus");
+        assert_eq!(plurals.fold("news series"), "news series");
+        assert_eq!(plurals.fold(""), "");
+    }
+
+    #[test]
+    fn custom_plural_exceptions_are_normalized() {
+        let mut plurals = PluralFolder::new();
+        plurals.insert(" Cherubim ", "Cherub");
+        plurals.insert("Kudzus", "kudzus");
+        assert_eq!(plurals.fold("cherubim"), "cherub");
+        assert_eq!(plurals.fold("kudzus"), "kudzus");
+        assert_eq!(plurals.fold("agents"), "agent");
+    }
+
     #[test]
     fn alias_table_resolves() {
         let table = AliasTable::with_defaults();
@@ -125,6 +983,87 @@ mod tests {
         assert_eq!(table.resolve("unknown-concept"), "unknown-concept");
     }
 
+    #[test]
+    fn resolve_traced_reports_aliases() {
+        let table = AliasTable::with_defaults();
+        assert_eq!(
+            table.resolve_traced(" LTM "),
+            Resolution {
+                canonical: "long-term-memory".to_string(),
+                was_alias: true,
+                original_normalized: "ltm".to_string(),
+            }
+        );
+        assert_eq!(
+            table.resolve_traced("Agent"),
+            Resolution {
+                canonical: "agent".to_string(),
+                was_alias: false,
+                original_normalized: "agent".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn alias_table_introspection() {
+        let mut table = AliasTable::with_defaults();
+        assert_eq!(table.len(), 8);
+        assert!(!table.is_empty());
+        assert!(table.contains("LTM"));
+        assert!(table.contains(" ltm "));
+        assert!(table.contains("Long_Term_Memory"));
+        assert!(!table.contains("agent"));
+
+        let mut pairs: Vec<(&str, &str)> = table
+            .iter()
+            .filter(|(_, canonical)| *canonical == "long-term-memory")
+            .collect();
+        pairs.sort_unstable();
+        assert_eq!(
+            pairs,
+            [
+                ("long-term-memory", "long-term-memory"),
+                ("ltm", "long-term-memory")
+            ]
+        );
+        assert_eq!(
+            table.canonical_targets(),
+            HashSet::from([
+                "long-term-memory",
+                "short-term-memory",
+                "working-memory",
+                "semantic-memory",
+                "episodic-memory",
+            ])
+        );
+
+        assert_eq!(table.remove("LTM"), Some("long-term-memory".to_string()));
+        assert_eq!(table.remove("LTM"), None);
+        assert!(!table.contains("ltm"));
+        assert_eq!(table.resolve("LTM"), "ltm");
+        assert_eq!(table.len(), 7);
+        assert!(AliasTable::new().is_empty());
+    }
+
+    #[test]
+    fn learning_never_overrides_or_chains() {
+        let mut table = AliasTable::with_defaults();
+        assert!(table.learn("LT Mem", "long-term-memory"));
+        assert_eq!(table.resolve("lt mem"), "long-term-memory");
+
+        // Known aliases keep their target.
+        assert!(!table.learn("LTM", "short-term-memory"));
+        assert_eq!(table.resolve("ltm"), "long-term-memory");
+        // A canonical label is not made an alias of something else.
+        assert!(!table.learn("long-term-memory", "memory"));
+        assert_eq!(table.resolve("long-term-memory"), "long-term-memory");
+        assert!(!table.learn("Agent", "agent"));
+
+        // An alias target is resolved first.
+        assert!(table.learn("long memory", "LTM"));
+        assert_eq!(table.resolve("long memory"), "long-term-memory");
+    }
+
     #[test]
     fn normalize_predicate_basic() {
         assert_eq!(normalize_predicate("is a"), "is_a");
@@ -138,4 +1077,13 @@ mod tests {
         assert_eq!(normalize_predicate("is.a"), "is_a");
         assert_eq!(normalize_predicate("multi - dash"), "multi_dash");
     }
+
+    #[test]
+    fn predicate_alias_table_resolves_after_normalization() {
+        let mut table = PredicateAliasTable::new();
+        table.insert("Must Have", "needs");
+        assert_eq!(table.resolve("must-have"), "needs");
+        assert_eq!(table.resolve("  MUST  HAVE "), "needs");
+        assert_eq!(table.resolve("Has Part"), "has_part");
+    }
 }
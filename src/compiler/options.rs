@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::compiler::normalizer::{
+    default_normalizer, AliasTable, Normalizer, PluralFolder, PredicateAliasTable, Resolution,
+    StopWords,
+};
+
+/// Knobs for [`compile_with`](super::compile_with).
+///
+/// Start from [`CompilerOptions::default`], which reproduces
+/// [`compile`](super::compile), and override the fields you need; new
+/// knobs are added with defaults that keep existing behavior.
+#[derive(Debug, Clone)]
+pub struct CompilerOptions {
+    /// Normalizes labels and predicates before anything else touches
+    /// them; a [`DefaultNormalizer`](super::DefaultNormalizer) with case
+    /// folding by default. Give a store the same normalizer (see
+    /// [`MemoryStore::with_normalizer`](crate::store::MemoryStore::with_normalizer))
+    /// so it resolves the labels compiled with it.
+    pub normalizer: Arc<dyn Normalizer>,
+    /// Resolves subject and object labels to their canonical form.
+    /// Aliases match whatever the case of the label.
+    pub aliases: AliasTable,
+    /// Stripped from multi-word subject, object, and qualifier value labels
+    /// after normalization and before alias resolution, so the stripped
+    /// label is what gets hashed; the label as it was before stripping is
+    /// kept as an alias. Empty (no stripping) by default; see
+    /// [`StopWords::with_defaults`].
+    pub stop_words: StopWords,
+    /// Folds plural words in subject, object, and qualifier value labels to
+    /// the singular, after stop words are stripped and before alias
+    /// resolution; the label as it was before folding is kept as an alias.
+    /// `None` (the default) leaves plurals alone.
+    pub plurals: Option<PluralFolder>,
+    /// Resolves predicate synonyms, after normalization and before the
+    /// fact hash is computed. Empty by default.
+    pub predicate_aliases: PredicateAliasTable,
+    /// Closed predicate vocabulary, in normalized form (`has_part`).
+    /// Predicates are checked after normalization and alias resolution;
+    /// `None` (the default) allows every predicate.
+    pub allowed_predicates: Option<HashSet<String>>,
+    /// What [`try_compile_with`](super::try_compile_with) does with a
+    /// predicate outside `allowed_predicates`.
+    pub predicate_check: PredicateCheck,
+    /// How string values are made safe for the parser reading the graph
+    /// text.
+    pub sanitize: SanitizePolicy,
+    /// Name, description, tags, and author of the emitted graph.
+    pub graph_meta: GraphMeta,
+    /// Domain the concept and fact hashes are computed in (see
+    /// [`concept_hash_ns`](super::hasher::concept_hash_ns)), so that the
+    /// same label in two domains yields two concepts. Recorded in
+    /// [`MemoryRecord::namespace`](crate::types::MemoryRecord::namespace)
+    /// and in the graph's metadata. `None` (the default) hashes as
+    /// [`concept_hash`](super::hasher::concept_hash) does.
+    pub namespace: Option<String>,
+}
+
+/// Handling of predicates outside [`CompilerOptions::allowed_predicates`].
+///
+/// Either way the output lists them in
+/// [`CompilerOutput::warnings`](crate::types::CompilerOutput::warnings);
+/// the lenient [`compile_with`](super::compile_with) always warns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PredicateCheck {
+    /// Fail with [`CompilerError::UnknownPredicate`](super::CompilerError::UnknownPredicate).
+    #[default]
+    Reject,
+    /// Keep the tuple and record a warning.
+    Warn,
+}
+
+/// How [`emit_graph_text_with`](super::emit_graph_text_with) writes string
+/// values (labels, predicates, context fields, validity bounds) into graph
+/// text.
+///
+/// The 0-openclaw parser misreads colons inside string values (see
+/// `compatibility.md` Gotcha #5); parsers without that bug can take values
+/// verbatim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SanitizePolicy {
+    /// Remove colons, as the emitter originally did. Lossy:
+    /// `2026-02-18T00:00:00Z` becomes `2026-02-18T000000Z`.
+    StripColons,
+    /// Write colons as the JSON escape `\u003a`, which the 0-openclaw
+    /// parser reads back as a colon. See
+    /// [`sanitize_for_graph`](super::emitter::sanitize_for_graph).
+    #[default]
+    EscapeColons,
+    /// Write values verbatim.
+    None,
+    /// Write values verbatim, but fail with
+    /// [`CompilerError::UnsafeGraphValue`](super::CompilerError::UnsafeGraphValue)
+    /// on a value the 0-openclaw parser cannot read. The lenient
+    /// [`compile_with`](super::compile_with) falls back to `EscapeColons`
+    /// for such graphs, so its output always parses.
+    /// Timestamps are written without colons, as under `StripColons`; the
+    /// decompiler reads that form back as the same instant.
+    Strict,
+}
+
+/// The header of an emitted graph: its name, description, and the author
+/// and tags in its metadata. Values are written like any other graph
+/// string, under the [`SanitizePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphMeta {
+    /// The graph's name; `None` (the default) names it
+    /// `zero_memory_compiled__<scope>` after the context scope.
+    pub name: Option<String>,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub author: String,
+}
+
+impl Default for GraphMeta {
+    fn default() -> Self {
+        Self {
+            name: None,
+            description: "Compiled memory graph from 0-memory".to_string(),
+            tags: vec!["memory".to_string(), "compiled".to_string()],
+            author: "0-memory".to_string(),
+        }
+    }
+}
+
+impl GraphMeta {
+    /// The graph name for a record compiled in `scope`.
+    pub fn name_for(&self, scope: &str) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("zero_memory_compiled__{}", scope),
+        }
+    }
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        Self {
+            normalizer: default_normalizer(),
+            aliases: AliasTable::with_defaults(),
+            stop_words: StopWords::new(),
+            plurals: None,
+            predicate_aliases: PredicateAliasTable::new(),
+            allowed_predicates: None,
+            predicate_check: PredicateCheck::default(),
+            sanitize: SanitizePolicy::default(),
+            graph_meta: GraphMeta::default(),
+            namespace: None,
+        }
+    }
+}
+
+impl CompilerOptions {
+    /// Whether `predicate`, already normalized and alias-resolved, is in
+    /// the allowed vocabulary.
+    pub(crate) fn allows_predicate(&self, predicate: &str) -> bool {
+        match &self.allowed_predicates {
+            Some(allowed) => allowed.contains(predicate),
+            None => true,
+        }
+    }
+
+    /// The canonical form of a raw predicate: normalized, then alias
+    /// resolved.
+    pub(crate) fn canonical_predicate(&self, raw: &str) -> String {
+        let predicate = self.normalizer.normalize_predicate(raw);
+        match self.predicate_aliases.get(&predicate) {
+            Some(canonical) => canonical.to_string(),
+            None => predicate,
+        }
+    }
+
+    /// The canonical form of a normalized label: stop words stripped,
+    /// plurals folded, and aliases resolved. The resolution's
+    /// `original_normalized` is the label as it was before aliases, which
+    /// stays in this normalizer's form where the alias table's differs.
+    pub(crate) fn canonical_label(&self, normalized: &str) -> Resolution {
+        let stripped = self.stop_words.strip(normalized);
+        let label = match &self.plurals {
+            Some(plurals) => plurals.fold(&stripped),
+            None => stripped,
+        };
+        match self.aliases.get(&label) {
+            Some(canonical) => Resolution {
+                canonical: canonical.to_string(),
+                was_alias: true,
+                original_normalized: label,
+            },
+            None => Resolution {
+                canonical: label.clone(),
+                was_alias: false,
+                original_normalized: label,
+            },
+        }
+    }
+}
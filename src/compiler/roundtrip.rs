@@ -0,0 +1,460 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::runtime_trait::{InspectValue, MemoryRuntime};
+use crate::types::*;
+
+/// Why [`verify_roundtrip`] could not check a graph at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundtripError {
+    /// The runtime failed to execute the graph; `message` is its error.
+    Execute { message: String },
+    /// The graph ran but produced no `output` map.
+    NoOutput,
+}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundtripError::Execute { message } => {
+                write!(f, "graph does not execute: {}", message)
+            }
+            RoundtripError::NoOutput => f.write_str("graph produced no `output` map"),
+        }
+    }
+}
+
+impl std::error::Error for RoundtripError {}
+
+/// One way an executed graph disagrees with the record it was emitted
+/// from; see [`verify_roundtrip`]. `found` is `None` when the field is
+/// missing or not a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundtripIssue {
+    /// No concept in the output hashed to this concept's hash.
+    MissingConcept { label: String, hash: ConceptHash },
+    /// The concept hashed as expected but carries another label.
+    ConceptLabelMismatch {
+        hash: ConceptHash,
+        expected: String,
+        found: Option<String>,
+    },
+    /// No relation in the output carries this episode hash.
+    MissingRelation { episode: EpisodeHash },
+    /// The relation is present but a field differs from the record's.
+    RelationMismatch {
+        episode: EpisodeHash,
+        field: &'static str,
+        expected: String,
+        found: Option<String>,
+    },
+    /// The output has no `context` block.
+    MissingContext,
+    /// A field of the `context` block differs from the record's.
+    ContextMismatch {
+        field: &'static str,
+        expected: String,
+        found: Option<String>,
+    },
+}
+
+impl fmt::Display for RoundtripIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = |found: &Option<String>| match found {
+            Some(found) => format!("{:?}", found),
+            None => "nothing".to_string(),
+        };
+        match self {
+            RoundtripIssue::MissingConcept { label, hash } => {
+                write!(
+                    f,
+                    "concept {:?} ({}) is missing from the output",
+                    label, hash
+                )
+            }
+            RoundtripIssue::ConceptLabelMismatch {
+                hash,
+                expected,
+                found: label,
+            } => write!(
+                f,
+                "concept {} should be labelled {:?}, found {}",
+                hash,
+                expected,
+                found(label)
+            ),
+            RoundtripIssue::MissingRelation { episode } => {
+                write!(f, "relation {} is missing from the output", episode)
+            }
+            RoundtripIssue::RelationMismatch {
+                episode,
+                field,
+                expected,
+                found: value,
+            } => write!(
+                f,
+                "relation {} should have {} {:?}, found {}",
+                episode,
+                field,
+                expected,
+                found(value)
+            ),
+            RoundtripIssue::MissingContext => f.write_str("the output has no context block"),
+            RoundtripIssue::ContextMismatch {
+                field,
+                expected,
+                found: value,
+            } => write!(
+                f,
+                "context should have {} {:?}, found {}",
+                field,
+                expected,
+                found(value)
+            ),
+        }
+    }
+}
+
+/// What [`verify_roundtrip`] checked and every disagreement it found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoundtripReport {
+    pub concepts_checked: usize,
+    pub relations_checked: usize,
+    pub issues: Vec<RoundtripIssue>,
+}
+
+impl RoundtripReport {
+    /// Whether the executed graph agrees with the record.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Whether `found` is `expected` as the graph carries it: unchanged, or
+/// with its colons removed by [`SanitizePolicy::StripColons`] or, for
+/// timestamps, [`SanitizePolicy::Strict`].
+///
+/// [`SanitizePolicy::StripColons`]: super::SanitizePolicy::StripColons
+/// [`SanitizePolicy::Strict`]: super::SanitizePolicy::Strict
+fn same_text(expected: &str, found: &str) -> bool {
+    found == expected || found == expected.replace(':', "")
+}
+
+/// `entry[field]` if it is not `expected` as the graph carries it.
+fn mismatch(entry: &Map<String, Value>, field: &str, expected: &str) -> Option<Option<String>> {
+    match entry.get(field).and_then(Value::as_str) {
+        Some(found) if same_text(expected, found) => None,
+        found => Some(found.map(str::to_string)),
+    }
+}
+
+/// The output entries under keys starting with `prefix`, by the lowercase
+/// value of their `key_field`.
+fn entries_by<'a>(
+    output: &'a Map<String, Value>,
+    prefix: &str,
+    key_field: &str,
+) -> HashMap<String, &'a Map<String, Value>> {
+    output
+        .iter()
+        .filter(|(key, _)| key.starts_with(prefix))
+        .filter_map(|(_, entry)| {
+            let entry = entry.as_object()?;
+            let key = entry.get(key_field)?.as_str()?.to_ascii_lowercase();
+            Some((key, entry))
+        })
+        .collect()
+}
+
+/// Execute `output.graph_text` through `runtime` and check that what it
+/// produces agrees with `output.record`:
+///
+/// - every concept appears with the hash the runtime computed from its
+///   label, so labels survived emission and hash as the record says (a
+///   label that lost its colons to sanitization hashes differently, and
+///   is matched by label instead);
+/// - every relation appears under its episode hash, with the record's
+///   subject, object, and fact hashes and predicate;
+/// - the `context` block carries the record's context hash, event time,
+///   source, and scope.
+///
+/// Strings are compared modulo the colons a
+/// [`SanitizePolicy`](super::SanitizePolicy) may strip. Every
+/// disagreement is listed in the report rather than stopping at the
+/// first; an error means the graph could not be checked at all.
+pub fn verify_roundtrip<R>(
+    output: &CompilerOutput,
+    runtime: &R,
+) -> Result<RoundtripReport, RoundtripError>
+where
+    R: MemoryRuntime,
+    R::Value: InspectValue,
+{
+    let outputs = runtime
+        .execute_graph(&output.graph_text, HashMap::new())
+        .map_err(|e| RoundtripError::Execute {
+            message: e.to_string(),
+        })?;
+    let executed = match outputs.get("output").map(InspectValue::to_json) {
+        Some(Value::Object(executed)) => executed,
+        _ => return Err(RoundtripError::NoOutput),
+    };
+    let record = &output.record;
+    let mut issues = Vec::new();
+
+    let concepts = entries_by(&executed, "concept_", "hash");
+    let labels: HashSet<&str> = concepts
+        .values()
+        .filter_map(|entry| entry.get("label")?.as_str())
+        .collect();
+    for concept in &record.concepts {
+        match concepts.get(&concept.hash.to_string()) {
+            // Stripping its colons changed what the runtime hashed.
+            None if concept.label.contains(':')
+                && labels.contains(concept.label.replace(':', "").as_str()) => {}
+            None => issues.push(RoundtripIssue::MissingConcept {
+                label: concept.label.clone(),
+                hash: concept.hash.clone(),
+            }),
+            Some(entry) => {
+                if let Some(found) = mismatch(entry, "label", &concept.label) {
+                    issues.push(RoundtripIssue::ConceptLabelMismatch {
+                        hash: concept.hash.clone(),
+                        expected: concept.label.clone(),
+                        found,
+                    });
+                }
+            }
+        }
+    }
+
+    let relations = entries_by(&executed, "rel_", "episode_hash");
+    for relation in &record.relations {
+        let Some(entry) = relations.get(&relation.episode_hash.to_string()) else {
+            issues.push(RoundtripIssue::MissingRelation {
+                episode: relation.episode_hash.clone(),
+            });
+            continue;
+        };
+        for (field, expected) in [
+            ("subject_hash", relation.subject_hash.to_string()),
+            ("object_hash", relation.object_hash.to_string()),
+            ("fact_hash", relation.fact_hash.to_string()),
+            ("predicate", relation.predicate.to_string()),
+        ] {
+            if let Some(found) = mismatch(entry, field, &expected) {
+                issues.push(RoundtripIssue::RelationMismatch {
+                    episode: relation.episode_hash.clone(),
+                    field,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    match executed.get("context").and_then(Value::as_object) {
+        None => issues.push(RoundtripIssue::MissingContext),
+        Some(entry) => {
+            let meta = &record.context.meta;
+            for (field, expected) in [
+                ("hash", record.context.hash.to_string()),
+                ("event_time", meta.event_time.to_string()),
+                ("source", meta.source.clone()),
+                ("scope", meta.scope.clone()),
+            ] {
+                if let Some(found) = mismatch(entry, field, &expected) {
+                    issues.push(RoundtripIssue::ContextMismatch {
+                        field,
+                        expected,
+                        found,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(RoundtripReport {
+        concepts_checked: record.concepts.len(),
+        relations_checked: record.relations.len(),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::compiler::decompiler::parse_graph_json;
+    use crate::compiler::emitter::{compile, compile_with};
+    use crate::compiler::{CompilerOptions, SanitizePolicy};
+
+    /// Evaluates the handful of node kinds the emitter writes, in order,
+    /// the way 0-openclaw does.
+    struct GraphRuntime;
+
+    impl MemoryRuntime for GraphRuntime {
+        type Value = Value;
+        type Hash = [u8; 32];
+        type Error = String;
+
+        fn hash(&self, input: &[u8]) -> [u8; 32] {
+            Sha256::digest(input).into()
+        }
+
+        fn execute_graph(
+            &self,
+            graph_source: &str,
+            _inputs: HashMap<String, Value>,
+        ) -> Result<HashMap<String, Value>, String> {
+            let graph = parse_graph_json(graph_source).map_err(|e| e.to_string())?;
+            let mut values: HashMap<String, Value> = HashMap::new();
+            for node in graph["nodes"].as_array().ok_or("no nodes")? {
+                let input = |i: usize| values[node["inputs"][i].as_str().unwrap()].clone();
+                let value = match (node["type"].as_str(), node["op"].as_str()) {
+                    (Some("Constant"), _) => node["value"].clone(),
+                    (_, Some("CreateMap")) => json!({}),
+                    (_, Some("SetField")) => {
+                        let mut map = input(0);
+                        map[node["params"]["field"].as_str().unwrap()] = input(1);
+                        map
+                    }
+                    (_, Some("Hash")) => {
+                        let bytes = match input(0) {
+                            Value::String(text) => text.into_bytes(),
+                            other => other.to_string().into_bytes(),
+                        };
+                        Value::String(hex::encode(Sha256::digest(bytes)))
+                    }
+                    (_, Some("MergeMap")) => {
+                        let mut merged = Map::new();
+                        for i in 0..node["inputs"].as_array().unwrap().len() {
+                            merged.extend(input(i).as_object().unwrap().clone());
+                        }
+                        Value::Object(merged)
+                    }
+                    other => return Err(format!("unknown node {:?}", other)),
+                };
+                values.insert(node["id"].as_str().unwrap().to_string(), value);
+            }
+            Ok(values
+                .remove("output")
+                .map(|output| HashMap::from([("output".to_string(), output)]))
+                .unwrap_or_default())
+        }
+
+        fn load_state(&self, _key: &str) -> Result<Option<Value>, String> {
+            Ok(None)
+        }
+
+        fn save_state(&self, _key: &str, _value: &Value) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn input() -> CompilerInput {
+        let mut qualified = SemanticTuple::from(("Bob", "likes", "coffee", 0.7));
+        qualified
+            .qualifiers
+            .insert("since".to_string(), "2020".to_string());
+        CompilerInput::builder(ContextMeta::now("user_prompt", "scope:1"))
+            .utterance("Alice lives in Paris")
+            .tuple("Alice", "lives_in", "Paris", 0.9)
+            .tuple("Alice", "visited", "https://example.com", 0.8)
+            .tuples([qualified])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn compiled_graphs_agree_with_their_records() {
+        let output = compile(&input());
+        let report = verify_roundtrip(&output, &GraphRuntime).unwrap();
+        assert_eq!(report.issues, []);
+        assert!(report.is_ok());
+        assert_eq!(report.concepts_checked, output.record.concepts.len());
+        assert_eq!(report.relations_checked, output.record.relations.len());
+    }
+
+    #[test]
+    fn namespaced_and_colon_stripped_graphs_agree() {
+        for options in [
+            CompilerOptions {
+                namespace: Some("team".to_string()),
+                ..CompilerOptions::default()
+            },
+            CompilerOptions {
+                sanitize: SanitizePolicy::StripColons,
+                ..CompilerOptions::default()
+            },
+        ] {
+            let output = compile_with(&input(), &options);
+            assert!(verify_roundtrip(&output, &GraphRuntime).unwrap().is_ok());
+        }
+    }
+
+    #[test]
+    fn every_disagreement_is_reported() {
+        let mut output = compile(&input());
+        output.graph_text = output
+            .graph_text
+            .replace(r#""value": "alice""#, r#""value": "alicia""#)
+            .replace(r#""source":"user_prompt""#, r#""source":"tool_output""#);
+        let relation = &output.record.relations[0];
+        output.graph_text = output
+            .graph_text
+            .replace(&relation.episode_hash.to_string(), &"0".repeat(64));
+
+        let issues = verify_roundtrip(&output, &GraphRuntime).unwrap().issues;
+        let alice = output
+            .record
+            .concepts
+            .iter()
+            .find(|c| c.label == "alice")
+            .unwrap();
+        assert!(issues.contains(&RoundtripIssue::MissingConcept {
+            label: alice.label.clone(),
+            hash: alice.hash.clone(),
+        }));
+        assert!(issues.contains(&RoundtripIssue::MissingRelation {
+            episode: relation.episode_hash.clone(),
+        }));
+        assert!(issues.contains(&RoundtripIssue::ContextMismatch {
+            field: "source",
+            expected: "user_prompt".to_string(),
+            found: Some("tool_output".to_string()),
+        }));
+        assert_eq!(issues.len(), 3);
+        assert_eq!(
+            issues
+                .iter()
+                .find(|issue| matches!(issue, RoundtripIssue::ContextMismatch { .. }))
+                .unwrap()
+                .to_string(),
+            r#"context should have source "user_prompt", found "tool_output""#
+        );
+    }
+
+    #[test]
+    fn graphs_that_fail_or_produce_nothing_are_errors() {
+        let mut output = compile(&input());
+        output.graph_text = "not a graph".to_string();
+        assert!(matches!(
+            verify_roundtrip(&output, &GraphRuntime),
+            Err(RoundtripError::Execute { .. })
+        ));
+
+        let mut output = compile(&input());
+        output.graph_text = output
+            .graph_text
+            .replace(r#""id": "output""#, r#""id": "merged""#);
+        assert_eq!(
+            verify_roundtrip(&output, &GraphRuntime),
+            Err(RoundtripError::NoOutput)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+pub use ed25519_dalek::SigningKey;
+use ed25519_dalek::{Signature, Signer as _, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use crate::compiler::decompiler::{decompile, proof_node, DecompileError};
+use crate::compiler::emitter::{compile_with, emit_compiled};
+use crate::compiler::hasher;
+use crate::compiler::options::CompilerOptions;
+use crate::types::{CompilerInput, CompilerOutput, MemoryRecord, Proof};
+
+/// What a proof field holds until it is computed.
+const PENDING: &str = "pending";
+
+/// Signs compiled records with an Ed25519 key, making them attributable
+/// to its holder. See [`compile_signed`].
+pub struct Signer {
+    key: SigningKey,
+}
+
+impl Signer {
+    pub fn new(keypair: SigningKey) -> Self {
+        Signer { key: keypair }
+    }
+
+    /// The public key records are signed under, in hex.
+    pub fn public_key(&self) -> String {
+        hex::encode(self.key.verifying_key().as_bytes())
+    }
+
+    /// The proof of `record` as it is now: its trace hash, and a signature
+    /// over its [canonical bytes](hasher::canonical_record_bytes). Ed25519
+    /// signatures are deterministic, so signing a record twice gives the
+    /// same proof.
+    pub fn sign(&self, record: &MemoryRecord) -> Proof {
+        let bytes = hasher::canonical_record_bytes(record);
+        Proof {
+            trace_hash: hex::encode(hasher::trace_hash(record)),
+            signer: self.public_key(),
+            signature: hex::encode(self.key.sign(&bytes).to_bytes()),
+        }
+    }
+}
+
+/// Like [`compile_with`], with the record signed by `signer`: the record's
+/// `proof` and the proof node of its graph both carry the signer's public
+/// key and the signature.
+pub fn compile_signed(
+    input: &CompilerInput,
+    options: &CompilerOptions,
+    signer: &Signer,
+) -> CompilerOutput {
+    let mut output = compile_with(input, options);
+    output.record.proof = Some(signer.sign(&output.record));
+    output.graph_text =
+        emit_compiled(&output.record, input, options, true).expect("lenient compiles do not fail");
+    output
+}
+
+/// How far a record's proof checks out. See [`verify_record`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofStatus {
+    /// There is no proof to check, or only a pending trace hash, as in
+    /// graphs emitted before trace hashes were computed.
+    Unsigned,
+    /// The trace hash matches the record, whose signature is pending.
+    TraceOnly,
+    /// The trace hash matches the record and the signature verifies under
+    /// the public key the proof names.
+    FullyVerified,
+}
+
+/// Why a record's proof does not check out.
+#[derive(Debug)]
+pub enum VerifyError {
+    /// The proof's trace hash is not the record's: the record changed
+    /// after it was compiled. Both hashes are hex.
+    TraceMismatch { expected: String, found: String },
+    /// The proof's `signer` or `signature` is not a well-formed Ed25519
+    /// public key or signature in hex.
+    MalformedProof { field: &'static str },
+    /// The signature does not verify under the signer's key: the record or
+    /// its proof changed after it was signed.
+    BadSignature,
+    /// [`verify_graph_text`] could not read the graph.
+    Graph(DecompileError),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::TraceMismatch { expected, found } => write!(
+                f,
+                "proof carries trace hash {}, but the record hashes to {}",
+                expected, found
+            ),
+            VerifyError::MalformedProof { field } => write!(f, "proof has a malformed {}", field),
+            VerifyError::BadSignature => write!(f, "signature does not verify"),
+            VerifyError::Graph(e) => write!(f, "cannot read graph: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VerifyError::Graph(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Check the proof of `record` against the record as it is now:
+/// recompute its canonical bytes, compare their hash with the proof's
+/// trace hash, and, unless the signature is pending, verify the signature
+/// under the public key the proof names.
+pub fn verify_record(record: &MemoryRecord) -> Result<ProofStatus, VerifyError> {
+    match &record.proof {
+        Some(proof) => verify_proof(record, proof),
+        None => Ok(ProofStatus::Unsigned),
+    }
+}
+
+/// Like [`verify_record`], for consumers who only have the `.0` graph
+/// text: the record is [decompiled](decompile) from it and checked against
+/// the graph's proof node.
+pub fn verify_graph_text(graph_text: &str) -> Result<ProofStatus, VerifyError> {
+    let record = decompile(graph_text).map_err(VerifyError::Graph)?;
+    match proof_node(graph_text) {
+        Some(proof) => verify_proof(&record, &proof),
+        None => Ok(ProofStatus::Unsigned),
+    }
+}
+
+/// Whether [`verify_record`] fully verifies `record`.
+pub fn verify_signature(record: &MemoryRecord) -> bool {
+    matches!(verify_record(record), Ok(ProofStatus::FullyVerified))
+}
+
+fn verify_proof(record: &MemoryRecord, proof: &Proof) -> Result<ProofStatus, VerifyError> {
+    if proof.trace_hash == PENDING {
+        return Ok(ProofStatus::Unsigned);
+    }
+    let bytes = hasher::canonical_record_bytes(record);
+    let found = hex::encode(Sha256::digest(&bytes));
+    if proof.trace_hash != found {
+        return Err(VerifyError::TraceMismatch {
+            expected: proof.trace_hash.clone(),
+            found,
+        });
+    }
+    if proof.signature == PENDING {
+        return Ok(ProofStatus::TraceOnly);
+    }
+
+    let key = hex::decode(&proof.signer)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or(VerifyError::MalformedProof { field: "signer" })?;
+    let signature = hex::decode(&proof.signature)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or(VerifyError::MalformedProof { field: "signature" })?;
+    key.verify_strict(&bytes, &signature)
+        .map_err(|_| VerifyError::BadSignature)?;
+    Ok(ProofStatus::FullyVerified)
+}
@@ -0,0 +1,468 @@
+/// Ranges of code points in category `So` (other symbol) as of Unicode
+/// 14.0: emoji and other pictographs, dingbats, and signs such as `©` and
+/// `°`. Sorted and disjoint.
+const OTHER_SYMBOLS: &[(char, char)] = &[
+    ('\u{a6}', '\u{a6}'),
+    ('\u{a9}', '\u{a9}'),
+    ('\u{ae}', '\u{ae}'),
+    ('\u{b0}', '\u{b0}'),
+    ('\u{482}', '\u{482}'),
+    ('\u{58d}', '\u{58e}'),
+    ('\u{60e}', '\u{60f}'),
+    ('\u{6de}', '\u{6de}'),
+    ('\u{6e9}', '\u{6e9}'),
+    ('\u{6fd}', '\u{6fe}'),
+    ('\u{7f6}', '\u{7f6}'),
+    ('\u{9fa}', '\u{9fa}'),
+    ('\u{b70}', '\u{b70}'),
+    ('\u{bf3}', '\u{bf8}'),
+    ('\u{bfa}', '\u{bfa}'),
+    ('\u{c7f}', '\u{c7f}'),
+    ('\u{d4f}', '\u{d4f}'),
+    ('\u{d79}', '\u{d79}'),
+    ('\u{f01}', '\u{f03}'),
+    ('\u{f13}', '\u{f13}'),
+    ('\u{f15}', '\u{f17}'),
+    ('\u{f1a}', '\u{f1f}'),
+    ('\u{f34}', '\u{f34}'),
+    ('\u{f36}', '\u{f36}'),
+    ('\u{f38}', '\u{f38}'),
+    ('\u{fbe}', '\u{fc5}'),
+    ('\u{fc7}', '\u{fcc}'),
+    ('\u{fce}', '\u{fcf}'),
+    ('\u{fd5}', '\u{fd8}'),
+    ('\u{109e}', '\u{109f}'),
+    ('\u{1390}', '\u{1399}'),
+    ('\u{166d}', '\u{166d}'),
+    ('\u{1940}', '\u{1940}'),
+    ('\u{19de}', '\u{19ff}'),
+    ('\u{1b61}', '\u{1b6a}'),
+    ('\u{1b74}', '\u{1b7c}'),
+    ('\u{2100}', '\u{2101}'),
+    ('\u{2103}', '\u{2106}'),
+    ('\u{2108}', '\u{2109}'),
+    ('\u{2114}', '\u{2114}'),
+    ('\u{2116}', '\u{2117}'),
+    ('\u{211e}', '\u{2123}'),
+    ('\u{2125}', '\u{2125}'),
+    ('\u{2127}', '\u{2127}'),
+    ('\u{2129}', '\u{2129}'),
+    ('\u{212e}', '\u{212e}'),
+    ('\u{213a}', '\u{213b}'),
+    ('\u{214a}', '\u{214a}'),
+    ('\u{214c}', '\u{214d}'),
+    ('\u{214f}', '\u{214f}'),
+    ('\u{218a}', '\u{218b}'),
+    ('\u{2195}', '\u{2199}'),
+    ('\u{219c}', '\u{219f}'),
+    ('\u{21a1}', '\u{21a2}'),
+    ('\u{21a4}', '\u{21a5}'),
+    ('\u{21a7}', '\u{21ad}'),
+    ('\u{21af}', '\u{21cd}'),
+    ('\u{21d0}', '\u{21d1}'),
+    ('\u{21d3}', '\u{21d3}'),
+    ('\u{21d5}', '\u{21f3}'),
+    ('\u{2300}', '\u{2307}'),
+    ('\u{230c}', '\u{231f}'),
+    ('\u{2322}', '\u{2328}'),
+    ('\u{232b}', '\u{237b}'),
+    ('\u{237d}', '\u{239a}'),
+    ('\u{23b4}', '\u{23db}'),
+    ('\u{23e2}', '\u{2426}'),
+    ('\u{2440}', '\u{244a}'),
+    ('\u{249c}', '\u{24e9}'),
+    ('\u{2500}', '\u{25b6}'),
+    ('\u{25b8}', '\u{25c0}'),
+    ('\u{25c2}', '\u{25f7}'),
+    ('\u{2600}', '\u{266e}'),
+    ('\u{2670}', '\u{2767}'),
+    ('\u{2794}', '\u{27bf}'),
+    ('\u{2800}', '\u{28ff}'),
+    ('\u{2b00}', '\u{2b2f}'),
+    ('\u{2b45}', '\u{2b46}'),
+    ('\u{2b4d}', '\u{2b73}'),
+    ('\u{2b76}', '\u{2b95}'),
+    ('\u{2b97}', '\u{2bff}'),
+    ('\u{2ce5}', '\u{2cea}'),
+    ('\u{2e50}', '\u{2e51}'),
+    ('\u{2e80}', '\u{2e99}'),
+    ('\u{2e9b}', '\u{2ef3}'),
+    ('\u{2f00}', '\u{2fd5}'),
+    ('\u{2ff0}', '\u{2ffb}'),
+    ('\u{3004}', '\u{3004}'),
+    ('\u{3012}', '\u{3013}'),
+    ('\u{3020}', '\u{3020}'),
+    ('\u{3036}', '\u{3037}'),
+    ('\u{303e}', '\u{303f}'),
+    ('\u{3190}', '\u{3191}'),
+    ('\u{3196}', '\u{319f}'),
+    ('\u{31c0}', '\u{31e3}'),
+    ('\u{3200}', '\u{321e}'),
+    ('\u{322a}', '\u{3247}'),
+    ('\u{3250}', '\u{3250}'),
+    ('\u{3260}', '\u{327f}'),
+    ('\u{328a}', '\u{32b0}'),
+    ('\u{32c0}', '\u{33ff}'),
+    ('\u{4dc0}', '\u{4dff}'),
+    ('\u{a490}', '\u{a4c6}'),
+    ('\u{a828}', '\u{a82b}'),
+    ('\u{a836}', '\u{a837}'),
+    ('\u{a839}', '\u{a839}'),
+    ('\u{aa77}', '\u{aa79}'),
+    ('\u{fd40}', '\u{fd4f}'),
+    ('\u{fdcf}', '\u{fdcf}'),
+    ('\u{fdfd}', '\u{fdff}'),
+    ('\u{ffe4}', '\u{ffe4}'),
+    ('\u{ffe8}', '\u{ffe8}'),
+    ('\u{ffed}', '\u{ffee}'),
+    ('\u{fffc}', '\u{fffd}'),
+    ('\u{10137}', '\u{1013f}'),
+    ('\u{10179}', '\u{10189}'),
+    ('\u{1018c}', '\u{1018e}'),
+    ('\u{10190}', '\u{1019c}'),
+    ('\u{101a0}', '\u{101a0}'),
+    ('\u{101d0}', '\u{101fc}'),
+    ('\u{10877}', '\u{10878}'),
+    ('\u{10ac8}', '\u{10ac8}'),
+    ('\u{1173f}', '\u{1173f}'),
+    ('\u{11fd5}', '\u{11fdc}'),
+    ('\u{11fe1}', '\u{11ff1}'),
+    ('\u{16b3c}', '\u{16b3f}'),
+    ('\u{16b45}', '\u{16b45}'),
+    ('\u{1bc9c}', '\u{1bc9c}'),
+    ('\u{1cf50}', '\u{1cfc3}'),
+    ('\u{1d000}', '\u{1d0f5}'),
+    ('\u{1d100}', '\u{1d126}'),
+    ('\u{1d129}', '\u{1d164}'),
+    ('\u{1d16a}', '\u{1d16c}'),
+    ('\u{1d183}', '\u{1d184}'),
+    ('\u{1d18c}', '\u{1d1a9}'),
+    ('\u{1d1ae}', '\u{1d1ea}'),
+    ('\u{1d200}', '\u{1d241}'),
+    ('\u{1d245}', '\u{1d245}'),
+    ('\u{1d300}', '\u{1d356}'),
+    ('\u{1d800}', '\u{1d9ff}'),
+    ('\u{1da37}', '\u{1da3a}'),
+    ('\u{1da6d}', '\u{1da74}'),
+    ('\u{1da76}', '\u{1da83}'),
+    ('\u{1da85}', '\u{1da86}'),
+    ('\u{1e14f}', '\u{1e14f}'),
+    ('\u{1ecac}', '\u{1ecac}'),
+    ('\u{1ed2e}', '\u{1ed2e}'),
+    ('\u{1f000}', '\u{1f02b}'),
+    ('\u{1f030}', '\u{1f093}'),
+    ('\u{1f0a0}', '\u{1f0ae}'),
+    ('\u{1f0b1}', '\u{1f0bf}'),
+    ('\u{1f0c1}', '\u{1f0cf}'),
+    ('\u{1f0d1}', '\u{1f0f5}'),
+    ('\u{1f10d}', '\u{1f1ad}'),
+    ('\u{1f1e6}', '\u{1f202}'),
+    ('\u{1f210}', '\u{1f23b}'),
+    ('\u{1f240}', '\u{1f248}'),
+    ('\u{1f250}', '\u{1f251}'),
+    ('\u{1f260}', '\u{1f265}'),
+    ('\u{1f300}', '\u{1f3fa}'),
+    ('\u{1f400}', '\u{1f6d7}'),
+    ('\u{1f6dd}', '\u{1f6ec}'),
+    ('\u{1f6f0}', '\u{1f6fc}'),
+    ('\u{1f700}', '\u{1f773}'),
+    ('\u{1f780}', '\u{1f7d8}'),
+    ('\u{1f7e0}', '\u{1f7eb}'),
+    ('\u{1f7f0}', '\u{1f7f0}'),
+    ('\u{1f800}', '\u{1f80b}'),
+    ('\u{1f810}', '\u{1f847}'),
+    ('\u{1f850}', '\u{1f859}'),
+    ('\u{1f860}', '\u{1f887}'),
+    ('\u{1f890}', '\u{1f8ad}'),
+    ('\u{1f8b0}', '\u{1f8b1}'),
+    ('\u{1f900}', '\u{1fa53}'),
+    ('\u{1fa60}', '\u{1fa6d}'),
+    ('\u{1fa70}', '\u{1fa74}'),
+    ('\u{1fa78}', '\u{1fa7c}'),
+    ('\u{1fa80}', '\u{1fa86}'),
+    ('\u{1fa90}', '\u{1faac}'),
+    ('\u{1fab0}', '\u{1faba}'),
+    ('\u{1fac0}', '\u{1fac5}'),
+    ('\u{1fad0}', '\u{1fad9}'),
+    ('\u{1fae0}', '\u{1fae7}'),
+    ('\u{1faf0}', '\u{1faf6}'),
+    ('\u{1fb00}', '\u{1fb92}'),
+    ('\u{1fb94}', '\u{1fbca}'),
+];
+
+/// Ranges of code points in the punctuation categories (`Pc`, `Pd`, `Ps`,
+/// `Pe`, `Pi`, `Pf`, `Po`) as of Unicode 14.0. Sorted and disjoint.
+const PUNCTUATION: &[(char, char)] = &[
+    ('\u{21}', '\u{23}'),
+    ('\u{25}', '\u{2a}'),
+    ('\u{2c}', '\u{2f}'),
+    ('\u{3a}', '\u{3b}'),
+    ('\u{3f}', '\u{40}'),
+    ('\u{5b}', '\u{5d}'),
+    ('\u{5f}', '\u{5f}'),
+    ('\u{7b}', '\u{7b}'),
+    ('\u{7d}', '\u{7d}'),
+    ('\u{a1}', '\u{a1}'),
+    ('\u{a7}', '\u{a7}'),
+    ('\u{ab}', '\u{ab}'),
+    ('\u{b6}', '\u{b7}'),
+    ('\u{bb}', '\u{bb}'),
+    ('\u{bf}', '\u{bf}'),
+    ('\u{37e}', '\u{37e}'),
+    ('\u{387}', '\u{387}'),
+    ('\u{55a}', '\u{55f}'),
+    ('\u{589}', '\u{58a}'),
+    ('\u{5be}', '\u{5be}'),
+    ('\u{5c0}', '\u{5c0}'),
+    ('\u{5c3}', '\u{5c3}'),
+    ('\u{5c6}', '\u{5c6}'),
+    ('\u{5f3}', '\u{5f4}'),
+    ('\u{609}', '\u{60a}'),
+    ('\u{60c}', '\u{60d}'),
+    ('\u{61b}', '\u{61b}'),
+    ('\u{61d}', '\u{61f}'),
+    ('\u{66a}', '\u{66d}'),
+    ('\u{6d4}', '\u{6d4}'),
+    ('\u{700}', '\u{70d}'),
+    ('\u{7f7}', '\u{7f9}'),
+    ('\u{830}', '\u{83e}'),
+    ('\u{85e}', '\u{85e}'),
+    ('\u{964}', '\u{965}'),
+    ('\u{970}', '\u{970}'),
+    ('\u{9fd}', '\u{9fd}'),
+    ('\u{a76}', '\u{a76}'),
+    ('\u{af0}', '\u{af0}'),
+    ('\u{c77}', '\u{c77}'),
+    ('\u{c84}', '\u{c84}'),
+    ('\u{df4}', '\u{df4}'),
+    ('\u{e4f}', '\u{e4f}'),
+    ('\u{e5a}', '\u{e5b}'),
+    ('\u{f04}', '\u{f12}'),
+    ('\u{f14}', '\u{f14}'),
+    ('\u{f3a}', '\u{f3d}'),
+    ('\u{f85}', '\u{f85}'),
+    ('\u{fd0}', '\u{fd4}'),
+    ('\u{fd9}', '\u{fda}'),
+    ('\u{104a}', '\u{104f}'),
+    ('\u{10fb}', '\u{10fb}'),
+    ('\u{1360}', '\u{1368}'),
+    ('\u{1400}', '\u{1400}'),
+    ('\u{166e}', '\u{166e}'),
+    ('\u{169b}', '\u{169c}'),
+    ('\u{16eb}', '\u{16ed}'),
+    ('\u{1735}', '\u{1736}'),
+    ('\u{17d4}', '\u{17d6}'),
+    ('\u{17d8}', '\u{17da}'),
+    ('\u{1800}', '\u{180a}'),
+    ('\u{1944}', '\u{1945}'),
+    ('\u{1a1e}', '\u{1a1f}'),
+    ('\u{1aa0}', '\u{1aa6}'),
+    ('\u{1aa8}', '\u{1aad}'),
+    ('\u{1b5a}', '\u{1b60}'),
+    ('\u{1b7d}', '\u{1b7e}'),
+    ('\u{1bfc}', '\u{1bff}'),
+    ('\u{1c3b}', '\u{1c3f}'),
+    ('\u{1c7e}', '\u{1c7f}'),
+    ('\u{1cc0}', '\u{1cc7}'),
+    ('\u{1cd3}', '\u{1cd3}'),
+    ('\u{2010}', '\u{2027}'),
+    ('\u{2030}', '\u{2043}'),
+    ('\u{2045}', '\u{2051}'),
+    ('\u{2053}', '\u{205e}'),
+    ('\u{207d}', '\u{207e}'),
+    ('\u{208d}', '\u{208e}'),
+    ('\u{2308}', '\u{230b}'),
+    ('\u{2329}', '\u{232a}'),
+    ('\u{2768}', '\u{2775}'),
+    ('\u{27c5}', '\u{27c6}'),
+    ('\u{27e6}', '\u{27ef}'),
+    ('\u{2983}', '\u{2998}'),
+    ('\u{29d8}', '\u{29db}'),
+    ('\u{29fc}', '\u{29fd}'),
+    ('\u{2cf9}', '\u{2cfc}'),
+    ('\u{2cfe}', '\u{2cff}'),
+    ('\u{2d70}', '\u{2d70}'),
+    ('\u{2e00}', '\u{2e2e}'),
+    ('\u{2e30}', '\u{2e4f}'),
+    ('\u{2e52}', '\u{2e5d}'),
+    ('\u{3001}', '\u{3003}'),
+    ('\u{3008}', '\u{3011}'),
+    ('\u{3014}', '\u{301f}'),
+    ('\u{3030}', '\u{3030}'),
+    ('\u{303d}', '\u{303d}'),
+    ('\u{30a0}', '\u{30a0}'),
+    ('\u{30fb}', '\u{30fb}'),
+    ('\u{a4fe}', '\u{a4ff}'),
+    ('\u{a60d}', '\u{a60f}'),
+    ('\u{a673}', '\u{a673}'),
+    ('\u{a67e}', '\u{a67e}'),
+    ('\u{a6f2}', '\u{a6f7}'),
+    ('\u{a874}', '\u{a877}'),
+    ('\u{a8ce}', '\u{a8cf}'),
+    ('\u{a8f8}', '\u{a8fa}'),
+    ('\u{a8fc}', '\u{a8fc}'),
+    ('\u{a92e}', '\u{a92f}'),
+    ('\u{a95f}', '\u{a95f}'),
+    ('\u{a9c1}', '\u{a9cd}'),
+    ('\u{a9de}', '\u{a9df}'),
+    ('\u{aa5c}', '\u{aa5f}'),
+    ('\u{aade}', '\u{aadf}'),
+    ('\u{aaf0}', '\u{aaf1}'),
+    ('\u{abeb}', '\u{abeb}'),
+    ('\u{fd3e}', '\u{fd3f}'),
+    ('\u{fe10}', '\u{fe19}'),
+    ('\u{fe30}', '\u{fe52}'),
+    ('\u{fe54}', '\u{fe61}'),
+    ('\u{fe63}', '\u{fe63}'),
+    ('\u{fe68}', '\u{fe68}'),
+    ('\u{fe6a}', '\u{fe6b}'),
+    ('\u{ff01}', '\u{ff03}'),
+    ('\u{ff05}', '\u{ff0a}'),
+    ('\u{ff0c}', '\u{ff0f}'),
+    ('\u{ff1a}', '\u{ff1b}'),
+    ('\u{ff1f}', '\u{ff20}'),
+    ('\u{ff3b}', '\u{ff3d}'),
+    ('\u{ff3f}', '\u{ff3f}'),
+    ('\u{ff5b}', '\u{ff5b}'),
+    ('\u{ff5d}', '\u{ff5d}'),
+    ('\u{ff5f}', '\u{ff65}'),
+    ('\u{10100}', '\u{10102}'),
+    ('\u{1039f}', '\u{1039f}'),
+    ('\u{103d0}', '\u{103d0}'),
+    ('\u{1056f}', '\u{1056f}'),
+    ('\u{10857}', '\u{10857}'),
+    ('\u{1091f}', '\u{1091f}'),
+    ('\u{1093f}', '\u{1093f}'),
+    ('\u{10a50}', '\u{10a58}'),
+    ('\u{10a7f}', '\u{10a7f}'),
+    ('\u{10af0}', '\u{10af6}'),
+    ('\u{10b39}', '\u{10b3f}'),
+    ('\u{10b99}', '\u{10b9c}'),
+    ('\u{10ead}', '\u{10ead}'),
+    ('\u{10f55}', '\u{10f59}'),
+    ('\u{10f86}', '\u{10f89}'),
+    ('\u{11047}', '\u{1104d}'),
+    ('\u{110bb}', '\u{110bc}'),
+    ('\u{110be}', '\u{110c1}'),
+    ('\u{11140}', '\u{11143}'),
+    ('\u{11174}', '\u{11175}'),
+    ('\u{111c5}', '\u{111c8}'),
+    ('\u{111cd}', '\u{111cd}'),
+    ('\u{111db}', '\u{111db}'),
+    ('\u{111dd}', '\u{111df}'),
+    ('\u{11238}', '\u{1123d}'),
+    ('\u{112a9}', '\u{112a9}'),
+    ('\u{1144b}', '\u{1144f}'),
+    ('\u{1145a}', '\u{1145b}'),
+    ('\u{1145d}', '\u{1145d}'),
+    ('\u{114c6}', '\u{114c6}'),
+    ('\u{115c1}', '\u{115d7}'),
+    ('\u{11641}', '\u{11643}'),
+    ('\u{11660}', '\u{1166c}'),
+    ('\u{116b9}', '\u{116b9}'),
+    ('\u{1173c}', '\u{1173e}'),
+    ('\u{1183b}', '\u{1183b}'),
+    ('\u{11944}', '\u{11946}'),
+    ('\u{119e2}', '\u{119e2}'),
+    ('\u{11a3f}', '\u{11a46}'),
+    ('\u{11a9a}', '\u{11a9c}'),
+    ('\u{11a9e}', '\u{11aa2}'),
+    ('\u{11c41}', '\u{11c45}'),
+    ('\u{11c70}', '\u{11c71}'),
+    ('\u{11ef7}', '\u{11ef8}'),
+    ('\u{11fff}', '\u{11fff}'),
+    ('\u{12470}', '\u{12474}'),
+    ('\u{12ff1}', '\u{12ff2}'),
+    ('\u{16a6e}', '\u{16a6f}'),
+    ('\u{16af5}', '\u{16af5}'),
+    ('\u{16b37}', '\u{16b3b}'),
+    ('\u{16b44}', '\u{16b44}'),
+    ('\u{16e97}', '\u{16e9a}'),
+    ('\u{16fe2}', '\u{16fe2}'),
+    ('\u{1bc9f}', '\u{1bc9f}'),
+    ('\u{1da87}', '\u{1da8b}'),
+    ('\u{1e95e}', '\u{1e95f}'),
+];
+
+fn in_ranges(c: char, ranges: &[(char, char)]) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Whether `c` is an emoji or other symbol: a code point in category `So`,
+/// or one of the parts emoji are built from that mean nothing alone (skin
+/// tone modifiers, the text and emoji variation selectors, the combining
+/// keycap, and tag characters).
+///
+/// Math, currency, and modifier symbols (`+`, `$`, `^`) are not, so `c++`
+/// and `c` stay apart. Neither is the zero width joiner, which also shapes
+/// Indic and Arabic text.
+pub fn is_emoji_or_symbol(c: char) -> bool {
+    matches!(
+        c,
+        '\u{20e3}'
+            | '\u{fe0e}'
+            | '\u{fe0f}'
+            | '\u{1f3fb}'..='\u{1f3ff}'
+            | '\u{e0020}'..='\u{e007f}'
+    ) || in_ranges(c, OTHER_SYMBOLS)
+}
+
+/// Whether `c` is in one of the Unicode punctuation categories.
+pub fn is_punctuation(c: char) -> bool {
+    in_ranges(c, PUNCTUATION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_sorted_and_disjoint() {
+        for table in [OTHER_SYMBOLS, PUNCTUATION] {
+            assert!(table.iter().all(|&(start, end)| start <= end));
+            assert!(table.windows(2).all(|pair| pair[0].1 < pair[1].0));
+        }
+    }
+
+    #[test]
+    fn emoji_and_signs_are_symbols() {
+        for c in [
+            '\u{2615}',
+            '\u{1f600}',
+            '\u{1f1e9}',
+            '\u{a9}',
+            '\u{b0}',
+            '\u{fe0f}',
+            '\u{1f3fd}',
+        ] {
+            assert!(is_emoji_or_symbol(c), "{:?}", c);
+        }
+        for c in [
+            'a', '0', '-', '+', '$', '^', '\u{e9}', '\u{4e2d}', '\u{200d}',
+        ] {
+            assert!(!is_emoji_or_symbol(c), "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn punctuation_spans_scripts() {
+        for c in [
+            '!', '?', '-', '_', '"', '\u{bf}', '\u{2026}', '\u{3002}', '\u{ff01}', '\u{60c}',
+        ] {
+            assert!(is_punctuation(c), "{:?}", c);
+        }
+        for c in ['a', '0', '+', '$', ' ', '\u{2615}'] {
+            assert!(!is_punctuation(c), "{:?}", c);
+        }
+    }
+}
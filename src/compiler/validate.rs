@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use zero_openclaw::runtime::parse_graph;
+
+use crate::adapters::openclaw::OpenclawAdapter;
+use crate::compiler::emitter::compile;
+use crate::runtime_trait::MemoryRuntime;
+use crate::types::{CompilerInput, CompilerOutput};
+
+/// Characters of graph text a [`ValidationError`] carries.
+const EXCERPT_CHARS: usize = 500;
+
+/// Why 0-openclaw rejected the graph text of a [`CompilerOutput`].
+///
+/// `message` is 0-openclaw's own; `excerpt` is the first 500 characters
+/// of the graph text, enough to spot a malformed header or first node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// `parse_graph` could not read the graph text.
+    Parse { message: String, excerpt: String },
+    /// The graph parsed but failed to execute, or produced no `output`.
+    Execute { message: String, excerpt: String },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Parse { message, excerpt } => {
+                write!(f, "graph does not parse: {}\n{}", message, excerpt)
+            }
+            ValidationError::Execute { message, excerpt } => {
+                write!(f, "graph does not execute: {}\n{}", message, excerpt)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+fn excerpt(graph_text: &str) -> String {
+    graph_text.chars().take(EXCERPT_CHARS).collect()
+}
+
+impl CompilerOutput {
+    /// Check that 0-openclaw parses `graph_text`.
+    ///
+    /// This guards against emitter regressions without running
+    /// `compat_test.rs`; see [`validate_with_execution`] for the stronger
+    /// check.
+    ///
+    /// [`validate_with_execution`]: Self::validate_with_execution
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        parse_graph(&self.graph_text)
+            .map(|_| ())
+            .map_err(|e| ValidationError::Parse {
+                message: e.to_string(),
+                excerpt: excerpt(&self.graph_text),
+            })
+    }
+
+    /// Like [`validate`](Self::validate), then execute the graph with no
+    /// inputs through 0-openclaw's `GraphInterpreter` and check that it
+    /// produces an `output`.
+    ///
+    /// Execution goes through [`OpenclawAdapter`], so this works from
+    /// sync code and from inside either flavor of tokio runtime.
+    pub fn validate_with_execution(&self) -> Result<(), ValidationError> {
+        self.validate()?;
+        let execute_error = |message: String| ValidationError::Execute {
+            message,
+            excerpt: excerpt(&self.graph_text),
+        };
+        let outputs = OpenclawAdapter::new()
+            .execute_graph(&self.graph_text, HashMap::new())
+            .map_err(|e| execute_error(e.to_string()))?;
+        if !outputs.contains_key("output") {
+            return Err(execute_error("graph produced no `output`".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// [`compile`], then [`CompilerOutput::validate`] the result.
+pub fn compile_validated(input: &CompilerInput) -> Result<CompilerOutput, ValidationError> {
+    let output = compile(input);
+    output.validate()?;
+    Ok(output)
+}
@@ -0,0 +1,149 @@
+use std::convert::Infallible;
+use std::error::Error;
+
+use crate::adapters::convert::ConvertError;
+use crate::adapters::verify::HashMismatch;
+use crate::chain::ChainError;
+use crate::compiler::io::{IngestError, RdfImportError};
+use crate::compiler::{
+    CompilerError, DecompileError, MergeError, MigrationError, RecordIssue, RoundtripError,
+};
+use crate::format::compact::CompactError;
+use crate::store::{DecayError, PersistError, SnapshotError};
+use crate::time::InvalidTimestamp;
+use crate::types::{CompilerInputError, ContextMetaError, HashParseError, TupleError};
+
+/// The boxed error of a runtime outside the crate.
+pub type BoxError = Box<dyn Error + Send + Sync + 'static>;
+
+/// Any error of this crate, for callers that want one type to `match` on
+/// or `?` into.
+///
+/// Every module keeps its own error type, which converts into this one
+/// with `From` and is kept whole in its variant, so one `match` reaches
+/// the tuple index, key, line, or path:
+///
+/// ```
+/// # use zero_memory::compiler::CompilerError;
+/// # use zero_memory::error::MemoryError;
+/// fn describe(e: &MemoryError) -> String {
+///     match e {
+///         MemoryError::Compile(CompilerError::EmptyPredicate { tuple_index }) => {
+///             format!("tuple {} has no predicate", tuple_index)
+///         }
+///         MemoryError::Ingest(e) => format!("bad input: {}", e),
+///         e => e.to_string(),
+///     }
+/// }
+/// ```
+///
+/// `MemoryError` displays as the module error does, and its
+/// [`source`](Error::source) is the module error's source.
+///
+/// The variants are flat rather than nested under compile, store,
+/// runtime, persistence, and parse categories, so the module error is
+/// one pattern away; they are listed in that order. Some only exist with
+/// their feature enabled, and new module errors add variants, so the
+/// enum is `#[non_exhaustive]`: a `match` needs a catch-all arm.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum MemoryError {
+    // Compile: compiling and checking records.
+    #[error(transparent)]
+    Compile(#[from] CompilerError),
+    #[error(transparent)]
+    Input(#[from] CompilerInputError),
+    #[error(transparent)]
+    Context(#[from] ContextMetaError),
+    #[error(transparent)]
+    Tuple(#[from] TupleError),
+    #[error(transparent)]
+    Merge(#[from] MergeError),
+    #[error(transparent)]
+    Record(#[from] RecordIssue),
+    #[error(transparent)]
+    HashMismatch(#[from] HashMismatch),
+    #[error(transparent)]
+    Chain(#[from] ChainError),
+    #[error(transparent)]
+    Roundtrip(#[from] RoundtripError),
+    #[cfg(feature = "sign")]
+    #[error(transparent)]
+    Verify(#[from] crate::compiler::sign::VerifyError),
+    #[cfg(feature = "validate")]
+    #[error(transparent)]
+    Validation(#[from] crate::compiler::validate::ValidationError),
+
+    // Store: store operations and backends.
+    #[error(transparent)]
+    Decay(#[from] DecayError),
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    // Runtime: runtimes the store talks to.
+    #[cfg(feature = "openclaw")]
+    #[error(transparent)]
+    Openclaw(#[from] crate::adapters::openclaw::OpenclawError),
+    #[cfg(feature = "remote")]
+    #[error(transparent)]
+    Remote(#[from] crate::adapters::remote::RemoteError),
+    #[cfg(feature = "testing")]
+    #[error(transparent)]
+    Mock(#[from] crate::testing::MockError),
+    /// Any other [`MemoryRuntime`](crate::runtime_trait::MemoryRuntime)'s
+    /// error, through [`runtime`](Self::runtime).
+    #[error(transparent)]
+    Runtime(BoxError),
+
+    // Persistence: saving and loading stores.
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+    /// [`PersistError`] with the runtime's error boxed.
+    #[error(transparent)]
+    Persistence(PersistError<BoxError>),
+
+    // Parse: reading text or values into the crate's types.
+    #[error(transparent)]
+    Decompile(#[from] DecompileError),
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+    #[error(transparent)]
+    Ingest(#[from] IngestError),
+    #[error(transparent)]
+    RdfImport(#[from] RdfImportError),
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+    #[error(transparent)]
+    Compact(#[from] CompactError),
+    #[error(transparent)]
+    HashParse(#[from] HashParseError),
+    #[error(transparent)]
+    Timestamp(#[from] InvalidTimestamp),
+}
+
+impl MemoryError {
+    /// A [`Runtime`](Self::Runtime) error from any runtime's error type.
+    pub fn runtime<E: Error + Send + Sync + 'static>(e: E) -> Self {
+        MemoryError::Runtime(Box::new(e))
+    }
+}
+
+impl From<Infallible> for MemoryError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+#[cfg(feature = "openclaw")]
+impl From<zero_openclaw::error::GatewayError> for MemoryError {
+    fn from(e: zero_openclaw::error::GatewayError) -> Self {
+        crate::adapters::openclaw::OpenclawError::Gateway(e).into()
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> From<PersistError<E>> for MemoryError {
+    fn from(e: PersistError<E>) -> Self {
+        MemoryError::Persistence(e.map_runtime(|e| Box::new(e) as BoxError))
+    }
+}
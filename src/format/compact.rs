@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::compiler::emitter::short_ids;
+use crate::types::*;
+
+/// Hex characters of a hash in `.0c` text when they suffice to tell it
+/// apart from the other hashes of the record.
+const COMPACT_ID_LEN: usize = 8;
+
+/// Why [`parse_compact`] could not read `.0c` text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompactError {
+    /// Line `line` (1-based) does not fit the `.0c` grammar.
+    Malformed { line: usize, reason: &'static str },
+    /// A relation on line `line` refers to a short hash the legend does not
+    /// define.
+    UnknownHash { line: usize, hash: String },
+    /// The text ends before its `# relations` header.
+    Truncated,
+}
+
+impl fmt::Display for CompactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactError::Malformed { line, reason } => write!(f, "line {}: {}", line, reason),
+            CompactError::UnknownHash { line, hash } => {
+                write!(f, "line {}: {} is not in the legend", line, hash)
+            }
+            CompactError::Truncated => write!(f, "text ends before `# relations`"),
+        }
+    }
+}
+
+impl std::error::Error for CompactError {}
+
+/// A relation line of `.0c` text. Concepts and the context are named by
+/// their short hashes, which the legend of the [`CompactRecord`] maps to
+/// labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactRelation {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+    pub context: String,
+    pub confidence: f64,
+    pub polarity: Polarity,
+}
+
+/// `.0c` text, parsed: a legend of short hashes and the relations that
+/// refer to them.
+///
+/// Displays as `.0c` text, so tooling can read, edit, and write `.0c`
+/// without going through a [`MemoryRecord`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactRecord {
+    /// Concept labels by short hash.
+    pub concepts: BTreeMap<String, String>,
+    /// Context scopes by short hash.
+    pub contexts: BTreeMap<String, String>,
+    pub relations: Vec<CompactRelation>,
+}
+
+impl From<&MemoryRecord> for CompactRecord {
+    fn from(record: &MemoryRecord) -> Self {
+        let mut concept_hashes: Vec<&[u8; 32]> =
+            record.concepts.iter().map(|c| c.hash.as_bytes()).collect();
+        concept_hashes.extend(
+            record
+                .relations
+                .iter()
+                .flat_map(|r| [r.subject_hash.as_bytes(), r.object_hash.as_bytes()]),
+        );
+        let concept_ids: HashMap<&[u8; 32], String> = concept_hashes
+            .iter()
+            .copied()
+            .zip(short_ids(&concept_hashes, COMPACT_ID_LEN))
+            .collect();
+
+        let mut context_hashes = vec![record.context.hash.as_bytes()];
+        context_hashes.extend(record.relations.iter().map(|r| r.context_hash.as_bytes()));
+        let context_ids: HashMap<&[u8; 32], String> = context_hashes
+            .iter()
+            .copied()
+            .zip(short_ids(&context_hashes, COMPACT_ID_LEN))
+            .collect();
+
+        let mut relations: Vec<&RelationNode> = record.relations.iter().collect();
+        relations.sort_by(|a, b| a.episode_hash.cmp(&b.episode_hash));
+
+        CompactRecord {
+            concepts: record
+                .concepts
+                .iter()
+                .map(|c| (concept_ids[c.hash.as_bytes()].clone(), c.label.clone()))
+                .collect(),
+            contexts: BTreeMap::from([(
+                context_ids[record.context.hash.as_bytes()].clone(),
+                record.context.meta.scope.clone(),
+            )]),
+            relations: relations
+                .into_iter()
+                .map(|r| CompactRelation {
+                    subject: concept_ids[r.subject_hash.as_bytes()].clone(),
+                    predicate: r.predicate.to_string(),
+                    object: concept_ids[r.object_hash.as_bytes()].clone(),
+                    context: context_ids[r.context_hash.as_bytes()].clone(),
+                    confidence: r.confidence,
+                    polarity: r.polarity,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for CompactRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# legend")?;
+        for (id, label) in &self.concepts {
+            writeln!(f, "{} {}", id, label)?;
+        }
+        for (id, scope) in &self.contexts {
+            writeln!(f, "@{} {}", id, scope)?;
+        }
+        writeln!(f, "# relations")?;
+        for r in &self.relations {
+            let negation = match r.polarity {
+                Polarity::Positive => "",
+                Polarity::Negative => "!",
+            };
+            writeln!(
+                f,
+                "{} {}{} {} @{} ({})",
+                r.subject, negation, r.predicate, r.object, r.context, r.confidence
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialize a MemoryRecord into `.0c` compact text: a legend mapping
+/// short hashes to concept labels (and, prefixed with `@`, to context
+/// scopes), then one line per relation.
+///
+/// ```text
+/// # legend
+/// a1b2c3d4 agent
+/// e5f6a7b8 memory
+/// @c9d0e1f2 s1
+/// # relations
+/// a1b2c3d4 needs e5f6a7b8 @c9d0e1f2 (0.98)
+/// ```
+///
+/// Short hashes are the first 8 hex characters of a hash, lengthened where
+/// two hashes of the record share them (see [`hasher::short_hex`]).
+/// A negative relation's predicate is prefixed with `!`. Validity periods,
+/// spans, and qualifier links are left out; use the `.0` graph text for a
+/// lossless record.
+///
+/// [`hasher::short_hex`]: crate::compiler::hasher::short_hex
+pub fn emit_compact(record: &MemoryRecord) -> String {
+    CompactRecord::from(record).to_string()
+}
+
+/// Whether `s` can be a short hash: lowercase hex, at most a full hash.
+fn is_short_hash(s: &str) -> bool {
+    !s.is_empty() && s.len() <= 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Parse `.0c` text written by [`emit_compact`] (or by displaying a
+/// [`CompactRecord`]). Blank lines are ignored; every relation must refer
+/// to short hashes defined in the legend.
+pub fn parse_compact(text: &str) -> Result<CompactRecord, CompactError> {
+    #[derive(PartialEq)]
+    enum Section {
+        Start,
+        Legend,
+        Relations,
+    }
+
+    let mut section = Section::Start;
+    let mut record = CompactRecord::default();
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let malformed = |reason| CompactError::Malformed {
+            line: line_no,
+            reason,
+        };
+        if line.is_empty() {
+            continue;
+        }
+        match section {
+            Section::Start if line == "# legend" => section = Section::Legend,
+            Section::Start => return Err(malformed("expected `# legend`")),
+            Section::Legend if line == "# relations" => section = Section::Relations,
+            Section::Legend => {
+                let (id, label) = line
+                    .split_once(' ')
+                    .ok_or_else(|| malformed("legend entry without a label"))?;
+                let (legend, id) = match id.strip_prefix('@') {
+                    Some(id) => (&mut record.contexts, id),
+                    None => (&mut record.concepts, id),
+                };
+                if !is_short_hash(id) {
+                    return Err(malformed("legend entry without a short hash"));
+                }
+                if label.is_empty() {
+                    return Err(malformed("legend entry without a label"));
+                }
+                if legend.insert(id.to_string(), label.to_string()).is_some() {
+                    return Err(malformed("short hash defined twice"));
+                }
+            }
+            Section::Relations => {
+                let fields: Vec<&str> = line.split(' ').collect();
+                let [subject, predicate, object, context, confidence] = fields[..] else {
+                    return Err(malformed("relation without five fields"));
+                };
+                let (predicate, polarity) = match predicate.strip_prefix('!') {
+                    Some(predicate) => (predicate, Polarity::Negative),
+                    None => (predicate, Polarity::Positive),
+                };
+                if predicate.is_empty() {
+                    return Err(malformed("relation without a predicate"));
+                }
+                let context = context
+                    .strip_prefix('@')
+                    .ok_or_else(|| malformed("context without `@`"))?;
+                let confidence = confidence
+                    .strip_prefix('(')
+                    .and_then(|c| c.strip_suffix(')'))
+                    .and_then(|c| c.parse::<f64>().ok())
+                    .ok_or_else(|| malformed("confidence is not a parenthesized number"))?;
+
+                for (hash, legend) in [
+                    (subject, &record.concepts),
+                    (object, &record.concepts),
+                    (context, &record.contexts),
+                ] {
+                    if !legend.contains_key(hash) {
+                        return Err(CompactError::UnknownHash {
+                            line: line_no,
+                            hash: hash.to_string(),
+                        });
+                    }
+                }
+                record.relations.push(CompactRelation {
+                    subject: subject.to_string(),
+                    predicate: predicate.to_string(),
+                    object: object.to_string(),
+                    context: context.to_string(),
+                    confidence,
+                    polarity,
+                });
+            }
+        }
+    }
+    if section != Section::Relations {
+        return Err(CompactError::Truncated);
+    }
+    Ok(record)
+}
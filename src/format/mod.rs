@@ -0,0 +1,3 @@
+pub mod compact;
+
+pub use compact::{emit_compact, parse_compact, CompactError, CompactRecord, CompactRelation};
@@ -1,5 +1,22 @@
 pub mod adapters;
+#[cfg(feature = "proptest-support")]
+pub mod arbitrary;
+pub mod chain;
 pub mod compiler;
+pub mod error;
+pub mod format;
+pub mod memory;
+pub mod retry;
 pub mod runtime_trait;
+#[cfg(feature = "schemars")]
+pub mod schemas;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod session;
 pub mod store;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod time;
 pub mod types;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::compiler::emitter::try_compile_with;
+use crate::compiler::CompilerOptions;
+use crate::error::MemoryError;
+use crate::runtime_trait::{MemoryRuntime, TextValue};
+use crate::store::MemoryStore;
+use crate::types::*;
+
+/// What [`Memory::remember`] stored.
+#[derive(Debug, Clone)]
+pub struct RememberReceipt {
+    pub insert: InsertResult,
+    /// Content address of the compiled record.
+    pub record_hash: RecordHash,
+    /// The record's `.0` graph text.
+    pub graph_text: String,
+}
+
+/// One fact [`Memory::recall`] found about a concept: its endpoints'
+/// labels (display forms where known), and what its episodes say.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecallHit {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+    pub polarity: Polarity,
+    /// Highest confidence among the fact's episodes.
+    pub confidence: f64,
+    /// How many times the fact was observed.
+    pub episodes: usize,
+    /// Latest `event_time` of the contexts it was observed in.
+    pub last_seen: EventTime,
+    pub fact_hash: FactHash,
+}
+
+/// A [`MemoryStore`] with the compile-and-insert wiring done: hand it
+/// [`CompilerInput`]s, ask it about concepts.
+///
+/// ```
+/// use zero_memory::memory::Memory;
+/// use zero_memory::types::*;
+///
+/// let mut memory = Memory::in_memory();
+/// let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+///     .tuple("Agent", "needs", "Memory", 0.9)
+///     .build()
+///     .unwrap();
+/// memory.remember(input).unwrap();
+/// assert_eq!(memory.recall("agent", 5)[0].object, "Memory");
+/// ```
+///
+/// `R` is the runtime the store is saved to and loaded from, if any; see
+/// [`with_runtime`](Memory::with_runtime).
+#[derive(Debug)]
+pub struct Memory<R = ()> {
+    store: MemoryStore,
+    options: CompilerOptions,
+    runtime: R,
+}
+
+impl Memory<()> {
+    /// An empty memory living only in this process.
+    pub fn in_memory() -> Self {
+        Self::with_runtime(())
+    }
+}
+
+impl Default for Memory<()> {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl<R> Memory<R> {
+    /// An empty memory that can [`save`](Self::save) its store to
+    /// `runtime`'s state and [`load`](Self::load) it back.
+    pub fn with_runtime(runtime: R) -> Self {
+        Self {
+            store: MemoryStore::new(),
+            options: CompilerOptions::default(),
+            runtime,
+        }
+    }
+
+    /// Compile inputs with `options` instead of the defaults, e.g. with a
+    /// namespace or a predicate vocabulary.
+    pub fn compiler_options(mut self, options: CompilerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Compile `input`, rejecting it if it is invalid (see
+    /// [`try_compile_with`]), and insert the record into the store.
+    pub fn remember(&mut self, input: CompilerInput) -> Result<RememberReceipt, MemoryError> {
+        let output = try_compile_with(&input, &self.options)?;
+        let record_hash = output.record.hash();
+        let insert = self.store.insert_record(output.record);
+        Ok(RememberReceipt {
+            insert,
+            record_hash,
+            graph_text: output.graph_text,
+        })
+    }
+
+    /// Up to `limit` facts about the concept labelled (or aliased)
+    /// `query`, most confident first, then most recently seen. Empty if
+    /// no concept has that label. See [`MemoryStore::recall`].
+    pub fn recall(&self, query: &str, limit: usize) -> Vec<RecallHit> {
+        let Some(recollection) = self.store.recall(query) else {
+            return Vec::new();
+        };
+        let mut hits: Vec<RecallHit> = Vec::new();
+        let mut by_fact: HashMap<FactHash, usize> = HashMap::new();
+        for relation in &recollection.relations {
+            if let Some(&i) = by_fact.get(&relation.fact_hash) {
+                let hit = &mut hits[i];
+                hit.confidence = hit.confidence.max(relation.confidence);
+                hit.episodes += 1;
+                hit.last_seen = hit.last_seen.max(self.observed_at(relation));
+                continue;
+            }
+            by_fact.insert(relation.fact_hash.clone(), hits.len());
+            hits.push(RecallHit {
+                subject: self.display_label(&relation.subject_hash),
+                predicate: relation.predicate.to_string(),
+                object: self.display_label(&relation.object_hash),
+                polarity: relation.polarity,
+                confidence: relation.confidence,
+                episodes: 1,
+                last_seen: self.observed_at(relation),
+                fact_hash: relation.fact_hash.clone(),
+            });
+        }
+        hits.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then(b.last_seen.cmp(&a.last_seen))
+                .then_with(|| a.fact_hash.cmp(&b.fact_hash))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// When an episode was observed: its context's `event_time`, or its
+    /// `created_at` if the context is missing.
+    fn observed_at(&self, relation: &RelationNode) -> EventTime {
+        self.store
+            .get_context(&relation.context_hash)
+            .map_or(relation.created_at, |ctx| ctx.meta.event_time)
+    }
+
+    fn display_label(&self, hash: &ConceptHash) -> String {
+        match self.store.get_concept(hash) {
+            Some(concept) => concept
+                .display_label
+                .clone()
+                .unwrap_or_else(|| concept.label.clone()),
+            None => self.store.label_of(hash).unwrap_or_default().to_string(),
+        }
+    }
+
+    pub fn store(&self) -> &MemoryStore {
+        &self.store
+    }
+
+    /// The store, for what the facade does not cover: forgetting,
+    /// decay, exports, ...
+    pub fn store_mut(&mut self) -> &mut MemoryStore {
+        &mut self.store
+    }
+
+    pub fn into_store(self) -> MemoryStore {
+        self.store
+    }
+
+    pub fn runtime(&self) -> &R {
+        &self.runtime
+    }
+
+    pub fn into_runtime(self) -> R {
+        self.runtime
+    }
+}
+
+impl<R> Memory<R>
+where
+    R: MemoryRuntime,
+    R::Value: TextValue,
+    R::Error: Error + Send + Sync + 'static,
+{
+    /// Save the store to the runtime's state under `key_prefix`; see
+    /// [`MemoryStore::persist_to`].
+    pub fn save(&self, key_prefix: &str) -> Result<(), MemoryError> {
+        Ok(self.store.persist_to(&self.runtime, key_prefix)?)
+    }
+
+    /// A memory over the store saved to `runtime` under `key_prefix`; see
+    /// [`MemoryStore::restore_from`].
+    pub fn load(runtime: R, key_prefix: &str) -> Result<Self, MemoryError> {
+        let store = MemoryStore::restore_from(&runtime, key_prefix)?;
+        Ok(Self {
+            store,
+            ..Self::with_runtime(runtime)
+        })
+    }
+}
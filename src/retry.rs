@@ -0,0 +1,175 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::runtime_trait::MemoryRuntime;
+
+/// When and how often [`RetryingRuntime`] retries a failed call. `E` is
+/// the runtime's error type.
+pub struct RetryPolicy<E> {
+    /// Attempts per call, the first included; `0` counts as `1`.
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubling before each one after.
+    pub backoff: Duration,
+    /// Wait a random time between half of each delay and all of it, so
+    /// callers failing together do not retry together.
+    pub jitter: bool,
+    /// Whether an error is worth retrying; others are returned at once.
+    pub retry_if: fn(&E) -> bool,
+}
+
+// Manual impls: derives would require `E: Clone` and `E: Debug`.
+impl<E> Clone for RetryPolicy<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for RetryPolicy<E> {}
+
+impl<E> fmt::Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("jitter", &self.jitter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> Default for RetryPolicy<E> {
+    /// Three attempts, 100 ms apart and then 200 ms, jittered, retrying
+    /// every error.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_millis(100),
+            jitter: true,
+            retry_if: |_| true,
+        }
+    }
+}
+
+impl<E> RetryPolicy<E> {
+    /// How long to wait after failed attempt `attempt` (1-based).
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.backoff.saturating_mul(1 << (attempt - 1).min(16));
+        if !self.jitter {
+            return delay;
+        }
+        let random = RandomState::new().build_hasher().finish();
+        let half = delay / 2;
+        half + half.mul_f64((random >> 11) as f64 / (1u64 << 53) as f64)
+    }
+}
+
+/// A [`MemoryRuntime`] that retries failed calls of another under a
+/// [`RetryPolicy`], sleeping the calling thread between attempts.
+///
+/// `execute_graph`, the state methods, and the batched state methods
+/// are retried whole; `hash` cannot fail and is passed straight through.
+/// Retried saves write the same values again, so they are safe to
+/// repeat.
+#[derive(Debug, Clone)]
+pub struct RetryingRuntime<R: MemoryRuntime> {
+    runtime: R,
+    policy: RetryPolicy<R::Error>,
+}
+
+impl<R: MemoryRuntime> RetryingRuntime<R> {
+    pub fn new(runtime: R, policy: RetryPolicy<R::Error>) -> Self {
+        Self { runtime, policy }
+    }
+
+    pub fn policy(&self) -> &RetryPolicy<R::Error> {
+        &self.policy
+    }
+
+    /// The wrapped runtime, whose calls are not retried.
+    pub fn inner(&self) -> &R {
+        &self.runtime
+    }
+
+    pub fn into_inner(self) -> R {
+        self.runtime
+    }
+
+    fn retry<T>(&self, mut call: impl FnMut(&R) -> Result<T, R::Error>) -> Result<T, R::Error> {
+        let mut attempt = 1;
+        loop {
+            match call(&self.runtime) {
+                Err(e) if attempt < self.policy.max_attempts && (self.policy.retry_if)(&e) => {
+                    std::thread::sleep(self.policy.delay(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<R: MemoryRuntime> MemoryRuntime for RetryingRuntime<R> {
+    type Value = R::Value;
+    type Hash = R::Hash;
+    type Error = R::Error;
+
+    fn hash(&self, input: &[u8]) -> Self::Hash {
+        self.runtime.hash(input)
+    }
+
+    fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        self.retry(|runtime| runtime.execute_graph(graph_source, inputs.clone()))
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        self.retry(|runtime| runtime.load_state(key))
+    }
+
+    fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        self.retry(|runtime| runtime.save_state(key, value))
+    }
+
+    fn load_state_many(&self, keys: &[String]) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        self.retry(|runtime| runtime.load_state_many(keys))
+    }
+
+    fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        self.retry(|runtime| runtime.save_state_many(entries))
+    }
+
+    fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        self.retry(|runtime| runtime.list_state_keys(prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jittered_delays_stay_between_half_and_all_of_the_backoff() {
+        let policy = RetryPolicy::<()> {
+            backoff: Duration::from_millis(100),
+            ..RetryPolicy::default()
+        };
+        for attempt in 1..=3 {
+            let full = Duration::from_millis(100) * (1 << (attempt - 1));
+            for _ in 0..50 {
+                let delay = policy.delay(attempt);
+                assert!(delay >= full / 2 && delay <= full, "{:?}", delay);
+            }
+        }
+        let steady = RetryPolicy::<()> {
+            jitter: false,
+            ..policy
+        };
+        assert_eq!(steady.delay(3), Duration::from_millis(400));
+        assert_eq!(steady.delay(40), Duration::from_millis(100) * (1 << 16));
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
+use crate::types::ContextMeta;
+
 /// Trait that any 0-lang runtime must implement for 0-memory to use it.
 ///
-/// For MVP, implemented by `OpenclawAdapter` (behind the `openclaw` feature).
+/// For MVP, implemented by `OpenclawAdapter` (behind the `openclaw` feature),
+/// which also implements `AsyncMemoryRuntime` for async callers.
 /// When 0-chain's executor matures, a `ChainAdapter` will be added.
 pub trait MemoryRuntime {
     type Value: Clone + std::fmt::Debug;
@@ -24,4 +27,481 @@ pub trait MemoryRuntime {
 
     /// Save state under a key.
     fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error>;
+
+    /// Load several keys at once, in order, `None` for each missing key.
+    ///
+    /// The default loads one key at a time; runtimes with a per-call cost,
+    /// such as a sync bridge over an async interpreter, override it.
+    fn load_state_many(&self, keys: &[String]) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        keys.iter().map(|key| self.load_state(key)).collect()
+    }
+
+    /// Save several entries at once, in order, stopping at the first
+    /// error. Entries saved before it stay saved.
+    ///
+    /// The default saves one entry at a time; see
+    /// [`load_state_many`](Self::load_state_many).
+    fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        entries
+            .iter()
+            .try_for_each(|(key, value)| self.save_state(key, value))
+    }
+
+    /// Every stored key starting with `prefix`, sorted, or `None` if the
+    /// runtime cannot enumerate its state. The default returns `None`.
+    fn list_state_keys(&self, _prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// This runtime with its state keys scoped to `namespace`.
+    fn namespaced(&self, namespace: StateNamespace) -> NamespacedRuntime<'_, Self>
+    where
+        Self: Sized,
+    {
+        NamespacedRuntime::new(self, namespace)
+    }
+}
+
+/// A runtime value that can carry text, which is how the store
+/// [persists](crate::store::MemoryStore::persist_to) itself into runtime
+/// state.
+pub trait TextValue: Sized {
+    fn from_text(text: String) -> Self;
+
+    /// The text this value carries, or `None` if it is not text.
+    fn as_text(&self) -> Option<&str>;
+}
+
+impl TextValue for String {
+    fn from_text(text: String) -> Self {
+        text
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl TextValue for serde_json::Value {
+    fn from_text(text: String) -> Self {
+        serde_json::Value::String(text)
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        self.as_str()
+    }
+}
+
+/// A runtime value that can be read as JSON, which is how
+/// [`verify_roundtrip`](crate::compiler::verify_roundtrip) inspects what a
+/// graph produced.
+pub trait InspectValue {
+    /// This value as JSON. Hashes and bytes become lowercase hex strings;
+    /// values with no JSON form become `null`.
+    fn to_json(&self) -> serde_json::Value;
+}
+
+impl InspectValue for String {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::String(self.clone())
+    }
+}
+
+impl InspectValue for serde_json::Value {
+    fn to_json(&self) -> serde_json::Value {
+        self.clone()
+    }
+}
+
+/// Async counterpart of [`MemoryRuntime`], for runtimes whose operations
+/// are futures, called from async code without blocking a worker thread.
+///
+/// `hash` stays sync: it is pure computation.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncMemoryRuntime: Send + Sync {
+    type Value: Clone + std::fmt::Debug + Send + Sync;
+    type Hash: AsRef<[u8]> + Clone;
+    type Error: std::fmt::Display + Send;
+
+    /// Compute SHA-256 hash of arbitrary bytes.
+    fn hash(&self, input: &[u8]) -> Self::Hash;
+
+    /// Execute a 0-lang graph given as source text, with named inputs.
+    async fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error>;
+
+    /// Load persisted state by key. Returns `None` if the key does not exist.
+    async fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error>;
+
+    /// Save state under a key.
+    async fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error>;
+
+    /// Load several keys at once. See [`MemoryRuntime::load_state_many`].
+    async fn load_state_many(
+        &self,
+        keys: &[String],
+    ) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            values.push(self.load_state(key).await?);
+        }
+        Ok(values)
+    }
+
+    /// Save several entries at once. See [`MemoryRuntime::save_state_many`].
+    async fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        for (key, value) in entries {
+            self.save_state(key, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Every stored key starting with `prefix`. See
+    /// [`MemoryRuntime::list_state_keys`].
+    async fn list_state_keys(&self, _prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// Drive a future to completion from sync code, inside or outside a tokio
+/// runtime.
+///
+/// * Inside a multi-thread runtime, blocks in place on the current
+///   runtime.
+/// * Inside a current-thread runtime, which cannot block in place, runs
+///   the future on a fresh current-thread runtime in a scoped thread. The
+///   outer runtime is stalled meanwhile, so the future must not wait on
+///   its tasks.
+/// * Outside any runtime, runs it on a fresh current-thread runtime.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: std::future::Future + Send,
+    F::Output: Send,
+{
+    use tokio::runtime::{Builder, Handle, RuntimeFlavor};
+
+    let run = |future: F| {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to create tokio runtime for a blocking call")
+            .block_on(future)
+    };
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+            std::thread::scope(|scope| {
+                scope
+                    .spawn(|| run(future))
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+        }
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => run(future),
+    }
+}
+
+/// An [`AsyncMemoryRuntime`] used where a [`MemoryRuntime`] is expected.
+/// Each call blocks until the async one completes: in place inside a
+/// multi-thread tokio runtime, on a separate thread inside a
+/// current-thread one, so it works from sync code and from either flavor.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Default)]
+pub struct BlockingRuntime<R>(pub R);
+
+#[cfg(feature = "async")]
+impl<R: AsyncMemoryRuntime> MemoryRuntime for BlockingRuntime<R> {
+    type Value = R::Value;
+    type Hash = R::Hash;
+    type Error = R::Error;
+
+    fn hash(&self, input: &[u8]) -> Self::Hash {
+        self.0.hash(input)
+    }
+
+    fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        block_on(self.0.execute_graph(graph_source, inputs))
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        block_on(self.0.load_state(key))
+    }
+
+    fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        block_on(self.0.save_state(key, value))
+    }
+
+    /// Crosses into async code once for the whole batch.
+    fn load_state_many(&self, keys: &[String]) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        block_on(self.0.load_state_many(keys))
+    }
+
+    /// Crosses into async code once for the whole batch.
+    fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        block_on(self.0.save_state_many(entries))
+    }
+
+    fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        block_on(self.0.list_state_keys(prefix))
+    }
+}
+
+/// A [`MemoryRuntime`] used where an [`AsyncMemoryRuntime`] is expected.
+/// Each call runs the sync one inline, on the calling task, so it suits
+/// runtimes whose calls are quick.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Default)]
+pub struct InlineRuntime<R>(pub R);
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<R> AsyncMemoryRuntime for InlineRuntime<R>
+where
+    R: MemoryRuntime + Send + Sync,
+    R::Value: Send + Sync,
+    R::Error: Send,
+{
+    type Value = R::Value;
+    type Hash = R::Hash;
+    type Error = R::Error;
+
+    fn hash(&self, input: &[u8]) -> Self::Hash {
+        self.0.hash(input)
+    }
+
+    async fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        self.0.execute_graph(graph_source, inputs)
+    }
+
+    async fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        self.0.load_state(key)
+    }
+
+    async fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        self.0.save_state(key, value)
+    }
+
+    async fn load_state_many(
+        &self,
+        keys: &[String],
+    ) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        self.0.load_state_many(keys)
+    }
+
+    async fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        self.0.save_state_many(entries)
+    }
+
+    async fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        self.0.list_state_keys(prefix)
+    }
+}
+
+/// The agent and session that runtime state belongs to, so agents sharing
+/// one runtime keep separate key spaces.
+///
+/// A key `k` is stored as `agent/{agent_id}/session/{session_id}/k`. A
+/// missing id is left empty, and `%` and `/` in ids are percent-escaped,
+/// so two namespaces never store a key under the same name. An empty id
+/// stores keys exactly like a missing one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StateNamespace {
+    pub agent_id: Option<String>,
+    pub session_id: Option<String>,
+}
+
+impl StateNamespace {
+    /// The namespace with neither an agent nor a session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn agent_id(mut self, agent_id: &str) -> Self {
+        self.agent_id = Some(agent_id.to_string());
+        self
+    }
+
+    pub fn session_id(mut self, session_id: &str) -> Self {
+        self.session_id = Some(session_id.to_string());
+        self
+    }
+
+    /// The prefix every key in this namespace is stored under, ending in
+    /// `/`.
+    pub fn prefix(&self) -> String {
+        format!(
+            "agent/{}/session/{}/",
+            escape_id(self.agent_id.as_deref()),
+            escape_id(self.session_id.as_deref())
+        )
+    }
+
+    /// The name `key` is stored under in this namespace.
+    pub fn key(&self, key: &str) -> String {
+        self.prefix() + key
+    }
+}
+
+/// The agent and session of an observation's context.
+impl From<&ContextMeta> for StateNamespace {
+    fn from(meta: &ContextMeta) -> Self {
+        Self {
+            agent_id: meta.agent_id.clone(),
+            session_id: meta.session_id.clone(),
+        }
+    }
+}
+
+fn escape_id(id: Option<&str>) -> String {
+    id.unwrap_or_default()
+        .replace('%', "%25")
+        .replace('/', "%2F")
+}
+
+/// A runtime whose state keys are scoped to a [`StateNamespace`], made by
+/// [`MemoryRuntime::namespaced`]. Keys go in and come out unprefixed;
+/// hashing and graph execution pass straight through.
+#[derive(Debug)]
+pub struct NamespacedRuntime<'a, R> {
+    runtime: &'a R,
+    namespace: StateNamespace,
+    prefix: String,
+}
+
+impl<'a, R> NamespacedRuntime<'a, R> {
+    pub fn new(runtime: &'a R, namespace: StateNamespace) -> Self {
+        let prefix = namespace.prefix();
+        Self {
+            runtime,
+            namespace,
+            prefix,
+        }
+    }
+
+    pub fn namespace(&self) -> &StateNamespace {
+        &self.namespace
+    }
+
+    /// The shared runtime, with unscoped keys.
+    pub fn inner(&self) -> &'a R {
+        self.runtime
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    fn entries<V: Clone>(&self, entries: &[(String, V)]) -> Vec<(String, V)> {
+        entries
+            .iter()
+            .map(|(key, value)| (self.key(key), value.clone()))
+            .collect()
+    }
+
+    fn strip(&self, keys: Option<Vec<String>>) -> Option<Vec<String>> {
+        keys.map(|keys| {
+            keys.iter()
+                .filter_map(|key| key.strip_prefix(self.prefix.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+    }
+}
+
+impl<R: MemoryRuntime> MemoryRuntime for NamespacedRuntime<'_, R> {
+    type Value = R::Value;
+    type Hash = R::Hash;
+    type Error = R::Error;
+
+    fn hash(&self, input: &[u8]) -> Self::Hash {
+        self.runtime.hash(input)
+    }
+
+    fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        self.runtime.execute_graph(graph_source, inputs)
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        self.runtime.load_state(&self.key(key))
+    }
+
+    fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        self.runtime.save_state(&self.key(key), value)
+    }
+
+    fn load_state_many(&self, keys: &[String]) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        let keys: Vec<String> = keys.iter().map(|key| self.key(key)).collect();
+        self.runtime.load_state_many(&keys)
+    }
+
+    fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        self.runtime.save_state_many(&self.entries(entries))
+    }
+
+    fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        let keys = self.runtime.list_state_keys(&self.key(prefix))?;
+        Ok(self.strip(keys))
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<R: AsyncMemoryRuntime> AsyncMemoryRuntime for NamespacedRuntime<'_, R> {
+    type Value = R::Value;
+    type Hash = R::Hash;
+    type Error = R::Error;
+
+    fn hash(&self, input: &[u8]) -> Self::Hash {
+        self.runtime.hash(input)
+    }
+
+    async fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Self::Value>,
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        self.runtime.execute_graph(graph_source, inputs).await
+    }
+
+    async fn load_state(&self, key: &str) -> Result<Option<Self::Value>, Self::Error> {
+        self.runtime.load_state(&self.key(key)).await
+    }
+
+    async fn save_state(&self, key: &str, value: &Self::Value) -> Result<(), Self::Error> {
+        self.runtime.save_state(&self.key(key), value).await
+    }
+
+    async fn load_state_many(
+        &self,
+        keys: &[String],
+    ) -> Result<Vec<Option<Self::Value>>, Self::Error> {
+        let keys: Vec<String> = keys.iter().map(|key| self.key(key)).collect();
+        self.runtime.load_state_many(&keys).await
+    }
+
+    async fn save_state_many(&self, entries: &[(String, Self::Value)]) -> Result<(), Self::Error> {
+        self.runtime.save_state_many(&self.entries(entries)).await
+    }
+
+    async fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, Self::Error> {
+        let keys = self.runtime.list_state_keys(&self.key(prefix)).await?;
+        Ok(self.strip(keys))
+    }
 }
@@ -0,0 +1,29 @@
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+use crate::types::{CompilerInput, CompilerOutput, MemoryRecord, SemanticTuple};
+
+/// Schema of a [`CompilerInput`], the body a client sends to be compiled.
+///
+/// Like every schema here, it describes what this build writes: hashes
+/// as 64 hex digits, timestamps as RFC 3339. Fields with a serde default
+/// are optional.
+pub fn compiler_input_schema() -> RootSchema {
+    schema_for!(CompilerInput)
+}
+
+/// Schema of a single [`SemanticTuple`].
+pub fn semantic_tuple_schema() -> RootSchema {
+    schema_for!(SemanticTuple)
+}
+
+/// Schema of a [`CompilerOutput`]: graph text, record, and warnings.
+pub fn compiler_output_schema() -> RootSchema {
+    schema_for!(CompilerOutput)
+}
+
+/// Schema of a [`MemoryRecord`] at
+/// [`RECORD_SCHEMA_VERSION`](crate::types::RECORD_SCHEMA_VERSION).
+pub fn memory_record_schema() -> RootSchema {
+    schema_for!(MemoryRecord)
+}
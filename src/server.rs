@@ -0,0 +1,236 @@
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, Request, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::compiler::emitter::try_compile;
+use crate::compiler::{record_from_json, RecordIssue};
+use crate::store::{IngestStats, SharedStore};
+use crate::types::*;
+
+/// Options of [`router_with`] and [`serve_with`].
+#[derive(Debug, Clone, Default)]
+pub struct ServerOptions {
+    /// If set, every request must carry `Authorization: Bearer <token>`;
+    /// others get `401`.
+    pub bearer_token: Option<String>,
+}
+
+/// What `GET /stats` returns.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreStats {
+    pub concepts: usize,
+    pub facts: usize,
+    /// Stored relation episodes.
+    pub relations: usize,
+    pub contexts: usize,
+    pub ingest: IngestStats,
+}
+
+/// A failed request: its status, and a message sent as
+/// `{"error": message}`, with the `issues` of a rejected record if any.
+struct ApiError {
+    status: StatusCode,
+    message: String,
+    issues: Vec<RecordIssue>,
+}
+
+impl ApiError {
+    fn bad_request(e: impl ToString) -> Self {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: e.to_string(),
+            issues: Vec::new(),
+        }
+    }
+
+    fn not_found(message: String) -> Self {
+        ApiError {
+            status: StatusCode::NOT_FOUND,
+            message,
+            issues: Vec::new(),
+        }
+    }
+
+    fn invalid_record(issues: Vec<RecordIssue>) -> Self {
+        let message = issues
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: format!("invalid record: {}", message),
+            issues,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = if self.issues.is_empty() {
+            json!({ "error": self.message })
+        } else {
+            json!({ "error": self.message, "issues": self.issues })
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// [`router_with`] the default options: no authentication.
+pub fn router(store: SharedStore) -> Router {
+    router_with(store, ServerOptions::default())
+}
+
+/// The HTTP API over `store`, all bodies JSON:
+///
+/// * `POST /records` inserts a [`MemoryRecord`], or a [`CompilerInput`]
+///   (told apart by its `tuples`) compiled with [`try_compile`], and
+///   returns the [`InsertResult`]. Records must pass
+///   [`MemoryRecord::validate`]; otherwise the response lists the issues.
+/// * `GET /concepts/{hash}` returns the concept with that hex hash.
+/// * `GET /concepts?label=...` returns the concept with that label or
+///   alias.
+/// * `GET /concepts/{hash}/relations` returns the concept's relation
+///   episodes; see [`MemoryStore::get_relations`](crate::store::MemoryStore::get_relations).
+/// * `GET /stats` returns [`StoreStats`].
+///
+/// Malformed bodies, inputs the compiler rejects, and bad hex hashes get
+/// `400`; unknown concepts get `404`. Errors come as `{"error": "..."}`.
+pub fn router_with(store: SharedStore, options: ServerOptions) -> Router {
+    let router = Router::new()
+        .route("/records", post(post_record))
+        .route("/concepts", get(concept_by_label))
+        .route("/concepts/:hash", get(concept))
+        .route("/concepts/:hash/relations", get(relations))
+        .route("/stats", get(stats))
+        .with_state(store);
+    match options.bearer_token {
+        Some(token) => {
+            let expected: Arc<str> = format!("Bearer {}", token).into();
+            router.layer(middleware::from_fn_with_state(expected, authorize))
+        }
+        None => router,
+    }
+}
+
+/// [`serve_with`] the default options.
+pub async fn serve(store: SharedStore, addr: SocketAddr) -> io::Result<()> {
+    serve_with(store, addr, ServerOptions::default()).await
+}
+
+/// Serve [`router_with`] on `addr` until the future is dropped or the
+/// listener fails.
+pub async fn serve_with(
+    store: SharedStore,
+    addr: SocketAddr,
+    options: ServerOptions,
+) -> io::Result<()> {
+    let to_io = |e| io::Error::new(io::ErrorKind::Other, e);
+    axum::Server::try_bind(&addr)
+        .map_err(to_io)?
+        .serve(router_with(store, options).into_make_service())
+        .await
+        .map_err(to_io)
+}
+
+async fn authorize<B>(
+    State(expected): State<Arc<str>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let header = request.headers().get(header::AUTHORIZATION);
+    if header.map_or(false, |value| value.as_bytes() == expected.as_bytes()) {
+        next.run(request).await
+    } else {
+        ApiError {
+            status: StatusCode::UNAUTHORIZED,
+            message: "missing or wrong bearer token".to_string(),
+            issues: Vec::new(),
+        }
+        .into_response()
+    }
+}
+
+async fn post_record(
+    State(store): State<SharedStore>,
+    body: String,
+) -> Result<Json<InsertResult>, ApiError> {
+    let value: Value = serde_json::from_str(&body).map_err(ApiError::bad_request)?;
+    let record = if value.get("tuples").is_some() {
+        let input = CompilerInput::deserialize(value).map_err(ApiError::bad_request)?;
+        try_compile(&input).map_err(ApiError::bad_request)?.record
+    } else {
+        let record = record_from_json(&body).map_err(ApiError::bad_request)?;
+        record.validate().map_err(ApiError::invalid_record)?;
+        record
+    };
+    Ok(Json(store.write().await.insert_record(record)))
+}
+
+fn parse_hash(hex: &str) -> Result<ConceptHash, ApiError> {
+    ConceptHash::from_hex(hex)
+        .map_err(|e| ApiError::bad_request(format!("bad concept hash {:?}: {}", hex, e)))
+}
+
+async fn concept(
+    State(store): State<SharedStore>,
+    Path(hex): Path<String>,
+) -> Result<Json<ConceptNode>, ApiError> {
+    let hash = parse_hash(&hex)?;
+    match store.read().await.get_concept(&hash) {
+        Some(concept) => Ok(Json(concept.clone())),
+        None => Err(ApiError::not_found(format!("no concept {}", hash))),
+    }
+}
+
+#[derive(Deserialize)]
+struct LabelQuery {
+    label: String,
+}
+
+async fn concept_by_label(
+    State(store): State<SharedStore>,
+    Query(query): Query<LabelQuery>,
+) -> Result<Json<ConceptNode>, ApiError> {
+    match store.read().await.get_concept_by_label(&query.label) {
+        Some(concept) => Ok(Json(concept.clone())),
+        None => Err(ApiError::not_found(format!(
+            "no concept labelled {:?}",
+            query.label
+        ))),
+    }
+}
+
+async fn relations(
+    State(store): State<SharedStore>,
+    Path(hex): Path<String>,
+) -> Result<Json<Vec<RelationNode>>, ApiError> {
+    let hash = parse_hash(&hex)?;
+    let store = store.read().await;
+    if store.get_concept(&hash).is_none() {
+        return Err(ApiError::not_found(format!("no concept {}", hash)));
+    }
+    Ok(Json(
+        store.get_relations(&hash).into_iter().cloned().collect(),
+    ))
+}
+
+async fn stats(State(store): State<SharedStore>) -> Json<StoreStats> {
+    let store = store.read().await;
+    Json(StoreStats {
+        concepts: store.concept_count(),
+        facts: store.fact_count(),
+        relations: store.relation_count(),
+        contexts: store.context_count(),
+        ingest: store.ingest_stats().clone(),
+    })
+}
@@ -0,0 +1,119 @@
+use crate::compiler::emitter::try_compile;
+use crate::compiler::{CompilerError, CompilerOutput};
+use crate::error::MemoryError;
+use crate::memory::{Memory, RememberReceipt};
+use crate::time::EventTime;
+use crate::types::*;
+
+/// A run of observations from one source and scope, stamping each with
+/// the time it was made.
+///
+/// Every [`observe`](Self::observe) gets a later event time than the one
+/// before it, so its context hash differs: observing the same tuple twice
+/// records one fact with two episodes.
+///
+/// ```
+/// use zero_memory::memory::Memory;
+/// use zero_memory::session::MemorySession;
+/// use zero_memory::types::*;
+///
+/// let mut memory = Memory::in_memory();
+/// let mut session = MemorySession::start("user_prompt", "conversation_42")
+///     .agent_id("assistant")
+///     .memory(&mut memory);
+/// let tuple = SemanticTuple::from(("Agent", "needs", "Memory", 0.9));
+/// session.observe_and_remember(vec![tuple.clone()]).unwrap();
+/// let receipt = session.observe_and_remember(vec![tuple]).unwrap();
+/// assert_eq!(receipt.insert.new_episodes, 1);
+/// assert_eq!(memory.recall("agent", 1)[0].episodes, 2);
+/// ```
+///
+/// `M` is the [`Memory`] observations are remembered in, if any; see
+/// [`memory`](Self::memory).
+#[derive(Debug)]
+pub struct MemorySession<M = ()> {
+    context: ContextMeta,
+    last: Option<EventTime>,
+    memory: M,
+}
+
+impl MemorySession<()> {
+    /// A session for observations from `source` in `scope`.
+    pub fn start(source: &str, scope: &str) -> Self {
+        MemorySession {
+            context: ContextMeta::now(source, scope),
+            last: None,
+            memory: (),
+        }
+    }
+
+    /// Remember what is observed in `memory`, with
+    /// [`observe_and_remember`](MemorySession::observe_and_remember).
+    pub fn memory<R>(self, memory: &mut Memory<R>) -> MemorySession<&mut Memory<R>> {
+        MemorySession {
+            context: self.context,
+            last: self.last,
+            memory,
+        }
+    }
+}
+
+impl<M> MemorySession<M> {
+    pub fn agent_id(mut self, agent_id: &str) -> Self {
+        self.context.agent_id = Some(agent_id.to_string());
+        self
+    }
+
+    pub fn session_id(mut self, session_id: &str) -> Self {
+        self.context.session_id = Some(session_id.to_string());
+        self
+    }
+
+    /// The context of the last observation, or of the next one before
+    /// there is any.
+    pub fn context(&self) -> &ContextMeta {
+        &self.context
+    }
+
+    /// `tuples` as an input, in this session's context at the current
+    /// time. Not validated; [`observe_and_compile`](Self::observe_and_compile)
+    /// rejects what the compiler would.
+    pub fn observe(&mut self, tuples: Vec<SemanticTuple>) -> CompilerInput {
+        let mut event_time = EventTime::now();
+        // The clock may not have moved, or may have gone back, since the
+        // last observation; keep event times (and context hashes) apart.
+        if let Some(last) = self.last {
+            if event_time <= last {
+                event_time = (last.as_datetime() + chrono::Duration::nanoseconds(1)).into();
+            }
+        }
+        self.last = Some(event_time);
+        self.context.event_time = event_time;
+        CompilerInput {
+            utterance: None,
+            tuples,
+            context: self.context.clone(),
+        }
+    }
+
+    /// [`observe`](Self::observe) `tuples` and compile them with
+    /// [`try_compile`].
+    pub fn observe_and_compile(
+        &mut self,
+        tuples: Vec<SemanticTuple>,
+    ) -> Result<CompilerOutput, CompilerError> {
+        try_compile(&self.observe(tuples))
+    }
+}
+
+impl<R> MemorySession<&mut Memory<R>> {
+    /// [`observe`](Self::observe) `tuples` and
+    /// [`remember`](Memory::remember) them.
+    pub fn observe_and_remember(
+        &mut self,
+        tuples: Vec<SemanticTuple>,
+    ) -> Result<RememberReceipt, MemoryError> {
+        let input = self.observe(tuples);
+        self.memory.remember(input)
+    }
+}
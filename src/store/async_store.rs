@@ -0,0 +1,165 @@
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::backend::MemoryBackend;
+use super::graph::MemoryStore;
+use crate::types::*;
+
+/// Async counterpart of [`MemoryBackend`] for agents running on tokio.
+///
+/// Every method returns owned data, so no lock guard or borrow is held
+/// across an `.await` in the caller. Methods take `&self`: implementations
+/// handle their own synchronization, which lets a store be shared between
+/// tasks by cloning a handle.
+#[async_trait]
+pub trait AsyncMemoryStore: Send + Sync {
+    type Error: fmt::Display + fmt::Debug + Send;
+
+    /// Insert a full memory record. See [`MemoryStore::insert_record`].
+    async fn insert_record(&self, record: MemoryRecord) -> Result<InsertResult, Self::Error>;
+
+    async fn get_concept(&self, hash: &ConceptHash) -> Result<Option<ConceptNode>, Self::Error>;
+
+    /// Look up a concept by label. The label is normalized before lookup.
+    async fn get_concept_by_label(&self, label: &str) -> Result<Option<ConceptNode>, Self::Error>;
+
+    /// All relation episodes that reference the concept as subject or
+    /// object, each returned once.
+    async fn get_relations(
+        &self,
+        concept_hash: &ConceptHash,
+    ) -> Result<Vec<RelationNode>, Self::Error>;
+
+    /// All episodes of a fact, ordered chronologically by context
+    /// `event_time` (ties in insertion order).
+    async fn get_relations_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> Result<Vec<RelationNode>, Self::Error>;
+
+    /// The most recent episode of a fact.
+    async fn latest_relation_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> Result<Option<RelationNode>, Self::Error>;
+
+    async fn get_context(&self, hash: &ContextHash) -> Result<Option<ContextNode>, Self::Error>;
+
+    /// Look up a concept by label together with its relations.
+    async fn recall(&self, label: &str) -> Result<Option<Recollection>, Self::Error>;
+
+    async fn concept_count(&self) -> Result<usize, Self::Error>;
+
+    /// Number of stored relation episodes.
+    async fn relation_count(&self) -> Result<usize, Self::Error>;
+}
+
+/// Shared handle that puts any sync [`MemoryBackend`] behind a
+/// `tokio::sync::RwLock`, so async tasks can use it without
+/// `spawn_blocking`.
+///
+/// Reads take the lock shared and run concurrently; inserts take it
+/// exclusively. Every trait method releases the lock before returning.
+/// Cloning the handle is cheap and shares the same store, mirroring how
+/// `OpenclawAdapter` shares its interpreter state behind
+/// `Arc<tokio::sync::RwLock<…>>`.
+///
+/// Operations outside the trait (decay, forgetting, snapshots, …) are
+/// reachable through [`read`](Self::read) and [`write`](Self::write).
+/// Avoid holding those guards across `.await` points.
+pub struct SharedStore<B = MemoryStore> {
+    inner: Arc<RwLock<B>>,
+}
+
+impl<B> SharedStore<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(backend)),
+        }
+    }
+
+    /// Lock the store for reading.
+    pub async fn read(&self) -> RwLockReadGuard<'_, B> {
+        self.inner.read().await
+    }
+
+    /// Lock the store for writing.
+    pub async fn write(&self) -> RwLockWriteGuard<'_, B> {
+        self.inner.write().await
+    }
+}
+
+impl<B> Clone for SharedStore<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<B: Default> Default for SharedStore<B> {
+    fn default() -> Self {
+        Self::new(B::default())
+    }
+}
+
+#[async_trait]
+impl<B> AsyncMemoryStore for SharedStore<B>
+where
+    B: MemoryBackend + Send + Sync,
+    B::Error: Send,
+{
+    type Error = B::Error;
+
+    async fn insert_record(&self, record: MemoryRecord) -> Result<InsertResult, B::Error> {
+        self.inner.write().await.insert_record(record)
+    }
+
+    async fn get_concept(&self, hash: &ConceptHash) -> Result<Option<ConceptNode>, B::Error> {
+        self.inner.read().await.get_concept(hash)
+    }
+
+    async fn get_concept_by_label(&self, label: &str) -> Result<Option<ConceptNode>, B::Error> {
+        self.inner.read().await.get_concept_by_label(label)
+    }
+
+    async fn get_relations(
+        &self,
+        concept_hash: &ConceptHash,
+    ) -> Result<Vec<RelationNode>, B::Error> {
+        self.inner.read().await.get_relations(concept_hash)
+    }
+
+    async fn get_relations_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> Result<Vec<RelationNode>, B::Error> {
+        self.inner.read().await.get_relations_by_fact(fact_hash)
+    }
+
+    async fn latest_relation_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> Result<Option<RelationNode>, B::Error> {
+        self.inner.read().await.latest_relation_by_fact(fact_hash)
+    }
+
+    async fn get_context(&self, hash: &ContextHash) -> Result<Option<ContextNode>, B::Error> {
+        self.inner.read().await.get_context(hash)
+    }
+
+    async fn recall(&self, label: &str) -> Result<Option<Recollection>, B::Error> {
+        self.inner.read().await.recall(label)
+    }
+
+    async fn concept_count(&self) -> Result<usize, B::Error> {
+        self.inner.read().await.concept_count()
+    }
+
+    async fn relation_count(&self) -> Result<usize, B::Error> {
+        self.inner.read().await.relation_count()
+    }
+}
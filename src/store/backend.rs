@@ -0,0 +1,115 @@
+use std::convert::Infallible;
+use std::fmt;
+
+use super::graph::MemoryStore;
+use crate::types::*;
+
+/// Read/write surface shared by every store implementation.
+///
+/// Downstream code written against this trait can switch between the
+/// in-memory [`MemoryStore`] and persistent backends (e.g. `SqliteStore`
+/// behind the `sqlite` feature) without touching call sites. Results are
+/// owned so that backends which materialize rows on demand can implement it.
+///
+/// All backends share the same dedup/merge semantics: concepts dedup by
+/// hash (max confidence, alias union, refreshed `updated_at`), relations
+/// dedup by episode hash, contexts are inserted once.
+pub trait MemoryBackend {
+    type Error: fmt::Display + fmt::Debug;
+
+    /// Insert a full memory record. See [`MemoryStore::insert_record`].
+    fn insert_record(&mut self, record: MemoryRecord) -> Result<InsertResult, Self::Error>;
+
+    fn get_concept(&self, hash: &ConceptHash) -> Result<Option<ConceptNode>, Self::Error>;
+
+    /// Look up a concept by label or one of its aliases. The label is
+    /// normalized before lookup.
+    fn get_concept_by_label(&self, label: &str) -> Result<Option<ConceptNode>, Self::Error>;
+
+    /// All relation episodes that reference the concept as subject or
+    /// object, each returned once.
+    fn get_relations(&self, concept_hash: &ConceptHash) -> Result<Vec<RelationNode>, Self::Error>;
+
+    /// All episodes of a fact, ordered chronologically by context
+    /// `event_time` (ties in insertion order).
+    fn get_relations_by_fact(&self, fact_hash: &FactHash)
+        -> Result<Vec<RelationNode>, Self::Error>;
+
+    /// The most recent episode of a fact, i.e. the last element of
+    /// [`get_relations_by_fact`](Self::get_relations_by_fact).
+    fn latest_relation_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> Result<Option<RelationNode>, Self::Error> {
+        Ok(self.get_relations_by_fact(fact_hash)?.pop())
+    }
+
+    fn get_context(&self, hash: &ContextHash) -> Result<Option<ContextNode>, Self::Error>;
+
+    fn concept_count(&self) -> Result<usize, Self::Error>;
+
+    /// Number of stored relation episodes.
+    fn relation_count(&self) -> Result<usize, Self::Error>;
+
+    /// Look up a concept by label together with its relations.
+    fn recall(&self, label: &str) -> Result<Option<Recollection>, Self::Error> {
+        let Some(concept) = self.get_concept_by_label(label)? else {
+            return Ok(None);
+        };
+        let relations = self.get_relations(&concept.hash)?;
+        Ok(Some(Recollection { concept, relations }))
+    }
+}
+
+impl MemoryBackend for MemoryStore {
+    type Error = Infallible;
+
+    fn insert_record(&mut self, record: MemoryRecord) -> Result<InsertResult, Infallible> {
+        Ok(MemoryStore::insert_record(self, record))
+    }
+
+    fn get_concept(&self, hash: &ConceptHash) -> Result<Option<ConceptNode>, Infallible> {
+        Ok(MemoryStore::get_concept(self, hash).cloned())
+    }
+
+    fn get_concept_by_label(&self, label: &str) -> Result<Option<ConceptNode>, Infallible> {
+        Ok(MemoryStore::get_concept_by_label(self, label).cloned())
+    }
+
+    fn get_relations(&self, concept_hash: &ConceptHash) -> Result<Vec<RelationNode>, Infallible> {
+        Ok(MemoryStore::get_relations(self, concept_hash)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    fn get_relations_by_fact(&self, fact_hash: &FactHash) -> Result<Vec<RelationNode>, Infallible> {
+        Ok(MemoryStore::get_relations_by_fact(self, fact_hash)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    fn latest_relation_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> Result<Option<RelationNode>, Infallible> {
+        Ok(MemoryStore::latest_relation_by_fact(self, fact_hash).cloned())
+    }
+
+    fn get_context(&self, hash: &ContextHash) -> Result<Option<ContextNode>, Infallible> {
+        Ok(MemoryStore::get_context(self, hash).cloned())
+    }
+
+    fn concept_count(&self) -> Result<usize, Infallible> {
+        Ok(MemoryStore::concept_count(self))
+    }
+
+    fn relation_count(&self) -> Result<usize, Infallible> {
+        Ok(MemoryStore::relation_count(self))
+    }
+
+    fn recall(&self, label: &str) -> Result<Option<Recollection>, Infallible> {
+        Ok(MemoryStore::recall(self, label))
+    }
+}
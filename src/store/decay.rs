@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use super::graph::MemoryStore;
+use crate::time::{require_timestamp, InvalidTimestamp};
+use crate::types::*;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Outcome of a decay pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecayReport {
+    /// Items whose confidence actually changed.
+    pub decayed: usize,
+    /// Items skipped because their reference timestamp is unknown: the
+    /// episode's context is missing, or the timestamp is
+    /// [`EventTime::EPOCH`], where [`EventTime::from_legacy`] puts
+    /// timestamps it could not read.
+    pub unparseable: usize,
+}
+
+/// Why a decay pass was refused. The store is left unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecayError {
+    /// `now` is not an RFC 3339 timestamp.
+    Timestamp(InvalidTimestamp),
+    /// The half-life is not a finite number of days above zero.
+    HalfLife(f64),
+    /// The floor is not in `[0, 1]`.
+    Floor(f64),
+}
+
+impl fmt::Display for DecayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayError::Timestamp(e) => e.fmt(f),
+            DecayError::HalfLife(days) => write!(
+                f,
+                "invalid half-life {} (expected a finite number of days above 0)",
+                days
+            ),
+            DecayError::Floor(floor) => write!(f, "invalid floor {} (expected [0, 1])", floor),
+        }
+    }
+}
+
+impl std::error::Error for DecayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecayError::Timestamp(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<InvalidTimestamp> for DecayError {
+    fn from(e: InvalidTimestamp) -> Self {
+        DecayError::Timestamp(e)
+    }
+}
+
+/// How far decay has already been applied, per item.
+///
+/// Each pass only applies the decay for time elapsed since the later of
+/// an item's own timestamp and the last pass that reached the item, so
+/// calling decay nightly yields the same confidence as one pass over the
+/// whole period (up to floor clamping), however old the items inserted
+/// between passes are. Items no pass has reached have no entry. Persisted
+/// with store snapshots.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DecayState {
+    pub(crate) episodes: HashMap<EpisodeHash, DateTime<Utc>>,
+    pub(crate) concepts: HashMap<ConceptHash, DateTime<Utc>>,
+}
+
+/// Read `now` and check the decay parameters before a pass.
+fn check(now: &str, half_life_days: f64, floor: f64) -> Result<DateTime<Utc>, DecayError> {
+    let now = require_timestamp(now)?;
+    if !(half_life_days.is_finite() && half_life_days > 0.0) {
+        return Err(DecayError::HalfLife(half_life_days));
+    }
+    if !(0.0..=1.0).contains(&floor) {
+        return Err(DecayError::Floor(floor));
+    }
+    Ok(now)
+}
+
+/// Apply exponential decay to `confidence` for the time between `since`
+/// and `now`. Never raises a value already below `floor`.
+fn decayed(
+    confidence: f64,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+    half_life_days: f64,
+    floor: f64,
+) -> f64 {
+    let elapsed_days = (now - since).num_milliseconds() as f64 / 1000.0 / SECONDS_PER_DAY;
+    if elapsed_days <= 0.0 {
+        return confidence;
+    }
+    let factor = 0.5_f64.powf(elapsed_days / half_life_days);
+    (confidence * factor).max(floor.min(confidence))
+}
+
+/// Reference point for one item: its own timestamp, or the last pass
+/// that reached it if that is later.
+fn reference(timestamp: DateTime<Utc>, through: Option<&DateTime<Utc>>) -> DateTime<Utc> {
+    through.map_or(timestamp, |t| (*t).max(timestamp))
+}
+
+/// How far decay has been applied to an item once a pass at `now` has
+/// reached it.
+fn advance(through: Option<&DateTime<Utc>>, now: DateTime<Utc>) -> DateTime<Utc> {
+    through.map_or(now, |t| (*t).max(now))
+}
+
+impl MemoryStore {
+    /// Fade episode confidence over time.
+    ///
+    /// Each episode decays by `0.5^(elapsed_days / half_life_days)`, where
+    /// elapsed time runs from its context's `event_time` (or the last decay
+    /// pass that reached the episode, whichever is later) to `now`, and is
+    /// clamped at `floor`.
+    /// Episodes whose context is missing or whose `event_time` is unknown
+    /// are skipped and counted in [`DecayReport::unparseable`].
+    ///
+    /// Intended to be called periodically, e.g. from a nightly maintenance
+    /// loop: repeated passes compose instead of compounding.
+    ///
+    /// Fails without touching the store if `now` is not a timestamp,
+    /// `half_life_days` is not a finite number above zero, or `floor` is
+    /// outside `[0, 1]`.
+    pub fn decay_confidence(
+        &mut self,
+        now: &str,
+        half_life_days: f64,
+        floor: f64,
+    ) -> Result<DecayReport, DecayError> {
+        let now = check(now, half_life_days, floor)?;
+        let mut report = DecayReport::default();
+        let mut reached = HashMap::new();
+
+        let updates: Vec<(EpisodeHash, f64)> = self
+            .stored_relations()
+            .filter_map(|relation| {
+                let event_time = self
+                    .get_context(&relation.context_hash)
+                    .map(|ctx| ctx.meta.event_time)
+                    .filter(|t| *t != EventTime::EPOCH);
+                let Some(event_time) = event_time else {
+                    report.unparseable += 1;
+                    return None;
+                };
+                let through = self.decay.episodes.get(&relation.episode_hash);
+                let since = reference(event_time.as_datetime(), through);
+                reached.insert(relation.episode_hash.clone(), advance(through, now));
+                let confidence = decayed(relation.confidence, since, now, half_life_days, floor);
+                (confidence != relation.confidence)
+                    .then(|| (relation.episode_hash.clone(), confidence))
+            })
+            .collect();
+
+        report.decayed = updates.len();
+        for (episode, confidence) in updates {
+            self.set_episode_confidence(&episode, confidence);
+        }
+        self.decay.episodes = reached;
+        Ok(report)
+    }
+
+    /// Fade concept confidence over time, measured from each concept's
+    /// `updated_at` (the last time it was observed). Same formula, floor,
+    /// and pass-composition rules as
+    /// [`decay_confidence`](Self::decay_confidence), which also lists the
+    /// errors.
+    pub fn decay_concept_confidence(
+        &mut self,
+        now: &str,
+        half_life_days: f64,
+        floor: f64,
+    ) -> Result<DecayReport, DecayError> {
+        let now = check(now, half_life_days, floor)?;
+        let mut passed = std::mem::take(&mut self.decay.concepts);
+        let mut reached = HashMap::new();
+        let mut report = DecayReport::default();
+
+        for concept in self.concepts_mut() {
+            if concept.updated_at == EventTime::EPOCH {
+                report.unparseable += 1;
+                continue;
+            }
+            let through = passed.remove(&concept.hash);
+            let since = reference(concept.updated_at.as_datetime(), through.as_ref());
+            reached.insert(concept.hash.clone(), advance(through.as_ref(), now));
+            let confidence = decayed(concept.confidence, since, now, half_life_days, floor);
+            if confidence != concept.confidence {
+                concept.confidence = confidence;
+                report.decayed += 1;
+            }
+        }
+
+        self.decay.concepts = reached;
+        Ok(report)
+    }
+}
@@ -0,0 +1,892 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use super::graph::MemoryStore;
+use crate::compiler::emitter::short_ids;
+use crate::compiler::hasher;
+use crate::time::{require_timestamp, InvalidTimestamp};
+use crate::types::*;
+
+/// Whether [`MemoryStore::export_since`] also carries concepts that were
+/// updated after the cutoff (confidence bumped, alias added) but are not
+/// referenced by any episode newer than the cutoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdatedConcepts {
+    /// Only concepts referenced by exported episodes are included.
+    Exclude,
+    /// Concepts whose `updated_at` is after the cutoff are included too,
+    /// attached to the first exported record.
+    Include,
+}
+
+/// Whether [`to_ntriples_with`] describes each episode as a reified
+/// `rdf:Statement`, carrying its confidence and context, as well as each
+/// fact as a plain triple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReifiedStatements {
+    /// Only concept labels and facts are written.
+    Exclude,
+    /// Every episode is written as a statement too.
+    Include,
+}
+
+/// Context used for the extra record that carries orphan concepts when the
+/// store holds no context to attach them to.
+fn orphan_context() -> ContextNode {
+    let meta = ContextMeta {
+        event_time: EventTime::EPOCH,
+        source: "0-memory-export".to_string(),
+        scope: "orphan_concepts".to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    }
+}
+
+/// Attach concepts that no exported episode references to the first record,
+/// creating a record with the synthetic orphan context if there is none.
+fn attach_loose_concepts(records: &mut Vec<MemoryRecord>, mut concepts: Vec<ConceptNode>) {
+    if concepts.is_empty() {
+        return;
+    }
+    if records.is_empty() {
+        records.push(MemoryRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
+            concepts: Vec::new(),
+            relations: Vec::new(),
+            context: orphan_context(),
+            proof: None,
+            prev: None,
+            namespace: None,
+        });
+    }
+    let first = &mut records[0];
+    first.concepts.append(&mut concepts);
+    first.concepts.sort_by(|a, b| a.label.cmp(&b.label));
+}
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+/// Append `text` to `out` as an N-Triples string literal.
+fn push_literal(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Append `name` to `out` as one IRI path segment, percent-encoding what
+/// an IRI cannot hold and what would end the segment.
+fn push_segment(out: &mut String, name: &str) {
+    for c in name.chars() {
+        if c <= ' ' || "<>\"{}|^`\\/#?%".contains(c) {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+/// N-Triples for `concepts` and `relations`; see [`to_ntriples_with`].
+fn write_ntriples(
+    mut concepts: Vec<&ConceptNode>,
+    mut relations: Vec<&RelationNode>,
+    base_iri: &str,
+    statements: ReifiedStatements,
+) -> String {
+    let base = base_iri.trim_end_matches('/');
+    let concept = |hash: &ConceptHash| format!("<{}/concept/{}>", base, hash);
+    let predicate = |name: &str| {
+        let mut iri = format!("<{}/pred/", base);
+        push_segment(&mut iri, name);
+        iri.push('>');
+        iri
+    };
+
+    concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+    relations.sort_by(|a, b| (&a.fact_hash, &a.episode_hash).cmp(&(&b.fact_hash, &b.episode_hash)));
+
+    let mut out = String::new();
+    for node in &concepts {
+        out.push_str(&format!("{} <{}> ", concept(&node.hash), RDFS_LABEL));
+        push_literal(&mut out, &node.label);
+        out.push_str(" .\n");
+    }
+
+    let mut facts = HashSet::new();
+    for relation in &relations {
+        if relation.polarity == Polarity::Positive && facts.insert(&relation.fact_hash) {
+            out.push_str(&format!(
+                "{} {} {} .\n",
+                concept(&relation.subject_hash),
+                predicate(&relation.predicate),
+                concept(&relation.object_hash)
+            ));
+        }
+    }
+
+    if statements == ReifiedStatements::Include {
+        for relation in &relations {
+            let episode = format!("<{}/episode/{}>", base, relation.episode_hash);
+            let mut triple = |predicate: &str, object: &str| {
+                out.push_str(&format!("{} {} {} .\n", episode, predicate, object));
+            };
+            let rdf = |name: &str| format!("<{}{}>", RDF, name);
+            triple(&rdf("type"), &rdf("Statement"));
+            triple(&rdf("subject"), &concept(&relation.subject_hash));
+            triple(&rdf("predicate"), &predicate(&relation.predicate));
+            triple(&rdf("object"), &concept(&relation.object_hash));
+            triple(
+                &format!("<{}/vocab/confidence>", base),
+                &format!("\"{}\"^^<{}>", relation.confidence, XSD_DOUBLE),
+            );
+            triple(
+                &format!("<{}/vocab/context>", base),
+                &format!("<{}/context/{}>", base, relation.context_hash),
+            );
+            if relation.polarity == Polarity::Negative {
+                triple(&format!("<{}/vocab/polarity>", base), "\"negative\"");
+            }
+        }
+    }
+    out
+}
+
+/// Export `record` as RDF N-Triples, which are also valid Turtle; see
+/// [`to_ntriples_with`].
+pub fn to_ntriples(record: &MemoryRecord, base_iri: &str) -> String {
+    to_ntriples_with(record, base_iri, ReifiedStatements::Exclude)
+}
+
+/// Export `record` as RDF N-Triples, which are also valid Turtle.
+///
+/// Concepts become `<{base_iri}/concept/{hash}>`, each with its label as
+/// an `rdfs:label` literal, and predicates `<{base_iri}/pred/{name}>`,
+/// percent-encoding what an IRI cannot hold. Each fact is one triple,
+/// however many episodes observed it; negative facts, which RDF cannot
+/// state, are left out. With [`ReifiedStatements::Include`], every
+/// episode is also an `rdf:Statement` at `<{base_iri}/episode/{hash}>`,
+/// carrying its confidence, its context as `<{base_iri}/context/{hash}>`,
+/// and, for negative facts, a `"negative"` polarity.
+///
+/// Triples are sorted by hash, so the output is deterministic.
+pub fn to_ntriples_with(
+    record: &MemoryRecord,
+    base_iri: &str,
+    statements: ReifiedStatements,
+) -> String {
+    write_ntriples(
+        record.concepts.iter().collect(),
+        record.relations.iter().collect(),
+        base_iri,
+        statements,
+    )
+}
+
+/// Append `text` to `out` escaped for XML text and attribute values.
+/// Control characters XML 1.0 cannot hold become U+FFFD.
+fn push_xml(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c < ' ' || c == '\u{fffe}' || c == '\u{ffff}' => out.push('\u{fffd}'),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Append a `<data>` element for key `key` to `out`.
+fn push_data(out: &mut String, indent: &str, key: &str, value: &str) {
+    out.push_str(&format!("{}<data key=\"{}\">", indent, key));
+    push_xml(out, value);
+    out.push_str("</data>\n");
+}
+
+/// GraphML for `concepts` and `relations`; see [`to_graphml`].
+fn write_graphml(mut concepts: Vec<&ConceptNode>, relations: Vec<&RelationNode>) -> String {
+    struct Edge<'a> {
+        relation: &'a RelationNode,
+        episodes: usize,
+        max_confidence: f64,
+    }
+
+    let mut edges: HashMap<&FactHash, Edge> = HashMap::new();
+    for relation in relations {
+        let edge = edges.entry(&relation.fact_hash).or_insert(Edge {
+            relation,
+            episodes: 0,
+            max_confidence: relation.confidence,
+        });
+        edge.episodes += 1;
+        edge.max_confidence = edge.max_confidence.max(relation.confidence);
+    }
+    let mut edges: Vec<Edge> = edges.into_values().collect();
+    edges.sort_by(|a, b| a.relation.fact_hash.cmp(&b.relation.fact_hash));
+    concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+         <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n  \
+         <key id=\"confidence\" for=\"node\" attr.name=\"confidence\" attr.type=\"double\"/>\n  \
+         <key id=\"created_at\" for=\"node\" attr.name=\"created_at\" attr.type=\"string\"/>\n  \
+         <key id=\"predicate\" for=\"edge\" attr.name=\"predicate\" attr.type=\"string\"/>\n  \
+         <key id=\"episode_count\" for=\"edge\" attr.name=\"episode_count\" attr.type=\"int\"/>\n  \
+         <key id=\"max_confidence\" for=\"edge\" attr.name=\"max_confidence\" attr.type=\"double\"/>\n  \
+         <key id=\"polarity\" for=\"edge\" attr.name=\"polarity\" attr.type=\"string\">\
+         <default>positive</default></key>\n  \
+         <graph id=\"memory\" edgedefault=\"directed\">\n",
+    );
+
+    let mut written = HashSet::new();
+    for concept in &concepts {
+        written.insert(&concept.hash);
+        out.push_str(&format!("    <node id=\"c{}\">\n", concept.hash));
+        push_data(&mut out, "      ", "label", &concept.label);
+        push_data(
+            &mut out,
+            "      ",
+            "confidence",
+            &concept.confidence.to_string(),
+        );
+        push_data(
+            &mut out,
+            "      ",
+            "created_at",
+            &concept.created_at.to_string(),
+        );
+        out.push_str("    </node>\n");
+    }
+    // Endpoints whose concept is missing still need a node for the edge
+    // to refer to.
+    for edge in &edges {
+        for hash in [&edge.relation.subject_hash, &edge.relation.object_hash] {
+            if written.insert(hash) {
+                out.push_str(&format!("    <node id=\"c{}\"/>\n", hash));
+            }
+        }
+    }
+
+    for edge in &edges {
+        let relation = edge.relation;
+        out.push_str(&format!(
+            "    <edge id=\"f{}\" source=\"c{}\" target=\"c{}\">\n",
+            relation.fact_hash, relation.subject_hash, relation.object_hash
+        ));
+        push_data(&mut out, "      ", "predicate", &relation.predicate);
+        push_data(
+            &mut out,
+            "      ",
+            "episode_count",
+            &edge.episodes.to_string(),
+        );
+        push_data(
+            &mut out,
+            "      ",
+            "max_confidence",
+            &edge.max_confidence.to_string(),
+        );
+        if relation.polarity == Polarity::Negative {
+            push_data(&mut out, "      ", "polarity", "negative");
+        }
+        out.push_str("    </edge>\n");
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Export the whole store as GraphML, for Gephi, yEd, and other graph
+/// viewers.
+///
+/// Each concept is a `<node>` with its label, confidence, and
+/// `created_at`. Each fact is one directed `<edge>` from subject to
+/// object, however many episodes observed it, with its predicate, the
+/// number of episodes, and the highest confidence among them; negative
+/// facts carry a `polarity` of `negative`. Labels are XML-escaped, so any
+/// label makes well-formed XML. Nodes and edges are sorted by hash.
+pub fn to_graphml(store: &MemoryStore) -> String {
+    write_graphml(store.concepts().collect(), store.relations().collect())
+}
+
+/// Export `record` as GraphML, like [`to_graphml`].
+pub fn record_to_graphml(record: &MemoryRecord) -> String {
+    write_graphml(
+        record.concepts.iter().collect(),
+        record.relations.iter().collect(),
+    )
+}
+
+/// Hex characters of a concept hash used in DOT and Mermaid node IDs,
+/// when they suffice to tell it apart from the other concepts drawn.
+const DIAGRAM_ID_LEN: usize = 8;
+
+/// Options for [`to_dot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DotOptions {
+    /// Draw only the concepts within `depth` facts of the concept with
+    /// this label or alias, rather than the whole store.
+    pub root: Option<String>,
+    /// How many facts away from `root` to go; 0 draws the root alone.
+    /// Ignored without a root.
+    pub depth: usize,
+}
+
+/// Append `text` to `out` as a quoted DOT string.
+fn push_dot_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Concepts within `depth` facts of `root`, following facts either way:
+/// nearest first, and by label at the same distance.
+fn neighbourhood<'a>(
+    store: &'a MemoryStore,
+    root: &ConceptHash,
+    depth: usize,
+) -> Vec<&'a ConceptNode> {
+    let Some(root) = store.get_concept(root) else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::from([root.hash.clone()]);
+    let mut found = vec![root];
+    let mut frontier = 0..1;
+    for _ in 0..depth {
+        let start = found.len();
+        for index in frontier {
+            for relation in store.get_relations(&found[index].hash) {
+                for end in [&relation.subject_hash, &relation.object_hash] {
+                    if seen.insert(end.clone()) {
+                        found.extend(store.get_concept(end));
+                    }
+                }
+            }
+        }
+        found[start..].sort_by(|a, b| a.label.cmp(&b.label).then_with(|| a.hash.cmp(&b.hash)));
+        frontier = start..found.len();
+    }
+    found
+}
+
+/// Export the store as a Graphviz DOT digraph, for a quick look at it.
+///
+/// Each concept is a node labelled with its display label, or its label
+/// if it has none, and identified by the shortest prefix of its hash
+/// that tells it apart from the other nodes. Each fact is one edge
+/// labelled with its predicate, thicker the higher the confidence of its
+/// most confident episode; negative facts are dashed. With a root in
+/// `options`, only the concepts near it and the facts between them are
+/// drawn; a root the store does not know draws an empty graph.
+///
+/// Nodes are sorted by label and edges by their endpoints and predicate,
+/// so the output is deterministic.
+pub fn to_dot(store: &MemoryStore, options: DotOptions) -> String {
+    let mut concepts: Vec<&ConceptNode> = match &options.root {
+        None => store.concepts().collect(),
+        Some(label) => match store.get_concept_by_label(label) {
+            Some(root) => neighbourhood(store, &root.hash, options.depth),
+            None => Vec::new(),
+        },
+    };
+    concepts.sort_by(|a, b| a.label.cmp(&b.label).then_with(|| a.hash.cmp(&b.hash)));
+
+    let hashes: Vec<&[u8; 32]> = concepts.iter().map(|c| c.hash.as_bytes()).collect();
+    let ids: HashMap<&ConceptHash, String> = concepts
+        .iter()
+        .map(|c| &c.hash)
+        .zip(short_ids(&hashes, DIAGRAM_ID_LEN))
+        .collect();
+
+    let mut edges: HashMap<&FactHash, (&RelationNode, f64)> = HashMap::new();
+    let drawn = store
+        .relations()
+        .filter(|r| ids.contains_key(&r.subject_hash) && ids.contains_key(&r.object_hash));
+    for relation in drawn {
+        let edge = edges
+            .entry(&relation.fact_hash)
+            .or_insert((relation, relation.confidence));
+        edge.1 = edge.1.max(relation.confidence);
+    }
+    let mut edges: Vec<(&RelationNode, f64)> = edges.into_values().collect();
+    edges.sort_by(|(a, _), (b, _)| {
+        (&ids[&a.subject_hash], &ids[&a.object_hash], &a.predicate).cmp(&(
+            &ids[&b.subject_hash],
+            &ids[&b.object_hash],
+            &b.predicate,
+        ))
+    });
+
+    let mut out = String::from("digraph memory {\n  node [shape=box];\n");
+    for concept in &concepts {
+        out.push_str(&format!("  \"{}\" [label=", ids[&concept.hash]));
+        push_dot_string(
+            &mut out,
+            concept.display_label.as_deref().unwrap_or(&concept.label),
+        );
+        out.push_str("];\n");
+    }
+    for (relation, confidence) in edges {
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=",
+            ids[&relation.subject_hash], ids[&relation.object_hash]
+        ));
+        push_dot_string(&mut out, &relation.predicate);
+        out.push_str(&format!(", penwidth={:.2}", 1.0 + 3.0 * confidence));
+        if relation.polarity == Polarity::Negative {
+            out.push_str(", style=dashed");
+        }
+        out.push_str("];\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Most concepts [`to_mermaid`] draws before it leaves the rest out,
+/// since Mermaid stops rendering large graphs.
+pub const MERMAID_MAX_NODES: usize = 50;
+
+/// Most characters of a label or predicate [`to_mermaid`] shows.
+pub const MERMAID_MAX_LABEL: usize = 40;
+
+/// `text` as a quoted Mermaid label, cut to [`MERMAID_MAX_LABEL`]
+/// characters, with the characters Mermaid gives a meaning written as
+/// `#code;` entities.
+fn mermaid_label(text: &str) -> String {
+    let mut out = String::from("\"");
+    let count = text.chars().count();
+    for (index, c) in text.chars().enumerate() {
+        if count > MERMAID_MAX_LABEL && index == MERMAID_MAX_LABEL - 1 {
+            out.push('…');
+            break;
+        }
+        match c {
+            '"' | '#' | '|' | '[' | ']' | '{' | '}' | '(' | ')' | '<' | '>' | '`' | ';' => {
+                out.push_str(&format!("#{};", c as u32))
+            }
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Export the store, or the concepts within `depth` facts of `root`, as a
+/// Mermaid `graph TD` diagram for Markdown documents.
+///
+/// Concepts are nodes labelled with their display label, or their label
+/// if they have none, and identified by `n` and the shortest prefix of
+/// their hash that tells them apart. Facts are edges labelled with their
+/// predicate, dotted for negative facts. Labels longer than
+/// [`MERMAID_MAX_LABEL`] characters are cut short with `…`. At most
+/// [`MERMAID_MAX_NODES`] concepts are drawn, the nearest to `root` first
+/// or, without a root, the first by label; a `…and N more` node counts
+/// the rest, whose facts are not drawn. A root the store does not know
+/// draws an empty graph.
+pub fn to_mermaid(store: &MemoryStore, root: Option<&ConceptHash>, depth: usize) -> String {
+    let mut concepts: Vec<&ConceptNode> = match root {
+        Some(root) => neighbourhood(store, root, depth),
+        None => {
+            let mut all: Vec<&ConceptNode> = store.concepts().collect();
+            all.sort_by(|a, b| a.label.cmp(&b.label).then_with(|| a.hash.cmp(&b.hash)));
+            all
+        }
+    };
+    let left_out = concepts.len().saturating_sub(MERMAID_MAX_NODES);
+    concepts.truncate(MERMAID_MAX_NODES);
+
+    let hashes: Vec<&[u8; 32]> = concepts.iter().map(|c| c.hash.as_bytes()).collect();
+    let ids: HashMap<&ConceptHash, (usize, String)> = concepts
+        .iter()
+        .map(|c| &c.hash)
+        .zip(short_ids(&hashes, DIAGRAM_ID_LEN).into_iter().enumerate())
+        .collect();
+
+    let mut facts = HashSet::new();
+    let mut edges: Vec<&RelationNode> = store
+        .relations()
+        .filter(|r| ids.contains_key(&r.subject_hash) && ids.contains_key(&r.object_hash))
+        .filter(|r| facts.insert(&r.fact_hash))
+        .collect();
+    edges.sort_by(|a, b| {
+        (ids[&a.subject_hash].0, ids[&a.object_hash].0, &a.predicate).cmp(&(
+            ids[&b.subject_hash].0,
+            ids[&b.object_hash].0,
+            &b.predicate,
+        ))
+    });
+
+    let mut out = String::from("graph TD\n");
+    for concept in &concepts {
+        out.push_str(&format!(
+            "  n{}[{}]\n",
+            ids[&concept.hash].1,
+            mermaid_label(concept.display_label.as_deref().unwrap_or(&concept.label))
+        ));
+    }
+    for relation in edges {
+        let arrow = match relation.polarity {
+            Polarity::Positive => "-->",
+            Polarity::Negative => "-.->",
+        };
+        out.push_str(&format!(
+            "  n{} {}|{}| n{}\n",
+            ids[&relation.subject_hash].1,
+            arrow,
+            mermaid_label(&relation.predicate),
+            ids[&relation.object_hash].1
+        ));
+    }
+    if left_out > 0 {
+        out.push_str(&format!("  more[\"…and {} more\"]\n", left_out));
+    }
+    out
+}
+
+/// `text` as a single-quoted Cypher string literal.
+fn cypher_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('\'');
+    for c in text.chars() {
+        match c {
+            '\'' => out.push_str("\\'"),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// `value` as a Cypher float literal, which keeps a decimal point even for
+/// whole numbers so Neo4j stores a float.
+fn cypher_float(value: f64) -> String {
+    format!("{:?}", value)
+}
+
+/// Export the whole store as a Neo4j Cypher script; see [`write_cypher`].
+pub fn to_cypher(store: &MemoryStore) -> String {
+    let mut text = Vec::new();
+    write_cypher(store, &mut text).expect("writing to a Vec does not fail");
+    String::from_utf8(text).expect("Cypher text is UTF-8")
+}
+
+/// Write the whole store to `writer` as a Neo4j Cypher script, one
+/// statement per line, without holding the script in memory.
+///
+/// Every statement is a `MERGE` keyed on a hash, so running the script
+/// twice, or scripts of two stores that share concepts, adds nothing
+/// twice, as inserting the same record into a store does not:
+///
+/// - `(:Concept {hash})` with its label, aliases, display label,
+///   confidence, and timestamps;
+/// - `(:Context {hash})` with its event time, source, scope, and agent
+///   and session IDs;
+/// - `[:FACT {fact_hash}]` from subject to object, with its predicate,
+///   polarity, number of episodes, and the highest confidence among them;
+/// - `(:Episode {hash})` with its fact hash, predicate, confidence, and
+///   `created_at`, linked to its context by `[:OBSERVED_IN]`.
+///
+/// Statements are sorted by hash. Strings are written as escaped Cypher
+/// literals, so labels may hold quotes, backslashes, and any Unicode.
+/// Statements are written with many small writes, so wrap unbuffered
+/// writers such as a [`File`](std::fs::File) in a
+/// [`BufWriter`](std::io::BufWriter).
+pub fn write_cypher<W: Write>(store: &MemoryStore, mut writer: W) -> io::Result<()> {
+    let mut concepts: Vec<&ConceptNode> = store.concepts().collect();
+    concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+    for concept in concepts {
+        let aliases: Vec<String> = concept.aliases.iter().map(|a| cypher_string(a)).collect();
+        write!(
+            writer,
+            "MERGE (c:Concept {{hash: '{}'}}) SET c.label = {}, c.aliases = [{}], \
+             c.confidence = {}, c.created_at = {}, c.updated_at = {}",
+            concept.hash,
+            cypher_string(&concept.label),
+            aliases.join(", "),
+            cypher_float(concept.confidence),
+            cypher_string(&concept.created_at.to_string()),
+            cypher_string(&concept.updated_at.to_string()),
+        )?;
+        if let Some(display) = &concept.display_label {
+            write!(writer, ", c.display_label = {}", cypher_string(display))?;
+        }
+        writeln!(writer, ";")?;
+    }
+
+    let mut contexts: Vec<&ContextNode> = store.contexts().collect();
+    contexts.sort_by(|a, b| a.hash.cmp(&b.hash));
+    for context in contexts {
+        let meta = &context.meta;
+        write!(
+            writer,
+            "MERGE (x:Context {{hash: '{}'}}) SET x.event_time = {}, x.source = {}, x.scope = {}",
+            context.hash,
+            cypher_string(&meta.event_time.to_string()),
+            cypher_string(&meta.source),
+            cypher_string(&meta.scope),
+        )?;
+        if let Some(agent) = &meta.agent_id {
+            write!(writer, ", x.agent_id = {}", cypher_string(agent))?;
+        }
+        if let Some(session) = &meta.session_id {
+            write!(writer, ", x.session_id = {}", cypher_string(session))?;
+        }
+        writeln!(writer, ";")?;
+    }
+
+    let mut relations: Vec<&RelationNode> = store.relations().collect();
+    relations.sort_by(|a, b| (&a.fact_hash, &a.episode_hash).cmp(&(&b.fact_hash, &b.episode_hash)));
+    let mut start = 0;
+    while start < relations.len() {
+        let fact = relations[start];
+        let end = start
+            + relations[start..]
+                .iter()
+                .take_while(|r| r.fact_hash == fact.fact_hash)
+                .count();
+        let episodes = &relations[start..end];
+        start = end;
+        let confidence = episodes
+            .iter()
+            .map(|r| r.confidence)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let polarity = match fact.polarity {
+            Polarity::Positive => "positive",
+            Polarity::Negative => "negative",
+        };
+        writeln!(
+            writer,
+            "MATCH (s:Concept {{hash: '{}'}}), (o:Concept {{hash: '{}'}}) \
+             MERGE (s)-[f:FACT {{fact_hash: '{}'}}]->(o) SET f.predicate = {}, \
+             f.polarity = '{}', f.episode_count = {}, f.confidence = {};",
+            fact.subject_hash,
+            fact.object_hash,
+            fact.fact_hash,
+            cypher_string(&fact.predicate),
+            polarity,
+            episodes.len(),
+            cypher_float(confidence),
+        )?;
+    }
+
+    for relation in relations {
+        writeln!(
+            writer,
+            "MATCH (x:Context {{hash: '{}'}}) MERGE (e:Episode {{hash: '{}'}}) \
+             SET e.fact_hash = '{}', e.predicate = {}, e.confidence = {}, e.created_at = {} \
+             MERGE (e)-[:OBSERVED_IN]->(x);",
+            relation.context_hash,
+            relation.episode_hash,
+            relation.fact_hash,
+            cypher_string(&relation.predicate),
+            cypher_float(relation.confidence),
+            cypher_string(&relation.created_at.to_string()),
+        )?;
+    }
+    writer.flush()
+}
+
+impl MemoryStore {
+    /// Export the entire store as RDF N-Triples, like [`to_ntriples`] for
+    /// one record holding every concept and episode.
+    pub fn export_ntriples(&self, base_iri: &str) -> String {
+        self.export_ntriples_with(base_iri, ReifiedStatements::Exclude)
+    }
+
+    /// Export the entire store as RDF N-Triples, like [`to_ntriples_with`].
+    pub fn export_ntriples_with(&self, base_iri: &str, statements: ReifiedStatements) -> String {
+        write_ntriples(
+            self.concepts().collect(),
+            self.relations().collect(),
+            base_iri,
+            statements,
+        )
+    }
+
+    /// Export the entire store as a list of `MemoryRecord`s, one per stored
+    /// context.
+    ///
+    /// Each record carries the episodes observed in that context plus clones
+    /// of every concept those episodes reference, with their current
+    /// confidence and aliases. Records are ordered by context `event_time`
+    /// (ties broken by context hash); concepts within a record are sorted
+    /// by label and relations by episode hash, so the output is
+    /// deterministic.
+    ///
+    /// Concepts referenced by no episode are attached to the first record,
+    /// or to an extra record with a synthetic `0-memory-export` context if
+    /// the store holds no contexts. Feeding the result into a fresh store
+    /// reproduces the same concept, fact, and episode counts.
+    ///
+    /// Episodes of [forgotten](Self::forget_fact) facts are not exported;
+    /// use a snapshot to carry tombstones along.
+    pub fn export_records(&self) -> Vec<MemoryRecord> {
+        let mut by_context: HashMap<&ContextHash, Vec<&RelationNode>> = HashMap::new();
+        for relation in self.relations() {
+            by_context
+                .entry(&relation.context_hash)
+                .or_default()
+                .push(relation);
+        }
+
+        let mut contexts: Vec<&ContextNode> = self.contexts().collect();
+        contexts.sort_by(|a, b| {
+            a.meta
+                .event_time
+                .cmp(&b.meta.event_time)
+                .then_with(|| a.hash.cmp(&b.hash))
+        });
+
+        let mut referenced = HashSet::new();
+        let mut records: Vec<MemoryRecord> = contexts
+            .into_iter()
+            .map(|context| {
+                let relations = by_context.remove(&context.hash).unwrap_or_default();
+                self.build_record(context.clone(), relations, &mut referenced)
+            })
+            .collect();
+
+        let orphans: Vec<ConceptNode> = self
+            .concepts()
+            .filter(|c| !referenced.contains(&c.hash))
+            .cloned()
+            .collect();
+        attach_loose_concepts(&mut records, orphans);
+
+        records
+    }
+
+    /// Export only the episodes recorded strictly after `cutoff`, grouped
+    /// by context exactly like [`export_records`](Self::export_records),
+    /// each record carrying the concepts its episodes reference.
+    ///
+    /// An episode's age is its own `created_at`, not its context's
+    /// `event_time`. `cutoff` is read like any [`EventTime`], so offsets
+    /// are honored. Episodes created at [`EventTime::EPOCH`], where
+    /// [`EventTime::from_legacy`] puts timestamps it could not read, are
+    /// always exported: an incremental backup must not drop what it cannot
+    /// prove is old.
+    ///
+    /// `updated` decides whether concepts updated after the cutoff but
+    /// referenced only by older episodes are exported as well.
+    pub fn export_since(
+        &self,
+        cutoff: &str,
+        updated: UpdatedConcepts,
+    ) -> Result<Vec<MemoryRecord>, InvalidTimestamp> {
+        let cutoff = require_timestamp(cutoff)?;
+        let is_after = |t: &EventTime| *t == EventTime::EPOCH || t.as_datetime() > cutoff;
+
+        let mut by_context: HashMap<&ContextHash, Vec<&RelationNode>> = HashMap::new();
+        for relation in self.relations().filter(|r| is_after(&r.created_at)) {
+            by_context
+                .entry(&relation.context_hash)
+                .or_default()
+                .push(relation);
+        }
+
+        let mut contexts: Vec<&ContextNode> = by_context
+            .keys()
+            .filter_map(|hash| self.get_context(hash))
+            .collect();
+        contexts.sort_by(|a, b| {
+            a.meta
+                .event_time
+                .cmp(&b.meta.event_time)
+                .then_with(|| a.hash.cmp(&b.hash))
+        });
+
+        let mut referenced = HashSet::new();
+        let mut records: Vec<MemoryRecord> = contexts
+            .into_iter()
+            .map(|context| {
+                let relations = by_context.remove(&context.hash).unwrap_or_default();
+                self.build_record(context.clone(), relations, &mut referenced)
+            })
+            .collect();
+
+        if updated == UpdatedConcepts::Include {
+            let touched: Vec<ConceptNode> = self
+                .concepts()
+                .filter(|c| !referenced.contains(&c.hash) && is_after(&c.updated_at))
+                .cloned()
+                .collect();
+            attach_loose_concepts(&mut records, touched);
+        }
+
+        Ok(records)
+    }
+
+    /// Assemble one record from a context and its episodes, cloning every
+    /// concept the episodes reference. Referenced hashes are added to
+    /// `referenced` so the caller can find orphans afterwards.
+    pub(crate) fn build_record(
+        &self,
+        context: ContextNode,
+        mut relations: Vec<&RelationNode>,
+        referenced: &mut HashSet<ConceptHash>,
+    ) -> MemoryRecord {
+        relations.sort_by(|a, b| a.episode_hash.cmp(&b.episode_hash));
+
+        let mut seen = HashSet::new();
+        let mut concepts = Vec::new();
+        for relation in &relations {
+            for hash in [&relation.subject_hash, &relation.object_hash] {
+                if !seen.insert(hash.clone()) {
+                    continue;
+                }
+                if let Some(concept) = self.get_concept(hash) {
+                    concepts.push(concept.clone());
+                }
+            }
+        }
+        concepts.sort_by(|a, b| a.label.cmp(&b.label));
+        referenced.extend(seen);
+
+        MemoryRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
+            concepts,
+            relations: relations.into_iter().cloned().collect(),
+            context,
+            proof: None,
+            prev: None,
+            namespace: None,
+        }
+    }
+}
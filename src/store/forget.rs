@@ -0,0 +1,78 @@
+use super::graph::MemoryStore;
+use crate::time::{require_timestamp, InvalidTimestamp};
+use crate::types::*;
+
+impl MemoryStore {
+    /// Forget a fact without deleting it.
+    ///
+    /// A [`Tombstone`] recording `reason` and `at` is kept for the fact and
+    /// its episodes stay in the store, but every read API
+    /// ([`get_relations`](Self::get_relations),
+    /// [`get_relations_by_fact`](Self::get_relations_by_fact),
+    /// [`relations`](Self::relations), exports) skips them. Audits can
+    /// still reach them through [`get_forgotten`](Self::get_forgotten) and
+    /// [`get_forgotten_relations`](Self::get_forgotten_relations).
+    ///
+    /// `on_reobserve` decides what a later insert of the same fact does.
+    /// Forgetting an already forgotten fact replaces its tombstone, and a
+    /// fact the store has never seen can be forgotten pre-emptively.
+    /// Returns whether the store currently holds episodes of the fact.
+    pub fn forget_fact(
+        &mut self,
+        fact_hash: &FactHash,
+        reason: &str,
+        at: &str,
+        on_reobserve: Reobservation,
+    ) -> Result<bool, InvalidTimestamp> {
+        require_timestamp(at)?;
+        self.tombstones.insert(
+            fact_hash.clone(),
+            Tombstone {
+                fact_hash: fact_hash.clone(),
+                reason: reason.to_string(),
+                forgotten_at: at.to_string(),
+                on_reobserve,
+            },
+        );
+        Ok(!self.stored_relations_by_fact(fact_hash).is_empty())
+    }
+
+    /// Lift a fact's tombstone, making its episodes visible again.
+    pub fn unforget_fact(&mut self, fact_hash: &FactHash) -> Option<Tombstone> {
+        self.tombstones.remove(fact_hash)
+    }
+
+    pub fn is_forgotten(&self, fact_hash: &FactHash) -> bool {
+        self.tombstones.contains_key(fact_hash)
+    }
+
+    pub fn get_tombstone(&self, fact_hash: &FactHash) -> Option<&Tombstone> {
+        self.tombstones.get(fact_hash)
+    }
+
+    /// Iterate over every tombstone, in no particular order.
+    pub fn get_forgotten(&self) -> impl Iterator<Item = &Tombstone> {
+        self.tombstones.values()
+    }
+
+    /// Episodes of a forgotten fact, for audits. Empty if the fact is not
+    /// forgotten.
+    pub fn get_forgotten_relations(&self, fact_hash: &FactHash) -> Vec<&RelationNode> {
+        if !self.is_forgotten(fact_hash) {
+            return Vec::new();
+        }
+        self.stored_relations_by_fact(fact_hash)
+    }
+
+    /// Remove the tombstone of `fact_hash` if its policy resurrects the
+    /// fact on re-observation. Returns whether a tombstone was lifted.
+    pub(crate) fn lift_resurrectable(&mut self, fact_hash: &FactHash) -> bool {
+        match self.tombstones.get(fact_hash) {
+            Some(tombstone) if tombstone.on_reobserve == Reobservation::Resurrect => {
+                self.tombstones.remove(fact_hash);
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,16 +1,108 @@
+use super::decay::DecayState;
 use super::index::LabelIndex;
+use super::intern::PredicateInterner;
+use super::limits::StoreLimits;
+use super::stats::IngestStats;
+use crate::compiler::normalizer::Normalizer;
 use crate::types::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+/// Merge a re-inserted concept into its stored counterpart: refresh
+/// `updated_at`, keep the max confidence, append unseen aliases, and take
+/// the incoming display label unless it has none. Returns whether any
+/// field actually changed.
+///
+/// Shared by every [`MemoryBackend`](super::MemoryBackend) so merge
+/// semantics cannot drift between backends.
+pub(crate) fn merge_concept(existing: &mut ConceptNode, incoming: ConceptNode) -> bool {
+    let mut changed = existing.updated_at != incoming.updated_at;
+    existing.updated_at = incoming.updated_at;
+    if incoming.confidence > existing.confidence {
+        existing.confidence = incoming.confidence;
+        changed = true;
+    }
+    for alias in incoming.aliases {
+        if !existing.aliases.contains(&alias) {
+            existing.aliases.push(alias);
+            changed = true;
+        }
+    }
+    if incoming.display_label.is_some() && incoming.display_label != existing.display_label {
+        existing.display_label = incoming.display_label;
+        changed = true;
+    }
+    changed
+}
+
+/// Drop the concepts of `record` whose label is blank, and the relations
+/// that connect them, counting both in `result`. The compiler skips
+/// tuples with blank labels; this keeps records built any other way from
+/// storing a concept no label resolves to.
+///
+/// Shared by every [`MemoryBackend`](super::MemoryBackend), like
+/// [`merge_concept`].
+pub(crate) fn refuse_blank_concepts(record: &mut MemoryRecord, result: &mut InsertResult) {
+    let blank: HashSet<ConceptHash> = record
+        .concepts
+        .iter()
+        .filter(|c| c.label.trim().is_empty())
+        .map(|c| c.hash.clone())
+        .collect();
+    if blank.is_empty() {
+        return;
+    }
+    let concepts = record.concepts.len();
+    record.concepts.retain(|c| !blank.contains(&c.hash));
+    let relations = record.relations.len();
+    record
+        .relations
+        .retain(|r| !blank.contains(&r.subject_hash) && !blank.contains(&r.object_hash));
+    result.refused_concepts += concepts - record.concepts.len();
+    result.refused_relations += relations - record.relations.len();
+}
+
+/// One entry of a fact's episode list: the episode and the `event_time`
+/// of its context, which the list is ordered by.
+#[derive(Debug, Clone)]
+struct FactEpisode {
+    /// `None` when the context is unknown; such episodes sort before every
+    /// dated one.
+    event_time: Option<EventTime>,
+    episode_hash: EpisodeHash,
+}
 
 /// In-memory graph store for concepts, relations, and contexts.
 #[derive(Debug, Clone)]
 pub struct MemoryStore {
     concepts: HashMap<ConceptHash, ConceptNode>,
-    relations_by_fact: HashMap<FactHash, Vec<RelationNode>>,
+    /// Owns every stored episode.
     relations_by_episode: HashMap<EpisodeHash, RelationNode>,
+    /// Episodes of each fact, ordered by context `event_time` (ties in
+    /// insertion order), resolved through `relations_by_episode`.
+    episodes_by_fact: HashMap<FactHash, Vec<FactEpisode>>,
     contexts: HashMap<ContextHash, ContextNode>,
-    adjacency: HashMap<ConceptHash, HashSet<FactHash>>,
+    /// Facts touching each concept, ordered by hash so per-concept
+    /// queries return them in a stable order.
+    adjacency: HashMap<ConceptHash, BTreeSet<FactHash>>,
+    /// Stored facts with [`Polarity::Negative`]; every other fact is
+    /// positive.
+    negative_facts: HashSet<FactHash>,
     label_index: LabelIndex,
+    predicates: PredicateInterner,
+    /// Number of stored episodes per context, so a context can be dropped
+    /// when eviction removes its last episode.
+    context_refs: HashMap<ContextHash, usize>,
+    pub(crate) limits: StoreLimits,
+    pub(crate) pinned: HashSet<ConceptHash>,
+    pub(crate) decay: DecayState,
+    /// Reinforcement rate applied when a known fact is observed again;
+    /// `0.0` disables reinforcement.
+    reinforcement: f64,
+    /// Whether inserts [validate](MemoryRecord::validate) records first.
+    strict: bool,
+    pub(crate) tombstones: HashMap<FactHash, Tombstone>,
+    pub(crate) ingest: IngestStats,
 }
 
 impl Default for MemoryStore {
@@ -23,122 +115,544 @@ impl MemoryStore {
     pub fn new() -> Self {
         Self {
             concepts: HashMap::new(),
-            relations_by_fact: HashMap::new(),
             relations_by_episode: HashMap::new(),
+            episodes_by_fact: HashMap::new(),
             contexts: HashMap::new(),
             adjacency: HashMap::new(),
+            negative_facts: HashSet::new(),
             label_index: LabelIndex::new(),
+            predicates: PredicateInterner::new(),
+            context_refs: HashMap::new(),
+            limits: StoreLimits::default(),
+            pinned: HashSet::new(),
+            decay: DecayState::default(),
+            reinforcement: 0.0,
+            strict: false,
+            tombstones: HashMap::new(),
+            ingest: IngestStats::default(),
         }
     }
 
+    /// Create an empty store whose label index normalizes with
+    /// `normalizer`. Pass the normalizer the records were compiled with
+    /// (their [`CompilerOptions::normalizer`](crate::compiler::CompilerOptions::normalizer)),
+    /// or [`get_concept_by_label`](Self::get_concept_by_label) will miss
+    /// labels the two normalize differently. Snapshots do not record the
+    /// normalizer: a loaded store uses the default one.
+    pub fn with_normalizer(normalizer: Arc<dyn Normalizer>) -> Self {
+        let mut store = Self::new();
+        store.label_index = LabelIndex::with_normalizer(normalizer);
+        store
+    }
+
+    /// Enable reinforcement on re-observation.
+    ///
+    /// Whenever an inserted relation belongs to a fact the store already
+    /// holds (a duplicate episode or a new episode of a known fact), the
+    /// fact's best stored episode is boosted to
+    /// `old + (1 - old) * rate`. `rate` is clamped to `[0, 1]`; `0.0`
+    /// (the default) turns reinforcement off, as does a rate that is not
+    /// finite.
+    pub fn set_reinforcement(&mut self, rate: f64) {
+        self.reinforcement = if rate.is_finite() {
+            rate.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+    }
+
+    pub fn reinforcement(&self) -> f64 {
+        self.reinforcement
+    }
+
+    /// Enable strict inserts: each record is
+    /// [validated](MemoryRecord::validate) first, and one with any issue
+    /// is rejected whole, leaving the store untouched, and reported in
+    /// [`InsertResult::rejected`]. Off by default.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
     /// Insert a full memory record. Deduplicates concepts by hash
     /// and relations by episode hash. Same fact from different contexts
     /// produces multiple episodes under the same FactHash.
     ///
     /// When a concept is re-inserted with the same hash, the store merges
     /// the new data: `updated_at` is refreshed, confidence takes the max
-    /// of old and new, and any new aliases are appended.
+    /// of old and new, and any new aliases are appended. The concept counts
+    /// in [`InsertResult::concepts_updated`] if that changed anything, and
+    /// in [`InsertResult::dupes_skipped`] otherwise.
+    ///
+    /// Aliases are indexed so [`get_concept_by_label`](Self::get_concept_by_label)
+    /// resolves them; claims that lose to another concept's label are
+    /// reported in [`InsertResult::alias_conflicts`].
+    ///
+    /// Concepts with a blank label are refused, along with the relations
+    /// that connect them, and counted in [`InsertResult::refused_concepts`]
+    /// and [`InsertResult::refused_relations`].
+    ///
+    /// With [reinforcement](Self::set_reinforcement) enabled, re-observed
+    /// facts are boosted and counted in [`InsertResult::reinforced`].
+    ///
+    /// Relations of a [forgotten](Self::forget_fact) fact are stored but
+    /// stay hidden, unless the tombstone's policy is
+    /// [`Reobservation::Resurrect`], in which case the tombstone is lifted
+    /// and counted in [`InsertResult::resurrected`].
+    ///
+    /// If the store has [`StoreLimits`], anything evicted to stay within
+    /// them is reported in [`InsertResult::evicted`].
+    ///
+    /// A [strict](Self::set_strict) store rejects records that fail
+    /// validation and reports them in [`InsertResult::rejected`].
+    ///
+    /// Every insert updates the [ingest counters](Self::ingest_stats).
     pub fn insert_record(&mut self, record: MemoryRecord) -> InsertResult {
         let mut result = InsertResult::default();
+        self.insert_unbounded(record, &mut result);
+        result.evicted = self.evict_to_limits();
+        result
+    }
+
+    /// Insert many records at once, e.g. for an initial load.
+    ///
+    /// Behaves like calling [`insert_record`](Self::insert_record) for each
+    /// record and summing the counts, except that the internal maps are
+    /// sized for the whole batch up front and [`StoreLimits`] are enforced
+    /// once at the end rather than after every record.
+    pub fn insert_records(
+        &mut self,
+        records: impl IntoIterator<Item = MemoryRecord>,
+    ) -> InsertResult {
+        let records: Vec<MemoryRecord> = records.into_iter().collect();
+        let concepts: usize = records.iter().map(|r| r.concepts.len()).sum();
+        let relations: usize = records.iter().map(|r| r.relations.len()).sum();
+        self.reserve(concepts, relations, records.len());
+
+        let mut result = InsertResult::default();
+        for record in records {
+            self.insert_unbounded(record, &mut result);
+        }
+        result.evicted = self.evict_to_limits();
+        result
+    }
 
+    /// Grow the internal maps for the given number of incoming items.
+    /// Upper bounds are fine: duplicates just leave spare capacity.
+    fn reserve(&mut self, concepts: usize, relations: usize, contexts: usize) {
+        self.concepts.reserve(concepts);
+        self.label_index.reserve(concepts);
+        self.adjacency.reserve(concepts);
+        self.relations_by_episode.reserve(relations);
+        self.episodes_by_fact.reserve(relations);
+        self.contexts.reserve(contexts);
+        self.context_refs.reserve(contexts);
+    }
+
+    /// Insert one record without enforcing limits, adding its counts to
+    /// `result`. The single code path behind both insert methods.
+    fn insert_unbounded(&mut self, mut record: MemoryRecord, result: &mut InsertResult) {
+        self.ingest.records += 1;
+        if self.strict {
+            if let Err(issues) = record.validate() {
+                result.rejected.push(RejectedRecord {
+                    hash: record.hash(),
+                    issues,
+                });
+                return;
+            }
+        }
+        refuse_blank_concepts(&mut record, result);
+        // The context goes first: relations are ordered by its event time.
+        self.index_context(record.context);
         for concept in record.concepts {
-            if let Some(existing) = self.concepts.get_mut(&concept.hash) {
-                existing.updated_at = concept.updated_at;
-                if concept.confidence > existing.confidence {
-                    existing.confidence = concept.confidence;
-                }
-                for alias in concept.aliases {
-                    if !existing.aliases.contains(&alias) {
-                        existing.aliases.push(alias);
-                    }
+            self.insert_concept(concept, result);
+        }
+        for relation in record.relations {
+            self.insert_relation(relation, result);
+        }
+    }
+
+    /// Insert or merge one concept.
+    fn insert_concept(&mut self, concept: ConceptNode, result: &mut InsertResult) {
+        if let Some(existing) = self.concepts.get_mut(&concept.hash) {
+            let hash = concept.hash.clone();
+            let added: Vec<String> = concept
+                .aliases
+                .iter()
+                .filter(|a| !existing.aliases.contains(a))
+                .cloned()
+                .collect();
+            let changed = merge_concept(existing, concept);
+            let label = existing.label.clone();
+            for alias in added {
+                match self.label_index.insert_alias(&alias, hash.clone()) {
+                    Ok(()) => result.merged_aliases.push(MergedAlias {
+                        alias,
+                        label: label.clone(),
+                        hash: hash.clone(),
+                    }),
+                    Err(conflict) => result.alias_conflicts.push(conflict),
                 }
-                result.dupes_skipped += 1;
+            }
+            if changed {
+                result.concepts_updated += 1;
             } else {
-                self.label_index
-                    .insert(&concept.label, concept.hash.clone());
-                self.concepts.insert(concept.hash.clone(), concept);
-                result.new_concepts += 1;
+                result.dupes_skipped += 1;
             }
+        } else {
+            let conflicts = self.index_concept(concept);
+            result.alias_conflicts.extend(conflicts);
+            result.new_concepts += 1;
         }
+    }
 
-        for relation in record.relations {
-            if self
-                .relations_by_episode
-                .contains_key(&relation.episode_hash)
-            {
-                result.dupes_skipped += 1;
-                continue;
-            }
+    /// Insert one relation episode, applying tombstone policy and
+    /// reinforcement for facts the store already knows.
+    fn insert_relation(&mut self, relation: RelationNode, result: &mut InsertResult) {
+        if self.lift_resurrectable(&relation.fact_hash) {
+            result.resurrected += 1;
+        }
+
+        if self.reinforce_fact(&relation.fact_hash) {
+            result.reinforced += 1;
+        }
+
+        if self
+            .relations_by_episode
+            .contains_key(&relation.episode_hash)
+        {
+            result.dupes_skipped += 1;
+            return;
+        }
+
+        let is_new_fact = !self.episodes_by_fact.contains_key(&relation.fact_hash);
+        if is_new_fact {
+            result.new_facts += 1;
+        }
 
-            let is_new_fact = !self.relations_by_fact.contains_key(&relation.fact_hash);
-            if is_new_fact {
-                result.new_facts += 1;
+        self.ingest.count_episode(
+            self.contexts
+                .get(&relation.context_hash)
+                .map(|ctx| &ctx.meta),
+        );
+        self.index_relation(relation);
+        result.new_episodes += 1;
+    }
+
+    /// Add a concept that is not yet in the store to the concept map and
+    /// index its label and aliases. No merge logic — callers check for
+    /// presence first. Returns the alias claims that lost a conflict.
+    pub(crate) fn index_concept(&mut self, concept: ConceptNode) -> Vec<AliasConflict> {
+        let mut conflicts: Vec<AliasConflict> = self
+            .label_index
+            .insert(&concept.label, concept.hash.clone())
+            .into_iter()
+            .collect();
+        for alias in &concept.aliases {
+            if let Err(conflict) = self.label_index.insert_alias(alias, concept.hash.clone()) {
+                conflicts.push(conflict);
             }
+        }
+        self.concepts.insert(concept.hash.clone(), concept);
+        conflicts
+    }
+
+    /// Add a relation episode to the episode map, the per-fact list, and
+    /// the adjacency sets of both endpoints, sharing the interned
+    /// predicate. Callers check episode dedup, and index the episode's
+    /// context first so it lands at its chronological position.
+    pub(crate) fn index_relation(&mut self, mut relation: RelationNode) {
+        relation.predicate = self.predicates.intern(&relation.predicate);
+        if relation.polarity == Polarity::Negative {
+            self.negative_facts.insert(relation.fact_hash.clone());
+        }
+        self.adjacency
+            .entry(relation.subject_hash.clone())
+            .or_default()
+            .insert(relation.fact_hash.clone());
+        self.adjacency
+            .entry(relation.object_hash.clone())
+            .or_default()
+            .insert(relation.fact_hash.clone());
 
-            self.adjacency
-                .entry(relation.subject_hash.clone())
-                .or_default()
-                .insert(relation.fact_hash.clone());
-            self.adjacency
-                .entry(relation.object_hash.clone())
-                .or_default()
-                .insert(relation.fact_hash.clone());
+        *self
+            .context_refs
+            .entry(relation.context_hash.clone())
+            .or_default() += 1;
 
-            self.relations_by_fact
-                .entry(relation.fact_hash.clone())
-                .or_default()
-                .push(relation.clone());
-            self.relations_by_episode
-                .insert(relation.episode_hash.clone(), relation);
+        let event_time = self
+            .contexts
+            .get(&relation.context_hash)
+            .map(|ctx| ctx.meta.event_time);
+        let episodes = self
+            .episodes_by_fact
+            .entry(relation.fact_hash.clone())
+            .or_default();
+        let position = episodes.partition_point(|e| e.event_time <= event_time);
+        episodes.insert(
+            position,
+            FactEpisode {
+                event_time,
+                episode_hash: relation.episode_hash.clone(),
+            },
+        );
+        self.relations_by_episode
+            .insert(relation.episode_hash.clone(), relation);
+    }
 
-            result.new_episodes += 1;
+    /// Overwrite the confidence of a stored episode in place.
+    pub(crate) fn set_episode_confidence(&mut self, episode_hash: &EpisodeHash, confidence: f64) {
+        if let Some(relation) = self.relations_by_episode.get_mut(episode_hash) {
+            relation.confidence = confidence;
         }
+    }
 
-        if !self.contexts.contains_key(&record.context.hash) {
-            self.contexts
-                .insert(record.context.hash.clone(), record.context);
+    /// Boost the highest-confidence stored episode of a fact by the
+    /// reinforcement rate. Forgotten facts are left alone: re-observing a
+    /// suppressed fact must not strengthen it. Returns whether a
+    /// confidence changed.
+    fn reinforce_fact(&mut self, fact_hash: &FactHash) -> bool {
+        if self.reinforcement == 0.0 || self.is_forgotten(fact_hash) {
+            return false;
         }
+        let Some(best) = self
+            .stored_relations_by_fact(fact_hash)
+            .into_iter()
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+        else {
+            return false;
+        };
+        let boosted = best.confidence + (1.0 - best.confidence) * self.reinforcement;
+        if boosted == best.confidence {
+            return false;
+        }
+        let episode_hash = best.episode_hash.clone();
+        self.set_episode_confidence(&episode_hash, boosted);
+        true
+    }
 
-        result
+    pub(crate) fn concepts_mut(&mut self) -> impl Iterator<Item = &mut ConceptNode> {
+        self.concepts.values_mut()
+    }
+
+    pub(crate) fn context_mut(&mut self, hash: &ContextHash) -> Option<&mut ContextNode> {
+        self.contexts.get_mut(hash)
+    }
+
+    pub(crate) fn contexts_mut(&mut self) -> impl Iterator<Item = &mut ContextNode> {
+        self.contexts.values_mut()
+    }
+
+    /// Remove one episode and everything that only existed because of it:
+    /// the fact entry and its adjacency links once the fact has no episodes
+    /// left, and the context once no episode references it.
+    pub(crate) fn remove_episode(&mut self, episode_hash: &EpisodeHash) -> Option<RelationNode> {
+        let relation = self.relations_by_episode.remove(episode_hash)?;
+        self.decay.episodes.remove(episode_hash);
+
+        if let Some(episodes) = self.episodes_by_fact.get_mut(&relation.fact_hash) {
+            episodes.retain(|e| &e.episode_hash != episode_hash);
+            if episodes.is_empty() {
+                self.episodes_by_fact.remove(&relation.fact_hash);
+                self.negative_facts.remove(&relation.fact_hash);
+                for endpoint in [&relation.subject_hash, &relation.object_hash] {
+                    if let Some(facts) = self.adjacency.get_mut(endpoint) {
+                        facts.remove(&relation.fact_hash);
+                        if facts.is_empty() {
+                            self.adjacency.remove(endpoint);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(refs) = self.context_refs.get_mut(&relation.context_hash) {
+            *refs -= 1;
+            if *refs == 0 {
+                self.context_refs.remove(&relation.context_hash);
+                self.contexts.remove(&relation.context_hash);
+            }
+        }
+
+        Some(relation)
+    }
+
+    /// Remove a concept, its label index entry, and every episode that
+    /// references it. Returns the removed episode hashes.
+    pub(crate) fn remove_concept(&mut self, hash: &ConceptHash) -> Vec<EpisodeHash> {
+        let episodes: Vec<EpisodeHash> = self
+            .relations_of(hash, true)
+            .into_iter()
+            .map(|r| r.episode_hash.clone())
+            .collect();
+        for episode in &episodes {
+            self.remove_episode(episode);
+        }
+        self.concepts.remove(hash);
+        self.decay.concepts.remove(hash);
+        self.label_index.remove_hash(hash);
+        episodes
+    }
+
+    /// Add a context node unless one with the same hash is already stored.
+    pub(crate) fn index_context(&mut self, context: ContextNode) {
+        if !self.contexts.contains_key(&context.hash) {
+            self.contexts.insert(context.hash.clone(), context);
+        }
     }
 
     pub fn get_concept(&self, hash: &ConceptHash) -> Option<&ConceptNode> {
         self.concepts.get(hash)
     }
 
+    /// Look up a concept by its label or one of its aliases. The label is
+    /// normalized before lookup.
     pub fn get_concept_by_label(&self, label: &str) -> Option<&ConceptNode> {
         let hash = self.label_index.lookup(label)?;
         self.concepts.get(hash)
     }
 
+    /// The label of a concept known only by hash, e.g. a relation
+    /// endpoint, without fetching the whole `ConceptNode`.
+    pub fn label_of(&self, hash: &ConceptHash) -> Option<&str> {
+        self.label_index.label_for(hash)
+    }
+
     /// Return all relation episodes that reference the given concept
     /// (as subject or object). Each episode is returned at most once,
-    /// deduplicated by `EpisodeHash`.
+    /// deduplicated by `EpisodeHash`. Facts come in `FactHash` order, each
+    /// with its episodes in the order of
+    /// [`get_relations_by_fact`](Self::get_relations_by_fact).
+    ///
+    /// Episodes of forgotten facts are skipped.
     pub fn get_relations(&self, concept_hash: &ConceptHash) -> Vec<&RelationNode> {
+        self.relations_of(concept_hash, false)
+    }
+
+    /// Like [`get_relations`](Self::get_relations), but only episodes of
+    /// facts with the given polarity, e.g. to find what a concept is
+    /// explicitly denied to relate to.
+    pub fn get_relations_with_polarity(
+        &self,
+        concept_hash: &ConceptHash,
+        polarity: Polarity,
+    ) -> Vec<&RelationNode> {
+        self.relations_where(concept_hash, |fh| {
+            !self.is_forgotten(fh) && self.fact_polarity(fh) == polarity
+        })
+    }
+
+    /// Polarity of a stored fact, from the index rather than its episodes.
+    fn fact_polarity(&self, fact_hash: &FactHash) -> Polarity {
+        if self.negative_facts.contains(fact_hash) {
+            Polarity::Negative
+        } else {
+            Polarity::Positive
+        }
+    }
+
+    /// Iterate over every stored negative fact that is not forgotten, in
+    /// no particular order.
+    pub fn negative_facts(&self) -> impl Iterator<Item = &FactHash> {
+        self.negative_facts
+            .iter()
+            .filter(|fh| !self.is_forgotten(fh))
+    }
+
+    fn relations_of(
+        &self,
+        concept_hash: &ConceptHash,
+        include_forgotten: bool,
+    ) -> Vec<&RelationNode> {
+        self.relations_where(concept_hash, |fh| {
+            include_forgotten || !self.is_forgotten(fh)
+        })
+    }
+
+    /// Episodes of the facts adjacent to a concept that pass `keep`, each
+    /// returned once.
+    fn relations_where(
+        &self,
+        concept_hash: &ConceptHash,
+        keep: impl Fn(&FactHash) -> bool,
+    ) -> Vec<&RelationNode> {
         let Some(fact_hashes) = self.adjacency.get(concept_hash) else {
             return Vec::new();
         };
         let mut seen = HashSet::new();
         let mut results = Vec::new();
         for fh in fact_hashes {
-            if let Some(episodes) = self.relations_by_fact.get(fh) {
-                for rel in episodes {
-                    if seen.insert(&rel.episode_hash) {
-                        results.push(rel);
-                    }
+            if !keep(fh) {
+                continue;
+            }
+            for rel in self.stored_relations_by_fact(fh) {
+                if seen.insert(&rel.episode_hash) {
+                    results.push(rel);
                 }
             }
         }
         results
     }
 
+    /// Return every episode of a fact in chronological order, or nothing
+    /// if the fact is forgotten; see
+    /// [`get_forgotten_relations`](Self::get_forgotten_relations).
+    ///
+    /// Episodes are ordered by their context's `event_time`. Episodes with
+    /// the same time keep insertion order, and episodes whose context is
+    /// missing come first.
     pub fn get_relations_by_fact(&self, fact_hash: &FactHash) -> Vec<&RelationNode> {
-        self.relations_by_fact
+        if self.is_forgotten(fact_hash) {
+            return Vec::new();
+        }
+        self.stored_relations_by_fact(fact_hash)
+    }
+
+    pub(crate) fn stored_relations_by_fact(&self, fact_hash: &FactHash) -> Vec<&RelationNode> {
+        self.episodes_by_fact
             .get(fact_hash)
-            .map(|v| v.iter().collect())
+            .map(|episodes| self.resolve(episodes).collect())
             .unwrap_or_default()
     }
 
+    /// The most recent episode of a fact by context `event_time`, or
+    /// `None` if the fact is unknown or forgotten. O(1).
+    pub fn latest_relation_by_fact(&self, fact_hash: &FactHash) -> Option<&RelationNode> {
+        if self.is_forgotten(fact_hash) {
+            return None;
+        }
+        let latest = self.episodes_by_fact.get(fact_hash)?.last()?;
+        self.relations_by_episode.get(&latest.episode_hash)
+    }
+
+    /// Resolve a fact's episode list through the episode map.
+    fn resolve<'a>(
+        &'a self,
+        episodes: &'a [FactEpisode],
+    ) -> impl Iterator<Item = &'a RelationNode> + 'a {
+        episodes
+            .iter()
+            .filter_map(|e| self.relations_by_episode.get(&e.episode_hash))
+    }
+
+    /// Look up a concept by label and collect the episodes that reference
+    /// it, as owned clones.
+    pub fn recall(&self, label: &str) -> Option<Recollection> {
+        let concept = self.get_concept_by_label(label)?;
+        Some(Recollection {
+            concept: concept.clone(),
+            relations: self
+                .get_relations(&concept.hash)
+                .into_iter()
+                .cloned()
+                .collect(),
+        })
+    }
+
     pub fn get_context(&self, hash: &ContextHash) -> Option<&ContextNode> {
         self.contexts.get(hash)
     }
@@ -147,11 +661,55 @@ impl MemoryStore {
         self.concepts.len()
     }
 
+    /// Number of stored episodes, including those of forgotten facts.
     pub fn relation_count(&self) -> usize {
         self.relations_by_episode.len()
     }
 
+    /// Number of distinct facts, i.e. relations counted once per `FactHash`
+    /// regardless of how many episodes observed them. Forgotten facts
+    /// that still have stored episodes are included.
+    pub fn fact_count(&self) -> usize {
+        self.episodes_by_fact.len()
+    }
+
+    pub fn context_count(&self) -> usize {
+        self.contexts.len()
+    }
+
     pub fn label_index(&self) -> &LabelIndex {
         &self.label_index
     }
+
+    /// Every predicate the store has seen, shared by its episodes.
+    pub fn predicates(&self) -> &PredicateInterner {
+        &self.predicates
+    }
+
+    /// Iterate over every stored concept, in no particular order.
+    pub fn concepts(&self) -> impl Iterator<Item = &ConceptNode> {
+        self.concepts.values()
+    }
+
+    /// Iterate over every relation episode of facts that are not
+    /// forgotten. Episodes of the same fact are yielded together, in the
+    /// order of [`get_relations_by_fact`](Self::get_relations_by_fact).
+    pub fn relations(&self) -> impl Iterator<Item = &RelationNode> {
+        self.episodes_by_fact
+            .iter()
+            .filter(|(fact, _)| !self.is_forgotten(fact))
+            .flat_map(|(_, episodes)| self.resolve(episodes))
+    }
+
+    /// Like [`relations`](Self::relations), but including forgotten facts.
+    pub(crate) fn stored_relations(&self) -> impl Iterator<Item = &RelationNode> {
+        self.episodes_by_fact
+            .values()
+            .flat_map(|episodes| self.resolve(episodes))
+    }
+
+    /// Iterate over every stored context, in no particular order.
+    pub fn contexts(&self) -> impl Iterator<Item = &ContextNode> {
+        self.contexts.values()
+    }
 }
@@ -1,14 +1,39 @@
-use crate::compiler::normalizer::normalize_label;
-use crate::types::ConceptHash;
+use crate::compiler::normalizer::{default_normalizer, Normalizer};
+use crate::types::{AliasConflict, ConceptHash};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-/// Reverse index from normalized label strings to ConceptHash.
+#[derive(Debug, Clone)]
+struct LabelEntry {
+    hash: ConceptHash,
+    /// Indexed through `insert_alias` rather than as a primary label.
+    alias: bool,
+}
+
+/// Reverse index from normalized label strings to ConceptHash, with a
+/// forward map back from each hash to the labels that point at it.
 ///
 /// Both `insert` and `lookup` normalize the label before accessing
-/// the map, so callers do not need to pre-normalize.
-#[derive(Debug, Clone, Default)]
+/// the map, so callers do not need to pre-normalize. Labels are
+/// normalized with the index's [`Normalizer`], which must be the one the
+/// labels were compiled with for lookups to find them. A label maps to at
+/// most one hash; re-inserting it for another hash moves it, and both
+/// directions are updated together.
+///
+/// Aliases share the namespace with primary labels but never override
+/// one; see [`AliasConflict`] for how competing claims are settled.
+#[derive(Debug, Clone)]
 pub struct LabelIndex {
-    label_to_hash: HashMap<String, ConceptHash>,
+    label_to_hash: HashMap<String, LabelEntry>,
+    /// Labels per hash, in the order they were first indexed.
+    hash_to_labels: HashMap<ConceptHash, Vec<String>>,
+    normalizer: Arc<dyn Normalizer>,
+}
+
+impl Default for LabelIndex {
+    fn default() -> Self {
+        Self::with_normalizer(default_normalizer())
+    }
 }
 
 impl LabelIndex {
@@ -16,12 +41,138 @@ impl LabelIndex {
         Self::default()
     }
 
-    pub fn insert(&mut self, label: &str, hash: ConceptHash) {
-        self.label_to_hash.insert(normalize_label(label), hash);
+    /// An empty index that normalizes labels with `normalizer`.
+    pub fn with_normalizer(normalizer: Arc<dyn Normalizer>) -> Self {
+        Self {
+            label_to_hash: HashMap::new(),
+            hash_to_labels: HashMap::new(),
+            normalizer,
+        }
+    }
+
+    pub fn normalizer(&self) -> &Arc<dyn Normalizer> {
+        &self.normalizer
+    }
+
+    /// Index a concept's primary label. A primary label displaces an
+    /// alias of another concept; the displaced claim is returned.
+    pub fn insert(&mut self, label: &str, hash: ConceptHash) -> Option<AliasConflict> {
+        let key = self.normalizer.normalize_label(label);
+        let entry = LabelEntry {
+            hash: hash.clone(),
+            alias: false,
+        };
+        let mut displaced = None;
+        if let Some(previous) = self.label_to_hash.insert(key.clone(), entry) {
+            if previous.hash == hash {
+                return None;
+            }
+            self.detach(&previous.hash, &key);
+            if previous.alias {
+                displaced = Some(AliasConflict {
+                    alias: key.clone(),
+                    kept: hash.clone(),
+                    rejected: previous.hash,
+                });
+            }
+        }
+        self.hash_to_labels.entry(hash).or_default().push(key);
+        displaced
+    }
+
+    /// Index an alias of a concept. Fails with the conflict if the alias
+    /// is another concept's primary label, or another concept's alias
+    /// with a lower hash; otherwise takes over the alias.
+    pub fn insert_alias(&mut self, alias: &str, hash: ConceptHash) -> Result<(), AliasConflict> {
+        let key = self.normalizer.normalize_label(alias);
+        if let Some(existing) = self.label_to_hash.get(&key) {
+            if existing.hash == hash {
+                return Ok(());
+            }
+            if !existing.alias || existing.hash < hash {
+                return Err(AliasConflict {
+                    alias: key,
+                    kept: existing.hash.clone(),
+                    rejected: hash,
+                });
+            }
+        }
+        let entry = LabelEntry {
+            hash: hash.clone(),
+            alias: true,
+        };
+        // Only reached with a losing alias claim (or none) in place, whose
+        // owner's reverse entry must be cleared.
+        if let Some(previous) = self.label_to_hash.insert(key.clone(), entry) {
+            self.detach(&previous.hash, &key);
+        }
+        self.hash_to_labels.entry(hash).or_default().push(key);
+        Ok(())
+    }
+
+    /// Remove the entry for `label`, but only if it still points at `hash`
+    /// (a later concept with the same normalized label may own it now).
+    pub fn remove(&mut self, label: &str, hash: &ConceptHash) -> bool {
+        let key = self.normalizer.normalize_label(label);
+        if self.lookup_key(&key) == Some(hash) {
+            self.label_to_hash.remove(&key);
+            self.detach(hash, &key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove every label and alias that resolves to `hash`.
+    pub fn remove_hash(&mut self, hash: &ConceptHash) {
+        for key in self.hash_to_labels.remove(hash).unwrap_or_default() {
+            self.label_to_hash.remove(&key);
+        }
+    }
+
+    /// Drop `key` from the labels recorded for `hash`.
+    fn detach(&mut self, hash: &ConceptHash, key: &str) {
+        if let Some(labels) = self.hash_to_labels.get_mut(hash) {
+            labels.retain(|l| l != key);
+            if labels.is_empty() {
+                self.hash_to_labels.remove(hash);
+            }
+        }
+    }
+
+    /// Reserve capacity for at least `additional` more labels.
+    pub fn reserve(&mut self, additional: usize) {
+        self.label_to_hash.reserve(additional);
+        self.hash_to_labels.reserve(additional);
     }
 
+    /// Resolve a label or alias to its concept hash.
     pub fn lookup(&self, label: &str) -> Option<&ConceptHash> {
-        self.label_to_hash.get(&normalize_label(label))
+        self.lookup_key(&self.normalizer.normalize_label(label))
+    }
+
+    fn lookup_key(&self, key: &str) -> Option<&ConceptHash> {
+        self.label_to_hash.get(key).map(|entry| &entry.hash)
+    }
+
+    /// The primary label of `hash`, normalized, falling back to its first
+    /// indexed alias when no primary label resolves to it.
+    pub fn label_for(&self, hash: &ConceptHash) -> Option<&str> {
+        let labels = self.hash_to_labels.get(hash)?;
+        labels
+            .iter()
+            .find(|key| matches!(self.label_to_hash.get(*key), Some(e) if !e.alias))
+            .or_else(|| labels.first())
+            .map(String::as_str)
+    }
+
+    /// Every label and alias that currently resolves to `hash`,
+    /// normalized, in the order they were first indexed.
+    pub fn labels_for(&self, hash: &ConceptHash) -> Vec<&str> {
+        self.hash_to_labels
+            .get(hash)
+            .map(|labels| labels.iter().map(String::as_str).collect())
+            .unwrap_or_default()
     }
 
     pub fn len(&self) -> usize {
@@ -32,3 +183,131 @@ impl LabelIndex {
         self.label_to_hash.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> ConceptHash {
+        ConceptHash::from_bytes([byte; 32])
+    }
+
+    #[test]
+    fn reverse_lookup_returns_normalized_labels() {
+        let mut index = LabelIndex::new();
+        index.insert("Agent", hash(1));
+        index.insert("  bot ", hash(1));
+
+        assert_eq!(index.label_for(&hash(1)), Some("agent"));
+        assert_eq!(index.labels_for(&hash(1)), vec!["agent", "bot"]);
+        assert_eq!(index.label_for(&hash(2)), None);
+        assert!(index.labels_for(&hash(2)).is_empty());
+    }
+
+    #[test]
+    fn reinserting_same_pair_does_not_duplicate() {
+        let mut index = LabelIndex::new();
+        index.insert("agent", hash(1));
+        index.insert("AGENT", hash(1));
+
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.labels_for(&hash(1)), vec!["agent"]);
+    }
+
+    #[test]
+    fn moving_a_label_updates_both_directions() {
+        let mut index = LabelIndex::new();
+        index.insert("agent", hash(1));
+        index.insert("bot", hash(1));
+        index.insert("bot", hash(2));
+
+        assert_eq!(index.lookup("bot"), Some(&hash(2)));
+        assert_eq!(index.labels_for(&hash(1)), vec!["agent"]);
+        assert_eq!(index.labels_for(&hash(2)), vec!["bot"]);
+
+        index.insert("agent", hash(2));
+        assert_eq!(index.label_for(&hash(1)), None);
+        assert_eq!(index.labels_for(&hash(2)), vec!["bot", "agent"]);
+    }
+
+    #[test]
+    fn aliases_resolve_and_appear_in_reverse_lookup() {
+        let mut index = LabelIndex::new();
+        index.insert("agent", hash(1));
+        assert_eq!(index.insert_alias("Bot", hash(1)), Ok(()));
+
+        assert_eq!(index.lookup("bot"), Some(&hash(1)));
+        assert_eq!(index.label_for(&hash(1)), Some("agent"));
+        assert_eq!(index.labels_for(&hash(1)), vec!["agent", "bot"]);
+    }
+
+    #[test]
+    fn alias_never_overrides_primary_label() {
+        let mut index = LabelIndex::new();
+        index.insert("bot", hash(2));
+
+        let conflict = index.insert_alias("bot", hash(1)).unwrap_err();
+        assert_eq!(
+            conflict,
+            AliasConflict {
+                alias: "bot".to_string(),
+                kept: hash(2),
+                rejected: hash(1),
+            }
+        );
+        assert_eq!(index.lookup("bot"), Some(&hash(2)));
+        assert!(index.labels_for(&hash(1)).is_empty());
+    }
+
+    #[test]
+    fn primary_label_displaces_alias() {
+        let mut index = LabelIndex::new();
+        index.insert_alias("bot", hash(1)).unwrap();
+
+        let displaced = index.insert("bot", hash(2)).unwrap();
+        assert_eq!(displaced.kept, hash(2));
+        assert_eq!(displaced.rejected, hash(1));
+        assert_eq!(index.lookup("bot"), Some(&hash(2)));
+        assert!(index.labels_for(&hash(1)).is_empty());
+    }
+
+    #[test]
+    fn competing_aliases_resolve_to_lower_hash_in_any_order() {
+        for (first, second) in [(hash(1), hash(2)), (hash(2), hash(1))] {
+            let mut index = LabelIndex::new();
+            let _ = index.insert_alias("bot", first);
+            let _ = index.insert_alias("bot", second);
+            assert_eq!(index.lookup("bot"), Some(&hash(1)));
+            assert_eq!(index.labels_for(&hash(1)), vec!["bot"]);
+            assert!(index.labels_for(&hash(2)).is_empty());
+        }
+    }
+
+    #[test]
+    fn remove_hash_drops_label_and_aliases() {
+        let mut index = LabelIndex::new();
+        index.insert("agent", hash(1));
+        index.insert_alias("bot", hash(1)).unwrap();
+        index.insert("memory", hash(2));
+
+        index.remove_hash(&hash(1));
+
+        assert_eq!(index.lookup("agent"), None);
+        assert_eq!(index.lookup("bot"), None);
+        assert_eq!(index.lookup("memory"), Some(&hash(2)));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn remove_clears_reverse_entry_only_for_owner() {
+        let mut index = LabelIndex::new();
+        index.insert("agent", hash(1));
+
+        assert!(!index.remove("agent", &hash(2)));
+        assert_eq!(index.label_for(&hash(1)), Some("agent"));
+
+        assert!(index.remove("agent", &hash(1)));
+        assert_eq!(index.label_for(&hash(1)), None);
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Shared storage for predicate strings.
+///
+/// Predicate vocabularies are tiny compared to episode counts, so the store
+/// keeps one `Arc<str>` per distinct predicate and hands out clones of it
+/// instead of letting every `RelationNode` own its own copy. Interning is
+/// exact: predicates are already normalized by the compiler.
+///
+/// Entries are never dropped, even once no episode uses them.
+#[derive(Debug, Clone, Default)]
+pub struct PredicateInterner {
+    predicates: HashSet<Arc<str>>,
+}
+
+impl PredicateInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the shared handle for `predicate`, adding it if unseen.
+    pub fn intern(&mut self, predicate: &str) -> Arc<str> {
+        if let Some(shared) = self.predicates.get(predicate) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(predicate);
+        self.predicates.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// Find the shared handle for `predicate` without adding it.
+    pub fn lookup(&self, predicate: &str) -> Option<&Arc<str>> {
+        self.predicates.get(predicate)
+    }
+
+    /// Iterate over every interned predicate, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.predicates.iter().map(|p| &**p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.predicates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.predicates.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_twice_shares_one_allocation() {
+        let mut interner = PredicateInterner::new();
+        let a = interner.intern("needs");
+        let b = interner.intern(&String::from("needs"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn lookup_finds_only_interned_predicates() {
+        let mut interner = PredicateInterner::new();
+        let needs = interner.intern("needs");
+        assert!(Arc::ptr_eq(interner.lookup("needs").unwrap(), &needs));
+        assert!(interner.lookup("uses").is_none());
+        assert!(interner.iter().eq(["needs"]));
+    }
+}
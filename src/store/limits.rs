@@ -0,0 +1,113 @@
+use super::graph::MemoryStore;
+use crate::types::*;
+
+/// Upper bounds on store size. `None` means unbounded.
+///
+/// When an insert pushes the store past a limit, the store evicts the
+/// least-recently-updated concepts (by `updated_at`) and then the oldest
+/// episodes (by `created_at`) until it fits again. Pinned concepts are
+/// never evicted, so if pinned concepts alone exceed `max_concepts` the
+/// store stays over that limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StoreLimits {
+    pub max_concepts: Option<usize>,
+    pub max_episodes: Option<usize>,
+}
+
+/// Sort key that puts the oldest timestamp first; the hash makes the order
+/// total and deterministic. Legacy timestamps that could not be read sit
+/// at [`EventTime::EPOCH`], so they are evicted first.
+fn age_key<H: Ord>(timestamp: EventTime, hash: &H) -> (EventTime, &H) {
+    (timestamp, hash)
+}
+
+impl MemoryStore {
+    /// Create an empty store that enforces the given limits on every insert.
+    pub fn with_limits(limits: StoreLimits) -> Self {
+        let mut store = Self::new();
+        store.limits = limits;
+        store
+    }
+
+    pub fn limits(&self) -> StoreLimits {
+        self.limits
+    }
+
+    /// Replace the limits. Nothing is evicted until the next insert or an
+    /// explicit [`evict_to_limits`](Self::evict_to_limits) call.
+    pub fn set_limits(&mut self, limits: StoreLimits) {
+        self.limits = limits;
+    }
+
+    /// Protect a concept from eviction. The concept does not have to be in
+    /// the store yet, so seed knowledge can be pinned before it is inserted.
+    /// Returns `false` if it was already pinned.
+    pub fn pin(&mut self, hash: &ConceptHash) -> bool {
+        self.pinned.insert(hash.clone())
+    }
+
+    /// Make a pinned concept evictable again. Returns `false` if it was not
+    /// pinned.
+    pub fn unpin(&mut self, hash: &ConceptHash) -> bool {
+        self.pinned.remove(hash)
+    }
+
+    pub fn is_pinned(&self, hash: &ConceptHash) -> bool {
+        self.pinned.contains(hash)
+    }
+
+    /// Iterate over pinned concept hashes, in no particular order.
+    pub fn pinned(&self) -> impl Iterator<Item = &ConceptHash> {
+        self.pinned.iter()
+    }
+
+    /// Evict until the store is within its limits and report what was
+    /// removed. Called automatically by `insert_record`.
+    pub fn evict_to_limits(&mut self) -> EvictionReport {
+        let mut report = EvictionReport::default();
+
+        if let Some(max) = self.limits.max_concepts {
+            let excess = self.concept_count().saturating_sub(max);
+            if excess > 0 {
+                let mut candidates: Vec<&ConceptNode> = self
+                    .concepts()
+                    .filter(|c| !self.pinned.contains(&c.hash))
+                    .collect();
+                candidates.sort_by(|a, b| {
+                    age_key(a.updated_at, &a.hash).cmp(&age_key(b.updated_at, &b.hash))
+                });
+                let victims: Vec<ConceptHash> = candidates
+                    .into_iter()
+                    .take(excess)
+                    .map(|c| c.hash.clone())
+                    .collect();
+                for hash in victims {
+                    report.episodes.extend(self.remove_concept(&hash));
+                    report.concepts.push(hash);
+                }
+            }
+        }
+
+        if let Some(max) = self.limits.max_episodes {
+            let excess = self.relation_count().saturating_sub(max);
+            if excess > 0 {
+                let mut candidates: Vec<&RelationNode> = self.stored_relations().collect();
+                candidates.sort_by(|a, b| {
+                    age_key(a.created_at, &a.episode_hash)
+                        .cmp(&age_key(b.created_at, &b.episode_hash))
+                });
+                let victims: Vec<EpisodeHash> = candidates
+                    .into_iter()
+                    .take(excess)
+                    .map(|r| r.episode_hash.clone())
+                    .collect();
+                for hash in victims {
+                    self.remove_episode(&hash);
+                    report.episodes.push(hash);
+                }
+            }
+        }
+
+        report
+    }
+}
@@ -1,4 +1,32 @@
+#[cfg(feature = "async")]
+pub mod async_store;
+pub mod backend;
+pub mod decay;
+pub mod export;
+pub mod forget;
 pub mod graph;
 pub mod index;
+pub mod intern;
+pub mod limits;
+pub mod persist;
+pub mod qualifiers;
+pub mod redact;
+pub mod snapshot;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod validity;
 
+#[cfg(feature = "async")]
+pub use async_store::{AsyncMemoryStore, SharedStore};
+pub use backend::MemoryBackend;
+pub use decay::{DecayError, DecayReport};
+pub use export::{DotOptions, ReifiedStatements, UpdatedConcepts};
 pub use graph::MemoryStore;
+pub use intern::PredicateInterner;
+pub use limits::StoreLimits;
+pub use persist::PersistError;
+pub use snapshot::SnapshotError;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+pub use stats::IngestStats;
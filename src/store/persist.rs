@@ -0,0 +1,464 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::graph::MemoryStore;
+use super::snapshot::SnapshotError;
+use crate::runtime_trait::{MemoryRuntime, TextValue};
+
+/// Largest chunk [`MemoryStore::persist_to`] writes, in bytes of snapshot
+/// JSON.
+pub const PERSIST_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Layout version recorded in the manifest. Bump whenever the manifest or
+/// the chunk contents change; `restore_from` refuses versions it does not
+/// know.
+pub const PERSIST_FORMAT_VERSION: u32 = 1;
+
+/// Error produced when persisting a store to a runtime or restoring it.
+/// `E` is the runtime's error type.
+#[derive(Debug)]
+pub enum PersistError<E> {
+    /// The runtime failed to load or save a key.
+    Runtime(E),
+    /// The store could not be written or read as a snapshot.
+    Snapshot(SnapshotError),
+    /// Nothing is stored under the manifest key.
+    NoManifest { key: String },
+    /// The manifest could not be parsed.
+    BadManifest(serde_json::Error),
+    /// The manifest was written by a newer layout. `supported` is the
+    /// newest version this build understands.
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// A chunk listed in the manifest is not stored.
+    MissingChunk { key: String },
+    /// The value under a manifest or chunk key is not text.
+    NotText { key: String },
+    /// The chunks add up to a different length than the manifest records.
+    SizeMismatch { expected: usize, found: usize },
+    /// The chunks do not hash to what the manifest records, usually
+    /// because a later persist was interrupted after writing chunks but
+    /// before writing its manifest. Hashes are lowercase hex.
+    HashMismatch { expected: String, found: String },
+}
+
+impl<E: fmt::Display> fmt::Display for PersistError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Runtime(e) => write!(f, "runtime state error: {}", e),
+            PersistError::Snapshot(e) => e.fmt(f),
+            PersistError::NoManifest { key } => {
+                write!(f, "no persisted store manifest under {:?}", key)
+            }
+            PersistError::BadManifest(e) => write!(f, "invalid persisted store manifest: {}", e),
+            PersistError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported persisted store version {} (this build reads version {})",
+                found, supported
+            ),
+            PersistError::MissingChunk { key } => {
+                write!(f, "persisted store chunk {:?} is missing", key)
+            }
+            PersistError::NotText { key } => {
+                write!(f, "persisted store value under {:?} is not text", key)
+            }
+            PersistError::SizeMismatch { expected, found } => write!(
+                f,
+                "persisted store chunks hold {} bytes, manifest expects {}",
+                found, expected
+            ),
+            PersistError::HashMismatch { expected, found } => write!(
+                f,
+                "persisted store chunks hash to {}, manifest expects {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for PersistError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistError::Snapshot(e) => Some(e),
+            PersistError::BadManifest(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<E> PersistError<E> {
+    /// The same error, with the runtime's error passed through `f`.
+    pub fn map_runtime<F>(self, f: impl FnOnce(E) -> F) -> PersistError<F> {
+        match self {
+            PersistError::Runtime(e) => PersistError::Runtime(f(e)),
+            PersistError::Snapshot(e) => PersistError::Snapshot(e),
+            PersistError::NoManifest { key } => PersistError::NoManifest { key },
+            PersistError::BadManifest(e) => PersistError::BadManifest(e),
+            PersistError::UnsupportedVersion { found, supported } => {
+                PersistError::UnsupportedVersion { found, supported }
+            }
+            PersistError::MissingChunk { key } => PersistError::MissingChunk { key },
+            PersistError::NotText { key } => PersistError::NotText { key },
+            PersistError::SizeMismatch { expected, found } => {
+                PersistError::SizeMismatch { expected, found }
+            }
+            PersistError::HashMismatch { expected, found } => {
+                PersistError::HashMismatch { expected, found }
+            }
+        }
+    }
+}
+
+impl<E> From<SnapshotError> for PersistError<E> {
+    fn from(e: SnapshotError) -> Self {
+        PersistError::Snapshot(e)
+    }
+}
+
+/// Stored under `{key_prefix}/manifest`, written after every chunk.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    /// Length of the snapshot JSON in bytes.
+    bytes: usize,
+    /// SHA-256 of the snapshot JSON, lowercase hex.
+    sha256: String,
+    /// Keys of the chunks, in order; concatenated they are the snapshot.
+    chunks: Vec<String>,
+}
+
+fn manifest_key(key_prefix: &str) -> String {
+    format!("{}/manifest", key_prefix)
+}
+
+/// Split `text` into pieces of at most `chunk_bytes` bytes, on character
+/// boundaries. A piece is longer only when one character is.
+fn split_chunks(text: &str, chunk_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = chunk_bytes.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+impl MemoryStore {
+    /// Save the store into `runtime`'s state under `key_prefix`, as a
+    /// [JSON snapshot](Self::save_json) split into chunks of at most
+    /// [`PERSIST_CHUNK_BYTES`].
+    ///
+    /// Chunks go to `{key_prefix}/chunk/{n}` in one batch, then a manifest
+    /// listing them with the snapshot's SHA-256 goes to
+    /// `{key_prefix}/manifest`. Persisting again overwrites both; chunks
+    /// beyond the new count are left in place, unlisted. If a persist is
+    /// interrupted between the two writes, [`restore_from`](Self::restore_from)
+    /// reports [`PersistError::HashMismatch`] rather than a mixed store.
+    pub fn persist_to<R>(&self, runtime: &R, key_prefix: &str) -> Result<(), PersistError<R::Error>>
+    where
+        R: MemoryRuntime,
+        R::Value: TextValue,
+    {
+        self.persist_to_with(runtime, key_prefix, PERSIST_CHUNK_BYTES)
+    }
+
+    /// [`persist_to`](Self::persist_to) with chunks of at most
+    /// `chunk_bytes`, for runtimes that limit the size of a value.
+    pub fn persist_to_with<R>(
+        &self,
+        runtime: &R,
+        key_prefix: &str,
+        chunk_bytes: usize,
+    ) -> Result<(), PersistError<R::Error>>
+    where
+        R: MemoryRuntime,
+        R::Value: TextValue,
+    {
+        let mut json = Vec::new();
+        self.save_json(&mut json)?;
+        let json = String::from_utf8(json).expect("serde_json writes UTF-8");
+
+        let entries: Vec<(String, R::Value)> = split_chunks(&json, chunk_bytes.max(1))
+            .into_iter()
+            .enumerate()
+            .map(|(n, chunk)| {
+                (
+                    format!("{}/chunk/{}", key_prefix, n),
+                    R::Value::from_text(chunk.to_string()),
+                )
+            })
+            .collect();
+        let manifest = Manifest {
+            version: PERSIST_FORMAT_VERSION,
+            bytes: json.len(),
+            sha256: hex::encode(Sha256::digest(json.as_bytes())),
+            chunks: entries.iter().map(|(key, _)| key.clone()).collect(),
+        };
+        let manifest = serde_json::to_string(&manifest).map_err(SnapshotError::from)?;
+
+        runtime
+            .save_state_many(&entries)
+            .map_err(PersistError::Runtime)?;
+        runtime
+            .save_state(&manifest_key(key_prefix), &R::Value::from_text(manifest))
+            .map_err(PersistError::Runtime)
+    }
+
+    /// Rebuild a store saved by [`persist_to`](Self::persist_to) under
+    /// `key_prefix`. The chunks are loaded in one batch and checked
+    /// against the manifest's length and SHA-256 before the snapshot is
+    /// parsed.
+    pub fn restore_from<R>(
+        runtime: &R,
+        key_prefix: &str,
+    ) -> Result<MemoryStore, PersistError<R::Error>>
+    where
+        R: MemoryRuntime,
+        R::Value: TextValue,
+    {
+        let key = manifest_key(key_prefix);
+        let manifest = runtime
+            .load_state(&key)
+            .map_err(PersistError::Runtime)?
+            .ok_or_else(|| PersistError::NoManifest { key: key.clone() })?;
+        let manifest: Manifest =
+            serde_json::from_str(manifest.as_text().ok_or(PersistError::NotText { key })?)
+                .map_err(PersistError::BadManifest)?;
+        if manifest.version != PERSIST_FORMAT_VERSION {
+            return Err(PersistError::UnsupportedVersion {
+                found: manifest.version,
+                supported: PERSIST_FORMAT_VERSION,
+            });
+        }
+
+        let chunks = runtime
+            .load_state_many(&manifest.chunks)
+            .map_err(PersistError::Runtime)?;
+        // The manifest is untrusted, so `bytes` is checked, not used to
+        // reserve memory.
+        let mut json = String::new();
+        for (key, chunk) in manifest.chunks.iter().zip(&chunks) {
+            let chunk = chunk
+                .as_ref()
+                .ok_or_else(|| PersistError::MissingChunk { key: key.clone() })?;
+            json.push_str(
+                chunk
+                    .as_text()
+                    .ok_or_else(|| PersistError::NotText { key: key.clone() })?,
+            );
+        }
+
+        if json.len() != manifest.bytes {
+            return Err(PersistError::SizeMismatch {
+                expected: manifest.bytes,
+                found: json.len(),
+            });
+        }
+        let found = hex::encode(Sha256::digest(json.as_bytes()));
+        if found != manifest.sha256.to_ascii_lowercase() {
+            return Err(PersistError::HashMismatch {
+                expected: manifest.sha256,
+                found,
+            });
+        }
+        Ok(MemoryStore::load_json(json.as_bytes())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::compiler::emitter::compile;
+    use crate::types::{CompilerInput, ContextMeta};
+
+    /// Keeps state in a map and counts the loads and saves it serves.
+    #[derive(Default)]
+    struct MapRuntime {
+        state: RefCell<HashMap<String, Value>>,
+        calls: RefCell<usize>,
+    }
+
+    impl MemoryRuntime for MapRuntime {
+        type Value = Value;
+        type Hash = [u8; 32];
+        type Error = String;
+
+        fn hash(&self, input: &[u8]) -> [u8; 32] {
+            Sha256::digest(input).into()
+        }
+
+        fn execute_graph(
+            &self,
+            _graph_source: &str,
+            _inputs: HashMap<String, Value>,
+        ) -> Result<HashMap<String, Value>, String> {
+            Err("no graphs here".to_string())
+        }
+
+        fn load_state(&self, key: &str) -> Result<Option<Value>, String> {
+            *self.calls.borrow_mut() += 1;
+            Ok(self.state.borrow().get(key).cloned())
+        }
+
+        fn save_state(&self, key: &str, value: &Value) -> Result<(), String> {
+            *self.calls.borrow_mut() += 1;
+            self.state
+                .borrow_mut()
+                .insert(key.to_string(), value.clone());
+            Ok(())
+        }
+    }
+
+    fn store() -> MemoryStore {
+        let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .tuple("Zoë", "lives_in", "Köln", 0.9)
+            .tuple("Zoë", "likes", "Rust", 0.8)
+            .tuple("Rust", "is_a", "language", 1.0)
+            .build()
+            .unwrap();
+        let mut store = MemoryStore::new();
+        store.insert_record(compile(&input).record);
+        store
+    }
+
+    fn json_of(store: &MemoryStore) -> String {
+        let mut json = Vec::new();
+        store.save_json(&mut json).unwrap();
+        String::from_utf8(json).unwrap()
+    }
+
+    #[test]
+    fn chunks_split_on_character_boundaries() {
+        assert_eq!(split_chunks("abcdef", 4), ["abcd", "ef"]);
+        assert_eq!(split_chunks("aöb", 2), ["a", "ö", "b"]);
+        assert_eq!(split_chunks("ööö", 1), ["ö", "ö", "ö"]);
+        assert!(split_chunks("", 4).is_empty());
+    }
+
+    #[test]
+    fn round_trips_in_many_chunks() {
+        let store = store();
+        let runtime = MapRuntime::default();
+        store.persist_to_with(&runtime, "mem", 7).unwrap();
+
+        let chunks = runtime
+            .state
+            .borrow()
+            .keys()
+            .filter(|key| key.starts_with("mem/chunk/"))
+            .count();
+        assert!(chunks > 10);
+        *runtime.calls.borrow_mut() = 0;
+        let restored = MemoryStore::restore_from(&runtime, "mem").unwrap();
+        assert_eq!(json_of(&restored), json_of(&store));
+        // Manifest, then every chunk one by one through the default batch.
+        assert_eq!(*runtime.calls.borrow(), 1 + chunks);
+    }
+
+    #[test]
+    fn prefixes_keep_stores_apart() {
+        let runtime = MapRuntime::default();
+        store().persist_to(&runtime, "a").unwrap();
+        MemoryStore::new().persist_to(&runtime, "b").unwrap();
+        assert_eq!(
+            MemoryStore::restore_from(&runtime, "a")
+                .unwrap()
+                .concept_count(),
+            store().concept_count()
+        );
+        assert_eq!(
+            MemoryStore::restore_from(&runtime, "b")
+                .unwrap()
+                .concept_count(),
+            0
+        );
+    }
+
+    #[test]
+    fn interrupted_persists_are_detected() {
+        let runtime = MapRuntime::default();
+        MemoryStore::new()
+            .persist_to_with(&runtime, "mem", 16)
+            .unwrap();
+        runtime
+            .state
+            .borrow_mut()
+            .insert("mem/chunk/0".to_string(), json!("{\"concepts\":[1],"));
+        assert!(matches!(
+            MemoryStore::restore_from(&runtime, "mem"),
+            Err(PersistError::HashMismatch { .. })
+        ));
+
+        runtime.state.borrow_mut().remove("mem/chunk/1");
+        let err = MemoryStore::restore_from(&runtime, "mem").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "persisted store chunk \"mem/chunk/1\" is missing"
+        );
+    }
+
+    #[test]
+    fn bad_manifests_are_rejected() {
+        let runtime = MapRuntime::default();
+        assert!(matches!(
+            MemoryStore::restore_from(&runtime, "mem"),
+            Err(PersistError::NoManifest { key }) if key == "mem/manifest"
+        ));
+
+        runtime
+            .save_state("mem/manifest", &json!({"version": 1}))
+            .unwrap();
+        assert!(matches!(
+            MemoryStore::restore_from(&runtime, "mem"),
+            Err(PersistError::NotText { .. })
+        ));
+
+        runtime
+            .save_state(
+                "mem/manifest",
+                &json!(r#"{"version":2,"bytes":0,"sha256":"","chunks":[]}"#),
+            )
+            .unwrap();
+        assert!(matches!(
+            MemoryStore::restore_from(&runtime, "mem"),
+            Err(PersistError::UnsupportedVersion {
+                found: 2,
+                supported: PERSIST_FORMAT_VERSION
+            })
+        ));
+    }
+
+    #[test]
+    fn manifest_sizes_are_checked_not_trusted() {
+        let runtime = MapRuntime::default();
+        MemoryStore::new().persist_to(&runtime, "mem").unwrap();
+        let manifest = runtime.state.borrow()["mem/manifest"].clone();
+        let mut manifest: Value = serde_json::from_str(manifest.as_str().unwrap()).unwrap();
+        let actual = manifest["bytes"].as_u64().unwrap() as usize;
+
+        manifest["bytes"] = json!(usize::MAX);
+        runtime
+            .save_state("mem/manifest", &json!(manifest.to_string()))
+            .unwrap();
+        let err = MemoryStore::restore_from(&runtime, "mem").unwrap_err();
+        assert!(matches!(
+            err,
+            PersistError::SizeMismatch { expected, found }
+                if expected == usize::MAX && found == actual
+        ));
+    }
+}
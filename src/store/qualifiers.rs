@@ -0,0 +1,26 @@
+use super::graph::MemoryStore;
+use crate::compiler::hasher;
+use crate::types::*;
+
+impl MemoryStore {
+    /// The qualifiers of a relation episode, as `(key, value label)` pairs
+    /// sorted by key, e.g. `[("day", "tuesday"), ("location", "paris")]`.
+    /// Empty if the episode has no qualifiers or its statement concept is
+    /// not stored.
+    ///
+    /// Qualifiers are stored as ordinary relations from the episode's
+    /// statement concept (see [`hasher::statement_label`]), so they can also
+    /// be walked with [`get_relations`](Self::get_relations) on that
+    /// concept. Qualifiers of forgotten qualifier facts are skipped.
+    pub fn get_qualifiers(&self, episode_hash: &EpisodeHash) -> Vec<(&str, &str)> {
+        let statement = hasher::concept_hash(&hasher::statement_label(episode_hash));
+        let mut qualifiers: Vec<(&str, &str)> = self
+            .get_relations(&statement)
+            .into_iter()
+            .filter(|r| r.subject_hash == statement)
+            .filter_map(|r| Some((&*r.predicate, self.label_of(&r.object_hash)?)))
+            .collect();
+        qualifiers.sort_unstable();
+        qualifiers
+    }
+}
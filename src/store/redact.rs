@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use super::graph::MemoryStore;
+use crate::types::*;
+
+/// Value that replaces a redacted metadata entry.
+pub const REDACTED: &str = "[redacted]";
+
+/// Replace the values of `keys` in `metadata` with [`REDACTED`]. Returns
+/// how many values changed; values already redacted are not counted.
+fn redact(metadata: &mut Option<HashMap<String, String>>, keys: &[&str]) -> usize {
+    let Some(metadata) = metadata else {
+        return 0;
+    };
+    let mut redacted = 0;
+    for key in keys {
+        if let Some(value) = metadata.get_mut(*key) {
+            if value.as_str() != REDACTED {
+                *value = REDACTED.to_string();
+                redacted += 1;
+            }
+        }
+    }
+    redacted
+}
+
+impl MemoryStore {
+    /// Replace the values of the named metadata keys of a stored context
+    /// with [`REDACTED`]. Keys stay present, so readers can tell a value
+    /// was removed. Returns how many values were redacted.
+    ///
+    /// The context keeps its hash: `ContextHash` covers `event_time`,
+    /// `source`, and `scope` only, so episodes still resolve to it.
+    /// Exports and snapshots read the stored context and carry the
+    /// redacted values, and re-inserting the original context does not
+    /// restore them, because stored contexts are never overwritten.
+    pub fn redact_context_metadata(&mut self, hash: &ContextHash, keys: &[&str]) -> usize {
+        self.context_mut(hash)
+            .map_or(0, |ctx| redact(&mut ctx.meta.metadata, keys))
+    }
+
+    /// Redact `key` in every stored context. Returns how many values were
+    /// redacted; see [`redact_context_metadata`](Self::redact_context_metadata).
+    pub fn redact_metadata_key_everywhere(&mut self, key: &str) -> usize {
+        self.contexts_mut()
+            .map(|ctx| redact(&mut ctx.meta.metadata, &[key]))
+            .sum()
+    }
+}
@@ -0,0 +1,912 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::decay::DecayState;
+use super::graph::MemoryStore;
+use super::stats::IngestStats;
+use crate::time::parse_timestamp;
+use crate::types::*;
+
+/// Magic bytes at the start of every binary snapshot.
+pub const BINARY_MAGIC: &[u8; 8] = b"ZMEMSNAP";
+
+/// Layout version written after the magic. Bump whenever the binary
+/// layout changes; `load_binary` refuses versions it does not know.
+///
+/// History:
+/// - 1: concepts, relations, contexts
+/// - 2: adds pinned concept hashes
+/// - 3: adds the confidence decay watermarks
+/// - 4: adds forgotten-fact tombstones
+/// - 5: adds the ingest counters
+/// - 6: adds the hashes of negative facts
+/// - 7: adds episode validity periods
+/// - 8: adds episode utterance spans
+/// - 9: adds concept display labels
+/// - 10: adds per-item decay times, superseding the watermarks
+pub const BINARY_FORMAT_VERSION: u32 = 10;
+
+/// Error produced when saving or loading a store snapshot.
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+    /// The input does not start with [`BINARY_MAGIC`].
+    BadMagic,
+    /// The binary header carries a version this build cannot read.
+    /// `supported` is the newest version this build understands.
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "snapshot I/O error: {}", e),
+            SnapshotError::Json(e) => write!(f, "invalid JSON snapshot: {}", e),
+            SnapshotError::Binary(e) => write!(f, "invalid binary snapshot: {}", e),
+            SnapshotError::BadMagic => write!(f, "not a 0-memory binary snapshot (bad magic)"),
+            SnapshotError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported binary snapshot version {} (this build reads version {})",
+                found, supported
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(e) => Some(e),
+            SnapshotError::Json(e) => Some(e),
+            SnapshotError::Binary(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(e: serde_json::Error) -> Self {
+        SnapshotError::Json(e)
+    }
+}
+
+impl From<bincode::Error> for SnapshotError {
+    fn from(e: bincode::Error) -> Self {
+        SnapshotError::Binary(e)
+    }
+}
+
+/// Serializable view of a store: only primary data, never derived indexes.
+///
+/// Adjacency, the label index, and the per-fact lists are rebuilt on load,
+/// so the snapshot stays valid when their in-memory layout changes.
+/// Everything is listed in hash order, so saving the same store twice
+/// gives the same bytes. Relations are listed fact by fact in
+/// chronological order, so episodes with equal event times keep their
+/// relative order across a save/load cycle.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoreSnapshot {
+    concepts: Vec<ConceptNode>,
+    relations: Vec<RelationNode>,
+    contexts: Vec<ContextNode>,
+    #[serde(default)]
+    pinned: Vec<ConceptHash>,
+    /// Store-wide decay watermark of older snapshots, applied on load to
+    /// every episode without an entry in `episodes_decayed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    episodes_decayed_through: Option<String>,
+    /// Store-wide decay watermark of older snapshots, applied on load to
+    /// every concept without an entry in `concepts_decayed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    concepts_decayed_through: Option<String>,
+    #[serde(default)]
+    tombstones: Vec<Tombstone>,
+    #[serde(default)]
+    ingest: IngestStats,
+    #[serde(default)]
+    episodes_decayed: Vec<DecayedThrough<EpisodeHash>>,
+    #[serde(default)]
+    concepts_decayed: Vec<DecayedThrough<ConceptHash>>,
+}
+
+/// Time up to which one item's confidence has been decayed.
+#[derive(Debug, Serialize, Deserialize)]
+struct DecayedThrough<H> {
+    hash: H,
+    /// RFC 3339.
+    through: String,
+}
+
+/// Binary layout of a context. Mirrors `ContextNode`, but without the
+/// `skip_serializing_if` attributes of `ContextMeta`, which a
+/// non-self-describing format like bincode cannot read back.
+#[derive(Debug, Serialize, Deserialize)]
+struct BinaryContext {
+    hash: ContextHash,
+    event_time: EventTime,
+    source: String,
+    scope: String,
+    agent_id: Option<String>,
+    session_id: Option<String>,
+    metadata: Option<HashMap<String, String>>,
+}
+
+/// Binary layout of a concept. Mirrors `ConceptNode` without its display
+/// label, which is listed only for the concepts that have one.
+#[derive(Debug, Serialize, Deserialize)]
+struct BinaryConcept {
+    hash: ConceptHash,
+    label: String,
+    aliases: Vec<String>,
+    confidence: f64,
+    created_at: EventTime,
+    updated_at: EventTime,
+}
+
+/// Display label of a concept that has one.
+#[derive(Debug, Serialize, Deserialize)]
+struct ConceptDisplayLabel {
+    hash: ConceptHash,
+    display_label: String,
+}
+
+/// Binary layout of a relation. Mirrors `RelationNode` without its
+/// polarity, validity, and spans: polarity is a property of the whole
+/// fact, so the body lists negative facts once instead of tagging every
+/// episode, and validity and spans are rare enough to be listed only for
+/// the episodes that have them.
+#[derive(Debug, Serialize, Deserialize)]
+struct BinaryRelation {
+    fact_hash: FactHash,
+    episode_hash: EpisodeHash,
+    subject_hash: ConceptHash,
+    predicate: Arc<str>,
+    object_hash: ConceptHash,
+    confidence: f64,
+    context_hash: ContextHash,
+    created_at: EventTime,
+}
+
+/// Validity period of an episode that has at least one bound.
+#[derive(Debug, Serialize, Deserialize)]
+struct EpisodeValidity {
+    episode_hash: EpisodeHash,
+    valid_from: Option<String>,
+    valid_to: Option<String>,
+}
+
+/// Utterance spans of an episode that has at least one.
+#[derive(Debug, Serialize, Deserialize)]
+struct EpisodeSpans {
+    episode_hash: EpisodeHash,
+    subject: Option<Span>,
+    object: Option<Span>,
+    evidence: Option<Span>,
+}
+
+/// The watermarks are only read from older versions; current snapshots
+/// write them as `None` and list per-item decay times at the end.
+#[derive(Debug, Serialize, Deserialize)]
+struct BinarySnapshot {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+    ingest: IngestStats,
+    negative_facts: Vec<FactHash>,
+    validity: Vec<EpisodeValidity>,
+    spans: Vec<EpisodeSpans>,
+    display_labels: Vec<ConceptDisplayLabel>,
+    episodes_decayed: Vec<DecayedThrough<EpisodeHash>>,
+    concepts_decayed: Vec<DecayedThrough<ConceptHash>>,
+}
+
+/// Version 9 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV9 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+    ingest: IngestStats,
+    negative_facts: Vec<FactHash>,
+    validity: Vec<EpisodeValidity>,
+    spans: Vec<EpisodeSpans>,
+    display_labels: Vec<ConceptDisplayLabel>,
+}
+
+impl From<BinarySnapshotV9> for BinarySnapshot {
+    fn from(v9: BinarySnapshotV9) -> Self {
+        BinarySnapshot {
+            concepts: v9.concepts,
+            relations: v9.relations,
+            contexts: v9.contexts,
+            pinned: v9.pinned,
+            episodes_decayed_through: v9.episodes_decayed_through,
+            concepts_decayed_through: v9.concepts_decayed_through,
+            tombstones: v9.tombstones,
+            ingest: v9.ingest,
+            negative_facts: v9.negative_facts,
+            validity: v9.validity,
+            spans: v9.spans,
+            display_labels: v9.display_labels,
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 8 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV8 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+    ingest: IngestStats,
+    negative_facts: Vec<FactHash>,
+    validity: Vec<EpisodeValidity>,
+    spans: Vec<EpisodeSpans>,
+}
+
+impl From<BinarySnapshotV8> for BinarySnapshot {
+    fn from(v8: BinarySnapshotV8) -> Self {
+        BinarySnapshot {
+            concepts: v8.concepts,
+            relations: v8.relations,
+            contexts: v8.contexts,
+            pinned: v8.pinned,
+            episodes_decayed_through: v8.episodes_decayed_through,
+            concepts_decayed_through: v8.concepts_decayed_through,
+            tombstones: v8.tombstones,
+            ingest: v8.ingest,
+            negative_facts: v8.negative_facts,
+            validity: v8.validity,
+            spans: v8.spans,
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 7 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV7 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+    ingest: IngestStats,
+    negative_facts: Vec<FactHash>,
+    validity: Vec<EpisodeValidity>,
+}
+
+impl From<BinarySnapshotV7> for BinarySnapshot {
+    fn from(v7: BinarySnapshotV7) -> Self {
+        BinarySnapshot {
+            concepts: v7.concepts,
+            relations: v7.relations,
+            contexts: v7.contexts,
+            pinned: v7.pinned,
+            episodes_decayed_through: v7.episodes_decayed_through,
+            concepts_decayed_through: v7.concepts_decayed_through,
+            tombstones: v7.tombstones,
+            ingest: v7.ingest,
+            negative_facts: v7.negative_facts,
+            validity: v7.validity,
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 6 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV6 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+    ingest: IngestStats,
+    negative_facts: Vec<FactHash>,
+}
+
+impl From<BinarySnapshotV6> for BinarySnapshot {
+    fn from(v6: BinarySnapshotV6) -> Self {
+        BinarySnapshot {
+            concepts: v6.concepts,
+            relations: v6.relations,
+            contexts: v6.contexts,
+            pinned: v6.pinned,
+            episodes_decayed_through: v6.episodes_decayed_through,
+            concepts_decayed_through: v6.concepts_decayed_through,
+            tombstones: v6.tombstones,
+            ingest: v6.ingest,
+            negative_facts: v6.negative_facts,
+            validity: Vec::new(),
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 5 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV5 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+    ingest: IngestStats,
+}
+
+impl From<BinarySnapshotV5> for BinarySnapshot {
+    fn from(v5: BinarySnapshotV5) -> Self {
+        BinarySnapshot {
+            concepts: v5.concepts,
+            relations: v5.relations,
+            contexts: v5.contexts,
+            pinned: v5.pinned,
+            episodes_decayed_through: v5.episodes_decayed_through,
+            concepts_decayed_through: v5.concepts_decayed_through,
+            tombstones: v5.tombstones,
+            ingest: v5.ingest,
+            negative_facts: Vec::new(),
+            validity: Vec::new(),
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 4 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV4 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+    tombstones: Vec<Tombstone>,
+}
+
+impl From<BinarySnapshotV4> for BinarySnapshot {
+    fn from(v4: BinarySnapshotV4) -> Self {
+        BinarySnapshot {
+            concepts: v4.concepts,
+            relations: v4.relations,
+            contexts: v4.contexts,
+            pinned: v4.pinned,
+            episodes_decayed_through: v4.episodes_decayed_through,
+            concepts_decayed_through: v4.concepts_decayed_through,
+            tombstones: v4.tombstones,
+            ingest: IngestStats::default(),
+            negative_facts: Vec::new(),
+            validity: Vec::new(),
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 3 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV3 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+    episodes_decayed_through: Option<String>,
+    concepts_decayed_through: Option<String>,
+}
+
+impl From<BinarySnapshotV3> for BinarySnapshot {
+    fn from(v3: BinarySnapshotV3) -> Self {
+        BinarySnapshot {
+            concepts: v3.concepts,
+            relations: v3.relations,
+            contexts: v3.contexts,
+            pinned: v3.pinned,
+            episodes_decayed_through: v3.episodes_decayed_through,
+            concepts_decayed_through: v3.concepts_decayed_through,
+            tombstones: Vec::new(),
+            ingest: IngestStats::default(),
+            negative_facts: Vec::new(),
+            validity: Vec::new(),
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 2 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV2 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+    pinned: Vec<ConceptHash>,
+}
+
+impl From<BinarySnapshotV2> for BinarySnapshot {
+    fn from(v2: BinarySnapshotV2) -> Self {
+        BinarySnapshot {
+            concepts: v2.concepts,
+            relations: v2.relations,
+            contexts: v2.contexts,
+            pinned: v2.pinned,
+            episodes_decayed_through: None,
+            concepts_decayed_through: None,
+            tombstones: Vec::new(),
+            ingest: IngestStats::default(),
+            negative_facts: Vec::new(),
+            validity: Vec::new(),
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+/// Version 1 body, kept so older snapshots still load.
+#[derive(Debug, Deserialize)]
+struct BinarySnapshotV1 {
+    concepts: Vec<BinaryConcept>,
+    relations: Vec<BinaryRelation>,
+    contexts: Vec<BinaryContext>,
+}
+
+impl From<BinarySnapshotV1> for BinarySnapshot {
+    fn from(v1: BinarySnapshotV1) -> Self {
+        BinarySnapshot {
+            concepts: v1.concepts,
+            relations: v1.relations,
+            contexts: v1.contexts,
+            pinned: Vec::new(),
+            episodes_decayed_through: None,
+            concepts_decayed_through: None,
+            tombstones: Vec::new(),
+            ingest: IngestStats::default(),
+            negative_facts: Vec::new(),
+            validity: Vec::new(),
+            spans: Vec::new(),
+            display_labels: Vec::new(),
+            episodes_decayed: Vec::new(),
+            concepts_decayed: Vec::new(),
+        }
+    }
+}
+
+impl From<StoreSnapshot> for BinarySnapshot {
+    fn from(snapshot: StoreSnapshot) -> Self {
+        let negative: HashSet<&FactHash> = snapshot
+            .relations
+            .iter()
+            .filter(|r| r.polarity == Polarity::Negative)
+            .map(|r| &r.fact_hash)
+            .collect();
+        let negative_facts = negative.into_iter().cloned().collect();
+        let validity = snapshot
+            .relations
+            .iter()
+            .filter(|r| r.valid_from.is_some() || r.valid_to.is_some())
+            .map(|r| EpisodeValidity {
+                episode_hash: r.episode_hash.clone(),
+                valid_from: r.valid_from.clone(),
+                valid_to: r.valid_to.clone(),
+            })
+            .collect();
+        let spans = snapshot
+            .relations
+            .iter()
+            .filter(|r| {
+                r.subject_span.is_some() || r.object_span.is_some() || r.evidence_span.is_some()
+            })
+            .map(|r| EpisodeSpans {
+                episode_hash: r.episode_hash.clone(),
+                subject: r.subject_span,
+                object: r.object_span,
+                evidence: r.evidence_span,
+            })
+            .collect();
+        let display_labels = snapshot
+            .concepts
+            .iter()
+            .filter_map(|c| {
+                Some(ConceptDisplayLabel {
+                    hash: c.hash.clone(),
+                    display_label: c.display_label.clone()?,
+                })
+            })
+            .collect();
+        BinarySnapshot {
+            concepts: snapshot.concepts.into_iter().map(Into::into).collect(),
+            relations: snapshot.relations.into_iter().map(Into::into).collect(),
+            contexts: snapshot.contexts.into_iter().map(Into::into).collect(),
+            pinned: snapshot.pinned,
+            episodes_decayed_through: snapshot.episodes_decayed_through,
+            concepts_decayed_through: snapshot.concepts_decayed_through,
+            tombstones: snapshot.tombstones,
+            ingest: snapshot.ingest,
+            negative_facts,
+            validity,
+            spans,
+            display_labels,
+            episodes_decayed: snapshot.episodes_decayed,
+            concepts_decayed: snapshot.concepts_decayed,
+        }
+    }
+}
+
+impl From<BinarySnapshot> for StoreSnapshot {
+    fn from(body: BinarySnapshot) -> Self {
+        let negative: HashSet<FactHash> = body.negative_facts.into_iter().collect();
+        let mut validity: HashMap<EpisodeHash, EpisodeValidity> = body
+            .validity
+            .into_iter()
+            .map(|v| (v.episode_hash.clone(), v))
+            .collect();
+        let mut spans: HashMap<EpisodeHash, EpisodeSpans> = body
+            .spans
+            .into_iter()
+            .map(|s| (s.episode_hash.clone(), s))
+            .collect();
+        let mut display_labels: HashMap<ConceptHash, String> = body
+            .display_labels
+            .into_iter()
+            .map(|d| (d.hash, d.display_label))
+            .collect();
+        StoreSnapshot {
+            concepts: body
+                .concepts
+                .into_iter()
+                .map(|c| {
+                    let mut node = c.into_node();
+                    node.display_label = display_labels.remove(&node.hash);
+                    node
+                })
+                .collect(),
+            relations: body
+                .relations
+                .into_iter()
+                .map(|r| {
+                    let polarity = if negative.contains(&r.fact_hash) {
+                        Polarity::Negative
+                    } else {
+                        Polarity::Positive
+                    };
+                    let mut node = r.into_node(polarity);
+                    if let Some(v) = validity.remove(&node.episode_hash) {
+                        node.valid_from = v.valid_from;
+                        node.valid_to = v.valid_to;
+                    }
+                    if let Some(s) = spans.remove(&node.episode_hash) {
+                        node.subject_span = s.subject;
+                        node.object_span = s.object;
+                        node.evidence_span = s.evidence;
+                    }
+                    node
+                })
+                .collect(),
+            contexts: body.contexts.into_iter().map(Into::into).collect(),
+            pinned: body.pinned,
+            episodes_decayed_through: body.episodes_decayed_through,
+            concepts_decayed_through: body.concepts_decayed_through,
+            tombstones: body.tombstones,
+            ingest: body.ingest,
+            episodes_decayed: body.episodes_decayed,
+            concepts_decayed: body.concepts_decayed,
+        }
+    }
+}
+
+impl From<ConceptNode> for BinaryConcept {
+    fn from(node: ConceptNode) -> Self {
+        BinaryConcept {
+            hash: node.hash,
+            label: node.label,
+            aliases: node.aliases,
+            confidence: node.confidence,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+        }
+    }
+}
+
+impl BinaryConcept {
+    /// The concept without a display label.
+    fn into_node(self) -> ConceptNode {
+        ConceptNode {
+            hash: self.hash,
+            label: self.label,
+            aliases: self.aliases,
+            confidence: self.confidence,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            display_label: None,
+        }
+    }
+}
+
+impl From<RelationNode> for BinaryRelation {
+    fn from(node: RelationNode) -> Self {
+        BinaryRelation {
+            fact_hash: node.fact_hash,
+            episode_hash: node.episode_hash,
+            subject_hash: node.subject_hash,
+            predicate: node.predicate,
+            object_hash: node.object_hash,
+            confidence: node.confidence,
+            context_hash: node.context_hash,
+            created_at: node.created_at,
+        }
+    }
+}
+
+impl BinaryRelation {
+    /// The relation with `polarity`, and no validity period or spans.
+    fn into_node(self, polarity: Polarity) -> RelationNode {
+        RelationNode {
+            fact_hash: self.fact_hash,
+            episode_hash: self.episode_hash,
+            subject_hash: self.subject_hash,
+            predicate: self.predicate,
+            object_hash: self.object_hash,
+            confidence: self.confidence,
+            context_hash: self.context_hash,
+            created_at: self.created_at,
+            polarity,
+            valid_from: None,
+            valid_to: None,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        }
+    }
+}
+
+impl From<ContextNode> for BinaryContext {
+    fn from(node: ContextNode) -> Self {
+        BinaryContext {
+            hash: node.hash,
+            event_time: node.meta.event_time,
+            source: node.meta.source,
+            scope: node.meta.scope,
+            agent_id: node.meta.agent_id,
+            session_id: node.meta.session_id,
+            metadata: node.meta.metadata,
+        }
+    }
+}
+
+impl From<BinaryContext> for ContextNode {
+    fn from(ctx: BinaryContext) -> Self {
+        ContextNode {
+            hash: ctx.hash,
+            meta: ContextMeta {
+                event_time: ctx.event_time,
+                source: ctx.source,
+                scope: ctx.scope,
+                agent_id: ctx.agent_id,
+                session_id: ctx.session_id,
+                metadata: ctx.metadata,
+            },
+        }
+    }
+}
+
+impl StoreSnapshot {
+    fn capture(store: &MemoryStore) -> Self {
+        let mut concepts: Vec<ConceptNode> = store.concepts().cloned().collect();
+        concepts.sort_by(|a, b| a.hash.cmp(&b.hash));
+        // Stable, so each fact's episodes stay in chronological order.
+        let mut relations: Vec<RelationNode> = store.stored_relations().cloned().collect();
+        relations.sort_by(|a, b| a.fact_hash.cmp(&b.fact_hash));
+        let mut contexts: Vec<ContextNode> = store.contexts().cloned().collect();
+        contexts.sort_by(|a, b| a.hash.cmp(&b.hash));
+        let mut pinned: Vec<ConceptHash> = store.pinned().cloned().collect();
+        pinned.sort();
+        let mut tombstones: Vec<Tombstone> = store.get_forgotten().cloned().collect();
+        tombstones.sort_by(|a, b| a.fact_hash.cmp(&b.fact_hash));
+        let mut episodes_decayed: Vec<DecayedThrough<EpisodeHash>> = store
+            .decay
+            .episodes
+            .iter()
+            .map(|(hash, through)| DecayedThrough {
+                hash: hash.clone(),
+                through: through.to_rfc3339(),
+            })
+            .collect();
+        episodes_decayed.sort_by(|a, b| a.hash.cmp(&b.hash));
+        let mut concepts_decayed: Vec<DecayedThrough<ConceptHash>> = store
+            .decay
+            .concepts
+            .iter()
+            .map(|(hash, through)| DecayedThrough {
+                hash: hash.clone(),
+                through: through.to_rfc3339(),
+            })
+            .collect();
+        concepts_decayed.sort_by(|a, b| a.hash.cmp(&b.hash));
+        StoreSnapshot {
+            concepts,
+            relations,
+            contexts,
+            pinned,
+            episodes_decayed_through: None,
+            concepts_decayed_through: None,
+            tombstones,
+            ingest: store.ingest_stats().clone(),
+            episodes_decayed,
+            concepts_decayed,
+        }
+    }
+
+    fn restore(self) -> MemoryStore {
+        let mut store = MemoryStore::new();
+        for context in self.contexts {
+            store.index_context(context);
+        }
+        for concept in self.concepts {
+            store.index_concept(concept);
+        }
+        for relation in self.relations {
+            store.index_relation(relation);
+        }
+        for hash in &self.pinned {
+            store.pin(hash);
+        }
+        for tombstone in self.tombstones {
+            store
+                .tombstones
+                .insert(tombstone.fact_hash.clone(), tombstone);
+        }
+        store.ingest = self.ingest;
+        let mut decay = DecayState::default();
+        if let Some(through) = self
+            .episodes_decayed_through
+            .as_deref()
+            .and_then(parse_timestamp)
+        {
+            for relation in store.stored_relations() {
+                decay
+                    .episodes
+                    .insert(relation.episode_hash.clone(), through);
+            }
+        }
+        if let Some(through) = self
+            .concepts_decayed_through
+            .as_deref()
+            .and_then(parse_timestamp)
+        {
+            for concept in store.concepts() {
+                decay.concepts.insert(concept.hash.clone(), through);
+            }
+        }
+        for entry in self.episodes_decayed {
+            if let Some(through) = parse_timestamp(&entry.through) {
+                decay.episodes.insert(entry.hash, through);
+            }
+        }
+        for entry in self.concepts_decayed {
+            if let Some(through) = parse_timestamp(&entry.through) {
+                decay.concepts.insert(entry.hash, through);
+            }
+        }
+        store.decay = decay;
+        store
+    }
+}
+
+impl MemoryStore {
+    /// Write the store as a JSON snapshot. Hashes are hex strings.
+    pub fn save_json<W: Write>(&self, writer: W) -> Result<(), SnapshotError> {
+        serde_json::to_writer(writer, &StoreSnapshot::capture(self))?;
+        Ok(())
+    }
+
+    /// Rebuild a store from a snapshot written by [`save_json`](Self::save_json).
+    pub fn load_json<R: Read>(reader: R) -> Result<MemoryStore, SnapshotError> {
+        let snapshot: StoreSnapshot = serde_json::from_reader(reader)?;
+        Ok(snapshot.restore())
+    }
+
+    /// Write the store as a compact binary snapshot.
+    ///
+    /// Layout: [`BINARY_MAGIC`], then [`BINARY_FORMAT_VERSION`] as a
+    /// little-endian `u32`, then the bincode-encoded body. Hashes are
+    /// stored as raw 32-byte arrays rather than hex.
+    pub fn save_binary<W: Write>(&self, mut writer: W) -> Result<(), SnapshotError> {
+        let body = BinarySnapshot::from(StoreSnapshot::capture(self));
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&BINARY_FORMAT_VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut writer, &body)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Rebuild a store from a snapshot written by [`save_binary`](Self::save_binary).
+    ///
+    /// The header is checked before any of the body is decoded: a missing
+    /// magic yields [`SnapshotError::BadMagic`] and an unknown version
+    /// yields [`SnapshotError::UnsupportedVersion`].
+    pub fn load_binary<R: Read>(mut reader: R) -> Result<MemoryStore, SnapshotError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => SnapshotError::BadMagic,
+            _ => SnapshotError::Io(e),
+        })?;
+        if &magic != BINARY_MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        let body: BinarySnapshot = match version {
+            1 => bincode::deserialize_from::<_, BinarySnapshotV1>(reader)?.into(),
+            2 => bincode::deserialize_from::<_, BinarySnapshotV2>(reader)?.into(),
+            3 => bincode::deserialize_from::<_, BinarySnapshotV3>(reader)?.into(),
+            4 => bincode::deserialize_from::<_, BinarySnapshotV4>(reader)?.into(),
+            5 => bincode::deserialize_from::<_, BinarySnapshotV5>(reader)?.into(),
+            6 => bincode::deserialize_from::<_, BinarySnapshotV6>(reader)?.into(),
+            7 => bincode::deserialize_from::<_, BinarySnapshotV7>(reader)?.into(),
+            8 => bincode::deserialize_from::<_, BinarySnapshotV8>(reader)?.into(),
+            9 => bincode::deserialize_from::<_, BinarySnapshotV9>(reader)?.into(),
+            BINARY_FORMAT_VERSION => bincode::deserialize_from(reader)?,
+            _ => {
+                return Err(SnapshotError::UnsupportedVersion {
+                    found: version,
+                    supported: BINARY_FORMAT_VERSION,
+                })
+            }
+        };
+        Ok(StoreSnapshot::from(body).restore())
+    }
+}
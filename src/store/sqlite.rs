@@ -0,0 +1,778 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+
+use super::backend::MemoryBackend;
+use super::graph::{merge_concept, refuse_blank_concepts};
+use crate::compiler::normalizer::{default_normalizer, DefaultNormalizer, Normalizer};
+use crate::time::parse_timestamp;
+use crate::types::*;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS concepts (
+    hash        BLOB PRIMARY KEY,
+    label       TEXT NOT NULL,
+    confidence  REAL NOT NULL,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL,
+    display_label TEXT
+);
+CREATE TABLE IF NOT EXISTS aliases (
+    concept_hash BLOB NOT NULL REFERENCES concepts(hash),
+    position     INTEGER NOT NULL,
+    alias        TEXT NOT NULL,
+    PRIMARY KEY (concept_hash, position)
+);
+CREATE TABLE IF NOT EXISTS labels (
+    label        TEXT PRIMARY KEY,
+    concept_hash BLOB NOT NULL,
+    is_alias     INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS contexts (
+    hash        BLOB PRIMARY KEY,
+    event_time  TEXT NOT NULL,
+    source      TEXT NOT NULL,
+    scope       TEXT NOT NULL,
+    agent_id    TEXT,
+    session_id  TEXT,
+    metadata    TEXT
+);
+CREATE TABLE IF NOT EXISTS relations (
+    seq          INTEGER PRIMARY KEY,
+    episode_hash BLOB NOT NULL UNIQUE,
+    fact_hash    BLOB NOT NULL,
+    subject_hash BLOB NOT NULL,
+    predicate    TEXT NOT NULL,
+    object_hash  BLOB NOT NULL,
+    confidence   REAL NOT NULL,
+    context_hash BLOB NOT NULL,
+    created_at   TEXT NOT NULL,
+    event_order  INTEGER,
+    negated      INTEGER NOT NULL DEFAULT 0,
+    valid_from   TEXT,
+    valid_to     TEXT,
+    spans        TEXT
+);
+CREATE INDEX IF NOT EXISTS relations_by_fact ON relations (fact_hash, seq);
+CREATE INDEX IF NOT EXISTS relations_by_subject ON relations (subject_hash);
+CREATE INDEX IF NOT EXISTS relations_by_object ON relations (object_hash);
+";
+
+/// Relation order within a fact: context event time (NULL, i.e. unknown,
+/// sorts first), then insertion order.
+const CHRONOLOGICAL: &str = "event_order, seq";
+
+const RELATION_COLUMNS: &str = "episode_hash, fact_hash, subject_hash, predicate, object_hash, \
+                                confidence, context_hash, created_at, negated, valid_from, \
+                                valid_to, spans";
+
+/// SQLite-backed store for datasets that do not fit in RAM.
+///
+/// Implements the same [`MemoryBackend`] surface as [`MemoryStore`](super::MemoryStore)
+/// with identical dedup/merge semantics — the concept merge step is the
+/// same function. Hashes are stored as 32-byte blobs; the `labels` table
+/// plays the role of the in-memory `LabelIndex`, and the subject/object
+/// indexes on `relations` replace the adjacency map.
+///
+/// Each `insert_record` call runs in a single transaction.
+pub struct SqliteStore {
+    conn: Connection,
+    normalizer: Arc<dyn Normalizer>,
+}
+
+impl SqliteStore {
+    /// Open (or create) a store at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> rusqlite::Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Open a throwaway store that lives only as long as this value.
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        migrate_event_order(&conn)?;
+        migrate_label_aliases(&conn)?;
+        migrate_polarity(&conn)?;
+        migrate_validity(&conn)?;
+        migrate_spans(&conn)?;
+        migrate_display_label(&conn)?;
+        Ok(Self {
+            conn,
+            normalizer: default_normalizer(),
+        })
+    }
+
+    /// Normalize labels with `normalizer` on insert and lookup, as
+    /// [`MemoryStore::with_normalizer`](super::MemoryStore::with_normalizer)
+    /// does. Labels already in the database keep the form they were
+    /// indexed under, so always open a database with the same normalizer.
+    pub fn with_normalizer(mut self, normalizer: Arc<dyn Normalizer>) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+}
+
+/// Sort key for a context `event_time`: microseconds since the epoch, or
+/// NULL when the time does not parse.
+fn event_order(event_time: &str) -> Option<i64> {
+    parse_timestamp(event_time).map(|t| t.timestamp_micros())
+}
+
+/// Add and backfill `relations.event_order` in databases created before
+/// episodes were ordered chronologically.
+fn migrate_event_order(conn: &Connection) -> rusqlite::Result<()> {
+    if has_column(conn, "relations", "event_order")? {
+        return Ok(());
+    }
+    conn.execute_batch("ALTER TABLE relations ADD COLUMN event_order INTEGER")?;
+    let contexts: Vec<(Vec<u8>, String)> = conn
+        .prepare("SELECT hash, event_time FROM contexts")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let mut update =
+        conn.prepare("UPDATE relations SET event_order = ?1 WHERE context_hash = ?2")?;
+    for (hash, event_time) in contexts {
+        update.execute(params![event_order(&event_time), hash])?;
+    }
+    Ok(())
+}
+
+/// Add `labels.is_alias` and index existing aliases in databases created
+/// before aliases were resolvable by label.
+fn migrate_label_aliases(conn: &Connection) -> rusqlite::Result<()> {
+    if has_column(conn, "labels", "is_alias")? {
+        return Ok(());
+    }
+    conn.execute_batch("ALTER TABLE labels ADD COLUMN is_alias INTEGER NOT NULL DEFAULT 0")?;
+    let aliases: Vec<([u8; 32], String)> = conn
+        .prepare("SELECT concept_hash, alias FROM aliases ORDER BY rowid")?
+        .query_map([], |row| Ok((hash_from_blob(row, 0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (hash, alias) in aliases {
+        // Conflicts were silently unresolvable before; nothing to report.
+        let _ = index_alias(
+            conn,
+            &DefaultNormalizer::default(),
+            &alias,
+            &ConceptHash::from_bytes(hash),
+        )?;
+    }
+    Ok(())
+}
+
+/// Add `relations.negated` in databases created before facts had a
+/// polarity; every existing episode is positive.
+fn migrate_polarity(conn: &Connection) -> rusqlite::Result<()> {
+    if has_column(conn, "relations", "negated")? {
+        return Ok(());
+    }
+    conn.execute_batch("ALTER TABLE relations ADD COLUMN negated INTEGER NOT NULL DEFAULT 0")
+}
+
+/// Add `relations.valid_from` / `valid_to` in databases created before
+/// episodes had a validity period; existing episodes have none.
+fn migrate_validity(conn: &Connection) -> rusqlite::Result<()> {
+    if has_column(conn, "relations", "valid_from")? {
+        return Ok(());
+    }
+    conn.execute_batch(
+        "ALTER TABLE relations ADD COLUMN valid_from TEXT;
+         ALTER TABLE relations ADD COLUMN valid_to TEXT;",
+    )
+}
+
+/// Add `relations.spans` in databases created before episodes carried
+/// utterance spans; existing episodes have none.
+fn migrate_spans(conn: &Connection) -> rusqlite::Result<()> {
+    if has_column(conn, "relations", "spans")? {
+        return Ok(());
+    }
+    conn.execute_batch("ALTER TABLE relations ADD COLUMN spans TEXT")
+}
+
+/// Add `concepts.display_label` in databases created before concepts had
+/// a display form; existing concepts have none.
+fn migrate_display_label(conn: &Connection) -> rusqlite::Result<()> {
+    if has_column(conn, "concepts", "display_label")? {
+        return Ok(());
+    }
+    conn.execute_batch("ALTER TABLE concepts ADD COLUMN display_label TEXT")
+}
+
+/// Subject, object, and evidence span of an episode, stored as one JSON
+/// array in `relations.spans`, or NULL when the episode has none.
+type StoredSpans = (Option<Span>, Option<Span>, Option<Span>);
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> rusqlite::Result<bool> {
+    conn.prepare("SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2")?
+        .exists(params![table, column])
+}
+
+/// Index a primary label, mirroring `LabelIndex::insert`: it displaces
+/// an alias of another concept, and the displaced claim is returned.
+fn index_label(
+    conn: &Connection,
+    normalizer: &dyn Normalizer,
+    label: &str,
+    hash: &ConceptHash,
+) -> rusqlite::Result<Option<AliasConflict>> {
+    let key = normalizer.normalize_label(label);
+    let previous = label_entry(conn, &key)?;
+    conn.prepare_cached(
+        "INSERT OR REPLACE INTO labels (label, concept_hash, is_alias) VALUES (?1, ?2, 0)",
+    )?
+    .execute(params![key, &hash.as_bytes()[..]])?;
+    Ok(match previous {
+        Some((owner, true)) if owner != *hash.as_bytes() => Some(AliasConflict {
+            alias: key,
+            kept: hash.clone(),
+            rejected: ConceptHash::from_bytes(owner),
+        }),
+        _ => None,
+    })
+}
+
+/// Index an alias, mirroring `LabelIndex::insert_alias`: another
+/// concept's primary label, or its alias with a lower hash, wins.
+fn index_alias(
+    conn: &Connection,
+    normalizer: &dyn Normalizer,
+    alias: &str,
+    hash: &ConceptHash,
+) -> rusqlite::Result<Result<(), AliasConflict>> {
+    let key = normalizer.normalize_label(alias);
+    match label_entry(conn, &key)? {
+        Some((owner, _)) if owner == *hash.as_bytes() => return Ok(Ok(())),
+        Some((owner, is_alias)) if !is_alias || &owner < hash.as_bytes() => {
+            return Ok(Err(AliasConflict {
+                alias: key,
+                kept: ConceptHash::from_bytes(owner),
+                rejected: hash.clone(),
+            }))
+        }
+        _ => {}
+    }
+    conn.prepare_cached(
+        "INSERT OR REPLACE INTO labels (label, concept_hash, is_alias) VALUES (?1, ?2, 1)",
+    )?
+    .execute(params![key, &hash.as_bytes()[..]])?;
+    Ok(Ok(()))
+}
+
+/// The concept a normalized label resolves to, and whether it is an alias.
+fn label_entry(conn: &Connection, key: &str) -> rusqlite::Result<Option<([u8; 32], bool)>> {
+    conn.prepare_cached("SELECT concept_hash, is_alias FROM labels WHERE label = ?1")?
+        .query_row(params![key], |row| {
+            Ok((hash_from_blob(row, 0)?, row.get(1)?))
+        })
+        .optional()
+}
+
+fn hash_from_blob(row: &Row<'_>, idx: usize) -> rusqlite::Result<[u8; 32]> {
+    let blob: Vec<u8> = row.get(idx)?;
+    blob.try_into().map_err(|b: Vec<u8>| {
+        rusqlite::Error::FromSqlConversionFailure(
+            idx,
+            Type::Blob,
+            format!("expected 32-byte hash, got {} bytes", b.len()).into(),
+        )
+    })
+}
+
+/// A stored timestamp. Databases written before timestamps were checked may
+/// hold anything, so this reads them through [`EventTime::from_legacy`].
+fn time_from_row(row: &Row<'_>, idx: usize) -> rusqlite::Result<EventTime> {
+    Ok(EventTime::from_legacy(&row.get::<_, String>(idx)?))
+}
+
+fn relation_from_row(row: &Row<'_>) -> rusqlite::Result<RelationNode> {
+    let spans: Option<String> = row.get(11)?;
+    let (subject_span, object_span, evidence_span) = spans
+        .map(|json| serde_json::from_str::<StoredSpans>(&json))
+        .transpose()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(11, Type::Text, e.into()))?
+        .unwrap_or_default();
+    Ok(RelationNode {
+        episode_hash: EpisodeHash::from_bytes(hash_from_blob(row, 0)?),
+        fact_hash: FactHash::from_bytes(hash_from_blob(row, 1)?),
+        subject_hash: ConceptHash::from_bytes(hash_from_blob(row, 2)?),
+        predicate: row.get::<_, String>(3)?.into(),
+        object_hash: ConceptHash::from_bytes(hash_from_blob(row, 4)?),
+        confidence: row.get(5)?,
+        context_hash: ContextHash::from_bytes(hash_from_blob(row, 6)?),
+        created_at: time_from_row(row, 7)?,
+        polarity: if row.get(8)? {
+            Polarity::Negative
+        } else {
+            Polarity::Positive
+        },
+        valid_from: row.get(9)?,
+        valid_to: row.get(10)?,
+        subject_span,
+        object_span,
+        evidence_span,
+    })
+}
+
+fn load_concept(conn: &Connection, hash: &ConceptHash) -> rusqlite::Result<Option<ConceptNode>> {
+    let concept = conn
+        .query_row(
+            "SELECT label, confidence, created_at, updated_at, display_label
+             FROM concepts WHERE hash = ?1",
+            params![&hash.as_bytes()[..]],
+            |row| {
+                Ok(ConceptNode {
+                    hash: hash.clone(),
+                    label: row.get(0)?,
+                    aliases: Vec::new(),
+                    confidence: row.get(1)?,
+                    created_at: time_from_row(row, 2)?,
+                    updated_at: time_from_row(row, 3)?,
+                    display_label: row.get(4)?,
+                })
+            },
+        )
+        .optional()?;
+    let Some(mut concept) = concept else {
+        return Ok(None);
+    };
+    let mut stmt =
+        conn.prepare_cached("SELECT alias FROM aliases WHERE concept_hash = ?1 ORDER BY position")?;
+    concept.aliases = stmt
+        .query_map(params![&hash.as_bytes()[..]], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(Some(concept))
+}
+
+fn write_aliases(tx: &Transaction<'_>, concept: &ConceptNode) -> rusqlite::Result<()> {
+    tx.execute(
+        "DELETE FROM aliases WHERE concept_hash = ?1",
+        params![&concept.hash.as_bytes()[..]],
+    )?;
+    let mut stmt = tx.prepare_cached(
+        "INSERT INTO aliases (concept_hash, position, alias) VALUES (?1, ?2, ?3)",
+    )?;
+    for (position, alias) in concept.aliases.iter().enumerate() {
+        stmt.execute(params![
+            &concept.hash.as_bytes()[..],
+            position as i64,
+            alias
+        ])?;
+    }
+    Ok(())
+}
+
+fn insert_concept(
+    tx: &Transaction<'_>,
+    normalizer: &dyn Normalizer,
+    concept: &ConceptNode,
+    conflicts: &mut Vec<AliasConflict>,
+) -> rusqlite::Result<()> {
+    tx.execute(
+        "INSERT INTO concepts (hash, label, confidence, created_at, updated_at, display_label)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            &concept.hash.as_bytes()[..],
+            concept.label,
+            concept.confidence,
+            concept.created_at.to_string(),
+            concept.updated_at.to_string(),
+            concept.display_label
+        ],
+    )?;
+    conflicts.extend(index_label(tx, normalizer, &concept.label, &concept.hash)?);
+    for alias in &concept.aliases {
+        if let Err(conflict) = index_alias(tx, normalizer, alias, &concept.hash)? {
+            conflicts.push(conflict);
+        }
+    }
+    write_aliases(tx, concept)
+}
+
+fn update_concept(tx: &Transaction<'_>, concept: &ConceptNode) -> rusqlite::Result<()> {
+    tx.execute(
+        "UPDATE concepts SET confidence = ?2, updated_at = ?3, display_label = ?4 WHERE hash = ?1",
+        params![
+            &concept.hash.as_bytes()[..],
+            concept.confidence,
+            concept.updated_at.to_string(),
+            concept.display_label
+        ],
+    )?;
+    write_aliases(tx, concept)
+}
+
+fn insert_relation(tx: &Transaction<'_>, relation: &RelationNode) -> rusqlite::Result<()> {
+    let event_time: Option<String> = tx
+        .query_row(
+            "SELECT event_time FROM contexts WHERE hash = ?1",
+            params![&relation.context_hash.as_bytes()[..]],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let spans: StoredSpans = (
+        relation.subject_span,
+        relation.object_span,
+        relation.evidence_span,
+    );
+    let spans = (spans != StoredSpans::default())
+        .then(|| serde_json::to_string(&spans).expect("spans always serialize"));
+    tx.execute(
+        &format!(
+            "INSERT INTO relations ({}, event_order) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            RELATION_COLUMNS
+        ),
+        params![
+            &relation.episode_hash.as_bytes()[..],
+            &relation.fact_hash.as_bytes()[..],
+            &relation.subject_hash.as_bytes()[..],
+            &*relation.predicate,
+            &relation.object_hash.as_bytes()[..],
+            relation.confidence,
+            &relation.context_hash.as_bytes()[..],
+            relation.created_at.to_string(),
+            relation.polarity == Polarity::Negative,
+            relation.valid_from,
+            relation.valid_to,
+            spans,
+            event_time.as_deref().and_then(event_order)
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_context(tx: &Transaction<'_>, context: &ContextNode) -> rusqlite::Result<()> {
+    let metadata = context
+        .meta
+        .metadata
+        .as_ref()
+        .map(|m| serde_json::to_string(m).expect("string map always serializes"));
+    tx.execute(
+        "INSERT OR IGNORE INTO contexts (hash, event_time, source, scope, agent_id, session_id, metadata)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            &context.hash.as_bytes()[..],
+            context.meta.event_time.to_string(),
+            context.meta.source,
+            context.meta.scope,
+            context.meta.agent_id,
+            context.meta.session_id,
+            metadata
+        ],
+    )?;
+    Ok(())
+}
+
+fn exists(conn: &Connection, sql: &str, key: &[u8]) -> rusqlite::Result<bool> {
+    conn.prepare_cached(sql)?.exists(params![key])
+}
+
+impl MemoryBackend for SqliteStore {
+    type Error = rusqlite::Error;
+
+    fn insert_record(&mut self, mut record: MemoryRecord) -> rusqlite::Result<InsertResult> {
+        let mut result = InsertResult::default();
+        refuse_blank_concepts(&mut record, &mut result);
+        let tx = self.conn.transaction()?;
+
+        // The context goes first: relations are ordered by its event time.
+        insert_context(&tx, &record.context)?;
+        for concept in record.concepts {
+            if let Some(mut existing) = load_concept(&tx, &concept.hash)? {
+                let added: Vec<String> = concept
+                    .aliases
+                    .iter()
+                    .filter(|a| !existing.aliases.contains(a))
+                    .cloned()
+                    .collect();
+                if !merge_concept(&mut existing, concept) {
+                    result.dupes_skipped += 1;
+                    continue;
+                }
+                update_concept(&tx, &existing)?;
+                for alias in added {
+                    match index_alias(&tx, &*self.normalizer, &alias, &existing.hash)? {
+                        Ok(()) => result.merged_aliases.push(MergedAlias {
+                            alias,
+                            label: existing.label.clone(),
+                            hash: existing.hash.clone(),
+                        }),
+                        Err(conflict) => result.alias_conflicts.push(conflict),
+                    }
+                }
+                result.concepts_updated += 1;
+            } else {
+                insert_concept(
+                    &tx,
+                    &*self.normalizer,
+                    &concept,
+                    &mut result.alias_conflicts,
+                )?;
+                result.new_concepts += 1;
+            }
+        }
+
+        for relation in record.relations {
+            if exists(
+                &tx,
+                "SELECT 1 FROM relations WHERE episode_hash = ?1",
+                relation.episode_hash.as_bytes(),
+            )? {
+                result.dupes_skipped += 1;
+                continue;
+            }
+            if !exists(
+                &tx,
+                "SELECT 1 FROM relations WHERE fact_hash = ?1",
+                relation.fact_hash.as_bytes(),
+            )? {
+                result.new_facts += 1;
+            }
+            insert_relation(&tx, &relation)?;
+            result.new_episodes += 1;
+        }
+
+        tx.commit()?;
+        Ok(result)
+    }
+
+    fn get_concept(&self, hash: &ConceptHash) -> rusqlite::Result<Option<ConceptNode>> {
+        load_concept(&self.conn, hash)
+    }
+
+    fn get_concept_by_label(&self, label: &str) -> rusqlite::Result<Option<ConceptNode>> {
+        let hash = self
+            .conn
+            .query_row(
+                "SELECT concept_hash FROM labels WHERE label = ?1",
+                params![self.normalizer.normalize_label(label)],
+                |row| hash_from_blob(row, 0),
+            )
+            .optional()?;
+        match hash {
+            Some(hash) => load_concept(&self.conn, &ConceptHash::from_bytes(hash)),
+            None => Ok(None),
+        }
+    }
+
+    fn get_relations(&self, concept_hash: &ConceptHash) -> rusqlite::Result<Vec<RelationNode>> {
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {} FROM relations WHERE fact_hash IN (
+                 SELECT fact_hash FROM relations WHERE subject_hash = ?1
+                 UNION
+                 SELECT fact_hash FROM relations WHERE object_hash = ?1
+             ) ORDER BY seq",
+            RELATION_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![&concept_hash.as_bytes()[..]], relation_from_row)?;
+        rows.collect()
+    }
+
+    fn get_relations_by_fact(&self, fact_hash: &FactHash) -> rusqlite::Result<Vec<RelationNode>> {
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {} FROM relations WHERE fact_hash = ?1 ORDER BY {}",
+            RELATION_COLUMNS, CHRONOLOGICAL
+        ))?;
+        let rows = stmt.query_map(params![&fact_hash.as_bytes()[..]], relation_from_row)?;
+        rows.collect()
+    }
+
+    fn latest_relation_by_fact(
+        &self,
+        fact_hash: &FactHash,
+    ) -> rusqlite::Result<Option<RelationNode>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM relations WHERE fact_hash = ?1
+                     ORDER BY event_order DESC, seq DESC LIMIT 1",
+                    RELATION_COLUMNS
+                ),
+                params![&fact_hash.as_bytes()[..]],
+                relation_from_row,
+            )
+            .optional()
+    }
+
+    fn get_context(&self, hash: &ContextHash) -> rusqlite::Result<Option<ContextNode>> {
+        self.conn
+            .query_row(
+                "SELECT event_time, source, scope, agent_id, session_id, metadata
+                 FROM contexts WHERE hash = ?1",
+                params![&hash.as_bytes()[..]],
+                |row| {
+                    let metadata: Option<String> = row.get(5)?;
+                    let metadata = metadata
+                        .map(|json| serde_json::from_str::<HashMap<String, String>>(&json))
+                        .transpose()
+                        .map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(5, Type::Text, e.into())
+                        })?;
+                    Ok(ContextNode {
+                        hash: hash.clone(),
+                        meta: ContextMeta {
+                            event_time: time_from_row(row, 0)?,
+                            source: row.get(1)?,
+                            scope: row.get(2)?,
+                            agent_id: row.get(3)?,
+                            session_id: row.get(4)?,
+                            metadata,
+                        },
+                    })
+                },
+            )
+            .optional()
+    }
+
+    fn concept_count(&self) -> rusqlite::Result<usize> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM concepts", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|n| n as usize)
+    }
+
+    fn relation_count(&self) -> rusqlite::Result<usize> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM relations", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|n| n as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opening_an_older_database_backfills_event_order() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&SCHEMA.replace(",\n    event_order  INTEGER", ""))
+            .unwrap();
+        conn.execute(
+            "INSERT INTO contexts (hash, event_time, source, scope) VALUES (?1, ?2, 'test', 'old')",
+            params![&[1u8; 32][..], "2026-02-18T00:00:00Z"],
+        )
+        .unwrap();
+        conn.execute(
+            &format!(
+                "INSERT INTO relations ({}) VALUES (?1, ?2, ?3, 'needs', ?4, 0.9, ?5, '', 0, NULL, NULL, NULL)",
+                RELATION_COLUMNS
+            ),
+            params![
+                &[2u8; 32][..],
+                &[3u8; 32][..],
+                &[4u8; 32][..],
+                &[5u8; 32][..],
+                &[1u8; 32][..]
+            ],
+        )
+        .unwrap();
+
+        let store = SqliteStore::from_connection(conn).unwrap();
+        let order: Option<i64> = store
+            .conn
+            .query_row("SELECT event_order FROM relations", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(order, event_order("2026-02-18T00:00:00Z"));
+        assert!(order.is_some());
+    }
+
+    #[test]
+    fn opening_an_older_database_indexes_aliases() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&SCHEMA.replace(",\n    is_alias     INTEGER NOT NULL DEFAULT 0", ""))
+            .unwrap();
+        conn.execute(
+            "INSERT INTO concepts (hash, label, confidence, created_at, updated_at) \
+             VALUES (?1, 'agent', 0.9, '', '')",
+            params![&[1u8; 32][..]],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO labels (label, concept_hash) VALUES ('agent', ?1)",
+            params![&[1u8; 32][..]],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO aliases (concept_hash, position, alias) VALUES (?1, 0, 'Bot')",
+            params![&[1u8; 32][..]],
+        )
+        .unwrap();
+
+        let store = SqliteStore::from_connection(conn).unwrap();
+        let bot = store.get_concept_by_label("bot").unwrap().unwrap();
+        assert_eq!(bot.hash, ConceptHash::from_bytes([1u8; 32]));
+        assert_eq!(
+            label_entry(&store.conn, "agent").unwrap(),
+            Some(([1u8; 32], false))
+        );
+    }
+
+    #[test]
+    fn opening_an_older_database_reads_episodes_as_positive() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&SCHEMA.replace(",\n    negated      INTEGER NOT NULL DEFAULT 0", ""))
+            .unwrap();
+        conn.execute(
+            "INSERT INTO relations (episode_hash, fact_hash, subject_hash, predicate, \
+             object_hash, confidence, context_hash, created_at) \
+             VALUES (?1, ?2, ?3, 'needs', ?4, 0.9, ?5, '')",
+            params![
+                &[2u8; 32][..],
+                &[3u8; 32][..],
+                &[4u8; 32][..],
+                &[5u8; 32][..],
+                &[1u8; 32][..]
+            ],
+        )
+        .unwrap();
+
+        let store = SqliteStore::from_connection(conn).unwrap();
+        let relations = store
+            .get_relations_by_fact(&FactHash::from_bytes([3u8; 32]))
+            .unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].polarity, Polarity::Positive);
+    }
+
+    #[test]
+    fn opening_an_older_database_reads_episodes_without_validity() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&SCHEMA.replace(
+            ",\n    valid_from   TEXT,\n    valid_to     TEXT,\n    spans        TEXT",
+            "",
+        ))
+        .unwrap();
+        conn.execute(
+            "INSERT INTO relations (episode_hash, fact_hash, subject_hash, predicate, \
+             object_hash, confidence, context_hash, created_at) \
+             VALUES (?1, ?2, ?3, 'lives_in', ?4, 0.9, ?5, '')",
+            params![
+                &[2u8; 32][..],
+                &[3u8; 32][..],
+                &[4u8; 32][..],
+                &[5u8; 32][..],
+                &[1u8; 32][..]
+            ],
+        )
+        .unwrap();
+
+        let store = SqliteStore::from_connection(conn).unwrap();
+        let relations = store
+            .get_relations_by_fact(&FactHash::from_bytes([3u8; 32]))
+            .unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].valid_from, None);
+        assert_eq!(relations[0].valid_to, None);
+        assert_eq!(relations[0].evidence_span, None);
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::graph::MemoryStore;
+use crate::types::*;
+
+/// Running counts of what has been ingested, maintained on every insert.
+///
+/// Counts are cumulative: they record what was fed to the store, so
+/// eviction and forgetting do not lower them. Only newly stored episodes
+/// count; duplicates are skipped. Episodes whose context has no
+/// `agent_id` are left out of `by_agent`. Persisted with store snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestStats {
+    /// Records passed to `insert_record` / `insert_records`.
+    pub records: u64,
+    /// Episodes stored.
+    pub episodes: u64,
+    /// Episodes stored, per context `source`.
+    pub by_source: HashMap<String, u64>,
+    /// Episodes stored, per context `scope`.
+    pub by_scope: HashMap<String, u64>,
+    /// Episodes stored, per context `agent_id`.
+    pub by_agent: HashMap<String, u64>,
+}
+
+/// Add one to `key`'s count, allocating the key only the first time.
+fn bump(counts: &mut HashMap<String, u64>, key: &str) {
+    match counts.get_mut(key) {
+        Some(count) => *count += 1,
+        None => {
+            counts.insert(key.to_string(), 1);
+        }
+    }
+}
+
+impl IngestStats {
+    /// Count one stored episode observed in `meta`, or only in the total
+    /// if its context is unknown.
+    pub(crate) fn count_episode(&mut self, meta: Option<&ContextMeta>) {
+        self.episodes += 1;
+        let Some(meta) = meta else {
+            return;
+        };
+        bump(&mut self.by_source, &meta.source);
+        bump(&mut self.by_scope, &meta.scope);
+        if let Some(agent) = &meta.agent_id {
+            bump(&mut self.by_agent, agent);
+        }
+    }
+}
+
+impl MemoryStore {
+    /// Ingest counters, kept up to date by every insert, so reading them
+    /// never walks the store.
+    pub fn ingest_stats(&self) -> &IngestStats {
+        &self.ingest
+    }
+}
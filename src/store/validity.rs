@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+
+use super::graph::MemoryStore;
+use crate::time::{parse_timestamp, require_timestamp, InvalidTimestamp};
+use crate::types::*;
+
+/// Whether `at` falls in the episode's validity period, `valid_from`
+/// inclusive and `valid_to` exclusive. A missing bound leaves that side
+/// open; a bound that does not parse matches nothing.
+fn valid_at(relation: &RelationNode, at: DateTime<Utc>) -> bool {
+    let started = match &relation.valid_from {
+        None => true,
+        Some(from) => matches!(parse_timestamp(from), Some(from) if from <= at),
+    };
+    let not_ended = match &relation.valid_to {
+        None => true,
+        Some(to) => matches!(parse_timestamp(to), Some(to) if at < to),
+    };
+    started && not_ended
+}
+
+impl MemoryStore {
+    /// Every episode whose fact holds at `timestamp`, according to the
+    /// episode's `valid_from` / `valid_to`. Episodes without bounds are
+    /// valid at all times. Episodes of forgotten facts are skipped.
+    ///
+    /// Validity is checked per episode: it is not part of the `FactHash`,
+    /// so two observations of the same fact may report different periods,
+    /// and only those covering `timestamp` are returned.
+    pub fn get_relations_valid_at(
+        &self,
+        timestamp: &str,
+    ) -> Result<Vec<&RelationNode>, InvalidTimestamp> {
+        let at = require_timestamp(timestamp)?;
+        Ok(self.relations().filter(|r| valid_at(r, at)).collect())
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::runtime_trait::MemoryRuntime;
+
+/// Why a [`MockRuntime`] could not execute a graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockError {
+    /// No response was programmed for the graph with this source hash
+    /// (lowercase hex).
+    NoResponse { graph_hash: String },
+    /// The graph was programmed to fail with this message; see
+    /// [`MockRuntime::with_error`].
+    Programmed(String),
+}
+
+impl fmt::Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MockError::NoResponse { graph_hash } => {
+                write!(f, "no response programmed for graph {}", graph_hash)
+            }
+            MockError::Programmed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for MockError {}
+
+/// One call to [`MockRuntime::execute_graph`](MemoryRuntime::execute_graph).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphCall {
+    pub source: String,
+    pub inputs: HashMap<String, Value>,
+}
+
+/// A [`MemoryRuntime`] for tests, with no 0-openclaw and no tokio.
+///
+/// Hashes with SHA-256 like the real runtimes, keeps state in memory (and
+/// lists it), and
+/// answers `execute_graph` from responses programmed per graph source
+/// (matched by the SHA-256 of the source). Every executed graph and every
+/// saved key is logged, in call order, whether or not the call succeeded.
+///
+/// ```ignore
+/// let runtime = MockRuntime::new().with_response(&graph_text, outputs);
+/// run_feature(&runtime);
+/// assert_eq!(runtime.executed_graphs().len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockRuntime {
+    responses: HashMap<[u8; 32], Result<HashMap<String, Value>, MockError>>,
+    transient: Mutex<HashMap<[u8; 32], (usize, String)>>,
+    state_errors: Mutex<Option<(usize, String)>>,
+    state: Mutex<HashMap<String, Value>>,
+    executed: Mutex<Vec<GraphCall>>,
+    saved: Mutex<Vec<String>>,
+}
+
+impl MockRuntime {
+    /// A runtime with no state and no programmed responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer every execution of `graph_source` with `outputs`.
+    pub fn with_response(mut self, graph_source: &str, outputs: HashMap<String, Value>) -> Self {
+        self.responses.insert(sha256(graph_source), Ok(outputs));
+        self
+    }
+
+    /// Fail every execution of `graph_source` with
+    /// [`MockError::Programmed`].
+    pub fn with_error(mut self, graph_source: &str, message: &str) -> Self {
+        self.responses.insert(
+            sha256(graph_source),
+            Err(MockError::Programmed(message.to_string())),
+        );
+        self
+    }
+
+    /// Fail the first `times` executions of `graph_source` with
+    /// [`MockError::Programmed`], then answer as programmed otherwise, for
+    /// testing retries.
+    pub fn with_transient_error(self, graph_source: &str, times: usize, message: &str) -> Self {
+        lock(&self.transient).insert(sha256(graph_source), (times, message.to_string()));
+        self
+    }
+
+    /// Fail the next `times` state calls, loads and saves alike, with
+    /// [`MockError::Programmed`]. A failed save is still logged.
+    pub fn with_state_errors(self, times: usize, message: &str) -> Self {
+        *lock(&self.state_errors) = Some((times, message.to_string()));
+        self
+    }
+
+    /// Start with `value` stored under `key`. Not logged as a save.
+    pub fn with_state(self, key: &str, value: Value) -> Self {
+        lock(&self.state).insert(key.to_string(), value);
+        self
+    }
+
+    /// Every graph executed so far, in call order.
+    pub fn executed_graphs(&self) -> Vec<GraphCall> {
+        lock(&self.executed).clone()
+    }
+
+    /// Every key saved so far, in call order, repeated if saved again.
+    pub fn saved_keys(&self) -> Vec<String> {
+        lock(&self.saved).clone()
+    }
+
+    /// Forget the call log, keeping state and responses.
+    pub fn clear_log(&self) {
+        lock(&self.executed).clear();
+        lock(&self.saved).clear();
+    }
+}
+
+/// A poisoned lock only means another test thread panicked; the data is
+/// still usable.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn sha256(input: impl AsRef<[u8]>) -> [u8; 32] {
+    Sha256::digest(input).into()
+}
+
+/// Count down a programmed run of failures, failing while it lasts.
+fn take_failure(failures: Option<&mut (usize, String)>) -> Result<(), MockError> {
+    match failures {
+        Some((times, message)) if *times > 0 => {
+            *times -= 1;
+            Err(MockError::Programmed(message.clone()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl MemoryRuntime for MockRuntime {
+    type Value = Value;
+    type Hash = [u8; 32];
+    type Error = MockError;
+
+    fn hash(&self, input: &[u8]) -> [u8; 32] {
+        sha256(input)
+    }
+
+    fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, Value>,
+    ) -> Result<HashMap<String, Value>, MockError> {
+        lock(&self.executed).push(GraphCall {
+            source: graph_source.to_string(),
+            inputs,
+        });
+        let graph_hash = sha256(graph_source);
+        take_failure(lock(&self.transient).get_mut(&graph_hash))?;
+        self.responses.get(&graph_hash).cloned().unwrap_or_else(|| {
+            Err(MockError::NoResponse {
+                graph_hash: hex::encode(graph_hash),
+            })
+        })
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<Value>, MockError> {
+        take_failure(lock(&self.state_errors).as_mut())?;
+        Ok(lock(&self.state).get(key).cloned())
+    }
+
+    fn save_state(&self, key: &str, value: &Value) -> Result<(), MockError> {
+        lock(&self.saved).push(key.to_string());
+        take_failure(lock(&self.state_errors).as_mut())?;
+        lock(&self.state).insert(key.to_string(), value.clone());
+        Ok(())
+    }
+
+    fn list_state_keys(&self, prefix: &str) -> Result<Option<Vec<String>>, MockError> {
+        let mut keys: Vec<String> = lock(&self.state)
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort();
+        Ok(Some(keys))
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Colon-free layouts produced by stripping the colons from an RFC 3339
+/// UTC time (`2026-02-18T000000Z`, optionally with fractional seconds) and
+/// by compact test fixtures (`20260218T000000Z`).
+const COLONLESS_FORMATS: [&str; 2] = ["%Y-%m-%dT%H%M%S%.fZ", "%Y%m%dT%H%M%SZ"];
+
+/// Parse a stored timestamp into UTC for chronological comparison.
+///
+/// Accepts RFC 3339 (`2026-02-18T00:00:00Z`, with any offset) and the
+/// colon-free UTC forms found in older graph text. Returns `None`
+/// for anything else; callers decide whether to skip or reject.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    COLONLESS_FORMATS.iter().find_map(|fmt| {
+        NaiveDateTime::parse_from_str(s, fmt)
+            .ok()
+            .map(|naive| DateTime::from_naive_utc_and_offset(naive, Utc))
+    })
+}
+
+/// A timestamp argument that [`parse_timestamp`] could not read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTimestamp(pub String);
+
+impl fmt::Display for InvalidTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid timestamp {:?} (expected RFC 3339)", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTimestamp {}
+
+/// Like [`parse_timestamp`], but reports the offending input on failure.
+pub fn require_timestamp(s: &str) -> Result<DateTime<Utc>, InvalidTimestamp> {
+    parse_timestamp(s).ok_or_else(|| InvalidTimestamp(s.to_string()))
+}
+
+/// When something happened: a context's `event_time`, a node's
+/// `created_at` / `updated_at`.
+///
+/// Ordered chronologically, whatever offset the timestamp was written
+/// with. Displays and serializes as canonical RFC 3339 in UTC
+/// (`2026-02-18T00:00:00Z`, with fractional seconds only when there are
+/// any), so an already-canonical string round-trips unchanged.
+/// Deserialization accepts what [`parse_timestamp`] reads and rejects
+/// anything else; use [`from_legacy`](Self::from_legacy) or [`lenient`]
+/// for data written before timestamps were checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventTime(DateTime<Utc>);
+
+impl EventTime {
+    /// The Unix epoch, which [`from_legacy`](Self::from_legacy) stands in
+    /// for timestamps it cannot read.
+    pub const EPOCH: EventTime = EventTime(DateTime::<Utc>::UNIX_EPOCH);
+
+    /// The current time.
+    ///
+    /// `SystemTime::now` panics on `wasm32-unknown-unknown`; there, with
+    /// the `wasm` feature, the time comes from JavaScript's `Date.now()`.
+    pub fn now() -> Self {
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        let now =
+            std::time::UNIX_EPOCH + std::time::Duration::from_millis(js_sys::Date::now() as u64);
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+        let now = std::time::SystemTime::now();
+        EventTime(now.into())
+    }
+
+    /// Read a timestamp written before timestamps were checked. Anything
+    /// [`parse_timestamp`] cannot read becomes [`EPOCH`](Self::EPOCH), so
+    /// it sorts before every real time, where stores always put unreadable
+    /// timestamps.
+    pub fn from_legacy(s: &str) -> Self {
+        s.parse().unwrap_or(Self::EPOCH)
+    }
+
+    /// The instant as a chrono value.
+    pub fn as_datetime(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl From<DateTime<Utc>> for EventTime {
+    fn from(instant: DateTime<Utc>) -> Self {
+        EventTime(instant)
+    }
+}
+
+impl FromStr for EventTime {
+    type Err = InvalidTimestamp;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        require_timestamp(s).map(EventTime)
+    }
+}
+
+impl fmt::Display for EventTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+impl Serialize for EventTime {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventTime {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(d)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// An RFC 3339 string, which is what this build writes; the readable
+/// colon-free forms are left out.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for EventTime {
+    fn schema_name() -> String {
+        "EventTime".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, SchemaObject};
+
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("date-time".to_string()),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// Deserialize an [`EventTime`] through [`EventTime::from_legacy`], for
+/// `#[serde(deserialize_with = "zero_memory::time::lenient")]` on fields
+/// fed by older data.
+pub fn lenient<'de, D: Deserializer<'de>>(d: D) -> Result<EventTime, D::Error> {
+    let text = String::deserialize(d)?;
+    Ok(EventTime::from_legacy(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rfc3339_with_offsets() {
+        let utc = parse_timestamp("2026-02-18T00:00:00Z").unwrap();
+        let offset = parse_timestamp("2026-02-18T01:00:00+01:00").unwrap();
+        assert_eq!(utc, offset, "offsets must normalize to the same instant");
+    }
+
+    #[test]
+    fn parses_colonless_forms() {
+        let expected = parse_timestamp("2026-02-18T12:34:56Z").unwrap();
+        assert_eq!(parse_timestamp("2026-02-18T123456Z"), Some(expected));
+        assert_eq!(parse_timestamp("20260218T123456Z"), Some(expected));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+        assert_eq!(parse_timestamp("2026-13-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn comparison_is_chronological_not_lexical() {
+        let earlier = parse_timestamp("2026-02-18T10:00:00+02:00").unwrap();
+        let later = parse_timestamp("2026-02-18T09:00:00Z").unwrap();
+        assert!(earlier < later);
+        assert!("2026-02-18T10:00:00+02:00" > "2026-02-18T09:00:00Z");
+    }
+
+    #[test]
+    fn event_times_render_canonically() {
+        for (input, canonical) in [
+            ("2026-02-18T00:00:00Z", "2026-02-18T00:00:00Z"),
+            ("2026-02-18T01:00:00+01:00", "2026-02-18T00:00:00Z"),
+            ("2026-02-18T00:00:00.5Z", "2026-02-18T00:00:00.500Z"),
+            ("20260218T000000Z", "2026-02-18T00:00:00Z"),
+            ("2026-02-18T000000.500Z", "2026-02-18T00:00:00.500Z"),
+        ] {
+            let time: EventTime = input.parse().unwrap();
+            assert_eq!(time.to_string(), canonical);
+            assert_eq!(canonical.parse::<EventTime>().unwrap(), time);
+        }
+    }
+
+    #[test]
+    fn event_times_order_chronologically() {
+        let earlier: EventTime = "2026-02-18T10:00:00+02:00".parse().unwrap();
+        let later: EventTime = "2026-02-18T09:00:00Z".parse().unwrap();
+        assert!(earlier < later);
+        assert!(EventTime::EPOCH < earlier);
+        assert!(EventTime::now() > later);
+    }
+
+    #[test]
+    fn event_time_serde_is_strict_unless_lenient() {
+        let time: EventTime = serde_json::from_str("\"2026-02-18T00:00:00Z\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&time).unwrap(),
+            "\"2026-02-18T00:00:00Z\""
+        );
+        let err = serde_json::from_str::<EventTime>("\"yesterday\"").unwrap_err();
+        assert!(err.to_string().contains("invalid timestamp"), "{}", err);
+
+        #[derive(Deserialize)]
+        struct Legacy {
+            #[serde(deserialize_with = "lenient")]
+            at: EventTime,
+        }
+        let legacy: Legacy = serde_json::from_str(r#"{"at": "yesterday"}"#).unwrap();
+        assert_eq!(legacy.at, EventTime::EPOCH);
+        assert_eq!(EventTime::from_legacy("20260218T000000Z"), time);
+    }
+}
@@ -1,26 +1,61 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::compiler::hasher;
+pub use crate::compiler::integrity::RecordIssue;
+pub use crate::compiler::merge::MergeError;
+pub use crate::compiler::migrate::MigrationError;
+use crate::compiler::normalizer::{normalize_label, normalize_predicate, AliasTable};
+pub use crate::time::EventTime;
+
 // ---------------------------------------------------------------------------
 // Hex serde helper — serializes [u8; 32] as a hex string for readability
 // ---------------------------------------------------------------------------
 
+/// Human-readable formats (JSON) get a hex string; compact binary formats
+/// (the store's binary snapshot) get the raw 32 bytes, halving their size.
 mod hex_serde {
     use super::*;
 
     pub fn serialize<S: Serializer>(bytes: &[u8; 32], s: S) -> Result<S::Ok, S::Error> {
-        s.serialize_str(&hex::encode(bytes))
+        if s.is_human_readable() {
+            s.serialize_str(&hex::encode(bytes))
+        } else {
+            bytes.serialize(s)
+        }
     }
 
     pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<[u8; 32], D::Error> {
+        if !d.is_human_readable() {
+            return <[u8; 32]>::deserialize(d);
+        }
         let hex_str = String::deserialize(d)?;
-        let bytes = hex::decode(&hex_str).map_err(serde::de::Error::custom)?;
-        let arr: [u8; 32] = bytes
-            .try_into()
-            .map_err(|_| serde::de::Error::custom("expected 32 bytes"))?;
-        Ok(arr)
+        super::parse_hex(&hex_str).map_err(serde::de::Error::custom)
+    }
+
+    /// What the derive cannot see: a hash is a string of exactly 64 hex
+    /// digits in JSON.
+    #[cfg(feature = "schemars")]
+    pub fn schema() -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, Metadata, SchemaObject, StringValidation};
+
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                min_length: Some(64),
+                max_length: Some(64),
+                pattern: Some("^[0-9a-fA-F]{64}$".to_string()),
+            })),
+            metadata: Some(Box::new(Metadata {
+                description: Some("SHA-256 hash, 64 hex digits".to_string()),
+                ..Metadata::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
     }
 }
 
@@ -48,8 +83,163 @@ pub struct EpisodeHash(pub [u8; 32]);
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct ContextHash(pub [u8; 32]);
 
+/// Content address of a whole record.
+/// `RecordHash = sha256(canonical record bytes)`; see
+/// [`hasher::record_hash`](crate::compiler::hasher::record_hash).
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RecordHash(pub [u8; 32]);
+
+/// Why a string or byte slice is not a hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashParseError {
+    /// The hex string holds this many characters rather than 64.
+    InvalidHexLength(usize),
+    /// The byte slice holds this many bytes rather than 32.
+    InvalidByteLength(usize),
+    /// The character at byte offset `index` of the hex string is not a hex
+    /// digit.
+    InvalidCharacter { character: char, index: usize },
+}
+
+impl fmt::Display for HashParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashParseError::InvalidHexLength(len) => {
+                write!(f, "expected 64 hex characters, found {}", len)
+            }
+            HashParseError::InvalidByteLength(len) => {
+                write!(f, "expected 32 bytes, found {}", len)
+            }
+            HashParseError::InvalidCharacter { character, index } => {
+                write!(f, "invalid hex character {:?} at {}", character, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HashParseError {}
+
+/// The 32 bytes written as 64 hex digits in `s`, in either case.
+fn parse_hex(s: &str) -> Result<[u8; 32], HashParseError> {
+    let mut bytes = [0u8; 32];
+    let mut len = 0;
+    for (index, character) in s.char_indices() {
+        let digit = character
+            .to_digit(16)
+            .ok_or(HashParseError::InvalidCharacter { character, index })?;
+        if len < 64 {
+            bytes[len / 2] |= (digit as u8) << if len % 2 == 0 { 4 } else { 0 };
+        }
+        len += 1;
+    }
+    if len != 64 {
+        return Err(HashParseError::InvalidHexLength(len));
+    }
+    Ok(bytes)
+}
+
+/// The first hex characters of a hash, written straight into a formatter;
+/// see the `fmt_short` method of each hash type.
+#[derive(Debug, Clone, Copy)]
+pub struct ShortHex<'a> {
+    bytes: &'a [u8; 32],
+    len: usize,
+}
+
+impl<'a> ShortHex<'a> {
+    /// The first `n` hex characters of `bytes`, or all 64 if `n` is larger.
+    pub fn new(bytes: &'a [u8; 32], n: usize) -> Self {
+        ShortHex {
+            bytes,
+            len: n.min(64),
+        }
+    }
+}
+
+impl fmt::Display for ShortHex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        for i in 0..self.len {
+            let byte = self.bytes[i / 2];
+            let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+            f.write_char(DIGITS[nibble as usize] as char)?;
+        }
+        Ok(())
+    }
+}
+
 macro_rules! impl_hash_type {
     ($t:ty) => {
+        impl $t {
+            /// The all-zero hash. No input hashes to it, so it serves as a
+            /// sentinel, e.g. in tests.
+            pub const ZERO: Self = Self([0; 32]);
+
+            /// Wrap raw hash bytes.
+            pub const fn from_bytes(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+
+            /// The raw hash bytes.
+            pub const fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+
+            /// Unwrap the raw hash bytes.
+            pub const fn into_inner(self) -> [u8; 32] {
+                self.0
+            }
+
+            /// The first `n` hex characters of the hash, or all 64 if `n`
+            /// is larger.
+            pub fn short_hex(&self, n: usize) -> String {
+                self.fmt_short(n).to_string()
+            }
+
+            /// Like [`short_hex`](Self::short_hex), but written straight
+            /// into a formatter, without an intermediate `String`.
+            pub fn fmt_short(&self, n: usize) -> ShortHex<'_> {
+                ShortHex::new(&self.0, n)
+            }
+
+            /// Parse a hash from its 64 hex digits, in either case.
+            pub fn from_hex(s: &str) -> Result<Self, HashParseError> {
+                parse_hex(s).map(Self)
+            }
+        }
+
+        impl std::str::FromStr for $t {
+            type Err = HashParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_hex(s)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $t {
+            type Error = HashParseError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                <[u8; 32]>::try_from(bytes)
+                    .map(Self)
+                    .map_err(|_| HashParseError::InvalidByteLength(bytes.len()))
+            }
+        }
+
+        /// Byte-lexicographic, which is also the order of the hex strings.
+        impl Ord for $t {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
         impl fmt::Debug for $t {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "{}({})", stringify!($t), hex::encode(self.0))
@@ -79,6 +269,17 @@ macro_rules! impl_hash_type {
                 hex_serde::deserialize(d).map(Self)
             }
         }
+
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for $t {
+            fn schema_name() -> String {
+                stringify!($t).to_string()
+            }
+
+            fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                hex_serde::schema()
+            }
+        }
     };
 }
 
@@ -86,25 +287,201 @@ impl_hash_type!(ConceptHash);
 impl_hash_type!(FactHash);
 impl_hash_type!(EpisodeHash);
 impl_hash_type!(ContextHash);
+impl_hash_type!(RecordHash);
 
 // ---------------------------------------------------------------------------
 // Input types
 // ---------------------------------------------------------------------------
 
 /// Input tuple from upstream (LLM or structured source).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SemanticTuple {
     pub subject: String,
     pub predicate: String,
     pub object: String,
     pub confidence: f64,
+    /// Whether the tuple asserts or denies the relation.
+    #[serde(default)]
+    pub polarity: Polarity,
+    /// ISO 8601 time from which the fact holds; `None` if unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    /// ISO 8601 time until which the fact holds; `None` if unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<String>,
+    /// Extra detail about this observation of the fact, keyed by role,
+    /// e.g. `{"location": "Paris", "day": "Tuesday"}` for "Alice met Bob
+    /// in Paris on Tuesday". The compiler reifies them as relations from a
+    /// statement concept minted for the episode; qualifiers are part of
+    /// the `EpisodeHash`, not the `FactHash`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub qualifiers: HashMap<String, String>,
+    /// Where the subject is mentioned in `CompilerInput::utterance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_span: Option<Span>,
+    /// Where the object is mentioned in `CompilerInput::utterance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_span: Option<Span>,
+    /// The part of `CompilerInput::utterance` the tuple was extracted from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_span: Option<Span>,
+}
+
+impl SemanticTuple {
+    /// A positive tuple with nothing else set, or the first reason the
+    /// compiler would reject it: a component that normalizes to nothing,
+    /// or a confidence outside `0.0..=1.0`.
+    pub fn new(
+        subject: &str,
+        predicate: &str,
+        object: &str,
+        confidence: f64,
+    ) -> Result<Self, TupleError> {
+        for (field, blank) in [
+            ("subject", normalize_label(subject).is_empty()),
+            ("predicate", normalize_predicate(predicate).is_empty()),
+            ("object", normalize_label(object).is_empty()),
+        ] {
+            if blank {
+                return Err(TupleError::EmptyField(field));
+            }
+        }
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err(TupleError::InvalidConfidence(confidence));
+        }
+        Ok((subject, predicate, object, confidence).into())
+    }
+
+    /// The labels and predicate the compiler would hash for this tuple,
+    /// resolving labels through `aliases`. Matches compiling with
+    /// [`CompilerOptions`](crate::compiler::CompilerOptions) whose
+    /// `aliases` are these and whose other normalization is the default:
+    /// no stop words, plural folding, predicate aliases, or namespace.
+    pub fn normalized(&self, aliases: &AliasTable) -> NormalizedTuple {
+        NormalizedTuple {
+            subject: aliases.resolve(&self.subject),
+            predicate: normalize_predicate(&self.predicate),
+            object: aliases.resolve(&self.object),
+            polarity: self.polarity,
+        }
+    }
+}
+
+/// A positive tuple with confidence 1.0.
+impl From<(&str, &str, &str)> for SemanticTuple {
+    fn from((subject, predicate, object): (&str, &str, &str)) -> Self {
+        (subject, predicate, object, 1.0).into()
+    }
+}
+
+/// A positive tuple. The confidence is clamped into `0.0..=1.0`, and NaN
+/// becomes 0.0; nothing else is checked, so use [`SemanticTuple::new`] to
+/// catch blank components before compiling.
+impl From<(&str, &str, &str, f64)> for SemanticTuple {
+    fn from((subject, predicate, object, confidence): (&str, &str, &str, f64)) -> Self {
+        SemanticTuple {
+            subject: subject.to_string(),
+            predicate: predicate.to_string(),
+            object: object.to_string(),
+            confidence: if confidence.is_nan() {
+                0.0
+            } else {
+                confidence.clamp(0.0, 1.0)
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Why [`SemanticTuple::new`] rejected a tuple.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TupleError {
+    /// `"subject"`, `"predicate"`, or `"object"` normalizes to nothing.
+    EmptyField(&'static str),
+    /// The confidence is outside `0.0..=1.0`, or NaN.
+    InvalidConfidence(f64),
+}
+
+impl fmt::Display for TupleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TupleError::EmptyField(field) => write!(f, "tuple {} is empty", field),
+            TupleError::InvalidConfidence(value) => {
+                write!(f, "confidence {} is outside 0.0..=1.0", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TupleError {}
+
+/// What the compiler hashes for a tuple; see [`SemanticTuple::normalized`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedTuple {
+    /// The subject label, normalized and alias-resolved.
+    pub subject: String,
+    /// The predicate, normalized.
+    pub predicate: String,
+    /// The object label, normalized and alias-resolved.
+    pub object: String,
+    pub polarity: Polarity,
+}
+
+impl NormalizedTuple {
+    pub fn subject_hash(&self) -> ConceptHash {
+        hasher::concept_hash(&self.subject)
+    }
+
+    pub fn object_hash(&self) -> ConceptHash {
+        hasher::concept_hash(&self.object)
+    }
+
+    pub fn fact_hash(&self) -> FactHash {
+        hasher::fact_hash_with_polarity(&self.subject, &self.predicate, &self.object, self.polarity)
+    }
+}
+
+/// A byte range `start..end` into an utterance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The text this span covers, or `None` if it is reversed, runs past
+    /// the end of `text`, or does not fall on char boundaries.
+    pub fn text<'a>(&self, text: &'a str) -> Option<&'a str> {
+        text.get(self.start..self.end)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Whether a fact asserts its relation ("likes") or denies it ("does not
+/// like"). Part of the fact's identity: the negative fact has a different
+/// `FactHash` than the positive one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Polarity {
+    #[default]
+    Positive,
+    Negative,
 }
 
 /// Metadata about the observation context.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ContextMeta {
-    /// ISO 8601 timestamp of the observed event.
-    pub event_time: String,
+    /// When the observed event happened.
+    pub event_time: EventTime,
     /// Origin of the observation, e.g. `"user_prompt"`, `"observation"`.
     pub source: String,
     /// Scope identifier, e.g. `"conversation_123"`.
@@ -120,8 +497,113 @@ pub struct ContextMeta {
     pub metadata: Option<HashMap<String, String>>,
 }
 
+impl ContextMeta {
+    /// A builder for a context, stamped with the current time unless
+    /// [`ContextMetaBuilder::event_time`] says otherwise.
+    pub fn builder() -> ContextMetaBuilder {
+        ContextMetaBuilder::default()
+    }
+
+    /// A context with just a source and scope, stamped with the current
+    /// time.
+    pub fn now(source: &str, scope: &str) -> Self {
+        ContextMeta {
+            event_time: EventTime::now(),
+            source: source.to_string(),
+            scope: scope.to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        }
+    }
+}
+
+/// Builds a [`ContextMeta`]; see [`ContextMeta::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct ContextMetaBuilder {
+    event_time: Option<EventTime>,
+    source: Option<String>,
+    scope: Option<String>,
+    agent_id: Option<String>,
+    session_id: Option<String>,
+    metadata: Option<HashMap<String, String>>,
+}
+
+impl ContextMetaBuilder {
+    /// When the observed event happened, instead of when `build` runs.
+    pub fn event_time(mut self, event_time: EventTime) -> Self {
+        self.event_time = Some(event_time);
+        self
+    }
+
+    /// Origin of the observation; required.
+    pub fn source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    /// Scope identifier; required.
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
+    pub fn agent_id(mut self, agent_id: &str) -> Self {
+        self.agent_id = Some(agent_id.to_string());
+        self
+    }
+
+    pub fn session_id(mut self, session_id: &str) -> Self {
+        self.session_id = Some(session_id.to_string());
+        self
+    }
+
+    /// Adds one metadata entry, replacing any earlier value for `key`.
+    pub fn metadata_entry(mut self, key: &str, value: &str) -> Self {
+        self.metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// The context, or the first of source and scope that is unset or
+    /// blank; the compiler would reject either.
+    pub fn build(self) -> Result<ContextMeta, ContextMetaError> {
+        let required = |value: Option<String>, field| match value {
+            Some(value) if !value.trim().is_empty() => Ok(value),
+            _ => Err(ContextMetaError::MissingField(field)),
+        };
+        Ok(ContextMeta {
+            source: required(self.source, "source")?,
+            scope: required(self.scope, "scope")?,
+            event_time: self.event_time.unwrap_or_else(EventTime::now),
+            agent_id: self.agent_id,
+            session_id: self.session_id,
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// Why [`ContextMetaBuilder::build`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextMetaError {
+    /// `"source"` or `"scope"` was never set, or set to a blank string.
+    MissingField(&'static str),
+}
+
+impl fmt::Display for ContextMetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextMetaError::MissingField(field) => write!(f, "context {} is missing", field),
+        }
+    }
+}
+
+impl std::error::Error for ContextMetaError {}
+
 /// Full input to the compiler.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompilerInput {
     /// The original utterance (if available).
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -132,6 +614,118 @@ pub struct CompilerInput {
     pub context: ContextMeta,
 }
 
+impl CompilerInput {
+    /// A builder for an input observed in `context`, with no utterance and
+    /// no tuples yet.
+    pub fn builder(context: ContextMeta) -> CompilerInputBuilder {
+        CompilerInputBuilder {
+            input: CompilerInput {
+                utterance: None,
+                tuples: Vec::new(),
+                context,
+            },
+            require_tuples: false,
+            error: None,
+        }
+    }
+}
+
+impl From<(ContextMeta, Vec<SemanticTuple>)> for CompilerInput {
+    fn from((context, tuples): (ContextMeta, Vec<SemanticTuple>)) -> Self {
+        CompilerInput {
+            utterance: None,
+            tuples,
+            context,
+        }
+    }
+}
+
+/// Builds a [`CompilerInput`]; see [`CompilerInput::builder`].
+#[derive(Debug, Clone)]
+pub struct CompilerInputBuilder {
+    input: CompilerInput,
+    require_tuples: bool,
+    /// The first tuple [`tuple`](Self::tuple) rejected, reported by `build`.
+    error: Option<CompilerInputError>,
+}
+
+impl CompilerInputBuilder {
+    pub fn utterance(mut self, utterance: &str) -> Self {
+        self.input.utterance = Some(utterance.to_string());
+        self
+    }
+
+    /// Adds a positive tuple, checked as by [`SemanticTuple::new`]. A
+    /// rejected tuple is not added, and fails `build`.
+    pub fn tuple(mut self, subject: &str, predicate: &str, object: &str, confidence: f64) -> Self {
+        let tuple_index = self.input.tuples.len();
+        match SemanticTuple::new(subject, predicate, object, confidence) {
+            Ok(tuple) => self.input.tuples.push(tuple),
+            Err(error) => {
+                self.error
+                    .get_or_insert(CompilerInputError::InvalidTuple { tuple_index, error });
+            }
+        }
+        self
+    }
+
+    /// Adds tuples as they are, unchecked; the compiler checks them.
+    pub fn tuples(mut self, tuples: impl IntoIterator<Item = SemanticTuple>) -> Self {
+        self.input.tuples.extend(tuples);
+        self
+    }
+
+    /// Makes `build` fail if no tuple was added.
+    pub fn require_tuples(mut self) -> Self {
+        self.require_tuples = true;
+        self
+    }
+
+    /// The input, or the first tuple [`tuple`](Self::tuple) rejected.
+    pub fn build(self) -> Result<CompilerInput, CompilerInputError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.require_tuples && self.input.tuples.is_empty() {
+            return Err(CompilerInputError::NoTuples);
+        }
+        Ok(self.input)
+    }
+}
+
+/// Why [`CompilerInputBuilder::build`] failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompilerInputError {
+    /// The tuple that would have been at `tuple_index` was rejected.
+    InvalidTuple {
+        tuple_index: usize,
+        error: TupleError,
+    },
+    /// No tuple was added, though [`require_tuples`](CompilerInputBuilder::require_tuples)
+    /// asked for one.
+    NoTuples,
+}
+
+impl fmt::Display for CompilerInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompilerInputError::InvalidTuple { tuple_index, error } => {
+                write!(f, "tuple {}: {}", tuple_index, error)
+            }
+            CompilerInputError::NoTuples => write!(f, "no tuples"),
+        }
+    }
+}
+
+impl std::error::Error for CompilerInputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CompilerInputError::InvalidTuple { error, .. } => Some(error),
+            CompilerInputError::NoTuples => None,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Stored node types
 // ---------------------------------------------------------------------------
@@ -142,31 +736,72 @@ pub struct CompilerInput {
 /// should merge rather than discard: take the max `confidence`, union the
 /// `aliases`, and refresh `updated_at`. The initial insert sets `confidence`
 /// to the first-seen value from the originating `SemanticTuple`.
+///
+/// `display_label` is the form to show the concept in (`OpenAI` for the
+/// label `openai`), or `None` when the label is shown as is. The concept
+/// hash is taken from the normalized `label` alone, and the `.0` graph does
+/// not carry the display label. A merge keeps the most recent one.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ConceptNode {
     pub hash: ConceptHash,
     pub label: String,
     pub aliases: Vec<String>,
     pub confidence: f64,
-    pub created_at: String,
-    pub updated_at: String,
+    pub created_at: EventTime,
+    pub updated_at: EventTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_label: Option<String>,
 }
 
 /// A stored relation node.
+///
+/// `valid_from` / `valid_to` bound the period in which the fact holds
+/// ("lives in Berlin" from 2019 to 2023), as opposed to `created_at`, when
+/// it was observed. Like `confidence`, validity belongs to the episode,
+/// not the fact: it is not part of the `FactHash`, so observations that
+/// disagree on the period are episodes of the same fact.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RelationNode {
     pub fact_hash: FactHash,
     pub episode_hash: EpisodeHash,
     pub subject_hash: ConceptHash,
-    pub predicate: String,
+    /// Shared so that stores can intern predicates across episodes.
+    /// Serializes as a plain string.
+    pub predicate: Arc<str>,
     pub object_hash: ConceptHash,
     pub confidence: f64,
     pub context_hash: ContextHash,
-    pub created_at: String,
+    pub created_at: EventTime,
+    #[serde(default)]
+    pub polarity: Polarity,
+    #[serde(default)]
+    pub valid_from: Option<String>,
+    #[serde(default)]
+    pub valid_to: Option<String>,
+    /// Provenance copied from the tuple: byte ranges into the utterance the
+    /// relation was compiled from. See [`evidence_text`](Self::evidence_text).
+    #[serde(default)]
+    pub subject_span: Option<Span>,
+    #[serde(default)]
+    pub object_span: Option<Span>,
+    #[serde(default)]
+    pub evidence_span: Option<Span>,
+}
+
+impl RelationNode {
+    /// The evidence for this relation, sliced out of the utterance it was
+    /// compiled from. `None` if the relation has no evidence span or the
+    /// span does not fit `utterance`.
+    pub fn evidence_text<'a>(&self, utterance: &'a str) -> Option<&'a str> {
+        self.evidence_span?.text(utterance)
+    }
 }
 
 /// A stored context node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ContextNode {
     pub hash: ContextHash,
     pub meta: ContextMeta,
@@ -176,12 +811,102 @@ pub struct ContextNode {
 // Memory record
 // ---------------------------------------------------------------------------
 
+/// Version of the serialized [`MemoryRecord`] layout this build writes.
+/// Bump whenever the layout changes, and teach
+/// [`migrate`](crate::compiler::migrate) to upgrade the previous version.
+///
+/// Version 1 is every record written before the version was recorded,
+/// whose timestamps were unchecked strings.
+pub const RECORD_SCHEMA_VERSION: u32 = 2;
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
 /// A complete memory record produced by the compiler.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MemoryRecord {
+    /// Layout version the record was written with; see
+    /// [`RECORD_SCHEMA_VERSION`]. Not part of the record's content, so
+    /// not part of its hash.
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub concepts: Vec<ConceptNode>,
     pub relations: Vec<RelationNode>,
     pub context: ContextNode,
+    /// Who compiled the record, if it was signed; the same data as the
+    /// proof node of its graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<Proof>,
+    /// Hash of the record before this one in a [`RecordChain`](crate::chain::RecordChain),
+    /// if the record is part of one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev: Option<RecordHash>,
+    /// The namespace the record's concept and fact hashes were computed
+    /// in (see [`CompilerOptions::namespace`](crate::compiler::CompilerOptions::namespace)),
+    /// if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+impl MemoryRecord {
+    /// Content address of the record, the same for the same content in
+    /// any order. See [`hasher::record_hash`](crate::compiler::hasher::record_hash).
+    pub fn hash(&self) -> RecordHash {
+        crate::compiler::hasher::record_hash(self)
+    }
+
+    /// Merkle root over the record's episode hashes. See
+    /// [`merkle::merkle_root`](crate::compiler::merkle::merkle_root).
+    pub fn merkle_root(&self) -> [u8; 32] {
+        crate::compiler::merkle::merkle_root(self)
+    }
+
+    /// Combine this record with `other`, of the same context. See
+    /// [`merge::merge_records`](crate::compiler::merge::merge_records).
+    pub fn merge(self, other: MemoryRecord) -> Result<MemoryRecord, MergeError> {
+        crate::compiler::merge::merge_records(self, other)
+    }
+
+    /// Combine `records`, all of one context, into the first. See
+    /// [`merge::merge_many`](crate::compiler::merge::merge_many).
+    pub fn merge_many(records: Vec<MemoryRecord>) -> Result<MemoryRecord, MergeError> {
+        crate::compiler::merge::merge_many(records)
+    }
+
+    /// Read a record serialized by this or an earlier version of the
+    /// crate, upgrading older layouts. See
+    /// [`migrate::record_from_json`](crate::compiler::migrate::record_from_json).
+    pub fn from_json_with_migration(json: &str) -> Result<MemoryRecord, MigrationError> {
+        crate::compiler::migrate::record_from_json(json)
+    }
+
+    /// Check that the record is internally consistent. See
+    /// [`integrity::check_record`](crate::compiler::integrity::check_record).
+    pub fn validate(&self) -> Result<(), Vec<RecordIssue>> {
+        crate::compiler::integrity::check_record(self)
+    }
+
+    /// The path proving that `episode` is one of the record's relations,
+    /// or `None` if it is not.
+    pub fn merkle_proof(&self, episode: &EpisodeHash) -> Option<crate::compiler::MerkleProof> {
+        crate::compiler::merkle::merkle_proof(self, episode)
+    }
+}
+
+/// The signed proof of a record: its trace hash, signed by its compiler.
+/// All three fields are lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Proof {
+    /// [`trace_hash`](crate::compiler::hasher::trace_hash) of the record.
+    pub trace_hash: String,
+    /// Ed25519 public key of the signer.
+    pub signer: String,
+    /// Ed25519 signature over the record's
+    /// [canonical bytes](crate::compiler::hasher::canonical_record_bytes).
+    pub signature: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -190,11 +915,60 @@ pub struct MemoryRecord {
 
 /// Output of the compiler pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompilerOutput {
     /// The `.0` format graph text.
     pub graph_text: String,
     /// The structured in-memory record.
     pub record: MemoryRecord,
+    /// Tuples dropped because an earlier tuple of the same input compiled
+    /// to the same episode, i.e. the same fact with the same qualifiers;
+    /// the kept relation has the highest confidence and the validity
+    /// period of the first tuple.
+    #[serde(default)]
+    pub duplicates_collapsed: usize,
+    /// Tuples left out because their subject, object, or predicate is
+    /// blank after normalization; each has a warning in `warnings`.
+    #[serde(default)]
+    pub tuples_skipped: usize,
+    /// Problems the compiler tolerated instead of rejecting the input.
+    #[serde(default)]
+    pub warnings: Vec<CompilerWarning>,
+}
+
+/// What [`compile_into`](crate::compiler::compile_into) added to a record.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IncrementalReport {
+    /// Concepts the record did not have before, in label order.
+    pub concepts_added: Vec<ConceptHash>,
+    /// Relation episodes appended to the record, in order.
+    pub relations_added: Vec<EpisodeHash>,
+    /// Tuples dropped because the record, or an earlier tuple of the same
+    /// call, already had their episode; see
+    /// [`CompilerOutput::duplicates_collapsed`].
+    pub duplicates_collapsed: usize,
+    /// See [`CompilerOutput::tuples_skipped`].
+    pub tuples_skipped: usize,
+    pub warnings: Vec<CompilerWarning>,
+}
+
+/// A problem found while compiling that did not stop the compile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CompilerWarning {
+    /// The predicate, normalized and alias-resolved, is not in
+    /// `CompilerOptions::allowed_predicates`.
+    UnknownPredicate {
+        tuple_index: usize,
+        predicate: String,
+    },
+    /// The subject or object label is blank after normalization, so the
+    /// tuple was skipped rather than compiled into a concept that no label
+    /// can find. `field` is `"subject"` or `"object"`.
+    EmptyLabel { tuple_index: usize, field: String },
+    /// The predicate is blank after normalization, so the tuple was
+    /// skipped.
+    EmptyPredicate { tuple_index: usize },
 }
 
 // ---------------------------------------------------------------------------
@@ -202,12 +976,116 @@ pub struct CompilerOutput {
 // ---------------------------------------------------------------------------
 
 /// Result of inserting a `MemoryRecord` into the store.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct InsertResult {
     pub new_concepts: usize,
     pub new_facts: usize,
     pub new_episodes: usize,
+    /// Concepts and episodes the store already held, which the insert
+    /// left exactly as they were.
     pub dupes_skipped: usize,
+    /// Concepts the store already held whose confidence, aliases,
+    /// `updated_at`, or display label the insert changed.
+    pub concepts_updated: usize,
+    /// Facts whose confidence was boosted because they were observed again
+    /// (only when the store has reinforcement enabled, and never for facts
+    /// that stay forgotten).
+    pub reinforced: usize,
+    /// Forgotten facts whose tombstone was lifted because they were
+    /// observed again under [`Reobservation::Resurrect`].
+    pub resurrected: usize,
+    /// Concepts refused because their label is blank: they hash to a
+    /// concept no lookup can find.
+    pub refused_concepts: usize,
+    /// Relations refused because they connect a refused concept.
+    pub refused_relations: usize,
+    /// Aliases that could not be indexed because another concept already
+    /// owns the label.
+    pub alias_conflicts: Vec<AliasConflict>,
+    /// Aliases a re-inserted concept brought to the stored concept, and
+    /// that were indexed. See
+    /// [`AliasTable::learn_merged`](crate::compiler::AliasTable::learn_merged).
+    pub merged_aliases: Vec<MergedAlias>,
+    /// What the store evicted to stay within its limits after this insert.
+    pub evicted: EvictionReport,
+    /// Records a [strict](crate::store::MemoryStore::set_strict) store
+    /// refused because they failed validation.
+    pub rejected: Vec<RejectedRecord>,
+}
+
+/// A record a strict store refused, none of which was stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RejectedRecord {
+    pub hash: RecordHash,
+    /// Everything [`MemoryRecord::validate`] found wrong with it.
+    pub issues: Vec<RecordIssue>,
+}
+
+/// An alias merged into a concept the store already held.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergedAlias {
+    /// The alias, as the incoming concept listed it.
+    pub alias: String,
+    /// Primary label of the stored concept.
+    pub label: String,
+    pub hash: ConceptHash,
+}
+
+/// An alias that two concepts claim. Lookups of `alias` resolve to
+/// `kept`; the claim of `rejected` is not indexed.
+///
+/// A concept's primary label always beats another concept's alias;
+/// between two aliases, the concept with the lower hash wins, so the
+/// outcome does not depend on insertion order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AliasConflict {
+    /// Normalized alias.
+    pub alias: String,
+    pub kept: ConceptHash,
+    pub rejected: ConceptHash,
+}
+
+/// Concepts and episodes removed by the store's eviction policy.
+///
+/// Evicting a concept also evicts every episode that references it, so
+/// `episodes` includes those cascaded removals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EvictionReport {
+    pub concepts: Vec<ConceptHash>,
+    pub episodes: Vec<EpisodeHash>,
+}
+
+impl EvictionReport {
+    pub fn is_empty(&self) -> bool {
+        self.concepts.is_empty() && self.episodes.is_empty()
+    }
+}
+
+/// A concept recalled by label, together with every visible relation
+/// episode that references it.
+#[derive(Debug, Clone)]
+pub struct Recollection {
+    pub concept: ConceptNode,
+    pub relations: Vec<RelationNode>,
+}
+
+/// What happens when a forgotten fact is inserted again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reobservation {
+    /// Lift the tombstone: the fact becomes visible again.
+    Resurrect,
+    /// Keep the tombstone: new episodes are stored but stay hidden.
+    Suppress,
+}
+
+/// Marker recording that a fact was forgotten, and why.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub fact_hash: FactHash,
+    pub reason: String,
+    /// ISO 8601 time the fact was forgotten.
+    pub forgotten_at: String,
+    pub on_reobserve: Reobservation,
 }
 
 #[cfg(test)]
@@ -235,9 +1113,10 @@ mod tests {
     fn semantic_tuple_serialization_roundtrip() {
         let tuple = SemanticTuple {
             subject: "Agent".to_string(),
-            predicate: "needs".to_string(),
+            predicate: "needs".into(),
             object: "LongTermMemory".to_string(),
             confidence: 0.98,
+            ..Default::default()
         };
         let json = serde_json::to_string(&tuple).unwrap();
         let parsed: SemanticTuple = serde_json::from_str(&json).unwrap();
@@ -248,7 +1127,7 @@ mod tests {
     #[test]
     fn context_meta_optional_fields() {
         let meta = ContextMeta {
-            event_time: "2026-02-18T00:00:00Z".to_string(),
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
             source: "user_prompt".to_string(),
             scope: "test".to_string(),
             agent_id: None,
@@ -264,19 +1143,21 @@ mod tests {
     #[test]
     fn memory_record_serialization() {
         let record = MemoryRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
             concepts: vec![ConceptNode {
                 hash: ConceptHash([1; 32]),
                 label: "test".to_string(),
                 aliases: vec![],
                 confidence: 1.0,
-                created_at: "2026-02-18T00:00:00Z".to_string(),
-                updated_at: "2026-02-18T00:00:00Z".to_string(),
+                created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+                updated_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+                display_label: None,
             }],
             relations: vec![],
             context: ContextNode {
                 hash: ContextHash([2; 32]),
                 meta: ContextMeta {
-                    event_time: "2026-02-18T00:00:00Z".to_string(),
+                    event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
                     scope: "unit_test".to_string(),
                     agent_id: None,
@@ -284,6 +1165,9 @@ mod tests {
                     metadata: None,
                 },
             },
+            proof: None,
+            prev: None,
+            namespace: None,
         };
         let json = serde_json::to_string(&record).unwrap();
         let parsed: MemoryRecord = serde_json::from_str(&json).unwrap();
@@ -313,4 +1197,274 @@ mod tests {
         let parsed: FactHash = serde_json::from_str(&json).unwrap();
         assert_eq!(original, parsed);
     }
+
+    #[test]
+    fn hashes_parse_from_hex() {
+        let hash = ConceptHash([0xab; 32]);
+        let lower = hash.to_string();
+        assert_eq!(ConceptHash::from_hex(&lower), Ok(hash.clone()));
+        assert_eq!(
+            lower.to_uppercase().parse::<ConceptHash>(),
+            Ok(hash.clone())
+        );
+        let episode: EpisodeHash = "00ff".repeat(16).parse().unwrap();
+        assert_eq!(episode.0[..2], [0x00, 0xff]);
+    }
+
+    #[test]
+    fn hex_parse_errors() {
+        assert_eq!(
+            FactHash::from_hex("abc"),
+            Err(HashParseError::InvalidHexLength(3))
+        );
+        assert_eq!(
+            FactHash::from_hex(&"a".repeat(66)),
+            Err(HashParseError::InvalidHexLength(66))
+        );
+        assert_eq!(
+            FactHash::from_hex(""),
+            Err(HashParseError::InvalidHexLength(0))
+        );
+        let bad = format!("{}g{}", "a".repeat(10), "a".repeat(53));
+        assert_eq!(
+            ContextHash::from_hex(&bad),
+            Err(HashParseError::InvalidCharacter {
+                character: 'g',
+                index: 10
+            })
+        );
+        assert_eq!(
+            ContextHash::from_hex(&format!("{}é", "a".repeat(62))),
+            Err(HashParseError::InvalidCharacter {
+                character: 'é',
+                index: 62
+            })
+        );
+        assert!(ContextHash::from_hex(&format!(" {}", "a".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn hashes_convert_from_byte_slices() {
+        let bytes = [7u8; 40];
+        assert_eq!(RecordHash::try_from(&bytes[..32]), Ok(RecordHash([7; 32])));
+        assert_eq!(
+            ConceptHash::try_from(&bytes[..]),
+            Err(HashParseError::InvalidByteLength(40))
+        );
+        assert_eq!(
+            ConceptHash::try_from(&bytes[..31]),
+            Err(HashParseError::InvalidByteLength(31))
+        );
+    }
+
+    #[test]
+    fn deserialize_rejects_bad_hex() {
+        let err = serde_json::from_str::<FactHash>("\"abc\"").unwrap_err();
+        assert!(err.to_string().contains("expected 64 hex characters"));
+    }
+
+    #[test]
+    fn hash_bytes_round_trip() {
+        let bytes: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let hash = EpisodeHash::from_bytes(bytes);
+        assert_eq!(hash.as_bytes(), &bytes);
+        assert_eq!(hash.clone().into_inner(), bytes);
+        assert_eq!(hash, EpisodeHash::from_hex(&hex::encode(bytes)).unwrap());
+
+        assert_eq!(ContextHash::ZERO.into_inner(), [0; 32]);
+        assert_eq!(FactHash::ZERO.to_string(), "0".repeat(64));
+        assert!(ConceptHash::ZERO < ConceptHash::from_bytes(bytes));
+        const SENTINEL: RecordHash = RecordHash::from_bytes([0xff; 32]);
+        assert_eq!(SENTINEL.as_bytes(), &[0xff; 32]);
+    }
+
+    #[test]
+    fn hashes_sort_like_their_hex() {
+        let labels = ["agent", "memory", "facts", "tools", "Agent", "", "z"];
+        let mut hashes: Vec<ConceptHash> = labels.iter().map(|l| hasher::concept_hash(l)).collect();
+        hashes.push(ConceptHash([0x00; 32]));
+        hashes.push(ConceptHash([0xff; 32]));
+        let mut hexes: Vec<String> = hashes.iter().map(ToString::to_string).collect();
+
+        hashes.sort();
+        hexes.sort();
+        let sorted: Vec<String> = hashes.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, hexes);
+
+        let facts: std::collections::BTreeSet<FactHash> =
+            hashes.iter().rev().map(|h| FactHash(h.0)).collect();
+        let keys: Vec<String> = facts.iter().map(ToString::to_string).collect();
+        assert_eq!(keys, hexes);
+    }
+
+    #[test]
+    fn context_builder_fills_what_was_set() {
+        let at: EventTime = "2026-02-18T00:00:00Z".parse().unwrap();
+        let meta = ContextMeta::builder()
+            .event_time(at)
+            .source("user_prompt")
+            .scope("s1")
+            .agent_id("agent-7")
+            .metadata_entry("batch", "1")
+            .metadata_entry("batch", "2")
+            .build()
+            .unwrap();
+        assert_eq!(meta.event_time, at);
+        assert_eq!(
+            (meta.source.as_str(), meta.scope.as_str()),
+            ("user_prompt", "s1")
+        );
+        assert_eq!(meta.agent_id.as_deref(), Some("agent-7"));
+        assert_eq!(meta.session_id, None);
+        assert_eq!(
+            meta.metadata,
+            Some(HashMap::from([("batch".to_string(), "2".to_string())]))
+        );
+    }
+
+    #[test]
+    fn context_builder_requires_source_and_scope() {
+        let missing = |builder: ContextMetaBuilder| builder.build().unwrap_err();
+        assert_eq!(
+            missing(ContextMeta::builder().scope("s1")),
+            ContextMetaError::MissingField("source")
+        );
+        assert_eq!(
+            missing(ContextMeta::builder().source("user_prompt").scope("  ")),
+            ContextMetaError::MissingField("scope")
+        );
+        assert_eq!(
+            missing(ContextMeta::builder()).to_string(),
+            "context source is missing"
+        );
+    }
+
+    #[test]
+    fn contexts_default_to_the_current_time() {
+        let before = EventTime::now();
+        let built = ContextMeta::builder()
+            .source("a")
+            .scope("b")
+            .build()
+            .unwrap();
+        let stamped = ContextMeta::now("a", "b");
+        assert!(before <= built.event_time && built.event_time <= stamped.event_time);
+        assert_eq!(
+            (stamped.source.as_str(), stamped.scope.as_str()),
+            ("a", "b")
+        );
+        assert_eq!(stamped.metadata, None);
+    }
+
+    #[test]
+    fn tuple_constructors_reject_what_the_compiler_would() {
+        let tuple = SemanticTuple::new("Agent", "needs", "Memory", 0.9).unwrap();
+        assert_eq!(
+            (
+                tuple.subject.as_str(),
+                tuple.predicate.as_str(),
+                tuple.object.as_str()
+            ),
+            ("Agent", "needs", "Memory")
+        );
+        assert_eq!(tuple.confidence, 0.9);
+        assert_eq!(tuple.polarity, Polarity::Positive);
+
+        let err = |s, p, o, c| SemanticTuple::new(s, p, o, c).unwrap_err();
+        assert_eq!(
+            err(" ", "needs", "b", 0.5),
+            TupleError::EmptyField("subject")
+        );
+        assert_eq!(err("a", " ", "b", 0.5), TupleError::EmptyField("predicate"));
+        assert_eq!(err("a", "needs", "", 0.5), TupleError::EmptyField("object"));
+        assert_eq!(
+            err("a", "needs", "b", 1.5),
+            TupleError::InvalidConfidence(1.5)
+        );
+        assert!(matches!(
+            err("a", "needs", "b", f64::NAN),
+            TupleError::InvalidConfidence(c) if c.is_nan()
+        ));
+    }
+
+    #[test]
+    fn tuple_conversions_clamp_confidence() {
+        let confidence = |c| SemanticTuple::from(("a", "needs", "b", c)).confidence;
+        assert_eq!(SemanticTuple::from(("a", "needs", "b")).confidence, 1.0);
+        assert_eq!(confidence(0.4), 0.4);
+        assert_eq!(confidence(1.5), 1.0);
+        assert_eq!(confidence(-0.1), 0.0);
+        assert_eq!(confidence(f64::NAN), 0.0);
+    }
+
+    #[test]
+    fn normalized_tuples_are_what_gets_hashed() {
+        let mut tuple = SemanticTuple::from(("The Agent", "Depends On", "LTM"));
+        tuple.polarity = Polarity::Negative;
+        let normalized = tuple.normalized(&AliasTable::with_defaults());
+        assert_eq!(normalized.subject, "the agent");
+        assert_eq!(normalized.predicate, "depends_on");
+        assert_eq!(normalized.object, "long-term-memory");
+        assert_eq!(normalized.subject_hash(), hasher::concept_hash("the agent"));
+        assert_eq!(
+            normalized.fact_hash(),
+            hasher::fact_hash_with_polarity(
+                "the agent",
+                "depends_on",
+                "long-term-memory",
+                Polarity::Negative
+            )
+        );
+    }
+
+    #[test]
+    fn input_builder_adds_tuples_in_order() {
+        let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .utterance("Agent needs memory and tools")
+            .tuple("Agent", "needs", "memory", 0.9)
+            .tuples([SemanticTuple::from(("Agent", "needs", "tools"))])
+            .require_tuples()
+            .build()
+            .unwrap();
+        assert_eq!(
+            input.utterance.as_deref(),
+            Some("Agent needs memory and tools")
+        );
+        let objects: Vec<&str> = input.tuples.iter().map(|t| t.object.as_str()).collect();
+        assert_eq!(objects, ["memory", "tools"]);
+        assert_eq!(input.context.scope, "s1");
+    }
+
+    #[test]
+    fn input_builder_names_the_first_rejected_tuple() {
+        let err = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+            .tuple("Agent", "needs", "memory", 0.9)
+            .tuple("Agent", "needs", "", 0.9)
+            .tuple("Agent", "needs", "tools", 2.0)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CompilerInputError::InvalidTuple {
+                tuple_index: 1,
+                error: TupleError::EmptyField("object"),
+            }
+        );
+        assert_eq!(err.to_string(), "tuple 1: tuple object is empty");
+    }
+
+    #[test]
+    fn input_builder_can_require_tuples() {
+        let context = ContextMeta::now("user_prompt", "s1");
+        let builder = CompilerInput::builder(context.clone());
+        assert!(builder.clone().build().unwrap().tuples.is_empty());
+        assert_eq!(
+            builder.require_tuples().build().unwrap_err(),
+            CompilerInputError::NoTuples
+        );
+
+        let input = CompilerInput::from((context, vec![SemanticTuple::from(("a", "b", "c"))]));
+        assert_eq!(input.tuples.len(), 1);
+        assert_eq!(input.utterance, None);
+    }
 }
@@ -0,0 +1,21 @@
+use serde_json::json;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::compiler::emitter::try_compile;
+use crate::types::CompilerInput;
+
+/// Compile a [`CompilerInput`] given as JSON, for JavaScript callers.
+///
+/// Returns the [`CompilerOutput`](crate::types::CompilerOutput) as JSON,
+/// or `{"error": "..."}` if the input is not a `CompilerInput` or the
+/// compiler rejects it. Hashes are the same as on native targets.
+#[wasm_bindgen]
+pub fn compile_json(input_json: &str) -> String {
+    let output = serde_json::from_str::<CompilerInput>(input_json)
+        .map_err(|e| e.to_string())
+        .and_then(|input| try_compile(&input).map_err(|e| e.to_string()));
+    match output {
+        Ok(output) => serde_json::to_string(&output).expect("compiler output serializes to JSON"),
+        Err(message) => json!({ "error": message }).to_string(),
+    }
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "async")]
+
+use zero_memory::compiler::hasher;
+use zero_memory::store::{AsyncMemoryStore, MemoryStore, SharedStore};
+use zero_memory::types::*;
+
+fn observation(subject: &str, predicate: &str, object: &str, scope: &str) -> MemoryRecord {
+    let meta = ContextMeta {
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+        source: "test".to_string(),
+        scope: scope.to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    let ctx = ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    };
+    let concept = |label: &str| ConceptNode {
+        hash: hasher::concept_hash(label),
+        label: label.to_string(),
+        aliases: vec![],
+        confidence: 0.9,
+        created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        updated_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        display_label: None,
+    };
+    let fact = hasher::fact_hash(subject, predicate, object);
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts: vec![concept(subject), concept(object)],
+        relations: vec![RelationNode {
+            episode_hash: hasher::episode_hash(&fact, &ctx.hash),
+            fact_hash: fact,
+            subject_hash: hasher::concept_hash(subject),
+            predicate: predicate.into(),
+            object_hash: hasher::concept_hash(object),
+            confidence: 0.9,
+            context_hash: ctx.hash.clone(),
+            created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+            polarity: Polarity::Positive,
+            valid_from: None,
+            valid_to: None,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        }],
+        context: ctx,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+#[tokio::test]
+async fn insert_and_read_back() {
+    let store: SharedStore = SharedStore::default();
+    let result = store
+        .insert_record(observation("agent", "needs", "memory", "s1"))
+        .await
+        .unwrap();
+    assert_eq!(result.new_concepts, 2);
+
+    let agent = store.get_concept_by_label("Agent").await.unwrap().unwrap();
+    assert_eq!(agent.label, "agent");
+    assert!(store.get_concept(&agent.hash).await.unwrap().is_some());
+    assert_eq!(store.get_relations(&agent.hash).await.unwrap().len(), 1);
+
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    let episodes = store.get_relations_by_fact(&fact).await.unwrap();
+    assert_eq!(episodes.len(), 1);
+    let ctx = store
+        .get_context(&episodes[0].context_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ctx.meta.scope, "s1");
+}
+
+#[tokio::test]
+async fn recall_returns_concept_with_relations() {
+    let store = SharedStore::new(MemoryStore::new());
+    store
+        .insert_record(observation("agent", "needs", "memory", "s1"))
+        .await
+        .unwrap();
+    store
+        .insert_record(observation("agent", "uses", "tools", "s1"))
+        .await
+        .unwrap();
+
+    let recalled = store.recall("agent").await.unwrap().unwrap();
+    assert_eq!(recalled.concept.label, "agent");
+    assert_eq!(recalled.relations.len(), 2);
+    assert!(store.recall("nobody").await.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn cloned_handles_share_one_store_across_tasks() {
+    let store: SharedStore = SharedStore::default();
+
+    let tasks: Vec<_> = (0..16)
+        .map(|i| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .insert_record(observation("agent", "knows", &format!("c{}", i), "s1"))
+                    .await
+                    .unwrap();
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    assert_eq!(store.concept_count().await.unwrap(), 17);
+    assert_eq!(store.relation_count().await.unwrap(), 16);
+}
+
+#[tokio::test]
+async fn guards_expose_store_specific_operations() {
+    let store: SharedStore = SharedStore::default();
+    store
+        .insert_record(observation("agent", "needs", "memory", "s1"))
+        .await
+        .unwrap();
+
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .write()
+        .await
+        .forget_fact(
+            &fact,
+            "test",
+            "2026-03-01T00:00:00Z",
+            Reobservation::Suppress,
+        )
+        .unwrap();
+
+    assert!(store.read().await.is_forgotten(&fact));
+    assert!(store.get_relations_by_fact(&fact).await.unwrap().is_empty());
+}
@@ -0,0 +1,124 @@
+use zero_memory::chain::{verify_chain, ChainError, RecordChain};
+use zero_memory::compiler::decompile;
+use zero_memory::compiler::emitter::{compile, emit_graph_text};
+use zero_memory::types::*;
+
+fn record(scope: &str, object: &str) -> MemoryRecord {
+    let context = ContextMeta::builder()
+        .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope(scope)
+        .build()
+        .unwrap();
+    let input = CompilerInput::builder(context)
+        .tuple("Agent", "needs", object, 0.9)
+        .build()
+        .unwrap();
+    compile(&input).record
+}
+
+fn chain() -> Vec<MemoryRecord> {
+    let records = ["Memory", "Tea", "Sleep", "Code"]
+        .iter()
+        .enumerate()
+        .map(|(i, object)| record(&format!("s{}", i), object));
+    RecordChain::new(records).collect()
+}
+
+#[test]
+fn each_record_points_to_the_one_before() {
+    let chained = chain();
+    assert_eq!(chained[0].prev, None);
+    for pair in chained.windows(2) {
+        assert_eq!(pair[1].prev, Some(pair[0].hash()));
+    }
+    verify_chain(&chained).unwrap();
+}
+
+#[test]
+fn short_chains_verify() {
+    verify_chain(&[]).unwrap();
+    verify_chain(&chain()[..1]).unwrap();
+}
+
+#[test]
+fn continued_chains_link_to_the_head() {
+    let first = chain();
+    let head = first.last().unwrap().hash();
+    let mut links = RecordChain::after(head.clone(), vec![record("s9", "Rest")]);
+    assert_eq!(links.head(), Some(&head));
+
+    let next = links.next().unwrap();
+    assert_eq!(next.prev, Some(head));
+    assert_eq!(links.head(), Some(&next.hash()));
+
+    let mut whole = first;
+    whole.push(next);
+    verify_chain(&whole).unwrap();
+}
+
+#[test]
+fn a_changed_record_breaks_the_link_after_it() {
+    let mut chained = chain();
+    chained[1].relations[0].confidence = 0.5;
+    assert_eq!(
+        verify_chain(&chained),
+        Err(ChainError {
+            index: 2,
+            expected: chained[1].hash(),
+            found: chained[2].prev.clone(),
+        })
+    );
+}
+
+#[test]
+fn dropped_and_reordered_records_break_the_chain() {
+    let mut dropped = chain();
+    dropped.remove(1);
+    assert_eq!(verify_chain(&dropped).unwrap_err().index, 1);
+
+    let mut swapped = chain();
+    swapped.swap(2, 3);
+    assert_eq!(verify_chain(&swapped).unwrap_err().index, 2);
+
+    let mut unlinked = chain();
+    unlinked[3].prev = None;
+    let err = verify_chain(&unlinked).unwrap_err();
+    assert_eq!((err.index, &err.found), (3, &None));
+    assert!(err.to_string().contains("no prev"));
+}
+
+#[test]
+fn the_link_is_part_of_the_hash() {
+    let chained = chain();
+    let mut relinked = chained[2].clone();
+    relinked.prev = Some(chained[0].hash());
+    assert_ne!(relinked.hash(), chained[2].hash());
+
+    let mut unlinked = chained[2].clone();
+    unlinked.prev = None;
+    assert_eq!(unlinked.hash(), record("s2", "Sleep").hash());
+}
+
+#[test]
+fn the_proof_node_carries_the_link() {
+    let chained = chain();
+    let prev = chained[1].prev.clone().unwrap();
+
+    let graph_text = emit_graph_text(&chained[1], &chained[1].context.meta);
+    assert!(graph_text.contains(&format!(r#""prev": "{}""#, prev)));
+    assert_eq!(decompile(&graph_text).unwrap().prev, Some(prev));
+
+    let first = emit_graph_text(&chained[0], &chained[0].context.meta);
+    assert!(!first.contains("\"prev\""));
+    assert_eq!(decompile(&first).unwrap().prev, None);
+}
+
+#[test]
+fn links_survive_json() {
+    let chained = chain();
+    let json = serde_json::to_string(&chained).unwrap();
+    let loaded: Vec<MemoryRecord> = serde_json::from_str(&json).unwrap();
+    verify_chain(&loaded).unwrap();
+    assert!(!serde_json::to_string(&chained[0]).unwrap().contains("prev"));
+}
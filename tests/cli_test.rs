@@ -0,0 +1,330 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use zero_memory::compiler::emitter::compile;
+use zero_memory::types::{CompilerInput, MemoryRecord, SemanticTuple};
+
+const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/input.json");
+
+fn zmem() -> Command {
+    Command::cargo_bin("zmem").unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "zero_memory_cli_test_{}_{}",
+        std::process::id(),
+        name
+    ))
+}
+
+fn stdout(output: &std::process::Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &std::process::Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+fn expected() -> CompilerInput {
+    serde_json::from_str(&fs::read_to_string(INPUT).unwrap()).unwrap()
+}
+
+#[test]
+fn compile_writes_graph_text_to_stdout() {
+    let output = zmem().args(["compile", INPUT]).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), compile(&expected()).graph_text);
+}
+
+#[test]
+fn compile_writes_graph_and_record_files() {
+    let graph = temp_path("out.0");
+    let record_path = temp_path("out.record.json");
+    let aliases = temp_path("aliases.json");
+    fs::write(&aliases, r#"{"Tools": "toolbox"}"#).unwrap();
+
+    let output = zmem()
+        .args(["compile", INPUT, "-o"])
+        .arg(&graph)
+        .arg("--record")
+        .arg(&record_path)
+        .arg("--aliases")
+        .arg(&aliases)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).is_empty());
+
+    let graph_text = fs::read_to_string(&graph).unwrap();
+    let record: MemoryRecord =
+        serde_json::from_str(&fs::read_to_string(&record_path).unwrap()).unwrap();
+    let labels: Vec<&str> = record.concepts.iter().map(|c| c.label.as_str()).collect();
+    // The file's alias, and the default ones (LTM).
+    assert!(labels.contains(&"toolbox"), "{:?}", labels);
+    assert!(labels.contains(&"long-term-memory"), "{:?}", labels);
+    assert_eq!(record.relations.len(), 2);
+    assert!(graph_text.contains("\"toolbox\""));
+
+    for path in [graph, record_path, aliases] {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[test]
+fn no_sanitize_writes_colons() {
+    let output = zmem()
+        .args(["compile", INPUT, "--no-sanitize"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("2026-02-18T00:00:00Z"));
+
+    let output = zmem().args(["compile", INPUT]).output().unwrap();
+    assert!(!stdout(&output).contains("2026-02-18T00:00:00Z"));
+}
+
+#[test]
+fn malformed_json_exits_with_1() {
+    let input = temp_path("malformed.json");
+    fs::write(&input, "{\"tuples\": [").unwrap();
+    let output = zmem().arg("compile").arg(&input).output().unwrap();
+    let _ = fs::remove_file(&input);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).starts_with("zmem: "), "{}", stderr(&output));
+    assert!(stderr(&output).contains("malformed.json"));
+}
+
+#[test]
+fn invalid_tuples_exit_with_1_and_name_the_tuple() {
+    let mut input = expected();
+    input.tuples[1].confidence = 1.5;
+    let path = temp_path("invalid.json");
+    fs::write(&path, serde_json::to_string(&input).unwrap()).unwrap();
+    let output = zmem().arg("compile").arg(&path).output().unwrap();
+    let _ = fs::remove_file(&path);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains("tuple 1: confidence 1.5 is not in [0, 1]"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn unreadable_files_exit_with_2() {
+    let missing = Path::new(INPUT).with_file_name("missing.json");
+    let output = zmem().arg("compile").arg(&missing).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = zmem()
+        .args(["compile", INPUT, "-o"])
+        .arg(temp_path("no/such/dir/out.0"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+const GRAPH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/compiled.0");
+
+#[test]
+fn inspect_summarizes_a_graph() {
+    let output = zmem().args(["inspect", GRAPH]).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let text = stdout(&output);
+    assert!(text.starts_with("kind: graph\n"), "{}", text);
+    assert!(text.contains("\nrelations: 5\n"), "{}", text);
+    assert!(text.contains("\n  source: "), "{}", text);
+    // Unsigned, but the proof node is still shown.
+    assert!(text.contains("\n  signature: pending\n"), "{}", text);
+    assert!(!text.contains("predicates:"), "{}", text);
+}
+
+#[test]
+fn inspect_summarizes_a_record_as_json() {
+    let path = temp_path("inspect.record.json");
+    let record = compile(&expected()).record;
+    fs::write(&path, serde_json::to_string(&record).unwrap()).unwrap();
+    let output = zmem()
+        .arg("inspect")
+        .arg(&path)
+        .arg("--json")
+        .output()
+        .unwrap();
+    let text = zmem().arg("inspect").arg(&path).output().unwrap();
+    let _ = fs::remove_file(&path);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["kind"], "record");
+    assert_eq!(summary["record_hash"], record.hash().to_string());
+    assert_eq!(summary["concepts"], record.concepts.len());
+    assert_eq!(summary["relations"], 2);
+    assert_eq!(summary["context"]["meta"]["scope"], "conversation_42");
+    assert_eq!(summary["predicates"]["needs"], 1);
+    assert_eq!(summary["predicates"]["uses"], 1);
+    assert!(summary["proof"].is_null());
+
+    assert!(stdout(&text).contains("\npredicates:\n  needs: 1\n  uses: 1\n"));
+}
+
+#[test]
+fn inspect_rejects_what_it_cannot_read() {
+    for (name, contents) in [
+        ("garbage.bin", &b"\xff\xfe\x00garbage"[..]),
+        ("notes.txt", b"just some notes"),
+        ("truncated.0", b"Graph { \"name\": \"x\", \"nodes\": ["),
+        ("other.json", b"{\"hello\": \"world\"}"),
+    ] {
+        let path = temp_path(name);
+        fs::write(&path, contents).unwrap();
+        let output = zmem().arg("inspect").arg(&path).output().unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(output.status.code(), Some(1), "{}", name);
+        assert!(stderr(&output).contains(name), "{}", stderr(&output));
+        assert!(!stderr(&output).contains("panicked"), "{}", stderr(&output));
+    }
+
+    let output = zmem()
+        .arg("inspect")
+        .arg(temp_path("missing.0"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+fn record_file(name: &str, input: &CompilerInput) -> PathBuf {
+    let path = temp_path(name);
+    let record = compile(input).record;
+    fs::write(&path, serde_json::to_string(&record).unwrap()).unwrap();
+    path
+}
+
+fn store(path: &Path, args: &[&str]) -> std::process::Output {
+    zmem()
+        .arg("store")
+        .arg("--path")
+        .arg(path)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn store_add_query_and_stats() {
+    for name in ["store.json", "store.bin"] {
+        let path = temp_path(name);
+        let first = record_file("first.record.json", &expected());
+        let mut input = expected();
+        input.tuples = vec![SemanticTuple::from(("LTM", "needs", "Storage", 0.6))];
+        let second = record_file("second.record.json", &input);
+
+        let output = store(&path, &["add", first.to_str().unwrap()]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert!(stdout(&output).starts_with("new concepts: 3\nnew facts: 2\n"));
+        let output = store(&path, &["add", second.to_str().unwrap()]);
+        assert!(
+            stdout(&output).contains("new facts: 1\n"),
+            "{}",
+            stdout(&output)
+        );
+        // Adding a record twice stores nothing new.
+        let output = store(&path, &["add", first.to_str().unwrap()]);
+        assert!(stdout(&output).contains("new episodes: 0\n"));
+
+        let output = store(&path, &["query", "--label", "agent"]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let lines: Vec<&str> = stdout(&output).lines().collect();
+        assert_eq!(lines.len(), 2, "{:?}", lines);
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("agent needs long-term-memory\t")));
+
+        let output = store(&path, &["query", "--label", "Agent", "--depth", "2"]);
+        let text = stdout(&output);
+        assert_eq!(text.lines().count(), 3, "{}", text);
+        assert!(text.contains("long-term-memory needs storage\tconfidence 0.6\t"));
+
+        let output = store(
+            &path,
+            &[
+                "query",
+                "--label",
+                "agent",
+                "--depth",
+                "2",
+                "--predicate",
+                "Uses",
+            ],
+        );
+        assert_eq!(stdout(&output).lines().count(), 1, "{}", stdout(&output));
+
+        let output = store(&path, &["query", "--label", "nobody"]);
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = store(&path, &["stats"]);
+        let text = stdout(&output);
+        assert!(
+            text.starts_with("concepts: 4\nfacts: 3\nrelations: 3\n"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("by scope:\n  conversation_42: 3\n"),
+            "{}",
+            text
+        );
+
+        for path in [path, first, second] {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[test]
+fn store_add_rejects_inconsistent_records() {
+    let path = temp_path("inconsistent.json");
+    let record = temp_path("inconsistent.record.json");
+    let mut dangling = compile(&expected()).record;
+    dangling.concepts.remove(0);
+    fs::write(&record, serde_json::to_string(&dangling).unwrap()).unwrap();
+
+    let output = store(&path, &["add", record.to_str().unwrap()]);
+    let _ = fs::remove_file(&record);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains("invalid record: "),
+        "{}",
+        stderr(&output)
+    );
+    assert!(!path.exists(), "nothing is saved");
+}
+
+#[test]
+fn missing_stores_are_empty_and_corrupt_ones_are_rejected() {
+    let path = temp_path("never-created.json");
+    let output = store(&path, &["stats"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("concepts: 0\n"));
+    assert!(!path.exists());
+
+    let path = temp_path("corrupt.bin");
+    fs::write(&path, b"not a snapshot").unwrap();
+    let record = record_file("corrupt.record.json", &expected());
+    let output = store(&path, &["add", record.to_str().unwrap()]);
+    // The corrupt store is left as it was.
+    assert_eq!(fs::read(&path).unwrap(), b"not a snapshot");
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(&record);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("bad magic"), "{}", stderr(&output));
+}
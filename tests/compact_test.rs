@@ -0,0 +1,154 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::hasher::short_hex;
+use zero_memory::format::{emit_compact, parse_compact, CompactError, CompactRecord};
+use zero_memory::types::*;
+
+fn tuple(subject: &str, predicate: &str, object: &str, confidence: f64) -> SemanticTuple {
+    SemanticTuple::from((subject, predicate, object, confidence))
+}
+
+fn input(tuples: Vec<SemanticTuple>) -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples,
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn concept(label: &str, hash: [u8; 32]) -> ConceptNode {
+    ConceptNode {
+        hash: ConceptHash::from_bytes(hash),
+        label: label.to_string(),
+        aliases: vec![],
+        confidence: 1.0,
+        created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        updated_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        display_label: None,
+    }
+}
+
+#[test]
+fn relations_are_one_line_each_after_the_legend() {
+    let record = compile(&input(vec![tuple("Agent", "needs", "Memory", 0.98)])).record;
+    let agent = record.concepts[0].hash.short_hex(8);
+    let memory = record.concepts[1].hash.short_hex(8);
+    let context = record.context.hash.short_hex(8);
+
+    let mut legend = [format!("{} agent", agent), format!("{} memory", memory)];
+    legend.sort();
+    let expected = format!(
+        "# legend\n{}\n@{} s1\n# relations\n{} needs {} @{} (0.98)\n",
+        legend.join("\n"),
+        context,
+        agent,
+        memory,
+        context
+    );
+    assert_eq!(emit_compact(&record), expected);
+}
+
+#[test]
+fn parsing_round_trips() {
+    let mut dislikes = tuple("Alice", "likes", "Mushrooms", 0.7);
+    dislikes.polarity = Polarity::Negative;
+    let record = compile(&input(vec![
+        tuple("Alice", "met", "Bob", 0.9),
+        dislikes,
+        tuple("Alice", "lives in", "Lisbon Old Town", 1.0),
+    ]))
+    .record;
+
+    let text = emit_compact(&record);
+    let parsed = parse_compact(&text).unwrap();
+    assert_eq!(parsed, CompactRecord::from(&record));
+    assert_eq!(parsed.to_string(), text);
+
+    let labels: Vec<&str> = parsed.concepts.values().map(String::as_str).collect();
+    assert!(labels.contains(&"lisbon old town"));
+    let negative = parsed
+        .relations
+        .iter()
+        .find(|r| r.polarity == Polarity::Negative)
+        .unwrap();
+    assert_eq!(negative.predicate, "likes");
+    assert_eq!(parsed.concepts[&negative.object], "mushrooms");
+}
+
+#[test]
+fn colliding_prefixes_are_lengthened() {
+    let mut record = compile(&input(vec![tuple("Agent", "needs", "Memory", 0.98)])).record;
+    let mut a = [0xabu8; 32];
+    let mut b = [0xabu8; 32];
+    a[5] = 0x01;
+    b[5] = 0x02;
+    record.concepts = vec![concept("agent", a), concept("memory", b)];
+    record.relations[0].subject_hash = ConceptHash::from_bytes(a);
+    record.relations[0].object_hash = ConceptHash::from_bytes(b);
+
+    let parsed = parse_compact(&emit_compact(&record)).unwrap();
+    let ids: Vec<&str> = parsed.concepts.keys().map(String::as_str).collect();
+    assert_eq!(ids, [short_hex(&a, 12), short_hex(&b, 12)]);
+    assert_eq!(parsed.relations[0].subject, short_hex(&a, 12));
+
+    // The context does not collide, so it keeps 8 characters.
+    assert_eq!(parsed.contexts.keys().next().unwrap().len(), 8);
+}
+
+#[test]
+fn empty_records_have_only_a_legend() {
+    let record = compile(&input(vec![])).record;
+    let parsed = parse_compact(&emit_compact(&record)).unwrap();
+    assert!(parsed.concepts.is_empty());
+    assert!(parsed.relations.is_empty());
+    assert_eq!(parsed.contexts.values().next().unwrap(), "s1");
+}
+
+#[test]
+fn unknown_hashes_are_rejected() {
+    let text = "# legend\na1b2c3d4 agent\n@c9d0e1f2 s1\n# relations\na1b2c3d4 needs e5f6a7b8 @c9d0e1f2 (0.98)\n";
+    assert_eq!(
+        parse_compact(text),
+        Err(CompactError::UnknownHash {
+            line: 5,
+            hash: "e5f6a7b8".to_string(),
+        })
+    );
+}
+
+#[test]
+fn malformed_lines_are_reported_by_number() {
+    let cases = [
+        ("a1b2c3d4 agent\n", 1),
+        ("# legend\nagent\n# relations\n", 2),
+        ("# legend\nXYZ agent\n# relations\n", 2),
+        ("# legend\na1 agent\na1 other\n# relations\n", 3),
+        (
+            "# legend\na1 agent\n@c9 s1\n# relations\na1 needs a1 @c9 0.9\n",
+            5,
+        ),
+        (
+            "# legend\na1 agent\n@c9 s1\n# relations\na1 needs a1 c9 (0.9)\n",
+            5,
+        ),
+        ("# legend\na1 agent\n@c9 s1\n# relations\na1 needs a1\n", 5),
+    ];
+    for (text, line) in cases {
+        match parse_compact(text) {
+            Err(CompactError::Malformed { line: found, .. }) => {
+                assert_eq!(found, line, "{:?}", text)
+            }
+            other => panic!("expected Malformed for {:?}, got {:?}", text, other),
+        }
+    }
+    assert_eq!(
+        parse_compact("# legend\na1 agent\n"),
+        Err(CompactError::Truncated)
+    );
+}
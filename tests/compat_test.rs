@@ -84,51 +84,12 @@ fn canonical_files_are_aggregate_free() {
     assert!(example.contains("MergeMap"));
 }
 
-/// Round-trip test: compile a CompilerInput → emit .0 graph text →
-/// parse through 0-openclaw → execute.
-///
-/// This is the critical integration gap identified in code review (X3/F5).
-/// It verifies the full pipeline end-to-end through the actual runtime.
-#[tokio::test]
-async fn compiler_output_parses_and_executes() {
-    use zero_memory::compiler::emitter::compile;
-    use zero_memory::types::{CompilerInput, ContextMeta, SemanticTuple};
-
-    let input = CompilerInput {
-        utterance: Some("Test round-trip".to_string()),
-        tuples: vec![
-            SemanticTuple {
-                subject: "Agent".to_string(),
-                predicate: "needs".to_string(),
-                object: "Memory".to_string(),
-                confidence: 0.95,
-            },
-            SemanticTuple {
-                subject: "0-memory".to_string(),
-                predicate: "provides".to_string(),
-                object: "Memory".to_string(),
-                confidence: 0.90,
-            },
-        ],
-        context: ContextMeta {
-            event_time: "20260218T000000Z".to_string(),
-            source: "unit_test".to_string(),
-            scope: "round_trip_test".to_string(),
-            agent_id: None,
-            session_id: None,
-            metadata: None,
-        },
-    };
-
-    let output = compile(&input);
-    assert!(
-        output.graph_text.contains("MergeMap"),
-        "Compiler output must use MergeMap"
-    );
-
+/// Parse compiler-emitted graph text through 0-openclaw and execute it,
+/// panicking with the graph text on failure.
+async fn parse_and_execute(graph_text: &str) {
     // This parse will fail if the emitter has escaping bugs (B1),
     // colon issues (B2/X1), or CreateMap API mismatches (B3/X2).
-    let parse_result = parse_graph(&output.graph_text);
+    let parse_result = parse_graph(graph_text);
     match parse_result {
         Ok(graph) => {
             assert!(!graph.nodes.is_empty(), "Parsed graph must have nodes");
@@ -147,7 +108,7 @@ async fn compiler_output_parses_and_executes() {
                 Err(e) => {
                     panic!(
                         "Compiler-emitted graph parsed but failed to execute: {}\n\nGraph text:\n{}",
-                        e, output.graph_text
+                        e, graph_text
                     );
                 }
             }
@@ -155,8 +116,78 @@ async fn compiler_output_parses_and_executes() {
         Err(e) => {
             panic!(
                 "Compiler-emitted .0 text failed to parse through 0-openclaw: {}\n\nGraph text:\n{}",
-                e, output.graph_text
+                e, graph_text
             );
         }
     }
 }
+
+fn round_trip_input() -> zero_memory::types::CompilerInput {
+    use zero_memory::types::{CompilerInput, ContextMeta, SemanticTuple};
+
+    CompilerInput {
+        utterance: Some("Test round-trip: agents need memory".to_string()),
+        tuples: vec![
+            SemanticTuple::from(("Agent", "needs", "Memory", 0.95)),
+            SemanticTuple::from(("0-memory", "provides", "Memory", 0.90)),
+            // Colons in values must survive the parser's key-quoting regex.
+            SemanticTuple::from((
+                "0-memory",
+                "documented_at",
+                "https://example.com/0-memory",
+                0.80,
+            )),
+        ],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "unit_test".to_string(),
+            scope: "round_trip_test".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+/// Round-trip test: compile a CompilerInput → emit .0 graph text →
+/// parse through 0-openclaw → execute.
+///
+/// This is the critical integration gap identified in code review (X3/F5).
+/// It verifies the full pipeline end-to-end through the actual runtime.
+#[tokio::test]
+async fn compiler_output_parses_and_executes() {
+    use zero_memory::compiler::emitter::compile;
+
+    let output = compile(&round_trip_input());
+    assert!(
+        output.graph_text.contains("MergeMap"),
+        "Compiler output must use MergeMap"
+    );
+    for field in ["aliases", "confidence", "created_at", "updated_at"] {
+        assert!(
+            output
+                .graph_text
+                .contains(&format!(r#""params": {{ "field": "{}" }}"#, field)),
+            "Concept maps must carry {}",
+            field
+        );
+    }
+    assert!(
+        output.graph_text.contains(r#""id": "utterance""#),
+        "Compiler output must carry the utterance"
+    );
+
+    parse_and_execute(&output.graph_text).await;
+}
+
+#[tokio::test]
+async fn compiler_output_without_utterance_parses_and_executes() {
+    use zero_memory::compiler::emitter::compile;
+
+    let mut input = round_trip_input();
+    input.utterance = None;
+    let output = compile(&input);
+    assert!(!output.graph_text.contains(r#""id": "utterance""#));
+
+    parse_and_execute(&output.graph_text).await;
+}
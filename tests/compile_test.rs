@@ -1,38 +1,33 @@
-use zero_memory::compiler::emitter::compile;
-use zero_memory::types::{CompilerInput, ContextMeta, SemanticTuple};
+use std::sync::Arc;
+
+use zero_memory::compiler::emitter::{
+    compile, compile_batch, compile_into, compile_with, emit_graph_text_with, try_compile,
+    try_compile_with,
+};
+use zero_memory::compiler::{
+    decompile, hasher, CaseMode, CompilerError, CompilerOptions, DefaultNormalizer, GraphMeta,
+    PluralFolder, PredicateCheck, StopWords, SymbolStripper,
+};
+use zero_memory::store::MemoryStore;
+use zero_memory::types::{
+    CompilerInput, CompilerWarning, ContextHash, ContextMeta, EpisodeHash, FactHash, Polarity,
+    SemanticTuple,
+};
 
 fn sample_input() -> CompilerInput {
-    CompilerInput {
-        utterance: Some("Agent needs long-term memory, 0-memory solves it with 0-lang".to_string()),
-        tuples: vec![
-            SemanticTuple {
-                subject: "Agent".to_string(),
-                predicate: "needs".to_string(),
-                object: "LongTermMemory".to_string(),
-                confidence: 0.98,
-            },
-            SemanticTuple {
-                subject: "0-memory".to_string(),
-                predicate: "solves".to_string(),
-                object: "LongTermMemory".to_string(),
-                confidence: 0.97,
-            },
-            SemanticTuple {
-                subject: "0-memory".to_string(),
-                predicate: "compiled_with".to_string(),
-                object: "0-lang".to_string(),
-                confidence: 0.99,
-            },
-        ],
-        context: ContextMeta {
-            event_time: "2026-02-18T00:00:00Z".to_string(),
-            source: "user_prompt".to_string(),
-            scope: "0-memory_design".to_string(),
-            agent_id: None,
-            session_id: None,
-            metadata: None,
-        },
-    }
+    let context = ContextMeta::builder()
+        .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope("0-memory_design")
+        .build()
+        .unwrap();
+    CompilerInput::builder(context)
+        .utterance("Agent needs long-term memory, 0-memory solves it with 0-lang")
+        .tuple("Agent", "needs", "LongTermMemory", 0.98)
+        .tuple("0-memory", "solves", "LongTermMemory", 0.97)
+        .tuple("0-memory", "compiled_with", "0-lang", 0.99)
+        .build()
+        .unwrap()
 }
 
 #[test]
@@ -105,7 +100,7 @@ fn compile_graph_text_is_valid_structure() {
 fn compile_context_node_is_populated() {
     let output = compile(&sample_input());
     assert_eq!(
-        output.record.context.meta.event_time,
+        output.record.context.meta.event_time.to_string(),
         "2026-02-18T00:00:00Z"
     );
     assert_eq!(output.record.context.meta.source, "user_prompt");
@@ -116,13 +111,15 @@ fn compile_context_node_is_populated() {
 fn compile_relations_have_valid_hashes() {
     let output = compile(&sample_input());
     for rel in &output.record.relations {
-        assert_ne!(rel.fact_hash.0, [0u8; 32], "FactHash must not be zero");
+        assert_ne!(rel.fact_hash, FactHash::ZERO, "FactHash must not be zero");
         assert_ne!(
-            rel.episode_hash.0, [0u8; 32],
+            rel.episode_hash,
+            EpisodeHash::ZERO,
             "EpisodeHash must not be zero"
         );
         assert_ne!(
-            rel.context_hash.0, [0u8; 32],
+            rel.context_hash,
+            ContextHash::ZERO,
             "ContextHash must not be zero"
         );
     }
@@ -139,3 +136,760 @@ fn compile_all_relations_share_same_context_hash() {
         );
     }
 }
+
+#[test]
+fn compile_with_applies_custom_aliases_to_subjects_and_objects() {
+    let input = CompilerInput {
+        tuples: vec![
+            SemanticTuple::from(("K8s", "runs", "containers", 0.9)),
+            SemanticTuple::from(("0-memory", "deployed_on", "k8s", 0.8)),
+        ],
+        ..sample_input()
+    };
+    let mut options = CompilerOptions::default();
+    options.aliases.insert("k8s", "kubernetes");
+
+    let default_output = compile(&input);
+    let custom_output = compile_with(&input, &options);
+
+    let kubernetes = hasher::concept_hash("kubernetes");
+    assert!(!default_output
+        .record
+        .concepts
+        .iter()
+        .any(|c| c.hash == kubernetes));
+    assert!(custom_output
+        .record
+        .concepts
+        .iter()
+        .any(|c| c.hash == kubernetes && c.label == "kubernetes"));
+    assert_eq!(custom_output.record.relations[0].subject_hash, kubernetes);
+    assert_eq!(custom_output.record.relations[1].object_hash, kubernetes);
+
+    // "k8s" is still written as an alias; only the labels change.
+    let k8s_label = r#""type": "Constant", "value": "k8s""#;
+    assert!(default_output.graph_text.contains(k8s_label));
+    assert!(!custom_output.graph_text.contains(k8s_label));
+    assert!(custom_output
+        .graph_text
+        .contains(r#""type": "Constant", "value": "kubernetes""#));
+}
+
+#[test]
+fn compile_matches_compile_with_default_options() {
+    let input = sample_input();
+    let plain = compile(&input);
+    let with_defaults = compile_with(&input, &CompilerOptions::default());
+    assert_eq!(plain.graph_text, with_defaults.graph_text);
+}
+
+#[test]
+fn try_compile_accepts_valid_input() {
+    let input = sample_input();
+    let output = try_compile(&input).unwrap();
+    assert_eq!(output.graph_text, compile(&input).graph_text);
+}
+
+#[test]
+fn try_compile_rejects_blank_labels() {
+    let mut input = sample_input();
+    input.tuples[1].object = "  ".to_string();
+    assert_eq!(
+        try_compile(&input).unwrap_err(),
+        CompilerError::EmptyLabel {
+            tuple_index: 1,
+            field: "object"
+        }
+    );
+
+    input.tuples[0].subject = String::new();
+    assert_eq!(
+        try_compile(&input).unwrap_err(),
+        CompilerError::EmptyLabel {
+            tuple_index: 0,
+            field: "subject"
+        },
+        "the first offending tuple is reported"
+    );
+}
+
+#[test]
+fn try_compile_rejects_blank_predicate() {
+    let mut input = sample_input();
+    input.tuples[2].predicate = " ".to_string();
+    let err = try_compile(&input).unwrap_err();
+    assert_eq!(err, CompilerError::EmptyPredicate { tuple_index: 2 });
+    assert_eq!(err.to_string(), "tuple 2: predicate is empty");
+}
+
+#[test]
+fn compile_skips_tuples_with_blank_labels_or_predicates() {
+    let mut input = sample_input();
+    input.tuples[1].object = "  ".to_string();
+    input.tuples[2].predicate = "\t".to_string();
+    input
+        .tuples
+        .push(SemanticTuple::from((" \u{a0} ", "needs", "memory", 0.9)));
+
+    let output = compile(&input);
+    assert_eq!(output.tuples_skipped, 3);
+    assert_eq!(
+        output.warnings,
+        vec![
+            CompilerWarning::EmptyLabel {
+                tuple_index: 1,
+                field: "object".to_string(),
+            },
+            CompilerWarning::EmptyPredicate { tuple_index: 2 },
+            CompilerWarning::EmptyLabel {
+                tuple_index: 3,
+                field: "subject".to_string(),
+            },
+        ]
+    );
+    let labels: Vec<&str> = output
+        .record
+        .concepts
+        .iter()
+        .map(|c| c.label.as_str())
+        .collect();
+    assert_eq!(labels, ["agent", "longtermmemory"]);
+    assert_eq!(output.record.relations.len(), 1);
+
+    let mut record = compile(&sample_input()).record;
+    let report = compile_into(
+        &mut record,
+        &sample_input().context,
+        &input.tuples[1..2],
+        &CompilerOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(report.tuples_skipped, 1);
+    assert!(report.concepts_added.is_empty());
+    assert!(report.relations_added.is_empty());
+}
+
+#[test]
+fn try_compile_rejects_invalid_confidence() {
+    for value in [-0.1, 1.5, f64::NAN, f64::INFINITY] {
+        let mut input = sample_input();
+        input.tuples[1].confidence = value;
+        match try_compile(&input) {
+            Err(CompilerError::InvalidConfidence {
+                tuple_index: 1,
+                value: found,
+            }) => assert!(found.to_bits() == value.to_bits()),
+            other => panic!("expected InvalidConfidence for {}, got {:?}", value, other),
+        }
+    }
+}
+
+#[test]
+fn try_compile_rejects_blank_context_fields() {
+    let mut input = sample_input();
+    input.context.scope = " ".to_string();
+    let err = try_compile(&input).unwrap_err();
+    assert_eq!(err, CompilerError::EmptyContextField { field: "scope" });
+    assert_eq!(err.to_string(), "context scope is empty");
+}
+
+#[test]
+fn compile_collapses_duplicate_tuples_keeping_max_confidence() {
+    let mut input = sample_input();
+    for (subject, confidence) in [("agent", 0.5), ("AGENT ", 0.99), ("Agent", 0.7)] {
+        input
+            .tuples
+            .push(SemanticTuple::from((subject, "Uses", "tools", confidence)));
+    }
+
+    let output = compile(&input);
+    assert_eq!(output.duplicates_collapsed, 2);
+    assert_eq!(output.record.relations.len(), 4);
+
+    let fact = hasher::fact_hash("agent", "uses", "tools");
+    let uses: Vec<_> = output
+        .record
+        .relations
+        .iter()
+        .filter(|r| r.fact_hash == fact)
+        .collect();
+    assert_eq!(uses.len(), 1);
+    assert_eq!(uses[0].confidence, 0.99);
+    assert_eq!(
+        output.graph_text.matches("\"predicate\":\"uses\"").count(),
+        1
+    );
+}
+
+#[test]
+fn compile_without_duplicates_collapses_nothing() {
+    assert_eq!(compile(&sample_input()).duplicates_collapsed, 0);
+}
+
+#[test]
+fn compile_keeps_rewritten_labels_as_aliases() {
+    let input = CompilerInput {
+        tuples: vec![
+            SemanticTuple::from(("Agent", "needs", "Long_Term_Memory", 0.9)),
+            SemanticTuple::from(("agent", "prefers", "LTM", 0.8)),
+            SemanticTuple::from(("Agent", "uses", "long-term-memory", 0.8)),
+        ],
+        ..sample_input()
+    };
+    let output = compile(&input);
+    let concept = |label: &str| {
+        output
+            .record
+            .concepts
+            .iter()
+            .find(|c| c.label == label)
+            .unwrap()
+    };
+
+    assert_eq!(concept("agent").aliases, vec!["Agent".to_string()]);
+    assert_eq!(
+        concept("long-term-memory").aliases,
+        vec![
+            "Long_Term_Memory".to_string(),
+            "LTM".to_string(),
+            "ltm".to_string()
+        ],
+        "raw and pre-alias forms are kept once each, never the label itself"
+    );
+}
+
+#[test]
+fn compile_leaves_canonical_labels_without_aliases() {
+    let input = CompilerInput {
+        tuples: vec![SemanticTuple::from(("agent", "needs", "memory", 0.9))],
+        ..sample_input()
+    };
+    let output = compile(&input);
+    assert!(output.record.concepts.iter().all(|c| c.aliases.is_empty()));
+}
+
+#[test]
+fn compile_with_predicate_aliases_unifies_synonymous_facts() {
+    let tuple = |predicate: &str| SemanticTuple::from(("Agent", predicate, "memory", 0.9));
+    let input = CompilerInput {
+        tuples: vec![tuple("needs"), tuple("Requires"), tuple("must have")],
+        ..sample_input()
+    };
+
+    let default_output = compile(&input);
+    assert_eq!(default_output.record.relations.len(), 3);
+
+    let mut options = CompilerOptions::default();
+    options.predicate_aliases.insert("requires", "needs");
+    options.predicate_aliases.insert("must have", "needs");
+    let output = compile_with(&input, &options);
+
+    let needs = hasher::fact_hash("agent", "needs", "memory");
+    assert_eq!(output.record.relations.len(), 1);
+    assert_eq!(output.duplicates_collapsed, 2);
+    assert_eq!(output.record.relations[0].fact_hash, needs);
+    assert_eq!(&*output.record.relations[0].predicate, "needs");
+}
+
+#[test]
+fn compile_with_stop_words_unifies_labels_with_articles() {
+    let tuple = |object: &str| SemanticTuple::from(("Agent", "queries", object, 0.9));
+    let input = CompilerInput {
+        tuples: vec![tuple("The Database"), tuple("database"), tuple("the")],
+        ..sample_input()
+    };
+
+    let default_output = compile(&input);
+    assert_eq!(default_output.record.relations.len(), 3);
+
+    let options = CompilerOptions {
+        stop_words: StopWords::with_defaults(),
+        ..CompilerOptions::default()
+    };
+    let output = compile_with(&input, &options);
+    assert_eq!(output.record.relations.len(), 2);
+    assert_eq!(output.duplicates_collapsed, 1);
+
+    let database = output
+        .record
+        .concepts
+        .iter()
+        .find(|c| c.label == "database")
+        .unwrap();
+    assert_eq!(database.hash, hasher::concept_hash("database"));
+    assert_eq!(
+        database.aliases,
+        vec!["The Database".to_string(), "the database".to_string()]
+    );
+    assert!(output.record.concepts.iter().any(|c| c.label == "the"));
+}
+
+#[test]
+fn compile_with_plurals_unifies_singular_and_plural_labels() {
+    let tuple = |subject: &str, object: &str| SemanticTuple::from((subject, "needs", object, 0.9));
+    let input = CompilerInput {
+        tuples: vec![
+            tuple("Agents", "Memories"),
+            tuple("agent", "memory"),
+            tuple("Bus", "Lens"),
+        ],
+        ..sample_input()
+    };
+
+    assert_eq!(compile(&input).record.relations.len(), 3);
+
+    let options = CompilerOptions {
+        plurals: Some(PluralFolder::new()),
+        ..CompilerOptions::default()
+    };
+    let output = compile_with(&input, &options);
+    assert_eq!(output.record.relations.len(), 2);
+    assert_eq!(output.duplicates_collapsed, 1);
+
+    let agent = output
+        .record
+        .concepts
+        .iter()
+        .find(|c| c.label == "agent")
+        .unwrap();
+    assert_eq!(agent.hash, hasher::concept_hash("agent"));
+    assert_eq!(
+        agent.aliases,
+        vec!["Agents".to_string(), "agents".to_string()]
+    );
+    for label in ["memory", "bus", "lens"] {
+        assert!(output.record.concepts.iter().any(|c| c.label == label));
+    }
+}
+
+#[test]
+fn compile_with_preserved_case_keeps_labels_as_written() {
+    let tuple = |subject: &str, object: &str| SemanticTuple::from((subject, "Needs", object, 0.9));
+    let input = CompilerInput {
+        tuples: vec![tuple("Agent", "LTM"), tuple("agent", "ltm")],
+        ..sample_input()
+    };
+
+    assert_eq!(compile(&input).record.relations.len(), 1);
+
+    let options = CompilerOptions {
+        normalizer: Arc::new(DefaultNormalizer {
+            case: CaseMode::Preserve,
+            ..DefaultNormalizer::default()
+        }),
+        ..CompilerOptions::default()
+    };
+    let output = compile_with(&input, &options);
+    assert_eq!(output.record.relations.len(), 2);
+    let labels: Vec<&str> = output
+        .record
+        .concepts
+        .iter()
+        .map(|c| c.label.as_str())
+        .collect();
+    assert!(labels.contains(&"Agent"));
+    assert!(labels.contains(&"agent"));
+    // Aliases match in any case; predicates are folded regardless.
+    assert!(labels.contains(&"long-term-memory"));
+    assert!(!labels.contains(&"LTM"));
+    assert_eq!(
+        output.record.relations[0].fact_hash,
+        hasher::fact_hash("Agent", "needs", "long-term-memory")
+    );
+}
+
+#[test]
+fn compile_keeps_the_form_an_alias_was_matched_in() {
+    let input = CompilerInput {
+        tuples: vec![SemanticTuple::from(("Agent", "uses", "The LTM", 0.9))],
+        ..sample_input()
+    };
+    let options = CompilerOptions {
+        stop_words: StopWords::with_defaults(),
+        ..CompilerOptions::default()
+    };
+    let output = compile_with(&input, &options);
+    let memory = output
+        .record
+        .concepts
+        .iter()
+        .find(|c| c.label == "long-term-memory")
+        .unwrap();
+    assert_eq!(
+        memory.aliases,
+        vec![
+            "The LTM".to_string(),
+            "the ltm".to_string(),
+            "ltm".to_string()
+        ]
+    );
+}
+
+#[test]
+fn compile_keeps_the_display_form_of_labels() {
+    let tuple = |subject: &str, object: &str| SemanticTuple::from((subject, "uses", object, 0.9));
+    let input = CompilerInput {
+        tuples: vec![
+            tuple("  OpenAi ", "LTM"),
+            tuple("OpenAI", "agent"),
+            tuple("openai", "Long-Term-Memory"),
+        ],
+        ..sample_input()
+    };
+    let record = compile(&input).record;
+    let display = |label: &str| {
+        let concept = record.concepts.iter().find(|c| c.label == label).unwrap();
+        assert_eq!(concept.hash, hasher::concept_hash(label));
+        concept.display_label.as_deref()
+    };
+
+    assert_eq!(display("openai"), Some("OpenAI"));
+    assert_eq!(display("agent"), None);
+    assert_eq!(display("long-term-memory"), Some("Long-Term-Memory"));
+
+    let plain = CompilerInput {
+        tuples: vec![tuple("OpenAI", "LTM")],
+        ..sample_input()
+    };
+    let record = compile(&plain).record;
+    let memory = record
+        .concepts
+        .iter()
+        .find(|c| c.label == "long-term-memory")
+        .unwrap();
+    assert_eq!(memory.display_label, None, "an alias is not a display form");
+}
+
+#[test]
+fn compile_with_symbol_stripping_unifies_chat_labels() {
+    let tuple = |object: &str| SemanticTuple::from(("Agent", "likes", object, 0.9));
+    let input = CompilerInput {
+        tuples: vec![
+            tuple("coffee \u{2615}!"),
+            tuple("Coffee"),
+            tuple("0-memory?"),
+            tuple("\u{2615}"),
+        ],
+        ..sample_input()
+    };
+    let options = CompilerOptions {
+        normalizer: Arc::new(DefaultNormalizer {
+            symbols: Some(SymbolStripper::new()),
+            ..DefaultNormalizer::default()
+        }),
+        ..CompilerOptions::default()
+    };
+    let record = compile_with(&input, &options).record;
+
+    let mut labels: Vec<&str> = record.concepts.iter().map(|c| c.label.as_str()).collect();
+    labels.sort();
+    assert_eq!(labels, ["0-memory", "agent", "coffee", "\u{2615}"]);
+
+    let coffee = record
+        .concepts
+        .iter()
+        .find(|c| c.label == "coffee")
+        .unwrap();
+    assert_eq!(coffee.hash, hasher::concept_hash("coffee"));
+    assert!(coffee.aliases.contains(&"coffee \u{2615}!".to_string()));
+    assert_eq!(coffee.display_label.as_deref(), Some("Coffee"));
+    assert_eq!(record.relations.len(), 3, "both coffees are one fact");
+}
+
+#[test]
+fn alias_tables_learn_what_the_store_merges() {
+    let tuple = |object: &str| SemanticTuple::from(("Agent", "uses", object, 0.9));
+    let input = |object: &str| CompilerInput {
+        tuples: vec![tuple(object)],
+        ..sample_input()
+    };
+
+    // Another agent knows "LT Mem" as an alias; this one does not.
+    let mut theirs = CompilerOptions::default();
+    theirs.aliases.insert("LT Mem", "long-term-memory");
+    let mut ours = CompilerOptions::default();
+
+    let mut store = MemoryStore::new();
+    store.insert_record(compile_with(&input("LTM"), &ours).record);
+    let result = store.insert_record(compile_with(&input("LT Mem"), &theirs).record);
+    assert!(result
+        .merged_aliases
+        .iter()
+        .any(|merged| merged.alias == "LT Mem" && merged.label == "long-term-memory"));
+
+    assert_eq!(ours.aliases.resolve("LT Mem"), "lt mem");
+    assert!(ours.aliases.learn_merged(&result) > 0);
+    assert_eq!(ours.aliases.resolve("LT Mem"), "long-term-memory");
+    assert_eq!(
+        compile_with(&input("LT Mem"), &ours).record.relations[0].object_hash,
+        hasher::concept_hash("long-term-memory")
+    );
+    assert_eq!(ours.aliases.learn_merged(&result), 0);
+}
+
+fn vocabulary_input() -> CompilerInput {
+    let tuple = |predicate: &str| SemanticTuple::from(("Car", predicate, "Wheel", 0.9));
+    CompilerInput {
+        tuples: vec![tuple("Has Part"), tuple("likes"), tuple("is a")],
+        ..sample_input()
+    }
+}
+
+fn vocabulary_options(check: PredicateCheck) -> CompilerOptions {
+    CompilerOptions {
+        allowed_predicates: Some(["has_part", "is_a"].iter().map(|p| p.to_string()).collect()),
+        predicate_check: check,
+        ..CompilerOptions::default()
+    }
+}
+
+#[test]
+fn try_compile_rejects_predicates_outside_vocabulary() {
+    let err = try_compile_with(
+        &vocabulary_input(),
+        &vocabulary_options(PredicateCheck::Reject),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        CompilerError::UnknownPredicate {
+            tuple_index: 1,
+            predicate: "likes".to_string()
+        }
+    );
+    assert!(err.to_string().starts_with("tuple 1:"));
+}
+
+#[test]
+fn vocabulary_check_runs_after_alias_resolution() {
+    let mut options = vocabulary_options(PredicateCheck::Reject);
+    options.predicate_aliases.insert("likes", "is a");
+    let output = try_compile_with(&vocabulary_input(), &options).unwrap();
+    assert!(output.warnings.is_empty());
+}
+
+#[test]
+fn warn_mode_keeps_tuples_outside_vocabulary() {
+    let output = try_compile_with(
+        &vocabulary_input(),
+        &vocabulary_options(PredicateCheck::Warn),
+    )
+    .unwrap();
+    assert_eq!(output.record.relations.len(), 3);
+    assert_eq!(
+        output.warnings,
+        vec![CompilerWarning::UnknownPredicate {
+            tuple_index: 1,
+            predicate: "likes".to_string()
+        }]
+    );
+}
+
+#[test]
+fn lenient_compile_warns_about_predicates_outside_vocabulary() {
+    let output = compile_with(
+        &vocabulary_input(),
+        &vocabulary_options(PredicateCheck::Reject),
+    );
+    assert_eq!(output.record.relations.len(), 3);
+    assert_eq!(output.warnings.len(), 1);
+}
+
+#[test]
+fn compile_keeps_negative_facts_apart_from_positive_ones() {
+    let tuple = |polarity| SemanticTuple {
+        subject: "User".to_string(),
+        predicate: "likes".to_string(),
+        object: "Mushrooms".to_string(),
+        confidence: 0.9,
+        polarity,
+        ..Default::default()
+    };
+    let input = CompilerInput {
+        tuples: vec![tuple(Polarity::Positive), tuple(Polarity::Negative)],
+        ..sample_input()
+    };
+    let output = compile(&input);
+
+    assert_eq!(output.duplicates_collapsed, 0);
+    let relations = &output.record.relations;
+    assert_eq!(relations.len(), 2);
+    assert_eq!(relations[0].polarity, Polarity::Positive);
+    assert_eq!(
+        relations[0].fact_hash,
+        hasher::fact_hash("user", "likes", "mushrooms")
+    );
+    assert_eq!(relations[1].polarity, Polarity::Negative);
+    assert_eq!(
+        relations[1].fact_hash,
+        hasher::fact_hash_with_polarity("user", "likes", "mushrooms", Polarity::Negative)
+    );
+    assert!(output.graph_text.contains("\"polarity\":\"positive\""));
+    assert!(output.graph_text.contains("\"polarity\":\"negative\""));
+}
+
+#[test]
+fn tuples_without_polarity_parse_as_positive() {
+    let tuple: SemanticTuple = serde_json::from_str(
+        r#"{"subject": "user", "predicate": "likes", "object": "tea", "confidence": 0.8}"#,
+    )
+    .unwrap();
+    assert_eq!(tuple.polarity, Polarity::Positive);
+
+    let tuple: SemanticTuple = serde_json::from_str(
+        r#"{"subject": "user", "predicate": "likes", "object": "tea", "confidence": 0.8,
+            "polarity": "negative"}"#,
+    )
+    .unwrap();
+    assert_eq!(tuple.polarity, Polarity::Negative);
+}
+
+#[test]
+fn compile_batch_matches_single_compiles() {
+    let mut inputs = Vec::new();
+    for (i, scope) in ["s1", "s2", "s3"].iter().enumerate() {
+        let mut input = sample_input();
+        input.context.scope = scope.to_string();
+        input.tuples.truncate(3 - i);
+        inputs.push(input);
+    }
+    inputs.push(CompilerInput {
+        tuples: vec![],
+        ..sample_input()
+    });
+    let mut options = CompilerOptions::default();
+    options.aliases.insert("ltm", "long-term memory");
+
+    let batch = compile_batch(&inputs, &options);
+
+    assert_eq!(batch.len(), inputs.len());
+    for (input, output) in inputs.iter().zip(&batch) {
+        let single = compile_with(input, &options);
+        assert_eq!(
+            serde_json::to_string(output).unwrap(),
+            serde_json::to_string(&single).unwrap()
+        );
+    }
+    assert!(compile_batch(&[], &options).is_empty());
+}
+
+#[test]
+fn compile_into_matches_a_single_compile() {
+    let input = sample_input();
+    let options = CompilerOptions::default();
+    let mut record = compile_with(
+        &CompilerInput {
+            tuples: input.tuples[..1].to_vec(),
+            ..input.clone()
+        },
+        &options,
+    )
+    .record;
+
+    let report = compile_into(&mut record, &input.context, &input.tuples[1..], &options).unwrap();
+
+    let single = compile_with(&input, &options);
+    assert_eq!(
+        serde_json::to_string(&record).unwrap(),
+        serde_json::to_string(&single.record).unwrap()
+    );
+    let graph_text = emit_graph_text_with(
+        &record,
+        &input.context,
+        input.utterance.as_deref(),
+        options.sanitize,
+        &options.graph_meta,
+    )
+    .unwrap();
+    assert_eq!(graph_text, single.graph_text);
+    let mut added = report.concepts_added.clone();
+    added.sort_by_key(|h| h.to_string());
+    let mut expected = vec![
+        hasher::concept_hash("0-memory"),
+        hasher::concept_hash("0-lang"),
+    ];
+    expected.sort_by_key(|h| h.to_string());
+    assert_eq!(added, expected);
+    assert_eq!(report.relations_added.len(), 2);
+    assert_eq!(report.duplicates_collapsed, 0);
+}
+
+#[test]
+fn compile_into_collapses_episodes_already_in_the_record() {
+    let input = sample_input();
+    let options = CompilerOptions::default();
+    let mut record = compile(&input).record;
+    let mut repeat = input.tuples[0].clone();
+    repeat.confidence = 0.5;
+
+    let report = compile_into(&mut record, &input.context, &[repeat], &options).unwrap();
+
+    assert!(report.concepts_added.is_empty());
+    assert!(report.relations_added.is_empty());
+    assert_eq!(report.duplicates_collapsed, 1);
+    assert_eq!(record.relations.len(), 3);
+    assert_eq!(record.relations[0].confidence, 0.98);
+}
+
+#[test]
+fn compile_into_rejects_another_context() {
+    let input = sample_input();
+    let mut record = compile(&input).record;
+    let before = serde_json::to_string(&record).unwrap();
+    let mut other = input.context.clone();
+    other.scope = "elsewhere".to_string();
+
+    let err = compile_into(
+        &mut record,
+        &other,
+        &input.tuples,
+        &CompilerOptions::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        CompilerError::ContextMismatch {
+            expected: hasher::context_hash(&input.context),
+            found: hasher::context_hash(&other),
+        }
+    );
+    assert_eq!(serde_json::to_string(&record).unwrap(), before);
+}
+
+#[test]
+fn default_graph_name_carries_the_scope() {
+    let output = compile(&sample_input());
+    assert!(output
+        .graph_text
+        .contains(r#""name": "zero_memory_compiled__0-memory_design","#));
+    assert!(output
+        .graph_text
+        .contains(r#""description": "Compiled memory graph from 0-memory","#));
+    assert!(output
+        .graph_text
+        .contains(r#""metadata": { "author": "0-memory", "tags": ["memory","compiled"] }"#));
+}
+
+#[test]
+fn graph_meta_sets_the_header() {
+    let options = CompilerOptions {
+        graph_meta: GraphMeta {
+            name: Some("nightly_import".to_string()),
+            description: r#"Import of the "notes" folder: part 1"#.to_string(),
+            tags: vec!["import".to_string(), "a\"b".to_string()],
+            author: "ingest-bot".to_string(),
+        },
+        ..CompilerOptions::default()
+    };
+    let output = compile_with(&sample_input(), &options);
+    let text = &output.graph_text;
+
+    assert!(text.contains(r#""name": "nightly_import","#));
+    assert!(text.contains(r#""description": "Import of the \"notes\" folder\u003a part 1","#));
+    assert!(text.contains(r#""metadata": { "author": "ingest-bot", "tags": ["import","a\"b"] }"#));
+    assert_eq!(
+        decompile(text).unwrap().relations.len(),
+        output.record.relations.len()
+    );
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "io")]
+
+use std::fs::File;
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::io::{read_csv, read_csv_with, CsvColumns, IngestError};
+use zero_memory::types::ContextMeta;
+
+fn context() -> ContextMeta {
+    ContextMeta {
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+        source: "spreadsheet".to_string(),
+        scope: "analyst".to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    }
+}
+
+#[test]
+fn fixture_compiles_to_the_expected_graph() {
+    let file = File::open(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/analyst.csv"
+    ))
+    .unwrap();
+    let input = read_csv(file, context()).unwrap();
+    assert_eq!(input.tuples.len(), 6);
+    assert_eq!(input.tuples[1].object, "Facts, episodes, and contexts");
+    assert_eq!(input.tuples[2].subject, "Smith, Jane");
+    assert_eq!(input.tuples[2].confidence, 1.0);
+    assert_eq!(input.tuples[5].confidence, 1.0);
+
+    let output = compile(&input);
+    // Agent, Memory, Facts..., Smith Jane, Rust, WebAssembly; `agent needs
+    // memory` folds into the first row.
+    assert_eq!(output.record.concepts.len(), 6);
+    assert_eq!(output.record.relations.len(), 5);
+}
+
+#[test]
+fn confidence_column_is_optional() {
+    let csv = "subject,predicate,object\nAgent,needs,Memory\n";
+    let input = read_csv(csv.as_bytes(), context()).unwrap();
+    assert_eq!(input.tuples.len(), 1);
+    assert_eq!(input.tuples[0].confidence, 1.0);
+}
+
+#[test]
+fn columns_can_be_mapped() {
+    let csv = "0.7;Memory;Agent;needs\n0.6;Rust;Agent;uses\n";
+    let columns = CsvColumns {
+        subject: 2,
+        predicate: 3,
+        object: 1,
+        confidence: Some(0),
+        has_header: false,
+    };
+    let input = read_csv_with(csv.replace(';', ",").as_bytes(), context(), &columns).unwrap();
+    assert_eq!(input.tuples.len(), 2);
+    assert_eq!(input.tuples[0].subject, "Agent");
+    assert_eq!(input.tuples[0].predicate, "needs");
+    assert_eq!(input.tuples[0].object, "Memory");
+    assert_eq!(input.tuples[0].confidence, 0.7);
+    assert_eq!(input.tuples[1].object, "Rust");
+}
+
+#[test]
+fn bad_confidence_reports_its_row() {
+    let csv = "subject,predicate,object,confidence\nAgent,needs,Memory,0.9\nAgent,uses,Rust,high\n";
+    let err = read_csv(csv.as_bytes(), context()).unwrap_err();
+    assert!(matches!(err, IngestError::Row { row: 3, .. }));
+    assert_eq!(err.to_string(), "row 3: confidence is not a number");
+}
+
+#[test]
+fn short_row_reports_its_row() {
+    let csv = "subject,predicate,object\nAgent,needs,Memory\nAgent,needs\n";
+    assert!(matches!(
+        read_csv(csv.as_bytes(), context()),
+        Err(IngestError::Row { row: 3, .. })
+    ));
+}
+
+#[test]
+fn invalid_utf8_reports_its_row() {
+    let mut csv = b"subject,predicate,object\nAgent,needs,Memory\nAgent,needs,".to_vec();
+    csv.extend_from_slice(&[0xff, b'\n']);
+    assert!(matches!(
+        read_csv(&csv[..], context()),
+        Err(IngestError::Csv { row: 3, .. })
+    ));
+}
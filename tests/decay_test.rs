@@ -0,0 +1,386 @@
+use zero_memory::compiler::hasher;
+use zero_memory::store::{DecayError, DecayReport, MemoryStore};
+use zero_memory::time::InvalidTimestamp;
+use zero_memory::types::*;
+
+/// Contexts read the way a legacy store would: an unparseable
+/// `event_time` lands on the epoch.
+fn context(scope: &str, event_time: &str) -> ContextNode {
+    let meta = ContextMeta {
+        event_time: EventTime::from_legacy(event_time),
+        source: "test".to_string(),
+        scope: scope.to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    }
+}
+
+fn concept(label: &str, confidence: f64, updated_at: &str) -> ConceptNode {
+    ConceptNode {
+        hash: hasher::concept_hash(label),
+        label: label.to_string(),
+        aliases: vec![],
+        confidence,
+        created_at: EventTime::from_legacy(updated_at),
+        updated_at: EventTime::from_legacy(updated_at),
+        display_label: None,
+    }
+}
+
+/// One record observing `subject -needs-> object` with the given
+/// confidence, in a context whose event time is `at`.
+fn observation(subject: &str, object: &str, confidence: f64, at: &str) -> MemoryRecord {
+    let ctx = context(&format!("{}_{}", subject, object), at);
+    let fact = hasher::fact_hash(subject, "needs", object);
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts: vec![concept(subject, 0.9, at), concept(object, 0.9, at)],
+        relations: vec![RelationNode {
+            episode_hash: hasher::episode_hash(&fact, &ctx.hash),
+            fact_hash: fact,
+            subject_hash: hasher::concept_hash(subject),
+            predicate: "needs".into(),
+            object_hash: hasher::concept_hash(object),
+            confidence,
+            context_hash: ctx.hash.clone(),
+            created_at: EventTime::from_legacy(at),
+            polarity: Polarity::Positive,
+            valid_from: None,
+            valid_to: None,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        }],
+        context: ctx,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+fn episode_confidence(store: &MemoryStore, subject: &str, object: &str) -> f64 {
+    let fact = hasher::fact_hash(subject, "needs", object);
+    let episodes = store.get_relations_by_fact(&fact);
+    assert_eq!(episodes.len(), 1);
+    episodes[0].confidence
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn one_half_life_halves_confidence() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+
+    let report = store
+        .decay_confidence("2026-01-31T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    assert_eq!(
+        report,
+        DecayReport {
+            decayed: 1,
+            unparseable: 0
+        }
+    );
+    assert_close(episode_confidence(&store, "agent", "memory"), 0.4);
+    let agent = store.get_concept_by_label("agent").unwrap();
+    assert_eq!(agent.confidence, 0.9, "concepts are untouched by default");
+}
+
+#[test]
+fn decay_is_clamped_at_floor() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+    store.insert_record(observation("agent", "noise", 0.05, "2026-01-01T00:00:00Z"));
+
+    let report = store
+        .decay_confidence("2027-01-01T00:00:00Z", 30.0, 0.1)
+        .unwrap();
+
+    assert_eq!(report.decayed, 1, "values below the floor are left alone");
+    assert_close(episode_confidence(&store, "agent", "memory"), 0.1);
+    assert_close(episode_confidence(&store, "agent", "noise"), 0.05);
+}
+
+#[test]
+fn repeated_passes_compose_instead_of_compounding() {
+    let mut nightly = MemoryStore::new();
+    let mut once = MemoryStore::new();
+    for store in [&mut nightly, &mut once] {
+        store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+    }
+
+    for day in 2..=31 {
+        let now = format!("2026-01-{:02}T00:00:00Z", day);
+        nightly.decay_confidence(&now, 30.0, 0.0).unwrap();
+    }
+    once.decay_confidence("2026-01-31T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    assert_close(episode_confidence(&nightly, "agent", "memory"), 0.4);
+    assert_close(episode_confidence(&once, "agent", "memory"), 0.4);
+}
+
+#[test]
+fn episodes_inserted_between_passes_decay_from_their_event_time() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-02-28T00:00:00Z"));
+    store
+        .decay_confidence("2026-03-01T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+    store.insert_record(observation("agent", "tools", 0.8, "2026-01-01T00:00:00Z"));
+
+    store
+        .decay_confidence("2026-03-02T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    assert_close(episode_confidence(&store, "agent", "tools"), 0.2);
+    assert_close(
+        episode_confidence(&store, "agent", "memory"),
+        0.8 * 0.5_f64.powf(2.0 / 30.0),
+    );
+}
+
+#[test]
+fn episodes_newer_than_now_are_untouched() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-03-01T00:00:00Z"));
+
+    let report = store
+        .decay_confidence("2026-02-01T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    assert_eq!(report.decayed, 0);
+    assert_eq!(episode_confidence(&store, "agent", "memory"), 0.8);
+}
+
+#[test]
+fn unparseable_event_times_are_skipped_and_counted() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+    store.insert_record(observation("agent", "tools", 0.8, "sometime last week"));
+
+    let report = store
+        .decay_confidence("2026-01-31T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    assert_eq!(
+        report,
+        DecayReport {
+            decayed: 1,
+            unparseable: 1
+        }
+    );
+    assert_eq!(episode_confidence(&store, "agent", "tools"), 0.8);
+}
+
+#[test]
+fn invalid_now_is_rejected() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+
+    let err = store.decay_confidence("tomorrow", 30.0, 0.0).unwrap_err();
+    assert_eq!(
+        err,
+        DecayError::Timestamp(InvalidTimestamp("tomorrow".to_string()))
+    );
+    assert_eq!(episode_confidence(&store, "agent", "memory"), 0.8);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+    let now = "2026-01-31T00:00:00Z";
+
+    for half_life in [0.0, -30.0, f64::NAN, f64::INFINITY] {
+        let err = store.decay_confidence(now, half_life, 0.0).unwrap_err();
+        assert!(matches!(err, DecayError::HalfLife(_)), "{}", half_life);
+        let err = store
+            .decay_concept_confidence(now, half_life, 0.0)
+            .unwrap_err();
+        assert!(matches!(err, DecayError::HalfLife(_)), "{}", half_life);
+    }
+    for floor in [-0.1, 1.5, f64::NAN] {
+        let err = store.decay_confidence(now, 30.0, floor).unwrap_err();
+        assert!(matches!(err, DecayError::Floor(_)), "{}", floor);
+        let err = store
+            .decay_concept_confidence(now, 30.0, floor)
+            .unwrap_err();
+        assert!(matches!(err, DecayError::Floor(_)), "{}", floor);
+    }
+
+    assert_eq!(episode_confidence(&store, "agent", "memory"), 0.8);
+    assert_eq!(store.get_concept_by_label("agent").unwrap().confidence, 0.9);
+    store.decay_confidence(now, 30.0, 0.0).unwrap();
+    assert_close(episode_confidence(&store, "agent", "memory"), 0.4);
+}
+
+#[test]
+fn concept_decay_uses_updated_at() {
+    let mut store = MemoryStore::new();
+    let mut record = observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z");
+    record.concepts[1].updated_at = "2026-01-16T00:00:00Z".parse().unwrap();
+    store.insert_record(record);
+
+    let report = store
+        .decay_concept_confidence("2026-01-31T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    assert_eq!(report.decayed, 2);
+    assert_close(
+        store.get_concept_by_label("agent").unwrap().confidence,
+        0.45,
+    );
+    assert_close(
+        store.get_concept_by_label("memory").unwrap().confidence,
+        0.9 * 0.5_f64.sqrt(),
+    );
+    assert_eq!(
+        episode_confidence(&store, "agent", "memory"),
+        0.8,
+        "episodes are decayed separately"
+    );
+}
+
+#[test]
+fn decay_watermark_survives_snapshots() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+    store
+        .decay_confidence("2026-01-31T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+
+    for mut loaded in [
+        MemoryStore::load_json(json.as_slice()).unwrap(),
+        MemoryStore::load_binary(binary.as_slice()).unwrap(),
+    ] {
+        loaded
+            .decay_confidence("2026-03-02T00:00:00Z", 30.0, 0.0)
+            .unwrap();
+        assert_close(episode_confidence(&loaded, "agent", "memory"), 0.2);
+    }
+}
+
+#[test]
+fn legacy_decay_watermarks_still_load() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.8, "2026-01-01T00:00:00Z"));
+    store
+        .decay_confidence("2026-01-31T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+
+    // Snapshots written before per-item decay times had one watermark.
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let mut snapshot: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    snapshot["episodes_decayed"] = serde_json::json!([]);
+    snapshot["episodes_decayed_through"] = "2026-01-31T00:00:00Z".into();
+
+    let mut loaded = MemoryStore::load_json(snapshot.to_string().as_bytes()).unwrap();
+    loaded
+        .decay_confidence("2026-03-02T00:00:00Z", 30.0, 0.0)
+        .unwrap();
+    assert_close(episode_confidence(&loaded, "agent", "memory"), 0.2);
+}
+
+#[test]
+fn reinforcement_is_off_by_default() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+    let result = store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+
+    assert_eq!(result.reinforced, 0);
+    assert_eq!(result.dupes_skipped, 3);
+    assert_eq!(episode_confidence(&store, "agent", "memory"), 0.6);
+}
+
+#[test]
+fn duplicate_episode_reinforces_stored_confidence() {
+    let mut store = MemoryStore::new();
+    store.set_reinforcement(0.5);
+    store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+
+    let result = store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+    assert_eq!(result.reinforced, 1);
+    assert_close(episode_confidence(&store, "agent", "memory"), 0.8);
+
+    store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+    assert_close(episode_confidence(&store, "agent", "memory"), 0.9);
+}
+
+#[test]
+fn new_episode_of_known_fact_reinforces_best_episode() {
+    let mut store = MemoryStore::new();
+    store.set_reinforcement(0.5);
+    store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+
+    let mut later = observation("agent", "memory", 0.3, "2026-01-02T00:00:00Z");
+    later.context = context("elsewhere", "2026-01-02T00:00:00Z");
+    let fact = later.relations[0].fact_hash.clone();
+    later.relations[0].context_hash = later.context.hash.clone();
+    later.relations[0].episode_hash = hasher::episode_hash(&fact, &later.context.hash);
+    let result = store.insert_record(later);
+
+    assert_eq!(result.new_episodes, 1);
+    assert_eq!(result.reinforced, 1);
+    let mut confidences: Vec<f64> = store
+        .get_relations_by_fact(&fact)
+        .iter()
+        .map(|r| r.confidence)
+        .collect();
+    confidences.sort_by(|a, b| a.total_cmp(b));
+    assert_close(confidences[0], 0.3);
+    assert_close(confidences[1], 0.8);
+}
+
+#[test]
+fn non_finite_reinforcement_rates_turn_reinforcement_off() {
+    for rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let mut store = MemoryStore::new();
+        store.set_reinforcement(rate);
+        assert_eq!(store.reinforcement(), 0.0, "{}", rate);
+        store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+
+        let result =
+            store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+        assert_eq!(result.reinforced, 0);
+        assert_eq!(episode_confidence(&store, "agent", "memory"), 0.6);
+
+        let mut json = Vec::new();
+        store.save_json(&mut json).unwrap();
+        MemoryStore::load_json(json.as_slice()).unwrap();
+    }
+}
+
+#[test]
+fn fully_confident_facts_are_not_counted_as_reinforced() {
+    let mut store = MemoryStore::new();
+    store.set_reinforcement(1.0);
+    store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+
+    let first = store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+    let second = store.insert_record(observation("agent", "memory", 0.6, "2026-01-01T00:00:00Z"));
+
+    assert_eq!(first.reinforced, 1);
+    assert_eq!(second.reinforced, 0);
+    assert_eq!(episode_confidence(&store, "agent", "memory"), 1.0);
+}
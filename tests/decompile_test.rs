@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use zero_memory::compiler::emitter::{compile, emit_graph_text};
+use zero_memory::compiler::{decompile, verify_trace_hash, DecompileError};
+use zero_memory::types::*;
+
+fn tuple(subject: &str, predicate: &str, object: &str, confidence: f64) -> SemanticTuple {
+    SemanticTuple::from((subject, predicate, object, confidence))
+}
+
+fn input() -> CompilerInput {
+    let mut met = tuple("Alice", "met", "Bob", 0.9);
+    met.qualifiers = HashMap::from([("location".to_string(), "Paris".to_string())]);
+    let mut dislikes = tuple("Alice", "likes", "Mushrooms", 0.7);
+    dislikes.polarity = Polarity::Negative;
+    let mut lives_in = tuple("Alice", "lives_in", "Lisbon", 0.8);
+    lives_in.valid_from = Some("2023-06-01".to_string());
+
+    CompilerInput {
+        utterance: None,
+        tuples: vec![met, dislikes, lives_in, tuple("Bob", "knows", "Alice", 0.6)],
+        context: ContextMeta::builder()
+            .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+            .source("user_prompt")
+            .scope("s1")
+            .build()
+            .unwrap(),
+    }
+}
+
+fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+    items.sort();
+    items
+}
+
+fn concept_hashes(record: &MemoryRecord) -> Vec<String> {
+    sorted(record.concepts.iter().map(|c| c.hash.to_string()).collect())
+}
+
+#[test]
+fn round_trip_reproduces_hashes_and_counts() {
+    let output = compile(&input());
+    let original = &output.record;
+    let decompiled = decompile(&output.graph_text).unwrap();
+
+    assert_eq!(decompiled.concepts.len(), original.concepts.len());
+    assert_eq!(decompiled.relations.len(), original.relations.len());
+    assert_eq!(decompiled.context.hash, original.context.hash);
+
+    assert_eq!(concept_hashes(&decompiled), concept_hashes(original));
+
+    let relation_keys = |r: &MemoryRecord| {
+        sorted(
+            r.relations
+                .iter()
+                .map(|rel| {
+                    (
+                        rel.episode_hash.to_string(),
+                        rel.fact_hash.to_string(),
+                        rel.subject_hash.to_string(),
+                        rel.object_hash.to_string(),
+                        rel.predicate.to_string(),
+                        rel.context_hash.to_string(),
+                    )
+                })
+                .collect(),
+        )
+    };
+    assert_eq!(relation_keys(&decompiled), relation_keys(original));
+}
+
+#[test]
+fn round_trip_keeps_episode_fields() {
+    let output = compile(&input());
+    let decompiled = decompile(&output.graph_text).unwrap();
+
+    for original in &output.record.relations {
+        let relation = decompiled
+            .relations
+            .iter()
+            .find(|r| r.episode_hash == original.episode_hash)
+            .unwrap();
+        assert_eq!(relation.confidence, original.confidence);
+        assert_eq!(relation.polarity, original.polarity);
+        assert_eq!(relation.valid_from, original.valid_from);
+    }
+}
+
+#[test]
+fn round_trip_keeps_concept_fields() {
+    let output = compile(&input());
+    let decompiled = decompile(&output.graph_text).unwrap();
+
+    let concepts = |r: &MemoryRecord| {
+        let mut concepts = r.concepts.clone();
+        concepts.sort_by(|a, b| a.label.cmp(&b.label));
+        serde_json::to_value(concepts).unwrap()
+    };
+    assert_eq!(concepts(&decompiled), concepts(&output.record));
+    let alice = decompiled
+        .concepts
+        .iter()
+        .find(|c| c.label == "alice")
+        .unwrap();
+    assert_eq!(alice.aliases, ["Alice"]);
+    assert_eq!(alice.confidence, 0.9);
+    assert_eq!(alice.display_label.as_deref(), Some("Alice"));
+}
+
+#[test]
+fn round_trip_reproduces_the_record_hash() {
+    let mut input = input();
+    input.tuples = vec![tuple("Café:Ünïcode", "is_a", "B|c", 0.9)];
+    let output = compile(&input);
+    let decompiled = decompile(&output.graph_text).unwrap();
+
+    assert!(decompiled
+        .concepts
+        .iter()
+        .any(|c| c.display_label.as_deref() == Some("Café:Ünïcode")));
+    assert_eq!(decompiled.hash(), output.record.hash());
+}
+
+#[test]
+fn graphs_without_concept_fields_fall_back_to_approximations() {
+    let output = compile(&input());
+    let old_layout: Vec<&str> = output
+        .graph_text
+        .lines()
+        .filter(|line| {
+            ![
+                "aliases",
+                "confidence",
+                "created_at",
+                "updated_at",
+                "display_label",
+            ]
+            .iter()
+            .any(|field| line.contains(&format!(r#""id": "concept_{}_"#, field)))
+        })
+        .collect();
+    let decompiled = decompile(&old_layout.join("\n")).unwrap();
+
+    assert_eq!(concept_hashes(&decompiled), concept_hashes(&output.record));
+    let mushrooms = decompiled
+        .concepts
+        .iter()
+        .find(|c| c.label == "mushrooms")
+        .unwrap();
+    assert!(mushrooms.aliases.is_empty());
+    assert!(mushrooms.display_label.is_none());
+    assert_eq!(mushrooms.confidence, 0.7);
+    assert_eq!(mushrooms.updated_at.to_string(), "2026-02-18T00:00:00Z");
+}
+
+#[test]
+fn decompiled_record_emits_the_same_graph() {
+    let output = compile(&input());
+    let decompiled = decompile(&output.graph_text).unwrap();
+    assert_eq!(
+        emit_graph_text(&decompiled, &decompiled.context.meta),
+        output.graph_text
+    );
+}
+
+#[test]
+fn empty_record_round_trips() {
+    let mut empty = input();
+    empty.tuples.clear();
+    let output = compile(&empty);
+    let decompiled = decompile(&output.graph_text).unwrap();
+    assert!(decompiled.concepts.is_empty());
+    assert!(decompiled.relations.is_empty());
+    assert_eq!(decompiled.context.hash, output.record.context.hash);
+}
+
+#[test]
+fn foreign_graphs_are_unrecognized() {
+    let cases = [
+        "not a graph",
+        r#"Graph { "name": "hello_world", "nodes": [] }"#,
+        r#"Graph { "name": "zero_memory_compiled", "nodes": [] }"#,
+        r#"Graph { "name": "zero_memory_compiled", "nodes": [{ "type": "Constant", "value": 1 }] }"#,
+    ];
+    for text in cases {
+        match decompile(text) {
+            Err(DecompileError::UnrecognizedLayout(_)) => {}
+            other => panic!(
+                "expected UnrecognizedLayout for {:?}, got {:?}",
+                text, other
+            ),
+        }
+    }
+}
+
+#[test]
+fn malformed_text_is_a_syntax_error() {
+    let output = compile(&input());
+    let truncated = &output.graph_text[..output.graph_text.len() / 2];
+    assert!(matches!(
+        decompile(truncated),
+        Err(DecompileError::Syntax(_))
+    ));
+}
+
+#[test]
+fn malformed_relations_name_the_node() {
+    let output = compile(&input());
+    let broken = output
+        .graph_text
+        .replacen(r#""fact_hash":""#, r#""fact_hash":"zz"#, 1);
+    match decompile(&broken) {
+        Err(DecompileError::InvalidField { node, field }) => {
+            assert!(node.starts_with("rel_"));
+            assert_eq!(field, "relation");
+        }
+        other => panic!("expected InvalidField, got {:?}", other),
+    }
+}
+
+#[test]
+fn colons_survive_the_round_trip() {
+    let mut input = input();
+    input.tuples = vec![tuple("Alice", "reads", "urn:isbn:0451450523", 0.9)];
+    input.context.scope = "project:alpha".to_string();
+    let output = compile(&input);
+    assert!(!output.graph_text.contains("isbn:"));
+
+    let decompiled = decompile(&output.graph_text).unwrap();
+    assert_eq!(
+        decompiled.context.meta.event_time.to_string(),
+        "2026-02-18T00:00:00Z"
+    );
+    assert_eq!(decompiled.context.meta.scope, "project:alpha");
+    let labels: Vec<&str> = decompiled
+        .concepts
+        .iter()
+        .map(|c| c.label.as_str())
+        .collect();
+    assert_eq!(sorted(labels), ["alice", "urn:isbn:0451450523"]);
+    assert_eq!(concept_hashes(&decompiled), concept_hashes(&output.record));
+}
+
+#[test]
+fn graphs_carry_the_trace_hash_of_their_record() {
+    let output = compile(&input());
+    assert!(verify_trace_hash(&output.record, &output.graph_text));
+
+    let decompiled = decompile(&output.graph_text).unwrap();
+    assert!(verify_trace_hash(&decompiled, &output.graph_text));
+
+    let mut changed = output.record.clone();
+    changed.relations[0].confidence = 0.1;
+    assert!(!verify_trace_hash(&changed, &output.graph_text));
+}
+
+#[test]
+fn pending_trace_hashes_do_not_verify() {
+    let output = compile(&input());
+    let trace = hex::encode(zero_memory::compiler::hasher::trace_hash(&output.record));
+    let pending = output.graph_text.replace(&trace, "pending");
+    assert!(!verify_trace_hash(&output.record, &pending));
+    assert!(!verify_trace_hash(&output.record, "not a graph"));
+}
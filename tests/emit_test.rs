@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+
+use zero_memory::compiler::emitter::{compile, emit_graph_text, emit_graph_to};
+use zero_memory::types::*;
+
+/// Graph text of `input()`, as compiled with its utterance.
+const GOLDEN_COMPILED: &str = include_str!("golden/compiled.0");
+/// Graph text of the record compiled from `input()`, emitted on its own.
+const GOLDEN_RECORD: &str = include_str!("golden/record.0");
+
+fn tuple(subject: &str, predicate: &str, object: &str, confidence: f64) -> SemanticTuple {
+    SemanticTuple::from((subject, predicate, object, confidence))
+}
+
+/// Qualifiers, polarity, validity, colons, and quotes: every part of the
+/// layout.
+fn input() -> CompilerInput {
+    let mut met = tuple("Alice", "met", "Bob", 0.9);
+    met.qualifiers = HashMap::from([("location".to_string(), "Paris".to_string())]);
+    let mut dislikes = tuple("Alice", "likes", "Mushrooms", 0.7);
+    dislikes.polarity = Polarity::Negative;
+    let mut lives_in = tuple("Alice", "lives_in", "Lisbon", 0.8);
+    lives_in.valid_from = Some("2023-06-01T00:00:00Z".to_string());
+    lives_in.valid_to = Some("2025-01-01T00:00:00Z".to_string());
+
+    CompilerInput {
+        utterance: Some(r#"Alice met Bob in Paris; she "hates" mushrooms: true"#.to_string()),
+        tuples: vec![
+            met,
+            dislikes,
+            lives_in,
+            tuple("Bob", "reads", "urn:isbn:0451450523", 0.6),
+        ],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "golden".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+/// A writer that accepts at most one byte per call, so every write is
+/// partial.
+struct Trickle(Vec<u8>);
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match buf.first() {
+            Some(&byte) => {
+                self.0.push(byte);
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn compiled_graph_matches_the_golden_file() {
+    assert_eq!(compile(&input()).graph_text, GOLDEN_COMPILED);
+}
+
+#[test]
+fn emitted_graph_matches_the_golden_file() {
+    let record = compile(&input()).record;
+    assert_eq!(
+        emit_graph_text(&record, &record.context.meta),
+        GOLDEN_RECORD
+    );
+}
+
+#[test]
+fn streamed_graph_matches_the_golden_file() {
+    let record = compile(&input()).record;
+    let mut text = Vec::new();
+    emit_graph_to(&record, &record.context.meta, &mut text).unwrap();
+    assert_eq!(String::from_utf8(text).unwrap(), GOLDEN_RECORD);
+}
+
+#[test]
+fn streaming_survives_partial_writes() {
+    let record = compile(&input()).record;
+    let mut writer = Trickle(Vec::new());
+    emit_graph_to(&record, &record.context.meta, &mut writer).unwrap();
+    assert_eq!(String::from_utf8(writer.0).unwrap(), GOLDEN_RECORD);
+}
+
+#[test]
+fn streaming_to_a_file_writes_the_whole_graph() {
+    let path = std::env::temp_dir().join(format!("zero_memory_emit_test_{}.0", std::process::id()));
+    let record = compile(&input()).record;
+
+    {
+        let mut file = BufWriter::new(File::create(&path).unwrap());
+        emit_graph_to(&record, &record.context.meta, &mut file).unwrap();
+        file.flush().unwrap();
+    }
+
+    let written = fs::read_to_string(&path).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(written, emit_graph_text(&record, &record.context.meta));
+}
+
+#[test]
+fn empty_records_stream_like_they_emit() {
+    let mut empty = input();
+    empty.tuples.clear();
+    let record = compile(&empty).record;
+    let mut text = Vec::new();
+    emit_graph_to(&record, &record.context.meta, &mut text).unwrap();
+    assert_eq!(
+        String::from_utf8(text).unwrap(),
+        emit_graph_text(&record, &record.context.meta)
+    );
+}
@@ -0,0 +1,141 @@
+use std::error::Error;
+
+use serde_json::json;
+use zero_memory::adapters::convert::{record_to_value, value_to_record, ConvertError};
+use zero_memory::compiler::emitter::{compile, try_compile};
+use zero_memory::compiler::io::{read_ndjson, IngestError};
+use zero_memory::compiler::CompilerError;
+use zero_memory::error::MemoryError;
+use zero_memory::store::{MemoryBackend, MemoryStore};
+use zero_memory::types::*;
+
+fn input(confidences: &[f64]) -> CompilerInput {
+    // Not `From<(.., f64)>`, which clamps the confidence.
+    let tuples = confidences.iter().map(|&confidence| SemanticTuple {
+        confidence,
+        ..SemanticTuple::from(("Agent", "needs", "Memory"))
+    });
+    CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+        .tuples(tuples)
+        .build()
+        .unwrap()
+}
+
+/// Compiles, stores, and re-reads a record, with every step's error
+/// turned into a `MemoryError` by `?`.
+fn ingest(
+    input: &CompilerInput,
+    mangle: impl Fn(&mut serde_json::Value),
+) -> Result<usize, MemoryError> {
+    let output = try_compile(input)?;
+    let mut value = record_to_value(&output.record);
+    mangle(&mut value);
+    let record = value_to_record(&value)?;
+    let mut store = MemoryStore::new();
+    // The backend trait's methods return `Result<_, Infallible>`.
+    let result = MemoryBackend::insert_record(&mut store, record)?;
+    Ok(result.new_episodes)
+}
+
+#[test]
+fn one_question_mark_covers_compile_parse_and_store() {
+    assert_eq!(ingest(&input(&[0.9]), |_| {}).unwrap(), 1);
+
+    let err = ingest(&input(&[0.9, 1.5]), |_| {}).unwrap_err();
+    assert!(matches!(
+        err,
+        MemoryError::Compile(CompilerError::InvalidConfidence {
+            tuple_index: 1,
+            value,
+        }) if value == 1.5
+    ));
+    assert_eq!(err.to_string(), "tuple 1: confidence 1.5 is not in [0, 1]");
+
+    let err = ingest(&input(&[0.9]), |value| {
+        value["relations"][0]["fact_hash"] = json!("00");
+    })
+    .unwrap_err();
+    match &err {
+        MemoryError::Convert(ConvertError { path, .. }) => {
+            assert_eq!(path, "relations[0].fact_hash")
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert_eq!(
+        err.to_string(),
+        "relations[0].fact_hash: expected 64 hex chars"
+    );
+}
+
+#[test]
+fn line_numbers_and_sources_survive() {
+    let read =
+        |text: &str| -> Result<CompilerInput, MemoryError> { Ok(read_ndjson(text.as_bytes())?) };
+
+    let err =
+        read("{\"subject\": \"a\", \"predicate\": \"b\", \"object\": \"c\", \"confidence\": 1}\n")
+            .unwrap_err();
+    assert!(matches!(
+        err,
+        MemoryError::Ingest(IngestError::MissingContext { line: 1 })
+    ));
+
+    let err = read("{}\nnot json\n").unwrap_err();
+    assert!(err.to_string().starts_with("line 1: "), "{}", err);
+    // The JSON error under the ingest error is still reachable.
+    let MemoryError::Ingest(ingest) = &err else {
+        panic!("unexpected error {:?}", err);
+    };
+    assert!(ingest.source().is_some());
+}
+
+#[test]
+fn records_that_disagree_with_themselves_convert_too() {
+    let mut record = compile(&input(&[0.9])).record;
+    record.context.hash = ContextHash([0; 32]);
+    let err: MemoryError = record.validate().unwrap_err().remove(0).into();
+    assert!(matches!(
+        err,
+        MemoryError::Record(RecordIssue::ContextHashMismatch { .. })
+    ));
+}
+
+#[test]
+fn any_runtime_error_can_be_wrapped() {
+    #[derive(Debug)]
+    struct Unreachable;
+
+    impl std::fmt::Display for Unreachable {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("runtime unreachable")
+        }
+    }
+
+    impl Error for Unreachable {}
+
+    let err = MemoryError::runtime(Unreachable);
+    assert_eq!(err.to_string(), "runtime unreachable");
+    match err {
+        MemoryError::Runtime(e) => assert!(e.downcast_ref::<Unreachable>().is_some()),
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn persistence_errors_keep_their_key() {
+    use zero_memory::store::PersistError;
+    use zero_memory::testing::MockRuntime;
+
+    let runtime = MockRuntime::new();
+    let restore = || -> Result<MemoryStore, MemoryError> {
+        Ok(MemoryStore::restore_from(&runtime, "memory")?)
+    };
+    let err = restore().unwrap_err();
+    match err {
+        MemoryError::Persistence(PersistError::NoManifest { key }) => {
+            assert_eq!(key, "memory/manifest")
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
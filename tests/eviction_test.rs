@@ -0,0 +1,261 @@
+use zero_memory::compiler::hasher;
+use zero_memory::store::{MemoryStore, StoreLimits};
+use zero_memory::types::*;
+
+fn context(scope: &str, event_time: &str) -> ContextNode {
+    let meta = ContextMeta {
+        event_time: event_time.parse().unwrap(),
+        source: "test".to_string(),
+        scope: scope.to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    }
+}
+
+fn concept(label: &str, at: &str) -> ConceptNode {
+    ConceptNode {
+        hash: hasher::concept_hash(label),
+        label: label.to_string(),
+        aliases: vec![],
+        confidence: 0.9,
+        created_at: at.parse().unwrap(),
+        updated_at: at.parse().unwrap(),
+        display_label: None,
+    }
+}
+
+/// One record observing `subject -predicate-> object` at `at`, in its own context.
+fn observation(subject: &str, predicate: &str, object: &str, at: &str) -> MemoryRecord {
+    let ctx = context(&format!("{}_{}_{}", subject, predicate, object), at);
+    let fact = hasher::fact_hash(subject, predicate, object);
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts: vec![concept(subject, at), concept(object, at)],
+        relations: vec![RelationNode {
+            episode_hash: hasher::episode_hash(&fact, &ctx.hash),
+            fact_hash: fact,
+            subject_hash: hasher::concept_hash(subject),
+            predicate: predicate.into(),
+            object_hash: hasher::concept_hash(object),
+            confidence: 0.9,
+            context_hash: ctx.hash.clone(),
+            created_at: at.parse().unwrap(),
+            polarity: Polarity::Positive,
+            valid_from: None,
+            valid_to: None,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        }],
+        context: ctx,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+#[test]
+fn unlimited_store_never_evicts() {
+    let mut store = MemoryStore::new();
+    for i in 0..20 {
+        let result = store.insert_record(observation(
+            &format!("c{}", i),
+            "links",
+            &format!("c{}", i + 1),
+            "2026-02-18T00:00:00Z",
+        ));
+        assert!(result.evicted.is_empty());
+    }
+    assert_eq!(store.concept_count(), 21);
+}
+
+#[test]
+fn concept_limit_evicts_least_recently_updated() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: Some(3),
+        max_episodes: None,
+    });
+    store.insert_record(observation("a", "links", "b", "2026-02-18T00:00:00Z"));
+    store.insert_record(observation("c", "links", "c", "2026-02-18T01:00:00Z"));
+    assert_eq!(store.concept_count(), 3);
+
+    let result = store.insert_record(observation("d", "links", "c", "2026-02-18T02:00:00Z"));
+
+    assert_eq!(store.concept_count(), 3);
+    assert_eq!(result.evicted.concepts.len(), 1);
+    let evicted = &result.evicted.concepts[0];
+    assert!(
+        *evicted == hasher::concept_hash("a") || *evicted == hasher::concept_hash("b"),
+        "one of the oldest concepts must go"
+    );
+    assert!(store.get_concept(evicted).is_none());
+}
+
+#[test]
+fn concept_eviction_cascades_through_indexes() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: Some(2),
+        max_episodes: None,
+    });
+    store.insert_record(observation("old", "links", "mid", "2026-02-18T00:00:00Z"));
+    let mut newer = observation("mid", "links", "new", "2026-02-19T00:00:00Z");
+    newer.concepts[0].updated_at = "2026-02-19T00:00:00Z".parse().unwrap();
+    let result = store.insert_record(newer);
+
+    assert_eq!(result.evicted.concepts, vec![hasher::concept_hash("old")]);
+    assert_eq!(result.evicted.episodes.len(), 1, "old→mid episode cascades");
+    assert!(store.get_concept_by_label("old").is_none());
+    assert_eq!(store.label_index().len(), 2);
+    assert_eq!(store.relation_count(), 1);
+    assert_eq!(store.fact_count(), 1);
+    assert!(store
+        .get_relations_by_fact(&hasher::fact_hash("old", "links", "mid"))
+        .is_empty());
+    assert_eq!(
+        store.get_relations(&hasher::concept_hash("mid")).len(),
+        1,
+        "adjacency must no longer point at the evicted fact"
+    );
+    assert_eq!(store.context_count(), 1, "orphaned context is dropped");
+}
+
+#[test]
+fn evicted_concepts_release_their_aliases() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: Some(2),
+        max_episodes: None,
+    });
+    let mut old = observation("old", "links", "mid", "2026-02-18T00:00:00Z");
+    old.concepts[0].aliases = vec!["legacy".to_string()];
+    store.insert_record(old);
+    assert!(store.get_concept_by_label("legacy").is_some());
+
+    let mut newer = observation("mid", "links", "new", "2026-02-19T00:00:00Z");
+    newer.concepts[0].updated_at = "2026-02-19T00:00:00Z".parse().unwrap();
+    store.insert_record(newer);
+
+    assert!(store.get_concept(&hasher::concept_hash("old")).is_none());
+    assert!(
+        store.get_concept_by_label("legacy").is_none(),
+        "alias must not dangle after eviction"
+    );
+    assert_eq!(store.label_index().len(), 2);
+}
+
+#[test]
+fn pinned_concepts_are_never_evicted() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: Some(2),
+        max_episodes: None,
+    });
+    let seed = hasher::concept_hash("seed");
+    store.pin(&seed);
+    assert!(store.is_pinned(&seed));
+
+    store.insert_record(observation("seed", "links", "x", "2026-02-18T00:00:00Z"));
+    for (i, hour) in ["01", "02", "03"].iter().enumerate() {
+        store.insert_record(observation(
+            &format!("y{}", i),
+            "links",
+            &format!("z{}", i),
+            &format!("2026-02-18T{}:00:00Z", hour),
+        ));
+        assert!(store.get_concept(&seed).is_some(), "seed must survive");
+    }
+    assert_eq!(store.concept_count(), 2);
+
+    assert!(store.unpin(&seed));
+    store.insert_record(observation(
+        "late",
+        "links",
+        "later",
+        "2026-02-18T09:00:00Z",
+    ));
+    assert!(
+        store.get_concept(&seed).is_none(),
+        "unpinned seed is evictable"
+    );
+}
+
+#[test]
+fn pinned_concepts_may_exceed_the_limit() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: Some(1),
+        max_episodes: None,
+    });
+    store.pin(&hasher::concept_hash("a"));
+    store.pin(&hasher::concept_hash("b"));
+    let result = store.insert_record(observation("a", "links", "b", "2026-02-18T00:00:00Z"));
+    assert!(result.evicted.is_empty());
+    assert_eq!(store.concept_count(), 2);
+}
+
+#[test]
+fn episode_limit_evicts_oldest_episodes() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: None,
+        max_episodes: Some(2),
+    });
+    store.insert_record(observation("a", "links", "b", "2026-02-18T03:00:00Z"));
+    store.insert_record(observation("a", "knows", "b", "2026-02-18T01:00:00Z"));
+    let result = store.insert_record(observation("a", "likes", "b", "2026-02-18T02:00:00Z"));
+
+    assert_eq!(store.relation_count(), 2);
+    assert_eq!(result.evicted.episodes.len(), 1);
+    assert!(result.evicted.concepts.is_empty());
+    assert!(
+        store
+            .get_relations_by_fact(&hasher::fact_hash("a", "knows", "b"))
+            .is_empty(),
+        "the 01:00 episode is the oldest by created_at"
+    );
+    assert_eq!(store.fact_count(), 2);
+    assert_eq!(store.concept_count(), 2, "episode eviction keeps concepts");
+}
+
+#[test]
+fn set_limits_applies_on_explicit_eviction() {
+    let mut store = MemoryStore::new();
+    for i in 0..5 {
+        store.insert_record(observation(
+            "hub",
+            &format!("p{}", i),
+            "spoke",
+            &format!("2026-02-18T0{}:00:00Z", i),
+        ));
+    }
+    store.set_limits(StoreLimits {
+        max_concepts: None,
+        max_episodes: Some(3),
+    });
+    assert_eq!(store.relation_count(), 5, "set_limits alone does not evict");
+
+    let report = store.evict_to_limits();
+    assert_eq!(report.episodes.len(), 2);
+    assert_eq!(store.relation_count(), 3);
+}
+
+#[test]
+fn pins_survive_snapshots() {
+    let mut store = MemoryStore::new();
+    let seed = hasher::concept_hash("seed");
+    store.pin(&seed);
+    store.insert_record(observation("seed", "links", "x", "2026-02-18T00:00:00Z"));
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    assert!(MemoryStore::load_json(json.as_slice())
+        .unwrap()
+        .is_pinned(&seed));
+
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    assert!(MemoryStore::load_binary(binary.as_slice())
+        .unwrap()
+        .is_pinned(&seed));
+}
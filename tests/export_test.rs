@@ -0,0 +1,792 @@
+use std::collections::{HashMap, HashSet};
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::hasher;
+use zero_memory::compiler::io::{from_ntriples, NTriplesOptions};
+use zero_memory::store::export::{
+    record_to_graphml, to_cypher, to_dot, to_graphml, to_mermaid, to_ntriples, to_ntriples_with,
+    write_cypher, MERMAID_MAX_LABEL, MERMAID_MAX_NODES,
+};
+use zero_memory::store::{DotOptions, MemoryStore, ReifiedStatements, UpdatedConcepts};
+use zero_memory::types::*;
+
+fn input(scope: &str, event_time: &str, tuples: &[(&str, &str, &str, f64)]) -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: tuples
+            .iter()
+            .map(|&tuple| SemanticTuple::from(tuple))
+            .collect(),
+        context: ContextMeta {
+            event_time: event_time.parse().unwrap(),
+            source: "export_test".to_string(),
+            scope: scope.to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn populated_store() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.insert_record(
+        compile(&input(
+            "session_1",
+            "2026-02-18T00:00:00Z",
+            &[
+                ("agent", "needs", "memory", 0.9),
+                ("agent", "uses", "0-lang", 0.8),
+            ],
+        ))
+        .record,
+    );
+    store.insert_record(
+        compile(&input(
+            "session_2",
+            "2026-02-19T00:00:00Z",
+            &[
+                ("agent", "needs", "memory", 0.95),
+                ("memory", "is_a", "resource", 0.7),
+            ],
+        ))
+        .record,
+    );
+    store
+}
+
+fn orphan_record(label: &str, scope: &str) -> MemoryRecord {
+    let meta = ContextMeta {
+        event_time: "2026-02-20T00:00:00Z".parse().unwrap(),
+        source: "export_test".to_string(),
+        scope: scope.to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts: vec![ConceptNode {
+            hash: hasher::concept_hash(label),
+            label: label.to_string(),
+            aliases: vec![],
+            confidence: 0.6,
+            created_at: meta.event_time,
+            updated_at: meta.event_time,
+            display_label: None,
+        }],
+        relations: vec![],
+        context: ContextNode {
+            hash: hasher::context_hash(&meta),
+            meta,
+        },
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+#[test]
+fn export_round_trip_reproduces_counts() {
+    let store = populated_store();
+    let records = store.export_records();
+
+    let mut fresh = MemoryStore::new();
+    for record in records {
+        fresh.insert_record(record);
+    }
+
+    assert_eq!(fresh.concept_count(), store.concept_count());
+    assert_eq!(fresh.fact_count(), store.fact_count());
+    assert_eq!(fresh.relation_count(), store.relation_count());
+    assert_eq!(fresh.context_count(), store.context_count());
+}
+
+#[test]
+fn export_groups_episodes_by_context() {
+    let store = populated_store();
+    let records = store.export_records();
+
+    assert_eq!(records.len(), 2, "one record per context");
+    assert_eq!(records[0].context.meta.scope, "session_1");
+    assert_eq!(records[1].context.meta.scope, "session_2");
+    for record in &records {
+        assert_eq!(record.relations.len(), 2);
+        for rel in &record.relations {
+            assert_eq!(rel.context_hash, record.context.hash);
+            let concept_hashes: Vec<_> = record.concepts.iter().map(|c| &c.hash).collect();
+            assert!(concept_hashes.contains(&&rel.subject_hash));
+            assert!(concept_hashes.contains(&&rel.object_hash));
+        }
+    }
+}
+
+#[test]
+fn export_carries_current_concept_state() {
+    let mut store = populated_store();
+    let mut bump = orphan_record("agent", "session_3");
+    bump.concepts[0].confidence = 0.99;
+    bump.concepts[0].aliases = vec!["bot".to_string()];
+    store.insert_record(bump);
+
+    let records = store.export_records();
+    let agent = records[0]
+        .concepts
+        .iter()
+        .find(|c| c.label == "agent")
+        .unwrap();
+    assert_eq!(agent.confidence, 0.99, "merged confidence must be exported");
+    assert_eq!(agent.aliases, vec!["bot".to_string()]);
+}
+
+#[test]
+fn export_keeps_orphan_concepts() {
+    let mut store = populated_store();
+    store.insert_record(orphan_record("lonely", "orphans"));
+
+    let records = store.export_records();
+    let carriers: Vec<_> = records
+        .iter()
+        .filter(|r| r.concepts.iter().any(|c| c.label == "lonely"))
+        .collect();
+    assert_eq!(carriers.len(), 1, "orphan must be exported exactly once");
+
+    let mut fresh = MemoryStore::new();
+    for record in records {
+        fresh.insert_record(record);
+    }
+    assert!(fresh.get_concept_by_label("lonely").is_some());
+    assert_eq!(fresh.concept_count(), store.concept_count());
+}
+
+#[test]
+fn export_of_empty_store_is_empty() {
+    assert!(MemoryStore::new().export_records().is_empty());
+}
+
+fn episode_scopes(store: &MemoryStore, records: &[MemoryRecord]) -> Vec<String> {
+    records
+        .iter()
+        .filter(|r| !r.relations.is_empty())
+        .map(|r| {
+            store
+                .get_context(&r.context.hash)
+                .unwrap()
+                .meta
+                .scope
+                .clone()
+        })
+        .collect()
+}
+
+#[test]
+fn export_since_returns_only_newer_episodes() {
+    let store = populated_store();
+    let records = store
+        .export_since("2026-02-18T12:00:00Z", UpdatedConcepts::Exclude)
+        .unwrap();
+
+    assert_eq!(episode_scopes(&store, &records), vec!["session_2"]);
+    assert_eq!(records[0].relations.len(), 2);
+    let labels: Vec<&str> = records[0]
+        .concepts
+        .iter()
+        .map(|c| c.label.as_str())
+        .collect();
+    assert_eq!(labels, vec!["agent", "memory", "resource"]);
+}
+
+#[test]
+fn export_since_cutoff_is_strict() {
+    let store = populated_store();
+    let records = store
+        .export_since("2026-02-19T00:00:00Z", UpdatedConcepts::Exclude)
+        .unwrap();
+    assert!(
+        records.is_empty(),
+        "an episode created exactly at the cutoff is not after it"
+    );
+}
+
+#[test]
+fn export_since_compares_chronologically() {
+    let store = populated_store();
+    // 2026-02-19T01:00:00+02:00 is 2026-02-18T23:00:00Z, i.e. before
+    // session_2 — although it sorts after it as a string.
+    let records = store
+        .export_since("2026-02-19T01:00:00+02:00", UpdatedConcepts::Exclude)
+        .unwrap();
+    assert_eq!(episode_scopes(&store, &records), vec!["session_2"]);
+}
+
+#[test]
+fn export_since_updated_concepts_are_opt_in() {
+    let mut store = populated_store();
+    let mut bump = orphan_record("0-lang", "session_3");
+    bump.concepts[0].confidence = 0.99;
+    store.insert_record(bump);
+
+    let cutoff = "2026-02-19T12:00:00Z";
+
+    let excluded = store
+        .export_since(cutoff, UpdatedConcepts::Exclude)
+        .unwrap();
+    assert!(
+        excluded.is_empty(),
+        "no episode is newer than the cutoff, so nothing is exported"
+    );
+
+    let included = store
+        .export_since(cutoff, UpdatedConcepts::Include)
+        .unwrap();
+    assert_eq!(included.len(), 1);
+    assert!(included[0].relations.is_empty());
+    let labels: Vec<&str> = included[0]
+        .concepts
+        .iter()
+        .map(|c| c.label.as_str())
+        .collect();
+    assert_eq!(labels, vec!["0-lang"]);
+    assert_eq!(included[0].concepts[0].confidence, 0.99);
+}
+
+#[test]
+fn export_since_rejects_invalid_cutoff() {
+    let store = populated_store();
+    let err = store
+        .export_since("last tuesday", UpdatedConcepts::Exclude)
+        .unwrap_err();
+    assert_eq!(err.0, "last tuesday");
+}
+
+#[test]
+fn export_since_keeps_episodes_with_unparseable_timestamps() {
+    let mut store = MemoryStore::new();
+    let mut record = compile(&input(
+        "legacy",
+        "2026-02-18T00:00:00Z",
+        &[("agent", "needs", "memory", 0.9)],
+    ))
+    .record;
+    record.relations[0].created_at = EventTime::from_legacy("sometime");
+    store.insert_record(record);
+
+    let records = store
+        .export_since("2030-01-01T00:00:00Z", UpdatedConcepts::Exclude)
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].relations.len(), 1);
+}
+
+const BASE: &str = "https://example.org/memory/";
+
+/// Tuples read back from N-Triples text by the importer.
+fn import(text: &str) -> Vec<SemanticTuple> {
+    let context = input("rdf", "2026-02-18T00:00:00Z", &[]).context;
+    from_ntriples(text, context, &NTriplesOptions::default())
+        .unwrap()
+        .tuples
+}
+
+/// Fact hashes of the fact triples in `text`, with concepts named by the
+/// labels the text gives them.
+fn imported_fact_hashes(text: &str) -> HashSet<FactHash> {
+    let tuples = import(text);
+    let labels: HashMap<&str, &str> = tuples
+        .iter()
+        .filter(|t| t.predicate == "label")
+        .map(|t| (t.subject.as_str(), t.object.as_str()))
+        .collect();
+    tuples
+        .iter()
+        .filter(|t| t.predicate != "label")
+        .map(|t| {
+            hasher::fact_hash(
+                labels[t.subject.as_str()],
+                &t.predicate,
+                labels[t.object.as_str()],
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn ntriples_round_trip_through_the_importer() {
+    let record = compile(&input(
+        "session_1",
+        "2026-02-18T00:00:00Z",
+        &[
+            ("agent", "needs", "memory", 0.9),
+            ("agent", "uses", "0-lang", 0.8),
+            ("memory", "is_a", "resource", 0.7),
+            ("agent", "needs", "memory", 0.95),
+        ],
+    ))
+    .record;
+    let text = to_ntriples(&record, BASE);
+    assert!(text.contains(&format!(
+        "<https://example.org/memory/concept/{}>",
+        record.concepts[0].hash
+    )));
+    assert!(text.contains("<https://example.org/memory/pred/needs>"));
+
+    let expected: HashSet<FactHash> = record
+        .relations
+        .iter()
+        .map(|r| r.fact_hash.clone())
+        .collect();
+    assert_eq!(imported_fact_hashes(&text), expected);
+    assert_eq!(import(&text).len(), record.concepts.len() + expected.len());
+}
+
+#[test]
+fn ntriples_labels_are_escaped() {
+    let label = "say \"hi\" \\ then\nleave";
+    let text = to_ntriples(&orphan_record(label, "session_1"), BASE);
+    assert!(text.contains(r#""say \"hi\" \\ then\nleave""#), "{}", text);
+    assert_eq!(text.lines().count(), 1);
+    assert_eq!(import(&text)[0].object, label);
+}
+
+#[test]
+fn ntriples_statements_carry_confidence_and_context() {
+    let record = compile(&input(
+        "session_1",
+        "2026-02-18T00:00:00Z",
+        &[
+            ("agent", "needs", "memory", 0.9),
+            ("agent", "uses", "0-lang", 0.8),
+        ],
+    ))
+    .record;
+    let text = to_ntriples_with(&record, BASE, ReifiedStatements::Include);
+    let tuples = import(&text);
+    let objects = |predicate: &str| -> Vec<&str> {
+        tuples
+            .iter()
+            .filter(|t| t.predicate == predicate)
+            .map(|t| t.object.as_str())
+            .collect()
+    };
+    let mut confidences = objects("confidence");
+    confidences.sort_unstable();
+    assert_eq!(confidences, vec!["0.8", "0.9"]);
+    let context = record.context.hash.to_string();
+    assert_eq!(objects("context"), vec![context.as_str(), context.as_str()]);
+    assert_eq!(objects("type"), vec!["Statement", "Statement"]);
+    assert_eq!(objects("predicate").len(), 2);
+
+    // The reified statements do not change the facts.
+    let plain = to_ntriples(&record, BASE);
+    assert!(plain.lines().all(|line| text.contains(line)));
+}
+
+#[test]
+fn ntriples_leave_negative_facts_to_statements() {
+    let mut negative = input(
+        "session_1",
+        "2026-02-18T00:00:00Z",
+        &[("agent", "likes", "rain", 0.9)],
+    );
+    negative.tuples[0].polarity = Polarity::Negative;
+    let record = compile(&negative).record;
+
+    let text = to_ntriples(&record, BASE);
+    assert!(import(&text).iter().all(|t| t.predicate == "label"));
+
+    let reified = to_ntriples_with(&record, BASE, ReifiedStatements::Include);
+    let tuples = import(&reified);
+    let polarity = tuples.iter().find(|t| t.predicate == "polarity").unwrap();
+    assert_eq!(polarity.object, "negative");
+}
+
+#[test]
+fn store_ntriples_hold_each_fact_once() {
+    let store = populated_store();
+    let text = store.export_ntriples(BASE);
+
+    let expected: HashSet<FactHash> = store.relations().map(|r| r.fact_hash.clone()).collect();
+    assert_eq!(expected.len(), store.fact_count());
+    assert_eq!(imported_fact_hashes(&text), expected);
+    assert_eq!(
+        import(&text).len(),
+        store.concept_count() + store.fact_count()
+    );
+
+    let reified = store.export_ntriples_with(BASE, ReifiedStatements::Include);
+    let statements = import(&reified)
+        .into_iter()
+        .filter(|t| t.predicate == "type")
+        .count();
+    assert_eq!(statements, store.relation_count());
+}
+
+/// The value of `node`'s `<data>` child for `key`, if it has one.
+fn graphml_data<'a>(node: roxmltree::Node<'a, '_>, key: &str) -> Option<&'a str> {
+    node.children()
+        .find(|c| c.has_tag_name("data") && c.attribute("key") == Some(key))
+        .map(|c| c.text().unwrap_or(""))
+}
+
+fn graphml_elements<'a, 'i>(
+    doc: &'a roxmltree::Document<'i>,
+    tag: &str,
+) -> Vec<roxmltree::Node<'a, 'i>> {
+    doc.descendants().filter(|n| n.has_tag_name(tag)).collect()
+}
+
+#[test]
+fn graphml_has_a_node_per_concept_and_an_edge_per_fact() {
+    let store = populated_store();
+    let xml = to_graphml(&store);
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+
+    assert_eq!(graphml_elements(&doc, "node").len(), store.concept_count());
+    let edges = graphml_elements(&doc, "edge");
+    assert_eq!(edges.len(), store.fact_count());
+    assert!(store.relation_count() > store.fact_count());
+
+    // `agent needs memory` was observed in both sessions.
+    let needs = edges
+        .iter()
+        .find(|e| graphml_data(**e, "predicate") == Some("needs"))
+        .unwrap();
+    assert_eq!(graphml_data(*needs, "episode_count"), Some("2"));
+    assert_eq!(graphml_data(*needs, "max_confidence"), Some("0.95"));
+    let agent = hasher::concept_hash("agent");
+    assert_eq!(
+        needs.attribute("source"),
+        Some(format!("c{}", agent).as_str())
+    );
+
+    let nodes = graphml_elements(&doc, "node");
+    let agent_node = nodes
+        .iter()
+        .find(|n| n.attribute("id") == needs.attribute("source"))
+        .unwrap();
+    assert_eq!(graphml_data(*agent_node, "label"), Some("agent"));
+    assert_eq!(
+        graphml_data(*agent_node, "created_at"),
+        Some("2026-02-18T00:00:00Z")
+    );
+}
+
+#[test]
+fn graphml_escapes_labels() {
+    let label = "<script> & \"friends\" 'n' \u{1} done";
+    let mut record = orphan_record(label, "session_1");
+    let other = orphan_record("b&b", "session_1").concepts.remove(0);
+    let fact = hasher::fact_hash_with_polarity(label, "<likes>", "b&b", Polarity::Negative);
+    record.relations.push(RelationNode {
+        episode_hash: hasher::episode_hash(&fact, &record.context.hash),
+        fact_hash: fact,
+        subject_hash: record.concepts[0].hash.clone(),
+        predicate: "<likes>".into(),
+        object_hash: other.hash.clone(),
+        confidence: 0.5,
+        context_hash: record.context.hash.clone(),
+        created_at: record.context.meta.event_time,
+        polarity: Polarity::Negative,
+        valid_from: None,
+        valid_to: None,
+        subject_span: None,
+        object_span: None,
+        evidence_span: None,
+    });
+    record.concepts.push(other);
+
+    let xml = record_to_graphml(&record);
+    assert!(!xml.contains("<script>"));
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let labels: Vec<&str> = graphml_elements(&doc, "node")
+        .into_iter()
+        .filter_map(|n| graphml_data(n, "label"))
+        .collect();
+    assert!(labels.contains(&"b&b"));
+    assert!(labels.contains(&"<script> & \"friends\" 'n' \u{fffd} done"));
+
+    let edges = graphml_elements(&doc, "edge");
+    assert_eq!(edges.len(), 1);
+    assert_eq!(graphml_data(edges[0], "predicate"), Some("<likes>"));
+    assert_eq!(graphml_data(edges[0], "polarity"), Some("negative"));
+}
+
+/// DOT of `dot_store()`, drawn whole.
+const GOLDEN_DOT: &str = include_str!("golden/memory.dot");
+
+/// Quotes, backslashes, a negative fact, and a fact seen twice.
+fn dot_store() -> MemoryStore {
+    let mut first = input(
+        "session_1",
+        "2026-02-18T00:00:00Z",
+        &[
+            ("Ada \"the Countess\" Lovelace", "wrote", "Notes", 0.9),
+            (
+                "Ada \"the Countess\" Lovelace",
+                "knew",
+                "Charles Babbage",
+                0.8,
+            ),
+            ("Charles Babbage", "designed", "Analytical Engine", 0.6),
+            ("Analytical Engine", "kept_in", "C:\\Museum\\Archive", 0.4),
+            ("Charles Babbage", "likes", "Street Music", 0.7),
+        ],
+    );
+    first.tuples[4].polarity = Polarity::Negative;
+    let second = input(
+        "session_2",
+        "2026-02-19T00:00:00Z",
+        &[("Charles Babbage", "designed", "Analytical Engine", 0.95)],
+    );
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&first).record);
+    store.insert_record(compile(&second).record);
+    store
+}
+
+/// Node labels of a DOT graph, in order.
+fn dot_labels(dot: &str) -> Vec<&str> {
+    dot.lines()
+        .filter(|line| !line.contains("->"))
+        .filter_map(|line| line.split_once("[label=\"")?.1.strip_suffix("\"];"))
+        .collect()
+}
+
+fn dot_edge_count(dot: &str) -> usize {
+    dot.lines().filter(|line| line.contains("->")).count()
+}
+
+#[test]
+fn dot_matches_the_golden_file() {
+    assert_eq!(to_dot(&dot_store(), DotOptions::default()), GOLDEN_DOT);
+}
+
+#[test]
+fn dot_escapes_labels() {
+    let dot = to_dot(&dot_store(), DotOptions::default());
+    assert!(dot.contains(r#"[label="Ada \"the Countess\" Lovelace"]"#));
+    assert!(dot.contains(r#"[label="C:\\Museum\\Archive"]"#));
+}
+
+#[test]
+fn dot_draws_a_subgraph_around_a_root() {
+    let store = dot_store();
+    let around = |depth| {
+        to_dot(
+            &store,
+            DotOptions {
+                root: Some("street music".to_string()),
+                depth,
+            },
+        )
+    };
+
+    let alone = around(0);
+    assert_eq!(dot_labels(&alone), vec!["Street Music"]);
+    assert_eq!(dot_edge_count(&alone), 0);
+
+    let near = around(1);
+    assert_eq!(dot_labels(&near), vec!["Charles Babbage", "Street Music"]);
+    assert_eq!(dot_edge_count(&near), 1);
+
+    let wider = around(2);
+    assert_eq!(
+        dot_labels(&wider),
+        vec![
+            "Ada \\\"the Countess\\\" Lovelace",
+            "Analytical Engine",
+            "Charles Babbage",
+            "Street Music"
+        ]
+    );
+    assert_eq!(dot_edge_count(&wider), 3);
+
+    assert_eq!(around(10), to_dot(&store, DotOptions::default()));
+}
+
+#[test]
+fn dot_of_an_unknown_root_is_empty() {
+    let dot = to_dot(
+        &dot_store(),
+        DotOptions {
+            root: Some("nobody".to_string()),
+            depth: 3,
+        },
+    );
+    assert_eq!(dot, "digraph memory {\n  node [shape=box];\n}\n");
+}
+
+#[test]
+fn cypher_merges_every_concept_context_fact_and_episode() {
+    let store = populated_store();
+    let cypher = to_cypher(&store);
+    let count = |pattern: &str| cypher.lines().filter(|l| l.contains(pattern)).count();
+    assert_eq!(count("MERGE (c:Concept "), store.concept_count());
+    assert_eq!(count("MERGE (x:Context "), store.context_count());
+    assert_eq!(count("[f:FACT "), store.fact_count());
+    assert_eq!(count("MERGE (e:Episode "), store.relation_count());
+    assert!(cypher
+        .lines()
+        .all(|l| l.starts_with("M") && l.ends_with(';')));
+    assert!(!cypher.contains("CREATE"));
+
+    // `agent needs memory` was observed in both sessions.
+    let needs = hasher::fact_hash("agent", "needs", "memory").to_string();
+    let fact = cypher
+        .lines()
+        .find(|l| l.contains("[f:FACT ") && l.contains(&needs))
+        .unwrap();
+    assert!(
+        fact.ends_with("f.polarity = 'positive', f.episode_count = 2, f.confidence = 0.95;"),
+        "{}",
+        fact
+    );
+    assert_eq!(count(&format!("SET e.fact_hash = '{}'", needs)), 2);
+}
+
+#[test]
+fn cypher_escapes_strings() {
+    let label = "it's a \\ path\n\"next\" ☕ café\u{7}";
+    let mut store = MemoryStore::new();
+    store.insert_record(orphan_record(label, "session_1"));
+    let cypher = to_cypher(&store);
+    assert!(
+        cypher.contains(r#"c.label = 'it\'s a \\ path\n"next" ☕ café\u0007'"#),
+        "{}",
+        cypher
+    );
+    assert_eq!(cypher.lines().count(), 2);
+}
+
+#[test]
+fn cypher_confidences_are_floats() {
+    let mut record = orphan_record("agent", "session_1");
+    record.concepts[0].confidence = 1.0;
+    let mut store = MemoryStore::new();
+    store.insert_record(record);
+    assert!(to_cypher(&store).contains("c.confidence = 1.0,"));
+}
+
+#[test]
+fn streamed_cypher_matches() {
+    let store = populated_store();
+    let mut streamed = Vec::new();
+    write_cypher(&store, &mut streamed).unwrap();
+    assert_eq!(String::from_utf8(streamed).unwrap(), to_cypher(&store));
+}
+
+fn store_of(tuples: &[(&str, &str, &str, f64)]) -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&input("session_1", "2026-02-18T00:00:00Z", tuples)).record);
+    store
+}
+
+fn mermaid_nodes(diagram: &str) -> Vec<&str> {
+    diagram
+        .lines()
+        .skip(1)
+        .filter(|l| !l.contains("->") && !l.contains("more["))
+        .collect()
+}
+
+fn mermaid_edges(diagram: &str) -> Vec<&str> {
+    diagram.lines().filter(|l| l.contains("->")).collect()
+}
+
+#[test]
+fn mermaid_draws_concepts_and_facts() {
+    let diagram = to_mermaid(&dot_store(), None, 0);
+    assert!(diagram.starts_with("graph TD\n"));
+    assert_eq!(mermaid_nodes(&diagram).len(), 6);
+    let edges = mermaid_edges(&diagram);
+    assert_eq!(edges.len(), 5);
+    assert_eq!(
+        edges
+            .iter()
+            .filter(|e| e.contains(" -.->|\"likes\"| "))
+            .count(),
+        1
+    );
+    assert!(diagram.contains(r#"["Ada #34;the Countess#34; Lovelace"]"#));
+    assert!(diagram.contains(r#"["C:\Museum\Archive"]"#));
+    assert!(!diagram.contains("more["));
+}
+
+#[test]
+fn mermaid_sanitizes_pipes_and_brackets() {
+    let store = store_of(&[("a|b", "maps_to", "[x] {y}", 0.9)]);
+    let diagram = to_mermaid(&store, None, 0);
+    assert!(diagram.contains(r#"["a#124;b"]"#), "{}", diagram);
+    assert!(
+        diagram.contains(r##"["#91;x#93; #123;y#125;"]"##),
+        "{}",
+        diagram
+    );
+    assert!(mermaid_edges(&diagram)[0].contains(r#"-->|"maps_to"|"#));
+}
+
+#[test]
+fn mermaid_truncates_labels_and_node_count() {
+    let leaves: Vec<String> = (0..MERMAID_MAX_NODES + 9)
+        .map(|i| format!("leaf {:02}", i))
+        .collect();
+    let mut tuples: Vec<(&str, &str, &str, f64)> = leaves
+        .iter()
+        .map(|l| ("hub", "links", l.as_str(), 0.9))
+        .collect();
+    let long = "x".repeat(MERMAID_MAX_LABEL + 20);
+    tuples.push(("hub", "names", long.as_str(), 0.9));
+    let store = store_of(&tuples);
+    assert_eq!(store.concept_count(), MERMAID_MAX_NODES + 11);
+
+    let hub = hasher::concept_hash("hub");
+    for diagram in [
+        to_mermaid(&store, None, 0),
+        to_mermaid(&store, Some(&hub), 1),
+    ] {
+        assert_eq!(mermaid_nodes(&diagram).len(), MERMAID_MAX_NODES);
+        assert!(
+            diagram.ends_with("  more[\"…and 11 more\"]\n"),
+            "{}",
+            diagram
+        );
+        assert_eq!(mermaid_edges(&diagram).len(), MERMAID_MAX_NODES - 1);
+        assert!(diagram.contains("[\"hub\"]"));
+        assert!(!diagram.contains("leaf 58"));
+    }
+
+    let short = store_of(&[("hub", "names", long.as_str(), 0.9)]);
+    let diagram = to_mermaid(&short, None, 0);
+    let cut = format!("[\"{}…\"]", "x".repeat(MERMAID_MAX_LABEL - 1));
+    assert!(diagram.contains(&cut), "{}", diagram);
+}
+
+#[test]
+fn mermaid_walks_a_cycle_once() {
+    let store = store_of(&[
+        ("a", "next", "b", 0.9),
+        ("b", "next", "c", 0.9),
+        ("c", "next", "d", 0.9),
+        ("d", "next", "a", 0.9),
+    ]);
+    let a = hasher::concept_hash("a");
+
+    let near = to_mermaid(&store, Some(&a), 1);
+    assert_eq!(mermaid_nodes(&near).len(), 3);
+    assert_eq!(mermaid_edges(&near).len(), 2);
+    assert!(!near.contains("[\"c\"]"));
+
+    let whole = to_mermaid(&store, Some(&a), 2);
+    assert_eq!(mermaid_nodes(&whole).len(), 4);
+    assert_eq!(mermaid_edges(&whole).len(), 4);
+    assert_eq!(to_mermaid(&store, Some(&a), 100), whole);
+    // The root comes first.
+    assert!(mermaid_nodes(&whole)[0].ends_with("[\"a\"]"));
+}
+
+#[test]
+fn mermaid_of_an_unknown_root_is_empty() {
+    let store = store_of(&[("a", "next", "b", 0.9)]);
+    let nobody = hasher::concept_hash("nobody");
+    assert_eq!(to_mermaid(&store, Some(&nobody), 3), "graph TD\n");
+}
@@ -0,0 +1,264 @@
+use zero_memory::compiler::hasher;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+const AT: &str = "2026-03-01T00:00:00Z";
+
+fn context(scope: &str) -> ContextNode {
+    let meta = ContextMeta {
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+        source: "test".to_string(),
+        scope: scope.to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    };
+    ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    }
+}
+
+fn concept(label: &str) -> ConceptNode {
+    ConceptNode {
+        hash: hasher::concept_hash(label),
+        label: label.to_string(),
+        aliases: vec![],
+        confidence: 0.9,
+        created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        updated_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        display_label: None,
+    }
+}
+
+/// One record observing `subject -predicate-> object` in context `scope`.
+fn observation(subject: &str, predicate: &str, object: &str, scope: &str) -> MemoryRecord {
+    let ctx = context(scope);
+    let fact = hasher::fact_hash(subject, predicate, object);
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts: vec![concept(subject), concept(object)],
+        relations: vec![RelationNode {
+            episode_hash: hasher::episode_hash(&fact, &ctx.hash),
+            fact_hash: fact,
+            subject_hash: hasher::concept_hash(subject),
+            predicate: predicate.into(),
+            object_hash: hasher::concept_hash(object),
+            confidence: 0.9,
+            context_hash: ctx.hash.clone(),
+            created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+            polarity: Polarity::Positive,
+            valid_from: None,
+            valid_to: None,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        }],
+        context: ctx,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+fn store_with_two_facts() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation("agent", "needs", "memory", "s1"));
+    store.insert_record(observation("agent", "uses", "tools", "s1"));
+    store
+}
+
+#[test]
+fn forgotten_fact_is_hidden_from_reads() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+
+    let known = store
+        .forget_fact(&fact, "user asked", AT, Reobservation::Suppress)
+        .unwrap();
+
+    assert!(known);
+    assert!(store.is_forgotten(&fact));
+    assert!(store.get_relations_by_fact(&fact).is_empty());
+    let agent = hasher::concept_hash("agent");
+    let visible = store.get_relations(&agent);
+    assert_eq!(visible.len(), 1);
+    assert_eq!(&*visible[0].predicate, "uses");
+    assert!(store
+        .get_relations(&hasher::concept_hash("memory"))
+        .is_empty());
+    assert_eq!(store.relations().count(), 1);
+    assert_eq!(store.relation_count(), 2, "episodes are kept, not deleted");
+}
+
+#[test]
+fn forgotten_fact_stays_auditable() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "user asked", AT, Reobservation::Suppress)
+        .unwrap();
+
+    let tombstones: Vec<&Tombstone> = store.get_forgotten().collect();
+    assert_eq!(tombstones.len(), 1);
+    assert_eq!(tombstones[0].fact_hash, fact);
+    assert_eq!(tombstones[0].reason, "user asked");
+    assert_eq!(tombstones[0].forgotten_at, AT);
+
+    let episodes = store.get_forgotten_relations(&fact);
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(&*episodes[0].predicate, "needs");
+
+    let live = hasher::fact_hash("agent", "uses", "tools");
+    assert!(store.get_forgotten_relations(&live).is_empty());
+}
+
+#[test]
+fn forget_rejects_invalid_timestamp() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+
+    assert!(store
+        .forget_fact(&fact, "why", "yesterday", Reobservation::Suppress)
+        .is_err());
+    assert!(!store.is_forgotten(&fact));
+}
+
+#[test]
+fn unknown_fact_can_be_forgotten_pre_emptively() {
+    let mut store = MemoryStore::new();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+
+    let known = store
+        .forget_fact(&fact, "never store this", AT, Reobservation::Suppress)
+        .unwrap();
+    assert!(!known);
+
+    store.insert_record(observation("agent", "needs", "memory", "s1"));
+    assert!(store.get_relations_by_fact(&fact).is_empty());
+    assert_eq!(store.get_forgotten_relations(&fact).len(), 1);
+}
+
+#[test]
+fn suppress_policy_keeps_reobserved_fact_hidden() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "stale", AT, Reobservation::Suppress)
+        .unwrap();
+
+    let result = store.insert_record(observation("agent", "needs", "memory", "s2"));
+
+    assert_eq!(result.new_episodes, 1, "the new episode is still recorded");
+    assert_eq!(result.resurrected, 0);
+    assert!(store.is_forgotten(&fact));
+    assert!(store.get_relations_by_fact(&fact).is_empty());
+    assert_eq!(store.get_forgotten_relations(&fact).len(), 2);
+}
+
+#[test]
+fn suppressed_facts_are_not_reinforced() {
+    let mut store = store_with_two_facts();
+    store.set_reinforcement(0.5);
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "stale", AT, Reobservation::Suppress)
+        .unwrap();
+
+    let result = store.insert_record(observation("agent", "needs", "memory", "s1"));
+
+    assert_eq!(result.reinforced, 0);
+    assert_eq!(store.get_forgotten_relations(&fact)[0].confidence, 0.9);
+}
+
+#[test]
+fn resurrected_facts_are_reinforced() {
+    let mut store = store_with_two_facts();
+    store.set_reinforcement(0.5);
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "stale", AT, Reobservation::Resurrect)
+        .unwrap();
+
+    let result = store.insert_record(observation("agent", "needs", "memory", "s1"));
+
+    assert_eq!(result.resurrected, 1);
+    assert_eq!(result.reinforced, 1);
+    assert!((store.get_relations_by_fact(&fact)[0].confidence - 0.95).abs() < 1e-9);
+}
+
+#[test]
+fn resurrect_policy_lifts_tombstone_on_reobservation() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "stale", AT, Reobservation::Resurrect)
+        .unwrap();
+
+    let result = store.insert_record(observation("agent", "needs", "memory", "s2"));
+
+    assert_eq!(result.resurrected, 1);
+    assert!(!store.is_forgotten(&fact));
+    assert_eq!(
+        store.get_relations_by_fact(&fact).len(),
+        2,
+        "old and new episodes are visible again"
+    );
+    assert_eq!(store.get_forgotten().count(), 0);
+}
+
+#[test]
+fn unforget_restores_visibility() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "oops", AT, Reobservation::Suppress)
+        .unwrap();
+
+    let tombstone = store.unforget_fact(&fact).unwrap();
+
+    assert_eq!(tombstone.reason, "oops");
+    assert_eq!(store.get_relations_by_fact(&fact).len(), 1);
+    assert!(store.unforget_fact(&fact).is_none());
+}
+
+#[test]
+fn tombstones_survive_snapshots() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "user asked", AT, Reobservation::Resurrect)
+        .unwrap();
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+
+    for loaded in [
+        MemoryStore::load_json(json.as_slice()).unwrap(),
+        MemoryStore::load_binary(binary.as_slice()).unwrap(),
+    ] {
+        assert_eq!(loaded.get_tombstone(&fact), store.get_tombstone(&fact));
+        assert!(loaded.get_relations_by_fact(&fact).is_empty());
+        assert_eq!(loaded.get_forgotten_relations(&fact).len(), 1);
+    }
+}
+
+#[test]
+fn export_skips_forgotten_facts() {
+    let mut store = store_with_two_facts();
+    let fact = hasher::fact_hash("agent", "needs", "memory");
+    store
+        .forget_fact(&fact, "user asked", AT, Reobservation::Suppress)
+        .unwrap();
+
+    let relations: Vec<RelationNode> = store
+        .export_records()
+        .into_iter()
+        .flat_map(|r| r.relations)
+        .collect();
+
+    assert_eq!(relations.len(), 1);
+    assert_eq!(&*relations[0].predicate, "uses");
+}
@@ -1,5 +1,7 @@
+use zero_memory::compiler::emitter::compile;
 use zero_memory::compiler::hasher;
-use zero_memory::types::ContextMeta;
+use zero_memory::compiler::normalizer::{normalize_label_with, CaseMode};
+use zero_memory::types::*;
 
 #[test]
 fn same_label_produces_same_concept_hash() {
@@ -30,7 +32,7 @@ fn same_fact_different_context_produces_different_episode_hash() {
     let fh = hasher::fact_hash("agent", "needs", "memory");
 
     let ctx1 = ContextMeta {
-        event_time: "2026-02-18T00:00:00Z".to_string(),
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
         source: "user_prompt".to_string(),
         scope: "session_1".to_string(),
         agent_id: None,
@@ -38,7 +40,7 @@ fn same_fact_different_context_produces_different_episode_hash() {
         metadata: None,
     };
     let ctx2 = ContextMeta {
-        event_time: "2026-02-19T00:00:00Z".to_string(),
+        event_time: "2026-02-19T00:00:00Z".parse().unwrap(),
         source: "observation".to_string(),
         scope: "session_2".to_string(),
         agent_id: None,
@@ -64,30 +66,186 @@ fn concept_hash_is_stable_known_value() {
     let expected = Sha256::digest(b"agent");
     let h = hasher::concept_hash("agent");
     assert_eq!(
-        h.0,
+        h.as_bytes(),
         expected.as_slice(),
         "ConceptHash must be raw sha256 of label bytes"
     );
 }
 
+#[cfg(feature = "nfc")]
+#[test]
+fn composed_and_decomposed_labels_are_one_concept() {
+    use sha2::{Digest, Sha256};
+    let tuple = |subject: &str| SemanticTuple::from((subject, "serves", "Coffee", 0.9));
+    let output = compile(&CompilerInput {
+        utterance: None,
+        tuples: vec![tuple("Caf\u{e9}"), tuple("Cafe\u{301}")],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    });
+    assert_eq!(output.record.concepts.len(), 2);
+    assert_eq!(output.record.relations.len(), 1);
+    let cafe = output
+        .record
+        .concepts
+        .iter()
+        .find(|c| c.label == "caf\u{e9}")
+        .unwrap();
+    assert_eq!(
+        cafe.hash.as_bytes(),
+        Sha256::digest("caf\u{e9}".as_bytes()).as_slice()
+    );
+}
+
+#[test]
+fn ascii_labels_hash_as_before_normalization() {
+    let agent = compile(&CompilerInput {
+        utterance: None,
+        tuples: vec![SemanticTuple::from(("  Agent ", "Needs", "Memory", 0.9))],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    });
+    assert_eq!(
+        agent.record.concepts[0].hash.to_string(),
+        "d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+    );
+}
+
 #[test]
 fn fact_hash_uses_pipe_separator() {
     use sha2::{Digest, Sha256};
     let expected = Sha256::digest(b"agent|needs|memory");
     let h = hasher::fact_hash("agent", "needs", "memory");
     assert_eq!(
-        h.0,
+        h.as_bytes(),
         expected.as_slice(),
         "FactHash must be sha256 of 's|p|o'"
     );
 }
 
+fn context(event_time: &str, source: &str, scope: &str) -> ContextMeta {
+    ContextMeta {
+        event_time: event_time.parse().unwrap(),
+        source: source.to_string(),
+        scope: scope.to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    }
+}
+
+#[test]
+fn pipes_inside_labels_do_not_pass_for_separators() {
+    assert_ne!(
+        hasher::fact_hash("a|b", "c", "d"),
+        hasher::fact_hash("a", "b|c", "d")
+    );
+    assert_ne!(
+        hasher::fact_hash("a", "b", "c|d"),
+        hasher::fact_hash("a", "b|c", "d")
+    );
+    assert_ne!(
+        hasher::fact_hash_with_polarity("a", "b", "c|neg", Polarity::Positive),
+        hasher::fact_hash_with_polarity("a", "b", "c", Polarity::Negative)
+    );
+}
+
+#[test]
+fn escapes_cannot_be_forged() {
+    // Escaping only pipes, both would hash `a\|b|c\|d`: a trailing
+    // backslash in a label would pass for an escape.
+    assert_ne!(
+        hasher::fact_hash("a\\", "b", "c|d"),
+        hasher::fact_hash("a|b", "c\\", "d")
+    );
+}
+
+#[test]
+fn pipes_inside_context_fields_do_not_pass_for_separators() {
+    let colliding = [
+        context("2026-02-18T00:00:00Z", "a|b", "c"),
+        context("2026-02-18T00:00:00Z", "a", "b|c"),
+    ];
+    let hashes: Vec<ContextHash> = colliding.iter().map(hasher::context_hash).collect();
+    assert_ne!(hashes[0], hashes[1]);
+}
+
+#[test]
+fn pipes_inside_qualifiers_do_not_pass_for_separators() {
+    let fh = hasher::fact_hash("alice", "met", "bob");
+    let ch = hasher::context_hash(&context("2026-02-18T00:00:00Z", "s", "t"));
+    let qualified = |key: &str, value: &str| {
+        hasher::qualified_episode_hash(&fh, &ch, &[(key.to_string(), value.to_string())])
+    };
+    assert_ne!(qualified("a|b", "c"), qualified("a", "b|c"));
+}
+
+#[test]
+fn escaped_joins_split_back_one_way_only() {
+    let atoms = ["", "a", "|", "\\"];
+    let mut parts: Vec<String> = Vec::new();
+    for x in atoms {
+        for y in atoms {
+            parts.push(format!("{}{}", x, y));
+        }
+    }
+    parts.sort();
+    parts.dedup();
+
+    let mut seen = std::collections::HashMap::new();
+    for a in &parts {
+        for b in &parts {
+            for c in &parts {
+                let joined = hasher::join_escaped(&[a, b, c]);
+                if let Some(earlier) = seen.insert(joined.clone(), (a, b, c)) {
+                    panic!(
+                        "{:?} and {:?} both join to {:?}",
+                        earlier,
+                        (a, b, c),
+                        joined
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hashes_without_pipes_or_backslashes_are_unchanged() {
+    use sha2::{Digest, Sha256};
+    assert_eq!(
+        hasher::fact_hash_with_polarity("agent", "needs", "memory", Polarity::Negative).as_bytes(),
+        Sha256::digest(b"agent|needs|memory|neg").as_slice()
+    );
+    let ctx = context("2026-02-18T00:00:00Z", "user_prompt", "s1");
+    assert_eq!(
+        hasher::context_hash(&ctx).as_bytes(),
+        Sha256::digest(b"2026-02-18T00:00:00Z|user_prompt|s1").as_slice()
+    );
+    assert_eq!(
+        hasher::fact_hash("a|b", "c", "d").as_bytes(),
+        Sha256::digest(b"a\\|b|c|d").as_slice()
+    );
+}
+
 #[test]
 fn episode_hash_concatenates_raw_bytes() {
     use sha2::{Digest, Sha256};
     let fh = hasher::fact_hash("agent", "needs", "memory");
     let ctx = ContextMeta {
-        event_time: "2026-02-18T00:00:00Z".to_string(),
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
         source: "test".to_string(),
         scope: "test_scope".to_string(),
         agent_id: None,
@@ -98,11 +256,11 @@ fn episode_hash_concatenates_raw_bytes() {
     let eh = hasher::episode_hash(&fh, &ch);
 
     let mut combined = Vec::new();
-    combined.extend_from_slice(&fh.0);
-    combined.extend_from_slice(&ch.0);
+    combined.extend_from_slice(fh.as_bytes());
+    combined.extend_from_slice(ch.as_bytes());
     let expected = Sha256::digest(&combined);
     assert_eq!(
-        eh.0,
+        eh.as_bytes(),
         expected.as_slice(),
         "EpisodeHash must be sha256(fact_bytes + ctx_bytes)"
     );
@@ -118,3 +276,237 @@ fn hash_display_format_is_lowercase_hex() {
         "Must be valid hex"
     );
 }
+
+fn trace_input() -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: vec![
+            SemanticTuple::from(("Agent", "needs", "Memory", 0.98)),
+            SemanticTuple {
+                subject: "Agent".to_string(),
+                predicate: "likes".to_string(),
+                object: "Noise".to_string(),
+                confidence: 0.4,
+                polarity: Polarity::Negative,
+                valid_from: Some("2026-01-01".to_string()),
+                ..Default::default()
+            },
+        ],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "test".to_string(),
+            scope: "test_scope".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+/// One concept and one relation, with hashes of repeated bytes so the
+/// canonical bytes can be written out by hand.
+fn tiny_record() -> MemoryRecord {
+    let context = ContextNode {
+        hash: ContextHash::from_bytes([2; 32]),
+        meta: trace_input().context,
+    };
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts: vec![ConceptNode {
+            hash: ConceptHash::from_bytes([1; 32]),
+            label: "agent".to_string(),
+            aliases: vec!["Agent".to_string()],
+            confidence: 0.9,
+            created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+            updated_at: "2026-02-19T00:00:00Z".parse().unwrap(),
+            display_label: None,
+        }],
+        relations: vec![RelationNode {
+            fact_hash: FactHash::from_bytes([3; 32]),
+            episode_hash: EpisodeHash::from_bytes([4; 32]),
+            subject_hash: ConceptHash::from_bytes([1; 32]),
+            predicate: "needs".into(),
+            object_hash: ConceptHash::from_bytes([1; 32]),
+            confidence: 0.98,
+            context_hash: context.hash.clone(),
+            created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+            polarity: Polarity::Positive,
+            valid_from: Some("2026-01-01".to_string()),
+            valid_to: None,
+            subject_span: Some(Span { start: 0, end: 5 }),
+            object_span: None,
+            evidence_span: None,
+        }],
+        context,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+#[test]
+fn canonical_record_bytes_are_fixed_json() {
+    let expected = format!(
+        concat!(
+            r#"{{"context":"{ctx}","#,
+            r#""concepts":[{{"hash":"{c}","label":"agent","aliases":["Agent"],"confidence":0.9,"created_at":"2026-02-18T00:00:00Z","updated_at":"2026-02-19T00:00:00Z"}}],"#,
+            r#""relations":[{{"episode_hash":"{e}","fact_hash":"{f}","subject_hash":"{c}","predicate":"needs","object_hash":"{c}","#,
+            r#""confidence":0.98,"polarity":"positive","valid_from":"2026-01-01","valid_to":null}}]}}"#
+        ),
+        ctx = "02".repeat(32),
+        c = "01".repeat(32),
+        e = "04".repeat(32),
+        f = "03".repeat(32),
+    );
+    let bytes = hasher::canonical_record_bytes(&tiny_record());
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+}
+
+#[test]
+fn canonical_record_bytes_ignore_order_and_what_the_graph_lacks() {
+    let record = compile(&trace_input()).record;
+    let bytes = hasher::canonical_record_bytes(&record);
+
+    let mut reordered = record.clone();
+    reordered.concepts.reverse();
+    reordered.relations.reverse();
+    assert_eq!(hasher::canonical_record_bytes(&reordered), bytes);
+
+    let mut annotated = record.clone();
+    annotated.relations[0].created_at = "2030-01-01T00:00:00Z".parse().unwrap();
+    annotated.relations[0].evidence_span = Some(Span { start: 0, end: 1 });
+    annotated.concepts[0].display_label = Some("Shown".to_string());
+    annotated.context.meta.agent_id = Some("agent-7".to_string());
+    annotated.proof = Some(Proof {
+        trace_hash: "00".repeat(32),
+        signer: "00".repeat(32),
+        signature: "00".repeat(64),
+    });
+    assert_eq!(hasher::canonical_record_bytes(&annotated), bytes);
+}
+
+#[test]
+fn trace_hash_is_sha256_of_the_canonical_bytes() {
+    use sha2::{Digest, Sha256};
+    let record = compile(&trace_input()).record;
+    let expected = Sha256::digest(hasher::canonical_record_bytes(&record));
+    assert_eq!(hasher::trace_hash(&record), expected.as_slice());
+}
+
+#[test]
+fn trace_hash_is_stable_known_value() {
+    let record = compile(&trace_input()).record;
+    assert_eq!(
+        hex::encode(hasher::trace_hash(&record)),
+        "62770b72fa5f2a3515a11dfb23741c4d67701a17ae504f37ac78e34e932b1fd1",
+        "trace hash of a fixed record must not change"
+    );
+}
+
+#[test]
+fn trace_hash_ignores_concept_and_relation_order() {
+    let record = compile(&trace_input()).record;
+    let mut reversed = record.clone();
+    reversed.concepts.reverse();
+    reversed.relations.reverse();
+    assert_eq!(hasher::trace_hash(&reversed), hasher::trace_hash(&record));
+}
+
+#[test]
+fn trace_hash_covers_what_the_graph_carries() {
+    let record = compile(&trace_input()).record;
+    let trace = hasher::trace_hash(&record);
+
+    let mut changed = record.clone();
+    changed.relations[0].confidence = 0.5;
+    assert_ne!(hasher::trace_hash(&changed), trace);
+
+    let mut changed = record.clone();
+    changed.concepts[0].aliases.push("bot".to_string());
+    assert_ne!(hasher::trace_hash(&changed), trace);
+
+    // Relation timestamps are not in the graph.
+    let mut unchanged = record.clone();
+    unchanged.relations[0].created_at = "2030-01-01T00:00:00Z".parse().unwrap();
+    assert_eq!(hasher::trace_hash(&unchanged), trace);
+}
+
+#[test]
+fn record_hash_is_stable_known_value() {
+    assert_eq!(
+        tiny_record().hash().to_string(),
+        "ee92632508f8ed3963017f3f7ef3a4bc73b4f2e604e82baece16fb58594d2343",
+        "record hash of a fixed record must not change"
+    );
+}
+
+fn label_hash(raw: &str, case: CaseMode) -> ConceptHash {
+    hasher::concept_hash(&normalize_label_with(raw, case))
+}
+
+fn all_hash_alike(labels: &[&str], case: CaseMode) -> bool {
+    labels
+        .windows(2)
+        .all(|pair| label_hash(pair[0], case) == label_hash(pair[1], case))
+}
+
+#[test]
+fn german_sharp_s_folds_to_ss() {
+    let labels = ["Stra\u{df}e", "STRASSE", "strasse", "STRA\u{1e9e}E"];
+    assert!(all_hash_alike(&labels, CaseMode::Fold));
+    assert_eq!(
+        label_hash("Stra\u{df}e", CaseMode::Fold),
+        hasher::concept_hash("strasse")
+    );
+    assert!(!all_hash_alike(&labels, CaseMode::Lower));
+}
+
+#[test]
+fn greek_sigmas_fold_alike() {
+    let labels = [
+        "\u{39f}\u{394}\u{39f}\u{3a3}",
+        "\u{3bf}\u{3b4}\u{3bf}\u{3c2}",
+        "\u{3bf}\u{3b4}\u{3bf}\u{3c3}",
+    ];
+    assert!(all_hash_alike(&labels, CaseMode::Fold));
+    // Lowercasing turns the word-final capital into a final sigma, which a
+    // medial sigma typed at the end does not match.
+    assert!(all_hash_alike(&labels[..2], CaseMode::Lower));
+    assert!(!all_hash_alike(&labels[1..], CaseMode::Lower));
+}
+
+#[test]
+fn turkish_i_folds_the_same_in_every_locale() {
+    assert!(all_hash_alike(&["ISTANBUL", "istanbul"], CaseMode::Fold));
+    // Dotted capital I folds to i and a combining dot above, as its
+    // lowercase form is written.
+    assert!(all_hash_alike(
+        &["\u{130}zmir", "i\u{307}zmir"],
+        CaseMode::Fold
+    ));
+    assert_ne!(
+        label_hash("\u{130}zmir", CaseMode::Fold),
+        label_hash("izmir", CaseMode::Fold)
+    );
+    // Dotless i has no case pair outside Turkish rules, so it stays.
+    assert_ne!(
+        label_hash("\u{131}spanak", CaseMode::Fold),
+        label_hash("ispanak", CaseMode::Fold)
+    );
+}
+
+#[test]
+fn preserve_keeps_the_label_bytes() {
+    assert_eq!(
+        normalize_label_with("  Stra\u{df}e_Name ", CaseMode::Preserve),
+        "Stra\u{df}e-Name"
+    );
+    assert_ne!(
+        label_hash("Agent", CaseMode::Preserve),
+        label_hash("agent", CaseMode::Preserve)
+    );
+    assert_eq!(
+        label_hash("Agent", CaseMode::Fold),
+        label_hash("Agent", CaseMode::Lower)
+    );
+}
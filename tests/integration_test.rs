@@ -10,27 +10,12 @@ fn build_test_input() -> CompilerInput {
             "Build 0-memory, a native agent memory system compiled with 0-lang".to_string(),
         ),
         tuples: vec![
-            SemanticTuple {
-                subject: "Agent".to_string(),
-                predicate: "needs".to_string(),
-                object: "LongTermMemory".to_string(),
-                confidence: 0.98,
-            },
-            SemanticTuple {
-                subject: "0-memory".to_string(),
-                predicate: "solves".to_string(),
-                object: "LongTermMemory".to_string(),
-                confidence: 0.97,
-            },
-            SemanticTuple {
-                subject: "0-memory".to_string(),
-                predicate: "compiled_with".to_string(),
-                object: "0-lang".to_string(),
-                confidence: 0.99,
-            },
+            SemanticTuple::from(("Agent", "needs", "LongTermMemory", 0.98)),
+            SemanticTuple::from(("0-memory", "solves", "LongTermMemory", 0.97)),
+            SemanticTuple::from(("0-memory", "compiled_with", "0-lang", 0.99)),
         ],
         context: ContextMeta {
-            event_time: "2026-02-18T00:00:00Z".to_string(),
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
             source: "user_prompt".to_string(),
             scope: "0-memory_design".to_string(),
             agent_id: None,
@@ -75,7 +60,7 @@ fn full_pipeline_compile_store_retrieve() {
     );
 
     // Step 6: Verify round-trip semantics
-    let predicates: Vec<&str> = rels.iter().map(|r| r.predicate.as_str()).collect();
+    let predicates: Vec<&str> = rels.iter().map(|r| &*r.predicate).collect();
     assert!(
         predicates.contains(&"solves"),
         "Must contain 'solves' relation"
@@ -112,7 +97,7 @@ fn full_pipeline_same_facts_new_context_creates_new_episodes() {
     let input1 = build_test_input();
     let mut input2 = build_test_input();
     input2.context.scope = "different_session".to_string();
-    input2.context.event_time = "2026-02-19T00:00:00Z".to_string();
+    input2.context.event_time = "2026-02-19T00:00:00Z".parse().unwrap();
 
     let output1 = compile(&input1);
     let output2 = compile(&input2);
@@ -161,3 +146,20 @@ fn full_pipeline_graph_text_nonempty_and_valid() {
     assert!(output.graph_text.contains("\"MergeMap\""));
     assert!(!output.graph_text.contains("Aggregate"));
 }
+
+#[test]
+fn full_pipeline_store_accumulates_surface_forms() {
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&build_test_input()).record);
+
+    let mut input = build_test_input();
+    input.context.scope = "later_session".to_string();
+    input.tuples[0].object = "LTM".to_string();
+    store.insert_record(compile(&input).record);
+
+    let memory = store.get_concept_by_label("longtermmemory").unwrap();
+    assert!(memory.aliases.contains(&"LongTermMemory".to_string()));
+    let ltm = store.get_concept_by_label("LTM").unwrap();
+    assert_eq!(ltm.label, "long-term-memory");
+    assert!(ltm.aliases.contains(&"ltm".to_string()));
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use zero_memory::compiler::emitter::{compile, compile_with};
+use zero_memory::compiler::{decompile, hasher, CompilerOptions, RecordIssue};
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+fn context(scope: &str) -> ContextMeta {
+    ContextMeta::builder()
+        .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope(scope)
+        .build()
+        .unwrap()
+}
+
+fn input(scope: &str) -> CompilerInput {
+    let mut met = SemanticTuple::from(("Alice", "met", "Bob", 0.9));
+    met.qualifiers = HashMap::from([
+        ("location".to_string(), "Paris".to_string()),
+        ("day".to_string(), "Tuesday".to_string()),
+    ]);
+    let mut dislikes = SemanticTuple::from(("Alice", "likes", "Mushrooms", 0.7));
+    dislikes.polarity = Polarity::Negative;
+    CompilerInput::builder(context(scope))
+        .tuple("Alice", "needs", "LTM", 0.8)
+        .tuples([met, dislikes])
+        .build()
+        .unwrap()
+}
+
+fn record() -> MemoryRecord {
+    compile(&input("s1")).record
+}
+
+fn issues(record: &MemoryRecord) -> Vec<RecordIssue> {
+    record.validate().unwrap_err()
+}
+
+#[test]
+fn compiled_records_are_valid() {
+    record().validate().unwrap();
+
+    let options = CompilerOptions {
+        namespace: Some("tenant-a".to_string()),
+        ..CompilerOptions::default()
+    };
+    compile_with(&input("s1"), &options)
+        .record
+        .validate()
+        .unwrap();
+
+    let output = compile(&input("s1"));
+    decompile(&output.graph_text).unwrap().validate().unwrap();
+}
+
+#[test]
+fn relations_must_reach_included_concepts() {
+    let mut record = record();
+    let bob = hasher::concept_hash("bob");
+    record.concepts.retain(|c| c.hash != bob);
+
+    let found = issues(&record);
+    assert!(!found.is_empty());
+    assert!(found.iter().all(|issue| matches!(
+        issue,
+        RecordIssue::MissingConcept { concept, .. } if *concept == bob
+    )));
+}
+
+#[test]
+fn relations_must_share_the_record_context() {
+    let mut record = record();
+    let other = compile(&input("s2")).record.context.hash;
+    let episode = record.relations[0].episode_hash.clone();
+    record.relations[0].context_hash = other.clone();
+
+    let found = issues(&record);
+    assert_eq!(
+        found[0],
+        RecordIssue::ForeignContext {
+            episode,
+            context: other,
+        }
+    );
+    // The episode hash no longer follows from the context either.
+    assert!(matches!(found[1], RecordIssue::EpisodeHashMismatch { .. }));
+    assert_eq!(found.len(), 2);
+}
+
+#[test]
+fn hashes_must_recompute() {
+    let mut record = record();
+    record.context.meta.scope = "elsewhere".to_string();
+    record.concepts[0].label = "renamed".to_string();
+    record.relations[0].fact_hash = FactHash::ZERO;
+    record.relations[1].episode_hash = EpisodeHash::ZERO;
+
+    let found = issues(&record);
+    assert!(matches!(found[0], RecordIssue::ContextHashMismatch { .. }));
+    assert!(matches!(
+        &found[1],
+        RecordIssue::ConceptHashMismatch { label, .. } if label == "renamed"
+    ));
+    assert!(found.iter().any(|issue| matches!(
+        issue,
+        RecordIssue::FactHashMismatch { hash, .. } if *hash == FactHash::ZERO
+    )));
+    assert!(found.iter().any(|issue| matches!(
+        issue,
+        RecordIssue::EpisodeHashMismatch { hash, .. } if *hash == EpisodeHash::ZERO
+    )));
+}
+
+#[test]
+fn qualifiers_are_part_of_the_episode() {
+    let mut record = record();
+    let paris = hasher::concept_hash("paris");
+    let index = record
+        .concepts
+        .iter()
+        .position(|c| c.hash == paris)
+        .unwrap();
+    record.concepts[index].label = "london".to_string();
+    record.concepts[index].hash = hasher::concept_hash("london");
+    for relation in &mut record.relations {
+        if relation.object_hash == paris {
+            relation.object_hash = hasher::concept_hash("london");
+        }
+    }
+
+    let found = issues(&record);
+    assert!(found
+        .iter()
+        .any(|issue| matches!(issue, RecordIssue::EpisodeHashMismatch { .. })));
+}
+
+#[test]
+fn episodes_must_be_unique() {
+    let mut record = record();
+    record.relations.push(record.relations[0].clone());
+
+    let episode = record.relations[0].episode_hash.clone();
+    assert_eq!(issues(&record), [RecordIssue::DuplicateEpisode(episode)]);
+}
+
+#[test]
+fn strict_stores_reject_invalid_records_whole() {
+    let mut bad = record();
+    bad.relations[0].fact_hash = FactHash::ZERO;
+
+    let mut store = MemoryStore::new();
+    assert!(!store.strict());
+    store.set_strict(true);
+    let result = store.insert_record(bad.clone());
+    assert_eq!(result.rejected.len(), 1);
+    assert_eq!(result.rejected[0].hash, bad.hash());
+    assert_eq!(result.rejected[0].issues, issues(&bad));
+    assert_eq!(result.new_concepts, 0);
+    assert_eq!(store.concept_count(), 0);
+    assert_eq!(store.relation_count(), 0);
+    assert_eq!(store.contexts().count(), 0);
+
+    let result = store.insert_records([bad.clone(), compile(&input("s2")).record]);
+    assert_eq!(result.rejected.len(), 1);
+    assert_eq!(store.contexts().count(), 1);
+
+    let mut lax = MemoryStore::new();
+    let result = lax.insert_record(bad);
+    assert!(result.rejected.is_empty());
+    assert!(lax.relation_count() > 0);
+}
+
+#[test]
+fn issues_describe_themselves() {
+    let mut record = record();
+    record.relations.push(record.relations[0].clone());
+    let message = issues(&record)[0].to_string();
+    assert!(message.starts_with("episode "));
+    assert!(message.ends_with(" appears more than once"));
+}
@@ -0,0 +1,148 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::CompilerError;
+use zero_memory::error::MemoryError;
+use zero_memory::memory::Memory;
+use zero_memory::types::*;
+
+fn context(scope: &str, event_time: &str) -> ContextMeta {
+    ContextMeta::builder()
+        .event_time(event_time.parse().unwrap())
+        .source("user_prompt")
+        .scope(scope)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn quickstart() {
+    let mut memory = Memory::in_memory();
+
+    let input = CompilerInput::builder(context("conversation_42", "2026-02-18T00:00:00Z"))
+        .utterance("An agent needs long-term memory")
+        .tuple("Agent", "needs", "LongTermMemory", 0.98)
+        .build()
+        .unwrap();
+    let receipt = memory.remember(input).unwrap();
+    assert_eq!(receipt.insert.new_concepts, 2);
+    assert_eq!(receipt.insert.new_facts, 1);
+    assert!(receipt.graph_text.contains("\"needs\""));
+
+    let hits = memory.recall("agent", 10);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(
+        (hits[0].subject.as_str(), hits[0].predicate.as_str()),
+        ("Agent", "needs")
+    );
+    assert_eq!(hits[0].object, "LongTermMemory");
+    assert_eq!(hits[0].confidence, 0.98);
+}
+
+#[test]
+fn recall_ranks_facts_and_folds_their_episodes() {
+    let mut memory = Memory::in_memory();
+    memory
+        .remember(
+            CompilerInput::builder(context("s1", "2026-02-18T00:00:00Z"))
+                .tuple("Alice", "likes", "Tea", 0.6)
+                .tuple("Alice", "knows", "Bob", 0.9)
+                .tuple("Alice", "lives_in", "Paris", 0.6)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    // Seen again later: the same fact, a second episode.
+    let receipt = memory
+        .remember(
+            CompilerInput::builder(context("s2", "2026-03-01T00:00:00Z"))
+                .tuple("Alice", "likes", "Tea", 0.5)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(receipt.insert.new_facts, 0);
+    assert_eq!(receipt.insert.new_episodes, 1);
+
+    let hits = memory.recall("ALICE", 10);
+    let objects: Vec<&str> = hits.iter().map(|hit| hit.object.as_str()).collect();
+    // Most confident first; equal confidence, most recently seen first.
+    assert_eq!(objects, ["Bob", "Tea", "Paris"]);
+    assert_eq!(hits[1].episodes, 2);
+    assert_eq!(hits[1].confidence, 0.6);
+    assert_eq!(hits[1].last_seen, "2026-03-01T00:00:00Z".parse().unwrap());
+
+    assert_eq!(memory.recall("alice", 1).len(), 1);
+    // Concepts are found as objects too.
+    assert_eq!(memory.recall("bob", 10)[0].subject, "Alice");
+    assert!(memory.recall("carol", 10).is_empty());
+}
+
+#[test]
+fn recall_orders_by_event_time_not_insert_time() {
+    let mut memory = Memory::in_memory();
+    // Told about March first, then about February; both records are
+    // ingested in April, in that order.
+    for (object, event_time, created_at) in [
+        ("Tea", "2026-03-01T00:00:00Z", "2026-04-01T00:00:00Z"),
+        ("Coffee", "2026-02-01T00:00:00Z", "2026-04-02T00:00:00Z"),
+    ] {
+        let input = CompilerInput::builder(context(object, event_time))
+            .tuple("Alice", "likes", object, 0.6)
+            .build()
+            .unwrap();
+        let mut record = compile(&input).record;
+        record.relations[0].created_at = created_at.parse().unwrap();
+        memory.store_mut().insert_record(record);
+    }
+
+    let hits = memory.recall("alice", 10);
+    let objects: Vec<&str> = hits.iter().map(|hit| hit.object.as_str()).collect();
+    assert_eq!(objects, ["Tea", "Coffee"]);
+    assert_eq!(hits[0].last_seen, "2026-03-01T00:00:00Z".parse().unwrap());
+    assert_eq!(hits[1].last_seen, "2026-02-01T00:00:00Z".parse().unwrap());
+}
+
+#[test]
+fn invalid_input_is_rejected_before_the_store() {
+    let mut memory = Memory::in_memory();
+    let mut input = CompilerInput::builder(context("s1", "2026-02-18T00:00:00Z"))
+        .tuple("Agent", "needs", "Memory", 0.9)
+        .tuple("Agent", "uses", "Tools", 0.9)
+        .build()
+        .unwrap();
+    // The builder checks tuples too; blank the predicate behind its back.
+    input.tuples[1].predicate.clear();
+    let err = memory.remember(input).unwrap_err();
+    assert!(matches!(
+        err,
+        MemoryError::Compile(CompilerError::EmptyPredicate { tuple_index: 1 })
+    ));
+    assert_eq!(memory.store().concept_count(), 0);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn memories_with_a_runtime_save_and_load() {
+    use zero_memory::testing::MockRuntime;
+
+    let mut memory = Memory::with_runtime(MockRuntime::new());
+    memory
+        .remember(
+            CompilerInput::builder(context("s1", "2026-02-18T00:00:00Z"))
+                .tuple("Agent", "needs", "Memory", 0.9)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    memory.save("memory").unwrap();
+    assert_eq!(
+        memory.runtime().saved_keys().last().unwrap(),
+        "memory/manifest"
+    );
+
+    let hits = memory.recall("agent", 5);
+    let loaded = Memory::load(memory.into_runtime(), "memory").unwrap();
+    assert_eq!(loaded.recall("agent", 5), hits);
+
+    let err = Memory::load(MockRuntime::new(), "memory").unwrap_err();
+    assert!(matches!(err, MemoryError::Persistence(_)));
+}
@@ -0,0 +1,154 @@
+use zero_memory::compiler::emitter::{compile, compile_with};
+use zero_memory::compiler::{hasher, CompilerOptions, MergeError};
+use zero_memory::types::*;
+
+fn context(scope: &str) -> ContextMeta {
+    ContextMeta::builder()
+        .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope(scope)
+        .build()
+        .unwrap()
+}
+
+fn record(scope: &str, tuples: &[(&str, &str, &str, f64)]) -> MemoryRecord {
+    let input = tuples
+        .iter()
+        .fold(
+            CompilerInput::builder(context(scope)),
+            |input, &(s, p, o, c)| input.tuple(s, p, o, c),
+        )
+        .build()
+        .unwrap();
+    compile(&input).record
+}
+
+fn hashes(record: &MemoryRecord) -> (Vec<ConceptHash>, Vec<EpisodeHash>) {
+    let mut concepts: Vec<ConceptHash> = record.concepts.iter().map(|c| c.hash.clone()).collect();
+    let mut episodes: Vec<EpisodeHash> = record
+        .relations
+        .iter()
+        .map(|r| r.episode_hash.clone())
+        .collect();
+    concepts.sort();
+    episodes.sort();
+    (concepts, episodes)
+}
+
+#[test]
+fn merged_batches_match_one_compile() {
+    let first = record("s1", &[("Agent", "needs", "Memory", 0.9)]);
+    let second = record(
+        "s1",
+        &[
+            ("Agent", "uses", "Tools", 0.8),
+            ("Tools", "need", "Memory", 0.7),
+        ],
+    );
+    let whole = record(
+        "s1",
+        &[
+            ("Agent", "needs", "Memory", 0.9),
+            ("Agent", "uses", "Tools", 0.8),
+            ("Tools", "need", "Memory", 0.7),
+        ],
+    );
+
+    let merged = first.merge(second).unwrap();
+    assert_eq!(hashes(&merged), hashes(&whole));
+    assert_eq!(merged.context.hash, whole.context.hash);
+    assert_eq!(merged.hash(), whole.hash());
+    merged.validate().unwrap();
+}
+
+#[test]
+fn merging_a_record_with_itself_changes_nothing() {
+    let record = record(
+        "s1",
+        &[
+            ("Agent", "needs", "Memory", 0.9),
+            ("Agent", "uses", "Tools", 0.8),
+        ],
+    );
+    let merged = record.clone().merge(record.clone()).unwrap();
+    assert_eq!(merged.concepts.len(), record.concepts.len());
+    assert_eq!(merged.relations.len(), record.relations.len());
+    assert_eq!(merged.hash(), record.hash());
+}
+
+#[test]
+fn duplicates_keep_the_higher_confidence_and_every_alias() {
+    let low = record("s1", &[("Agent", "needs", "LTM", 0.4)]);
+    let high = record("s1", &[("agent", "needs", "Long_Term_Memory", 0.9)]);
+
+    let merged = low.merge(high).unwrap();
+    assert_eq!(merged.relations.len(), 1);
+    assert_eq!(merged.relations[0].confidence, 0.9);
+    let memory = merged
+        .concepts
+        .iter()
+        .find(|c| c.label == "long-term-memory")
+        .unwrap();
+    assert_eq!(memory.confidence, 0.9);
+    assert!(memory.aliases.contains(&"LTM".to_string()));
+    assert!(memory.aliases.contains(&"Long_Term_Memory".to_string()));
+}
+
+#[test]
+fn records_of_other_contexts_do_not_merge() {
+    let ours = record("s1", &[("Agent", "needs", "Memory", 0.9)]);
+    let theirs = record("s2", &[("Agent", "needs", "Memory", 0.9)]);
+
+    let err = ours.clone().merge(theirs.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        MergeError::ContextMismatch {
+            expected: ours.context.hash.clone(),
+            found: theirs.context.hash.clone(),
+        }
+    );
+    assert!(err.to_string().starts_with("context "));
+}
+
+#[test]
+fn records_of_other_namespaces_do_not_merge() {
+    let input = CompilerInput::builder(context("s1"))
+        .tuple("Agent", "needs", "Memory", 0.9)
+        .build()
+        .unwrap();
+    let options = CompilerOptions {
+        namespace: Some("tenant-a".to_string()),
+        ..CompilerOptions::default()
+    };
+    let namespaced = compile_with(&input, &options).record;
+
+    assert_eq!(
+        compile(&input).record.merge(namespaced).unwrap_err(),
+        MergeError::NamespaceMismatch {
+            expected: None,
+            found: Some("tenant-a".to_string()),
+        }
+    );
+}
+
+#[test]
+fn merge_many_folds_into_the_first() {
+    let records: Vec<MemoryRecord> = ["Memory", "Tools", "Sleep", "Memory"]
+        .iter()
+        .map(|object| record("s1", &[("Agent", "needs", object, 0.9)]))
+        .collect();
+
+    let merged = MemoryRecord::merge_many(records).unwrap();
+    assert_eq!(merged.relations.len(), 3);
+    assert_eq!(merged.concepts.len(), 4);
+    let first = &merged.relations[0];
+    assert_eq!(
+        first.fact_hash,
+        hasher::fact_hash("agent", "needs", "memory")
+    );
+
+    assert_eq!(
+        MemoryRecord::merge_many(Vec::new()).unwrap_err(),
+        MergeError::Empty
+    );
+}
@@ -0,0 +1,152 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::MerkleProof;
+use zero_memory::types::*;
+
+fn input(tuples: usize) -> CompilerInput {
+    let context = ContextMeta::builder()
+        .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope("s1")
+        .build()
+        .unwrap();
+    (0..tuples)
+        .fold(CompilerInput::builder(context), |input, i| {
+            input.tuple("Agent", "needs", &format!("Thing{}", i), 0.9)
+        })
+        .build()
+        .unwrap()
+}
+
+/// A record whose episode hashes are `[1; 32]`, `[2; 32]`, ... `[n; 32]`,
+/// listed in reverse so the tree has to sort them.
+fn record(n: u8) -> MemoryRecord {
+    let mut record = compile(&input(1)).record;
+    let template = record.relations.pop().unwrap();
+    record.relations = (1..=n)
+        .rev()
+        .map(|i| RelationNode {
+            episode_hash: EpisodeHash::from_bytes([i; 32]),
+            ..template.clone()
+        })
+        .collect();
+    record
+}
+
+#[test]
+fn known_roots() {
+    // Computed independently from the rule documented on `merkle_root`.
+    let cases = [
+        (
+            1,
+            "dcffe786ded16d283c663846ad0c4ff26558fccde36ca9d30b2ea19eade9fc0e",
+        ),
+        (
+            2,
+            "3a066e0f40c6a1981ebfa60d2411625d0517ae22c2fc8c7c1784ff8a75c78565",
+        ),
+        (
+            3,
+            "603d687b5c3c7e5abebc0fdd1c8c120c18699996178c1ba6ade1cd814a533428",
+        ),
+        (
+            5,
+            "f6ab5bbc08c6028b7dd999a9fe1d057cce4d7d5fc7a47b18a17118eeda7fb8dc",
+        ),
+    ];
+    for (n, root) in cases {
+        assert_eq!(hex::encode(record(n).merkle_root()), root, "{} leaves", n);
+    }
+    assert_eq!(
+        hex::encode(record(0).merkle_root()),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
+
+#[test]
+fn every_leaf_proves_its_inclusion() {
+    for n in 1..=9 {
+        let record = record(n);
+        let root = record.merkle_root();
+        for i in 1..=n {
+            let leaf = EpisodeHash::from_bytes([i; 32]);
+            let proof = record.merkle_proof(&leaf).unwrap();
+            assert_eq!(proof.index, (i - 1) as usize);
+            assert!(proof.verify(&root, &leaf), "leaf {} of {}", i, n);
+        }
+    }
+}
+
+#[test]
+fn proofs_do_not_verify_other_leaves_or_roots() {
+    let record = record(5);
+    let root = record.merkle_root();
+    let proof = record
+        .merkle_proof(&EpisodeHash::from_bytes([3; 32]))
+        .unwrap();
+
+    assert!(!proof.verify(&root, &EpisodeHash::from_bytes([4; 32])));
+    assert!(!proof.verify(
+        &self::record(4).merkle_root(),
+        &EpisodeHash::from_bytes([3; 32])
+    ));
+
+    let mut moved = proof.clone();
+    moved.index ^= 1;
+    assert!(!moved.verify(&root, &EpisodeHash::from_bytes([3; 32])));
+
+    let mut beyond = proof.clone();
+    beyond.index += 8;
+    assert!(!beyond.verify(&root, &EpisodeHash::from_bytes([3; 32])));
+
+    let mut bent = proof;
+    bent.siblings[0][0] ^= 1;
+    assert!(!bent.verify(&root, &EpisodeHash::from_bytes([3; 32])));
+}
+
+#[test]
+fn missing_episodes_have_no_proof() {
+    assert_eq!(
+        record(3).merkle_proof(&EpisodeHash::from_bytes([9; 32])),
+        None
+    );
+    assert_eq!(
+        record(0).merkle_proof(&EpisodeHash::from_bytes([1; 32])),
+        None
+    );
+}
+
+#[test]
+fn single_leaf_proofs_are_empty() {
+    let record = record(1);
+    let leaf = EpisodeHash::from_bytes([1; 32]);
+    let proof = record.merkle_proof(&leaf).unwrap();
+    assert_eq!(
+        proof,
+        MerkleProof {
+            index: 0,
+            siblings: vec![]
+        }
+    );
+    assert!(proof.verify(&record.merkle_root(), &leaf));
+}
+
+#[test]
+fn duplicate_episodes_count_once() {
+    let mut doubled = record(3);
+    doubled.relations.push(doubled.relations[0].clone());
+    assert_eq!(doubled.merkle_root(), record(3).merkle_root());
+}
+
+#[test]
+fn compiled_records_prove_their_relations() {
+    let record = compile(&input(4)).record;
+    let root = record.merkle_root();
+    for relation in &record.relations {
+        let proof = record.merkle_proof(&relation.episode_hash).unwrap();
+        assert!(proof.verify(&root, &relation.episode_hash));
+    }
+
+    let mut changed = record.clone();
+    changed.relations.pop();
+    assert_ne!(changed.merkle_root(), root);
+}
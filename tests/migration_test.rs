@@ -0,0 +1,135 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::{record_from_json, MigrationError};
+use zero_memory::types::*;
+
+const RECORD_V1: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/record_v1.json"
+));
+const RECORD_V2: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/record_v2.json"
+));
+
+fn input() -> CompilerInput {
+    let context = ContextMeta::builder()
+        .event_time("2025-06-01T09:30:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope("session-42")
+        .agent_id("agent-7")
+        .build()
+        .unwrap();
+    CompilerInput::builder(context)
+        .tuple("Alice", "needs", "LTM", 0.8)
+        .tuple("Alice", "knows", "Bob", 0.6)
+        .build()
+        .unwrap()
+}
+
+fn time(s: &str) -> EventTime {
+    s.parse().unwrap()
+}
+
+#[test]
+fn version_1_fixture_upgrades() {
+    let record = MemoryRecord::from_json_with_migration(RECORD_V1).unwrap();
+    assert_eq!(record.schema_version, RECORD_SCHEMA_VERSION);
+    assert_eq!(record.concepts.len(), 3);
+    assert_eq!(record.relations.len(), 2);
+    assert_eq!(record.namespace, None);
+    assert_eq!(record.concepts[0].display_label, None);
+    assert_eq!(record.relations[0].polarity, Polarity::Positive);
+
+    assert_eq!(record.concepts[0].created_at, time("2025-06-01T09:30:00Z"));
+    assert_eq!(record.concepts[1].created_at, time("2025-06-01T07:30:00Z"));
+    assert_eq!(record.concepts[1].updated_at, EventTime::EPOCH);
+    assert_eq!(record.relations[0].created_at, time("2025-06-01T09:30:00Z"));
+
+    record.validate().unwrap();
+    let compiled = compile(&input()).record;
+    assert_eq!(record.context.hash, compiled.context.hash);
+    assert_eq!(
+        record.merkle_root(),
+        compiled.merkle_root(),
+        "old snapshots must keep their episodes"
+    );
+}
+
+#[test]
+fn version_1_loads_without_migration_when_timestamps_are_canonical() {
+    let mut record = compile(&input()).record;
+    record.schema_version = 1;
+    let mut json: serde_json::Value = serde_json::to_value(&record).unwrap();
+    json.as_object_mut().unwrap().remove("schema_version");
+
+    let plain: MemoryRecord = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(plain.schema_version, 1);
+    let migrated = record_from_json(&json.to_string()).unwrap();
+    assert_eq!(migrated.schema_version, RECORD_SCHEMA_VERSION);
+    assert_eq!(migrated.hash(), record.hash());
+
+    // The fixture's legacy timestamps are what plain deserialization rejects.
+    assert!(serde_json::from_str::<MemoryRecord>(RECORD_V1).is_err());
+}
+
+#[test]
+fn current_records_round_trip() {
+    let record = compile(&input()).record;
+    assert_eq!(record.schema_version, RECORD_SCHEMA_VERSION);
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(json.starts_with(&format!("{{\"schema_version\":{}", RECORD_SCHEMA_VERSION)));
+    let parsed = record_from_json(&json).unwrap();
+    assert_eq!(parsed.schema_version, RECORD_SCHEMA_VERSION);
+    assert_eq!(parsed.hash(), record.hash());
+}
+
+#[test]
+fn version_2_fixture_loads() {
+    let record = record_from_json(RECORD_V2).unwrap();
+    assert_eq!(record.schema_version, 2);
+    record.validate().unwrap();
+    assert_eq!(
+        serde_json::from_str::<MemoryRecord>(RECORD_V2)
+            .unwrap()
+            .hash(),
+        record.hash()
+    );
+    assert!(record
+        .relations
+        .iter()
+        .any(|r| r.polarity == Polarity::Negative));
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let mut json = serde_json::to_value(compile(&input()).record).unwrap();
+    json["schema_version"] = serde_json::Value::from(RECORD_SCHEMA_VERSION + 1);
+
+    let err = record_from_json(&json.to_string()).unwrap_err();
+    assert!(matches!(
+        err,
+        MigrationError::UnsupportedVersion { found, supported: RECORD_SCHEMA_VERSION }
+            if found == u64::from(RECORD_SCHEMA_VERSION + 1)
+    ));
+    assert!(err.to_string().contains("newer than this build"));
+
+    json["schema_version"] = serde_json::Value::from(0);
+    let err = record_from_json(&json.to_string()).unwrap_err();
+    assert_eq!(err.to_string(), "unknown record schema version 0");
+}
+
+#[test]
+fn malformed_documents_are_json_errors() {
+    assert!(matches!(
+        record_from_json("not json"),
+        Err(MigrationError::Json(_))
+    ));
+    assert!(matches!(
+        record_from_json(r#"{"schema_version": "two"}"#),
+        Err(MigrationError::Json(_))
+    ));
+    assert!(matches!(
+        record_from_json(r#"{"concepts": []}"#),
+        Err(MigrationError::Json(_))
+    ));
+}
@@ -0,0 +1,217 @@
+#![cfg(feature = "testing")]
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use zero_memory::compiler::emitter::compile;
+use zero_memory::runtime_trait::{MemoryRuntime, StateNamespace};
+use zero_memory::store::MemoryStore;
+use zero_memory::testing::{GraphCall, MockError, MockRuntime};
+use zero_memory::types::*;
+
+fn graph_text() -> String {
+    let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+        .tuple("Agent", "needs", "Memory", 0.9)
+        .build()
+        .unwrap();
+    compile(&input).graph_text
+}
+
+fn outputs() -> HashMap<String, Value> {
+    HashMap::from([("output".to_string(), json!({"concepts": 2}))])
+}
+
+#[test]
+fn programmed_graphs_answer_and_are_logged() {
+    let graph = graph_text();
+    let runtime = MockRuntime::new().with_response(&graph, outputs());
+    let inputs = HashMap::from([("x".to_string(), json!(1))]);
+
+    assert_eq!(
+        runtime.execute_graph(&graph, inputs.clone()).unwrap(),
+        outputs()
+    );
+    assert_eq!(
+        runtime.executed_graphs(),
+        [GraphCall {
+            source: graph,
+            inputs,
+        }]
+    );
+}
+
+#[test]
+fn unprogrammed_graphs_fail_but_are_still_logged() {
+    let runtime = MockRuntime::new()
+        .with_response("graph a", outputs())
+        .with_error("graph b", "interpreter exploded");
+
+    let err = runtime
+        .execute_graph("graph c", HashMap::new())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        MockError::NoResponse {
+            graph_hash: hex::encode(Sha256::digest("graph c")),
+        }
+    );
+    assert!(err
+        .to_string()
+        .starts_with("no response programmed for graph "));
+    assert_eq!(
+        runtime
+            .execute_graph("graph b", HashMap::new())
+            .unwrap_err()
+            .to_string(),
+        "interpreter exploded"
+    );
+
+    let sources: Vec<String> = runtime
+        .executed_graphs()
+        .into_iter()
+        .map(|call| call.source)
+        .collect();
+    assert_eq!(sources, ["graph c", "graph b"]);
+    runtime.clear_log();
+    assert!(runtime.executed_graphs().is_empty());
+}
+
+#[test]
+fn state_round_trips_and_saves_are_logged() {
+    let runtime = MockRuntime::new().with_state("seeded", json!("initial"));
+    assert_eq!(
+        runtime.load_state("seeded").unwrap(),
+        Some(json!("initial"))
+    );
+    assert_eq!(runtime.load_state("missing").unwrap(), None);
+
+    runtime.save_state("a", &json!(1)).unwrap();
+    runtime.save_state("b", &json!([1, 2])).unwrap();
+    runtime.save_state("a", &json!(2)).unwrap();
+    assert_eq!(runtime.load_state("a").unwrap(), Some(json!(2)));
+    assert_eq!(runtime.saved_keys(), ["a", "b", "a"]);
+}
+
+#[test]
+fn hashes_like_the_real_runtimes() {
+    let runtime = MockRuntime::new();
+    let expected: [u8; 32] = Sha256::digest(b"hello").into();
+    assert_eq!(runtime.hash(b"hello"), expected);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn mocks_async_runtimes_too() {
+    use zero_memory::runtime_trait::{AsyncMemoryRuntime, InlineRuntime};
+
+    let runtime = InlineRuntime(MockRuntime::new().with_response("graph", outputs()));
+    assert_eq!(
+        runtime
+            .execute_graph("graph", HashMap::new())
+            .await
+            .unwrap(),
+        outputs()
+    );
+    runtime.save_state("key", &json!(true)).await.unwrap();
+    assert_eq!(runtime.0.saved_keys(), ["key"]);
+}
+
+#[test]
+fn namespaces_share_a_runtime_without_colliding() {
+    let runtime = MockRuntime::new().with_state("working_set", json!("global"));
+    let alice = runtime.namespaced(StateNamespace::new().agent_id("alice").session_id("s1"));
+    let bob = runtime.namespaced(StateNamespace::new().agent_id("bob").session_id("s1"));
+
+    alice.save_state("working_set", &json!(["a"])).unwrap();
+    alice.save_state("goals", &json!("ship")).unwrap();
+    bob.save_state("working_set", &json!(["b"])).unwrap();
+
+    assert_eq!(alice.load_state("working_set").unwrap(), Some(json!(["a"])));
+    assert_eq!(bob.load_state("working_set").unwrap(), Some(json!(["b"])));
+    assert_eq!(bob.load_state("goals").unwrap(), None);
+    assert_eq!(
+        runtime.load_state("working_set").unwrap(),
+        Some(json!("global"))
+    );
+
+    assert_eq!(
+        alice.list_state_keys("").unwrap(),
+        Some(vec!["goals".to_string(), "working_set".to_string()])
+    );
+    assert_eq!(
+        bob.list_state_keys("").unwrap(),
+        Some(vec!["working_set".to_string()])
+    );
+    assert_eq!(
+        alice.list_state_keys("work").unwrap(),
+        Some(vec!["working_set".to_string()])
+    );
+    assert_eq!(
+        runtime.saved_keys(),
+        [
+            "agent/alice/session/s1/working_set",
+            "agent/alice/session/s1/goals",
+            "agent/bob/session/s1/working_set",
+        ]
+    );
+}
+
+#[test]
+fn namespace_ids_are_escaped() {
+    let slashed = StateNamespace::new().agent_id("a/session/b");
+    let nested = StateNamespace::new().agent_id("a").session_id("b");
+    assert_eq!(slashed.key("k"), "agent/a%2Fsession%2Fb/session//k");
+    assert_eq!(nested.key("k"), "agent/a/session/b/k");
+    assert_eq!(
+        StateNamespace::new().agent_id("50%").key("k"),
+        "agent/50%25/session//k"
+    );
+    assert_eq!(
+        StateNamespace::new().agent_id("").key("k"),
+        StateNamespace::new().key("k")
+    );
+
+    let meta = ContextMeta::now("user_prompt", "s1");
+    assert_eq!(StateNamespace::from(&meta), StateNamespace::new());
+}
+
+#[test]
+fn namespaced_batches_stay_in_their_namespace() {
+    let runtime = MockRuntime::new();
+    let ns = runtime.namespaced(StateNamespace::new().session_id("s9"));
+    ns.save_state_many(&[("a".to_string(), json!(1)), ("b".to_string(), json!(2))])
+        .unwrap();
+    assert_eq!(
+        ns.load_state_many(&["b".to_string(), "c".to_string()])
+            .unwrap(),
+        [Some(json!(2)), None]
+    );
+    assert_eq!(
+        runtime.list_state_keys("").unwrap(),
+        Some(vec![
+            "agent//session/s9/a".to_string(),
+            "agent//session/s9/b".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn stores_persist_into_mock_state() {
+    let input = CompilerInput::builder(ContextMeta::now("user_prompt", "s1"))
+        .tuple("Agent", "needs", "Memory", 0.9)
+        .build()
+        .unwrap();
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&input).record);
+
+    let runtime = MockRuntime::new();
+    store.persist_to_with(&runtime, "memory", 1024).unwrap();
+    let saved = runtime.saved_keys();
+    assert!(saved.len() > 2);
+    assert_eq!(saved.last().unwrap(), "memory/manifest");
+
+    let restored = MemoryStore::restore_from(&runtime, "memory").unwrap();
+    assert_eq!(restored.concept_count(), 2);
+    assert_eq!(restored.relation_count(), 1);
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256};
+use zero_memory::compiler::emitter::{compile, compile_into, compile_with, emit_graph_text};
+use zero_memory::compiler::{decompile, hasher, CompilerError, CompilerOptions};
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+fn context() -> ContextMeta {
+    ContextMeta {
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+        source: "user_prompt".to_string(),
+        scope: "s1".to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    }
+}
+
+fn input() -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: vec![
+            SemanticTuple::from(("Model", "needs", "Data", 0.9)),
+            SemanticTuple {
+                subject: "Model".to_string(),
+                predicate: "met".to_string(),
+                object: "Alice".to_string(),
+                confidence: 0.8,
+                polarity: Polarity::Negative,
+                qualifiers: HashMap::from([("location".to_string(), "Paris".to_string())]),
+                ..Default::default()
+            },
+        ],
+        context: context(),
+    }
+}
+
+fn in_namespace(namespace: &str) -> CompilerOptions {
+    CompilerOptions {
+        namespace: Some(namespace.to_string()),
+        ..CompilerOptions::default()
+    }
+}
+
+fn json(record: &MemoryRecord) -> serde_json::Value {
+    serde_json::to_value(record).unwrap()
+}
+
+fn concept<'a>(record: &'a MemoryRecord, label: &str) -> &'a ConceptNode {
+    record.concepts.iter().find(|c| c.label == label).unwrap()
+}
+
+#[test]
+fn namespaced_hashes_prefix_the_namespace_and_a_nul() {
+    assert_eq!(
+        hasher::concept_hash_ns("tenant-a", "model").as_bytes()[..],
+        Sha256::digest(b"tenant-a\0model")[..]
+    );
+    assert_eq!(
+        hasher::fact_hash_ns("tenant-a", "model", "needs", "data", Polarity::Positive).as_bytes()[..],
+        Sha256::digest(b"tenant-a\0model|needs|data")[..]
+    );
+    assert_eq!(
+        hasher::fact_hash_ns("tenant-a", "model", "needs", "data", Polarity::Negative).as_bytes()[..],
+        Sha256::digest(b"tenant-a\0model|needs|data|neg")[..]
+    );
+    assert_ne!(
+        hasher::concept_hash_ns("a", "bc"),
+        hasher::concept_hash_ns("ab", "c")
+    );
+}
+
+#[test]
+fn the_same_label_is_two_concepts_in_two_namespaces() {
+    let a = compile_with(&input(), &in_namespace("tenant-a")).record;
+    let b = compile_with(&input(), &in_namespace("tenant-b")).record;
+    let plain = compile(&input()).record;
+
+    let hashes = [&a, &b, &plain].map(|r| concept(r, "model").hash.clone());
+    assert_ne!(hashes[0], hashes[1]);
+    assert_ne!(hashes[0], hashes[2]);
+    assert_eq!(hashes[0], hasher::concept_hash_ns("tenant-a", "model"));
+    assert_eq!(hashes[2], hasher::concept_hash("model"));
+
+    assert_eq!(
+        a.relations[0].fact_hash,
+        hasher::fact_hash_ns("tenant-a", "model", "needs", "data", Polarity::Positive)
+    );
+    assert_ne!(a.relations[0].episode_hash, b.relations[0].episode_hash);
+}
+
+#[test]
+fn no_namespace_compiles_as_before() {
+    let default = compile_with(&input(), &CompilerOptions::default());
+    assert_eq!(json(&default.record), json(&compile(&input()).record));
+    assert_eq!(default.record.namespace, None);
+    assert!(!default.graph_text.contains("namespace"));
+    assert!(!default.graph_text.contains("concept_key_"));
+}
+
+#[test]
+fn the_namespace_is_recorded() {
+    let output = compile_with(&input(), &in_namespace("tenant-a"));
+    assert_eq!(output.record.namespace.as_deref(), Some("tenant-a"));
+    assert!(output
+        .graph_text
+        .contains(r#""tags": ["memory","compiled"], "namespace": "tenant-a" }"#));
+
+    let text = serde_json::to_string(&output.record).unwrap();
+    let loaded: MemoryRecord = serde_json::from_str(&text).unwrap();
+    assert_eq!(json(&loaded), json(&output.record));
+    assert!(!serde_json::to_string(&compile(&input()).record)
+        .unwrap()
+        .contains("namespace"));
+}
+
+#[test]
+fn the_namespace_is_part_of_the_record_hash() {
+    let record = compile_with(&input(), &in_namespace("tenant-a")).record;
+    let mut renamed = record.clone();
+    renamed.namespace = Some("tenant-b".to_string());
+    assert_ne!(renamed.hash(), record.hash());
+}
+
+#[test]
+fn namespaced_graphs_hash_concepts_through_a_key_node() {
+    let output = compile_with(&input(), &in_namespace("tenant-a"));
+    assert!(output
+        .graph_text
+        .contains(r#""value": "tenant-a\u0000model""#));
+
+    // The statement concept is hashed from its label alone.
+    let statement = output
+        .record
+        .concepts
+        .iter()
+        .find(|c| c.label.starts_with("statement-"))
+        .unwrap();
+    assert_eq!(statement.hash, hasher::concept_hash(&statement.label));
+    assert!(!output
+        .graph_text
+        .contains(&format!("\\u0000{}", statement.label)));
+}
+
+#[test]
+fn namespaced_graphs_decompile() {
+    let output = compile_with(&input(), &in_namespace("tenant:a"));
+    let decompiled = decompile(&output.graph_text).unwrap();
+
+    assert_eq!(decompiled.namespace.as_deref(), Some("tenant:a"));
+    let mut hashes: Vec<_> = decompiled.concepts.iter().map(|c| &c.hash).collect();
+    let mut expected: Vec<_> = output.record.concepts.iter().map(|c| &c.hash).collect();
+    hashes.sort();
+    expected.sort();
+    assert_eq!(hashes, expected);
+    assert_eq!(
+        emit_graph_text(&decompiled, &decompiled.context.meta),
+        emit_graph_text(&output.record, &output.record.context.meta)
+    );
+}
+
+#[test]
+fn qualifiers_of_namespaced_records_are_found() {
+    let record = compile_with(&input(), &in_namespace("tenant-a")).record;
+    let qualified = record.relations[1].episode_hash.clone();
+    let mut store = MemoryStore::new();
+    store.insert_record(record);
+    assert_eq!(store.get_qualifiers(&qualified), [("location", "paris")]);
+}
+
+#[test]
+fn compile_into_keeps_to_the_record_namespace() {
+    let mut record = compile_with(&input(), &in_namespace("tenant-a")).record;
+    let before = record.clone();
+    let tuples = [SemanticTuple::from(("Model", "likes", "Tea", 0.7))];
+
+    assert_eq!(
+        compile_into(
+            &mut record,
+            &context(),
+            &tuples,
+            &CompilerOptions::default()
+        ),
+        Err(CompilerError::NamespaceMismatch {
+            expected: Some("tenant-a".to_string()),
+            found: None,
+        })
+    );
+    assert_eq!(json(&record), json(&before));
+
+    let report = compile_into(&mut record, &context(), &tuples, &in_namespace("tenant-a")).unwrap();
+    assert_eq!(
+        report.concepts_added,
+        [hasher::concept_hash_ns("tenant-a", "tea")]
+    );
+}
@@ -0,0 +1,210 @@
+use std::io::Cursor;
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::io::{read_ndjson, read_ndjson_blocks, write_ndjson, IngestError};
+use zero_memory::types::{CompilerInput, ContextMeta, SemanticTuple};
+
+const HEADER: &str =
+    r#"{"context": {"event_time": "2026-02-18T00:00:00Z", "source": "chat", "scope": "s1"}}"#;
+const TUPLE_A: &str =
+    r#"{"subject": "Agent", "predicate": "needs", "object": "Memory", "confidence": 0.9}"#;
+const TUPLE_B: &str =
+    r#"{"subject": "Memory", "predicate": "holds", "object": "Facts", "confidence": 0.8}"#;
+
+fn sample_input() -> CompilerInput {
+    let mut metadata = std::collections::HashMap::new();
+    metadata.insert("batch".to_string(), "7".to_string());
+    CompilerInput {
+        utterance: Some("The agent needs memory.".to_string()),
+        tuples: vec![
+            SemanticTuple {
+                subject: "Agent".to_string(),
+                predicate: "needs".into(),
+                object: "Memory".to_string(),
+                confidence: 0.9,
+                valid_from: Some("2026-01-01T00:00:00Z".to_string()),
+                ..Default::default()
+            },
+            SemanticTuple::from(("Memory", "holds", "Facts", 0.8)),
+        ],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "chat".to_string(),
+            scope: "s1".to_string(),
+            agent_id: Some("agent_a".to_string()),
+            session_id: None,
+            metadata: Some(metadata),
+        },
+    }
+}
+
+fn json(input: &CompilerInput) -> serde_json::Value {
+    serde_json::to_value(input).unwrap()
+}
+
+#[test]
+fn read_ndjson_reads_a_context_and_its_tuples() {
+    let text = format!("{}\n{}\n{}\n", HEADER, TUPLE_A, TUPLE_B);
+    let input = read_ndjson(Cursor::new(text)).unwrap();
+    assert_eq!(input.context.scope, "s1");
+    assert_eq!(input.utterance, None);
+    assert_eq!(input.tuples.len(), 2);
+    assert_eq!(input.tuples[0].subject, "Agent");
+    assert_eq!(input.tuples[1].object, "Facts");
+}
+
+#[test]
+fn read_ndjson_skips_blank_lines_and_accepts_crlf() {
+    let text = format!("\n{}\r\n\r\n  \n{}\r\n{}\n\n\n", HEADER, TUPLE_A, TUPLE_B);
+    let input = read_ndjson(Cursor::new(text)).unwrap();
+    assert_eq!(input.tuples.len(), 2);
+}
+
+#[test]
+fn read_ndjson_accepts_a_bare_context_meta_header() {
+    let text = format!(
+        "{}\n{}\n",
+        r#"{"event_time": "2026-02-18T00:00:00Z", "source": "chat", "scope": "s2"}"#, TUPLE_A
+    );
+    let input = read_ndjson(Cursor::new(text)).unwrap();
+    assert_eq!(input.context.scope, "s2");
+    assert_eq!(input.tuples.len(), 1);
+}
+
+#[test]
+fn write_ndjson_round_trips() {
+    let input = sample_input();
+    let mut buf = Vec::new();
+    write_ndjson(&input, &mut buf).unwrap();
+    let text = String::from_utf8(buf.clone()).unwrap();
+    assert_eq!(text.lines().count(), 3);
+    assert!(text.ends_with('\n'));
+
+    let back = read_ndjson(Cursor::new(buf)).unwrap();
+    assert_eq!(json(&back), json(&input));
+    assert_eq!(compile(&back).graph_text, compile(&input).graph_text);
+}
+
+#[test]
+fn malformed_json_reports_its_line() {
+    let text = format!("{}\n\n{}\n{{\"subject\": \"Agent\",\n", HEADER, TUPLE_A);
+    match read_ndjson(Cursor::new(text)) {
+        Err(IngestError::Json { line, .. }) => assert_eq!(line, 4),
+        other => panic!("expected a JSON error, got {:?}", other),
+    }
+}
+
+#[test]
+fn tuple_missing_a_field_reports_its_line() {
+    let text = format!(
+        "{}\n{}\n{}\n",
+        HEADER, TUPLE_A, r#"{"subject": "Agent", "predicate": "needs", "confidence": 0.9}"#
+    );
+    let err = read_ndjson(Cursor::new(text)).unwrap_err();
+    assert!(matches!(err, IngestError::Json { line: 3, .. }));
+    let message = err.to_string();
+    assert!(message.starts_with("line 3: "), "{}", message);
+    assert!(message.contains("object"), "{}", message);
+}
+
+#[test]
+fn tuples_before_any_context_are_rejected() {
+    let text = format!("\n{}\n{}\n", TUPLE_A, HEADER);
+    assert!(matches!(
+        read_ndjson(Cursor::new(text)),
+        Err(IngestError::MissingContext { line: 2 })
+    ));
+}
+
+#[test]
+fn read_ndjson_rejects_a_second_context() {
+    let text = format!("{}\n{}\n\n{}\n{}\n", HEADER, TUPLE_A, HEADER, TUPLE_B);
+    assert!(matches!(
+        read_ndjson(Cursor::new(text)),
+        Err(IngestError::UnexpectedContext { line: 4 })
+    ));
+}
+
+#[test]
+fn read_ndjson_rejects_an_empty_stream() {
+    assert!(matches!(
+        read_ndjson(Cursor::new("\n\n")),
+        Err(IngestError::Empty)
+    ));
+}
+
+#[test]
+fn blocks_split_at_context_lines() {
+    let header2 = HEADER.replace("\"s1\"", "\"s2\"");
+    let text = format!("{}\n{}\n{}\n{}\n", HEADER, TUPLE_A, header2, TUPLE_B);
+    let blocks: Vec<CompilerInput> = read_ndjson_blocks(Cursor::new(text))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].context.scope, "s1");
+    assert_eq!(blocks[0].tuples[0].subject, "Agent");
+    assert_eq!(blocks[1].context.scope, "s2");
+    assert_eq!(blocks[1].tuples[0].subject, "Memory");
+}
+
+#[test]
+fn blocks_split_at_blank_lines_keep_the_previous_context() {
+    let text = format!("{}\n{}\n\n\n{}\n\n", HEADER, TUPLE_A, TUPLE_B);
+    let blocks: Vec<CompilerInput> = read_ndjson_blocks(Cursor::new(text))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].tuples.len(), 1);
+    assert_eq!(blocks[1].tuples.len(), 1);
+    assert_eq!(blocks[1].context.scope, "s1");
+    assert_eq!(blocks[1].tuples[0].subject, "Memory");
+}
+
+#[test]
+fn blocks_of_written_inputs_read_back() {
+    let first = sample_input();
+    let mut second = sample_input();
+    second.context.scope = "s2".to_string();
+    second.utterance = None;
+    second.tuples.truncate(1);
+
+    let mut buf = Vec::new();
+    write_ndjson(&first, &mut buf).unwrap();
+    buf.extend_from_slice(b"\n");
+    write_ndjson(&second, &mut buf).unwrap();
+
+    let blocks: Vec<CompilerInput> = read_ndjson_blocks(Cursor::new(buf))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(json(&blocks[0]), json(&first));
+    assert_eq!(json(&blocks[1]), json(&second));
+}
+
+#[test]
+fn a_context_without_tuples_is_its_own_block() {
+    let header2 = HEADER.replace("\"s1\"", "\"s2\"");
+    let text = format!("{}\n{}\n{}\n", HEADER, header2, TUPLE_A);
+    let blocks: Vec<CompilerInput> = read_ndjson_blocks(Cursor::new(text))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert!(blocks[0].tuples.is_empty());
+    assert_eq!(blocks[1].context.scope, "s2");
+    assert_eq!(blocks[1].tuples.len(), 1);
+}
+
+#[test]
+fn blocks_stop_after_the_first_error() {
+    let text = format!(
+        "{}\n{}\n\n{}\nnot json\n{}\n",
+        HEADER, TUPLE_A, TUPLE_B, TUPLE_A
+    );
+    let mut blocks = read_ndjson_blocks(Cursor::new(text));
+    assert_eq!(blocks.next().unwrap().unwrap().tuples.len(), 1);
+    assert!(matches!(
+        blocks.next(),
+        Some(Err(IngestError::Json { line: 5, .. }))
+    ));
+    assert!(blocks.next().is_none());
+}
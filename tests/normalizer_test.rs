@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use zero_memory::compiler::emitter::{compile, compile_with};
+use zero_memory::compiler::normalizer::{normalize_label, normalize_predicate};
+use zero_memory::compiler::{hasher, CompilerOptions, DefaultNormalizer, Normalizer};
+#[cfg(feature = "sqlite")]
+use zero_memory::store::SqliteStore;
+use zero_memory::store::{MemoryBackend, MemoryStore};
+use zero_memory::types::*;
+
+/// Keeps only letters and digits, lowercased, so `Working Set`,
+/// `working-set`, and `WorkingSet` are all `workingset`.
+#[derive(Debug)]
+struct Squash;
+
+impl Normalizer for Squash {
+    fn normalize_label(&self, raw: &str) -> String {
+        raw.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    fn normalize_predicate(&self, raw: &str) -> String {
+        self.normalize_label(raw)
+    }
+}
+
+fn input() -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: vec![SemanticTuple::from((
+            "Working Set",
+            "Fits In",
+            "Main  Memory",
+            0.9,
+        ))],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn squash() -> CompilerOptions {
+    CompilerOptions {
+        normalizer: Arc::new(Squash),
+        ..CompilerOptions::default()
+    }
+}
+
+fn label_of<B: MemoryBackend>(store: &B, label: &str) -> Option<String>
+where
+    B::Error: std::fmt::Debug,
+{
+    store
+        .get_concept_by_label(label)
+        .unwrap()
+        .map(|concept| concept.label)
+}
+
+#[test]
+fn the_default_normalizer_is_the_free_functions() {
+    let normalizer = DefaultNormalizer::default();
+    for raw in ["  Long_Term Memory ", "Stra\u{df}e", "has.Part"] {
+        assert_eq!(normalizer.normalize_label(raw), normalize_label(raw));
+        assert_eq!(
+            normalizer.normalize_predicate(raw),
+            normalize_predicate(raw)
+        );
+    }
+
+    let explicit = CompilerOptions {
+        normalizer: Arc::new(DefaultNormalizer::default()),
+        ..CompilerOptions::default()
+    };
+    assert_eq!(
+        compile_with(&input(), &explicit).graph_text,
+        compile(&input()).graph_text
+    );
+}
+
+#[test]
+fn compile_hashes_what_the_normalizer_returns() {
+    let record = compile_with(&input(), &squash()).record;
+    let mut labels: Vec<&str> = record.concepts.iter().map(|c| c.label.as_str()).collect();
+    labels.sort_unstable();
+    assert_eq!(labels, ["mainmemory", "workingset"]);
+    assert_eq!(
+        record.relations[0].subject_hash,
+        hasher::concept_hash("workingset")
+    );
+    assert_eq!(
+        record.relations[0].fact_hash,
+        hasher::fact_hash("workingset", "fitsin", "mainmemory")
+    );
+    assert_eq!(&*record.relations[0].predicate, "fitsin");
+}
+
+fn check_store<B: MemoryBackend>(mut matching: B, mut default: B)
+where
+    B::Error: std::fmt::Debug,
+{
+    let record = compile_with(&input(), &squash()).record;
+    matching.insert_record(record.clone()).unwrap();
+    default.insert_record(record).unwrap();
+
+    for query in ["workingset", "Working Set", "working-set", "Working_Set"] {
+        assert_eq!(
+            label_of(&matching, query).as_deref(),
+            Some("workingset"),
+            "{}",
+            query
+        );
+    }
+    // Under the default normalizer, only the label and the surface forms
+    // indexed as aliases (`Working Set`) still resolve.
+    assert_eq!(
+        label_of(&default, "Working Set").as_deref(),
+        Some("workingset")
+    );
+    assert_eq!(label_of(&default, "working-set"), None);
+    assert_eq!(label_of(&default, "Working_Set"), None);
+}
+
+#[test]
+fn memory_stores_look_up_labels_with_the_compile_normalizer() {
+    check_store(
+        MemoryStore::with_normalizer(Arc::new(Squash)),
+        MemoryStore::new(),
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_stores_look_up_labels_with_the_compile_normalizer() {
+    check_store(
+        SqliteStore::open_in_memory()
+            .unwrap()
+            .with_normalizer(Arc::new(Squash)),
+        SqliteStore::open_in_memory().unwrap(),
+    );
+}
@@ -0,0 +1,127 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::io::{from_ntriples, IriLabel, NTriplesOptions, RdfImportError};
+use zero_memory::types::ContextMeta;
+
+const FIXTURE: &str = include_str!("data/people.nt");
+
+fn context() -> ContextMeta {
+    ContextMeta {
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+        source: "rdf".to_string(),
+        scope: "people".to_string(),
+        agent_id: None,
+        session_id: None,
+        metadata: None,
+    }
+}
+
+fn malformed_line(text: &str) -> usize {
+    match from_ntriples(text, context(), &NTriplesOptions::default()) {
+        Err(RdfImportError::Malformed { line, .. }) => line,
+        Ok(_) => panic!("expected {:?} to be malformed", text),
+    }
+}
+
+#[test]
+fn fixture_compiles_to_the_expected_graph() {
+    let input = from_ntriples(FIXTURE, context(), &NTriplesOptions::default()).unwrap();
+    assert_eq!(input.tuples.len(), 31);
+    assert!(input.tuples.iter().all(|t| t.confidence == 1.0));
+
+    let output = compile(&input);
+    // The repeated `alice knows bob` is one relation; the `Rust` and
+    // `0-openclaw` literals name the concepts their IRIs do.
+    assert_eq!(output.record.concepts.len(), 23);
+    assert_eq!(output.record.relations.len(), 30);
+    assert_eq!(output.tuples_skipped, 0);
+}
+
+#[test]
+fn iris_map_to_local_names() {
+    let input = from_ntriples(FIXTURE, context(), &NTriplesOptions::default()).unwrap();
+    let first = &input.tuples[0];
+    assert_eq!(first.subject, "alice");
+    assert_eq!(first.predicate, "name");
+    assert_eq!(first.object, "Alice Smith");
+
+    let find = |predicate: &str, subject: &str| {
+        input
+            .tuples
+            .iter()
+            .find(|t| t.predicate == predicate && t.subject == subject)
+            .unwrap()
+            .object
+            .clone()
+    };
+    assert_eq!(find("worksOn", "carol"), "openclaw");
+    assert_eq!(find("homepage", "zeroMemory"), "0-memory");
+    assert_eq!(find("mbox", "carol"), "carol@example.org");
+    assert_eq!(find("type", "Rust"), "ProgrammingLanguage");
+    assert_eq!(find("address", "alice"), "_:addr1");
+}
+
+#[test]
+fn full_iris_can_be_kept() {
+    let options = NTriplesOptions {
+        iri_label: IriLabel::Full,
+        confidence: 0.7,
+    };
+    let input = from_ntriples(FIXTURE, context(), &options).unwrap();
+    let first = &input.tuples[1];
+    assert_eq!(first.subject, "http://example.org/people#alice");
+    assert_eq!(first.predicate, "knows");
+    assert_eq!(first.object, "http://example.org/people#bob");
+    assert!(input.tuples.iter().all(|t| t.confidence == 0.7));
+
+    // The `Rust` and `0-openclaw` literals no longer match their IRIs.
+    assert_eq!(compile(&input).record.concepts.len(), 25);
+}
+
+#[test]
+fn literals_keep_their_lexical_form() {
+    let input = from_ntriples(FIXTURE, context(), &NTriplesOptions::default()).unwrap();
+    let objects: Vec<&str> = input.tuples.iter().map(|t| t.object.as_str()).collect();
+    assert!(objects.contains(&"42"));
+    assert!(objects.contains(&"Carol \"CJ\" Lee"));
+    assert!(objects.contains(&"Agent memory, compiled"));
+    assert!(objects.contains(&"Mémoire d'agent"));
+}
+
+#[test]
+fn escapes_in_literals_are_decoded() {
+    let text = r#"<http://e.org/a> <http://e.org/says> "tab\there\nback\\slash \U0001F600" ."#;
+    let input = from_ntriples(text, context(), &NTriplesOptions::default()).unwrap();
+    assert_eq!(input.tuples[0].object, "tab\there\nback\\slash 😀");
+}
+
+#[test]
+fn malformed_lines_report_their_number() {
+    let good = "<http://e.org/a> <http://e.org/p> <http://e.org/b> .";
+    let cases = [
+        "<http://e.org/a> <http://e.org/p> <http://e.org/b>",
+        "<http://e.org/a> <http://e.org/p> <http://e.org/b",
+        "<http://e.org/a> \"p\" <http://e.org/b> .",
+        "\"a\" <http://e.org/p> <http://e.org/b> .",
+        "<http://e.org/a> <http://e.org/p> \"unterminated .",
+        "<http://e.org/a> <http://e.org/p> \"bad \\q escape\" .",
+        "<http://e.org/a b> <http://e.org/p> <http://e.org/b> .",
+        "<http://e.org/a> <http://e.org/p> \"x\"@ .",
+        "<http://e.org/a> <http://e.org/p> \"x\"^^xsd:string .",
+        "_: <http://e.org/p> <http://e.org/b> .",
+        "<http://e.org/a> <http://e.org/p> <http://e.org/b> . trailing",
+        "<http://e.org/a> <http://e.org/p> \"\\u00ZZ\" .",
+    ];
+    for bad in cases {
+        let text = format!("# header\n{}\n\n{}\n{}\n", good, good, bad);
+        assert_eq!(malformed_line(&text), 5, "{}", bad);
+    }
+}
+
+#[test]
+fn error_names_the_line() {
+    let err = from_ntriples("\n<a> <p> .\n", context(), &NTriplesOptions::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2: expected an IRI, blank node, or literal"
+    );
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "openclaw")]
+
+use zero_memory::adapters::openclaw::OpenclawAdapter;
+use zero_memory::compiler::emitter::compile;
+use zero_memory::runtime_trait::{MemoryRuntime, StateNamespace};
+use zero_memory::store::{MemoryStore, PersistError};
+use zero_memory::types::*;
+
+fn store(scopes: usize) -> MemoryStore {
+    let mut store = MemoryStore::new();
+    for scope in 0..scopes {
+        let input = CompilerInput::builder(ContextMeta::now("observation", &format!("s{}", scope)))
+            .tuple(&format!("agent_{}", scope), "observed", "Paris", 0.9)
+            .tuple("Paris", "located_in", "France", 1.0)
+            .tuple(&format!("agent_{}", scope), "prefers", "café au lait", 0.7)
+            .build()
+            .unwrap();
+        store.insert_record(compile(&input).record);
+    }
+    store
+}
+
+fn json_of(store: &MemoryStore) -> Vec<u8> {
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    json
+}
+
+#[test]
+fn stores_round_trip_through_openclaw_state() {
+    let adapter = OpenclawAdapter::new();
+    let original = store(200);
+    original.persist_to_with(&adapter, "memory", 4096).unwrap();
+
+    let chunks = adapter
+        .list_state_keys("memory/chunk/")
+        .unwrap()
+        .unwrap()
+        .len();
+    assert!(chunks > 1);
+    let restored = MemoryStore::restore_from(&adapter, "memory").unwrap();
+    assert_eq!(restored.concept_count(), original.concept_count());
+    assert_eq!(restored.relation_count(), original.relation_count());
+    assert_eq!(json_of(&restored), json_of(&original));
+}
+
+#[tokio::test]
+async fn stores_round_trip_inside_a_current_thread_runtime() {
+    let adapter = OpenclawAdapter::new();
+    let agent = adapter.namespaced(StateNamespace::new().agent_id("a"));
+    store(3).persist_to(&agent, "memory").unwrap();
+
+    assert_eq!(
+        MemoryStore::restore_from(&agent, "memory")
+            .unwrap()
+            .context_count(),
+        3
+    );
+    assert!(matches!(
+        MemoryStore::restore_from(&adapter, "memory"),
+        Err(PersistError::NoManifest { .. })
+    ));
+}
@@ -0,0 +1,131 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::hasher;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+fn input(scope: &str, tuples: &[(&str, &str, Polarity)]) -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: tuples
+            .iter()
+            .map(|(predicate, object, polarity)| SemanticTuple {
+                subject: "user".to_string(),
+                predicate: predicate.to_string(),
+                object: object.to_string(),
+                confidence: 0.9,
+                polarity: *polarity,
+                ..Default::default()
+            })
+            .collect(),
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: scope.to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn store() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.insert_record(
+        compile(&input(
+            "s1",
+            &[
+                ("likes", "tea", Polarity::Positive),
+                ("likes", "mushrooms", Polarity::Negative),
+                ("likes", "coffee", Polarity::Positive),
+            ],
+        ))
+        .record,
+    );
+    store
+}
+
+fn objects(store: &MemoryStore, relations: Vec<&RelationNode>) -> Vec<String> {
+    let mut labels: Vec<String> = relations
+        .iter()
+        .map(|r| store.label_of(&r.object_hash).unwrap().to_string())
+        .collect();
+    labels.sort();
+    labels
+}
+
+fn negative_mushrooms() -> FactHash {
+    hasher::fact_hash_with_polarity("user", "likes", "mushrooms", Polarity::Negative)
+}
+
+#[test]
+fn relations_filter_by_polarity() {
+    let store = store();
+    let user = hasher::concept_hash("user");
+
+    let negative = store.get_relations_with_polarity(&user, Polarity::Negative);
+    assert_eq!(objects(&store, negative), ["mushrooms"]);
+    let positive = store.get_relations_with_polarity(&user, Polarity::Positive);
+    assert_eq!(objects(&store, positive), ["coffee", "tea"]);
+    assert_eq!(store.get_relations(&user).len(), 3);
+}
+
+#[test]
+fn negative_facts_are_indexed() {
+    let store = store();
+    let negative: Vec<&FactHash> = store.negative_facts().collect();
+    assert_eq!(negative, [&negative_mushrooms()]);
+}
+
+#[test]
+fn positive_and_negative_observations_are_separate_facts() {
+    let mut store = store();
+    let result = store
+        .insert_record(compile(&input("s2", &[("likes", "mushrooms", Polarity::Positive)])).record);
+    assert_eq!(result.new_facts, 1);
+    assert_eq!(store.fact_count(), 4);
+    assert_eq!(store.negative_facts().count(), 1);
+}
+
+#[test]
+fn forgotten_negative_facts_are_hidden() {
+    let mut store = store();
+    store
+        .forget_fact(
+            &negative_mushrooms(),
+            "retracted",
+            "2026-03-01T00:00:00Z",
+            Reobservation::Suppress,
+        )
+        .unwrap();
+
+    assert_eq!(store.negative_facts().count(), 0);
+    let user = hasher::concept_hash("user");
+    assert!(store
+        .get_relations_with_polarity(&user, Polarity::Negative)
+        .is_empty());
+}
+
+#[test]
+fn snapshots_keep_polarity() {
+    let store = store();
+
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+
+    for loaded in [
+        MemoryStore::load_binary(binary.as_slice()).unwrap(),
+        MemoryStore::load_json(json.as_slice()).unwrap(),
+    ] {
+        let negative: Vec<&FactHash> = loaded.negative_facts().collect();
+        assert_eq!(negative, [&negative_mushrooms()]);
+        let relations = loaded.get_relations_by_fact(&negative_mushrooms());
+        assert_eq!(relations[0].polarity, Polarity::Negative);
+        let tea = hasher::fact_hash("user", "likes", "tea");
+        assert_eq!(
+            loaded.get_relations_by_fact(&tea)[0].polarity,
+            Polarity::Positive
+        );
+    }
+}
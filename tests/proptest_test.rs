@@ -0,0 +1,106 @@
+#![cfg(feature = "proptest-support")]
+
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use zero_memory::adapters::convert::{
+    input_to_value, record_to_value, value_to_input, value_to_record,
+};
+use zero_memory::arbitrary::{arb_compiler_input, arb_consistent_memory_record};
+use zero_memory::compiler::emitter::compile;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+proptest! {
+    #[test]
+    fn consistent_records_validate(record in arb_consistent_memory_record()) {
+        prop_assert_eq!(record.validate(), Ok(()));
+    }
+
+    #[test]
+    fn arbitrary_inputs_compile_every_tuple(input in any::<CompilerInput>()) {
+        let output = compile(&input);
+        prop_assert_eq!(output.tuples_skipped, 0);
+        // Qualifiers compile to relations of their own.
+        prop_assert!(
+            output.record.relations.len() + output.duplicates_collapsed >= input.tuples.len()
+        );
+    }
+
+    #[test]
+    fn insert_record_keeps_counts_coherent(record in any::<MemoryRecord>()) {
+        let mut store = MemoryStore::new();
+        let result = store.insert_record(record.clone());
+        let facts: HashSet<&FactHash> = record.relations.iter().map(|r| &r.fact_hash).collect();
+        prop_assert_eq!(result.new_concepts, record.concepts.len());
+        prop_assert_eq!(result.new_episodes, record.relations.len());
+        prop_assert_eq!(result.new_facts, facts.len());
+        prop_assert_eq!(store.concept_count(), record.concepts.len());
+        prop_assert_eq!(store.relation_count(), record.relations.len());
+        prop_assert_eq!(store.fact_count(), facts.len());
+        prop_assert_eq!(store.context_count(), 1);
+
+        let again = store.insert_record(record.clone());
+        prop_assert_eq!(again.new_concepts + again.new_facts + again.new_episodes, 0);
+        prop_assert_eq!(again.concepts_updated, 0);
+        prop_assert_eq!(again.dupes_skipped, record.concepts.len() + record.relations.len());
+        prop_assert_eq!(store.concept_count(), record.concepts.len());
+        prop_assert_eq!(store.relation_count(), record.relations.len());
+    }
+
+    #[test]
+    fn inserts_add_up_across_records(
+        records in prop::collection::vec(arb_consistent_memory_record(), 1..5),
+    ) {
+        let mut store = MemoryStore::new();
+        let (mut concepts, mut episodes, mut contexts) =
+            (HashSet::new(), HashSet::new(), HashSet::new());
+        let mut new_concepts = 0;
+        for record in records {
+            concepts.extend(record.concepts.iter().map(|c| c.hash.clone()));
+            episodes.extend(record.relations.iter().map(|r| r.episode_hash.clone()));
+            contexts.insert(record.context.hash.clone());
+            new_concepts += store.insert_record(record).new_concepts;
+        }
+        prop_assert_eq!(store.concept_count(), concepts.len());
+        prop_assert_eq!(new_concepts, concepts.len());
+        prop_assert_eq!(store.relation_count(), episodes.len());
+        prop_assert_eq!(store.context_count(), contexts.len());
+    }
+
+    #[test]
+    fn records_round_trip_through_values(record in any::<MemoryRecord>()) {
+        let value = record_to_value(&record);
+        let back = value_to_record(&value).unwrap();
+        prop_assert_eq!(back.hash(), record.hash());
+        prop_assert_eq!(record_to_value(&back), value);
+    }
+
+    #[test]
+    fn inputs_round_trip_through_values(input in any::<CompilerInput>()) {
+        let value = input_to_value(&input);
+        prop_assert_eq!(input_to_value(&value_to_input(&value).unwrap()), value);
+    }
+}
+
+#[test]
+fn common_labels_make_records_overlap() {
+    let mut runner = proptest::test_runner::TestRunner::deterministic();
+    let strategy = prop::collection::vec(arb_compiler_input(), 8);
+    let inputs = strategy.new_tree(&mut runner).unwrap().current();
+    let mut seen = HashSet::new();
+    let shared = inputs
+        .iter()
+        .flat_map(|input| {
+            let record = compile(input).record;
+            record
+                .concepts
+                .into_iter()
+                .map(|c| c.hash)
+                .collect::<HashSet<_>>()
+        })
+        .filter(|hash| !seen.insert(hash.clone()))
+        .count();
+    assert!(shared > 0);
+}
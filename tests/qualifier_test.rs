@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use zero_memory::compiler::emitter::{compile, try_compile};
+use zero_memory::compiler::{hasher, CompilerError};
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+fn met(qualifiers: &[(&str, &str)]) -> SemanticTuple {
+    SemanticTuple {
+        subject: "Alice".to_string(),
+        predicate: "met".to_string(),
+        object: "Bob".to_string(),
+        confidence: 0.9,
+        qualifiers: qualifiers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn input(tuples: Vec<SemanticTuple>) -> CompilerInput {
+    CompilerInput {
+        utterance: Some("Alice met Bob in Paris on Tuesday".to_string()),
+        tuples,
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn statement_of(relation: &RelationNode) -> ConceptHash {
+    hasher::concept_hash(&hasher::statement_label(&relation.episode_hash))
+}
+
+#[test]
+fn qualifiers_are_reified_off_a_statement_concept() {
+    let output = compile(&input(vec![met(&[
+        ("Location", "Paris"),
+        ("day", "Tuesday"),
+    ])]));
+    let record = &output.record;
+
+    let main = &record.relations[0];
+    assert_eq!(main.fact_hash, hasher::fact_hash("alice", "met", "bob"));
+    let statement = statement_of(main);
+    assert!(record.concepts.iter().any(|c| c.hash == statement));
+
+    let mut qualifiers: Vec<&RelationNode> = record
+        .relations
+        .iter()
+        .filter(|r| r.subject_hash == statement)
+        .collect();
+    qualifiers.sort_by(|a, b| a.predicate.cmp(&b.predicate));
+    assert_eq!(qualifiers.len(), 2);
+    assert_eq!(&*qualifiers[0].predicate, "day");
+    assert_eq!(qualifiers[0].object_hash, hasher::concept_hash("tuesday"));
+    assert_eq!(&*qualifiers[1].predicate, "location");
+    assert_eq!(qualifiers[1].object_hash, hasher::concept_hash("paris"));
+}
+
+#[test]
+fn qualifiers_change_the_episode_but_not_the_fact() {
+    let output = compile(&input(vec![
+        met(&[("location", "Paris")]),
+        met(&[("location", "London")]),
+        met(&[]),
+    ]));
+    let fact = hasher::fact_hash("alice", "met", "bob");
+    let mains: Vec<&RelationNode> = output
+        .record
+        .relations
+        .iter()
+        .filter(|r| r.fact_hash == fact)
+        .collect();
+
+    assert_eq!(output.duplicates_collapsed, 0);
+    assert_eq!(mains.len(), 3);
+    assert_ne!(mains[0].episode_hash, mains[1].episode_hash);
+    let ctx = hasher::context_hash(&input(vec![]).context);
+    assert_eq!(mains[2].episode_hash, hasher::episode_hash(&fact, &ctx));
+}
+
+#[test]
+fn same_fact_and_qualifiers_collapse() {
+    let output = compile(&input(vec![
+        met(&[("location", "Paris")]),
+        met(&[("Location", "paris")]),
+    ]));
+    assert_eq!(output.duplicates_collapsed, 1);
+    assert_eq!(output.record.relations.len(), 2);
+}
+
+#[test]
+fn qualifier_keys_differing_only_in_case_are_one_qualifier() {
+    let twice = try_compile(&input(vec![met(&[
+        ("Location", "Berlin"),
+        ("location", "berlin"),
+    ])]))
+    .unwrap();
+    let once = try_compile(&input(vec![met(&[("location", "Berlin")])])).unwrap();
+
+    assert_eq!(twice.record.relations.len(), 2);
+    assert_eq!(
+        twice.record.relations[0].episode_hash,
+        once.record.relations[0].episode_hash
+    );
+    twice.record.validate().unwrap();
+    let berlin = twice
+        .record
+        .concepts
+        .iter()
+        .find(|c| c.label == "berlin")
+        .unwrap();
+    assert!(berlin.aliases.iter().any(|a| a == "Berlin"));
+}
+
+#[test]
+fn unqualified_tuples_mint_no_statement() {
+    let output = compile(&input(vec![met(&[])]));
+    assert_eq!(output.record.relations.len(), 1);
+    assert_eq!(output.record.concepts.len(), 2);
+    assert!(!output.graph_text.contains("statement_hash"));
+}
+
+#[test]
+fn store_answers_qualifier_queries() {
+    let output = compile(&input(vec![
+        met(&[("location", "Paris"), ("day", "Tuesday")]),
+        met(&[]),
+    ]));
+    let qualified = output.record.relations[0].episode_hash.clone();
+    let plain = output.record.relations[3].episode_hash.clone();
+
+    let mut store = MemoryStore::new();
+    store.insert_record(output.record);
+
+    assert_eq!(
+        store.get_qualifiers(&qualified),
+        [("day", "tuesday"), ("location", "paris")]
+    );
+    assert!(store.get_qualifiers(&plain).is_empty());
+    let paris = store.get_concept_by_label("paris").unwrap();
+    assert_eq!(store.get_relations(&paris.hash).len(), 1);
+}
+
+#[test]
+fn graph_text_links_relation_to_its_statement() {
+    let output = compile(&input(vec![met(&[("location", "Paris")])]));
+    let statement = statement_of(&output.record.relations[0]);
+    assert!(output
+        .graph_text
+        .contains(&format!(r#""statement_hash":"{}""#, statement)));
+    assert!(output.graph_text.contains(r#""predicate":"location""#));
+}
+
+#[test]
+fn try_compile_rejects_blank_qualifiers() {
+    let err = try_compile(&input(vec![met(&[("location", "  ")])])).unwrap_err();
+    assert_eq!(
+        err,
+        CompilerError::EmptyQualifier {
+            tuple_index: 0,
+            key: "location".to_string(),
+        }
+    );
+}
+
+#[test]
+fn tuples_without_qualifiers_parse() {
+    let tuple: SemanticTuple = serde_json::from_str(
+        r#"{ "subject": "alice", "predicate": "met", "object": "bob", "confidence": 0.9 }"#,
+    )
+    .unwrap();
+    assert_eq!(tuple.qualifiers, HashMap::new());
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::types::*;
+
+/// xorshift64: enough randomness to vary records across seeds, and
+/// reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+const LABELS: &[&str] = &["Agent", "Memory", "Alice", "Bob", "Paris", "Tea", "Lisbon"];
+const PREDICATES: &[&str] = &["needs", "likes", "met", "lives_in", "knows"];
+
+/// A compiled record with a few relations, some negative, some bounded in
+/// time, some qualified, some with spans; varied by `seed`.
+fn random_record(seed: u64) -> MemoryRecord {
+    let mut rng = Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1);
+    let tuples = (0..2 + rng.below(5))
+        .map(|_| {
+            let mut tuple = SemanticTuple {
+                subject: rng.pick(LABELS).to_string(),
+                predicate: rng.pick(PREDICATES).to_string(),
+                object: rng.pick(LABELS).to_string(),
+                confidence: (1 + rng.below(100)) as f64 / 100.0,
+                ..Default::default()
+            };
+            if rng.below(3) == 0 {
+                tuple.polarity = Polarity::Negative;
+            }
+            if rng.below(3) == 0 {
+                tuple.valid_from = Some("2024-01-01".to_string());
+            }
+            if rng.below(4) == 0 {
+                tuple.qualifiers =
+                    HashMap::from([("location".to_string(), rng.pick(LABELS).to_string())]);
+            }
+            if rng.below(2) == 0 {
+                tuple.evidence_span = Some(Span { start: 0, end: 3 });
+            }
+            tuple
+        })
+        .collect();
+    compile(&CompilerInput {
+        utterance: Some("the utterance".to_string()),
+        tuples,
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: format!("scope-{}", seed),
+            agent_id: Some("agent-1".to_string()),
+            session_id: None,
+            metadata: Some(HashMap::from([
+                ("channel".to_string(), "chat".to_string()),
+                ("locale".to_string(), "en".to_string()),
+            ])),
+        },
+    })
+    .record
+}
+
+/// The next float up, the smallest possible change to a confidence.
+fn nudge(x: f64) -> f64 {
+    f64::from_bits(x.to_bits() + 1)
+}
+
+/// One second after `time`, the smallest change to a timestamp that shows.
+fn tick(time: EventTime) -> EventTime {
+    (time.as_datetime() + chrono::Duration::seconds(1)).into()
+}
+
+/// `bytes` with the low bit of byte `index` flipped.
+fn flip(bytes: &[u8; 32], index: usize) -> [u8; 32] {
+    let mut bytes = *bytes;
+    bytes[index] ^= 1;
+    bytes
+}
+
+type Mutation = (&'static str, fn(&mut MemoryRecord));
+
+/// Every field of a record, changed one at a time.
+const MUTATIONS: &[Mutation] = &[
+    ("concept hash", |r| {
+        r.concepts[0].hash = ConceptHash::from_bytes(flip(r.concepts[0].hash.as_bytes(), 31))
+    }),
+    ("concept label", |r| r.concepts[0].label.push('x')),
+    ("concept aliases", |r| {
+        r.concepts[0].aliases.push("x".to_string())
+    }),
+    ("concept confidence", |r| {
+        r.concepts[0].confidence = nudge(r.concepts[0].confidence)
+    }),
+    ("concept created_at", |r| {
+        r.concepts[0].created_at = tick(r.concepts[0].created_at)
+    }),
+    ("concept updated_at", |r| {
+        r.concepts[0].updated_at = tick(r.concepts[0].updated_at)
+    }),
+    ("concept removed", |r| {
+        r.concepts.pop();
+    }),
+    ("relation fact_hash", |r| {
+        r.relations[0].fact_hash =
+            FactHash::from_bytes(flip(r.relations[0].fact_hash.as_bytes(), 0))
+    }),
+    ("relation episode_hash", |r| {
+        r.relations[0].episode_hash =
+            EpisodeHash::from_bytes(flip(r.relations[0].episode_hash.as_bytes(), 31))
+    }),
+    ("relation subject_hash", |r| {
+        r.relations[0].subject_hash =
+            ConceptHash::from_bytes(flip(r.relations[0].subject_hash.as_bytes(), 0))
+    }),
+    ("relation predicate", |r| {
+        r.relations[0].predicate = format!("{}x", r.relations[0].predicate).into()
+    }),
+    ("relation object_hash", |r| {
+        r.relations[0].object_hash =
+            ConceptHash::from_bytes(flip(r.relations[0].object_hash.as_bytes(), 0))
+    }),
+    ("relation confidence", |r| {
+        r.relations[0].confidence = nudge(r.relations[0].confidence)
+    }),
+    ("relation context_hash", |r| {
+        r.relations[0].context_hash =
+            ContextHash::from_bytes(flip(r.relations[0].context_hash.as_bytes(), 0))
+    }),
+    ("relation created_at", |r| {
+        r.relations[0].created_at = tick(r.relations[0].created_at)
+    }),
+    ("relation polarity", |r| {
+        r.relations[0].polarity = match r.relations[0].polarity {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+        }
+    }),
+    ("relation valid_from", |r| {
+        r.relations[0].valid_from = Some("1999".to_string())
+    }),
+    ("relation valid_to", |r| {
+        r.relations[0].valid_to = Some("2999".to_string())
+    }),
+    ("relation subject_span", |r| {
+        r.relations[0].subject_span = Some(Span { start: 1, end: 2 })
+    }),
+    ("relation object_span", |r| {
+        r.relations[0].object_span = Some(Span { start: 1, end: 2 })
+    }),
+    ("relation evidence_span", |r| {
+        r.relations[0].evidence_span = Some(Span { start: 9, end: 9 })
+    }),
+    ("relation removed", |r| {
+        r.relations.pop();
+    }),
+    ("context hash", |r| {
+        r.context.hash = ContextHash::from_bytes(flip(r.context.hash.as_bytes(), 0))
+    }),
+    ("context event_time", |r| {
+        r.context.meta.event_time = tick(r.context.meta.event_time)
+    }),
+    ("context source", |r| r.context.meta.source.push('x')),
+    ("context scope", |r| r.context.meta.scope.push('x')),
+    ("context agent_id", |r| r.context.meta.agent_id = None),
+    ("context session_id", |r| {
+        r.context.meta.session_id = Some("s".to_string())
+    }),
+    ("context metadata value", |r| {
+        r.context
+            .meta
+            .metadata
+            .as_mut()
+            .unwrap()
+            .insert("locale".to_string(), "pt".to_string());
+    }),
+    ("context metadata removed", |r| {
+        r.context.meta.metadata = None
+    }),
+];
+
+#[test]
+fn order_does_not_change_the_hash() {
+    for seed in 0..64 {
+        let record = random_record(seed);
+        let mut rng = Rng(seed + 1);
+        let mut shuffled = record.clone();
+        rng.shuffle(&mut shuffled.concepts);
+        rng.shuffle(&mut shuffled.relations);
+        assert_eq!(shuffled.hash(), record.hash(), "seed {}", seed);
+    }
+}
+
+#[test]
+fn every_field_changes_the_hash() {
+    for seed in 0..64 {
+        let record = random_record(seed);
+        for (field, mutate) in MUTATIONS {
+            let mut changed = record.clone();
+            mutate(&mut changed);
+            assert_ne!(changed.hash(), record.hash(), "{} (seed {})", field, seed);
+        }
+    }
+}
+
+#[test]
+fn different_records_hash_differently() {
+    let hashes: Vec<RecordHash> = (0..64).map(|seed| random_record(seed).hash()).collect();
+    for (i, a) in hashes.iter().enumerate() {
+        assert!(!hashes[i + 1..].contains(a), "seed {} collides", i);
+    }
+}
+
+#[test]
+fn metadata_insertion_order_does_not_change_the_hash() {
+    let record = random_record(7);
+    let mut reinserted = record.clone();
+    let metadata: Vec<(String, String)> = record
+        .context
+        .meta
+        .metadata
+        .clone()
+        .unwrap()
+        .into_iter()
+        .collect();
+    let mut rebuilt = HashMap::new();
+    for (key, value) in metadata.into_iter().rev() {
+        rebuilt.insert(key, value);
+    }
+    reinserted.context.meta.metadata = Some(rebuilt);
+    assert_eq!(reinserted.hash(), record.hash());
+}
+
+#[test]
+fn the_proof_is_not_part_of_the_content() {
+    let record = random_record(3);
+    let mut proven = record.clone();
+    proven.proof = Some(Proof {
+        trace_hash: "00".repeat(32),
+        signer: "11".repeat(32),
+        signature: "22".repeat(64),
+    });
+    assert_eq!(proven.hash(), record.hash());
+}
+
+#[test]
+fn the_hash_survives_a_json_round_trip() {
+    let record = random_record(11);
+    let json = serde_json::to_string(&record).unwrap();
+    let loaded: MemoryRecord = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.hash(), record.hash());
+}
+
+#[test]
+fn record_hashes_serialize_as_hex() {
+    let hash = random_record(5).hash();
+    let json = serde_json::to_string(&hash).unwrap();
+    assert_eq!(json, format!("\"{}\"", hash));
+    assert_eq!(hash.to_string().len(), 64);
+    assert_eq!(serde_json::from_str::<RecordHash>(&json).unwrap(), hash);
+}
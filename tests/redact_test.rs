@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::hasher;
+use zero_memory::store::redact::REDACTED;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+fn input(scope: &str, email: &str) -> CompilerInput {
+    let mut metadata = HashMap::new();
+    metadata.insert("email".to_string(), email.to_string());
+    metadata.insert("token".to_string(), "sk-secret".to_string());
+    metadata.insert("channel".to_string(), "slack".to_string());
+    CompilerInput {
+        utterance: None,
+        tuples: vec![SemanticTuple::from(("agent", "needs", "memory", 0.9))],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: scope.to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: Some(metadata),
+        },
+    }
+}
+
+fn store_with(inputs: &[CompilerInput]) -> MemoryStore {
+    let mut store = MemoryStore::new();
+    for input in inputs {
+        store.insert_record(compile(input).record);
+    }
+    store
+}
+
+fn metadata<'a>(store: &'a MemoryStore, hash: &ContextHash) -> &'a HashMap<String, String> {
+    store
+        .get_context(hash)
+        .unwrap()
+        .meta
+        .metadata
+        .as_ref()
+        .unwrap()
+}
+
+#[test]
+fn redacts_named_keys_only() {
+    let input = input("s1", "ada@example.com");
+    let hash = hasher::context_hash(&input.context);
+    let mut store = store_with(&[input]);
+
+    assert_eq!(
+        store.redact_context_metadata(&hash, &["email", "token", "missing"]),
+        2
+    );
+
+    let meta = metadata(&store, &hash);
+    assert_eq!(meta["email"], REDACTED);
+    assert_eq!(meta["token"], REDACTED);
+    assert_eq!(meta["channel"], "slack");
+    assert!(!meta.contains_key("missing"));
+
+    assert_eq!(
+        store.redact_context_metadata(&hash, &["email"]),
+        0,
+        "already redacted values are not counted again"
+    );
+}
+
+#[test]
+fn unknown_context_redacts_nothing() {
+    let mut store = store_with(&[input("s1", "ada@example.com")]);
+    assert_eq!(
+        store.redact_context_metadata(&ContextHash::ZERO, &["email"]),
+        0
+    );
+}
+
+#[test]
+fn redaction_keeps_context_hash() {
+    let input = input("s1", "ada@example.com");
+    let hash = hasher::context_hash(&input.context);
+    let mut store = store_with(&[input]);
+
+    store.redact_context_metadata(&hash, &["email", "token"]);
+
+    let context = store.get_context(&hash).unwrap();
+    assert_eq!(context.hash, hash);
+    assert_eq!(hasher::context_hash(&context.meta), hash);
+    for relation in store.relations() {
+        assert!(store.get_context(&relation.context_hash).is_some());
+    }
+}
+
+#[test]
+fn redacts_key_in_every_context() {
+    let mut store = store_with(&[
+        input("s1", "ada@example.com"),
+        input("s2", "bob@example.com"),
+    ]);
+
+    assert_eq!(store.redact_metadata_key_everywhere("email"), 2);
+    for context in store.contexts() {
+        let meta = context.meta.metadata.as_ref().unwrap();
+        assert_eq!(meta["email"], REDACTED);
+        assert_eq!(meta["token"], "sk-secret");
+    }
+}
+
+#[test]
+fn redacted_values_do_not_leak_out() {
+    let mut store = store_with(&[input("s1", "ada@example.com")]);
+    store.redact_metadata_key_everywhere("email");
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    let exported = serde_json::to_vec(&store.export_records()).unwrap();
+
+    let secret = b"ada@example.com";
+    for bytes in [&json, &binary, &exported] {
+        assert!(!bytes.windows(secret.len()).any(|w| w == secret));
+    }
+}
+
+#[test]
+fn reinserting_original_context_does_not_restore_values() {
+    let input = input("s1", "ada@example.com");
+    let hash = hasher::context_hash(&input.context);
+    let mut store = store_with(std::slice::from_ref(&input));
+    store.redact_metadata_key_everywhere("email");
+
+    store.insert_record(compile(&input).record);
+
+    assert_eq!(metadata(&store, &hash)["email"], REDACTED);
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "remote")]
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use zero_memory::adapters::remote::{RemoteError, RemoteRuntime};
+use zero_memory::runtime_trait::{MemoryRuntime, StateNamespace};
+
+// wiremock serves from its own thread, so the blocking client can be
+// called straight from the test.
+
+#[tokio::test]
+async fn graphs_execute_remotely() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/execute"))
+        .and(header("Authorization", "Bearer token"))
+        .and(body_json(
+            json!({"graph_source": "graph", "inputs": {"x": 1}}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"output": {"concepts": 2}})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let runtime = RemoteRuntime::new(&server.uri(), Some("token"));
+    let outputs = runtime
+        .execute_graph("graph", HashMap::from([("x".to_string(), json!(1))]))
+        .unwrap();
+    assert_eq!(
+        outputs,
+        HashMap::from([("output".to_string(), json!({"concepts": 2}))])
+    );
+}
+
+#[tokio::test]
+async fn state_maps_to_get_put_and_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/state/working_set"))
+        .and(body_json(json!(["a", "b"])))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/state/working_set"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!(["a", "b"])))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/state/working_set"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let runtime = RemoteRuntime::new(&server.uri(), None);
+    runtime
+        .save_state("working_set", &json!(["a", "b"]))
+        .unwrap();
+    assert_eq!(
+        runtime.load_state("working_set").unwrap(),
+        Some(json!(["a", "b"]))
+    );
+    runtime.delete_state("working_set").unwrap();
+}
+
+#[tokio::test]
+async fn missing_state_loads_as_none() {
+    let server = MockServer::start().await;
+    Mock::given(path("/state/agent%2Fa%2Fsession%2F%2Fgoals"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("no such key"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let runtime = RemoteRuntime::new(&server.uri(), None);
+    let agent = runtime.namespaced(StateNamespace::new().agent_id("a"));
+    assert_eq!(agent.load_state("goals").unwrap(), None);
+    runtime.delete_state("agent/a/session//goals").unwrap();
+}
+
+#[tokio::test]
+async fn server_errors_carry_status_and_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/execute"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("interpreter exploded"))
+        .mount(&server)
+        .await;
+    Mock::given(path("/state/key"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("store down"))
+        .mount(&server)
+        .await;
+
+    let runtime = RemoteRuntime::new(&server.uri(), None);
+    let err = runtime.execute_graph("graph", HashMap::new()).unwrap_err();
+    assert!(
+        matches!(&err, RemoteError::Http { status: 500, body } if body == "interpreter exploded"),
+        "{:?}",
+        err
+    );
+    assert_eq!(
+        err.to_string(),
+        "remote runtime returned HTTP 500: interpreter exploded"
+    );
+
+    for err in [
+        runtime.load_state("key").unwrap_err(),
+        runtime.save_state("key", &json!(1)).unwrap_err(),
+        runtime.delete_state("key").unwrap_err(),
+    ] {
+        assert!(
+            matches!(&err, RemoteError::Http { status: 500, body } if body == "store down"),
+            "{:?}",
+            err
+        );
+    }
+}
+
+#[tokio::test]
+async fn malformed_outputs_are_decode_errors() {
+    let server = MockServer::start().await;
+    Mock::given(path("/execute"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+        .mount(&server)
+        .await;
+
+    let runtime = RemoteRuntime::new(&server.uri(), None);
+    assert!(matches!(
+        runtime.execute_graph("graph", HashMap::new()),
+        Err(RemoteError::Decode(_))
+    ));
+}
+
+#[test]
+fn unreachable_services_are_transport_errors() {
+    let runtime = RemoteRuntime::new("http://127.0.0.1:1", None);
+    assert!(matches!(
+        runtime.load_state("key"),
+        Err(RemoteError::Transport(_))
+    ));
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "testing")]
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use zero_memory::retry::{RetryPolicy, RetryingRuntime};
+use zero_memory::runtime_trait::MemoryRuntime;
+use zero_memory::testing::{MockError, MockRuntime};
+
+fn policy(max_attempts: u32) -> RetryPolicy<MockError> {
+    RetryPolicy {
+        max_attempts,
+        backoff: Duration::from_millis(1),
+        jitter: false,
+        retry_if: |_| true,
+    }
+}
+
+fn outputs() -> HashMap<String, serde_json::Value> {
+    HashMap::from([("output".to_string(), json!({"ok": true}))])
+}
+
+#[test]
+fn transient_failures_are_retried_until_success() {
+    let mock = MockRuntime::new()
+        .with_response("graph", outputs())
+        .with_transient_error("graph", 2, "busy");
+    let runtime = RetryingRuntime::new(mock, policy(3));
+
+    assert_eq!(
+        runtime.execute_graph("graph", HashMap::new()).unwrap(),
+        outputs()
+    );
+    assert_eq!(runtime.inner().executed_graphs().len(), 3);
+}
+
+#[test]
+fn the_last_error_is_returned_once_attempts_run_out() {
+    let mock = MockRuntime::new()
+        .with_response("graph", outputs())
+        .with_transient_error("graph", 5, "busy");
+    let runtime = RetryingRuntime::new(mock, policy(3));
+
+    assert_eq!(
+        runtime.execute_graph("graph", HashMap::new()),
+        Err(MockError::Programmed("busy".to_string()))
+    );
+    assert_eq!(runtime.inner().executed_graphs().len(), 3);
+
+    // The mock has two failures left; a fresh call uses them up and succeeds.
+    assert!(runtime.execute_graph("graph", HashMap::new()).is_ok());
+    assert_eq!(runtime.inner().executed_graphs().len(), 6);
+}
+
+#[test]
+fn errors_rejected_by_retry_if_propagate_immediately() {
+    let runtime = RetryingRuntime::new(
+        MockRuntime::new(),
+        RetryPolicy {
+            retry_if: |e| matches!(e, MockError::Programmed(_)),
+            ..policy(5)
+        },
+    );
+
+    assert!(matches!(
+        runtime.execute_graph("unprogrammed", HashMap::new()),
+        Err(MockError::NoResponse { .. })
+    ));
+    assert_eq!(runtime.inner().executed_graphs().len(), 1);
+}
+
+#[test]
+fn state_calls_are_retried() {
+    let runtime = RetryingRuntime::new(MockRuntime::new().with_state_errors(2, "flaky"), policy(3));
+
+    runtime.save_state("key", &json!(1)).unwrap();
+    assert_eq!(runtime.inner().saved_keys(), ["key", "key", "key"]);
+
+    let runtime = RetryingRuntime::new(
+        runtime.into_inner().with_state_errors(1, "flaky"),
+        policy(2),
+    );
+    assert_eq!(runtime.load_state("key").unwrap(), Some(json!(1)));
+
+    let runtime = RetryingRuntime::new(
+        runtime.into_inner().with_state_errors(2, "flaky"),
+        policy(2),
+    );
+    assert_eq!(
+        runtime.load_state("key"),
+        Err(MockError::Programmed("flaky".to_string()))
+    );
+}
+
+#[test]
+fn one_attempt_means_no_retries() {
+    for max_attempts in [0, 1] {
+        let mock = MockRuntime::new()
+            .with_response("graph", outputs())
+            .with_transient_error("graph", 1, "busy");
+        let runtime = RetryingRuntime::new(mock, policy(max_attempts));
+        assert!(runtime.execute_graph("graph", HashMap::new()).is_err());
+        assert_eq!(runtime.inner().executed_graphs().len(), 1);
+    }
+}
+
+#[test]
+fn backoff_doubles_between_attempts() {
+    let mock = MockRuntime::new()
+        .with_response("graph", outputs())
+        .with_transient_error("graph", 2, "busy");
+    let runtime = RetryingRuntime::new(
+        mock,
+        RetryPolicy {
+            backoff: Duration::from_millis(20),
+            ..policy(3)
+        },
+    );
+
+    let started = Instant::now();
+    runtime.execute_graph("graph", HashMap::new()).unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(60));
+}
+
+#[test]
+fn hashing_is_passed_through() {
+    let runtime = RetryingRuntime::new(MockRuntime::new(), RetryPolicy::default());
+    assert_eq!(runtime.hash(b"hello"), runtime.inner().hash(b"hello"));
+}
@@ -0,0 +1,260 @@
+#![cfg(feature = "async")]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use zero_memory::runtime_trait::{
+    AsyncMemoryRuntime, BlockingRuntime, InlineRuntime, MemoryRuntime, NamespacedRuntime,
+    StateNamespace,
+};
+
+fn sha256(input: &[u8]) -> [u8; 32] {
+    Sha256::digest(input).into()
+}
+
+/// A runtime whose state lives behind a tokio lock and whose graphs echo
+/// their inputs, yielding once so each call really suspends. Counts the
+/// batches it is asked to save.
+#[derive(Default)]
+struct EchoRuntime {
+    state: RwLock<HashMap<String, String>>,
+    batches: AtomicUsize,
+}
+
+#[async_trait]
+impl AsyncMemoryRuntime for EchoRuntime {
+    type Value = String;
+    type Hash = [u8; 32];
+    type Error = String;
+
+    fn hash(&self, input: &[u8]) -> [u8; 32] {
+        sha256(input)
+    }
+
+    async fn execute_graph(
+        &self,
+        graph_source: &str,
+        inputs: HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, String> {
+        tokio::task::yield_now().await;
+        if graph_source.is_empty() {
+            return Err("empty graph".to_string());
+        }
+        Ok(inputs)
+    }
+
+    async fn load_state(&self, key: &str) -> Result<Option<String>, String> {
+        tokio::task::yield_now().await;
+        Ok(self.state.read().await.get(key).cloned())
+    }
+
+    async fn save_state(&self, key: &str, value: &String) -> Result<(), String> {
+        self.state
+            .write()
+            .await
+            .insert(key.to_string(), value.clone());
+        Ok(())
+    }
+
+    async fn save_state_many(&self, entries: &[(String, String)]) -> Result<(), String> {
+        self.batches.fetch_add(1, Ordering::Relaxed);
+        for (key, value) in entries {
+            self.save_state(key, value).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct SyncEchoRuntime {
+    state: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryRuntime for SyncEchoRuntime {
+    type Value = String;
+    type Hash = [u8; 32];
+    type Error = String;
+
+    fn hash(&self, input: &[u8]) -> [u8; 32] {
+        sha256(input)
+    }
+
+    fn execute_graph(
+        &self,
+        _graph_source: &str,
+        inputs: HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, String> {
+        Ok(inputs)
+    }
+
+    fn load_state(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.state.lock().unwrap().get(key).cloned())
+    }
+
+    fn save_state(&self, key: &str, value: &String) -> Result<(), String> {
+        self.state
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.clone());
+        Ok(())
+    }
+}
+
+fn exercise_sync<R: MemoryRuntime<Value = String, Error = String>>(runtime: &R) {
+    assert_eq!(runtime.load_state("key").unwrap(), None);
+    runtime.save_state("key", &"stored".to_string()).unwrap();
+    assert_eq!(
+        runtime.load_state("key").unwrap(),
+        Some("stored".to_string())
+    );
+
+    let inputs = HashMap::from([("x".to_string(), "1".to_string())]);
+    assert_eq!(
+        runtime.execute_graph("graph", inputs.clone()).unwrap(),
+        inputs
+    );
+    assert_eq!(
+        runtime.execute_graph("", HashMap::new()).unwrap_err(),
+        "empty graph"
+    );
+    assert_eq!(runtime.hash(b"hello").as_ref(), sha256(b"hello"));
+}
+
+async fn exercise_async<R: AsyncMemoryRuntime<Value = String, Error = String>>(runtime: &R) {
+    assert_eq!(runtime.load_state("key").await.unwrap(), None);
+    runtime
+        .save_state("key", &"stored".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        runtime.load_state("key").await.unwrap(),
+        Some("stored".to_string())
+    );
+
+    let inputs = HashMap::from([("x".to_string(), "1".to_string())]);
+    assert_eq!(
+        runtime
+            .execute_graph("graph", inputs.clone())
+            .await
+            .unwrap(),
+        inputs
+    );
+    assert_eq!(runtime.hash(b"hello").as_ref(), sha256(b"hello"));
+}
+
+#[test]
+fn blocking_runtime_works_outside_tokio() {
+    exercise_sync(&BlockingRuntime(EchoRuntime::default()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn blocking_runtime_works_inside_multi_thread_runtime() {
+    exercise_sync(&BlockingRuntime(EchoRuntime::default()));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn blocking_runtime_works_inside_current_thread_runtime() {
+    exercise_sync(&BlockingRuntime(EchoRuntime::default()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn async_runtimes_work_on_multi_thread_runtime() {
+    exercise_async(&EchoRuntime::default()).await;
+    exercise_async(&InlineRuntime(BlockingRuntime(EchoRuntime::default()))).await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn async_runtimes_work_on_current_thread_runtime() {
+    exercise_async(&EchoRuntime::default()).await;
+    exercise_async(&InlineRuntime(BlockingRuntime(EchoRuntime::default()))).await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn namespaces_work_on_async_runtimes() {
+    let runtime = EchoRuntime::default();
+    let namespace = StateNamespace::new().agent_id("a");
+    exercise_async(&NamespacedRuntime::new(&runtime, namespace.clone())).await;
+    assert_eq!(
+        runtime.load_state(&namespace.key("key")).await.unwrap(),
+        Some("stored".to_string())
+    );
+    assert_eq!(runtime.load_state("key").await.unwrap(), None);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn inline_runtime_wraps_sync_runtimes() {
+    let runtime = InlineRuntime(SyncEchoRuntime::default());
+    runtime
+        .save_state("key", &"stored".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        runtime.load_state("key").await.unwrap(),
+        Some("stored".to_string())
+    );
+    assert_eq!(
+        runtime.0.load_state("key").unwrap(),
+        Some("stored".to_string())
+    );
+}
+
+fn entries(n: usize) -> Vec<(String, String)> {
+    (0..n)
+        .map(|i| (format!("key_{}", i), format!("value_{}", i)))
+        .collect()
+}
+
+fn exercise_batches<R: MemoryRuntime<Value = String, Error = String>>(runtime: &R) {
+    runtime.save_state_many(&entries(500)).unwrap();
+    let keys: Vec<String> = ["key_0", "key_250", "key_499", "key_500"]
+        .iter()
+        .map(|key| key.to_string())
+        .collect();
+    assert_eq!(
+        runtime.load_state_many(&keys).unwrap(),
+        [
+            Some("value_0".to_string()),
+            Some("value_250".to_string()),
+            Some("value_499".to_string()),
+            None,
+        ]
+    );
+    assert_eq!(
+        runtime.load_state("key_42").unwrap(),
+        Some("value_42".to_string())
+    );
+}
+
+#[test]
+fn batches_cross_the_bridge_once() {
+    let runtime = BlockingRuntime(EchoRuntime::default());
+    exercise_batches(&runtime);
+    assert_eq!(runtime.0.batches.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batches_work_inside_multi_thread_runtime() {
+    exercise_batches(&BlockingRuntime(EchoRuntime::default()));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn batches_work_inside_current_thread_runtime() {
+    exercise_batches(&BlockingRuntime(EchoRuntime::default()));
+}
+
+#[test]
+fn default_batches_loop_over_single_keys() {
+    let runtime = SyncEchoRuntime::default();
+    runtime.save_state_many(&entries(3)).unwrap();
+    assert_eq!(runtime.state.lock().unwrap().len(), 3);
+    assert_eq!(
+        runtime
+            .load_state_many(&["key_2".to_string(), "nope".to_string()])
+            .unwrap(),
+        [Some("value_2".to_string()), None]
+    );
+}
@@ -0,0 +1,147 @@
+use zero_memory::compiler::emitter::{compile_with, emit_graph_text_with, try_compile_with};
+use zero_memory::compiler::{CompilerError, CompilerOptions, GraphMeta, SanitizePolicy};
+use zero_memory::types::*;
+
+/// Colons in a label, a predicate, and every context field.
+fn input() -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: vec![SemanticTuple::from((
+            "Book",
+            "schema:isbn",
+            "urn:isbn:0451450523",
+            0.9,
+        ))],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "agent://reader".to_string(),
+            scope: "project:alpha".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn options(sanitize: SanitizePolicy) -> CompilerOptions {
+    CompilerOptions {
+        sanitize,
+        ..CompilerOptions::default()
+    }
+}
+
+fn graph_text(sanitize: SanitizePolicy) -> String {
+    try_compile_with(&input(), &options(sanitize))
+        .unwrap()
+        .graph_text
+}
+
+#[test]
+fn strip_colons_removes_them_everywhere() {
+    let text = graph_text(SanitizePolicy::StripColons);
+    for value in [
+        r#""urnisbn0451450523""#,
+        r#""predicate":"schemaisbn""#,
+        r#""event_time":"2026-02-18T000000Z""#,
+        r#""source":"agent//reader""#,
+        r#""scope":"projectalpha""#,
+    ] {
+        assert!(text.contains(value), "missing {}", value);
+    }
+}
+
+#[test]
+fn escape_colons_is_the_default() {
+    let text = graph_text(SanitizePolicy::EscapeColons);
+    assert_eq!(text, graph_text(CompilerOptions::default().sanitize));
+    for value in [
+        r#""urn\u003aisbn\u003a0451450523""#,
+        r#""predicate":"schema\u003aisbn""#,
+        r#""event_time":"2026-02-18T00\u003a00\u003a00Z""#,
+        r#""source":"agent\u003a//reader""#,
+        r#""scope":"project\u003aalpha""#,
+    ] {
+        assert!(text.contains(value), "missing {}", value);
+    }
+}
+
+#[test]
+fn none_writes_values_verbatim() {
+    let text = graph_text(SanitizePolicy::None);
+    for value in [
+        r#""urn:isbn:0451450523""#,
+        r#""predicate":"schema:isbn""#,
+        r#""event_time":"2026-02-18T00:00:00Z""#,
+        r#""source":"agent://reader""#,
+        r#""scope":"project:alpha""#,
+    ] {
+        assert!(text.contains(value), "missing {}", value);
+    }
+}
+
+#[test]
+fn strict_rejects_values_the_parser_cannot_read() {
+    let err = try_compile_with(&input(), &options(SanitizePolicy::Strict)).unwrap_err();
+    match &err {
+        CompilerError::UnsafeGraphValue { value } => assert!(value.contains(':')),
+        other => panic!("expected UnsafeGraphValue, got {:?}", other),
+    }
+    assert!(err.to_string().contains("0-openclaw"));
+}
+
+#[test]
+fn strict_checks_context_fields() {
+    let mut input = input();
+    input.tuples[0].predicate = "isbn".to_string();
+    input.tuples[0].object = "0451450523".to_string();
+    input.context.event_time = "20260218T000000Z".parse().unwrap();
+    input.context.source = "reader".to_string();
+
+    let err = try_compile_with(&input, &options(SanitizePolicy::Strict)).unwrap_err();
+    assert_eq!(
+        err,
+        CompilerError::UnsafeGraphValue {
+            value: "project:alpha".to_string(),
+        }
+    );
+}
+
+#[test]
+fn strict_accepts_safe_values_verbatim() {
+    let mut input = input();
+    input.tuples[0].predicate = "isbn".to_string();
+    input.tuples[0].object = "0451450523".to_string();
+    input.context.event_time = "20260218T000000Z".parse().unwrap();
+    input.context.source = "reader".to_string();
+    input.context.scope = "project-alpha".to_string();
+
+    // Timestamps are the one thing strict rewrites, the same way
+    // StripColons does.
+    let strict = try_compile_with(&input, &options(SanitizePolicy::Strict)).unwrap();
+    let stripped = try_compile_with(&input, &options(SanitizePolicy::StripColons)).unwrap();
+    assert_eq!(strict.graph_text, stripped.graph_text);
+}
+
+#[test]
+fn lenient_compile_escapes_unsafe_values_under_strict() {
+    let output = compile_with(&input(), &options(SanitizePolicy::Strict));
+    assert_eq!(output.graph_text, graph_text(SanitizePolicy::EscapeColons));
+}
+
+#[test]
+fn emit_graph_text_with_applies_the_policy() {
+    let output = compile_with(&input(), &CompilerOptions::default());
+    let record = &output.record;
+    let context = &record.context.meta;
+    let meta = GraphMeta::default();
+
+    assert_eq!(
+        emit_graph_text_with(record, context, None, SanitizePolicy::EscapeColons, &meta).unwrap(),
+        output.graph_text
+    );
+    assert!(emit_graph_text_with(record, context, None, SanitizePolicy::Strict, &meta).is_err());
+    assert_eq!(
+        emit_graph_text_with(record, context, None, SanitizePolicy::None, &meta).unwrap(),
+        graph_text(SanitizePolicy::None)
+    );
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "schemars")]
+
+use jsonschema::JSONSchema;
+use schemars::schema::RootSchema;
+use serde_json::{json, Value};
+use zero_memory::compiler::emitter::compile;
+use zero_memory::schemas;
+use zero_memory::types::*;
+
+const RECORD_V2: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/record_v2.json"
+));
+
+fn validator(schema: RootSchema) -> JSONSchema {
+    JSONSchema::compile(&serde_json::to_value(schema).unwrap()).unwrap()
+}
+
+fn errors(schema: &JSONSchema, instance: &Value) -> Vec<String> {
+    match schema.validate(instance) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|e| format!("{}: {}", e.instance_path, e))
+            .collect(),
+    }
+}
+
+fn input() -> CompilerInput {
+    let mut met = SemanticTuple::from(("Alice", "met", "Bob", 0.9));
+    met.qualifiers
+        .insert("location".to_string(), "Paris".to_string());
+    met.evidence_span = Some(Span { start: 0, end: 13 });
+    let context = ContextMeta::builder()
+        .event_time("2026-02-18T09:30:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope("session-42")
+        .agent_id("agent-7")
+        .build()
+        .unwrap();
+    CompilerInput::builder(context)
+        .utterance("Alice met Bob in Paris.")
+        .tuple("Alice", "needs", "LTM", 0.8)
+        .tuples([met])
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn record_fixture_matches_the_record_schema() {
+    let schema = validator(schemas::memory_record_schema());
+    let fixture: Value = serde_json::from_str(RECORD_V2).unwrap();
+    assert_eq!(errors(&schema, &fixture), Vec::<String>::new());
+}
+
+#[test]
+fn serialized_types_match_their_schemas() {
+    let input = input();
+    let output = compile(&input);
+    for (schema, instance) in [
+        (
+            schemas::compiler_input_schema(),
+            serde_json::to_value(&input).unwrap(),
+        ),
+        (
+            schemas::semantic_tuple_schema(),
+            serde_json::to_value(&input.tuples[1]).unwrap(),
+        ),
+        (
+            schemas::compiler_output_schema(),
+            serde_json::to_value(&output).unwrap(),
+        ),
+        (
+            schemas::memory_record_schema(),
+            serde_json::to_value(&output.record).unwrap(),
+        ),
+    ] {
+        assert_eq!(errors(&validator(schema), &instance), Vec::<String>::new());
+    }
+}
+
+#[test]
+fn hashes_are_64_hex_digits() {
+    let schema = validator(schemas::memory_record_schema());
+    let mut record: Value = serde_json::from_str(RECORD_V2).unwrap();
+    assert!(schema.is_valid(&record));
+    record["context"]["hash"] = json!("abc123");
+    assert!(!schema.is_valid(&record));
+    record["context"]["hash"] = json!("z".repeat(64));
+    assert!(!schema.is_valid(&record));
+
+    let definitions = &schemas::memory_record_schema().definitions;
+    let hash = serde_json::to_value(&definitions["ContextHash"]).unwrap();
+    assert_eq!(hash["type"], "string");
+    assert_eq!(hash["minLength"], 64);
+    assert_eq!(hash["maxLength"], 64);
+}
+
+#[test]
+fn inputs_need_a_context_and_tuples() {
+    let schema = validator(schemas::compiler_input_schema());
+    let mut input = serde_json::to_value(input()).unwrap();
+    assert!(schema.is_valid(&input));
+
+    input["context"]["event_time"] = json!("yesterday");
+    assert!(!schema.is_valid(&input));
+    input.as_object_mut().unwrap().remove("context");
+    assert!(!schema.is_valid(&input));
+    assert!(!schema.is_valid(&json!({"tuples": [{"subject": "Alice"}]})));
+}
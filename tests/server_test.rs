@@ -0,0 +1,241 @@
+#![cfg(feature = "server")]
+
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use zero_memory::compiler::emitter::compile;
+use zero_memory::server::{router, router_with, ServerOptions};
+use zero_memory::store::SharedStore;
+use zero_memory::types::*;
+
+fn input() -> CompilerInput {
+    let context = ContextMeta::builder()
+        .event_time("2026-02-18T00:00:00Z".parse().unwrap())
+        .source("user_prompt")
+        .scope("conversation_42")
+        .build()
+        .unwrap();
+    CompilerInput::builder(context)
+        .tuple("Agent", "needs", "Memory", 0.9)
+        .tuple("Agent", "uses", "Tools", 0.7)
+        .build()
+        .unwrap()
+}
+
+async fn send(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    let response = router.clone().oneshot(request.unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, body)
+}
+
+async fn get(router: &Router, uri: &str) -> (StatusCode, Value) {
+    send(router, Method::GET, uri, None).await
+}
+
+#[tokio::test]
+async fn records_and_inputs_are_inserted() {
+    let store = SharedStore::default();
+    let router = router(store.clone());
+
+    let record = compile(&input()).record;
+    let (status, body) = send(
+        &router,
+        Method::POST,
+        "/records",
+        Some(serde_json::to_value(&record).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["new_concepts"], 3);
+    assert_eq!(body["new_facts"], 2);
+
+    // The same tuples as an input, observed later: new episodes only.
+    let mut input = input();
+    input.context.event_time = "2026-02-19T00:00:00Z".parse().unwrap();
+    let (status, body) = send(
+        &router,
+        Method::POST,
+        "/records",
+        Some(serde_json::to_value(&input).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["new_facts"], 0);
+    assert_eq!(body["new_episodes"], 2);
+
+    assert_eq!(store.read().await.relation_count(), 4);
+}
+
+#[tokio::test]
+async fn bad_bodies_are_rejected() {
+    let router = router(SharedStore::default());
+
+    let (status, body) = send(
+        &router,
+        Method::POST,
+        "/records",
+        Some(json!({ "nope": 1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].is_string(), "{}", body);
+
+    let mut input = serde_json::to_value(input()).unwrap();
+    input["tuples"][1]["confidence"] = json!(1.5);
+    let (status, body) = send(&router, Method::POST, "/records", Some(input)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "tuple 1: confidence 1.5 is not in [0, 1]");
+
+    let request = Request::post("/records")
+        .body(Body::from("{not json"))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn inconsistent_records_are_rejected() {
+    let store: SharedStore = SharedStore::default();
+    let router = router(store.clone());
+
+    let mut record = serde_json::to_value(compile(&input()).record).unwrap();
+    record["concepts"][0]["label"] = json!("impostor");
+    let (status, body) = send(&router, Method::POST, "/records", Some(record)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid record: "));
+    assert!(
+        body["issues"][0]["ConceptHashMismatch"].is_object(),
+        "{}",
+        body
+    );
+
+    let mut record = serde_json::to_value(compile(&input()).record).unwrap();
+    record["concepts"].as_array_mut().unwrap().remove(0);
+    let (status, body) = send(&router, Method::POST, "/records", Some(record)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["issues"][0]["MissingConcept"].is_object(), "{}", body);
+
+    assert_eq!(store.read().await.concept_count(), 0);
+}
+
+#[tokio::test]
+async fn concepts_and_their_relations() {
+    let store: SharedStore = SharedStore::default();
+    let record = compile(&input()).record;
+    store.write().await.insert_record(record);
+    let router = router(store);
+
+    let (status, agent) = get(&router, "/concepts?label=AGENT").await;
+    assert_eq!(status, StatusCode::OK, "{}", agent);
+    assert_eq!(agent["label"], "agent");
+    let hash = agent["hash"].as_str().unwrap().to_string();
+
+    let (status, body) = get(&router, &format!("/concepts/{}", hash)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, agent);
+
+    let (status, body) = get(&router, &format!("/concepts/{}/relations", hash)).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut predicates: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|relation| relation["predicate"].as_str().unwrap())
+        .collect();
+    predicates.sort_unstable();
+    assert_eq!(predicates, ["needs", "uses"]);
+
+    let (status, _) = get(&router, "/concepts?label=nobody").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get(&router, "/concepts").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let missing = "00".repeat(32);
+    for uri in [
+        format!("/concepts/{}", missing),
+        format!("/concepts/{}/relations", missing),
+    ] {
+        let (status, body) = get(&router, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+        assert_eq!(body["error"], format!("no concept {}", missing));
+    }
+    for uri in ["/concepts/abc", "/concepts/zz/relations"] {
+        let (status, body) = get(&router, uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert!(
+            body["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("bad concept hash"),
+            "{}",
+            body
+        );
+    }
+}
+
+#[tokio::test]
+async fn stats_count_the_store() {
+    let store: SharedStore = SharedStore::default();
+    store.write().await.insert_record(compile(&input()).record);
+    let router = router(store);
+
+    let (status, body) = get(&router, "/stats").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["concepts"], 3);
+    assert_eq!(body["facts"], 2);
+    assert_eq!(body["relations"], 2);
+    assert_eq!(body["contexts"], 1);
+    assert_eq!(body["ingest"]["records"], 1);
+    assert_eq!(body["ingest"]["by_scope"]["conversation_42"], 2);
+}
+
+#[tokio::test]
+async fn bearer_tokens_are_checked() {
+    let options = ServerOptions {
+        bearer_token: Some("s3cret".to_string()),
+    };
+    let router = router_with(SharedStore::default(), options);
+
+    let (status, body) = get(&router, "/stats").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body["error"].is_string());
+
+    for (token, expected) in [
+        ("Bearer wrong", StatusCode::UNAUTHORIZED),
+        ("Bearer s3cret", StatusCode::OK),
+    ] {
+        let request = Request::get("/stats")
+            .header(header::AUTHORIZATION, token)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), expected, "{}", token);
+    }
+}
+
+#[tokio::test]
+async fn serve_fails_on_an_address_in_use() {
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = taken.local_addr().unwrap();
+    let result = zero_memory::server::serve(SharedStore::default(), addr).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,69 @@
+use zero_memory::compiler::CompilerError;
+use zero_memory::error::MemoryError;
+use zero_memory::memory::Memory;
+use zero_memory::session::MemorySession;
+use zero_memory::types::*;
+
+fn tuple() -> SemanticTuple {
+    SemanticTuple::from(("Agent", "needs", "Memory", 0.9))
+}
+
+#[test]
+fn each_observation_is_a_new_context() {
+    let mut session = MemorySession::start("user_prompt", "conversation_42")
+        .agent_id("assistant")
+        .session_id("session-7");
+    let first = session.observe(vec![tuple()]);
+    let second = session.observe(vec![tuple()]);
+
+    assert_eq!(first.context.source, "user_prompt");
+    assert_eq!(first.context.scope, "conversation_42");
+    assert_eq!(first.context.agent_id.as_deref(), Some("assistant"));
+    assert_eq!(first.context.session_id.as_deref(), Some("session-7"));
+    // Even when the clock has not moved between the two.
+    assert!(second.context.event_time > first.context.event_time);
+    assert_eq!(session.context().event_time, second.context.event_time);
+
+    let first = session.observe_and_compile(vec![tuple()]).unwrap();
+    let second = session.observe_and_compile(vec![tuple()]).unwrap();
+    assert_ne!(first.record.context.hash, second.record.context.hash);
+    assert_eq!(
+        first.record.relations[0].fact_hash,
+        second.record.relations[0].fact_hash
+    );
+}
+
+#[test]
+fn the_same_tuple_observed_twice_is_two_episodes() {
+    let mut memory = Memory::in_memory();
+    let mut session = MemorySession::start("user_prompt", "s1").memory(&mut memory);
+
+    let first = session.observe_and_remember(vec![tuple()]).unwrap();
+    assert_eq!(first.insert.new_facts, 1);
+    let second = session.observe_and_remember(vec![tuple()]).unwrap();
+    assert_eq!(second.insert.new_facts, 0);
+    assert_eq!(second.insert.new_episodes, 1);
+    assert_ne!(first.record_hash, second.record_hash);
+
+    let hits = memory.recall("agent", 5);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].episodes, 2);
+}
+
+#[test]
+fn invalid_observations_are_rejected() {
+    let mut memory = Memory::in_memory();
+    let mut session = MemorySession::start("user_prompt", "s1").memory(&mut memory);
+    let mut empty = tuple();
+    empty.predicate.clear();
+
+    assert_eq!(
+        session
+            .observe_and_compile(vec![empty.clone()])
+            .unwrap_err(),
+        CompilerError::EmptyPredicate { tuple_index: 0 }
+    );
+    let err = session.observe_and_remember(vec![empty]).unwrap_err();
+    assert!(matches!(err, MemoryError::Compile(_)));
+    assert_eq!(memory.store().concept_count(), 0);
+}
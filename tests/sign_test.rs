@@ -0,0 +1,198 @@
+#![cfg(feature = "sign")]
+
+use zero_memory::compiler::emitter::{compile, emit_graph_text};
+use zero_memory::compiler::sign::SigningKey;
+use zero_memory::compiler::{
+    compile_signed, decompile, verify_graph_text, verify_record, verify_signature,
+    verify_trace_hash, CompilerOptions, ProofStatus, Signer, VerifyError,
+};
+use zero_memory::types::*;
+
+fn input() -> CompilerInput {
+    CompilerInput {
+        utterance: Some("the agent needs memory".to_string()),
+        tuples: vec![SemanticTuple::from(("Agent", "needs", "Memory", 0.98))],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+fn signer() -> Signer {
+    Signer::new(SigningKey::from_bytes(&[7; 32]))
+}
+
+#[test]
+fn signed_records_carry_their_proof() {
+    let signer = signer();
+    let output = compile_signed(&input(), &CompilerOptions::default(), &signer);
+    let proof = output.record.proof.as_ref().unwrap();
+
+    assert_eq!(proof.signer, signer.public_key());
+    assert_eq!(
+        proof.trace_hash,
+        hex::encode(zero_memory::compiler::hasher::trace_hash(&output.record))
+    );
+    assert!(verify_signature(&output.record));
+    assert!(verify_trace_hash(&output.record, &output.graph_text));
+    for value in [&proof.trace_hash, &proof.signer, &proof.signature] {
+        assert!(output.graph_text.contains(value.as_str()));
+    }
+    assert!(!output.graph_text.contains("pending"));
+}
+
+#[test]
+fn signatures_are_deterministic() {
+    let output = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    let again = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    assert_eq!(output.record.proof, again.record.proof);
+    assert_eq!(output.graph_text, again.graph_text);
+}
+
+#[test]
+fn signing_leaves_the_record_otherwise_unchanged() {
+    let signed = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    let mut unsigned = compile(&input());
+    assert!(unsigned.record.proof.is_none());
+    assert!(!verify_signature(&unsigned.record));
+
+    unsigned.record.proof = signed.record.proof.clone();
+    assert!(verify_signature(&unsigned.record));
+}
+
+#[test]
+fn changed_records_fail_verification() {
+    let output = compile_signed(&input(), &CompilerOptions::default(), &signer());
+
+    let mut changed = output.record.clone();
+    changed.relations[0].confidence = 0.5;
+    assert!(!verify_signature(&changed));
+
+    let mut forged = output.record.clone();
+    let other = Signer::new(SigningKey::from_bytes(&[8; 32]));
+    forged.proof.as_mut().unwrap().signer = other.public_key();
+    assert!(!verify_signature(&forged));
+}
+
+#[test]
+fn signed_graphs_decompile_with_their_proof() {
+    let output = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    let decompiled = decompile(&output.graph_text).unwrap();
+
+    assert_eq!(decompiled.proof, output.record.proof);
+    assert!(verify_signature(&decompiled));
+    assert_eq!(
+        emit_graph_text(&decompiled, &decompiled.context.meta),
+        emit_graph_text(&output.record, &output.record.context.meta)
+    );
+}
+
+#[test]
+fn records_keep_their_proof_through_json() {
+    let output = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    let json = serde_json::to_string(&output.record).unwrap();
+    let record: MemoryRecord = serde_json::from_str(&json).unwrap();
+    assert!(verify_signature(&record));
+
+    let unsigned = serde_json::to_string(&compile(&input()).record).unwrap();
+    assert!(!unsigned.contains("proof"));
+}
+
+#[test]
+fn verify_record_distinguishes_how_far_a_proof_goes() {
+    let signed = compile_signed(&input(), &CompilerOptions::default(), &signer()).record;
+    assert_eq!(verify_record(&signed).unwrap(), ProofStatus::FullyVerified);
+
+    let unsigned = compile(&input()).record;
+    assert_eq!(verify_record(&unsigned).unwrap(), ProofStatus::Unsigned);
+
+    let mut trace_only = signed.clone();
+    trace_only.proof.as_mut().unwrap().signature = "pending".to_string();
+    assert_eq!(verify_record(&trace_only).unwrap(), ProofStatus::TraceOnly);
+}
+
+#[test]
+fn verify_graph_text_reads_the_proof_node() {
+    let signed = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    assert_eq!(
+        verify_graph_text(&signed.graph_text).unwrap(),
+        ProofStatus::FullyVerified
+    );
+    assert_eq!(
+        verify_graph_text(&compile(&input()).graph_text).unwrap(),
+        ProofStatus::TraceOnly
+    );
+
+    let trace = signed.record.proof.as_ref().unwrap().trace_hash.clone();
+    let old_layout = compile(&input()).graph_text.replace(&trace, "pending");
+    assert_eq!(
+        verify_graph_text(&old_layout).unwrap(),
+        ProofStatus::Unsigned
+    );
+    assert!(matches!(
+        verify_graph_text("not a graph"),
+        Err(VerifyError::Graph(_))
+    ));
+}
+
+#[test]
+fn flipping_a_confidence_fails_verification() {
+    let signed = compile_signed(&input(), &CompilerOptions::default(), &signer());
+
+    let mut record = signed.record.clone();
+    record.relations[0].confidence = 0.97;
+    assert!(matches!(
+        verify_record(&record),
+        Err(VerifyError::TraceMismatch { .. })
+    ));
+
+    for graph_text in [&signed.graph_text, &compile(&input()).graph_text] {
+        let tampered = graph_text.replacen(r#""confidence":0.98"#, r#""confidence":0.97"#, 1);
+        assert_ne!(&tampered, graph_text);
+        assert!(matches!(
+            verify_graph_text(&tampered),
+            Err(VerifyError::TraceMismatch { .. })
+        ));
+    }
+}
+
+#[test]
+fn re_hashed_tampering_fails_the_signature() {
+    let signed = compile_signed(&input(), &CompilerOptions::default(), &signer());
+    let mut record = signed.record.clone();
+    record.relations[0].confidence = 0.97;
+    let trace = hex::encode(zero_memory::compiler::hasher::trace_hash(&record));
+    record.proof.as_mut().unwrap().trace_hash = trace;
+    assert!(matches!(
+        verify_record(&record),
+        Err(VerifyError::BadSignature)
+    ));
+
+    let mut garbled = signed.record.clone();
+    garbled.proof.as_mut().unwrap().signer = "zz".to_string();
+    assert!(matches!(
+        verify_record(&garbled),
+        Err(VerifyError::MalformedProof { field: "signer" })
+    ));
+}
+
+#[test]
+fn changing_the_namespace_fails_verification() {
+    let options = CompilerOptions {
+        namespace: Some("tenant-a".to_string()),
+        ..CompilerOptions::default()
+    };
+    let signed_in_a = compile_signed(&input(), &options, &signer());
+    assert!(verify_record(&signed_in_a.record).is_ok());
+    let mut moved = signed_in_a.record.clone();
+    moved.namespace = Some("tenant-b".to_string());
+    assert!(matches!(
+        verify_record(&moved),
+        Err(VerifyError::TraceMismatch { .. })
+    ));
+}
@@ -0,0 +1,314 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::hasher;
+use zero_memory::store::snapshot::{BINARY_FORMAT_VERSION, BINARY_MAGIC};
+use zero_memory::store::{IngestStats, MemoryStore, SnapshotError};
+use zero_memory::types::{CompilerInput, ContextMeta, SemanticTuple};
+
+fn make_input(scope_idx: usize, tuples_per_scope: usize) -> CompilerInput {
+    let tuples: Vec<SemanticTuple> = (0..tuples_per_scope)
+        .map(|t| SemanticTuple {
+            subject: format!("concept_{}", (scope_idx * 7 + t) % 300),
+            predicate: format!("pred_{}", t % 10),
+            object: format!("concept_{}", (scope_idx * 13 + t * 3) % 300),
+            confidence: 0.5 + (t % 50) as f64 / 100.0,
+            ..Default::default()
+        })
+        .collect();
+    let mut context = ContextMeta::builder()
+        .event_time(
+            format!("2026-02-18T00:{:02}:00Z", scope_idx % 60)
+                .parse()
+                .unwrap(),
+        )
+        .source("snapshot_test")
+        .scope(&format!("scope_{}", scope_idx))
+        .metadata_entry("batch", &scope_idx.to_string());
+    if scope_idx % 2 == 0 {
+        context = context.agent_id("agent_a");
+    }
+    CompilerInput::from((context.build().unwrap(), tuples))
+}
+
+/// Builds a store with 5,000 relation episodes across 50 contexts.
+fn build_large_store() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    for scope_idx in 0..50 {
+        store.insert_record(compile(&make_input(scope_idx, 100)).record);
+    }
+    store
+}
+
+fn assert_same_store(original: &MemoryStore, loaded: &MemoryStore) {
+    assert_eq!(loaded.concept_count(), original.concept_count());
+    assert_eq!(loaded.relation_count(), original.relation_count());
+    assert_eq!(loaded.label_index().len(), original.label_index().len());
+
+    for concept in original.concepts() {
+        let restored = loaded
+            .get_concept(&concept.hash)
+            .expect("every concept must survive the round-trip");
+        assert_eq!(restored.label, concept.label);
+        assert_eq!(restored.confidence, concept.confidence);
+        assert!(loaded.get_concept_by_label(&concept.label).is_some());
+        assert_eq!(
+            loaded.get_relations(&concept.hash).len(),
+            original.get_relations(&concept.hash).len(),
+            "adjacency must be rebuilt for {}",
+            concept.label
+        );
+    }
+
+    for relation in original.relations() {
+        let original_eps: Vec<_> = original
+            .get_relations_by_fact(&relation.fact_hash)
+            .iter()
+            .map(|r| r.episode_hash.clone())
+            .collect();
+        let loaded_eps: Vec<_> = loaded
+            .get_relations_by_fact(&relation.fact_hash)
+            .iter()
+            .map(|r| r.episode_hash.clone())
+            .collect();
+        assert_eq!(
+            loaded_eps, original_eps,
+            "per-fact episode order must match"
+        );
+    }
+
+    for context in original.contexts() {
+        let restored = loaded.get_context(&context.hash).unwrap();
+        assert_eq!(restored.meta.scope, context.meta.scope);
+        assert_eq!(restored.meta.agent_id, context.meta.agent_id);
+        assert_eq!(restored.meta.metadata, context.meta.metadata);
+    }
+}
+
+#[test]
+fn binary_round_trip_large_store() {
+    let store = build_large_store();
+    assert_eq!(store.relation_count(), 5000);
+
+    let mut bytes = Vec::new();
+    store.save_binary(&mut bytes).unwrap();
+    let loaded = MemoryStore::load_binary(bytes.as_slice()).unwrap();
+
+    assert_same_store(&store, &loaded);
+}
+
+#[test]
+fn json_round_trip_large_store() {
+    let store = build_large_store();
+
+    let mut bytes = Vec::new();
+    store.save_json(&mut bytes).unwrap();
+    let loaded = MemoryStore::load_json(bytes.as_slice()).unwrap();
+
+    assert_same_store(&store, &loaded);
+}
+
+#[test]
+fn binary_snapshot_is_smaller_than_json() {
+    let store = build_large_store();
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+
+    assert!(
+        binary.len() * 2 < json.len(),
+        "binary ({} bytes) should be well under half of JSON ({} bytes)",
+        binary.len(),
+        json.len()
+    );
+}
+
+#[test]
+fn binary_snapshot_starts_with_header() {
+    let mut bytes = Vec::new();
+    MemoryStore::new().save_binary(&mut bytes).unwrap();
+
+    assert_eq!(&bytes[..8], BINARY_MAGIC);
+    assert_eq!(&bytes[8..12], &BINARY_FORMAT_VERSION.to_le_bytes());
+}
+
+#[test]
+fn binary_snapshot_stores_raw_hash_bytes() {
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&make_input(0, 1)).record);
+
+    let mut bytes = Vec::new();
+    store.save_binary(&mut bytes).unwrap();
+
+    let concept_hash = hasher::concept_hash("concept-0");
+    let raw = *concept_hash.as_bytes();
+    assert!(
+        bytes.windows(32).any(|w| w == raw),
+        "concept hash must appear as raw 32 bytes"
+    );
+    let hex = concept_hash.to_string();
+    assert!(
+        !bytes.windows(64).any(|w| w == hex.as_bytes()),
+        "concept hash must not be stored as hex"
+    );
+}
+
+#[test]
+fn load_binary_rejects_unknown_version() {
+    let mut bytes = Vec::new();
+    build_large_store().save_binary(&mut bytes).unwrap();
+    bytes[8..12].copy_from_slice(&(BINARY_FORMAT_VERSION + 1).to_le_bytes());
+
+    match MemoryStore::load_binary(bytes.as_slice()) {
+        Err(SnapshotError::UnsupportedVersion { found, supported }) => {
+            assert_eq!(found, BINARY_FORMAT_VERSION + 1);
+            assert_eq!(supported, BINARY_FORMAT_VERSION);
+        }
+        other => panic!("expected UnsupportedVersion, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn load_binary_rejects_bad_magic() {
+    let result = MemoryStore::load_binary(&b"{\"concepts\": []}"[..]);
+    assert!(matches!(result, Err(SnapshotError::BadMagic)));
+
+    let result = MemoryStore::load_binary(&b"ZMEM"[..]);
+    assert!(
+        matches!(result, Err(SnapshotError::BadMagic)),
+        "truncated header must be reported as bad magic"
+    );
+}
+
+#[test]
+fn load_binary_rejects_truncated_body() {
+    let mut bytes = Vec::new();
+    build_large_store().save_binary(&mut bytes).unwrap();
+    bytes.truncate(bytes.len() / 2);
+
+    assert!(matches!(
+        MemoryStore::load_binary(bytes.as_slice()),
+        Err(SnapshotError::Binary(_))
+    ));
+}
+
+/// Rewrite a current snapshot as an older version by dropping the
+/// trailing sections that version lacks. Apart from the ingest counters
+/// and display labels, only valid for stores whose trailing sections are
+/// all empty.
+fn downgrade(bytes: &mut Vec<u8>, version: u32, store: &MemoryStore) {
+    // Bytes each version added at the end of the body. Empty sections are
+    // all zeros: an empty bincode Vec is a zero u64 length prefix, a `None`
+    // is one tag byte. The ingest counters are never empty once a record
+    // has been inserted, and neither are the display labels of compiled
+    // concepts such as `concept_1`.
+    let ingest = bincode::serialized_size(store.ingest_stats()).unwrap() as usize;
+    let display_labels = 8 + store
+        .concepts()
+        .filter_map(|c| Some((&c.hash, c.display_label.as_ref()?)))
+        .map(|entry| bincode::serialized_size(&entry).unwrap() as usize)
+        .sum::<usize>();
+    for v in (version + 1..=BINARY_FORMAT_VERSION).rev() {
+        let (len, empty) = match v {
+            2 => (8, true),       // pinned concept hashes
+            3 => (1 + 1, true),   // two decay watermarks
+            4 => (8, true),       // tombstones
+            5 => (ingest, false), // ingest counters
+            6 => (8, true),       // negative fact hashes
+            7 => (8, true),       // episode validity periods
+            8 => (8, true),       // episode utterance spans
+            9 => (display_labels, false),
+            10 => (8 + 8, true), // per-item decay times
+            _ => unreachable!(),
+        };
+        let section = bytes.len() - len;
+        assert!(!empty || bytes[section..].iter().all(|&b| b == 0));
+        bytes.truncate(section);
+    }
+    bytes[8..12].copy_from_slice(&version.to_le_bytes());
+}
+
+#[test]
+fn load_binary_reads_older_versions() {
+    let store = build_large_store();
+    for version in 1..BINARY_FORMAT_VERSION {
+        let mut bytes = Vec::new();
+        store.save_binary(&mut bytes).unwrap();
+        downgrade(&mut bytes, version, &store);
+
+        let loaded = MemoryStore::load_binary(bytes.as_slice()).unwrap();
+        assert_same_store(&store, &loaded);
+        if version < 5 {
+            assert_eq!(loaded.ingest_stats(), &IngestStats::default());
+        } else {
+            assert_eq!(loaded.ingest_stats(), store.ingest_stats());
+        }
+        if version < 9 {
+            assert!(loaded.concepts().all(|c| c.display_label.is_none()));
+        }
+    }
+}
+
+#[test]
+fn snapshots_keep_display_labels() {
+    let store = build_large_store();
+    let concept = store.get_concept_by_label("concept_1").unwrap();
+    assert_eq!(concept.display_label.as_deref(), Some("concept_1"));
+
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    for loaded in [
+        MemoryStore::load_binary(binary.as_slice()).unwrap(),
+        MemoryStore::load_json(json.as_slice()).unwrap(),
+    ] {
+        for concept in store.concepts() {
+            let restored = loaded.get_concept(&concept.hash).unwrap();
+            assert_eq!(restored.display_label, concept.display_label);
+        }
+    }
+}
+
+#[test]
+fn snapshots_keep_ingest_stats() {
+    let store = build_large_store();
+    assert_eq!(store.ingest_stats().records, 50);
+
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    let loaded = MemoryStore::load_binary(binary.as_slice()).unwrap();
+    assert_eq!(loaded.ingest_stats(), store.ingest_stats());
+
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+    let loaded = MemoryStore::load_json(json.as_slice()).unwrap();
+    assert_eq!(loaded.ingest_stats(), store.ingest_stats());
+}
+
+#[test]
+fn json_snapshots_list_everything_in_hash_order() {
+    let store = build_large_store();
+    let mut first = Vec::new();
+    store.save_json(&mut first).unwrap();
+
+    // A reloaded store hashes its maps differently, so only a sorted
+    // capture lists its contents the same way.
+    let loaded = MemoryStore::load_json(first.as_slice()).unwrap();
+    let mut second = Vec::new();
+    loaded.save_json(&mut second).unwrap();
+
+    let first: serde_json::Value = serde_json::from_slice(&first).unwrap();
+    let second: serde_json::Value = serde_json::from_slice(&second).unwrap();
+    assert_eq!(first, second);
+
+    let hashes: Vec<&str> = first["concepts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["hash"].as_str().unwrap())
+        .collect();
+    let mut sorted = hashes.clone();
+    sorted.sort_unstable();
+    assert_eq!(hashes, sorted);
+}
@@ -0,0 +1,126 @@
+use zero_memory::compiler::emitter::{compile, try_compile};
+use zero_memory::compiler::CompilerError;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+const UTTERANCE: &str = "I moved to Zürich last year";
+
+fn span(start: usize, end: usize) -> Option<Span> {
+    Some(Span { start, end })
+}
+
+fn input(utterance: Option<&str>, evidence_span: Option<Span>) -> CompilerInput {
+    CompilerInput {
+        utterance: utterance.map(str::to_string),
+        tuples: vec![SemanticTuple {
+            subject: "user".to_string(),
+            predicate: "lives_in".to_string(),
+            object: "Zürich".to_string(),
+            confidence: 0.9,
+            subject_span: span(0, 1),
+            object_span: span(11, 18),
+            evidence_span,
+            ..Default::default()
+        }],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+#[test]
+fn relations_carry_spans_into_the_utterance() {
+    let output = try_compile(&input(Some(UTTERANCE), span(0, 18))).unwrap();
+    let relation = &output.record.relations[0];
+
+    assert_eq!(relation.subject_span.unwrap().text(UTTERANCE), Some("I"));
+    assert_eq!(
+        relation.object_span.unwrap().text(UTTERANCE),
+        Some("Zürich")
+    );
+    assert_eq!(relation.evidence_text(UTTERANCE), Some("I moved to Zürich"));
+}
+
+#[test]
+fn relations_without_evidence_have_no_evidence_text() {
+    let output = try_compile(&input(Some(UTTERANCE), None)).unwrap();
+    assert_eq!(output.record.relations[0].evidence_text(UTTERANCE), None);
+}
+
+#[test]
+fn try_compile_rejects_spans_that_do_not_fit() {
+    let cases = [
+        (Some(UTTERANCE), span(0, 99)),  // past the end
+        (Some(UTTERANCE), span(0, 13)),  // inside "ü"
+        (Some(UTTERANCE), span(18, 11)), // reversed
+        (None, span(0, 1)),              // no utterance
+    ];
+    for (utterance, evidence_span) in cases {
+        let input = input(utterance, evidence_span);
+        let err = try_compile(&input).unwrap_err();
+        match &err {
+            CompilerError::InvalidSpan { tuple_index, .. } => assert_eq!(*tuple_index, 0),
+            other => panic!("expected InvalidSpan, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn try_compile_reports_the_offending_field() {
+    let err = try_compile(&input(Some(UTTERANCE), span(0, 13))).unwrap_err();
+    assert_eq!(
+        err,
+        CompilerError::InvalidSpan {
+            tuple_index: 0,
+            field: "evidence",
+            span: Span { start: 0, end: 13 },
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "tuple 0: evidence span 0..13 does not fit the utterance"
+    );
+}
+
+#[test]
+fn lenient_compile_keeps_bad_spans_without_panicking() {
+    let output = compile(&input(Some(UTTERANCE), span(0, 13)));
+    let relation = &output.record.relations[0];
+    assert_eq!(relation.evidence_span, span(0, 13));
+    assert_eq!(relation.evidence_text(UTTERANCE), None);
+}
+
+#[test]
+fn snapshots_keep_spans() {
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&input(Some(UTTERANCE), span(0, 18))).record);
+
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+
+    for loaded in [
+        MemoryStore::load_binary(binary.as_slice()).unwrap(),
+        MemoryStore::load_json(json.as_slice()).unwrap(),
+    ] {
+        let relation = loaded.relations().next().unwrap();
+        assert_eq!(relation.subject_span, span(0, 1));
+        assert_eq!(relation.object_span, span(11, 18));
+        assert_eq!(relation.evidence_text(UTTERANCE), Some("I moved to Zürich"));
+    }
+}
+
+#[test]
+fn tuples_without_spans_parse() {
+    let tuple: SemanticTuple = serde_json::from_str(
+        r#"{ "subject": "user", "predicate": "lives_in", "object": "zürich", "confidence": 0.9 }"#,
+    )
+    .unwrap();
+    assert_eq!(tuple.evidence_span, None);
+}
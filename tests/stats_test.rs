@@ -0,0 +1,147 @@
+use zero_memory::compiler::hasher;
+use zero_memory::store::{MemoryStore, StoreLimits};
+use zero_memory::types::*;
+
+fn context(source: &str, scope: &str, agent: Option<&str>) -> ContextNode {
+    let meta = ContextMeta {
+        event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+        source: source.to_string(),
+        scope: scope.to_string(),
+        agent_id: agent.map(str::to_string),
+        session_id: None,
+        metadata: None,
+    };
+    ContextNode {
+        hash: hasher::context_hash(&meta),
+        meta,
+    }
+}
+
+fn concept(label: &str) -> ConceptNode {
+    ConceptNode {
+        hash: hasher::concept_hash(label),
+        label: label.to_string(),
+        aliases: vec![],
+        confidence: 0.9,
+        created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        updated_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+        display_label: None,
+    }
+}
+
+/// One record observing `agent -needs-> each object` in `ctx`.
+fn observation(ctx: ContextNode, objects: &[&str]) -> MemoryRecord {
+    let mut concepts = vec![concept("agent")];
+    let mut relations = Vec::new();
+    for object in objects {
+        let fact = hasher::fact_hash("agent", "needs", object);
+        concepts.push(concept(object));
+        relations.push(RelationNode {
+            episode_hash: hasher::episode_hash(&fact, &ctx.hash),
+            fact_hash: fact,
+            subject_hash: hasher::concept_hash("agent"),
+            predicate: "needs".into(),
+            object_hash: hasher::concept_hash(object),
+            confidence: 0.9,
+            context_hash: ctx.hash.clone(),
+            created_at: "2026-02-18T00:00:00Z".parse().unwrap(),
+            polarity: Polarity::Positive,
+            valid_from: None,
+            valid_to: None,
+            subject_span: None,
+            object_span: None,
+            evidence_span: None,
+        });
+    }
+    MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
+        concepts,
+        relations,
+        context: ctx,
+        proof: None,
+        prev: None,
+        namespace: None,
+    }
+}
+
+#[test]
+fn counts_episodes_per_source_scope_and_agent() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation(
+        context("user_prompt", "s1", Some("a1")),
+        &["memory", "tools"],
+    ));
+    store.insert_record(observation(context("observation", "s1", None), &["memory"]));
+    store.insert_record(observation(
+        context("user_prompt", "s2", Some("a2")),
+        &["time"],
+    ));
+
+    let stats = store.ingest_stats();
+    assert_eq!(stats.records, 3);
+    assert_eq!(stats.episodes, 4);
+    assert_eq!(stats.by_source["user_prompt"], 3);
+    assert_eq!(stats.by_source["observation"], 1);
+    assert_eq!(stats.by_scope["s1"], 3);
+    assert_eq!(stats.by_scope["s2"], 1);
+    assert_eq!(stats.by_agent["a1"], 2);
+    assert_eq!(stats.by_agent["a2"], 1);
+    assert_eq!(stats.by_agent.values().sum::<u64>(), 3);
+}
+
+#[test]
+fn duplicate_episodes_are_not_counted() {
+    let mut store = MemoryStore::new();
+    let record = observation(context("user_prompt", "s1", None), &["memory"]);
+    store.insert_record(record.clone());
+    store.insert_record(record);
+
+    let stats = store.ingest_stats();
+    assert_eq!(stats.records, 2);
+    assert_eq!(stats.episodes, 1);
+    assert_eq!(stats.by_scope["s1"], 1);
+}
+
+#[test]
+fn bulk_insert_counts_like_single_inserts() {
+    let records = vec![
+        observation(context("user_prompt", "s1", Some("a1")), &["memory"]),
+        observation(context("observation", "s2", None), &["memory", "tools"]),
+    ];
+
+    let mut one_by_one = MemoryStore::new();
+    for record in records.clone() {
+        one_by_one.insert_record(record);
+    }
+    let mut bulk = MemoryStore::new();
+    bulk.insert_records(records);
+
+    assert_eq!(bulk.ingest_stats(), one_by_one.ingest_stats());
+}
+
+#[test]
+fn eviction_does_not_lower_counts() {
+    let mut store = MemoryStore::with_limits(StoreLimits {
+        max_concepts: None,
+        max_episodes: Some(1),
+    });
+    store.insert_record(observation(
+        context("user_prompt", "s1", None),
+        &["memory", "tools"],
+    ));
+
+    assert_eq!(store.relation_count(), 1);
+    assert_eq!(store.ingest_stats().episodes, 2);
+    assert_eq!(store.ingest_stats().by_source["user_prompt"], 2);
+}
+
+#[test]
+fn ingest_stats_serialize_as_json_maps() {
+    let mut store = MemoryStore::new();
+    store.insert_record(observation(context("user_prompt", "s1", None), &["memory"]));
+
+    let json = serde_json::to_value(store.ingest_stats()).unwrap();
+    assert_eq!(json["episodes"], 1);
+    assert_eq!(json["by_source"]["user_prompt"], 1);
+    assert!(json["by_agent"].as_object().unwrap().is_empty());
+}
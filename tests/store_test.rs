@@ -1,10 +1,18 @@
 use zero_memory::compiler::hasher;
-use zero_memory::store::MemoryStore;
+#[cfg(feature = "sqlite")]
+use zero_memory::store::SqliteStore;
+use zero_memory::store::{MemoryBackend, MemoryStore};
 use zero_memory::types::*;
 
 fn make_context(scope: &str) -> (ContextHash, ContextNode) {
+    make_context_at(scope, "2026-02-18T00:00:00Z")
+}
+
+/// A context at `event_time`, read like legacy data: unreadable times land
+/// at the epoch.
+fn make_context_at(scope: &str, event_time: &str) -> (ContextHash, ContextNode) {
     let meta = ContextMeta {
-        event_time: "2026-02-18T00:00:00Z".to_string(),
+        event_time: EventTime::from_legacy(event_time),
         source: "test".to_string(),
         scope: scope.to_string(),
         agent_id: None,
@@ -24,8 +32,17 @@ fn make_record(
     relations: Vec<(&str, &str, &str, f64)>,
     scope: &str,
 ) -> MemoryRecord {
-    let (ctx_hash, ctx_node) = make_context(scope);
-    let now = "2026-02-18T00:00:00Z".to_string();
+    make_record_at(concepts, relations, scope, "2026-02-18T00:00:00Z")
+}
+
+fn make_record_at(
+    concepts: Vec<(&str, f64)>,
+    relations: Vec<(&str, &str, &str, f64)>,
+    scope: &str,
+    event_time: &str,
+) -> MemoryRecord {
+    let (ctx_hash, ctx_node) = make_context_at(scope, event_time);
+    let now: EventTime = "2026-02-18T00:00:00Z".parse().unwrap();
 
     let concept_nodes: Vec<ConceptNode> = concepts
         .iter()
@@ -34,8 +51,9 @@ fn make_record(
             label: label.to_string(),
             aliases: vec![],
             confidence: *conf,
-            created_at: now.clone(),
-            updated_at: now.clone(),
+            created_at: now,
+            updated_at: now,
+            display_label: None,
         })
         .collect();
 
@@ -48,30 +66,38 @@ fn make_record(
                 fact_hash: fh,
                 episode_hash: eh,
                 subject_hash: hasher::concept_hash(s),
-                predicate: p.to_string(),
+                predicate: (*p).into(),
                 object_hash: hasher::concept_hash(o),
                 confidence: *conf,
                 context_hash: ctx_hash.clone(),
-                created_at: now.clone(),
+                created_at: now,
+                polarity: Polarity::Positive,
+                valid_from: None,
+                valid_to: None,
+                subject_span: None,
+                object_span: None,
+                evidence_span: None,
             }
         })
         .collect();
 
     MemoryRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
         concepts: concept_nodes,
         relations: relation_nodes,
         context: ctx_node,
+        proof: None,
+        prev: None,
+        namespace: None,
     }
 }
 
-#[test]
-fn insert_and_retrieve_concept() {
-    let mut store = MemoryStore::new();
+fn insert_and_retrieve_concept<B: MemoryBackend>(mut store: B) {
     let record = make_record(vec![("agent", 0.9)], vec![], "test_scope");
-    store.insert_record(record);
+    store.insert_record(record).unwrap();
 
     let hash = hasher::concept_hash("agent");
-    let concept = store.get_concept(&hash);
+    let concept = store.get_concept(&hash).unwrap();
     assert!(
         concept.is_some(),
         "Must be able to retrieve inserted concept"
@@ -79,52 +105,52 @@ fn insert_and_retrieve_concept() {
     assert_eq!(concept.unwrap().label, "agent");
 }
 
-#[test]
-fn insert_same_concept_twice_deduplicates() {
-    let mut store = MemoryStore::new();
-
+fn insert_same_concept_twice_deduplicates<B: MemoryBackend>(mut store: B) {
     let record1 = make_record(vec![("agent", 0.9)], vec![], "scope_1");
-    let result1 = store.insert_record(record1);
+    let result1 = store.insert_record(record1).unwrap();
     assert_eq!(result1.new_concepts, 1);
 
     let record2 = make_record(vec![("agent", 0.95)], vec![], "scope_2");
-    let result2 = store.insert_record(record2);
-    assert_eq!(result2.dupes_skipped, 1);
+    let result2 = store.insert_record(record2).unwrap();
+    assert_eq!(result2.concepts_updated, 1, "the confidence went up");
+    assert_eq!(result2.dupes_skipped, 0);
     assert_eq!(result2.new_concepts, 0);
 
+    let record3 = make_record(vec![("agent", 0.5)], vec![], "scope_3");
+    let result3 = store.insert_record(record3).unwrap();
+    assert_eq!(result3.concepts_updated, 0, "nothing about agent changed");
+    assert_eq!(result3.dupes_skipped, 1);
+
     assert_eq!(
-        store.concept_count(),
+        store.concept_count().unwrap(),
         1,
         "Duplicate concept must not increase count"
     );
 
-    let concept = store.get_concept_by_label("agent").unwrap();
+    let concept = store.get_concept_by_label("agent").unwrap().unwrap();
     assert_eq!(
         concept.confidence, 0.95,
         "Re-inserted concept with higher confidence should update to max"
     );
 }
 
-#[test]
-fn same_fact_different_context_produces_two_episodes() {
-    let mut store = MemoryStore::new();
-
+fn same_fact_different_context_produces_two_episodes<B: MemoryBackend>(mut store: B) {
     let record1 = make_record(
         vec![("agent", 0.9), ("memory", 0.9)],
         vec![("agent", "needs", "memory", 0.98)],
         "session_1",
     );
-    store.insert_record(record1);
+    store.insert_record(record1).unwrap();
 
     let record2 = make_record(
         vec![("agent", 0.9), ("memory", 0.9)],
         vec![("agent", "needs", "memory", 0.95)],
         "session_2",
     );
-    store.insert_record(record2);
+    store.insert_record(record2).unwrap();
 
     let fh = hasher::fact_hash("agent", "needs", "memory");
-    let episodes = store.get_relations_by_fact(&fh);
+    let episodes = store.get_relations_by_fact(&fh).unwrap();
     assert_eq!(
         episodes.len(),
         2,
@@ -132,9 +158,7 @@ fn same_fact_different_context_produces_two_episodes() {
     );
 }
 
-#[test]
-fn get_relations_returns_correct_neighbors() {
-    let mut store = MemoryStore::new();
+fn get_relations_returns_correct_neighbors<B: MemoryBackend>(mut store: B) {
     let record = make_record(
         vec![("agent", 0.9), ("memory", 0.9), ("0-lang", 0.9)],
         vec![
@@ -143,102 +167,216 @@ fn get_relations_returns_correct_neighbors() {
         ],
         "test_scope",
     );
-    store.insert_record(record);
+    store.insert_record(record).unwrap();
 
     let agent_hash = hasher::concept_hash("agent");
-    let rels = store.get_relations(&agent_hash);
+    let rels = store.get_relations(&agent_hash).unwrap();
     assert_eq!(rels.len(), 2, "Agent should be connected to 2 relations");
 
     let memory_hash = hasher::concept_hash("memory");
-    let rels = store.get_relations(&memory_hash);
+    let rels = store.get_relations(&memory_hash).unwrap();
     assert_eq!(rels.len(), 1, "Memory should be connected to 1 relation");
 }
 
-#[test]
-fn get_concept_by_label_works() {
-    let mut store = MemoryStore::new();
+fn get_concept_by_label_works<B: MemoryBackend>(mut store: B) {
     let record = make_record(vec![("agent", 0.9)], vec![], "test_scope");
-    store.insert_record(record);
+    store.insert_record(record).unwrap();
 
-    let concept = store.get_concept_by_label("agent");
+    let concept = store.get_concept_by_label("agent").unwrap();
     assert!(concept.is_some());
     assert_eq!(concept.unwrap().confidence, 0.9);
 }
 
-#[test]
-fn get_context_works() {
-    let mut store = MemoryStore::new();
+fn get_context_works<B: MemoryBackend>(mut store: B) {
     let (ctx_hash, _) = make_context("test_scope");
     let record = make_record(vec![("agent", 0.9)], vec![], "test_scope");
-    store.insert_record(record);
+    store.insert_record(record).unwrap();
 
-    let ctx = store.get_context(&ctx_hash);
+    let ctx = store.get_context(&ctx_hash).unwrap();
     assert!(ctx.is_some());
     assert_eq!(ctx.unwrap().meta.scope, "test_scope");
 }
 
-#[test]
-fn empty_store_counts_are_zero() {
-    let store = MemoryStore::new();
-    assert_eq!(store.concept_count(), 0);
-    assert_eq!(store.relation_count(), 0);
+fn empty_store_counts_are_zero<B: MemoryBackend>(store: B) {
+    assert_eq!(store.concept_count().unwrap(), 0);
+    assert_eq!(store.relation_count().unwrap(), 0);
 }
 
-#[test]
-fn concept_reinsert_merges_confidence_and_aliases() {
-    let mut store = MemoryStore::new();
-
+fn concept_reinsert_merges_confidence_and_aliases<B: MemoryBackend>(mut store: B) {
     let record1 = make_record(vec![("agent", 0.8)], vec![], "scope_1");
-    store.insert_record(record1);
+    store.insert_record(record1).unwrap();
 
     let mut record2 = make_record(vec![("agent", 0.95)], vec![], "scope_2");
     record2.concepts[0].aliases = vec!["bot".to_string()];
-    record2.concepts[0].updated_at = "2026-02-19T00:00:00Z".to_string();
-    store.insert_record(record2);
+    record2.concepts[0].updated_at = "2026-02-19T00:00:00Z".parse().unwrap();
+    store.insert_record(record2).unwrap();
 
-    let concept = store.get_concept_by_label("agent").unwrap();
+    let concept = store.get_concept_by_label("agent").unwrap().unwrap();
     assert_eq!(concept.confidence, 0.95, "Confidence should take the max");
     assert_eq!(
-        concept.updated_at, "2026-02-19T00:00:00Z",
+        concept.updated_at.to_string(),
+        "2026-02-19T00:00:00Z",
         "updated_at should be refreshed"
     );
     assert!(
         concept.aliases.contains(&"bot".to_string()),
         "New alias should be merged"
     );
-    assert_eq!(store.concept_count(), 1, "Still only one concept");
+    assert_eq!(store.concept_count().unwrap(), 1, "Still only one concept");
 }
 
-#[test]
-fn label_index_normalizes_on_lookup() {
-    let mut store = MemoryStore::new();
+fn concept_reinsert_counts_only_real_updates<B: MemoryBackend>(mut store: B) {
+    let agent = |change: fn(&mut ConceptNode)| {
+        let mut record = make_record(vec![("agent", 0.8)], vec![], "scope_1");
+        change(&mut record.concepts[0]);
+        record
+    };
+    store.insert_record(agent(|_| {})).unwrap();
+    let mut updated = |record| {
+        let result = store.insert_record(record).unwrap();
+        assert_eq!(result.concepts_updated + result.dupes_skipped, 1);
+        result.concepts_updated == 1
+    };
+
+    assert!(
+        !updated(agent(|_| {})),
+        "an identical concept is a duplicate"
+    );
+    assert!(!updated(agent(|c| c.confidence = 0.1)));
+    assert!(updated(agent(|c| c.confidence = 0.9)));
+    assert!(updated(agent(|c| c.aliases = vec!["bot".to_string()])));
+    assert!(!updated(agent(|c| c.aliases = vec!["bot".to_string()])));
+    assert!(updated(agent(
+        |c| c.display_label = Some("Agent".to_string())
+    )));
+    assert!(!updated(agent(
+        |c| c.display_label = Some("Agent".to_string())
+    )));
+    assert!(!updated(agent(|c| c.display_label = None)));
+    assert!(updated(agent(|c| {
+        c.updated_at = "2026-02-19T00:00:00Z".parse().unwrap()
+    })));
+}
+
+fn concept_reinsert_keeps_the_latest_display_label<B: MemoryBackend>(mut store: B) {
+    let display = |label: Option<&str>, scope| {
+        let mut record = make_record(vec![("openai", 0.9)], vec![], scope);
+        record.concepts[0].display_label = label.map(str::to_string);
+        record
+    };
+    store.insert_record(display(None, "scope_1")).unwrap();
+    store
+        .insert_record(display(Some("OpenAi"), "scope_2"))
+        .unwrap();
+    store
+        .insert_record(display(Some("OpenAI"), "scope_3"))
+        .unwrap();
+    store.insert_record(display(None, "scope_4")).unwrap();
+
+    for label in ["openai", "OpenAI", "OPENAI"] {
+        let concept = store.get_concept_by_label(label).unwrap().unwrap();
+        assert_eq!(concept.label, "openai");
+        assert_eq!(concept.display_label.as_deref(), Some("OpenAI"));
+    }
+}
+
+fn concepts_with_blank_labels_are_refused<B: MemoryBackend>(mut store: B) {
+    let record = make_record(
+        vec![("agent", 0.9), ("  ", 0.9), ("tools", 0.8)],
+        vec![
+            ("agent", "uses", "  ", 0.9),
+            ("agent", "uses", "tools", 0.8),
+        ],
+        "scope_1",
+    );
+    let result = store.insert_record(record).unwrap();
+
+    assert_eq!(result.refused_concepts, 1);
+    assert_eq!(result.refused_relations, 1);
+    assert_eq!(result.new_concepts, 2);
+    assert_eq!(result.new_episodes, 1);
+    assert_eq!(store.concept_count().unwrap(), 2);
+    assert_eq!(store.relation_count().unwrap(), 1);
+    assert!(store
+        .get_concept(&hasher::concept_hash("  "))
+        .unwrap()
+        .is_none());
+}
+
+fn label_index_normalizes_on_lookup<B: MemoryBackend>(mut store: B) {
     let record = make_record(vec![("agent", 0.9)], vec![], "test_scope");
-    store.insert_record(record);
+    store.insert_record(record).unwrap();
 
     assert!(
-        store.get_concept_by_label("Agent").is_some(),
+        store.get_concept_by_label("Agent").unwrap().is_some(),
         "Lookup with uppercase should find normalized lowercase entry"
     );
     assert!(
-        store.get_concept_by_label("  agent  ").is_some(),
+        store.get_concept_by_label("  agent  ").unwrap().is_some(),
         "Lookup with whitespace should find trimmed entry"
     );
     assert!(
-        store.get_concept_by_label("AGENT").is_some(),
+        store.get_concept_by_label("AGENT").unwrap().is_some(),
         "Lookup with all-caps should find entry"
     );
 }
 
-#[test]
-fn insert_result_tracks_new_facts_vs_episodes() {
-    let mut store = MemoryStore::new();
+fn get_concept_by_label_resolves_aliases<B: MemoryBackend>(mut store: B) {
+    let mut record = make_record(vec![("agent", 0.9), ("memory", 0.9)], vec![], "aliases");
+    record.concepts[0].aliases = vec!["Bot".to_string(), "memory".to_string()];
+    let result = store.insert_record(record).unwrap();
+
+    let agent = hasher::concept_hash("agent");
+    let memory = hasher::concept_hash("memory");
+    assert_eq!(
+        store.get_concept_by_label("bot").unwrap().unwrap().hash,
+        agent
+    );
+    assert_eq!(
+        store.get_concept_by_label("memory").unwrap().unwrap().hash,
+        memory,
+        "a primary label beats another concept's alias"
+    );
+    assert_eq!(
+        result.alias_conflicts,
+        vec![AliasConflict {
+            alias: "memory".to_string(),
+            kept: memory.clone(),
+            rejected: agent.clone(),
+        }]
+    );
+    assert!(result.merged_aliases.is_empty());
 
+    // Aliases learned on merge are indexed too, and reported.
+    let mut record = make_record(vec![("agent", 0.9)], vec![], "aliases_later");
+    record.concepts[0].aliases = vec!["assistant".to_string()];
+    let result = store.insert_record(record).unwrap();
+    assert!(result.alias_conflicts.is_empty());
+    assert_eq!(
+        result.merged_aliases,
+        vec![MergedAlias {
+            alias: "assistant".to_string(),
+            label: "agent".to_string(),
+            hash: agent.clone(),
+        }]
+    );
+    assert_eq!(
+        store
+            .get_concept_by_label("Assistant")
+            .unwrap()
+            .unwrap()
+            .hash,
+        agent
+    );
+}
+
+fn insert_result_tracks_new_facts_vs_episodes<B: MemoryBackend>(mut store: B) {
     let record1 = make_record(
         vec![("agent", 0.9), ("memory", 0.9)],
         vec![("agent", "needs", "memory", 0.98)],
         "session_1",
     );
-    let r1 = store.insert_record(record1);
+    let r1 = store.insert_record(record1).unwrap();
     assert_eq!(r1.new_facts, 1);
     assert_eq!(r1.new_episodes, 1);
 
@@ -247,10 +385,387 @@ fn insert_result_tracks_new_facts_vs_episodes() {
         vec![("agent", "needs", "memory", 0.95)],
         "session_2",
     );
-    let r2 = store.insert_record(record2);
+    let r2 = store.insert_record(record2).unwrap();
     assert_eq!(r2.new_facts, 0, "Same fact should not count as new");
     assert_eq!(
         r2.new_episodes, 1,
         "Different context should produce new episode"
     );
 }
+
+fn episodes_of_a_fact_are_chronological<B: MemoryBackend>(mut store: B) {
+    // Inserted out of order; one time carries an offset, one cannot be parsed.
+    for (scope, event_time) in [
+        ("march", "2026-03-01T00:00:00Z"),
+        ("january", "2026-01-01T00:00:00Z"),
+        ("february", "2026-02-01T09:00:00+09:00"),
+        ("unknown", "last tuesday"),
+        ("february_again", "2026-02-01T00:00:00Z"),
+    ] {
+        let record = make_record_at(
+            vec![("agent", 0.9), ("memory", 0.9)],
+            vec![("agent", "needs", "memory", 0.9)],
+            scope,
+            event_time,
+        );
+        store.insert_record(record).unwrap();
+    }
+
+    let fh = hasher::fact_hash("agent", "needs", "memory");
+    let scopes: Vec<String> = store
+        .get_relations_by_fact(&fh)
+        .unwrap()
+        .iter()
+        .map(|r| {
+            store
+                .get_context(&r.context_hash)
+                .unwrap()
+                .unwrap()
+                .meta
+                .scope
+        })
+        .collect();
+    assert_eq!(
+        scopes,
+        ["unknown", "january", "february", "february_again", "march"],
+        "legacy (epoch) first, then by instant; equal instants keep insertion order"
+    );
+
+    let latest = store.latest_relation_by_fact(&fh).unwrap().unwrap();
+    let (march, _) = make_context_at("march", "2026-03-01T00:00:00Z");
+    assert_eq!(latest.context_hash, march);
+    let unknown = hasher::fact_hash("agent", "forgets", "memory");
+    assert!(store.latest_relation_by_fact(&unknown).unwrap().is_none());
+}
+
+fn negative_facts_are_distinct_and_keep_polarity<B: MemoryBackend>(mut store: B) {
+    let mut record = make_record(
+        vec![("user", 0.9), ("mushrooms", 0.9)],
+        vec![("user", "likes", "mushrooms", 0.9)],
+        "polarity",
+    );
+    let (ctx_hash, _) = make_context("polarity");
+    let negative_fact =
+        hasher::fact_hash_with_polarity("user", "likes", "mushrooms", Polarity::Negative);
+    let mut negative = record.relations[0].clone();
+    negative.episode_hash = hasher::episode_hash(&negative_fact, &ctx_hash);
+    negative.fact_hash = negative_fact.clone();
+    negative.polarity = Polarity::Negative;
+    record.relations.push(negative);
+
+    let result = store.insert_record(record).unwrap();
+    assert_eq!(result.new_facts, 2);
+
+    let positive_fact = hasher::fact_hash("user", "likes", "mushrooms");
+    let positive = store.get_relations_by_fact(&positive_fact).unwrap();
+    assert_eq!(positive.len(), 1);
+    assert_eq!(positive[0].polarity, Polarity::Positive);
+    let negative = store.get_relations_by_fact(&negative_fact).unwrap();
+    assert_eq!(negative.len(), 1);
+    assert_eq!(negative[0].polarity, Polarity::Negative);
+}
+
+fn episodes_keep_validity<B: MemoryBackend>(mut store: B) {
+    let mut record = make_record(
+        vec![("user", 0.9), ("berlin", 0.9)],
+        vec![("user", "lives_in", "berlin", 0.9)],
+        "validity",
+    );
+    record.relations[0].valid_from = Some("2019-01-01T00:00:00Z".to_string());
+    store.insert_record(record).unwrap();
+
+    let fact = hasher::fact_hash("user", "lives_in", "berlin");
+    let relations = store.get_relations_by_fact(&fact).unwrap();
+    assert_eq!(
+        relations[0].valid_from.as_deref(),
+        Some("2019-01-01T00:00:00Z")
+    );
+    assert_eq!(relations[0].valid_to, None);
+}
+
+fn episodes_keep_spans<B: MemoryBackend>(mut store: B) {
+    let mut record = make_record(
+        vec![("user", 0.9), ("berlin", 0.9)],
+        vec![("user", "lives_in", "berlin", 0.9)],
+        "spans",
+    );
+    record.relations[0].evidence_span = Some(Span { start: 0, end: 20 });
+    record.relations[0].object_span = Some(Span { start: 14, end: 20 });
+    store.insert_record(record).unwrap();
+
+    let fact = hasher::fact_hash("user", "lives_in", "berlin");
+    let relations = store.get_relations_by_fact(&fact).unwrap();
+    assert_eq!(relations[0].subject_span, None);
+    assert_eq!(relations[0].object_span, Some(Span { start: 14, end: 20 }));
+    assert_eq!(relations[0].evidence_span, Some(Span { start: 0, end: 20 }));
+}
+
+/// Runs the shared store suite against one backend, so every backend is
+/// held to exactly the same dedup/merge semantics.
+macro_rules! backend_suite {
+    ($name:ident, $new:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn insert_and_retrieve_concept() {
+                super::insert_and_retrieve_concept($new);
+            }
+
+            #[test]
+            fn insert_same_concept_twice_deduplicates() {
+                super::insert_same_concept_twice_deduplicates($new);
+            }
+
+            #[test]
+            fn same_fact_different_context_produces_two_episodes() {
+                super::same_fact_different_context_produces_two_episodes($new);
+            }
+
+            #[test]
+            fn get_relations_returns_correct_neighbors() {
+                super::get_relations_returns_correct_neighbors($new);
+            }
+
+            #[test]
+            fn get_concept_by_label_works() {
+                super::get_concept_by_label_works($new);
+            }
+
+            #[test]
+            fn get_context_works() {
+                super::get_context_works($new);
+            }
+
+            #[test]
+            fn empty_store_counts_are_zero() {
+                super::empty_store_counts_are_zero($new);
+            }
+
+            #[test]
+            fn concept_reinsert_merges_confidence_and_aliases() {
+                super::concept_reinsert_merges_confidence_and_aliases($new);
+            }
+
+            #[test]
+            fn concept_reinsert_counts_only_real_updates() {
+                super::concept_reinsert_counts_only_real_updates($new);
+            }
+
+            #[test]
+            fn concept_reinsert_keeps_the_latest_display_label() {
+                super::concept_reinsert_keeps_the_latest_display_label($new);
+            }
+
+            #[test]
+            fn concepts_with_blank_labels_are_refused() {
+                super::concepts_with_blank_labels_are_refused($new);
+            }
+
+            #[test]
+            fn label_index_normalizes_on_lookup() {
+                super::label_index_normalizes_on_lookup($new);
+            }
+
+            #[test]
+            fn get_concept_by_label_resolves_aliases() {
+                super::get_concept_by_label_resolves_aliases($new);
+            }
+
+            #[test]
+            fn insert_result_tracks_new_facts_vs_episodes() {
+                super::insert_result_tracks_new_facts_vs_episodes($new);
+            }
+
+            #[test]
+            fn episodes_of_a_fact_are_chronological() {
+                super::episodes_of_a_fact_are_chronological($new);
+            }
+
+            #[test]
+            fn negative_facts_are_distinct_and_keep_polarity() {
+                super::negative_facts_are_distinct_and_keep_polarity($new);
+            }
+
+            #[test]
+            fn episodes_keep_validity() {
+                super::episodes_keep_validity($new);
+            }
+
+            #[test]
+            fn episodes_keep_spans() {
+                super::episodes_keep_spans($new);
+            }
+        }
+    };
+}
+
+backend_suite!(memory, MemoryStore::new());
+#[cfg(feature = "sqlite")]
+backend_suite!(sqlite, SqliteStore::open_in_memory().unwrap());
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_store_persists_across_reopen() {
+    let path = std::env::temp_dir().join(format!(
+        "zero_memory_store_test_{}.sqlite",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    {
+        let mut store = SqliteStore::open(&path).unwrap();
+        let mut record = make_record(
+            vec![("agent", 0.9), ("memory", 0.8)],
+            vec![("agent", "needs", "memory", 0.98)],
+            "persisted",
+        );
+        record.concepts[0].aliases = vec!["bot".to_string()];
+        store.insert_record(record).unwrap();
+    }
+
+    let store = SqliteStore::open(&path).unwrap();
+    assert_eq!(store.concept_count().unwrap(), 2);
+    assert_eq!(store.relation_count().unwrap(), 1);
+    let agent = store.get_concept_by_label("Agent").unwrap().unwrap();
+    assert_eq!(agent.aliases, vec!["bot".to_string()]);
+    let (ctx_hash, _) = make_context("persisted");
+    assert_eq!(
+        store.get_context(&ctx_hash).unwrap().unwrap().meta.scope,
+        "persisted"
+    );
+
+    drop(store);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn insert_records_matches_inserting_one_by_one() {
+    let records = || {
+        vec![
+            make_record(
+                vec![("agent", 0.8), ("memory", 0.9)],
+                vec![("agent", "needs", "memory", 0.98)],
+                "session_1",
+            ),
+            make_record(
+                vec![("agent", 0.95), ("memory", 0.9), ("0-lang", 0.7)],
+                vec![
+                    ("agent", "needs", "memory", 0.9),
+                    ("agent", "uses", "0-lang", 0.8),
+                ],
+                "session_2",
+            ),
+            make_record(
+                vec![("agent", 0.8)],
+                vec![("agent", "needs", "memory", 0.98)],
+                "session_1",
+            ),
+        ]
+    };
+
+    let mut looped = MemoryStore::new();
+    let mut expected = InsertResult::default();
+    for record in records() {
+        let r = looped.insert_record(record);
+        expected.new_concepts += r.new_concepts;
+        expected.new_facts += r.new_facts;
+        expected.new_episodes += r.new_episodes;
+        expected.dupes_skipped += r.dupes_skipped;
+        expected.concepts_updated += r.concepts_updated;
+    }
+
+    let mut bulk = MemoryStore::new();
+    let result = bulk.insert_records(records());
+
+    assert_eq!(result.new_concepts, expected.new_concepts);
+    assert_eq!(result.new_facts, expected.new_facts);
+    assert_eq!(result.new_episodes, expected.new_episodes);
+    assert_eq!(result.dupes_skipped, expected.dupes_skipped);
+    assert_eq!(result.concepts_updated, expected.concepts_updated);
+    assert_eq!(bulk.concept_count(), looped.concept_count());
+    assert_eq!(bulk.relation_count(), looped.relation_count());
+    assert_eq!(bulk.context_count(), looped.context_count());
+    assert_eq!(
+        bulk.get_concept_by_label("agent").unwrap().confidence,
+        0.95,
+        "bulk insert merges concepts like insert_record"
+    );
+}
+
+#[test]
+fn stored_episodes_share_interned_predicates() {
+    let mut store = MemoryStore::new();
+    store.insert_record(make_record(
+        vec![("agent", 0.9), ("memory", 0.9), ("0-lang", 0.9)],
+        vec![
+            ("agent", "needs", "memory", 0.98),
+            ("0-lang", "needs", "memory", 0.9),
+        ],
+        "session_1",
+    ));
+
+    let memory = hasher::concept_hash("memory");
+    let rels = store.get_relations(&memory);
+    assert_eq!(rels.len(), 2);
+    assert!(std::sync::Arc::ptr_eq(
+        &rels[0].predicate,
+        &rels[1].predicate
+    ));
+    assert_eq!(store.predicates().len(), 1);
+    assert!(store.predicates().lookup("needs").is_some());
+}
+
+#[test]
+fn label_of_resolves_relation_endpoints() {
+    let mut store = MemoryStore::new();
+    store.insert_record(make_record(
+        vec![("agent", 0.9), ("memory", 0.9)],
+        vec![("agent", "needs", "memory", 0.98)],
+        "session_1",
+    ));
+    store.insert_record(make_record(vec![("agent", 0.95)], vec![], "session_2"));
+
+    let fh = hasher::fact_hash("agent", "needs", "memory");
+    let relation = store.get_relations_by_fact(&fh)[0];
+    assert_eq!(store.label_of(&relation.subject_hash), Some("agent"));
+    assert_eq!(store.label_of(&relation.object_hash), Some("memory"));
+    assert_eq!(
+        store.label_index().labels_for(&relation.subject_hash),
+        vec!["agent"],
+        "re-inserting a concept must not duplicate its label"
+    );
+    assert_eq!(store.label_of(&hasher::concept_hash("nobody")), None);
+}
+
+#[test]
+fn get_relations_orders_facts_by_hash() {
+    let mut store = MemoryStore::new();
+    store.insert_record(make_record(
+        vec![
+            ("memory", 0.9),
+            ("agent", 0.9),
+            ("0-lang", 0.9),
+            ("tools", 0.9),
+        ],
+        vec![
+            ("agent", "needs", "memory", 0.98),
+            ("0-lang", "needs", "memory", 0.9),
+            ("memory", "holds", "tools", 0.8),
+            ("tools", "feed", "memory", 0.7),
+        ],
+        "session_1",
+    ));
+    store.insert_record(make_record(
+        vec![],
+        vec![("agent", "needs", "memory", 0.9)],
+        "session_2",
+    ));
+
+    let rels = store.get_relations(&hasher::concept_hash("memory"));
+    assert_eq!(rels.len(), 5);
+    let facts: Vec<&FactHash> = rels.iter().map(|r| &r.fact_hash).collect();
+    let mut sorted = facts.clone();
+    sorted.sort();
+    assert_eq!(facts, sorted);
+}
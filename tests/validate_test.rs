@@ -0,0 +1,99 @@
+#![cfg(feature = "validate")]
+
+use zero_memory::adapters::openclaw::OpenclawAdapter;
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::{compile_validated, verify_roundtrip, RoundtripIssue, ValidationError};
+use zero_memory::types::*;
+
+fn input() -> CompilerInput {
+    CompilerInput {
+        utterance: Some("the agent needs memory".to_string()),
+        tuples: vec![SemanticTuple::from(("Agent", "needs", "Memory", 0.98))],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: "s1".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+#[test]
+fn compiled_graphs_validate() {
+    let output = compile(&input());
+    output.validate().unwrap();
+    output.validate_with_execution().unwrap();
+}
+
+#[tokio::test]
+async fn graphs_execute_inside_a_current_thread_runtime() {
+    compile(&input()).validate_with_execution().unwrap();
+}
+
+#[test]
+fn compile_validated_returns_the_compiled_output() {
+    let validated = compile_validated(&input()).unwrap();
+    assert_eq!(validated.graph_text, compile(&input()).graph_text);
+}
+
+#[test]
+fn unparsable_graphs_carry_an_excerpt() {
+    let mut output = compile(&input());
+    output.graph_text = output
+        .graph_text
+        .replacen("\"nodes\": [", "\"nodes\": [[", 1);
+
+    match output.validate() {
+        Err(ValidationError::Parse { message, excerpt }) => {
+            assert!(!message.is_empty());
+            assert_eq!(excerpt.chars().count(), 500);
+            assert!(output.graph_text.starts_with(&excerpt));
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn graphs_without_output_fail_execution() {
+    let mut output = compile(&input());
+    output.graph_text = output
+        .graph_text
+        .replace("\"outputs\": [\"output\"]", "\"outputs\": []");
+
+    output.validate().unwrap();
+    assert!(matches!(
+        output.validate_with_execution(),
+        Err(ValidationError::Execute { .. })
+    ));
+}
+
+#[test]
+fn executed_graphs_agree_with_their_records() {
+    let mut input = input();
+    input.tuples.push(SemanticTuple::from((
+        "0-memory",
+        "documented_at",
+        "https://example.com/0-memory",
+        0.8,
+    )));
+    let output = compile(&input);
+    let report = verify_roundtrip(&output, &OpenclawAdapter::new()).unwrap();
+    assert_eq!(report.issues, []);
+    assert_eq!(report.concepts_checked, output.record.concepts.len());
+    assert_eq!(report.relations_checked, 2);
+}
+
+#[tokio::test]
+async fn roundtrip_disagreements_are_reported() {
+    let mut output = compile(&input());
+    output.graph_text = output
+        .graph_text
+        .replace(r#""value": "agent""#, r#""value": "agents""#);
+    let report = verify_roundtrip(&output, &OpenclawAdapter::new()).unwrap();
+    assert!(matches!(
+        report.issues.as_slice(),
+        [RoundtripIssue::MissingConcept { label, .. }] if label == "agent"
+    ));
+}
@@ -0,0 +1,167 @@
+use zero_memory::compiler::emitter::compile;
+use zero_memory::compiler::hasher;
+use zero_memory::store::MemoryStore;
+use zero_memory::types::*;
+
+fn lives_in(city: &str, valid_from: Option<&str>, valid_to: Option<&str>) -> SemanticTuple {
+    SemanticTuple {
+        subject: "user".to_string(),
+        predicate: "lives_in".to_string(),
+        object: city.to_string(),
+        confidence: 0.9,
+        valid_from: valid_from.map(str::to_string),
+        valid_to: valid_to.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn input(scope: &str, tuples: Vec<SemanticTuple>) -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples,
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "user_prompt".to_string(),
+            scope: scope.to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+/// Lived in Paris until 2019, Berlin from 2019 to 2023, Lisbon since.
+fn store() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.insert_record(
+        compile(&input(
+            "s1",
+            vec![
+                lives_in("paris", None, Some("2019-01-01T00:00:00Z")),
+                lives_in(
+                    "berlin",
+                    Some("2019-01-01T00:00:00Z"),
+                    Some("2023-06-01T00:00:00Z"),
+                ),
+                lives_in("lisbon", Some("2023-06-01T00:00:00Z"), None),
+            ],
+        ))
+        .record,
+    );
+    store
+}
+
+fn cities_at(store: &MemoryStore, timestamp: &str) -> Vec<String> {
+    let mut cities: Vec<String> = store
+        .get_relations_valid_at(timestamp)
+        .unwrap()
+        .iter()
+        .map(|r| store.label_of(&r.object_hash).unwrap().to_string())
+        .collect();
+    cities.sort();
+    cities
+}
+
+#[test]
+fn filters_episodes_by_validity_interval() {
+    let store = store();
+    assert_eq!(cities_at(&store, "2010-01-01T00:00:00Z"), ["paris"]);
+    assert_eq!(cities_at(&store, "2020-01-01T00:00:00Z"), ["berlin"]);
+    assert_eq!(cities_at(&store, "2026-01-01T00:00:00Z"), ["lisbon"]);
+}
+
+#[test]
+fn interval_includes_start_and_excludes_end() {
+    let store = store();
+    assert_eq!(cities_at(&store, "2019-01-01T00:00:00Z"), ["berlin"]);
+    assert_eq!(cities_at(&store, "2023-06-01T02:00:00+02:00"), ["lisbon"]);
+}
+
+#[test]
+fn episodes_without_bounds_are_always_valid() {
+    let mut store = store();
+    store.insert_record(compile(&input("s2", vec![lives_in("earth", None, None)])).record);
+    assert_eq!(
+        cities_at(&store, "1900-01-01T00:00:00Z"),
+        ["earth", "paris"]
+    );
+    assert_eq!(
+        cities_at(&store, "2100-01-01T00:00:00Z"),
+        ["earth", "lisbon"]
+    );
+}
+
+#[test]
+fn unparseable_bounds_match_nothing() {
+    let mut store = MemoryStore::new();
+    store.insert_record(compile(&input("s1", vec![lives_in("rome", Some("soon"), None)])).record);
+    assert!(cities_at(&store, "2026-01-01T00:00:00Z").is_empty());
+}
+
+#[test]
+fn rejects_invalid_query_timestamp() {
+    let err = store().get_relations_valid_at("yesterday").unwrap_err();
+    assert_eq!(err.0, "yesterday");
+}
+
+#[test]
+fn validity_is_not_part_of_fact_hash() {
+    let berlin = |from| lives_in("berlin", Some(from), None);
+    let first = compile(&input("s1", vec![berlin("2019-01-01T00:00:00Z")]));
+    let second = compile(&input("s2", vec![berlin("2020-01-01T00:00:00Z")]));
+
+    let fact = hasher::fact_hash("user", "lives_in", "berlin");
+    assert_eq!(first.record.relations[0].fact_hash, fact);
+    assert_eq!(second.record.relations[0].fact_hash, fact);
+
+    let mut store = MemoryStore::new();
+    store.insert_record(first.record);
+    let result = store.insert_record(second.record);
+    assert_eq!(result.new_facts, 0);
+    assert_eq!(result.new_episodes, 1);
+    assert_eq!(cities_at(&store, "2019-06-01T00:00:00Z"), ["berlin"]);
+    assert_eq!(store.get_relations_by_fact(&fact).len(), 2);
+}
+
+#[test]
+fn graph_text_carries_validity() {
+    let output = compile(&input(
+        "s1",
+        vec![lives_in("berlin", Some("2019-01-01T00:00:00Z"), None)],
+    ));
+    assert!(output
+        .graph_text
+        .contains(r#""valid_from":"2019-01-01T00\u003a00\u003a00Z""#));
+    assert!(!output.graph_text.contains("valid_to"));
+}
+
+#[test]
+fn tuples_without_validity_parse() {
+    let tuple: SemanticTuple = serde_json::from_str(
+        r#"{ "subject": "user", "predicate": "lives_in", "object": "berlin", "confidence": 0.9 }"#,
+    )
+    .unwrap();
+    assert_eq!(tuple.valid_from, None);
+    assert_eq!(tuple.valid_to, None);
+}
+
+#[test]
+fn snapshots_keep_validity() {
+    let store = store();
+
+    let mut binary = Vec::new();
+    store.save_binary(&mut binary).unwrap();
+    let mut json = Vec::new();
+    store.save_json(&mut json).unwrap();
+
+    for loaded in [
+        MemoryStore::load_binary(binary.as_slice()).unwrap(),
+        MemoryStore::load_json(json.as_slice()).unwrap(),
+    ] {
+        let fact = hasher::fact_hash("user", "lives_in", "berlin");
+        let berlin = loaded.get_relations_by_fact(&fact)[0];
+        assert_eq!(berlin.valid_from.as_deref(), Some("2019-01-01T00:00:00Z"));
+        assert_eq!(berlin.valid_to.as_deref(), Some("2023-06-01T00:00:00Z"));
+        assert_eq!(cities_at(&loaded, "2020-01-01T00:00:00Z"), ["berlin"]);
+    }
+}
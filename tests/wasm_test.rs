@@ -0,0 +1,71 @@
+#![cfg(feature = "wasm")]
+
+//! Runs natively with `cargo test --features wasm`, and on wasm32 with
+//! `wasm-pack test --node -- --features wasm --test wasm_test`; the golden
+//! hashes must come out the same on both.
+
+use serde_json::Value;
+use zero_memory::compiler::hasher;
+use zero_memory::types::*;
+use zero_memory::wasm::compile_json;
+
+fn trace_input() -> CompilerInput {
+    CompilerInput {
+        utterance: None,
+        tuples: vec![
+            SemanticTuple::from(("Agent", "needs", "Memory", 0.98)),
+            SemanticTuple {
+                subject: "Agent".to_string(),
+                predicate: "likes".to_string(),
+                object: "Noise".to_string(),
+                confidence: 0.4,
+                polarity: Polarity::Negative,
+                valid_from: Some("2026-01-01".to_string()),
+                ..Default::default()
+            },
+        ],
+        context: ContextMeta {
+            event_time: "2026-02-18T00:00:00Z".parse().unwrap(),
+            source: "test".to_string(),
+            scope: "test_scope".to_string(),
+            agent_id: None,
+            session_id: None,
+            metadata: None,
+        },
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn compile_json_matches_the_golden_hashes() {
+    let input = serde_json::to_string(&trace_input()).unwrap();
+    let output: CompilerOutput = serde_json::from_str(&compile_json(&input)).unwrap();
+
+    assert_eq!(
+        hex::encode(hasher::trace_hash(&output.record)),
+        "62770b72fa5f2a3515a11dfb23741c4d67701a17ae504f37ac78e34e932b1fd1"
+    );
+    let agent = output
+        .record
+        .concepts
+        .iter()
+        .find(|concept| &*concept.label == "agent")
+        .unwrap();
+    assert_eq!(
+        agent.hash.to_string(),
+        "d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+    );
+    assert!(output.graph_text.starts_with("Graph"));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn compile_json_reports_errors_as_json() {
+    let error: Value = serde_json::from_str(&compile_json("{not json")).unwrap();
+    assert!(error["error"].is_string(), "{}", error);
+
+    let mut input = serde_json::to_value(trace_input()).unwrap();
+    input["tuples"][1]["confidence"] = Value::from(1.5);
+    let error: Value = serde_json::from_str(&compile_json(&input.to_string())).unwrap();
+    assert_eq!(error["error"], "tuple 1: confidence 1.5 is not in [0, 1]");
+}